@@ -1,7 +1,7 @@
 //! Contains HTTP symbol retrieval specific functionality
 
 use crate::*;
-use reqwest::{Client, Url};
+use reqwest::{Client, StatusCode, Url};
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -220,7 +220,7 @@ async fn fetch_symbol_file(
         .send()
         .await
         .and_then(|res| res.error_for_status())
-        .map_err(|_| SymbolError::NotFound)?;
+        .map_err(fetch_error)?;
 
     // Now try to create the temp cache file (not yet in the cache)
     let final_cache_path = cache.join(sym_lookup.cache_rel);
@@ -255,6 +255,17 @@ async fn fetch_symbol_file(
     Ok(symbol_file)
 }
 
+/// Map a failed symbol server request to a [`SymbolError`].
+///
+/// A 404 just means the server doesn't have the file, anything else is a
+/// failure worth reporting.
+fn fetch_error(e: reqwest::Error) -> SymbolError {
+    match e.status() {
+        Some(StatusCode::NOT_FOUND) => SymbolError::NotFound,
+        status => SymbolError::FetchFailed(status.map(|status| status.as_u16())),
+    }
+}
+
 /// Like fetch_symbol_file but instead of parsing the file live, we just download it opaquely based
 /// on the given Lookup.
 ///
@@ -484,6 +495,7 @@ impl SymbolSupplier for HttpSymbolSupplier {
         trace!("HttpSymbolSupplier search (SimpleSymbolSupplier found nothing)");
 
         // Second: try to directly download sym files
+        let mut fetch_failure = None;
         for url in &self.urls {
             // First, try to get a breakpad .sym file from the symbol server
             let sym = fetch_symbol_file(&self.client, url, module, &self.cache, &self.tmp).await;
@@ -494,6 +506,9 @@ impl SymbolSupplier for HttpSymbolSupplier {
                 }
                 Err(e) => {
                     trace!("HttpSymbolSupplier failed: {}", e);
+                    if let SymbolError::FetchFailed(_) = e {
+                        fetch_failure = Some(e);
+                    }
                 }
             }
         }
@@ -526,8 +541,9 @@ impl SymbolSupplier for HttpSymbolSupplier {
             }
         }
 
-        // If we get this far, we have failed to find anything
-        Err(SymbolError::NotFound)
+        // If we get this far, we have failed to find anything, but a server
+        // failing is more interesting than the others not having the file.
+        Err(fetch_failure.unwrap_or(SymbolError::NotFound))
     }
 
     async fn locate_file(
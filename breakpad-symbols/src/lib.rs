@@ -46,6 +46,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use std::{borrow::Cow, sync::Arc};
 
 pub use minidump_common::{traits::Module, utils::basename};
@@ -69,7 +70,12 @@ pub mod fuzzing_private_exports {
 }
 
 /// Statistics on the symbols of a module.
+///
+/// All fields are `pub`, but the type is `non_exhaustive`, so that more
+/// statistics can be added without breaking changes. Build one outside
+/// this crate by starting from [`Default::default`] and setting fields.
 #[derive(Default, Debug, Clone)]
+#[non_exhaustive]
 pub struct SymbolStats {
     /// If the module's symbols were downloaded, this is the url used.
     pub symbol_url: Option<String>,
@@ -77,6 +83,51 @@ pub struct SymbolStats {
     pub loaded_symbols: bool,
     /// If we tried to parse the symbols, but failed.
     pub corrupt_symbols: bool,
+    /// How long looking up the symbols took, including downloading and
+    /// parsing them.
+    pub lookup_time: Option<Duration>,
+    /// Where the symbols were loaded from, if they were.
+    pub origin: Option<SymbolOrigin>,
+    /// Whether the symbols were loaded from a local cache of a symbol server.
+    pub cache_hit: bool,
+    /// How many bytes of symbols were downloaded, if they were downloaded
+    /// (rather than loaded from a local file or cache).
+    pub bytes_fetched: Option<u64>,
+    /// If the symbols were found but couldn't be parsed, why not.
+    pub parse_error: Option<String>,
+    /// If a symbol server failed to answer the request for the symbols
+    /// (rather than not having them).
+    pub fetch_failed: bool,
+    /// The HTTP status of the failed request, if it got a response.
+    pub fetch_status: Option<u16>,
+}
+
+/// Where the symbols of a module were loaded from.
+///
+/// See [`SymbolStats::origin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolOrigin {
+    /// A local symbol file.
+    LocalFile(PathBuf),
+    /// A symbol file previously downloaded into a local cache (which
+    /// [`SymbolStats::symbol_url`] is the url of).
+    Cache(PathBuf),
+    /// A symbol server.
+    Url(String),
+}
+
+impl SymbolOrigin {
+    /// `symbol_file`'s origin, if it knows where it came from.
+    fn of(symbol_file: &SymbolFile) -> Option<SymbolOrigin> {
+        match (&symbol_file.path, &symbol_file.url) {
+            // Symbol files only know their url when loaded from disk if
+            // it was recorded in the file when it was cached.
+            (Some(path), Some(_)) => Some(SymbolOrigin::Cache(path.clone())),
+            (Some(path), None) => Some(SymbolOrigin::LocalFile(path.clone())),
+            (None, Some(url)) => Some(SymbolOrigin::Url(url.clone())),
+            (None, None) => None,
+        }
+    }
 }
 
 /// Statistics on pending symbols.
@@ -148,6 +199,61 @@ impl Module for SimpleModule {
     }
 }
 
+/// The name and identifier a symbol store files a module's symbols under.
+///
+/// By default this is just the module's debug file and debug identifier,
+/// but [`Symbolizer::set_symbol_key_mapper`] can change what modules are
+/// looked up as.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SymbolKey {
+    /// The debug file name (like `xul.pdb` or `libxul.so`).
+    pub debug_file: String,
+    /// The debug identifier.
+    pub debug_id: DebugId,
+}
+
+impl SymbolKey {
+    /// The key for `module`'s symbols, if it has a debug file and identifier.
+    pub fn from_module(module: &(dyn Module + Sync)) -> Option<SymbolKey> {
+        Some(SymbolKey {
+            debug_file: module.debug_file()?.into_owned(),
+            debug_id: module.debug_identifier()?,
+        })
+    }
+}
+
+type SymbolKeyMapper = Box<dyn Fn(&(dyn Module + Sync)) -> Option<SymbolKey> + Send + Sync>;
+
+/// A module whose debug file and identifier have been replaced with a [`SymbolKey`].
+struct RemappedModule<'a> {
+    module: &'a (dyn Module + Sync),
+    key: SymbolKey,
+}
+
+impl Module for RemappedModule<'_> {
+    fn base_address(&self) -> u64 {
+        self.module.base_address()
+    }
+    fn size(&self) -> u64 {
+        self.module.size()
+    }
+    fn code_file(&self) -> Cow<'_, str> {
+        self.module.code_file()
+    }
+    fn code_identifier(&self) -> Option<CodeId> {
+        self.module.code_identifier()
+    }
+    fn debug_file(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(&self.key.debug_file))
+    }
+    fn debug_identifier(&self) -> Option<DebugId> {
+        Some(self.key.debug_id)
+    }
+    fn version(&self) -> Option<Cow<'_, str>> {
+        self.module.version()
+    }
+}
+
 /// Like `PathBuf::file_name`, but try to work on Windows or POSIX-style paths.
 fn leafname(path: &str) -> &str {
     path.rsplit(|c| c == '/' || c == '\\')
@@ -191,11 +297,16 @@ pub struct FileLookup {
 /// You can use [this script][packagesymbols] to run dump_syms and put the
 /// resulting symbol files in the proper directory structure.
 ///
+/// If the module has no debug filename or identifier (for instance because
+/// it's an unloaded module), [`code_info_breakpad_sym_lookup`] is used instead.
+///
 /// [module_line]: https://chromium.googlesource.com/breakpad/breakpad/+/master/docs/symbol_files.md#MODULE-records
 /// [packagesymbols]: https://gist.github.com/luser/2ad32d290f224782fcfc#file-packagesymbols-py
 pub fn breakpad_sym_lookup(module: &(dyn Module + Sync)) -> Option<FileLookup> {
-    let debug_file = module.debug_file()?;
-    let debug_id = module.debug_identifier()?;
+    let (debug_file, debug_id) = match (module.debug_file(), module.debug_identifier()) {
+        (Some(debug_file), Some(debug_id)) => (debug_file, debug_id),
+        _ => return code_info_breakpad_sym_lookup(module),
+    };
 
     let leaf = leafname(&debug_file);
     let filename = replace_or_add_extension(leaf, "pdb", "sym");
@@ -206,6 +317,29 @@ pub fn breakpad_sym_lookup(module: &(dyn Module + Sync)) -> Option<FileLookup> {
     })
 }
 
+/// Get a relative symbol path at which to locate symbols for `module` by its
+/// code file and identifier.
+///
+/// This is `<code filename>/<code identifier>/<code filename>.sym`, with the
+/// code filename's extension (if any) replaced by *sym*, which is how symbol
+/// servers like Mozilla's can also serve the symbols of Windows modules.
+pub fn code_info_breakpad_sym_lookup(module: &(dyn Module + Sync)) -> Option<FileLookup> {
+    let code_file = module.code_file();
+    let code_id = module.code_identifier()?;
+
+    let leaf = leafname(&code_file);
+    if leaf.is_empty() {
+        return None;
+    }
+    let stem = leaf.rsplit_once('.').map_or(leaf, |(stem, _)| stem);
+    let filename = format!("{stem}.sym");
+    let rel_path = [leaf, code_id.as_ref(), &filename[..]].join("/");
+    Some(FileLookup {
+        cache_rel: rel_path.clone(),
+        server_rel: rel_path,
+    })
+}
+
 /// Returns a lookup for this module's extra debuginfo (pdb)
 pub fn extra_debuginfo_lookup(module: &(dyn Module + Sync)) -> Option<FileLookup> {
     let debug_file = module.debug_file()?;
@@ -276,6 +410,7 @@ pub fn lookup(module: &(dyn Module + Sync), file_kind: FileKind) -> Option<FileL
 /// from the network, aggressive caching is pretty important. The current
 /// approach is a nice balance of simple and effective.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum SymbolError {
     /// Symbol file could not be found.
     ///
@@ -298,6 +433,12 @@ pub enum SymbolError {
     /// you find yourself here.
     #[error("parse error: {0} at line {1}")]
     ParseError(&'static str, u64),
+    /// A symbol server couldn't be reached, or answered with an error other
+    /// than "not found".
+    ///
+    /// Holds the HTTP status of the response, if there was one.
+    #[error("symbol server request failed (status: {0:?})")]
+    FetchFailed(Option<u16>),
 }
 
 #[derive(Clone, Debug, thiserror::Error)]
@@ -330,6 +471,7 @@ impl PartialEq for SymbolError {
             (SymbolError::NotFound, SymbolError::NotFound)
                 | (SymbolError::LoadError(_), SymbolError::LoadError(_))
                 | (SymbolError::ParseError(..), SymbolError::ParseError(..))
+                | (SymbolError::FetchFailed(_), SymbolError::FetchFailed(_))
         )
     }
 }
@@ -476,7 +618,8 @@ pub trait FrameWalker {
     fn has_grand_callee(&self) -> bool;
     /// Get the number of bytes the callee's callee's parameters take up
     /// on the stack (or 0 if unknown/invalid). This is needed for
-    /// STACK WIN unwinding.
+    /// STACK WIN unwinding, and is available to CFI evaluation on every
+    /// architecture.
     fn get_grand_callee_parameter_size(&self) -> u32;
     /// Get a register-sized value stored at this address.
     fn get_register_at_address(&self, address: u64) -> Option<u64>;
@@ -600,6 +743,10 @@ pub struct Symbolizer {
     // way messier but not impossible.
     symbols: Mutex<HashMap<ModuleKey, CachedOperation<SymbolFile, SymbolError>>>,
     pending_stats: Mutex<PendingSymbolStats>,
+    /// How long locating each module's symbols took.
+    lookup_times: Mutex<HashMap<ModuleKey, Duration>>,
+    /// Remaps modules before their symbols are looked up.
+    symbol_key_mapper: Option<SymbolKeyMapper>,
 }
 
 impl Symbolizer {
@@ -609,9 +756,35 @@ impl Symbolizer {
             supplier: Box::new(supplier),
             symbols: Mutex::new(HashMap::new()),
             pending_stats: Mutex::default(),
+            lookup_times: Mutex::default(),
+            symbol_key_mapper: None,
         }
     }
 
+    /// Look up modules' symbols under the key `mapper` returns for them.
+    ///
+    /// This is for when a symbol store's names don't match the modules in
+    /// minidumps, like when modules are renamed between being built and
+    /// having their symbols uploaded. `mapper` is consulted before every
+    /// lookup (including [`Symbolizer::get_file_path`]), and returning `None`
+    /// looks the module up as usual (see [`SymbolKey::from_module`]).
+    /// Only the debug file and identifier are replaced: modules keep their
+    /// own code file and identifier, so modules that map to the same key
+    /// still have their symbols loaded (and counted in [`Symbolizer::stats`])
+    /// separately.
+    pub fn set_symbol_key_mapper<F>(&mut self, mapper: F)
+    where
+        F: Fn(&(dyn Module + Sync)) -> Option<SymbolKey> + Send + Sync + 'static,
+    {
+        self.symbol_key_mapper = Some(Box::new(mapper));
+    }
+
+    /// `module`, with the debug file and identifier the symbol key mapper picked for it.
+    fn remap_module<'a>(&self, module: &'a (dyn Module + Sync)) -> Option<RemappedModule<'a>> {
+        let key = self.symbol_key_mapper.as_ref()?(module)?;
+        Some(RemappedModule { module, key })
+    }
+
     /// Helper method for non-minidump-using callers.
     ///
     /// Pass `debug_file` and `debug_id` describing a specific module,
@@ -687,18 +860,27 @@ impl Symbolizer {
     ///
     /// Keys are the file name of the module (code_file's file name).
     pub fn stats(&self) -> HashMap<String, SymbolStats> {
+        let lookup_times = self.lookup_times.lock().unwrap();
         self.symbols
             .lock()
             .unwrap()
             .iter()
             .map(|(k, res)| {
                 let res = res.get().expect("Had uninitialized SymbolFile entry?");
-                let mut stats = SymbolStats::default();
+                let mut stats = SymbolStats {
+                    lookup_time: lookup_times.get(k).copied(),
+                    ..SymbolStats::default()
+                };
                 match res {
                     Ok(sym) => {
                         stats.symbol_url = sym.url.clone();
                         stats.loaded_symbols = true;
                         stats.corrupt_symbols = false;
+                        stats.origin = SymbolOrigin::of(sym);
+                        stats.cache_hit = matches!(stats.origin, Some(SymbolOrigin::Cache(_)));
+                        if let Some(SymbolOrigin::Url(_)) = stats.origin {
+                            stats.bytes_fetched = Some(sym.size);
+                        }
                     }
                     Err(SymbolError::NotFound) => {
                         stats.loaded_symbols = false;
@@ -709,9 +891,15 @@ impl Symbolizer {
                     Err(SymbolError::LoadError(_)) => {
                         stats.loaded_symbols = false;
                     }
-                    Err(SymbolError::ParseError(..)) => {
+                    Err(SymbolError::FetchFailed(status)) => {
+                        stats.loaded_symbols = false;
+                        stats.fetch_failed = true;
+                        stats.fetch_status = *status;
+                    }
+                    Err(err @ SymbolError::ParseError(..)) => {
                         stats.loaded_symbols = true;
                         stats.corrupt_symbols = true;
+                        stats.parse_error = Some(err.to_string());
                     }
                 }
                 (leafname(&k.0).to_string(), stats)
@@ -750,14 +938,27 @@ impl Symbolizer {
         &self,
         module: &(dyn Module + Sync),
     ) -> CachedOperation<SymbolFile, SymbolError> {
+        let remapped = self.remap_module(module);
+        let module = match &remapped {
+            Some(remapped) => remapped,
+            None => module,
+        };
         // This clones an Arc<Once> that we will use to only do this operation once
         let k = module_key(module);
-        let symbol_once = self.symbols.lock().unwrap().entry(k).or_default().clone();
+        let symbol_once = self
+            .symbols
+            .lock()
+            .unwrap()
+            .entry(k.clone())
+            .or_default()
+            .clone();
         symbol_once
             .get_or_init(|| async {
                 trace!("locating symbols for module {}", module.code_file());
                 self.pending_stats.lock().unwrap().symbols_requested += 1;
+                let start = Instant::now();
                 let result = self.supplier.locate_symbols(module).await;
+                self.lookup_times.lock().unwrap().insert(k, start.elapsed());
                 self.pending_stats.lock().unwrap().symbols_processed += 1;
                 result
             })
@@ -773,7 +974,10 @@ impl Symbolizer {
         module: &(dyn Module + Sync),
         file_kind: FileKind,
     ) -> Result<PathBuf, FileError> {
-        self.supplier.locate_file(module, file_kind).await
+        match self.remap_module(module) {
+            Some(remapped) => self.supplier.locate_file(&remapped, file_kind).await,
+            None => self.supplier.locate_file(module, file_kind).await,
+        }
     }
 }
 
@@ -856,6 +1060,23 @@ mod test {
             ..SimpleModule::default()
         };
         assert!(breakpad_sym_lookup(&bad3).is_none());
+
+        // Without debug info, the code file and identifier are used.
+        let code_only = SimpleModule {
+            code_file: Some("C:\\Windows\\System32\\foo.dll".to_string()),
+            code_identifier: Some(CodeId::new("5D236E0F2000".to_string())),
+            ..SimpleModule::default()
+        };
+        assert_eq!(
+            &breakpad_sym_lookup(&code_only).unwrap().cache_rel,
+            "foo.dll/5d236e0f2000/foo.sym"
+        );
+
+        let bad4 = SimpleModule {
+            code_file: Some("foo.dll".to_string()),
+            ..SimpleModule::default()
+        };
+        assert!(breakpad_sym_lookup(&bad4).is_none());
     }
 
     #[tokio::test]
@@ -997,6 +1218,7 @@ FUNC 1000 30 10 some func
         symbolizer.fill_symbol(&m1, &mut f1).await.unwrap();
         assert_eq!(f1.function.unwrap(), "some func");
         assert_eq!(f1.function_base.unwrap(), 0x1000);
+        assert_eq!(f1.parameter_size.unwrap(), 0x10);
         assert_eq!(f1.source_file.unwrap(), "foo.c");
         assert_eq!(f1.source_line.unwrap(), 100);
         assert_eq!(f1.source_line_base.unwrap(), 0x1000);
@@ -1038,4 +1260,108 @@ FUNC 1000 30 10 another func
             .await
             .is_none());
     }
+
+    #[tokio::test]
+    async fn test_symbol_stats() {
+        let t = tempfile::tempdir().unwrap();
+        let path = t.path();
+        let module = |name: &str, id: &str| SimpleModule {
+            code_file: Some(format!("{}.dll", name)),
+            ..SimpleModule::new(&format!("{}.pdb", name), DebugId::from_str(id).unwrap())
+        };
+        let local = module("local", "abcd1234-abcd-1234-abcd-abcd12345678-a");
+        let cached = module("cached", "ffff0000-0000-0000-0000-abcd12345678-a");
+        let corrupt = module("corrupt", "abcd0000-0000-0000-0000-abcd12345678-a");
+        let missing = module("missing", "00001234-0000-0000-0000-abcd12345678-a");
+        let local_path = path.join("local.pdb/ABCD1234ABCD1234ABCDABCD12345678a/local.sym");
+        let cached_path = path.join("cached.pdb/FFFF0000000000000000ABCD12345678a/cached.sym");
+        write_good_symbol_file(&local_path);
+        // The http supplier records where it downloaded symbols from.
+        write_symbol_file(
+            &cached_path,
+            b"MODULE Linux x86 ffff0000000000000000ABCD12345678a cached
+INFO URL https://symbols.example.com/cached.pdb/FFFF0000000000000000ABCD12345678a/cached.sym
+",
+        );
+        write_bad_symbol_file(
+            &path.join("corrupt.pdb/ABCD0000000000000000ABCD12345678a/corrupt.sym"),
+        );
+
+        let supplier = SimpleSymbolSupplier::new(vec![PathBuf::from(path)]);
+        let symbolizer = Symbolizer::new(supplier);
+        for module in [&local, &cached, &corrupt, &missing] {
+            let mut frame = SimpleFrame::with_instruction(0x1010);
+            let _ = symbolizer.fill_symbol(module, &mut frame).await;
+        }
+        let stats = symbolizer.stats();
+
+        let local = &stats["local.dll"];
+        assert!(local.loaded_symbols);
+        assert_eq!(local.origin, Some(SymbolOrigin::LocalFile(local_path)));
+        assert!(!local.cache_hit);
+        assert_eq!(local.bytes_fetched, None);
+        assert!(local.lookup_time.is_some());
+
+        let cached = &stats["cached.dll"];
+        assert_eq!(cached.origin, Some(SymbolOrigin::Cache(cached_path)));
+        assert!(cached.cache_hit);
+        assert!(cached.symbol_url.is_some());
+
+        let corrupt = &stats["corrupt.dll"];
+        assert!(corrupt.corrupt_symbols);
+        assert_eq!(corrupt.origin, None);
+        assert!(corrupt
+            .parse_error
+            .as_ref()
+            .unwrap()
+            .starts_with("parse error"));
+
+        let missing = &stats["missing.dll"];
+        assert!(!missing.loaded_symbols);
+        assert_eq!(missing.parse_error, None);
+        assert!(missing.lookup_time.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_symbol_key_mapper() {
+        let t = tempfile::tempdir().unwrap();
+        let path = t.path();
+        let debug_id = DebugId::from_str("abcd1234-abcd-1234-abcd-abcd12345678-a").unwrap();
+        write_symbol_file(
+            &path.join("foo.pdb/ABCD1234ABCD1234ABCDABCD12345678a/foo.sym"),
+            b"MODULE Linux x86 ABCD1234ABCD1234ABCDABCD12345678a foo
+FILE 1 foo.c
+FUNC 1000 30 10 some func
+1000 30 100 1
+",
+        );
+
+        // The module was renamed after its symbols were uploaded.
+        let m = SimpleModule::new("foo-renamed.pdb", debug_id);
+        let supplier = SimpleSymbolSupplier::new(vec![PathBuf::from(path)]);
+        let mut symbolizer = Symbolizer::new(supplier);
+        symbolizer.set_symbol_key_mapper(|module| {
+            let key = SymbolKey::from_module(module)?;
+            Some(SymbolKey {
+                debug_file: key.debug_file.replace("-renamed", ""),
+                ..key
+            })
+        });
+        let mut f = SimpleFrame::with_instruction(0x1010);
+        symbolizer.fill_symbol(&m, &mut f).await.unwrap();
+        assert_eq!(f.function.unwrap(), "some func");
+        assert_eq!(
+            symbolizer
+                .get_file_path(&m, FileKind::BreakpadSym)
+                .await
+                .unwrap(),
+            path.join("foo.pdb/ABCD1234ABCD1234ABCDABCD12345678a/foo.sym")
+        );
+
+        // Without the mapper it isn't found.
+        let supplier = SimpleSymbolSupplier::new(vec![PathBuf::from(path)]);
+        let symbolizer = Symbolizer::new(supplier);
+        let mut f = SimpleFrame::with_instruction(0x1010);
+        assert!(symbolizer.fill_symbol(&m, &mut f).await.is_err());
+    }
 }
@@ -135,7 +135,9 @@ impl SymbolFile {
                 } else if fully_consumed {
                     // Success! The last iteration cleared the buffer and we still got
                     // no more bytes, so that's a proper EOF with a complete parse!
-                    return Ok(parser.finish());
+                    let mut symbol_file = parser.finish();
+                    symbol_file.size = total_consumed;
+                    return Ok(symbol_file);
                 } else if !tried_to_grow {
                     // We still have some stuff in the buffer, assume this is because
                     // the buffer is full, and try to make it BIGGER and ask for more again.
@@ -267,7 +269,9 @@ impl SymbolFile {
                 } else if fully_consumed {
                     // Success! The last iteration cleared the buffer and we still got
                     // no more bytes, so that's a proper EOF with a complete parse!
-                    return Ok(parser.finish());
+                    let mut symbol_file = parser.finish();
+                    symbol_file.size = total_consumed;
+                    return Ok(symbol_file);
                 } else if !tried_to_grow {
                     // We still have some stuff in the buffer, assume this is because
                     // the buffer is full, and try to make it BIGGER and ask for more again.
@@ -330,7 +334,9 @@ impl SymbolFile {
     // Parse a SymbolFile from a file.
     pub fn from_file(path: &Path) -> Result<SymbolFile, SymbolError> {
         let file = File::open(path)?;
-        Self::parse(file, |_| ())
+        let mut symbol_file = Self::parse(file, |_| ())?;
+        symbol_file.path = Some(path.to_owned());
+        Ok(symbol_file)
     }
 
     /// Fill in as much source information for `frame` as possible.
@@ -341,14 +347,15 @@ impl SymbolFile {
         }
         let addr = frame.get_instruction() - module.base_address();
         if let Some(func) = self.functions.get(addr) {
-            // TODO: although FUNC records have a parameter size, it appears that
-            // they aren't to be trusted? The STACK WIN records are more reliable
-            // when available. This is important precisely because these values
-            // are used to unwind subsequent STACK WIN frames (because certain
-            // calling conventions have the caller push the callee's arguments,
-            // which affects the the stack's size!).
+            // FUNC records have a parameter size, but the STACK WIN records are
+            // more reliable when available. This is important precisely because
+            // these values are used to unwind subsequent STACK WIN frames
+            // (because certain calling conventions have the caller push the
+            // callee's arguments, which affects the the stack's size!).
             //
-            // Need to spend more time thinking about if this is the right approach
+            // Without STACK WIN records (e.g. on x64, which only has CFI) the
+            // FUNC record's size is all there is, and it's still worth passing
+            // along for CFI evaluation and argument recovery.
             let parameter_size = if let Some(info) = self.win_stack_framedata_info.get(addr) {
                 info.parameter_size
             } else if let Some(info) = self.win_stack_fpo_info.get(addr) {
@@ -694,6 +694,8 @@ impl SymbolParser {
             win_stack_fpo_info: into_rangemap_safe(self.win_stack_fpo_info),
             // Will get filled in by the caller
             url: self.url,
+            path: None,
+            size: 0,
             ambiguities_repaired: 0,
             ambiguities_discarded: 0,
             corruptions_discarded: 0,
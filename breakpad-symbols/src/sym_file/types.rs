@@ -3,6 +3,7 @@
 
 use range_map::{Range, RangeMap};
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// A publicly visible linker symbol.
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
@@ -245,6 +246,7 @@ impl StackInfoWin {
 
 /// A parsed .sym file containing debug symbols.
 #[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct SymbolFile {
     /// The set of source files involved in compilation.
     pub files: HashMap<u32, String>,
@@ -266,6 +268,10 @@ pub struct SymbolFile {
     // log an event.
     /// If the symbol file was loaded from a URL, this is the url
     pub url: Option<String>,
+    /// If the symbol file was loaded from a local file, this is its path.
+    pub path: Option<PathBuf>,
+    /// The size of the symbol file in bytes.
+    pub size: u64,
     /// The number of times the parser found that the symbol file was
     /// strictly ambiguous but simple heuristics repaired it. (e.g.
     /// two STACK WIN entries overlapped, but the second was a suffix of
@@ -0,0 +1,138 @@
+//! Identifying x86 cpus from their `cpuid` data.
+//!
+//! Minidumps record the cpu's vendor string and the family, model, and
+//! stepping `cpuid` reports, but what people usually want to know is which
+//! microarchitecture that is. That takes a table of known family and model
+//! pairs, which is what [`x86_microarchitecture`] has.
+
+/// The vendor string of Intel cpus.
+pub const VENDOR_INTEL: &str = "GenuineIntel";
+/// The vendor string of AMD cpus.
+pub const VENDOR_AMD: &str = "AuthenticAMD";
+/// The vendor string of Hygon cpus, which are licensed AMD Zen designs.
+pub const VENDOR_HYGON: &str = "HygonGenuine";
+
+/// The family, model, and stepping of an x86 cpu, as `cpuid` leaf 1 reports
+/// them.
+///
+/// The family and model are the "display" values, with the extended family
+/// and model folded in, which are the values `/proc/cpuinfo` and vendor
+/// documentation use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct X86CpuSignature {
+    pub family: u32,
+    pub model: u32,
+    pub stepping: u32,
+}
+
+impl X86CpuSignature {
+    /// Decode the signature from `cpuid` leaf 1's `eax`, which minidumps
+    /// store as the `version_information` of their `X86CpuInfo`.
+    pub fn from_version_information(eax: u32) -> X86CpuSignature {
+        let base_family = (eax >> 8) & 0xf;
+        let base_model = (eax >> 4) & 0xf;
+        let family = if base_family == 0xf {
+            base_family + ((eax >> 20) & 0xff)
+        } else {
+            base_family
+        };
+        let model = if base_family == 0x6 || base_family == 0xf {
+            (((eax >> 16) & 0xf) << 4) | base_model
+        } else {
+            base_model
+        };
+        X86CpuSignature {
+            family,
+            model,
+            stepping: eax & 0xf,
+        }
+    }
+}
+
+/// The name of the microarchitecture of an x86 cpu, like "Tiger Lake" or "Zen 3".
+///
+/// `vendor` is the cpu's vendor string, like [`VENDOR_INTEL`]. If it isn't
+/// known, the vendor is guessed from the family, where that's unambiguous
+/// for 64-bit cpus: family 6 is Intel, and families 0x10 and up are AMD.
+///
+/// Returns `None` for unknown vendors and models.
+pub fn x86_microarchitecture(
+    vendor: Option<&str>,
+    signature: X86CpuSignature,
+) -> Option<&'static str> {
+    let vendor = match vendor {
+        Some(vendor) => vendor,
+        None if signature.family == 0x6 => VENDOR_INTEL,
+        None if signature.family >= 0x10 => VENDOR_AMD,
+        None => return None,
+    };
+    match vendor {
+        VENDOR_INTEL => intel_microarchitecture(signature.family, signature.model),
+        VENDOR_AMD => amd_microarchitecture(signature.family, signature.model),
+        VENDOR_HYGON if signature.family == 0x18 => Some("Zen"),
+        _ => None,
+    }
+}
+
+fn intel_microarchitecture(family: u32, model: u32) -> Option<&'static str> {
+    Some(match (family, model) {
+        (0x5, _) => "P5",
+        (0x6, 0x01 | 0x03 | 0x05 | 0x06 | 0x07 | 0x08 | 0x0a | 0x0b) => "P6",
+        (0x6, 0x09 | 0x0d) => "Pentium M",
+        (0x6, 0x0e) => "Yonah",
+        (0x6, 0x0f | 0x16) => "Merom",
+        (0x6, 0x17 | 0x1d) => "Penryn",
+        (0x6, 0x1a | 0x1e | 0x1f | 0x2e) => "Nehalem",
+        (0x6, 0x25 | 0x2c | 0x2f) => "Westmere",
+        (0x6, 0x2a | 0x2d) => "Sandy Bridge",
+        (0x6, 0x3a | 0x3e) => "Ivy Bridge",
+        (0x6, 0x3c | 0x3f | 0x45 | 0x46) => "Haswell",
+        (0x6, 0x3d | 0x47 | 0x4f | 0x56) => "Broadwell",
+        (0x6, 0x4e | 0x5e | 0x55) => "Skylake",
+        (0x6, 0x8e | 0x9e) => "Kaby Lake",
+        (0x6, 0xa5 | 0xa6) => "Comet Lake",
+        (0x6, 0x66) => "Cannon Lake",
+        (0x6, 0x6a | 0x6c | 0x7d | 0x7e) => "Ice Lake",
+        (0x6, 0x8c | 0x8d) => "Tiger Lake",
+        (0x6, 0xa7) => "Rocket Lake",
+        (0x6, 0x97 | 0x9a) => "Alder Lake",
+        (0x6, 0xb7 | 0xba | 0xbf) => "Raptor Lake",
+        (0x6, 0x8f) => "Sapphire Rapids",
+        (0x6, 0xcf) => "Emerald Rapids",
+        (0x6, 0xaa | 0xac) => "Meteor Lake",
+        (0x6, 0xbd) => "Lunar Lake",
+        (0x6, 0xc5 | 0xc6) => "Arrow Lake",
+        (0x6, 0x1c | 0x26 | 0x27 | 0x35 | 0x36) => "Bonnell",
+        (0x6, 0x37 | 0x4a | 0x4d | 0x5a | 0x5d) => "Silvermont",
+        (0x6, 0x4c) => "Airmont",
+        (0x6, 0x5c | 0x5f) => "Goldmont",
+        (0x6, 0x7a) => "Goldmont Plus",
+        (0x6, 0x86 | 0x96 | 0x9c) => "Tremont",
+        (0x6, 0x57) => "Knights Landing",
+        (0x6, 0x85) => "Knights Mill",
+        (0xf, _) => "NetBurst",
+        _ => return None,
+    })
+}
+
+fn amd_microarchitecture(family: u32, model: u32) -> Option<&'static str> {
+    Some(match (family, model) {
+        (0x6, _) => "K7",
+        (0xf, _) | (0x11, _) => "K8",
+        (0x10, _) | (0x12, _) => "K10",
+        (0x14, _) => "Bobcat",
+        (0x15, 0x00..=0x0f) => "Bulldozer",
+        (0x15, 0x10..=0x1f) => "Piledriver",
+        (0x15, 0x30..=0x3f) => "Steamroller",
+        (0x15, 0x60..=0x7f) => "Excavator",
+        (0x16, 0x00..=0x0f) => "Jaguar",
+        (0x16, 0x30..=0x3f) => "Puma",
+        (0x17, 0x08 | 0x18) => "Zen+",
+        (0x17, 0x00..=0x2f) => "Zen",
+        (0x17, 0x30..=0xaf) => "Zen 2",
+        (0x19, 0x10..=0x1f | 0x60..=0x7f | 0xa0..=0xaf) => "Zen 4",
+        (0x19, 0x00..=0x5f) => "Zen 3",
+        (0x1a, _) => "Zen 5",
+        _ => return None,
+    })
+}
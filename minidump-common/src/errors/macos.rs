@@ -3,6 +3,7 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use enum_primitive_derive::Primitive;
+use num_traits::FromPrimitive;
 
 /// Values for
 /// [`MINIDUMP_EXCEPTION::exception_code`](crate::format::MINIDUMP_EXCEPTION::exception_code)
@@ -415,3 +416,155 @@ pub enum ExceptionCodeMacGuardVNFlavor {
 pub enum ExceptionCodeMacGuardVirtMemoryFlavor {
     GUARD_EXC_DEALLOC_GAP = 0x00000001,
 }
+
+/// The details encoded in the code and subcode of a Mac/iOS resource exception
+///
+/// See the [osfmk/kern/exc_resource.h][header] header in Apple's kernel sources
+///
+/// [header]: https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ExceptionCodeMacResourceInfo {
+    Cpu {
+        flavor: ExceptionCodeMacResourceCpuFlavor,
+        interval_seconds: u64,
+        cpu_limit_percent: u64,
+        cpu_consumed_percent: u64,
+    },
+    Wakeups {
+        flavor: ExceptionCodeMacResourceWakeupsFlavor,
+        interval_seconds: u64,
+        wakeups_permitted: u64,
+        wakeups_observed: u64,
+    },
+    Memory {
+        flavor: ExceptionCodeMacResourceMemoryFlavor,
+        high_watermark_limit_mib: u64,
+    },
+    Io {
+        flavor: ExceptionCodeMacResourceIOFlavor,
+        interval_seconds: u64,
+        io_limit_percent: u64,
+        io_observed_percent: u64,
+    },
+    Threads {
+        flavor: ExceptionCodeMacResourceThreadsFlavor,
+        high_watermark_limit: u64,
+    },
+}
+
+impl ExceptionCodeMacResourceInfo {
+    /// Decode the code and subcode of a resource exception of type `ty`.
+    ///
+    /// Returns `None` if the flavor isn't one we know the layout of.
+    pub fn from_codes(ty: ExceptionCodeMacResourceType, code: u64, subcode: u64) -> Option<Self> {
+        let flavor = (code >> 58) & 0x7;
+        let info = match ty {
+            // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L71-L99
+            ExceptionCodeMacResourceType::RESOURCE_TYPE_CPU => Self::Cpu {
+                flavor: ExceptionCodeMacResourceCpuFlavor::from_u64(flavor)?,
+                interval_seconds: (code >> 7) & 0x1ffffff,
+                cpu_limit_percent: code & 0x7f,
+                cpu_consumed_percent: subcode & 0x7f,
+            },
+            // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L105-L134
+            ExceptionCodeMacResourceType::RESOURCE_TYPE_WAKEUPS => Self::Wakeups {
+                flavor: ExceptionCodeMacResourceWakeupsFlavor::from_u64(flavor)?,
+                interval_seconds: (code >> 20) & 0xfffff,
+                wakeups_permitted: code & 0xfff,
+                wakeups_observed: subcode & 0xfff,
+            },
+            // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L139-L162
+            ExceptionCodeMacResourceType::RESOURCE_TYPE_MEMORY => Self::Memory {
+                flavor: ExceptionCodeMacResourceMemoryFlavor::from_u64(flavor)?,
+                high_watermark_limit_mib: code & 0x1fff,
+            },
+            // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L168-L196
+            ExceptionCodeMacResourceType::RESOURCE_TYPE_IO => Self::Io {
+                flavor: ExceptionCodeMacResourceIOFlavor::from_u64(flavor)?,
+                interval_seconds: (code >> 15) & 0x1ffff,
+                io_limit_percent: code & 0x7fff,
+                io_observed_percent: subcode & 0x7fff,
+            },
+            // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L199-L207
+            ExceptionCodeMacResourceType::RESOURCE_TYPE_THREADS => Self::Threads {
+                flavor: ExceptionCodeMacResourceThreadsFlavor::from_u64(flavor)?,
+                high_watermark_limit: code & 0x7fff,
+            },
+        };
+        Some(info)
+    }
+}
+
+/// The details encoded in the code and subcode of a Mac/iOS guard exception
+///
+/// The code holds the guard type in bits 61-63, the flavor in bits 32-60 and
+/// the guarded target (a port name, file descriptor, ...) in bits 0-31.
+///
+/// See the [osfmk/kern/exc_guard.h][header] header in Apple's kernel sources
+///
+/// [header]: https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_guard.h
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ExceptionCodeMacGuardInfo {
+    /// `GUARD_TYPE_NONE`, which records nothing about the guard.
+    Unknown,
+    MachPort {
+        flavor: ExceptionCodeMacGuardMachPortFlavor,
+        port_name: u32,
+        guard_identifier: u64,
+    },
+    Fd {
+        flavor: ExceptionCodeMacGuardFDFlavor,
+        fd: u32,
+        guard_identifier: u64,
+    },
+    User {
+        namespace: u32,
+        reason_code: u64,
+    },
+    Vn {
+        flavor: ExceptionCodeMacGuardVNFlavor,
+        pid: u32,
+        guard_identifier: u64,
+    },
+    VirtMemory {
+        flavor: ExceptionCodeMacGuardVirtMemoryFlavor,
+        offset: u64,
+    },
+}
+
+impl ExceptionCodeMacGuardInfo {
+    /// Decode the code and subcode of a guard exception of type `ty`.
+    ///
+    /// Returns `None` if the flavor isn't one we know of.
+    pub fn from_codes(ty: ExceptionCodeMacGuardType, code: u64, subcode: u64) -> Option<Self> {
+        let flavor = (code >> 32) & 0x1fffffff;
+        let target = code as u32;
+        let info = match ty {
+            ExceptionCodeMacGuardType::GUARD_TYPE_NONE => Self::Unknown,
+            ExceptionCodeMacGuardType::GUARD_TYPE_MACH_PORT => Self::MachPort {
+                flavor: ExceptionCodeMacGuardMachPortFlavor::from_u64(flavor)?,
+                port_name: target,
+                guard_identifier: subcode,
+            },
+            ExceptionCodeMacGuardType::GUARD_TYPE_FD => Self::Fd {
+                flavor: ExceptionCodeMacGuardFDFlavor::from_u64(flavor)?,
+                fd: target,
+                guard_identifier: subcode,
+            },
+            ExceptionCodeMacGuardType::GUARD_TYPE_USER => Self::User {
+                namespace: target,
+                reason_code: subcode,
+            },
+            ExceptionCodeMacGuardType::GUARD_TYPE_VN => Self::Vn {
+                flavor: ExceptionCodeMacGuardVNFlavor::from_u64(flavor)?,
+                pid: target,
+                guard_identifier: subcode,
+            },
+            ExceptionCodeMacGuardType::GUARD_TYPE_VIRT_MEMORY => Self::VirtMemory {
+                flavor: ExceptionCodeMacGuardVirtMemoryFlavor::from_u64(flavor)?,
+                offset: subcode,
+            },
+        };
+        Some(info)
+    }
+}
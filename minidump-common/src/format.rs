@@ -210,7 +210,13 @@ pub enum MINIDUMP_STREAM_TYPE {
     Memory64ListStream = 9,
     CommentStreamA = 10,
     CommentStreamW = 11,
+    /// The list of handles the process had open
+    ///
+    /// See ['MINIDUMP_HANDLE_DATA_STREAM'].
     HandleDataStream = 12,
+    /// The dynamic function tables the process registered (with `RtlAddFunctionTable`)
+    ///
+    /// See ['MINIDUMP_FUNCTION_TABLE_STREAM'].
     FunctionTable = 13,
     /// The list of executable modules from the process that were unloaded by the time of the crash
     ///
@@ -232,12 +238,30 @@ pub enum MINIDUMP_STREAM_TYPE {
     ///
     /// See ['MINIDUMP_MEMORY_INFO_LIST'].
     MemoryInfoListStream = 16,
+    /// Extra information about threads, like their start address and CPU times
+    ///
+    /// See ['MINIDUMP_THREAD_INFO_LIST'].
     ThreadInfoListStream = 17,
+    /// The operations on handles recorded by Application Verifier's handle tracing
+    ///
+    /// See ['MINIDUMP_HANDLE_OPERATION_LIST'].
     HandleOperationListStream = 18,
+    /// Security tokens of the process and its threads
+    ///
+    /// See ['MINIDUMP_TOKEN_INFO_LIST'].
     TokenStream = 19,
     JavaScriptDataStream = 20,
+    /// System-wide memory information
+    ///
+    /// See ['MINIDUMP_SYSTEM_MEMORY_INFO_1'].
     SystemMemoryInfoStream = 21,
+    /// Memory usage counters for the process
+    ///
+    /// See ['MINIDUMP_PROCESS_VM_COUNTERS_1'] and ['MINIDUMP_PROCESS_VM_COUNTERS_2'].
     ProcessVmCountersStream = 22,
+    /// Intel Processor Trace data for the process's threads
+    ///
+    /// See ['IPT_TRACE_DATA'].
     IptTraceStream = 23,
     /// Names of threads
     ///
@@ -294,6 +318,8 @@ pub enum MINIDUMP_STREAM_TYPE {
     /// The contents of /proc/self/environ from a Linux system
     LinuxEnviron = 0x47670007,
     /// The contents of /proc/self/auxv from a Linux system
+    ///
+    /// See [`AuxvType`].
     LinuxAuxv = 0x47670008,
     /// The contents of /proc/self/maps from a Linux system
     LinuxMaps = 0x47670009,
@@ -312,6 +338,15 @@ pub enum MINIDUMP_STREAM_TYPE {
     ///
     /// See ['MINIDUMP_MAC_CRASH_INFO'].
     MozMacosCrashInfoStream = 0x4d7a0001,
+
+    /// The kernel boot arguments (the `kern.bootargs` sysctl). Only available
+    /// on macOS.
+    ///
+    /// See ['MINIDUMP_MAC_BOOTARGS'].
+    MozMacosBootargsStream = 0x4d7a0002,
+
+    /// The contents of /proc/self/limits from a Linux system.
+    MozLinuxLimits = 0x4d7a0003,
 }
 
 impl From<MINIDUMP_STREAM_TYPE> for u32 {
@@ -417,6 +452,62 @@ pub const VS_FFI_SIGNATURE: u32 = 0xfeef04bd;
 /// The expected value of `VS_FIXEDFILEINFO.struct_version`
 pub const VS_FFI_STRUCVERSION: u32 = 0x00010000;
 
+bitflags! {
+    /// Flags in `VS_FIXEDFILEINFO.file_flags`
+    ///
+    /// Only the flags that are also set in `VS_FIXEDFILEINFO.file_flags_mask` are valid.
+    pub struct VersionFileFlags: u32 {
+        /// The file contains debugging information or is compiled with debugging features enabled
+        const VS_FF_DEBUG = 0x00000001;
+        /// The file is a development version, not a commercially released product
+        const VS_FF_PRERELEASE = 0x00000002;
+        /// The file has been modified and is not identical to the original shipping file
+        const VS_FF_PATCHED = 0x00000004;
+        /// The file was not built using standard release procedures
+        const VS_FF_PRIVATEBUILD = 0x00000008;
+        /// The file's version structure was created dynamically
+        const VS_FF_INFOINFERRED = 0x00000010;
+        /// The file is a variation of the normal file of the same version number
+        const VS_FF_SPECIALBUILD = 0x00000020;
+    }
+}
+
+/// Known values of `VS_FIXEDFILEINFO.file_os`
+///
+/// These are combinations of the operating system a file was designed for and
+/// the windowing system it runs on, from verrsrc.h.
+#[repr(u32)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Primitive)]
+pub enum VersionFileOs {
+    VOS_UNKNOWN = 0x00000000,
+    VOS_DOS = 0x00010000,
+    VOS_OS216 = 0x00020000,
+    VOS_OS232 = 0x00030000,
+    VOS_NT = 0x00040000,
+    VOS__WINDOWS16 = 0x00000001,
+    VOS__PM16 = 0x00000002,
+    VOS__PM32 = 0x00000003,
+    VOS__WINDOWS32 = 0x00000004,
+    VOS_DOS_WINDOWS16 = 0x00010001,
+    VOS_DOS_WINDOWS32 = 0x00010004,
+    VOS_OS216_PM16 = 0x00020002,
+    VOS_OS232_PM32 = 0x00030003,
+    VOS_NT_WINDOWS32 = 0x00040004,
+}
+
+/// Known values of `VS_FIXEDFILEINFO.file_type`
+#[repr(u32)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Primitive)]
+pub enum VersionFileType {
+    VFT_UNKNOWN = 0x00000000,
+    VFT_APP = 0x00000001,
+    VFT_DLL = 0x00000002,
+    VFT_DRV = 0x00000003,
+    VFT_FONT = 0x00000004,
+    VFT_VXD = 0x00000005,
+    VFT_STATIC_LIB = 0x00000007,
+}
+
 /// Known values for the `signature` field of CodeView records
 ///
 /// In addition to the two CodeView record formats used for linking
@@ -855,6 +946,9 @@ bitflags! {
         ///
         /// NOTE: at this precise moment breakpad doesn't define this, but Microsoft does!
         const CONTEXT_ARM64_X18 = 0x0000010 | ContextFlagsCpu::CONTEXT_ARM64.bits;
+        /// Whether the context has extended state (XSTATE) after the end of
+        /// [`CONTEXT_ARM64`], like the SVE registers (see [`XSTATE_ARM64_SVE`]).
+        const CONTEXT_ARM64_XSTATE = 0x0000020 | ContextFlagsCpu::CONTEXT_ARM64.bits;
         const CONTEXT_ARM64_FULL = Self::CONTEXT_ARM64_CONTROL.bits | Self::CONTEXT_ARM64_INTEGER.bits | Self::CONTEXT_ARM64_FLOATING_POINT.bits;
         const CONTEXT_ARM64_ALL = Self::CONTEXT_ARM64_FULL.bits | Self::CONTEXT_ARM64_DEBUG_REGISTERS.bits | Self::CONTEXT_ARM64_X18.bits;
     }
@@ -1739,6 +1833,14 @@ impl<'a> Iterator for XstateFeatureIter<'a> {
     }
 }
 
+/// The entry in `XSTATE_CONFIG_FEATURE_MSC_INFO.features` that holds the SVE
+/// registers of ARM64 contexts.
+///
+/// This shares its index with [`XstateFeatureIndex::GSSE_AND_AVX`], which is
+/// only used by x86 and amd64 contexts. Unlike theirs, the offset of this
+/// feature is from the start of the [`CONTEXT_ARM64`].
+pub const XSTATE_ARM64_SVE: usize = 2;
+
 /// Several known entries in `XSTATE_CONFIG_FEATURE_MSC_INFO.features`.
 #[repr(usize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -1900,6 +2002,616 @@ bitflags! {
     }
 }
 
+/// A list of extra information about threads in a minidump
+///
+/// This is the format of the [`MINIDUMP_STREAM_TYPE::ThreadInfoListStream`]. The individual
+/// [`MINIDUMP_THREAD_INFO`] entries follow this header in the stream.
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_thread_info_list
+#[derive(Debug, Clone, Pread, SizeWith)]
+pub struct MINIDUMP_THREAD_INFO_LIST {
+    /// The size of this header
+    pub size_of_header: u32,
+    /// The size of each entry in the list
+    pub size_of_entry: u32,
+    /// The number of entries in the list
+    pub number_of_entries: u32,
+}
+
+/// Extra information about a thread in a minidump
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_thread_info
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_THREAD_INFO {
+    /// The id of the thread
+    pub thread_id: u32,
+    /// What went wrong while writing the thread's information
+    ///
+    /// See [`ThreadInfoDumpFlags`] for valid values.
+    pub dump_flags: u32,
+    /// The HRESULT of the failure, if `dump_flags` has `MINIDUMP_THREAD_INFO_ERROR_THREAD`
+    pub dump_error: u32,
+    /// The thread's exit code, if it had exited
+    pub exit_status: u32,
+    /// When the thread was created, as a Windows `FILETIME`
+    pub create_time: u64,
+    /// When the thread exited, as a Windows `FILETIME`
+    pub exit_time: u64,
+    /// How long the thread has run in kernel mode, in 100-nanosecond intervals
+    pub kernel_time: u64,
+    /// How long the thread has run in user mode, in 100-nanosecond intervals
+    pub user_time: u64,
+    /// The address of the thread's start routine
+    pub start_address: u64,
+    /// The thread's processor affinity mask
+    pub affinity: u64,
+}
+
+bitflags! {
+    /// Potential values for [`MINIDUMP_THREAD_INFO::dump_flags`]
+    ///
+    /// See [Microsoft's documentation][msdn] for details.
+    ///
+    /// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_thread_info
+    pub struct ThreadInfoDumpFlags: u32 {
+        const MINIDUMP_THREAD_INFO_ERROR_THREAD    = 0x00000001;
+        const MINIDUMP_THREAD_INFO_WRITING_THREAD  = 0x00000002;
+        const MINIDUMP_THREAD_INFO_EXITED_THREAD   = 0x00000004;
+        const MINIDUMP_THREAD_INFO_INVALID_INFO    = 0x00000008;
+        const MINIDUMP_THREAD_INFO_INVALID_CONTEXT = 0x00000010;
+        const MINIDUMP_THREAD_INFO_INVALID_TEB     = 0x00000020;
+    }
+}
+
+/// The header of the list of open handles in a minidump
+///
+/// This is the format of the [`MINIDUMP_STREAM_TYPE::HandleDataStream`]. The individual
+/// [`MINIDUMP_HANDLE_DESCRIPTOR`] or [`MINIDUMP_HANDLE_DESCRIPTOR_2`] entries (depending
+/// on `size_of_descriptor`) follow this header in the stream.
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_handle_data_stream
+#[derive(Debug, Clone, Default, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_HANDLE_DATA_STREAM {
+    /// The size of this header
+    pub size_of_header: u32,
+    /// The size of each descriptor in the list
+    pub size_of_descriptor: u32,
+    /// The number of descriptors in the list
+    pub number_of_descriptors: u32,
+    pub reserved: u32,
+}
+
+/// Information about an open handle in a minidump
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_handle_descriptor
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_HANDLE_DESCRIPTOR {
+    /// The operating system handle value
+    pub handle: u64,
+    /// An RVA to a `MINIDUMP_STRING` naming the object type, or 0
+    pub type_name_rva: RVA,
+    /// An RVA to a `MINIDUMP_STRING` naming the object, or 0
+    pub object_name_rva: RVA,
+    /// The attributes of the handle
+    pub attributes: u32,
+    /// The access rights granted when the handle was opened
+    pub granted_access: u32,
+    /// The number of handles to the object
+    pub handle_count: u32,
+    /// The number of kernel references to the object
+    pub pointer_count: u32,
+}
+
+/// Information about an open handle in a minidump, with extra object information
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_handle_descriptor_2
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_HANDLE_DESCRIPTOR_2 {
+    /// The operating system handle value
+    pub handle: u64,
+    /// An RVA to a `MINIDUMP_STRING` naming the object type, or 0
+    pub type_name_rva: RVA,
+    /// An RVA to a `MINIDUMP_STRING` naming the object, or 0
+    pub object_name_rva: RVA,
+    /// The attributes of the handle
+    pub attributes: u32,
+    /// The access rights granted when the handle was opened
+    pub granted_access: u32,
+    /// The number of handles to the object
+    pub handle_count: u32,
+    /// The number of kernel references to the object
+    pub pointer_count: u32,
+    /// An RVA to a `MINIDUMP_HANDLE_OBJECT_INFORMATION` structure, or 0
+    pub object_info_rva: RVA,
+    pub reserved0: u32,
+}
+
+/// The header of the list of handle operations in a minidump
+///
+/// This is the format of the [`MINIDUMP_STREAM_TYPE::HandleOperationListStream`]. The
+/// individual [`AVRF_HANDLE_OPERATION`] entries follow this header in the stream.
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_handle_operation_list
+#[derive(Debug, Clone, Default, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_HANDLE_OPERATION_LIST {
+    /// The size of this header
+    pub size_of_header: u32,
+    /// The size of each entry in the list
+    pub size_of_entry: u32,
+    /// The number of entries in the list
+    pub number_of_entries: u32,
+    pub reserved: u32,
+}
+
+/// The maximum number of return addresses in an [`AVRF_BACKTRACE_INFORMATION`]
+pub const AVRF_MAX_TRACES: usize = 32;
+
+/// A backtrace captured by Application Verifier's handle tracing
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/avrfsdk/ns-avrfsdk-avrf_backtrace_information
+#[derive(Debug, Clone, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct AVRF_BACKTRACE_INFORMATION {
+    /// The number of valid entries in `return_addresses`
+    pub depth: u32,
+    /// An index identifying this backtrace in the trace database
+    pub index: u32,
+    /// The return addresses of the backtrace, innermost first
+    pub return_addresses: [u64; 32],
+}
+
+/// A single operation on a handle, recorded by Application Verifier's handle tracing
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/avrfsdk/ns-avrfsdk-avrf_handle_operation
+#[derive(Debug, Clone, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct AVRF_HANDLE_OPERATION {
+    /// The operating system handle value
+    pub handle: u64,
+    /// The id of the process that performed the operation
+    pub process_id: u32,
+    /// The id of the thread that performed the operation
+    pub thread_id: u32,
+    /// What kind of operation this was
+    ///
+    /// See [`HandleTraceOperation`] for valid values.
+    pub operation_type: u32,
+    pub spare0: u32,
+    /// The stack of the thread when it performed the operation
+    pub back_trace_information: AVRF_BACKTRACE_INFORMATION,
+}
+
+/// Known values of [`AVRF_HANDLE_OPERATION::operation_type`]
+///
+/// This matches the Microsoft enum [`eHANDLE_TRACE_OPERATIONS`][msdn].
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/avrfsdk/ne-avrfsdk-ehandle_trace_operations
+#[repr(u32)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Primitive)]
+pub enum HandleTraceOperation {
+    OperationDbUnused = 0,
+    /// The handle was opened
+    OperationDbOPEN = 1,
+    /// The handle was closed
+    OperationDbCLOSE = 2,
+    /// The handle was used when it wasn't valid
+    OperationDbBADREF = 3,
+}
+
+/// The header of the list of dynamic function tables in a minidump
+///
+/// This is the format of the [`MINIDUMP_STREAM_TYPE::FunctionTable`] stream. After this
+/// header (and `size_of_align_pad` bytes of padding), each function table is stored as:
+///
+/// * a [`MINIDUMP_FUNCTION_TABLE_DESCRIPTOR`] (`size_of_descriptor` bytes)
+/// * the OS's native description of the table (`size_of_native_descriptor` bytes)
+/// * the table's function entries (`entry_count * size_of_function_entry` bytes)
+/// * padding (the descriptor's `size_of_align_pad` bytes)
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_function_table_stream
+#[derive(Debug, Clone, Default, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_FUNCTION_TABLE_STREAM {
+    /// The size of this header
+    pub size_of_header: u32,
+    /// The size of each [`MINIDUMP_FUNCTION_TABLE_DESCRIPTOR`]
+    pub size_of_descriptor: u32,
+    /// The size of each OS-specific table descriptor
+    pub size_of_native_descriptor: u32,
+    /// The size of each function entry
+    pub size_of_function_entry: u32,
+    /// The number of function tables
+    pub number_of_descriptors: u32,
+    /// The size of the padding after this header
+    pub size_of_align_pad: u32,
+}
+
+/// A dynamic function table in a minidump
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_function_table_descriptor
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_FUNCTION_TABLE_DESCRIPTOR {
+    /// The lowest address of the functions in the table
+    pub minimum_address: u64,
+    /// The highest address of the functions in the table
+    pub maximum_address: u64,
+    /// The address the function entries' addresses are relative to
+    pub base_address: u64,
+    /// The number of function entries in the table
+    pub entry_count: u32,
+    /// The size of the padding after the table's entries
+    pub size_of_align_pad: u32,
+}
+
+/// An x86-64 function table entry, describing where to find a function's unwind information
+///
+/// All addresses are relative to the base address of the table (or module) containing the
+/// entry. This struct matches the Microsoft struct of the same name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct IMAGE_RUNTIME_FUNCTION_ENTRY {
+    /// The address of the start of the function
+    pub begin_address: u32,
+    /// The address of the end of the function
+    pub end_address: u32,
+    /// The address of the function's `UNWIND_INFO`
+    pub unwind_info_address: u32,
+}
+
+/// Memory usage counters for a process, version 1
+///
+/// This is one of the formats of the [`MINIDUMP_STREAM_TYPE::ProcessVmCountersStream`]
+/// (see `revision`).
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_process_vm_counters_1
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_PROCESS_VM_COUNTERS_1 {
+    /// The version of this struct (1)
+    pub revision: u16,
+    pub __padding: u16,
+    /// The number of page faults
+    pub page_fault_count: u32,
+    /// The peak working set size, in bytes
+    pub peak_working_set_size: u64,
+    /// The current working set size, in bytes
+    pub working_set_size: u64,
+    /// The peak paged pool usage, in bytes
+    pub quota_peak_paged_pool_usage: u64,
+    /// The current paged pool usage, in bytes
+    pub quota_paged_pool_usage: u64,
+    /// The peak nonpaged pool usage, in bytes
+    pub quota_peak_non_paged_pool_usage: u64,
+    /// The current nonpaged pool usage, in bytes
+    pub quota_non_paged_pool_usage: u64,
+    /// The commit charge (memory the system has committed for the process), in bytes
+    pub pagefile_usage: u64,
+    /// The peak commit charge, in bytes
+    pub peak_pagefile_usage: u64,
+    /// The private memory of the process, in bytes
+    pub private_usage: u64,
+}
+
+/// Memory usage counters for a process, version 2
+///
+/// This is one of the formats of the [`MINIDUMP_STREAM_TYPE::ProcessVmCountersStream`]
+/// (see `revision`).
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_process_vm_counters_2
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_PROCESS_VM_COUNTERS_2 {
+    /// The version of this struct (2)
+    pub revision: u16,
+    /// Which groups of fields are valid
+    ///
+    /// See [`ProcessVmCountersFlags`] for valid values.
+    pub flags: u16,
+    /// The number of page faults
+    pub page_fault_count: u32,
+    /// The peak working set size, in bytes
+    pub peak_working_set_size: u64,
+    /// The current working set size, in bytes
+    pub working_set_size: u64,
+    /// The peak paged pool usage, in bytes
+    pub quota_peak_paged_pool_usage: u64,
+    /// The current paged pool usage, in bytes
+    pub quota_paged_pool_usage: u64,
+    /// The peak nonpaged pool usage, in bytes
+    pub quota_peak_non_paged_pool_usage: u64,
+    /// The current nonpaged pool usage, in bytes
+    pub quota_non_paged_pool_usage: u64,
+    /// The commit charge (memory the system has committed for the process), in bytes
+    pub pagefile_usage: u64,
+    /// The peak commit charge, in bytes
+    pub peak_pagefile_usage: u64,
+    /// The peak virtual address space size, in bytes
+    pub peak_virtual_size: u64,
+    /// The current virtual address space size, in bytes
+    pub virtual_size: u64,
+    /// The private memory of the process, in bytes
+    pub private_usage: u64,
+    /// The private working set size, in bytes
+    pub private_working_set_size: u64,
+    /// The shared commit usage, in bytes
+    pub shared_commit_usage: u64,
+    /// The shared commit usage of the process's job, in bytes
+    pub job_shared_commit_usage: u64,
+    /// The private commit usage of the process's job, in bytes
+    pub job_private_commit_usage: u64,
+    /// The peak private commit usage of the process's job, in bytes
+    pub job_peak_private_commit_usage: u64,
+    /// The private commit limit of the process's job, in bytes
+    pub job_private_commit_limit: u64,
+    /// The total commit limit of the process's job, in bytes
+    pub job_total_commit_limit: u64,
+}
+
+bitflags! {
+    /// Potential values for [`MINIDUMP_PROCESS_VM_COUNTERS_2::flags`]
+    pub struct ProcessVmCountersFlags: u16 {
+        /// The fields shared with [`MINIDUMP_PROCESS_VM_COUNTERS_1`] are valid
+        const MINIDUMP_PROCESS_VM_COUNTERS             = 0x0001;
+        /// `peak_virtual_size` and `virtual_size` are valid
+        const MINIDUMP_PROCESS_VM_COUNTERS_VIRTUALSIZE = 0x0002;
+        /// `private_usage` is valid
+        const MINIDUMP_PROCESS_VM_COUNTERS_EX          = 0x0004;
+        /// `private_working_set_size` and `shared_commit_usage` are valid
+        const MINIDUMP_PROCESS_VM_COUNTERS_EX2         = 0x0008;
+        /// The `job_*` fields are valid
+        const MINIDUMP_PROCESS_VM_COUNTERS_JOB         = 0x0010;
+    }
+}
+
+/// The header of the list of security tokens in a minidump
+///
+/// This is the format of the [`MINIDUMP_STREAM_TYPE::TokenStream`]. The tokens follow this
+/// header, each starting with a [`MINIDUMP_TOKEN_INFO_HEADER`].
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_token_info_list
+#[derive(Debug, Clone, Default, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_TOKEN_INFO_LIST {
+    /// The size of the whole list, in bytes
+    pub token_list_size: u32,
+    /// The number of tokens in the list
+    pub token_list_entries: u32,
+    /// The size of this header
+    pub list_header_size: u32,
+    /// The size of each token's [`MINIDUMP_TOKEN_INFO_HEADER`]
+    pub element_header_size: u32,
+}
+
+/// The header of a security token in a minidump
+///
+/// The token's data follows this header.
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_token_info_header
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_TOKEN_INFO_HEADER {
+    /// The size of the token, including this header, in bytes
+    pub token_size: u32,
+    /// The id of the token
+    pub token_id: u32,
+    /// The handle of the token
+    pub token_handle: u64,
+}
+
+/// System-wide memory information, version 1
+///
+/// This is the format of the [`MINIDUMP_STREAM_TYPE::SystemMemoryInfoStream`]. Later
+/// revisions are expected to only add fields, so a reader may use the prefix it knows.
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_system_memory_info_1
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_SYSTEM_MEMORY_INFO_1 {
+    /// The version of this struct (1)
+    pub revision: u16,
+    pub flags: u16,
+    pub basic_info: MINIDUMP_SYSTEM_BASIC_INFORMATION,
+    pub file_cache_info: MINIDUMP_SYSTEM_FILECACHE_INFORMATION,
+    pub basic_perf_info: MINIDUMP_SYSTEM_BASIC_PERFORMANCE_INFORMATION,
+    pub perf_info: MINIDUMP_SYSTEM_PERFORMANCE_INFORMATION,
+}
+
+/// Basic information about the system's memory
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_system_basic_information
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_SYSTEM_BASIC_INFORMATION {
+    pub timer_resolution: u32,
+    /// The size of a page, in bytes
+    pub page_size: u32,
+    pub number_of_physical_pages: u32,
+    pub lowest_physical_page_number: u32,
+    pub highest_physical_page_number: u32,
+    pub allocation_granularity: u32,
+    pub minimum_user_mode_address: u64,
+    pub maximum_user_mode_address: u64,
+    pub active_processors_affinity_mask: u64,
+    pub number_of_processors: u32,
+}
+
+/// Information about the system's file cache
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_system_filecache_information
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_SYSTEM_FILECACHE_INFORMATION {
+    pub current_size: u64,
+    pub peak_size: u64,
+    pub page_fault_count: u32,
+    pub minimum_working_set: u64,
+    pub maximum_working_set: u64,
+    pub current_size_including_transition_in_pages: u64,
+    pub peak_size_including_transition_in_pages: u64,
+    pub transition_re_purpose_count: u32,
+    pub flags: u32,
+}
+
+/// Basic performance counters for the system's memory, in pages
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_system_basic_performance_information
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_SYSTEM_BASIC_PERFORMANCE_INFORMATION {
+    /// The number of free physical pages
+    pub available_pages: u64,
+    /// The number of pages the system has committed
+    pub committed_pages: u64,
+    /// The most pages the system can commit without growing the page file
+    pub commit_limit: u64,
+    /// The most pages the system has had committed at once
+    pub peak_commitment: u64,
+}
+
+/// Detailed performance counters for the system
+///
+/// This struct matches the [Microsoft struct][msdn] of the same name.
+///
+/// [msdn]: https://docs.microsoft.com/en-us/windows/win32/api/minidumpapiset/ns-minidumpapiset-minidump_system_performance_information
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct MINIDUMP_SYSTEM_PERFORMANCE_INFORMATION {
+    pub idle_process_time: u64,
+    pub io_read_transfer_count: u64,
+    pub io_write_transfer_count: u64,
+    pub io_other_transfer_count: u64,
+    pub io_read_operation_count: u32,
+    pub io_write_operation_count: u32,
+    pub io_other_operation_count: u32,
+    pub available_pages: u32,
+    pub committed_pages: u32,
+    pub commit_limit: u32,
+    pub peak_commitment: u32,
+    pub page_fault_count: u32,
+    pub copy_on_write_count: u32,
+    pub transition_count: u32,
+    pub cache_transition_count: u32,
+    pub demand_zero_count: u32,
+    pub page_read_count: u32,
+    pub page_read_io_count: u32,
+    pub cache_read_count: u32,
+    pub cache_io_count: u32,
+    pub dirty_pages_write_count: u32,
+    pub dirty_write_io_count: u32,
+    pub mapped_pages_write_count: u32,
+    pub mapped_write_io_count: u32,
+    pub paged_pool_pages: u32,
+    pub non_paged_pool_pages: u32,
+    pub paged_pool_allocs: u32,
+    pub paged_pool_frees: u32,
+    pub non_paged_pool_allocs: u32,
+    pub non_paged_pool_frees: u32,
+    pub free_system_ptes: u32,
+    pub resident_system_code_page: u32,
+    pub total_system_driver_pages: u32,
+    pub total_system_code_pages: u32,
+    pub non_paged_pool_lookaside_hits: u32,
+    pub paged_pool_lookaside_hits: u32,
+    pub available_paged_pool_pages: u32,
+    pub resident_system_cache_page: u32,
+    pub resident_paged_pool_page: u32,
+    pub resident_system_driver_page: u32,
+    pub cc_fast_read_no_wait: u32,
+    pub cc_fast_read_wait: u32,
+    pub cc_fast_read_resource_miss: u32,
+    pub cc_fast_read_not_possible: u32,
+    pub cc_fast_mdl_read_no_wait: u32,
+    pub cc_fast_mdl_read_wait: u32,
+    pub cc_fast_mdl_read_resource_miss: u32,
+    pub cc_fast_mdl_read_not_possible: u32,
+    pub cc_map_data_no_wait: u32,
+    pub cc_map_data_wait: u32,
+    pub cc_map_data_no_wait_miss: u32,
+    pub cc_map_data_wait_miss: u32,
+    pub cc_pin_mapped_data_count: u32,
+    pub cc_pin_read_no_wait: u32,
+    pub cc_pin_read_wait: u32,
+    pub cc_pin_read_no_wait_miss: u32,
+    pub cc_pin_read_wait_miss: u32,
+    pub cc_copy_read_no_wait: u32,
+    pub cc_copy_read_wait: u32,
+    pub cc_copy_read_no_wait_miss: u32,
+    pub cc_copy_read_wait_miss: u32,
+    pub cc_mdl_read_no_wait: u32,
+    pub cc_mdl_read_wait: u32,
+    pub cc_mdl_read_no_wait_miss: u32,
+    pub cc_mdl_read_wait_miss: u32,
+    pub cc_read_ahead_ios: u32,
+    pub cc_lazy_write_ios: u32,
+    pub cc_lazy_write_pages: u32,
+    pub cc_data_flushes: u32,
+    pub cc_data_pages: u32,
+    pub context_switches: u32,
+    pub first_level_tb_fills: u32,
+    pub second_level_tb_fills: u32,
+    pub system_calls: u32,
+    pub cc_total_dirty_pages: u64,
+    pub cc_dirty_page_threshold: u64,
+    pub resident_available_pages: i64,
+    pub shared_committed_pages: u64,
+}
+
+/// The header of an [`MINIDUMP_STREAM_TYPE::IptTraceStream`]
+///
+/// Microsoft doesn't document this stream. It holds the Intel Processor
+/// Trace driver's trace buffer for the process, laid out like the driver's
+/// `IPT_TRACE_DATA` (as used by [WinIPT][winipt]): this header, then
+/// `trace_size` bytes holding an [`IPT_TRACE_HEADER`] and trace for each
+/// thread.
+///
+/// [winipt]: https://github.com/ionescu007/winipt
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct IPT_TRACE_DATA {
+    pub trace_version: u16,
+    pub valid_trace: u16,
+    /// The size of the per-thread traces that follow, in bytes
+    pub trace_size: u32,
+}
+
+/// The header of one thread's trace in an [`MINIDUMP_STREAM_TYPE::IptTraceStream`]
+///
+/// This is followed by `trace_size` bytes of trace packets. See [`IPT_TRACE_DATA`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Pread, Pwrite, SizeWith)]
+pub struct IPT_TRACE_HEADER {
+    pub thread_id: u64,
+    /// The driver's `IPT_TIMING_SETTINGS` for the trace
+    pub timing_settings: u32,
+    pub mtc_frequency: u32,
+    pub frequency_to_tsc_ratio: u32,
+    /// Where in the trace the ring buffer's writes had got to, in bytes
+    pub ring_buffer_offset: u32,
+    /// The size of the trace packets that follow, in bytes
+    pub trace_size: u32,
+}
+
 /// A Breakpad extension containing some additional process information
 ///
 /// Taken from the definition in Breakpad's [minidump_format.h][fmt].
@@ -1992,18 +2704,21 @@ pub struct DSO_DEBUG_32 {
 /// Dynamic linker information for a shared library on 64-bit Linux
 ///
 /// This is functionally equivalent to the data in `struct link_map` defined in <link.h>.
+/// Unlike most minidump structs, this one is naturally aligned, so `ld` is preceded by padding.
 #[derive(Debug, Clone, Pread, Pwrite, SizeWith)]
 pub struct LINK_MAP_64 {
     pub addr: u64,
     /// The offset of a string containing the filename of this shared library
     pub name: RVA,
+    pub __padding: u32,
     pub ld: u64,
 }
 
 /// DSO debug data for 64-bit Linux minidumps
 ///
 /// Used when converting minidumps to coredumps. This is functionally equivalent to the data
-/// in `struct r_debug` defined in <link.h>.
+/// in `struct r_debug` defined in <link.h>. Unlike most minidump structs, this one is
+/// naturally aligned, so `brk` is preceded by padding.
 #[derive(Debug, Clone, Pread, Pwrite, SizeWith)]
 pub struct DSO_DEBUG_64 {
     /// The version number of this protocol, from `r_debug.r_version`
@@ -2012,6 +2727,7 @@ pub struct DSO_DEBUG_64 {
     pub map: RVA,
     /// The number of [`LINK_MAP_64`] entries pointed to by `map`
     pub dso_count: u32,
+    pub __padding: u32,
     /// The address of a function internal to the run-time linker used by debuggers to
     /// set a breakpoint.
     pub brk: u64,
@@ -2021,6 +2737,65 @@ pub struct DSO_DEBUG_64 {
     pub dynamic: u64,
 }
 
+/// The keys of the entries in the auxiliary vector (`/proc/self/auxv`) on Linux
+///
+/// Each entry is a pair of native-sized words: one of these keys, and its value. See the
+/// [include/uapi/linux/auxvec.h][header] header in the Linux kernel sources.
+///
+/// [header]: https://github.com/torvalds/linux/blob/master/include/uapi/linux/auxvec.h
+#[repr(u64)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Primitive)]
+pub enum AuxvType {
+    /// The end of the vector
+    AT_NULL = 0,
+    AT_IGNORE = 1,
+    /// The file descriptor of the program
+    AT_EXECFD = 2,
+    /// The address of the program's program headers
+    AT_PHDR = 3,
+    /// The size of a program header
+    AT_PHENT = 4,
+    /// The number of program headers
+    AT_PHNUM = 5,
+    /// The system page size
+    AT_PAGESZ = 6,
+    /// The base address of the dynamic linker
+    AT_BASE = 7,
+    AT_FLAGS = 8,
+    /// The program's entry point
+    AT_ENTRY = 9,
+    /// Whether the program is not ELF
+    AT_NOTELF = 10,
+    AT_UID = 11,
+    AT_EUID = 12,
+    AT_GID = 13,
+    AT_EGID = 14,
+    /// The address of a string identifying the CPU, for the dynamic linker
+    AT_PLATFORM = 15,
+    /// A bitmask of CPU capabilities
+    AT_HWCAP = 16,
+    /// The frequency of `times()`
+    AT_CLKTCK = 17,
+    /// Whether the program was run in secure mode (like a setuid program)
+    AT_SECURE = 23,
+    /// The address of a string identifying the real CPU
+    AT_BASE_PLATFORM = 24,
+    /// The address of 16 random bytes
+    AT_RANDOM = 25,
+    /// An extension of `AT_HWCAP`
+    AT_HWCAP2 = 26,
+    AT_RSEQ_FEATURE_SIZE = 27,
+    AT_RSEQ_ALIGN = 28,
+    /// The address of the program's file name
+    AT_EXECFN = 31,
+    /// The entry point of the vdso's system call function (32-bit x86 only)
+    AT_SYSINFO = 32,
+    /// The address of the vdso's ELF header
+    AT_SYSINFO_EHDR = 33,
+    /// The minimum stack size needed by the kernel's signal delivery
+    AT_MINSIGSTKSZ = 51,
+}
+
 /// A variable-length UTF-8-encoded string carried within a minidump file.
 ///
 /// See <https://crashpad.chromium.org/doxygen/structcrashpad_1_1MinidumpUTF8String.html>
@@ -2404,6 +3179,16 @@ pub const MAC_CRASH_INFO_STRING_MAX_SIZE: usize = 8192;
 /// undocumented, so just in case we set a large maximum.
 pub const MAC_CRASH_INFOS_MAX: usize = 20;
 
+/// MacOS kernel boot arguments.
+///
+/// This is the format of the [`MINIDUMP_STREAM_TYPE::MozMacosBootargsStream`].
+#[derive(Debug, Clone, Pread, SizeWith)]
+pub struct MINIDUMP_MAC_BOOTARGS {
+    pub stream_type: u32,
+    /// Where the boot arguments are stored, as a length-prefixed UTF-16 string.
+    pub bootargs: RVA64,
+}
+
 bitflags! {
     /// Possible values of [`ARMCpuInfo::elf_hwcaps`]
     ///
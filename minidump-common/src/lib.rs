@@ -8,7 +8,9 @@
 
 #![warn(missing_debug_implementations)]
 
+pub mod cpu;
 pub mod errors;
 pub mod format;
+pub mod poison;
 pub mod traits;
 pub mod utils;
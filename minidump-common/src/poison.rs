@@ -0,0 +1,113 @@
+//! Well-known values that allocators and runtimes fill memory with.
+//!
+//! Debug heaps, hardened allocators, and sanitizers fill freed and
+//! uninitialized memory with recognizable patterns, so that a pointer loaded
+//! from such memory stands out. A crash address that matches one of these is
+//! a strong hint about what went wrong: a freed-memory pattern suggests a
+//! use-after-free, an uninitialized-memory one a missing initialization.
+//!
+//! Pointers loaded from poisoned memory are often offset by a field access
+//! before being dereferenced, and on 64-bit targets only the low 32 bits may
+//! be poisoned, so [`poison_pattern`] tolerates both.
+
+/// A value that some allocator or runtime fills memory with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoisonPattern {
+    /// A short identifier for the pattern, like `"msvc_freed_heap_poison"`.
+    pub kind: &'static str,
+    /// A description of what memory with this pattern is.
+    pub description: &'static str,
+    /// The pattern itself.
+    ///
+    /// Patterns that fit in 32 bits are matched against the low 32 bits of
+    /// values, wider ones against the whole value.
+    pub value: u64,
+}
+
+/// Every known [`PoisonPattern`].
+///
+/// Some allocators share patterns, in which case the first one listed wins.
+pub static POISON_PATTERNS: &[PoisonPattern] = &[
+    PoisonPattern {
+        kind: "msvc_freed_heap_poison",
+        description: "freed memory from the MSVC debug heap (use-after-free)",
+        value: 0xdddddddd,
+    },
+    PoisonPattern {
+        kind: "msvc_uninitialized_heap_poison",
+        description: "uninitialized memory from the MSVC debug heap",
+        value: 0xcdcdcdcd,
+    },
+    PoisonPattern {
+        kind: "msvc_heap_guard_poison",
+        description: "guard bytes around an MSVC debug heap allocation (buffer overflow)",
+        value: 0xfdfdfdfd,
+    },
+    PoisonPattern {
+        kind: "msvc_uninitialized_stack_poison",
+        description: "uninitialized stack memory from MSVC's /RTC checks",
+        value: 0xcccccccc,
+    },
+    PoisonPattern {
+        kind: "windows_freed_heap_poison",
+        description: "freed memory from the Windows debug heap (use-after-free)",
+        value: 0xfeeefeee,
+    },
+    PoisonPattern {
+        kind: "windows_uninitialized_heap_poison",
+        description: "uninitialized memory from the Windows debug heap",
+        value: 0xbaadf00d,
+    },
+    PoisonPattern {
+        kind: "mozjemalloc_freed_poison",
+        description: "freed memory from mozjemalloc (use-after-free)",
+        value: 0xe5e5e5e5,
+    },
+    PoisonPattern {
+        kind: "jemalloc_freed_poison",
+        description: "freed memory from jemalloc (use-after-free), or an uninitialized Linux kernel slab object",
+        value: 0x5a5a5a5a,
+    },
+    PoisonPattern {
+        kind: "jemalloc_uninitialized_poison",
+        description: "uninitialized memory from jemalloc",
+        value: 0xa5a5a5a5,
+    },
+    PoisonPattern {
+        kind: "asan_uninitialized_poison",
+        description: "uninitialized memory from AddressSanitizer's allocator",
+        value: 0xbebebebe,
+    },
+    PoisonPattern {
+        kind: "kernel_slab_freed_poison",
+        description: "a freed Linux kernel slab object (use-after-free)",
+        value: 0x6b6b6b6b,
+    },
+    // x86-64's LIST_POISON1, which LIST_POISON2 (0x122 past it) also matches.
+    PoisonPattern {
+        kind: "kernel_list_poison",
+        description: "a pointer from a deleted Linux kernel list entry (use-after-free)",
+        value: 0xdead000000000100,
+    },
+];
+
+/// The largest offset from a pattern that still counts as matching it.
+///
+/// Pointers loaded from poisoned memory usually crash on a field access, a
+/// little past the pattern itself.
+pub const MAX_POISON_OFFSET: u64 = 0x1000;
+
+/// The [`PoisonPattern`] that `value` matches, if any.
+///
+/// `value` matches a pattern if it's at most [`MAX_POISON_OFFSET`] past it.
+/// Patterns that fit in 32 bits only need to match the low 32 bits of `value`.
+pub fn poison_pattern(value: u64) -> Option<&'static PoisonPattern> {
+    POISON_PATTERNS.iter().find(|pattern| {
+        let value = if pattern.value > u64::from(u32::MAX) {
+            value
+        } else {
+            value & u64::from(u32::MAX)
+        };
+        matches!(value.checked_sub(pattern.value), Some(offset) if offset <= MAX_POISON_OFFSET)
+    })
+}
@@ -2,7 +2,7 @@
 use libfuzzer_sys::fuzz_target;
 
 use minidump::system_info::{Cpu, Os};
-use minidump::{MinidumpContext, MinidumpContextValidity, MinidumpMemory};
+use minidump::{MinidumpContext, MinidumpContextValidity, MinidumpMemory, UnifiedMemory};
 use minidump::{MinidumpModule, MinidumpModuleList};
 use minidump_processor::walk_stack;
 use minidump_processor::{string_symbol_supplier, CallStack, ProcessorOptions,  Symbolizer, SystemInfo};
@@ -50,9 +50,13 @@ impl TestFixture {
             os_version: None,
             os_build: None,
             cpu: Cpu::X86_64,
+            endian: minidump::Endian::Little,
             cpu_info: None,
             cpu_microcode_version: None,
+            cpu_features: None,
             cpu_count: 1,
+            available_physical_memory: None,
+            commit_limit: None,
         };
 
         let symbolizer = Symbolizer::new(string_symbol_supplier(self.symbols.clone()));
@@ -63,7 +67,7 @@ impl TestFixture {
             0,
             &options,
             &mut stack,
-            Some(&stack_memory),
+            Some(&UnifiedMemory::Memory(&stack_memory)),
             &self.modules,
             &system_info,
             &symbolizer,
@@ -1,5 +1,6 @@
-use crate::{CallStack, CallingConvention, FunctionArg, FunctionArgs};
-use minidump::{CpuContext, MinidumpMemory, MinidumpRawContext};
+use crate::{CallStack, CallingConvention, FrameTrust, FunctionArg, FunctionArgs};
+use minidump::system_info::Os;
+use minidump::{CpuContext, MinidumpRawContext, UnifiedMemory};
 
 // # Recovering x86 function arguments
 //
@@ -65,9 +66,35 @@ use minidump::{CpuContext, MinidumpMemory, MinidumpRawContext};
 // we just unconditionally assume all arguments are pointer-sized. This is intuitively true
 // most of the time. The major exceptions are `bool` and `uint64_t`. Maybe those
 // are worth carving out special cases for, but until then: it's all pointers!
+//
+//
+//
+// # Recovering x86-64 function arguments
+//
+// x86-64 passes the first few integer arguments in registers, which callers
+// don't bother preserving. Even the first frame's registers may well have been
+// reused since the call, so they're never reported as arguments. The arguments
+// after those are passed on the stack though, at fixed offsets from the stack
+// pointer the caller had when it made the call:
+//
+// * System V (everything but Windows): the first 6 arguments are passed in
+//   rdi, rsi, rdx, rcx, r8, and r9, and the 7th argument is at the caller's
+//   stack pointer.
+// * Windows: the first 4 arguments are passed in rcx, rdx, r8, and r9, and
+//   the caller reserves 32 bytes of "home space" for them on the stack,
+//   so the 5th argument is 32 bytes above the caller's stack pointer.
+//
+// The number of arguments comes from the function name as above, with "this"
+// taking the first register of member functions. If the name doesn't have
+// an argument list, the symbol file's parameter size (the size of the
+// arguments passed on the stack) is used to find the stack-passed ones.
+//
+// Only frames with a stack-passed argument get arguments, and only if the
+// caller's stack pointer was recovered by something better than stack
+// scanning, since the offsets are meaningless otherwise.
 
 /// Try to recover function arguments
-pub fn fill_arguments(call_stack: &mut CallStack, stack_memory: Option<&MinidumpMemory>) {
+pub fn fill_arguments(call_stack: &mut CallStack, stack_memory: Option<&UnifiedMemory>, os: Os) {
     // Collect up all the results at once to avoid borrowing issues.
     let args = call_stack
         .frames
@@ -91,7 +118,7 @@ pub fn fill_arguments(call_stack: &mut CallStack, stack_memory: Option<&Minidump
                     // is actually the base of the stack. Since we're walking down
                     // the stack, the base of the stack is a good upper-bound
                     // (and default value) for any stack/frame pointer.
-                    let stack_base = mem.base_address.saturating_add(mem.size);
+                    let stack_base = mem.base_address().saturating_add(mem.size());
 
                     let caller_stack_pointer = call_stack
                         .frames
@@ -140,6 +167,9 @@ pub fn fill_arguments(call_stack: &mut CallStack, stack_memory: Option<&Minidump
                         CallingConvention::Cdecl => {
                             // Nothing to do
                         }
+                        CallingConvention::Amd64SystemV | CallingConvention::Amd64Windows => {
+                            return None;
+                        }
                     }
 
                     // Now handle the rest
@@ -157,6 +187,11 @@ pub fn fill_arguments(call_stack: &mut CallStack, stack_memory: Option<&Minidump
                     });
                 }
             }
+            if let (Some(mem), Some(func_name), MinidumpRawContext::Amd64(_)) =
+                (stack_memory, &frame.function_name, &frame.context.raw)
+            {
+                return amd64_arguments(call_stack, frame_idx, mem, func_name, os);
+            }
             None
         })
         .collect::<Vec<_>>();
@@ -167,6 +202,93 @@ pub fn fill_arguments(call_stack: &mut CallStack, stack_memory: Option<&Minidump
     }
 }
 
+fn amd64_arguments(
+    call_stack: &CallStack,
+    frame_idx: usize,
+    mem: &UnifiedMemory,
+    func_name: &str,
+    os: Os,
+) -> Option<FunctionArgs> {
+    const POINTER_WIDTH: u64 = 8;
+
+    let frame = &call_stack.frames[frame_idx];
+    // The stack-passed arguments are only where we expect if we really know
+    // where the caller's stack pointer was.
+    let caller = call_stack.frames.get(frame_idx + 1)?;
+    if caller.trust <= FrameTrust::CfiScan {
+        return None;
+    }
+
+    let (calling_convention, registers, home_space): (_, &[&str], u64) = if os == Os::Windows {
+        (
+            CallingConvention::Amd64Windows,
+            &["rcx", "rdx", "r8", "r9"],
+            4 * POINTER_WIDTH,
+        )
+    } else {
+        (
+            CallingConvention::Amd64SystemV,
+            &["rdi", "rsi", "rdx", "rcx", "r8", "r9"],
+            0,
+        )
+    };
+
+    let names = match parse_x86_arg_list(func_name) {
+        Some((cc, argument_list)) => {
+            let this = match cc {
+                CallingConvention::Cdecl => None,
+                _ => Some("this"),
+            };
+            this.into_iter()
+                .chain(argument_list)
+                .map(String::from)
+                .collect::<Vec<_>>()
+        }
+        None => {
+            let stack_args = frame.parameter_size? as u64 / POINTER_WIDTH;
+            registers
+                .iter()
+                .map(|&reg| String::from(reg))
+                .chain((0..stack_args).map(|_| String::from("stack")))
+                .collect()
+        }
+    };
+    if names.len() <= registers.len() {
+        // Everything was passed in registers, so there's nothing to recover.
+        return None;
+    }
+
+    let stack_base = mem.base_address().saturating_add(mem.size());
+    let caller_stack_pointer = caller.context.get_stack_pointer();
+    // The caller's frame ends where its own caller's stack pointer was.
+    let caller_frame_end = call_stack
+        .frames
+        .get(frame_idx + 2)
+        .map(|f| f.context.get_stack_pointer())
+        .unwrap_or(stack_base);
+
+    let args = names
+        .into_iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            // Register-passed arguments are unknown (see above).
+            let value = idx.checked_sub(registers.len()).and_then(|stack_idx| {
+                let offset = home_space + stack_idx as u64 * POINTER_WIDTH;
+                caller_stack_pointer
+                    .checked_add(offset)
+                    .filter(|&addr| addr < caller_frame_end)
+                    .and_then(|addr| mem.get_memory_at_address::<u64>(addr))
+            });
+            FunctionArg { name, value }
+        })
+        .collect();
+
+    Some(FunctionArgs {
+        calling_convention,
+        args,
+    })
+}
+
 fn parse_x86_arg_list(func_name: &str) -> Option<(CallingConvention, Vec<&str>)> {
     if let Some((func_name, arg_list)) = func_name.split_once('(') {
         if let Some((arg_list, _junk)) = arg_list.rsplit_once(')') {
@@ -233,3 +355,108 @@ fn parse_x86_arg_list(func_name: &str) -> Option<(CallingConvention, Vec<&str>)>
     }
     None
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CallStackInfo, StackFrame};
+    use minidump::format::{CONTEXT_AMD64, MINIDUMP_MEMORY_DESCRIPTOR};
+    use minidump::{MinidumpContext, MinidumpMemory};
+
+    const STACK_BASE: u64 = 0x8000;
+
+    /// A stack whose context frame is in `func_name`, and whose callers have
+    /// the given trust and stack pointers.
+    fn stack(func_name: &str, callers: &[(FrameTrust, u64)]) -> CallStack {
+        let frame = |trust, sp| {
+            let raw = CONTEXT_AMD64 {
+                rsp: sp,
+                rdi: 0x1d1,
+                rcx: 0xc1c,
+                ..CONTEXT_AMD64::default()
+            };
+            StackFrame::from_context(
+                MinidumpContext::from_raw(MinidumpRawContext::Amd64(raw)),
+                trust,
+            )
+        };
+        let mut stack = CallStack::with_info(1, CallStackInfo::Ok);
+        let mut context_frame = frame(FrameTrust::Context, STACK_BASE);
+        context_frame.function_name = Some(String::from(func_name));
+        stack.frames.push(context_frame);
+        for &(trust, sp) in callers {
+            stack.frames.push(frame(trust, sp));
+        }
+        stack
+    }
+
+    /// Each word of the stack holds its own offset from the base.
+    fn stack_bytes() -> Vec<u8> {
+        (0..0x20u64).flat_map(|i| (i * 8).to_le_bytes()).collect()
+    }
+
+    fn values(stack: &CallStack) -> Vec<Option<u64>> {
+        let args = stack.frames[0].arguments.as_ref().unwrap();
+        args.args.iter().map(|arg| arg.value).collect()
+    }
+
+    #[test]
+    fn test_amd64_stack_arguments() {
+        let bytes = stack_bytes();
+        let memory = MinidumpMemory {
+            desc: MINIDUMP_MEMORY_DESCRIPTOR::default(),
+            base_address: STACK_BASE,
+            size: bytes.len() as u64,
+            bytes: &bytes,
+            endian: scroll::LE,
+        };
+        let mem = UnifiedMemory::Memory(&memory);
+        let callers = [
+            (FrameTrust::CallFrameInfo, STACK_BASE + 0x10),
+            (FrameTrust::FramePointer, STACK_BASE + 0x80),
+        ];
+        let func_name = "f(int, int, int, int, int, int, long, void*)";
+
+        let mut linux = stack(func_name, &callers);
+        fill_arguments(&mut linux, Some(&mem), Os::Linux);
+        let args = linux.frames[0].arguments.as_ref().unwrap();
+        assert!(matches!(
+            args.calling_convention,
+            CallingConvention::Amd64SystemV
+        ));
+        assert_eq!(args.args[6].name, "long");
+        // The context has values for rdi and rcx, but they may not be the
+        // arguments anymore.
+        assert_eq!(
+            values(&linux),
+            vec![None, None, None, None, None, None, Some(0x10), Some(0x18)]
+        );
+
+        // Windows has fewer argument registers, and home space for them.
+        let mut windows = stack(func_name, &callers);
+        fill_arguments(&mut windows, Some(&mem), Os::Windows);
+        assert_eq!(
+            values(&windows)[3..],
+            [None, Some(0x30), Some(0x38), Some(0x40), Some(0x48)]
+        );
+
+        // Without an argument list, the parameter size says how many there are.
+        let mut sized = stack("g", &callers);
+        sized.frames[0].parameter_size = Some(16);
+        fill_arguments(&mut sized, Some(&mem), Os::Linux);
+        let args = sized.frames[0].arguments.as_ref().unwrap();
+        assert_eq!(args.args[0].name, "rdi");
+        assert_eq!(args.args[6].name, "stack");
+        assert_eq!(values(&sized)[6..], [Some(0x10), Some(0x18)]);
+
+        // Nothing on the stack, so nothing to recover.
+        let mut registers_only = stack("h(int, int)", &callers);
+        fill_arguments(&mut registers_only, Some(&mem), Os::Linux);
+        assert!(registers_only.frames[0].arguments.is_none());
+
+        // A scanned caller's stack pointer can't be trusted.
+        let mut scanned = stack(func_name, &[(FrameTrust::Scan, STACK_BASE + 0x10)]);
+        fill_arguments(&mut scanned, Some(&mem), Os::Linux);
+        assert!(scanned.frames[0].arguments.is_none());
+    }
+}
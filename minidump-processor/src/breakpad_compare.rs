@@ -0,0 +1,328 @@
+//! Comparing our stacks against the ones breakpad's `minidump_stackwalk` produces.
+//!
+//! This is a validation tool for anyone migrating from breakpad: run both
+//! processors on the same minidump and symbols, and get a structured list of
+//! every place the stacks disagree. Breakpad is driven through its
+//! machine-readable (`-m`) output, which is stable and easy to parse.
+//!
+//! Frames are compared by module, function, source file and line. The
+//! offsets breakpad prints are kept for reference but are *not* compared,
+//! since they depend on exactly which address each processor reports for
+//! caller frames.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+use minidump::Module;
+use minidump_common::utils::basename;
+
+use crate::process_state::{ProcessState, StackFrame};
+
+/// An error encountered while getting stacks from breakpad's `minidump_stackwalk`.
+#[derive(Debug, thiserror::Error)]
+pub enum BreakpadCompareError {
+    #[error("Failed to run minidump_stackwalk")]
+    SpawnError(#[from] io::Error),
+    #[error("minidump_stackwalk failed ({0})")]
+    StackwalkFailed(ExitStatus),
+}
+
+impl BreakpadCompareError {
+    /// Returns just the name of the error, as a more human-friendly version of
+    /// an error-code for error logging.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BreakpadCompareError::SpawnError(_) => "SpawnError",
+            BreakpadCompareError::StackwalkFailed(_) => "StackwalkFailed",
+        }
+    }
+}
+
+/// The parts of a stack frame that breakpad and rust-minidump can be compared on.
+///
+/// This is what breakpad's machine-readable output contains for each frame,
+/// and can be computed for one of our frames with [`BreakpadFrame::from_frame`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BreakpadFrame {
+    /// The file name of the module's code file.
+    pub module: Option<String>,
+    /// The name of the function.
+    pub function: Option<String>,
+    /// The file name of the source file.
+    pub source_file: Option<String>,
+    /// The line number in the source file.
+    pub source_line: Option<u32>,
+    /// The offset from the start of the source line, function, or module (in
+    /// that order of preference, depending on what's known), or the absolute
+    /// address if none of those are.
+    pub offset: u64,
+}
+
+impl BreakpadFrame {
+    /// Summarize one of our frames the way breakpad would print it.
+    pub fn from_frame(frame: &StackFrame) -> BreakpadFrame {
+        let addr = frame.instruction;
+        let mut result = BreakpadFrame {
+            offset: addr,
+            ..BreakpadFrame::default()
+        };
+        let module = match &frame.module {
+            Some(module) => module,
+            None => return result,
+        };
+        result.module = Some(basename(&module.code_file()).to_owned());
+        result.offset = addr.wrapping_sub(module.base_address());
+
+        if let (Some(func_name), Some(func_base)) = (&frame.function_name, frame.function_base) {
+            result.function = Some(func_name.clone());
+            result.offset = addr.wrapping_sub(func_base);
+            if let (Some(src_file), Some(src_line), Some(src_base)) = (
+                &frame.source_file_name,
+                frame.source_line,
+                frame.source_line_base,
+            ) {
+                result.source_file = Some(basename(src_file).to_owned());
+                result.source_line = Some(src_line);
+                result.offset = addr.wrapping_sub(src_base);
+            }
+        }
+        result
+    }
+
+    /// Whether two frames refer to the same place in the program (ignoring offsets).
+    pub fn same_location(&self, other: &BreakpadFrame) -> bool {
+        self.module == other.module
+            && self.function == other.function
+            && self.source_file == other.source_file
+            && self.source_line == other.source_line
+    }
+
+    fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        match &self.module {
+            Some(module) => write!(f, "{}", module)?,
+            None => write!(f, "{:#x}", self.offset)?,
+        }
+        if let Some(function) = &self.function {
+            write!(f, "!{}", function)?;
+        }
+        if let (Some(file), Some(line)) = (&self.source_file, self.source_line) {
+            write!(f, " [{} : {}]", file, line)?;
+        }
+        Ok(())
+    }
+}
+
+/// The stacks breakpad's `minidump_stackwalk` produced for a minidump.
+#[derive(Clone, Debug, Default)]
+pub struct BreakpadStacks {
+    /// The index of the thread breakpad considered to have crashed.
+    pub crashing_thread: Option<usize>,
+    /// The frames of each thread, indexed like [`ProcessState::threads`].
+    pub threads: Vec<Vec<BreakpadFrame>>,
+}
+
+impl BreakpadStacks {
+    /// Run breakpad's `minidump_stackwalk` at `stackwalk` on `minidump`,
+    /// with the given symbol directories, and parse its output.
+    pub fn from_stackwalk(
+        stackwalk: &Path,
+        minidump: &Path,
+        symbol_paths: &[PathBuf],
+    ) -> Result<BreakpadStacks, BreakpadCompareError> {
+        let output = Command::new(stackwalk)
+            .arg("-m")
+            .arg(minidump)
+            .args(symbol_paths)
+            .output()?;
+        if !output.status.success() {
+            return Err(BreakpadCompareError::StackwalkFailed(output.status));
+        }
+        Ok(BreakpadStacks::parse(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Parse the machine-readable (`-m`) output of breakpad's `minidump_stackwalk`.
+    ///
+    /// Lines that aren't a crash record or a stack frame are ignored.
+    pub fn parse(output: &str) -> BreakpadStacks {
+        let mut crashing_thread = None;
+        let mut threads = BTreeMap::<usize, Vec<BreakpadFrame>>::new();
+
+        for line in output.lines() {
+            let fields = line.split('|').collect::<Vec<_>>();
+            if fields[0] == "Crash" {
+                crashing_thread = fields.get(3).and_then(|idx| idx.parse().ok());
+                continue;
+            }
+            // thread_index|frame_index|module|function|source_file|source_line|offset
+            if fields.len() != 7 {
+                continue;
+            }
+            let (thread_idx, frame_idx) = match (fields[0].parse(), fields[1].parse::<usize>()) {
+                (Ok(thread_idx), Ok(frame_idx)) => (thread_idx, frame_idx),
+                _ => continue,
+            };
+            let non_empty = |field: &str| Some(field.to_owned()).filter(|f| !f.is_empty());
+            let frame = BreakpadFrame {
+                module: non_empty(fields[2]),
+                function: non_empty(fields[3]),
+                source_file: non_empty(fields[4]).map(|file| basename(&file).to_owned()),
+                source_line: fields[5].parse().ok(),
+                offset: fields[6]
+                    .strip_prefix("0x")
+                    .and_then(|offset| u64::from_str_radix(offset, 16).ok())
+                    .unwrap_or(0),
+            };
+            let frames = threads.entry(thread_idx).or_default();
+            if frames.len() == frame_idx {
+                frames.push(frame);
+            }
+        }
+
+        let thread_count = threads.keys().next_back().map_or(0, |&idx| idx + 1);
+        BreakpadStacks {
+            crashing_thread,
+            threads: (0..thread_count)
+                .map(|idx| threads.remove(&idx).unwrap_or_default())
+                .collect(),
+        }
+    }
+}
+
+/// A frame where the two processors disagree.
+#[derive(Clone, Debug)]
+pub struct FrameMismatch {
+    /// The index of the frame in the thread.
+    pub frame_index: usize,
+    /// Our frame, if our stack was this long.
+    pub ours: Option<BreakpadFrame>,
+    /// Breakpad's frame, if its stack was this long.
+    pub breakpad: Option<BreakpadFrame>,
+}
+
+/// The comparison of one thread's stacks.
+#[derive(Clone, Debug)]
+pub struct ThreadComparison {
+    /// The index of the thread, as in [`ProcessState::threads`].
+    pub thread_index: usize,
+    pub our_frame_count: usize,
+    pub breakpad_frame_count: usize,
+    /// Every frame where the stacks disagree, in order.
+    pub mismatches: Vec<FrameMismatch>,
+}
+
+/// A structured diff of our stacks against breakpad's.
+#[derive(Clone, Debug)]
+pub struct StackComparison {
+    pub our_crashing_thread: Option<usize>,
+    pub breakpad_crashing_thread: Option<usize>,
+    /// The comparison of every thread either processor reported.
+    pub threads: Vec<ThreadComparison>,
+}
+
+impl StackComparison {
+    /// Whether the two processors produced the same stacks.
+    pub fn is_match(&self) -> bool {
+        self.our_crashing_thread == self.breakpad_crashing_thread
+            && self
+                .threads
+                .iter()
+                .all(|thread| thread.mismatches.is_empty())
+    }
+
+    /// Write a human-readable description of the differences to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        if self.is_match() {
+            return writeln!(f, "Stacks match breakpad");
+        }
+        if self.our_crashing_thread != self.breakpad_crashing_thread {
+            writeln!(
+                f,
+                "Crashing thread: {:?} (breakpad: {:?})",
+                self.our_crashing_thread, self.breakpad_crashing_thread
+            )?;
+        }
+        for thread in &self.threads {
+            if thread.mismatches.is_empty() {
+                continue;
+            }
+            writeln!(
+                f,
+                "Thread {}: {} frames (breakpad: {})",
+                thread.thread_index, thread.our_frame_count, thread.breakpad_frame_count
+            )?;
+            for mismatch in &thread.mismatches {
+                write!(f, "{:4}  ", mismatch.frame_index)?;
+                match &mismatch.ours {
+                    Some(frame) => frame.print(f)?,
+                    None => write!(f, "<none>")?,
+                }
+                write!(f, "\n      breakpad: ")?;
+                match &mismatch.breakpad {
+                    Some(frame) => frame.print(f)?,
+                    None => write!(f, "<none>")?,
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare the stacks in `state` against the ones breakpad produced for the same minidump.
+///
+/// Inline frames are ignored, as breakpad doesn't report them.
+pub fn compare_with_breakpad(state: &ProcessState, breakpad: &BreakpadStacks) -> StackComparison {
+    let thread_count = state.threads.len().max(breakpad.threads.len());
+    let threads = (0..thread_count)
+        .map(|thread_index| {
+            let ours = state
+                .threads
+                .get(thread_index)
+                .map(|stack| {
+                    stack
+                        .frames
+                        .iter()
+                        .map(BreakpadFrame::from_frame)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let theirs = breakpad
+                .threads
+                .get(thread_index)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+
+            let mismatches = (0..ours.len().max(theirs.len()))
+                .filter_map(|frame_index| {
+                    let our_frame = ours.get(frame_index);
+                    let their_frame = theirs.get(frame_index);
+                    match (our_frame, their_frame) {
+                        (Some(a), Some(b)) if a.same_location(b) => None,
+                        _ => Some(FrameMismatch {
+                            frame_index,
+                            ours: our_frame.cloned(),
+                            breakpad: their_frame.cloned(),
+                        }),
+                    }
+                })
+                .collect();
+
+            ThreadComparison {
+                thread_index,
+                our_frame_count: ours.len(),
+                breakpad_frame_count: theirs.len(),
+                mismatches,
+            }
+        })
+        .collect();
+
+    StackComparison {
+        our_crashing_thread: state.requesting_thread,
+        breakpad_crashing_thread: breakpad.crashing_thread,
+        threads,
+    }
+}
@@ -0,0 +1,106 @@
+//! Grouping many crashes by a signature, to see which ones are the same.
+//!
+//! A crash signature is a short string that crashes with the same cause
+//! should share. There's no perfect recipe for one, so [`group_by_signature`]
+//! takes the signature function as an argument, and [`crash_signature`] is
+//! just a reasonable default.
+
+use std::collections::BTreeMap;
+
+use minidump::Module;
+use minidump_common::utils::basename;
+
+use crate::process_state::{ProcessState, StackFrame};
+
+/// How many frames of the crashing thread [`crash_signature`] uses.
+const SIGNATURE_FRAMES: usize = 3;
+
+/// The crashes that share a signature, from [`group_by_signature`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureGroup<Id> {
+    /// How many crashes had this signature.
+    pub count: usize,
+    /// The ids of the first few crashes with this signature.
+    pub examples: Vec<Id>,
+}
+
+/// A default crash signature: the crash reason, followed by the innermost
+/// few frames of the crashing thread.
+///
+/// Frames are described by their (normalized, if available) function name,
+/// or by their module and module offset if they weren't symbolicated.
+/// For example:
+///
+/// ```text
+/// EXCEPTION_ACCESS_VIOLATION_WRITE | CrashFunction() | main | test_app.exe@0x1234
+/// ```
+pub fn crash_signature(state: &ProcessState) -> String {
+    let reason = state
+        .exception_info
+        .as_ref()
+        .map(|info| info.reason.to_string())
+        .unwrap_or_else(|| String::from("No crash"));
+
+    let frames = match state.crashing_thread() {
+        Some(stack) if !stack.frames.is_empty() => stack
+            .frames
+            .iter()
+            .take(SIGNATURE_FRAMES)
+            .map(describe_frame)
+            .collect::<Vec<_>>(),
+        _ => vec![String::from("<no crashing thread>")],
+    };
+    format!("{} | {}", reason, frames.join(" | "))
+}
+
+fn describe_frame(frame: &StackFrame) -> String {
+    if let Some(name) = frame
+        .normalized_function_name
+        .as_ref()
+        .or(frame.function_name.as_ref())
+    {
+        return name.clone();
+    }
+    match &frame.module {
+        Some(module) => format!(
+            "{}@{:#x}",
+            basename(&module.code_file()),
+            frame.instruction - module.base_address()
+        ),
+        None => format!("@{:#x}", frame.instruction),
+    }
+}
+
+/// Group `states` by the signature `signature` computes for each of them.
+///
+/// Each state comes with an id (like a file name or crash id) that identifies
+/// it in the output. Every signature's group counts how many states had it,
+/// and keeps the ids of the first `max_examples` of them.
+///
+/// [`crash_signature`] is a reasonable default signature, but anything that
+/// can be sorted will do: the OS and crashing module, say.
+pub fn group_by_signature<'a, Id, K, I, F>(
+    states: I,
+    max_examples: usize,
+    mut signature: F,
+) -> BTreeMap<K, SignatureGroup<Id>>
+where
+    I: IntoIterator<Item = (Id, &'a ProcessState)>,
+    F: FnMut(&ProcessState) -> K,
+    K: Ord,
+{
+    let mut groups = BTreeMap::new();
+    for (id, state) in states {
+        let group = groups
+            .entry(signature(state))
+            .or_insert_with(|| SignatureGroup {
+                count: 0,
+                examples: Vec::new(),
+            });
+        group.count += 1;
+        if group.examples.len() < max_examples {
+            group.examples.push(id);
+        }
+    }
+    groups
+}
@@ -7,16 +7,43 @@ use std::path::Path;
 use std::str::FromStr;
 use tracing::error;
 
-/// Things extracted from the Evil JSON File
+use crate::ExtraInfoProvider;
+
+/// **\[UNSTABLE\]** An [`ExtraInfoProvider`] for the evil "raw json"
+/// mozilla's legacy infrastructure relies on.
+///
+/// This provides module certificates (from its `ModuleSignatureInfo`) and
+/// thread names (from its `ThreadIdNameMapping`).
+/// [`ProcessorOptions::evil_json`][crate::ProcessorOptions::evil_json] is a
+/// shorthand for adding one of these.
 #[derive(Debug, Default)]
-pub(crate) struct Evil {
+pub struct EvilJsonProvider {
     /// module name => cert
-    pub certs: HashMap<String, String>,
+    certs: HashMap<String, String>,
     /// thread id => thread name
-    pub thread_names: HashMap<u32, String>,
+    thread_names: HashMap<u32, String>,
+}
+
+impl ExtraInfoProvider for EvilJsonProvider {
+    fn module_cert(&self, module_name: &str) -> Option<String> {
+        self.certs.get(module_name).cloned()
+    }
+
+    fn thread_name(&self, thread_id: u32) -> Option<String> {
+        self.thread_names.get(&thread_id).cloned()
+    }
+}
+
+impl EvilJsonProvider {
+    /// Read the evil json file at `evil_path`.
+    ///
+    /// Returns `None` (and logs why) if it can't be read or isn't valid JSON.
+    pub fn from_path(evil_path: &Path) -> Option<EvilJsonProvider> {
+        handle_evil(evil_path)
+    }
 }
 
-pub(crate) fn handle_evil(evil_path: &Path) -> Option<Evil> {
+fn handle_evil(evil_path: &Path) -> Option<EvilJsonProvider> {
     // Get the evil json
     let evil_json = File::open(evil_path)
         .map_err(|e| {
@@ -96,7 +123,7 @@ pub(crate) fn handle_evil(evil_path: &Path) -> Option<Evil> {
         })
         .collect();
 
-    Some(Evil {
+    Some(EvilJsonProvider {
         certs,
         thread_names,
     })
@@ -0,0 +1,344 @@
+//! Rating how likely a crash is to be exploitable.
+//!
+//! This is the same idea as Breakpad's exploitability engines: look at the
+//! exception and the crashing thread's registers for the signs that an
+//! attacker could have been steering the crash, and rate it by the worst
+//! one. Each sign found is a [`ExploitabilitySignal`], and the rating of
+//! the crash is the highest rating of its signals.
+//!
+//! Accesses to addresses near null (below the processor's
+//! [`null_deref_threshold`][crate::ProcessorOptions::null_deref_threshold])
+//! are ignored, as those are almost always plain null pointer dereferences.
+//!
+//! This is a heuristic for triage, not a verdict: a crash with no signals
+//! can still be exploitable, and plenty of crashes with signals aren't.
+
+use std::ops::Range;
+
+use minidump::{CrashReason, MinidumpContext, MinidumpRawContext, UnifiedMemoryList};
+use minidump_common::errors::{ExceptionCodeWindows, ExceptionCodeWindowsAccessType, FastFailCode};
+
+use crate::process_state::ProcessState;
+
+/// How likely a crash is to be exploitable.
+///
+/// These are ordered from least to most concerning, so ratings can be compared.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExploitabilityRating {
+    /// Nothing suggests the crash is exploitable.
+    None,
+    /// Probably not exploitable, but it isn't a null pointer dereference either.
+    Low,
+    /// Not necessarily exploitable, but weird enough to be worth a closer look.
+    Interesting,
+    /// Possibly exploitable.
+    Medium,
+    /// Likely exploitable.
+    High,
+}
+
+impl ExploitabilityRating {
+    /// The name of this rating in the JSON output, like `"high"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExploitabilityRating::None => "none",
+            ExploitabilityRating::Low => "low",
+            ExploitabilityRating::Interesting => "interesting",
+            ExploitabilityRating::Medium => "medium",
+            ExploitabilityRating::High => "high",
+        }
+    }
+}
+
+/// A sign that a crash might be exploitable, from [`ExploitabilityAnalysis::signals`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExploitabilitySignal {
+    /// The crash was a write to an address that isn't near null.
+    WriteAccessViolation,
+    /// The crash was executing an address that isn't near null, either
+    /// because nothing was mapped there or because it isn't executable.
+    ExecuteAccessViolation,
+    /// The crash was a read (or an access of unknown kind) of an address
+    /// that isn't near null.
+    ReadAccessViolation,
+    /// The instruction pointer isn't in any module.
+    PcOutsideModules,
+    /// The instruction pointer is in the crashing thread's stack.
+    PcOnStack,
+    /// The crash address is the value of this register, and that value looks
+    /// like data (repeated bytes or text) rather than a pointer.
+    CrashAddressLooksLikeData {
+        /// The name of the register.
+        register: &'static str,
+    },
+    /// A stack buffer overrun was caught by a stack cookie check.
+    StackBufferOverrun,
+    /// An illegal instruction in a module, but not an intentional trap like
+    /// `ud2`, which suggests a jump into the middle of an instruction.
+    IllegalInstruction,
+}
+
+impl ExploitabilitySignal {
+    /// How concerning this signal is on its own.
+    pub fn rating(&self) -> ExploitabilityRating {
+        match self {
+            ExploitabilitySignal::WriteAccessViolation
+            | ExploitabilitySignal::ExecuteAccessViolation
+            | ExploitabilitySignal::PcOutsideModules
+            | ExploitabilitySignal::PcOnStack
+            | ExploitabilitySignal::StackBufferOverrun => ExploitabilityRating::High,
+            ExploitabilitySignal::CrashAddressLooksLikeData { .. } => ExploitabilityRating::Medium,
+            ExploitabilitySignal::IllegalInstruction => ExploitabilityRating::Interesting,
+            ExploitabilitySignal::ReadAccessViolation => ExploitabilityRating::Low,
+        }
+    }
+
+    /// The name of this signal in the JSON output, like `"write_access_violation"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExploitabilitySignal::WriteAccessViolation => "write_access_violation",
+            ExploitabilitySignal::ExecuteAccessViolation => "execute_access_violation",
+            ExploitabilitySignal::ReadAccessViolation => "read_access_violation",
+            ExploitabilitySignal::PcOutsideModules => "pc_outside_modules",
+            ExploitabilitySignal::PcOnStack => "pc_on_stack",
+            ExploitabilitySignal::CrashAddressLooksLikeData { .. } => {
+                "crash_address_looks_like_data"
+            }
+            ExploitabilitySignal::StackBufferOverrun => "stack_buffer_overrun",
+            ExploitabilitySignal::IllegalInstruction => "illegal_instruction",
+        }
+    }
+
+    /// A description of this signal for the human output.
+    pub fn description(&self) -> String {
+        match self {
+            ExploitabilitySignal::WriteAccessViolation => {
+                String::from("write to an address that isn't near null")
+            }
+            ExploitabilitySignal::ExecuteAccessViolation => {
+                String::from("execution of an address that isn't near null")
+            }
+            ExploitabilitySignal::ReadAccessViolation => {
+                String::from("read of an address that isn't near null")
+            }
+            ExploitabilitySignal::PcOutsideModules => {
+                String::from("instruction pointer isn't in any module")
+            }
+            ExploitabilitySignal::PcOnStack => String::from("instruction pointer is on the stack"),
+            ExploitabilitySignal::CrashAddressLooksLikeData { register } => {
+                format!(
+                    "crash address is the value of {}, which looks like data",
+                    register
+                )
+            }
+            ExploitabilitySignal::StackBufferOverrun => String::from("stack buffer overrun"),
+            ExploitabilitySignal::IllegalInstruction => {
+                String::from("illegal instruction in a module")
+            }
+        }
+    }
+}
+
+/// How likely the crash is to be exploitable.
+///
+/// See [`ProcessorOptions::analyze_exploitability`][crate::ProcessorOptions::analyze_exploitability].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExploitabilityAnalysis {
+    /// The highest rating of any of the signals, or
+    /// [`ExploitabilityRating::None`] if there aren't any.
+    pub rating: ExploitabilityRating,
+    /// Every sign of exploitability that was found.
+    pub signals: Vec<ExploitabilitySignal>,
+}
+
+impl ExploitabilityAnalysis {
+    /// Rate the crash in `state`.
+    ///
+    /// `stack_range` is the address range of the crashing thread's stack, if
+    /// known, `near_null` the address below which accesses are taken for null
+    /// pointer dereferences (see
+    /// [`ProcessorOptions::null_deref_threshold`][crate::ProcessorOptions::null_deref_threshold]),
+    /// and `memory_list` is used to look at the crashing instruction.
+    ///
+    /// Returns `None` if there's no exception or crashing thread to analyze.
+    pub fn from_state(
+        state: &ProcessState,
+        stack_range: Option<Range<u64>>,
+        near_null: u64,
+        memory_list: &UnifiedMemoryList,
+    ) -> Option<ExploitabilityAnalysis> {
+        let exception = state.exception_info.as_ref()?;
+        let context = &state.crashing_thread()?.frames.first()?.context;
+        let pc = context.get_instruction_pointer();
+        let address = exception.address;
+
+        let mut signals = Vec::new();
+        if let Some(access) = access_violation(exception.reason) {
+            if address >= near_null {
+                signals.push(match access {
+                    Some(ExceptionCodeWindowsAccessType::WRITE) => {
+                        ExploitabilitySignal::WriteAccessViolation
+                    }
+                    Some(ExceptionCodeWindowsAccessType::EXEC) => {
+                        ExploitabilitySignal::ExecuteAccessViolation
+                    }
+                    // Faulting on the instruction pointer means it was executing it.
+                    _ if address == pc => ExploitabilitySignal::ExecuteAccessViolation,
+                    _ => ExploitabilitySignal::ReadAccessViolation,
+                });
+                if let Some(register) = data_register(context, address) {
+                    signals.push(ExploitabilitySignal::CrashAddressLooksLikeData { register });
+                }
+            }
+        }
+
+        let in_module = state.modules.module_at_address(pc).is_some();
+        if pc >= near_null && !in_module {
+            signals.push(ExploitabilitySignal::PcOutsideModules);
+        }
+        if matches!(stack_range, Some(range) if range.contains(&pc)) {
+            signals.push(ExploitabilitySignal::PcOnStack);
+        }
+
+        if is_stack_buffer_overrun(exception.reason) {
+            signals.push(ExploitabilitySignal::StackBufferOverrun);
+        }
+
+        if is_illegal_instruction(exception.reason)
+            && in_module
+            && !is_trap_instruction(context, pc, memory_list)
+        {
+            signals.push(ExploitabilitySignal::IllegalInstruction);
+        }
+
+        let rating = signals
+            .iter()
+            .map(ExploitabilitySignal::rating)
+            .max()
+            .unwrap_or(ExploitabilityRating::None);
+        Some(ExploitabilityAnalysis { rating, signals })
+    }
+}
+
+/// If `reason` is a memory access violation, the kind of access if known.
+pub(crate) fn access_violation(
+    reason: CrashReason,
+) -> Option<Option<ExceptionCodeWindowsAccessType>> {
+    match reason {
+        CrashReason::WindowsAccessViolation(access) => Some(Some(access)),
+        CrashReason::LinuxSigsegv(_)
+        | CrashReason::LinuxSigbus(_)
+        | CrashReason::MacBadAccessKern(_)
+        | CrashReason::MacBadAccessArm(_)
+        | CrashReason::MacBadAccessPpc(_)
+        | CrashReason::MacBadAccessX86(_) => Some(None),
+        _ => None,
+    }
+}
+
+fn is_stack_buffer_overrun(reason: CrashReason) -> bool {
+    matches!(
+        reason,
+        CrashReason::WindowsStackBufferOverrun(code)
+            if code == FastFailCode::FAST_FAIL_LEGACY_GS_VIOLATION as u64
+                || code == FastFailCode::FAST_FAIL_STACK_COOKIE_CHECK_FAILURE as u64
+    )
+}
+
+fn is_illegal_instruction(reason: CrashReason) -> bool {
+    matches!(
+        reason,
+        CrashReason::WindowsGeneral(ExceptionCodeWindows::EXCEPTION_ILLEGAL_INSTRUCTION)
+            | CrashReason::LinuxSigill(_)
+            | CrashReason::MacBadInstructionArm(_)
+            | CrashReason::MacBadInstructionPpc(_)
+            | CrashReason::MacBadInstructionX86(_)
+    )
+}
+
+/// Whether the instruction at `pc` is one compilers emit on purpose to
+/// crash, like x86's `ud2`.
+fn is_trap_instruction(
+    context: &MinidumpContext,
+    pc: u64,
+    memory_list: &UnifiedMemoryList,
+) -> bool {
+    let byte_at = |addr: u64| -> Option<u8> {
+        memory_list
+            .memory_at_address(addr)?
+            .get_memory_at_address(addr)
+    };
+    match context.raw {
+        MinidumpRawContext::X86(_) | MinidumpRawContext::Amd64(_) => {
+            byte_at(pc) == Some(0x0f) && pc.checked_add(1).and_then(byte_at) == Some(0x0b)
+        }
+        _ => false,
+    }
+}
+
+/// The first valid register of `context` (other than the instruction
+/// pointer) holding `address`, if `address` looks like data.
+fn data_register(context: &MinidumpContext, address: u64) -> Option<&'static str> {
+    if !looks_like_data(address) {
+        return None;
+    }
+    let pc = context.get_instruction_pointer();
+    context
+        .valid_registers()
+        .find(|&(_, value)| value == address && value != pc)
+        .map(|(name, _)| name)
+}
+
+/// Whether `value` looks like it was copied out of a buffer rather than being
+/// a pointer: all of its nonzero bytes are the same, or they're all text.
+fn looks_like_data(value: u64) -> bool {
+    // Ignore the zero high bytes of 32-bit values.
+    let width = if value >> 32 == 0 { 4 } else { 8 };
+    let bytes = &value.to_le_bytes()[..width];
+    let repeated = bytes.iter().all(|&byte| byte == bytes[0]);
+    let text = bytes
+        .iter()
+        .all(|&byte| byte.is_ascii_graphic() || byte == b' ');
+    repeated || text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_data_register() {
+        use minidump::format::CONTEXT_AMD64;
+
+        let raw = CONTEXT_AMD64 {
+            rip: 0x7ff612340000,
+            rax: 0x7ff612345678,
+            rbx: 0x4141414141414141,
+            ..CONTEXT_AMD64::default()
+        };
+        let context = MinidumpContext::from_raw(MinidumpRawContext::Amd64(raw));
+        assert_eq!(data_register(&context, 0x4141414141414141), Some("rbx"));
+        // Pointer-looking values don't count, even if a register holds them.
+        assert_eq!(data_register(&context, 0x7ff612345678), None);
+        assert_eq!(data_register(&context, 0x4242424242424242), None);
+    }
+
+    #[test]
+    fn test_rating_order() {
+        use ExploitabilityRating::*;
+        assert!(None < Low);
+        assert!(Low < Interesting);
+        assert!(Interesting < Medium);
+        assert!(Medium < High);
+    }
+
+    #[test]
+    fn test_looks_like_data() {
+        assert!(looks_like_data(0x41414141));
+        assert!(looks_like_data(0x4141414141414141));
+        assert!(looks_like_data(0x64636261));
+        assert!(!looks_like_data(0x7ffd5a3c1000));
+        assert!(!looks_like_data(0x00401234));
+        assert!(!looks_like_data(0x10000));
+    }
+}
@@ -0,0 +1,93 @@
+//! Merging information from outside the minidump into the processed report.
+//!
+//! Crash reporters often send metadata alongside the minidump (a release
+//! channel, experiment flags, names for threads) that belongs in the report.
+//! An [`ExtraInfoProvider`] in
+//! [`ProcessorOptions::extra_info`][crate::ProcessorOptions::extra_info]
+//! contributes it, and [`JsonFields`] covers the simple case of a JSON object
+//! whose fields should just be added to the JSON output.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+/// A source of information about the crash that isn't in the minidump.
+///
+/// Every method has a default that provides nothing, so providers only need
+/// to implement the ones they have something for. When several providers
+/// have something for the same thing, the last one in
+/// [`ProcessorOptions::extra_info`][crate::ProcessorOptions::extra_info] wins.
+pub trait ExtraInfoProvider: Send + Sync {
+    /// Fields to add to the top level of the JSON output.
+    ///
+    /// These go in [`ProcessState::extra_json`][crate::ProcessState::extra_json].
+    /// Fields the processor produces itself can't be replaced.
+    fn json_fields(&self) -> Map<String, Value> {
+        Map::new()
+    }
+
+    /// Annotations for the module with the given name (the basename of its
+    /// code file), for [`ProcessState::module_annotations`][crate::ProcessState::module_annotations].
+    fn module_annotations(&self, _module_name: &str) -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
+
+    /// The subject of the certificate the module with the given name was
+    /// signed with, for [`ProcessState::cert_info`][crate::ProcessState::cert_info].
+    fn module_cert(&self, _module_name: &str) -> Option<String> {
+        None
+    }
+
+    /// A name for the thread with the given id, if the minidump doesn't name
+    /// it.
+    fn thread_name(&self, _thread_id: u32) -> Option<String> {
+        None
+    }
+
+    /// A name for the thread with the given id, replacing any the minidump
+    /// gives it.
+    fn thread_name_override(&self, _thread_id: u32) -> Option<String> {
+        None
+    }
+}
+
+impl fmt::Debug for dyn ExtraInfoProvider + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ExtraInfoProvider")
+    }
+}
+
+/// An [`ExtraInfoProvider`] that adds the fields of a JSON object to the top
+/// level of the JSON output.
+#[derive(Debug, Clone, Default)]
+pub struct JsonFields {
+    fields: Map<String, Value>,
+}
+
+impl JsonFields {
+    /// Provide the given fields.
+    pub fn new(fields: Map<String, Value>) -> JsonFields {
+        JsonFields { fields }
+    }
+
+    /// Read the fields from a JSON object.
+    pub fn from_reader(reader: impl Read) -> Result<JsonFields, serde_json::Error> {
+        serde_json::from_reader(reader).map(JsonFields::new)
+    }
+
+    /// Read the fields from a file containing a JSON object.
+    pub fn from_path(path: &Path) -> io::Result<JsonFields> {
+        let file = BufReader::new(File::open(path)?);
+        Ok(JsonFields::from_reader(file)?)
+    }
+}
+
+impl ExtraInfoProvider for JsonFields {
+    fn json_fields(&self) -> Map<String, Value> {
+        self.fields.clone()
+    }
+}
@@ -0,0 +1,52 @@
+//! A hook for running your own analysis on each frame as stacks are walked.
+//!
+//! Some things are easiest to figure out while a stack is being walked, when
+//! the frame, its callee, and the thread's stack memory are all at hand:
+//! recognizing frames in a custom allocator or a trampoline, or pulling
+//! application-specific data out of the stack at known offsets from the stack
+//! pointer. A [`FrameAnalyzer`] set in
+//! [`ProcessorOptions::frame_analyzer`][crate::ProcessorOptions::frame_analyzer]
+//! gets to look at every frame, and annotate it with whatever it finds.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use async_trait::async_trait;
+use minidump::{MinidumpModuleList, UnifiedMemory};
+
+use crate::process_state::StackFrame;
+
+/// Custom analysis to run on each frame of each thread's stack.
+///
+/// See [`ProcessorOptions::frame_analyzer`][crate::ProcessorOptions::frame_analyzer].
+///
+/// Threads may be walked concurrently (see
+/// [`ProcessorOptions::thread_concurrency`][crate::ProcessorOptions::thread_concurrency]),
+/// so [`analyze_frame`][Self::analyze_frame] can be called for frames of
+/// different threads at the same time, and needs to synchronize any state it
+/// keeps. Calls for the frames of any one thread are made in order, from the
+/// top of the stack down, and one at a time.
+#[async_trait]
+pub trait FrameAnalyzer: Send + Sync {
+    /// Analyze `frame`, which was just walked and symbolicated.
+    ///
+    /// `callee` is the frame it was unwound from (`None` for the context
+    /// frame), `stack_memory` the thread's stack memory (if the minidump has
+    /// it), and `modules` the process's loaded modules.
+    ///
+    /// The returned annotations are added to [`StackFrame::annotations`],
+    /// replacing any with the same key.
+    async fn analyze_frame(
+        &self,
+        frame: &StackFrame,
+        callee: Option<&StackFrame>,
+        stack_memory: Option<&UnifiedMemory<'_, '_>>,
+        modules: &MinidumpModuleList,
+    ) -> BTreeMap<String, String>;
+}
+
+impl fmt::Debug for dyn FrameAnalyzer + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FrameAnalyzer")
+    }
+}
@@ -77,16 +77,42 @@
 doc_comment::doctest!("../README.md");
 
 mod arg_recovery;
+pub mod breakpad_compare;
+mod crash_signature;
 mod evil;
+mod exploitability;
+mod extra_info;
+mod frame_analysis;
+mod mini_process_state;
+mod module_map;
+mod name_normalization;
 mod op_analysis;
 mod process_state;
 mod processor;
+mod rop_detection;
+mod sanitizer_report;
+mod stack_overflow;
 mod stackwalker;
 pub mod symbols;
 mod system_info;
+mod thread_filter;
+mod walk_cursor;
 
+pub use crate::crash_signature::*;
+pub use crate::evil::EvilJsonProvider;
+pub use crate::exploitability::*;
+pub use crate::extra_info::*;
+pub use crate::frame_analysis::*;
+pub use crate::mini_process_state::*;
+pub use crate::module_map::*;
+pub use crate::name_normalization::*;
 pub use crate::process_state::*;
 pub use crate::processor::*;
+pub use crate::rop_detection::*;
+pub use crate::sanitizer_report::*;
+pub use crate::stack_overflow::*;
 pub use crate::stackwalker::*;
 pub use crate::symbols::*;
 pub use crate::system_info::*;
+pub use crate::thread_filter::*;
+pub use crate::walk_cursor::*;
@@ -0,0 +1,223 @@
+//! A reduced [`ProcessState`] for long-term storage and grouping.
+
+use std::io::prelude::*;
+
+use minidump::Module;
+use minidump_common::utils::basename;
+use serde_json::json;
+
+use crate::module_map::ModuleMapEntry;
+use crate::process_state::{ProcessState, StackFrame};
+use crate::FrameTrust;
+
+/// The version of [`MiniProcessState::print_json`]'s schema.
+///
+/// This is only bumped for changes that would break an existing consumer,
+/// like removing or retyping a field.
+pub const MINI_PROCESS_STATE_SCHEMA_VERSION: u32 = 1;
+
+/// A frame of [`MiniThread`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MiniFrame {
+    /// See [`StackFrame::instruction`].
+    pub instruction: u64,
+    /// How the frame was found.
+    pub trust: FrameTrust,
+    /// The index of the frame's module in [`MiniProcessState::modules`].
+    pub module_index: Option<usize>,
+    /// The offset of the instruction from the start of the module.
+    pub module_offset: Option<u64>,
+    /// The name of the function.
+    pub function: Option<String>,
+    /// The offset of the instruction from the start of the function.
+    pub function_offset: Option<u64>,
+    /// The file name of the source file.
+    pub source_file: Option<String>,
+    /// The line number in the source file.
+    pub source_line: Option<u32>,
+    /// The names of the functions inlined into this frame, innermost first.
+    pub inlines: Vec<String>,
+}
+
+/// The crashing thread of a [`MiniProcessState`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MiniThread {
+    pub thread_id: u32,
+    pub thread_name: Option<String>,
+    pub frames: Vec<MiniFrame>,
+}
+
+/// A deliberately lossy projection of a [`ProcessState`], keeping only what's
+/// needed to identify and group a crash.
+///
+/// This is the crash reason, the system the crash happened on, the
+/// symbolicated stack of the crashing thread, and just the modules that
+/// stack refers to. It's a fraction of the size of a full process state,
+/// and its JSON schema (see [`MiniProcessState::print_json`]) is stable,
+/// so it can be stored and compared across versions of this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MiniProcessState {
+    /// The crash reason, as in `crash_info.type` of the full JSON output.
+    pub crash_reason: Option<String>,
+    /// The memory address implicated in the crash.
+    pub crash_address: Option<u64>,
+    /// The operating system, as in `system_info.os` of the full JSON output.
+    pub os: String,
+    /// The operating system version, as in `system_info.os_ver`.
+    pub os_version: Option<String>,
+    /// The CPU architecture, as in `system_info.cpu_arch`.
+    pub cpu: String,
+    /// The thread that crashed (or requested the dump), if known.
+    pub crashing_thread: Option<MiniThread>,
+    /// The modules the crashing thread's frames are in, sorted by address.
+    pub modules: Vec<ModuleMapEntry>,
+}
+
+impl MiniProcessState {
+    /// Reduce `state` to the parts worth keeping.
+    pub fn from_process_state(state: &ProcessState) -> MiniProcessState {
+        let crashing_thread = state.crashing_thread();
+        let referenced_modules = crashing_thread
+            .map(|stack| stack.referenced_modules())
+            .unwrap_or_default();
+
+        let crashing_thread = crashing_thread.map(|stack| MiniThread {
+            thread_id: stack.thread_id,
+            thread_name: stack.thread_name.clone(),
+            frames: stack
+                .frames
+                .iter()
+                .map(|frame| {
+                    let module_index = frame.module.as_ref().and_then(|module| {
+                        referenced_modules
+                            .iter()
+                            .position(|m| m.base_address() == module.base_address())
+                    });
+                    mini_frame(frame, module_index)
+                })
+                .collect(),
+        });
+
+        MiniProcessState {
+            crash_reason: state
+                .exception_info
+                .as_ref()
+                .map(|info| info.reason.to_string()),
+            crash_address: state.exception_info.as_ref().map(|info| info.address),
+            os: state.system_info.os.long_name().into_owned(),
+            os_version: state
+                .system_info
+                .format_os_version()
+                .map(|version| version.into_owned()),
+            cpu: state.system_info.cpu.to_string(),
+            crashing_thread,
+            modules: referenced_modules
+                .into_iter()
+                .map(ModuleMapEntry::from_module)
+                .collect(),
+        }
+    }
+
+    /// Write the mini process state to `f` as JSON.
+    ///
+    /// The schema is:
+    ///
+    /// ```json
+    /// {
+    ///   "schema_version": 1,
+    ///   "crash_reason": <string> | null,
+    ///   "crash_address": <hexstring> | null,
+    ///   "os": <string>,
+    ///   "os_version": <string> | null,
+    ///   "cpu": <string>,
+    ///   "crashing_thread": {
+    ///     "thread_id": <u32>,
+    ///     "thread_name": <string> | null,
+    ///     "frames": [
+    ///       {
+    ///         "instruction": <hexstring>,
+    ///         "trust": <string>,
+    ///         "module_index": <u32> | null,
+    ///         "module_offset": <hexstring> | null,
+    ///         "function": <string> | null,
+    ///         "function_offset": <hexstring> | null,
+    ///         "file": <string> | null,
+    ///         "line": <u32> | null,
+    ///         "inlines": [<string>]
+    ///       }
+    ///     ]
+    ///   } | null,
+    ///   "modules": [<module>]
+    /// }
+    /// ```
+    ///
+    /// where modules are described as in [`ModuleMap::print_json`][crate::ModuleMap::print_json],
+    /// and trust values are those of the full JSON output.
+    pub fn print_json<T: Write>(&self, f: &mut T, pretty: bool) -> Result<(), serde_json::Error> {
+        let json_hex = |val: u64| format!("{:#x}", val);
+        let output = json!({
+            "schema_version": MINI_PROCESS_STATE_SCHEMA_VERSION,
+            // optional
+            "crash_reason": self.crash_reason,
+            // optional
+            "crash_address": self.crash_address.map(json_hex),
+            "os": self.os,
+            // optional
+            "os_version": self.os_version,
+            "cpu": self.cpu,
+            // optional
+            "crashing_thread": self.crashing_thread.as_ref().map(|thread| json!({
+                "thread_id": thread.thread_id,
+                // optional
+                "thread_name": thread.thread_name,
+                "frames": thread.frames.iter().map(|frame| json!({
+                    "instruction": json_hex(frame.instruction),
+                    "trust": frame.trust,
+                    // optional
+                    "module_index": frame.module_index,
+                    // optional
+                    "module_offset": frame.module_offset.map(json_hex),
+                    // optional
+                    "function": frame.function,
+                    // optional
+                    "function_offset": frame.function_offset.map(json_hex),
+                    // optional
+                    "file": frame.source_file,
+                    // optional
+                    "line": frame.source_line,
+                    "inlines": frame.inlines,
+                })).collect::<Vec<_>>(),
+            })),
+            "modules": self.modules.iter().map(ModuleMapEntry::to_json).collect::<Vec<_>>(),
+        });
+        if pretty {
+            serde_json::to_writer_pretty(f, &output)
+        } else {
+            serde_json::to_writer(f, &output)
+        }
+    }
+}
+
+fn mini_frame(frame: &StackFrame, module_index: Option<usize>) -> MiniFrame {
+    MiniFrame {
+        instruction: frame.instruction,
+        trust: frame.trust,
+        module_index,
+        module_offset: frame
+            .module
+            .as_ref()
+            .map(|module| frame.instruction - module.base_address()),
+        function: frame.function_name.clone(),
+        function_offset: frame.function_base.map(|base| frame.instruction - base),
+        source_file: frame
+            .source_file_name
+            .as_deref()
+            .map(|file| basename(file).to_owned()),
+        source_line: frame.source_line,
+        inlines: frame
+            .inlines
+            .iter()
+            .map(|inline| inline.function_name.clone())
+            .collect(),
+    }
+}
@@ -0,0 +1,111 @@
+//! A minimal description of a minidump's module layout, for external tools.
+
+use std::io;
+use std::io::prelude::*;
+
+use minidump::{MinidumpModule, MinidumpModuleList, Module};
+use minidump_common::utils::basename;
+use serde_json::json;
+
+/// Where a module was loaded, and how to identify it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleMapEntry {
+    /// The file name of the module's code file.
+    pub name: String,
+    /// The full path of the module's code file.
+    pub code_file: String,
+    /// The address the module was loaded at.
+    pub base_address: u64,
+    /// The size of the module's image, in bytes.
+    pub size: u64,
+    /// The file name of the module's debug file, if known.
+    pub debug_file: Option<String>,
+    /// The module's debug identifier, in breakpad's format.
+    pub debug_id: Option<String>,
+    /// The module's code identifier.
+    pub code_id: Option<String>,
+}
+
+/// The module layout of a minidump: just enough to set up the module map of
+/// another tool (like a disassembler or debugger), without the rest of the
+/// [`ProcessState`][crate::ProcessState].
+///
+/// Modules are sorted by address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleMap {
+    pub modules: Vec<ModuleMapEntry>,
+}
+
+impl ModuleMapEntry {
+    /// Describe `module`.
+    pub fn from_module(module: &MinidumpModule) -> ModuleMapEntry {
+        let code_file = module.code_file().into_owned();
+        ModuleMapEntry {
+            name: basename(&code_file).to_owned(),
+            base_address: module.base_address(),
+            size: module.size(),
+            debug_file: module.debug_file().map(|file| basename(&file).to_owned()),
+            debug_id: module
+                .debug_identifier()
+                .map(|id| id.breakpad().to_string()),
+            code_id: module.code_identifier().map(|id| id.as_str().to_owned()),
+            code_file,
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        json!({
+            "name": self.name,
+            "code_file": self.code_file,
+            "base_address": format!("{:#x}", self.base_address),
+            "size": format!("{:#x}", self.size),
+            // optional
+            "debug_file": self.debug_file,
+            // optional
+            "debug_id": self.debug_id,
+            // optional
+            "code_id": self.code_id,
+        })
+    }
+}
+
+impl ModuleMap {
+    /// Collect the layout of `modules`.
+    pub fn from_modules(modules: &MinidumpModuleList) -> ModuleMap {
+        let modules = modules.by_addr().map(ModuleMapEntry::from_module).collect();
+        ModuleMap { modules }
+    }
+
+    /// Write the module map to `f` as a JSON array of objects.
+    pub fn print_json<T: Write>(&self, f: &mut T, pretty: bool) -> Result<(), serde_json::Error> {
+        let output = json!(self
+            .modules
+            .iter()
+            .map(ModuleMapEntry::to_json)
+            .collect::<Vec<_>>());
+        if pretty {
+            serde_json::to_writer_pretty(f, &output)
+        } else {
+            serde_json::to_writer(f, &output)
+        }
+    }
+
+    /// Write the module map to `f` as tab-separated values, one module per
+    /// line after a header line. Unknown values are left empty.
+    pub fn print_tsv<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        writeln!(f, "name\tbase_address\tsize\tdebug_file\tdebug_id\tcode_id")?;
+        for module in &self.modules {
+            writeln!(
+                f,
+                "{}\t{:#x}\t{:#x}\t{}\t{}\t{}",
+                module.name,
+                module.base_address,
+                module.size,
+                module.debug_file.as_deref().unwrap_or(""),
+                module.debug_id.as_deref().unwrap_or(""),
+                module.code_id.as_deref().unwrap_or(""),
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+//! Normalizing the function names different toolchains produce for the same function.
+//!
+//! GCC, Clang, and MSVC disagree on how to spell several C++ constructs, so
+//! the same function built with different toolchains (or even different
+//! versions of one) can end up with names that don't compare equal. This
+//! makes grouping crashes by their stacks unreliable. Each rule in
+//! [`NameNormalization`] canonicalizes one such construct:
+//!
+//! * `anonymous_namespaces`: `` `anonymous namespace' ``, `'anonymous namespace'`,
+//!   and `{anonymous}` all become `(anonymous namespace)`.
+//! * `lambdas`: `{lambda(int)#1}`, `'lambda'(int)`, `'lambda0'(int)`, and
+//!   `<lambda_0123abcd>` all become `{lambda}`.
+//! * `template_args`: template arguments are removed, so
+//!   `std::vector<int, std::allocator<int> >::push_back` becomes
+//!   `std::vector::push_back`. Comparison operators (`operator<` and friends)
+//!   and Rust's qualified paths (`<T as Trait>::method`) are left alone.
+//!
+//! Normalized names are for grouping, not display, which is why
+//! [`StackFrame::normalized_function_name`][crate::StackFrame::normalized_function_name]
+//! is kept separately from the raw name.
+
+/// Which name normalization rules to apply.
+///
+/// See the [module-level docs][self] for what each rule does. The default
+/// applies no rules.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NameNormalization {
+    pub anonymous_namespaces: bool,
+    pub lambdas: bool,
+    pub template_args: bool,
+}
+
+impl NameNormalization {
+    /// Apply every rule.
+    pub fn all() -> NameNormalization {
+        NameNormalization {
+            anonymous_namespaces: true,
+            lambdas: true,
+            template_args: true,
+        }
+    }
+
+    /// Whether any rules are enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.anonymous_namespaces || self.lambdas || self.template_args
+    }
+
+    /// Normalize `name` with the enabled rules.
+    pub fn normalize(&self, name: &str) -> String {
+        let mut name = name.to_owned();
+        if self.anonymous_namespaces {
+            name = normalize_anonymous_namespaces(&name);
+        }
+        // This must happen before template arguments are stripped, because
+        // MSVC's lambda names look like template arguments.
+        if self.lambdas {
+            name = normalize_lambdas(&name);
+        }
+        if self.template_args {
+            name = strip_template_args(&name);
+        }
+        name
+    }
+}
+
+const ANONYMOUS_NAMESPACE: &str = "(anonymous namespace)";
+const LAMBDA: &str = "{lambda}";
+
+fn normalize_anonymous_namespaces(name: &str) -> String {
+    name.replace("`anonymous namespace'", ANONYMOUS_NAMESPACE)
+        .replace("'anonymous namespace'", ANONYMOUS_NAMESPACE)
+        .replace("{anonymous}", ANONYMOUS_NAMESPACE)
+}
+
+fn normalize_lambdas(name: &str) -> String {
+    let mut output = String::with_capacity(name.len());
+    let mut rest = name;
+    while !rest.is_empty() {
+        if let Some(len) = lambda_len(rest) {
+            output.push_str(LAMBDA);
+            rest = &rest[len..];
+        } else {
+            let c = rest.chars().next().unwrap();
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    output
+}
+
+/// If `name` starts with a lambda, the length of it.
+fn lambda_len(name: &str) -> Option<usize> {
+    if name.starts_with("{lambda(") {
+        // GCC/Clang: {lambda(int, char)#1}
+        balanced_len(name, '{', '}')
+    } else if let Some(rest) = name.strip_prefix("'lambda") {
+        // llvm-cxxfilt: 'lambda'(int), 'lambda0'(int)
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let rest = rest[digits..].strip_prefix('\'')?;
+        let params = if rest.starts_with('(') {
+            balanced_len(rest, '(', ')')?
+        } else {
+            0
+        };
+        Some(name.len() - rest.len() + params)
+    } else if let Some(rest) = name.strip_prefix("<lambda_") {
+        // MSVC: <lambda_0123abcd>
+        let id = rest.bytes().take_while(u8::is_ascii_alphanumeric).count();
+        rest[id..]
+            .starts_with('>')
+            .then(|| name.len() - rest.len() + id + 1)
+    } else {
+        None
+    }
+}
+
+/// The length of the `open`...`close` delimited group at the start of `s`,
+/// including any nested groups.
+fn balanced_len(s: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0usize;
+    for (idx, c) in s.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth = depth.checked_sub(1)?;
+            if depth == 0 {
+                return Some(idx + c.len_utf8());
+            }
+        }
+    }
+    None
+}
+
+fn strip_template_args(name: &str) -> String {
+    let mut output = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(idx) = rest.find('<') {
+        output.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+
+        // Only strip a '<' that follows a name, which excludes comparison
+        // operators and Rust's `<T as Trait>` paths.
+        let follows_name = matches!(
+            output.chars().next_back(),
+            Some(c) if c.is_alphanumeric() || c == '_' || c == '}'
+        ) && !output.ends_with("operator");
+        if follows_name {
+            if let Some(len) = template_args_len(rest) {
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        // Not template arguments; keep any run of '<' (e.g. `operator<<`) as is.
+        let len = rest.bytes().take_while(|&b| b == b'<').count();
+        output.push_str(&rest[..len]);
+        rest = &rest[len..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// The length of the template arguments at the start of `s`.
+///
+/// Arrows (`->`) don't close template arguments, and unbalanced arguments
+/// aren't stripped.
+fn template_args_len(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut prev = None;
+    for (idx, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' if prev != Some('-') => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(idx + 1);
+                }
+            }
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_anonymous_namespaces() {
+        let rules = NameNormalization {
+            anonymous_namespaces: true,
+            ..NameNormalization::default()
+        };
+        for name in [
+            "`anonymous namespace'::Foo(int)",
+            "'anonymous namespace'::Foo(int)",
+            "{anonymous}::Foo(int)",
+            "(anonymous namespace)::Foo(int)",
+        ] {
+            assert_eq!(rules.normalize(name), "(anonymous namespace)::Foo(int)");
+        }
+    }
+
+    #[test]
+    fn test_lambdas() {
+        let rules = NameNormalization {
+            lambdas: true,
+            ..NameNormalization::default()
+        };
+        for name in [
+            "Foo::Bar()::{lambda(int, std::pair<int, int>)#1}::operator()(int) const",
+            "Foo::Bar()::'lambda'(int, std::pair<int, int>)::operator()(int) const",
+            "Foo::Bar()::'lambda0'(int, std::pair<int, int>)::operator()(int) const",
+            "Foo::Bar()::<lambda_1a2b3c4d>::operator()(int) const",
+        ] {
+            assert_eq!(
+                rules.normalize(name),
+                "Foo::Bar()::{lambda}::operator()(int) const"
+            );
+        }
+        // Things that only look like lambdas are left alone.
+        assert_eq!(rules.normalize("lambda_helper(int)"), "lambda_helper(int)");
+        assert_eq!(rules.normalize("Foo<lambda_x"), "Foo<lambda_x");
+    }
+
+    #[test]
+    fn test_template_args() {
+        let rules = NameNormalization {
+            template_args: true,
+            ..NameNormalization::default()
+        };
+        assert_eq!(
+            rules.normalize("std::vector<int, std::allocator<int> >::push_back(int const&)"),
+            "std::vector::push_back(int const&)"
+        );
+        assert_eq!(
+            rules.normalize("mozilla::Maybe<std::function<void (int)> >::reset()"),
+            "mozilla::Maybe::reset()"
+        );
+        assert_eq!(rules.normalize("Foo<decltype(a->b)>::Bar()"), "Foo::Bar()");
+        assert_eq!(
+            rules.normalize("alloc::vec::Vec<T,A>::push"),
+            "alloc::vec::Vec::push"
+        );
+        // Not template arguments
+        for name in [
+            "Foo::operator<(Foo const&)",
+            "Foo::operator<<(std::ostream&)",
+            "Foo::operator<=(Foo const&)",
+            "Foo::operator->()",
+            "<core::cell::BorrowError as core::fmt::Debug>::fmt",
+            "Foo<unbalanced",
+        ] {
+            assert_eq!(rules.normalize(name), name);
+        }
+    }
+
+    #[test]
+    fn test_all() {
+        let rules = NameNormalization::all();
+        assert!(rules.is_enabled());
+        assert!(!NameNormalization::default().is_enabled());
+        assert_eq!(
+            rules.normalize("`anonymous namespace'::Run<T>()::<lambda_1f>::operator()() const"),
+            "(anonymous namespace)::Run()::{lambda}::operator()() const"
+        );
+    }
+}
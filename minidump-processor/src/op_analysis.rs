@@ -115,9 +115,9 @@ pub fn pretty_print_instruction_bytes(
 ///
 /// This may fail if there are no bytes at the instruction pointer, or if
 /// `get_instruction_memory_access` fails
-pub fn get_thread_memory_access<'a, Descriptor>(
+pub fn get_thread_memory_access(
     context: &MinidumpContext,
-    memory_list: &minidump::MinidumpMemoryListBase<'a, Descriptor>,
+    memory_list: &minidump::UnifiedMemoryList,
 ) -> Result<Vec<MemoryAccess>, OpAnalysisError> {
     let instruction_bytes = get_thread_instruction_bytes(context, memory_list)?;
     get_instruction_memory_access(context, instruction_bytes)
@@ -132,9 +132,9 @@ pub fn get_thread_memory_access<'a, Descriptor>(
 ///
 /// This may fail if there are no bytes at the instruction pointer, or if
 /// `pretty_print_instruction_bytes` fails
-pub fn pretty_print_thread_instruction<'a, Descriptor>(
+pub fn pretty_print_thread_instruction(
     context: &MinidumpContext,
-    memory_list: &minidump::MinidumpMemoryListBase<'a, Descriptor>,
+    memory_list: &minidump::UnifiedMemoryList,
 ) -> Result<String, OpAnalysisError> {
     let instruction_bytes = get_thread_instruction_bytes(context, memory_list)?;
     pretty_print_instruction_bytes(context, instruction_bytes)
@@ -148,17 +148,17 @@ pub fn pretty_print_thread_instruction<'a, Descriptor>(
 /// # Errors
 ///
 /// This may fail if there are no bytes at the instruction pointer
-fn get_thread_instruction_bytes<'a, Descriptor>(
+fn get_thread_instruction_bytes<'a>(
     context: &MinidumpContext,
-    memory_list: &'a minidump::MinidumpMemoryListBase<'a, Descriptor>,
+    memory_list: &minidump::UnifiedMemoryList<'a>,
 ) -> Result<&'a [u8], OpAnalysisError> {
     let instruction_pointer = context.get_instruction_pointer();
 
     memory_list
         .memory_at_address(instruction_pointer)
         .map(|memory| {
-            let offset = (instruction_pointer - memory.base_address) as usize;
-            &memory.bytes[offset..]
+            let offset = (instruction_pointer - memory.base_address()) as usize;
+            &memory.bytes()[offset..]
         })
         .ok_or(OpAnalysisError::ReadThreadInstructionFailed)
 }
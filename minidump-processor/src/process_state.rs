@@ -5,37 +5,66 @@
 
 use std::borrow::{Borrow, Cow};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
 use std::io;
 use std::io::prelude::*;
-use std::time::SystemTime;
+use std::ops::Range;
+use std::time::{Duration, SystemTime};
 
 use crate::op_analysis::MemoryAccess;
 use crate::system_info::SystemInfo;
-use crate::{FrameSymbolizer, SymbolStats};
+use crate::{
+    ExploitabilityAnalysis, FrameSymbolizer, ProcessStatePatch, RopChainAnalysis, SanitizerReport,
+    StackOverflowAnalysis, SymbolOrigin, SymbolStats,
+};
 use minidump::system_info::Cpu;
 use minidump::*;
+use minidump_common::poison::PoisonPattern;
 use minidump_common::utils::basename;
 use serde_json::json;
 
 /// Indicates how well the instruction pointer derived during
 /// stack walking is trusted. Since the stack walker can resort to
 /// stack scanning, it can wind up with dubious frames.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///
+/// Variants are ordered from least to most trustworthy, so they can be
+/// compared (`frame.trust >= FrameTrust::FramePointer`). Casting one to an
+/// integer gives a number that is stable across releases; new variants will
+/// get numbers in the gaps between the existing ones.
+///
+/// Not every architecture's unwinder can produce every variant.
+///
+/// This is serialized with the same names as in the JSON output.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
 pub enum FrameTrust {
     /// Unknown
-    None,
+    #[serde(rename = "non")]
+    None = 0,
     /// Scanned the stack, found this.
-    Scan,
+    #[serde(rename = "scan")]
+    Scan = 10,
     /// Found while scanning stack using call frame info.
-    CfiScan,
+    #[serde(rename = "cfi_scan")]
+    CfiScan = 20,
     /// Derived from frame pointer.
-    FramePointer,
+    #[serde(rename = "frame_pointer")]
+    FramePointer = 50,
+    /// Derived from call frame info, but the recovered registers look suspicious.
+    ///
+    /// See [`ProcessorOptions::check_cfi_plausibility`][crate::ProcessorOptions::check_cfi_plausibility].
+    #[serde(rename = "cfi_low_confidence")]
+    CallFrameInfoLowConfidence = 60,
     /// Derived from call frame info.
-    CallFrameInfo,
+    #[serde(rename = "cfi")]
+    CallFrameInfo = 70,
     /// Explicitly provided by some external stack walker.
-    PreWalked,
+    #[serde(rename = "prewalked")]
+    PreWalked = 90,
     /// Given as instruction pointer in a context.
-    Context,
+    #[serde(rename = "context")]
+    Context = 100,
 }
 
 #[derive(Debug, Clone)]
@@ -43,6 +72,10 @@ pub enum CallingConvention {
     Cdecl,
     WindowsThisCall,
     OtherThisCall,
+    /// The System V x86-64 calling convention, used everywhere but Windows.
+    Amd64SystemV,
+    /// The Windows x64 calling convention.
+    Amd64Windows,
 }
 
 /// Arguments for this function
@@ -71,12 +104,33 @@ pub struct FunctionArg {
 pub struct InlineFrame {
     /// The name of the function
     pub function_name: String,
+    /// The function name after normalization, if that changed it.
+    ///
+    /// See [`ProcessorOptions::name_normalization`][crate::ProcessorOptions::name_normalization].
+    pub normalized_function_name: Option<String>,
     /// The file name of the stack frame
     pub source_file_name: Option<String>,
     /// The line number of the stack frame
     pub source_line: Option<u32>,
 }
 
+/// An unloaded module that overlaps a frame's instruction address.
+///
+/// See [`StackFrame::unloaded_module_candidates`][] for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnloadedModuleCandidate {
+    /// The name of the unloaded module.
+    pub name: String,
+    /// The base address the module was loaded at.
+    pub base_address: u64,
+    /// The frame's instruction address relative to `base_address`.
+    pub offset: u64,
+    /// The checksum of the module's image.
+    pub checksum: u32,
+    /// The timestamp of the module's image.
+    pub time_date_stamp: u32,
+}
+
 /// A single stack frame produced from unwinding a thread's stack.
 #[derive(Debug, Clone)]
 pub struct StackFrame {
@@ -139,7 +193,7 @@ pub struct StackFrame {
 
     /// Any unloaded modules which overlap with this address.
     ///
-    /// This is currently only populated if `module` is None.
+    /// This is only populated if `module` is None.
     ///
     /// Since unloaded modules may overlap, there may be more than
     /// one module. Since a module may be unloaded and reloaded at
@@ -149,15 +203,43 @@ pub struct StackFrame {
     /// So this is a `BTreeMap<module_name, Set<offsets>>`.
     pub unloaded_modules: BTreeMap<String, BTreeSet<u64>>,
 
+    /// Every unloaded module entry which overlaps with this address.
+    ///
+    /// This is the raw counterpart of `unloaded_modules`: entries are in the
+    /// order they were stored in the minidump and aren't deduplicated, so a
+    /// module that was loaded and unloaded several times shows up once per
+    /// load. The checksum and timestamp can tell those loads apart.
+    ///
+    /// This is only populated if `module` is None.
+    pub unloaded_module_candidates: Vec<UnloadedModuleCandidate>,
+
+    /// The unloaded module whose symbols the frame's function and source
+    /// location came from.
+    ///
+    /// Frames that aren't in a loaded module are symbolicated with the
+    /// symbols of the unloaded modules they overlap, when those can be found
+    /// (see [`StackFrame::unloaded_module_candidates`]). This is the
+    /// candidate that had the frame's address.
+    pub unloaded_module_symbols: Option<UnloadedModuleCandidate>,
+
     /// The function name, may be omitted if debug symbols are not available.
     pub function_name: Option<String>,
 
+    /// The function name after normalization, if that changed it.
+    ///
+    /// See [`ProcessorOptions::name_normalization`][crate::ProcessorOptions::name_normalization].
+    pub normalized_function_name: Option<String>,
+
     /// The start address of the function, may be omitted if debug symbols
     /// are not available.
     pub function_base: Option<u64>,
 
     /// The size, in bytes, of the arguments pushed on the stack for this function.
-    /// WIN STACK unwinding needs this value to work; it's otherwise uninteresting.
+    ///
+    /// This comes from the function's STACK WIN record if it has one, and
+    /// otherwise its FUNC or PUBLIC record, whatever the architecture. STACK
+    /// WIN unwinding of the caller's caller needs it, and argument recovery
+    /// uses it to know how many arguments were passed on the stack.
     pub parameter_size: Option<u32>,
 
     /// The source file name, may be omitted if debug symbols are not available.
@@ -207,6 +289,79 @@ pub struct StackFrame {
 
     /// Any function args we recovered.
     pub arguments: Option<FunctionArgs>,
+
+    /// Problems the stack walker noticed with this frame that weren't
+    /// bad enough to reject it outright (e.g. a misaligned stack pointer).
+    ///
+    /// The presence of any warnings suggests that the frame (and the
+    /// frames after it) deserve less trust than `trust` alone implies.
+    pub warnings: Vec<String>,
+
+    /// Whether this frame (or something inlined into it) is in one of
+    /// [`ProcessorOptions::noreturn_functions`][crate::ProcessorOptions::noreturn_functions].
+    pub noreturn: bool,
+
+    /// Why this frame has no function name, if it doesn't and the reason is
+    /// known.
+    pub symbol_miss_reason: Option<SymbolMissReason>,
+
+    /// Annotations from [`ProcessorOptions::frame_analyzer`][crate::ProcessorOptions::frame_analyzer].
+    pub annotations: BTreeMap<String, String>,
+}
+
+/// Why a [`StackFrame`] couldn't be symbolicated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolMissReason {
+    /// The frame isn't in any loaded module.
+    NoModule,
+    /// No symbol file could be found for the frame's module.
+    MissingSymbolFile,
+    /// A symbol server failed to answer the request for the module's
+    /// symbols, with the given HTTP status if it got a response.
+    FetchFailed { status: Option<u16> },
+    /// The module's symbol file was found but couldn't be parsed.
+    CorruptSymbolFile,
+    /// The module's symbols were loaded but don't cover the frame's address.
+    AddressNotCovered,
+}
+
+impl SymbolMissReason {
+    /// The name of this reason in the JSON output, like `"missing_symbol_file"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SymbolMissReason::NoModule => "no_module",
+            SymbolMissReason::MissingSymbolFile => "missing_symbol_file",
+            SymbolMissReason::FetchFailed { .. } => "fetch_failed",
+            SymbolMissReason::CorruptSymbolFile => "corrupt_symbol_file",
+            SymbolMissReason::AddressNotCovered => "address_not_covered",
+        }
+    }
+}
+
+/// Where a [`ProcessState::process_create_time`] came from.
+///
+/// Minidumps can say when the process started in a few ways. The processor
+/// uses the first of these it finds, in the order they're listed here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessCreateTimeSource {
+    /// The process times in the MISC_INFO stream.
+    MiscInfo,
+    /// The creation time of the earliest-created thread in the
+    /// ThreadInfoListStream.
+    ThreadInfo,
+    /// A Crashpad `StartupTime` annotation (seconds since the Unix epoch).
+    CrashpadAnnotation,
+}
+
+impl ProcessCreateTimeSource {
+    /// The name of this source in the JSON output, like `"misc_info"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcessCreateTimeSource::MiscInfo => "misc_info",
+            ProcessCreateTimeSource::ThreadInfo => "thread_info",
+            ProcessCreateTimeSource::CrashpadAnnotation => "crashpad_annotation",
+        }
+    }
 }
 
 /// Information about the results of unwinding a thread's stack.
@@ -222,6 +377,83 @@ pub enum CallStackInfo {
     UnsupportedCpu,
     /// This thread wrote the minidump, it was skipped.
     DumpThreadSkipped,
+    /// The thread was excluded by
+    /// [`ProcessorOptions::thread_filter`][crate::ProcessorOptions::thread_filter],
+    /// so its stack wasn't walked.
+    NotWalked,
+}
+
+/// Why the walk of a [`CallStack`] stopped where it did.
+///
+/// [`CallStackInfo`] says whether the stack could be walked at all; this says
+/// why a walk ended, either at what looked like the end of the stack or
+/// because of one of the processor's limits. Most walks end with one of the
+/// first five reasons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkEndReason {
+    /// The caller's return address was null (or close to it), which is how
+    /// most stacks end.
+    NullReturnAddress,
+    /// The caller's stack pointer wasn't above the callee's, so the caller
+    /// can't have been right.
+    StackPointerWentBackwards,
+    /// The minidump doesn't have any of the thread's stack memory, so the
+    /// walk couldn't get past the first frame.
+    MissingStackMemory,
+    /// Finding the caller needed more stack memory than the minidump has,
+    /// which ends at `address`. This usually means the dump's copy of the
+    /// stack was truncated.
+    StackMemoryExhausted { address: u64 },
+    /// There was stack memory to look in, but neither CFI, frame pointers,
+    /// nor stack scanning found a caller.
+    NoCallerFound,
+    /// The caller's stack pointer wasn't on the thread's stack (see
+    /// [`ProcessorOptions::constrain_to_thread_stack`][crate::ProcessorOptions::constrain_to_thread_stack]).
+    LeftThreadStack,
+    /// The walk stopped cleanly at the bottom of the thread's stack (see
+    /// [`ProcessorOptions::stop_at_stack_bottom`][crate::ProcessorOptions::stop_at_stack_bottom]).
+    ReachedStackBottom,
+    /// The walk was cut short because the thread reached
+    /// [`ProcessorOptions::max_frames_per_thread`][crate::ProcessorOptions::max_frames_per_thread]
+    /// frames. There were more frames that weren't walked.
+    MaxFramesReached,
+    /// The walk was stopped after
+    /// [`ProcessorOptions::max_consecutive_scan_frames`][crate::ProcessorOptions::max_consecutive_scan_frames]
+    /// frames in a row were found by stack scanning, as scanning was probably
+    /// just finding noise by then.
+    ScanLimitReached,
+    /// The walk took longer than
+    /// [`ProcessorOptions::frame_walk_timeout`][crate::ProcessorOptions::frame_walk_timeout],
+    /// and was stopped after the last complete frame.
+    WalkTimedOut,
+    /// The walk was stopped because a group of frames kept repeating, each
+    /// time only creeping a little further up the stack, so the unwind was
+    /// going in circles.
+    /// Only one copy of the group is kept, see [`CallStack::repeated_frames`]
+    /// and [`ProcessorOptions::stop_on_unwind_cycles`][crate::ProcessorOptions::stop_on_unwind_cycles].
+    CycleDetected,
+    /// We don't know how to walk stacks for the thread's CPU.
+    UnsupportedCpu,
+}
+
+impl WalkEndReason {
+    /// The name of this reason in the JSON output, like `"null_return_address"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WalkEndReason::NullReturnAddress => "null_return_address",
+            WalkEndReason::StackPointerWentBackwards => "stack_pointer_went_backwards",
+            WalkEndReason::MissingStackMemory => "missing_stack_memory",
+            WalkEndReason::StackMemoryExhausted { .. } => "stack_memory_exhausted",
+            WalkEndReason::NoCallerFound => "no_caller_found",
+            WalkEndReason::LeftThreadStack => "left_thread_stack",
+            WalkEndReason::ReachedStackBottom => "reached_stack_bottom",
+            WalkEndReason::MaxFramesReached => "max_frames_reached",
+            WalkEndReason::ScanLimitReached => "scan_limit_reached",
+            WalkEndReason::WalkTimedOut => "walk_timed_out",
+            WalkEndReason::CycleDetected => "cycle_detected",
+            WalkEndReason::UnsupportedCpu => "unsupported_cpu",
+        }
+    }
 }
 
 /// A stack of `StackFrame`s produced as a result of unwinding a thread.
@@ -240,6 +472,37 @@ pub struct CallStack {
     pub thread_name: Option<String>,
     /// The GetLastError() value stored in the TEB.
     pub last_error_value: Option<CrashReason>,
+    /// The address range of the stack that the thread's descriptor declares, if known.
+    pub stack_range: Option<Range<u64>>,
+    /// What the memory at the thread's stack pointer was used for, if the
+    /// minidump has memory info.
+    ///
+    /// Anything but [`MemoryKind::Stack`] suggests a stack overflow (into a
+    /// guard page) or a stack pivot.
+    pub stack_memory_kind: Option<MemoryKind>,
+    /// The group of frames the walk found repeating, if it stopped because of
+    /// one ([`WalkEndReason::CycleDetected`]).
+    pub repeated_frames: Option<RepeatedFrames>,
+    /// Why the stack walk stopped, if the stack was walked.
+    pub walk_end_reason: Option<WalkEndReason>,
+    /// The registers in the extended part of the first frame's context, like
+    /// the AVX registers of an amd64 context.
+    pub extended_state: Option<MinidumpExtendedState>,
+}
+
+/// A group of frames at the bottom of a [`CallStack`] that repeated over and
+/// over in the stack walk.
+///
+/// The stack only has one copy of the group, at the end of
+/// [`CallStack::frames`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RepeatedFrames {
+    /// The index in [`CallStack::frames`] of the group's first frame.
+    pub first_frame: usize,
+    /// How many frames are in the group.
+    pub frame_count: usize,
+    /// How many times in a row the group was walked before the walk stopped.
+    pub repeated: usize,
 }
 
 impl CallStack {
@@ -253,10 +516,36 @@ impl CallStack {
             thread_id: 0,
             thread_name: None,
             last_error_value: None,
+            stack_range: None,
+            stack_memory_kind: None,
+            repeated_frames: None,
+            walk_end_reason: None,
+            extended_state: None,
         }
     }
 }
 
+/// A backtrace that was recorded at crash time by the crashing process (or
+/// whatever wrote the minidump), rather than recovered by our stack walker.
+///
+/// Comparing this against the walked [`CallStack`] of the same thread can
+/// reveal unwinder bugs or stack corruption.
+#[derive(Debug, Clone)]
+pub struct CapturedBacktrace {
+    /// Where the backtrace came from (currently always "mac_crash_info").
+    pub source: &'static str,
+    /// The thread the backtrace was captured on, if known.
+    ///
+    /// This is the raw value reported by the source, and may not be
+    /// directly comparable to [`CallStack::thread_id`].
+    pub thread_id: Option<u64>,
+    /// The symbolicated frames.
+    ///
+    /// These were never unwound, so they don't have any register state,
+    /// and all have a trust of [`FrameTrust::PreWalked`].
+    pub frames: Vec<StackFrame>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct LinuxStandardBase {
     pub id: String,
@@ -265,11 +554,160 @@ pub struct LinuxStandardBase {
     pub description: String,
 }
 
+/// The process's memory usage when the minidump was written.
+///
+/// These are the most useful fields of [`MinidumpProcessVmCounters`], for triaging
+/// out-of-memory crashes. Each is `None` if the minidump didn't have it. All sizes
+/// are in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessMemoryUsage {
+    pub working_set_size: Option<u64>,
+    pub peak_working_set_size: Option<u64>,
+    /// The commit charge (memory the system has committed for the process).
+    pub pagefile_usage: Option<u64>,
+    pub peak_pagefile_usage: Option<u64>,
+    pub private_usage: Option<u64>,
+    pub virtual_size: Option<u64>,
+    pub peak_virtual_size: Option<u64>,
+    pub page_fault_count: Option<u64>,
+}
+
+impl ProcessMemoryUsage {
+    /// Extract the key memory usage counters from `counters`.
+    pub fn from_vm_counters(counters: &MinidumpProcessVmCounters) -> ProcessMemoryUsage {
+        ProcessMemoryUsage {
+            working_set_size: counters.working_set_size(),
+            peak_working_set_size: counters.peak_working_set_size(),
+            pagefile_usage: counters.pagefile_usage(),
+            peak_pagefile_usage: counters.peak_pagefile_usage(),
+            private_usage: counters.private_usage(),
+            virtual_size: counters.virtual_size(),
+            peak_virtual_size: counters.peak_virtual_size(),
+            page_fault_count: counters.page_fault_count(),
+        }
+    }
+}
+
+/// The most useful fields of the process's `/proc/self/status` on Linux, from
+/// [`MinidumpLinuxProcStatus`], for triaging out-of-memory crashes.
+///
+/// Each is `None` if the file didn't have it. All sizes are in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinuxProcStatus {
+    /// The resident set size (`VmRSS`).
+    pub vm_rss: Option<u64>,
+    /// The peak virtual memory size (`VmPeak`).
+    pub vm_peak: Option<u64>,
+    /// The number of threads in the process.
+    pub threads: Option<u32>,
+}
+
+impl LinuxProcStatus {
+    /// Extract the key fields from `status`.
+    pub fn from_proc_status(status: &MinidumpLinuxProcStatus) -> LinuxProcStatus {
+        LinuxProcStatus {
+            vm_rss: status.vm_rss(),
+            vm_peak: status.vm_peak(),
+            threads: status.threads(),
+        }
+    }
+}
+
+/// A shared library the dynamic linker had loaded, but that is missing from the
+/// minidump's module list.
+///
+/// See [`ProcessState::unlisted_modules`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnlistedModule {
+    /// The path of the library.
+    pub name: String,
+    /// The library's load address, from its `link_map` entry.
+    pub load_address: u64,
+}
+
+/// A region of the process's address space.
+///
+/// See [`ProcessState::memory_regions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryRegion {
+    /// The first address in the region.
+    pub base_address: u64,
+    /// The size of the region, in bytes.
+    pub size: u64,
+    /// What the region was used for.
+    pub kind: MemoryKind,
+    /// The state of the region's pages (Windows only).
+    pub state: Option<format::MemoryState>,
+    /// The access protection of the region's pages (Windows only).
+    pub protection: Option<format::MemoryProtection>,
+    /// What kind of mapping the region is (Windows only).
+    pub memory_type: Option<format::MemoryType>,
+    /// The region's permissions as `/proc/self/maps` writes them, like
+    /// `"r-xp"` (Linux only).
+    pub permissions: Option<String>,
+}
+
+impl MemoryRegion {
+    /// Whether the region is free address space (`MEM_FREE`).
+    pub fn is_free(&self) -> bool {
+        matches!(self.state, Some(state) if state.contains(format::MemoryState::MEM_FREE))
+    }
+
+    /// The addresses the region covers.
+    pub fn range(&self) -> Range<u64> {
+        self.base_address..self.base_address.saturating_add(self.size)
+    }
+}
+
+/// What a [`MemoryDump`] is of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryDumpKind {
+    /// The memory around the crashing thread's instruction pointer.
+    InstructionPointer,
+    /// The memory starting at the crashing thread's stack pointer.
+    StackPointer,
+}
+
+impl MemoryDumpKind {
+    /// A description of what the dump is of, for the human output.
+    pub fn description(&self) -> &'static str {
+        match self {
+            MemoryDumpKind::InstructionPointer => "Memory around instruction pointer",
+            MemoryDumpKind::StackPointer => "Memory at stack pointer",
+        }
+    }
+}
+
+/// Raw memory near an interesting address of the crashing thread, from
+/// [`ProcessState::crash_memory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryDump {
+    /// What the memory is near.
+    pub kind: MemoryDumpKind,
+    /// The interesting address (the value of the register).
+    pub address: u64,
+    /// The address of the first byte in `bytes`.
+    pub start: u64,
+    /// The memory, with `None` for bytes the minidump doesn't have.
+    pub bytes: Vec<Option<u8>>,
+    /// Where addresses related to the dump are in modules, as `module+offset`,
+    /// keyed by the address in the dump they're for.
+    ///
+    /// For the instruction pointer this is the instruction pointer itself,
+    /// for the stack pointer any pointer-sized values on the stack that point
+    /// into a module.
+    pub annotations: BTreeMap<u64, String>,
+}
+
+/// How many bytes of memory to put in a [`MemoryDump`].
+pub const MEMORY_DUMP_SIZE: usize = 128;
+
 /// Info about an exception that may have occurred
 ///
 /// May not be available if the minidump wasn't triggered by an exception, or if required
 /// info about the exception is missing
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ExceptionInfo {
     /// a `CrashReason` describing the crash reason.
     pub reason: CrashReason,
@@ -284,10 +722,301 @@ pub struct ExceptionInfo {
     pub instruction_str: Option<String>,
     /// A list of memory accesses performed by crashing instruction (if available)
     pub memory_accesses: Option<Vec<MemoryAccess>>,
+    /// What the memory at `address` was used for, if the minidump has memory info.
+    pub address_memory_kind: Option<MemoryKind>,
+    /// The crash address, or an address the crashing instruction accessed,
+    /// if it matches a value allocators poison memory with.
+    pub poisoned_address: Option<PoisonedAddress>,
+    /// The crash as a null pointer dereference, if it's a memory access fault
+    /// at an address below
+    /// [`ProcessorOptions::null_deref_threshold`][crate::ProcessorOptions::null_deref_threshold].
+    pub null_deref: Option<NullDeref>,
+    /// The register of the crashing context that the crash address was
+    /// probably computed from, if it's a memory access fault: the one closest
+    /// below the address, if it's at most a page below. For null pointer
+    /// dereferences this is [`NullDeref::register`].
+    pub address_register: Option<AddressRegister>,
+    /// Which region of memory the crash address is in, if the minidump has
+    /// enough information to say.
+    pub crash_address_details: Option<CrashAddressDetails>,
+    /// The raw exception code of the exception stream's record.
+    pub code: u32,
+    /// The raw exception flags of the exception stream's record.
+    pub flags: u32,
+    /// The raw parameters of the exception stream's record (see
+    /// [`MinidumpException::parameters`]).
+    pub parameters: Vec<u64>,
+    /// The decoded parameters of the exception, for Windows exceptions that document them.
+    pub windows_parameters: Option<WindowsExceptionParameters>,
+    /// Every record of the exception's chain of nested exceptions, from the
+    /// exception stream's own record to the innermost one, if it has any
+    /// nested records (see [`MinidumpException::nested_exceptions`]).
+    ///
+    /// When there are some, `reason`, `address` and `windows_parameters`
+    /// describe the innermost record with a nonzero exception code.
+    pub nested_exceptions: Vec<NestedException>,
+}
+
+/// An address that matches a well-known [`PoisonPattern`], from
+/// [`ExceptionInfo::poisoned_address`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoisonedAddress {
+    /// The address.
+    pub address: u64,
+    /// The pattern it matches.
+    pub pattern: &'static PoisonPattern,
+}
+
+impl PoisonedAddress {
+    /// A description of the address for the human output.
+    pub fn description(&self) -> String {
+        format!(
+            "{:#x} looks like {}",
+            self.address, self.pattern.description
+        )
+    }
+}
+
+/// A crash accessing an address near null, from [`ExceptionInfo::null_deref`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NullDeref {
+    /// The address that was accessed, which is the offset from null.
+    pub offset: u64,
+    /// The kind of access, if known.
+    pub access: Option<minidump_common::errors::ExceptionCodeWindowsAccessType>,
+    /// The register that probably held the null pointer, if any register of
+    /// the crashing context is at or a little below the crash address.
+    pub register: Option<AddressRegister>,
+}
+
+/// A register that probably held the pointer a crash address was computed
+/// from, from [`ExceptionInfo::address_register`] and [`NullDeref::register`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressRegister {
+    /// The name of the register.
+    pub name: &'static str,
+    /// The value of the register.
+    pub value: u64,
+}
+
+impl AddressRegister {
+    /// A description of `address` in terms of this register, like
+    /// "rax+0x38 where rax == 0x0".
+    pub fn description(&self, address: u64) -> String {
+        format!(
+            "{}+{:#x} where {} == {:#x}",
+            self.name,
+            address - self.value,
+            self.name,
+            self.value
+        )
+    }
+}
+
+impl NullDeref {
+    /// A description of the dereference for the human output, like
+    /// "read at offset 0x38 (rax+0x38 where rax == 0x0)".
+    pub fn description(&self) -> String {
+        use minidump_common::errors::ExceptionCodeWindowsAccessType::*;
+        let access = match self.access {
+            Some(READ) => "read",
+            Some(WRITE) => "write",
+            Some(EXEC) => "execute",
+            None => "access",
+        };
+        let mut description = format!("{} at offset {:#x}", access, self.offset);
+        if let Some(register) = self.register {
+            description += &format!(" ({})", register.description(self.offset));
+        }
+        description
+    }
+}
+
+/// Where the crash address is, from [`ExceptionInfo::crash_address_details`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashAddressDetails {
+    /// The kind of region the crash address is in.
+    pub region: CrashAddressRegion,
+    /// The protection of the memory at the crash address, if it's mapped and
+    /// the minidump has memory info.
+    pub protection: Option<RegionProtection>,
+    /// The closest mapped memory, if the crash address is unmapped.
+    pub nearest_mapped: Option<NearestMapping>,
+    /// Whether the crash was an execute fault in memory that's mapped, but
+    /// not executable.
+    pub executed_non_executable: bool,
+}
+
+/// The kind of region the crash address is in, from [`CrashAddressDetails::region`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrashAddressRegion {
+    /// A loaded module's image.
+    Module {
+        /// The name of the module.
+        name: String,
+        /// The offset of the crash address in the module.
+        offset: u64,
+    },
+    /// A thread's stack, and which thread's if known.
+    Stack { thread_id: Option<u32> },
+    /// The heap, or anonymous memory (which is mostly heap).
+    Heap,
+    /// Any other mapping, like a mapped file or a guard page.
+    Mapped,
+    /// Nothing is mapped at the crash address.
+    Unmapped,
+}
+
+impl CrashAddressRegion {
+    /// The name of this kind of region in the JSON output, like `"module"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrashAddressRegion::Module { .. } => "module",
+            CrashAddressRegion::Stack { .. } => "stack",
+            CrashAddressRegion::Heap => "heap",
+            CrashAddressRegion::Mapped => "mapped",
+            CrashAddressRegion::Unmapped => "unmapped",
+        }
+    }
+}
+
+/// Whether some memory was readable, writable, and executable.
+///
+/// Displays like `/proc/self/maps` does, like `r-x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegionProtection {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl fmt::Display for RegionProtection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flag = |set, c| if set { c } else { '-' };
+        write!(
+            f,
+            "{}{}{}",
+            flag(self.read, 'r'),
+            flag(self.write, 'w'),
+            flag(self.execute, 'x')
+        )
+    }
+}
+
+/// The mapped memory closest to an unmapped crash address, from
+/// [`CrashAddressDetails::nearest_mapped`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NearestMapping {
+    /// The mapped address closest to the crash address: the start of a
+    /// mapping above it, or the last address of one below it.
+    pub address: u64,
+    /// How far the crash address is from `address`.
+    pub distance: u64,
+}
+
+impl CrashAddressDetails {
+    /// A description of where the crash address is for the human output,
+    /// like "module foo.dll+0x1234 (r-x)".
+    pub fn description(&self, crash_address: u64) -> String {
+        let mut description = match &self.region {
+            CrashAddressRegion::Module { name, offset } => {
+                format!("module {}+{:#x}", name, offset)
+            }
+            CrashAddressRegion::Stack {
+                thread_id: Some(thread_id),
+            } => format!("stack of thread {:#x}", thread_id),
+            region => region.name().to_string(),
+        };
+        if let Some(protection) = self.protection {
+            description += &format!(" ({})", protection);
+        }
+        if let Some(nearest) = self.nearest_mapped {
+            let direction = if nearest.address > crash_address {
+                "below"
+            } else {
+                "above"
+            };
+            description += &format!(
+                ", {:#x} bytes {} the nearest mapped memory at {:#x}",
+                nearest.distance, direction, nearest.address
+            );
+        }
+        if self.executed_non_executable {
+            description += ", executed but not executable";
+        }
+        description
+    }
+}
+
+/// One record of a chain of nested exceptions, from [`ExceptionInfo::nested_exceptions`].
+#[derive(Debug, Clone)]
+pub struct NestedException {
+    /// The raw exception code.
+    pub code: u32,
+    /// The raw exception flags.
+    pub flags: u32,
+    /// A `CrashReason` describing the exception.
+    pub reason: CrashReason,
+    /// The memory address implicated in the exception, like [`ExceptionInfo::address`].
+    pub address: u64,
+}
+
+/// A crashing thread picked without the help of an exception stream, from
+/// [`ProcessState::crashing_thread_guess`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashingThreadGuess {
+    /// The id of the thread that was picked.
+    pub thread_id: u32,
+    /// Every reason the thread looks like it crashed (at least one).
+    pub reasons: Vec<CrashingThreadGuessReason>,
+}
+
+/// Why a thread looks like it crashed, from [`CrashingThreadGuess::reasons`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrashingThreadGuessReason {
+    /// The thread's instruction pointer is a tiny address, like a call
+    /// through a null function pointer would leave it at.
+    PcNearNull,
+    /// The thread's instruction pointer isn't in executable memory.
+    PcNotExecutable,
+    /// The thread is in this function that aborts the process, like `abort`
+    /// or `__assert_fail`.
+    InAbortFunction { function: String },
+    /// The thread's stack has a frame in this signal handler trampoline or
+    /// exception dispatcher, so the thread was handling a fault.
+    SignalHandlerFrame { function: String },
+}
+
+impl CrashingThreadGuessReason {
+    /// The name of this reason in the JSON output, like `"pc_not_executable"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrashingThreadGuessReason::PcNearNull => "pc_near_null",
+            CrashingThreadGuessReason::PcNotExecutable => "pc_not_executable",
+            CrashingThreadGuessReason::InAbortFunction { .. } => "in_abort_function",
+            CrashingThreadGuessReason::SignalHandlerFrame { .. } => "signal_handler_frame",
+        }
+    }
+}
+
+impl fmt::Display for CrashingThreadGuessReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrashingThreadGuessReason::PcNearNull => f.write_str("instruction pointer near null"),
+            CrashingThreadGuessReason::PcNotExecutable => {
+                f.write_str("instruction pointer not executable")
+            }
+            CrashingThreadGuessReason::InAbortFunction { function } => write!(f, "in {}", function),
+            CrashingThreadGuessReason::SignalHandlerFrame { function } => {
+                write!(f, "handling a signal in {}", function)
+            }
+        }
+    }
 }
 
 /// The state of a process as recorded by a `Minidump`.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ProcessState {
     /// The PID of the process.
     pub process_id: Option<u32>,
@@ -295,12 +1024,43 @@ pub struct ProcessState {
     pub time: SystemTime,
     /// When the process started, if available
     pub process_create_time: Option<SystemTime>,
+    /// Where [`ProcessState::process_create_time`] came from.
+    pub process_create_time_source: Option<ProcessCreateTimeSource>,
     /// Known code signing certificates (module name => cert name)
     pub cert_info: HashMap<String, String>,
+    /// Annotations from [`ProcessorOptions::extra_info`][crate::ProcessorOptions::extra_info]
+    /// for loaded and unloaded modules (module name => annotations).
+    pub module_annotations: HashMap<String, BTreeMap<String, String>>,
+    /// Fields from [`ProcessorOptions::extra_info`][crate::ProcessorOptions::extra_info]
+    /// to add to the top level of the JSON output.
+    pub extra_json: serde_json::Map<String, serde_json::Value>,
     /// Info about the exception that triggered the dump (if one did)
     pub exception_info: Option<ExceptionInfo>,
     /// A string describing an assertion that was hit, if present.
     pub assertion: Option<String>,
+    /// The first function in the crashing thread's stack that never returns
+    /// (see [`ProcessorOptions::noreturn_functions`][crate::ProcessorOptions::noreturn_functions]),
+    /// if any.
+    ///
+    /// This suggests the crash was an assertion, panic or abort rather than
+    /// a hardware fault.
+    pub likely_abort: Option<String>,
+    /// How much the crashing thread's stack looks like a return-oriented-programming
+    /// chain, if [`ProcessorOptions::detect_rop_chains`][crate::ProcessorOptions::detect_rop_chains]
+    /// is set and there is a crashing thread.
+    pub rop_chain: Option<RopChainAnalysis>,
+    /// How likely the crash is to be exploitable, if
+    /// [`ProcessorOptions::analyze_exploitability`][crate::ProcessorOptions::analyze_exploitability]
+    /// is set and there is an exception and a crashing thread.
+    pub exploitability: Option<ExploitabilityAnalysis>,
+    /// Why the crash looks like the crashing thread ran out of stack, if it
+    /// does (or if its stack is deeply recursive, see
+    /// [`StackOverflowAnalysis::is_stack_overflow`]).
+    pub stack_overflow: Option<StackOverflowAnalysis>,
+    /// The AddressSanitizer report found in the captured memory, if
+    /// [`ProcessorOptions::extract_sanitizer_report`][crate::ProcessorOptions::extract_sanitizer_report]
+    /// is set and there is one.
+    pub sanitizer_report: Option<SanitizerReport>,
     /// The index of the thread that requested a dump be written.
     /// If a dump was produced as a result of a crash, this
     /// will point to the thread that crashed.  If the dump was produced as
@@ -310,6 +1070,16 @@ pub struct ProcessState {
     /// extended Breakpad information is present, this field will be
     /// `None`.
     pub requesting_thread: Option<usize>,
+    /// Whether [`requesting_thread`][Self::requesting_thread] was found by
+    /// matching the exception context against each thread's context, because
+    /// the exception stream's thread id didn't match any thread.
+    pub crashing_thread_heuristic: bool,
+    /// How [`requesting_thread`][Self::requesting_thread] was picked, if it
+    /// was guessed from the threads' states because the minidump has no
+    /// usable exception stream.
+    ///
+    /// See [`ProcessorOptions::guess_crashing_thread`][crate::ProcessorOptions::guess_crashing_thread].
+    pub crashing_thread_guess: Option<CrashingThreadGuess>,
     /// Stacks for each thread (except possibly the exception handler
     /// thread) at the time of the crash.
     pub threads: Vec<CallStack>,
@@ -319,17 +1089,59 @@ pub struct ProcessState {
     pub system_info: SystemInfo,
     /// Linux Standard Base Info
     pub linux_standard_base: Option<LinuxStandardBase>,
+    /// The records of the macOS `__DATA,__crash_info` sections of the
+    /// process's modules, if the minidump has them.
+    ///
+    /// The messages in them are often the best explanation of the crash there
+    /// is; see [`ProcessState::mac_crash_messages`].
     pub mac_crash_info: Option<Vec<RawMacCrashInfo>>,
+    /// The kernel boot arguments of the macOS system the minidump was
+    /// written on, if the minidump has them.
+    pub mac_boot_args: Option<String>,
+    /// Crashpad's annotations for the process and its modules, if the minidump
+    /// was written by crashpad.
+    pub crashpad_info: Option<MinidumpCrashpadInfo>,
+    /// The process's memory usage, if the minidump has a `ProcessVmCountersStream`.
+    pub process_memory_usage: Option<ProcessMemoryUsage>,
+    /// Key fields of the process's `/proc/self/status`, if the minidump has a
+    /// `LinuxProcStatus` stream.
+    pub linux_proc_status: Option<LinuxProcStatus>,
+    /// Backtraces that were captured at crash time and stored in the minidump.
+    pub captured_backtraces: Vec<CapturedBacktrace>,
     /// The modules that were loaded into the process represented by the
     /// `ProcessState`.
     pub modules: MinidumpModuleList,
     pub unloaded_modules: MinidumpUnloadedModuleList,
+    /// Shared libraries in the dynamic linker's list (from the `LinuxDsoDebug` stream)
+    /// that are missing from [`modules`][Self::modules], like ones that were still
+    /// being loaded.
+    pub unlisted_modules: Vec<UnlistedModule>,
+    /// The process's memory map, from the minidump's memory info list (on
+    /// Windows) or `/proc/self/maps` (on Linux), in order by address.
+    pub memory_regions: Vec<MemoryRegion>,
     // modules_without_symbols
     // modules_with_corrupt_symbols
     // exploitability
     pub unknown_streams: Vec<MinidumpUnknownStream>,
     pub unimplemented_streams: Vec<MinidumpUnimplementedStream>,
     pub symbol_stats: HashMap<String, SymbolStats>,
+    /// Raw memory around the crashing thread's instruction and stack
+    /// pointers, if
+    /// [`ProcessorOptions::dump_crash_memory`][crate::ProcessorOptions::dump_crash_memory]
+    /// is set.
+    pub crash_memory: Vec<MemoryDump>,
+    /// Whether [`ProcessState::print_json`] includes `symbol_stats`, from
+    /// [`ProcessorOptions::report_symbol_stats`][crate::ProcessorOptions::report_symbol_stats].
+    pub report_symbol_stats: bool,
+    /// Whether [`ProcessState::print_json`] includes `memory_info`, from
+    /// [`ProcessorOptions::report_memory_info`][crate::ProcessorOptions::report_memory_info].
+    pub report_memory_info: bool,
+    /// Whether [`ProcessState::print_json`] includes the registers of every
+    /// frame, from
+    /// [`ProcessorOptions::report_frame_registers`][crate::ProcessorOptions::report_frame_registers].
+    pub report_frame_registers: bool,
+    /// Structural problems with the minidump, from [`Minidump::check_integrity`][minidump::Minidump::check_integrity].
+    pub integrity_issues: Vec<CorruptionIssue>,
 }
 
 impl FrameTrust {
@@ -340,8 +1152,9 @@ impl FrameTrust {
             FrameTrust::Context => "given as instruction pointer in context",
             FrameTrust::PreWalked => "recovered by external stack walker",
             FrameTrust::CallFrameInfo => "call frame info",
-            FrameTrust::CfiScan => "call frame info with scanning",
+            FrameTrust::CallFrameInfoLowConfidence => "call frame info (implausible registers)",
             FrameTrust::FramePointer => "previous frame's frame pointer",
+            FrameTrust::CfiScan => "call frame info with scanning",
             FrameTrust::Scan => "stack scanning",
             FrameTrust::None => "unknown",
         }
@@ -352,8 +1165,9 @@ impl FrameTrust {
             FrameTrust::Context => "context",
             FrameTrust::PreWalked => "prewalked",
             FrameTrust::CallFrameInfo => "cfi",
-            FrameTrust::CfiScan => "cfi_scan",
+            FrameTrust::CallFrameInfoLowConfidence => "cfi_low_confidence",
             FrameTrust::FramePointer => "frame_pointer",
+            FrameTrust::CfiScan => "cfi_scan",
             FrameTrust::Scan => "scan",
             FrameTrust::None => "non",
         }
@@ -369,7 +1183,10 @@ impl StackFrame {
             resume_address: context.get_instruction_pointer(),
             module: None,
             unloaded_modules: BTreeMap::new(),
+            unloaded_module_candidates: Vec::new(),
+            unloaded_module_symbols: None,
             function_name: None,
+            normalized_function_name: None,
             function_base: None,
             parameter_size: None,
             source_file_name: None,
@@ -377,6 +1194,10 @@ impl StackFrame {
             source_line_base: None,
             inlines: Vec::new(),
             arguments: None,
+            warnings: Vec::new(),
+            noreturn: false,
+            symbol_miss_reason: None,
+            annotations: BTreeMap::new(),
             trust,
             context,
         }
@@ -403,6 +1224,7 @@ impl FrameSymbolizer for StackFrame {
     fn add_inline_frame(&mut self, name: &str, file: Option<&str>, line: Option<u32>) {
         self.inlines.push(InlineFrame {
             function_name: name.to_string(),
+            normalized_function_name: None,
             source_file_name: file.map(ToString::to_string),
             source_line: line,
         })
@@ -420,18 +1242,45 @@ fn print_registers<T: Write>(f: &mut T, ctx: &MinidumpContext) -> io::Result<()>
     };
 
     // Iterate over registers in a known order.
+    let valid = ctx
+        .general_purpose_registers()
+        .iter()
+        .filter(|reg| registers.contains(*reg));
+    print_register_values(f, |reg| ctx.format_register(reg), valid)
+}
+
+fn print_fp_registers<T: Write>(
+    f: &mut T,
+    ctx: &MinidumpContext,
+    extended_state: Option<&MinidumpExtendedState>,
+) -> io::Result<()> {
+    print_register_values(
+        f,
+        |reg| ctx.format_register(reg),
+        ctx.fp_register_names().iter(),
+    )?;
+    if let Some(state) = extended_state {
+        let format = |reg: &str| state.format_register(reg).unwrap_or_default();
+        print_register_values(f, format, state.register_names().iter())?;
+    }
+    Ok(())
+}
+
+fn print_register_values<'a, T: Write>(
+    f: &mut T,
+    format_register: impl Fn(&str) -> String,
+    registers: impl Iterator<Item = &'a &'a str>,
+) -> io::Result<()> {
     let mut output = String::new();
-    for reg in ctx.general_purpose_registers() {
-        if registers.contains(reg) {
-            let reg_val = ctx.format_register(reg);
-            let next = format!(" {: >6} = {}", reg, reg_val);
-            if output.chars().count() + next.chars().count() > 80 {
-                // Flush the buffer.
-                writeln!(f, " {}", output)?;
-                output.truncate(0);
-            }
-            output.push_str(&next);
+    for reg in registers {
+        let reg_val = format_register(reg);
+        let next = format!(" {: >6} = {}", reg, reg_val);
+        if output.chars().count() + next.chars().count() > 80 {
+            // Flush the buffer.
+            writeln!(f, " {}", output)?;
+            output.truncate(0);
         }
+        output.push_str(&next);
     }
     if !output.is_empty() {
         writeln!(f, " {}", output)?;
@@ -459,6 +1308,56 @@ fn json_registers(ctx: &MinidumpContext) -> serde_json::Value {
     json!(output)
 }
 
+fn windows_parameters_json(
+    params: &WindowsExceptionParameters,
+    json_hex: impl Fn(u64) -> String,
+) -> serde_json::Value {
+    match *params {
+        WindowsExceptionParameters::AccessViolation { access, address } => json!({
+            "kind": "access_violation",
+            // optional
+            "access": access.map(|access| format!("{:?}", access)),
+            // optional
+            "address": address.map(json_hex),
+        }),
+        WindowsExceptionParameters::InPageError {
+            access,
+            address,
+            nt_status,
+        } => json!({
+            "kind": "in_page_error",
+            // optional
+            "access": access.map(|access| format!("{:?}", access)),
+            "address": json_hex(address),
+            "nt_status": format!("{:#010x}", nt_status),
+            // optional
+            "nt_status_name": params.nt_status().map(|status| format!("{:?}", status)),
+        }),
+        WindowsExceptionParameters::FastFail { code } => json!({
+            "kind": "fast_fail",
+            "fast_fail_code": code,
+            // optional
+            "fast_fail_name": params.fast_fail_code().map(|code| format!("{:?}", code)),
+        }),
+    }
+}
+
+fn memory_kind_json(kind: &MemoryKind) -> serde_json::Value {
+    let (thread_id, path) = match kind {
+        MemoryKind::Stack { thread_id } => (*thread_id, None),
+        MemoryKind::MappedFile(path) => (None, path.as_deref()),
+        _ => (None, None),
+    };
+    json!({
+        // stack | heap | mapped_file | anonymous | guard | unmapped
+        "kind": kind.name(),
+        // optional, for stacks
+        "thread_id": thread_id,
+        // optional, for mapped files
+        "path": path,
+    })
+}
+
 impl CallStack {
     /// Create a `CallStack` with `info` and no frames.
     pub fn with_info(id: u32, info: CallStackInfo) -> CallStack {
@@ -468,7 +1367,24 @@ impl CallStack {
             thread_id: id,
             thread_name: None,
             last_error_value: None,
+            stack_range: None,
+            stack_memory_kind: None,
+            repeated_frames: None,
+            walk_end_reason: None,
+            extended_state: None,
+        }
+    }
+
+    /// The modules this stack's frames are in, in order by address.
+    ///
+    /// Inline frames don't need to be considered, as they're always in the
+    /// same module as the frame they're inlined into.
+    pub fn referenced_modules(&self) -> Vec<&MinidumpModule> {
+        let mut modules = BTreeMap::new();
+        for module in self.frames.iter().filter_map(|frame| frame.module.as_ref()) {
+            modules.entry(module.base_address()).or_insert(module);
         }
+        modules.into_values().collect()
     }
 
     /// Write a human-readable description of the call stack to `f`.
@@ -476,11 +1392,22 @@ impl CallStack {
     /// This is very verbose, it implements the output format used by
     /// minidump_stackwalk.
     pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        if self.frames.is_empty() {
+        Self::print_frames(f, &self.frames, false, None)
+    }
+
+    /// Print `frames`, with their floating point and vector registers if
+    /// `fp_registers` is set. `extended_state` belongs to the first frame.
+    fn print_frames<T: Write>(
+        f: &mut T,
+        frames: &[StackFrame],
+        fp_registers: bool,
+        mut extended_state: Option<&MinidumpExtendedState>,
+    ) -> io::Result<()> {
+        if frames.is_empty() {
             writeln!(f, "<no frames>")?;
         }
         let mut frame_count = 0;
-        for frame in &self.frames {
+        for frame in frames {
             // First print out inlines
             for inline in &frame.inlines {
                 // Frame number
@@ -545,17 +1472,41 @@ impl CallStack {
                     // We didn't have a function name, so just give a byte offset from the module
                     write!(f, " + {:#x}", addr - module.base_address())?;
                 }
+            } else if let (Some(unloaded), Some(func_name), Some(func_base)) = (
+                &frame.unloaded_module_symbols,
+                &frame.function_name,
+                &frame.function_base,
+            ) {
+                // The address is in an unloaded module we had the symbols of
+                write!(
+                    f,
+                    "{:#x} (in unloaded {}!{}",
+                    addr, unloaded.name, func_name
+                )?;
+                if let (Some(src_file), Some(src_line), Some(src_base)) = (
+                    &frame.source_file_name,
+                    &frame.source_line,
+                    &frame.source_line_base,
+                ) {
+                    write!(
+                        f,
+                        " [{} : {} + {:#x}]",
+                        basename(src_file),
+                        src_line,
+                        addr - src_base
+                    )?;
+                } else {
+                    write!(f, " + {:#x}", addr - func_base)?;
+                }
+                write!(f, ")")?;
             } else {
                 // We didn't even find a module, so just print the raw address
                 write!(f, "{:#x}", addr)?;
 
-                // List off overlapping unloaded modules.
-
-                // First we need to collect them up by name so that we can print
-                // all the overlaps from one module together and dedupe them.
-                // (!!! was that code deleted?)
+                // List off overlapping unloaded modules, with all the offsets in
+                // each one together.
                 for (name, offsets) in &frame.unloaded_modules {
-                    write!(f, " (unloaded {}@", name)?;
+                    write!(f, " (in unloaded {} + ", name)?;
                     let mut first = true;
                     for offset in offsets {
                         if first {
@@ -573,9 +1524,22 @@ impl CallStack {
             // Print the valid registers
             writeln!(f)?;
             print_registers(f, &frame.context)?;
+            if let Some(flags) = frame.context.decoded_flags() {
+                writeln!(f, "    Flags: {}", flags)?;
+            }
+            let extended_state = extended_state.take();
+            if fp_registers {
+                print_fp_registers(f, &frame.context, extended_state)?;
+            }
 
             // And the trust we have of this result
             writeln!(f, "    Found by: {}", frame.trust.description())?;
+            for warning in &frame.warnings {
+                writeln!(f, "    Warning: {}", warning)?;
+            }
+            for (key, value) in &frame.annotations {
+                writeln!(f, "    Annotation: {} = {}", key, value)?;
+            }
 
             // Now print out recovered args
             if let Some(args) = &frame.arguments {
@@ -591,6 +1555,8 @@ impl CallStack {
                     CallingConvention::OtherThisCall => {
                         "non-windows thiscall [C++ member function]"
                     }
+                    CallingConvention::Amd64SystemV => "the System V x86-64 calling convention",
+                    CallingConvention::Amd64Windows => "the Windows x64 calling convention",
                 };
 
                 writeln!(f, "    Arguments (assuming {})", cc_summary)?;
@@ -614,6 +1580,79 @@ impl CallStack {
     }
 }
 
+/// Note that `stack`'s walk was cut short, if it was.
+fn print_truncation<T: Write>(f: &mut T, stack: &CallStack) -> io::Result<()> {
+    let reason = match stack.walk_end_reason {
+        Some(reason) => reason,
+        None => return Ok(()),
+    };
+    match reason {
+        WalkEndReason::MaxFramesReached => {
+            writeln!(f, "<stopped walking after {} frames>", stack.frames.len())
+        }
+        WalkEndReason::WalkTimedOut => writeln!(
+            f,
+            "<stopped walking after {} frames: timed out>",
+            stack.frames.len()
+        ),
+        WalkEndReason::ScanLimitReached => writeln!(
+            f,
+            "<stopped walking after {} frames: too many frames found by scanning in a row>",
+            stack.frames.len()
+        ),
+        WalkEndReason::CycleDetected => match &stack.repeated_frames {
+            Some(repeated) => writeln!(
+                f,
+                "<stopped walking: frames {}-{} repeated {} times, the repeats were elided>",
+                repeated.first_frame,
+                repeated.first_frame + repeated.frame_count - 1,
+                repeated.repeated
+            ),
+            None => writeln!(f, "<stopped walking: frames repeated>"),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Write `dump` as a hexdump, 16 bytes to a line.
+fn print_memory_dump<T: Write>(f: &mut T, dump: &MemoryDump) -> io::Result<()> {
+    writeln!(f, "{} ({:#x}):", dump.kind.description(), dump.address)?;
+    for (row, bytes) in dump.bytes.chunks(16).enumerate() {
+        let row_start = dump.start + row as u64 * 16;
+        write!(f, "  {:#018x} ", row_start)?;
+        if bytes.iter().all(Option::is_none) {
+            writeln!(f, " <not in minidump>")?;
+            continue;
+        }
+        for byte in bytes {
+            match byte {
+                Some(byte) => write!(f, " {:02x}", byte)?,
+                None => write!(f, " ??")?,
+            }
+        }
+        write!(f, "{:width$}  ", "", width = (16 - bytes.len()) * 3)?;
+        for byte in bytes {
+            let c = match byte {
+                Some(byte) if byte.is_ascii_graphic() || *byte == b' ' => *byte as char,
+                Some(_) => '.',
+                None => ' ',
+            };
+            write!(f, "{}", c)?;
+        }
+        let row_end = row_start + bytes.len() as u64;
+        let annotations = dump
+            .annotations
+            .range(row_start..row_end)
+            .map(|(_, annotation)| annotation.as_str())
+            .collect::<Vec<_>>();
+        if !annotations.is_empty() {
+            write!(f, "  {}", annotations.join(", "))?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
 fn eq_some<T: PartialEq>(opt: Option<T>, val: T) -> bool {
     match opt {
         Some(v) => v == val,
@@ -621,35 +1660,154 @@ fn eq_some<T: PartialEq>(opt: Option<T>, val: T) -> bool {
     }
 }
 
+/// The longest a [`ProcessState::mac_crash_messages`] message can be, in
+/// bytes, before it's cut short.
+pub const MAX_MAC_CRASH_MESSAGE_LEN: usize = 1024;
+
 impl ProcessState {
+    /// The messages (like an abort message or a dyld error) in
+    /// [`ProcessState::mac_crash_info`].
+    ///
+    /// Several modules often record the same message, so each message is only
+    /// included once. Messages longer than [`MAX_MAC_CRASH_MESSAGE_LEN`] are
+    /// cut short, and end in "...".
+    pub fn mac_crash_messages(&self) -> Vec<String> {
+        let mut messages = Vec::<String>::new();
+        let records = self.mac_crash_info.iter().flatten();
+        for message in records.flat_map(|record| [record.message(), record.message2()]) {
+            let message = match message.map(str::trim) {
+                Some(message) if !message.is_empty() => message,
+                _ => continue,
+            };
+            let message = if message.len() > MAX_MAC_CRASH_MESSAGE_LEN {
+                let mut end = MAX_MAC_CRASH_MESSAGE_LEN;
+                while !message.is_char_boundary(end) {
+                    end -= 1;
+                }
+                format!("{}...", &message[..end])
+            } else {
+                message.to_owned()
+            };
+            if !messages.contains(&message) {
+                messages.push(message);
+            }
+        }
+        messages
+    }
+
+    /// How long the process had been running when the minidump was written,
+    /// if [`ProcessState::process_create_time`] is known.
+    pub fn uptime(&self) -> Option<Duration> {
+        let create_time = self.process_create_time?;
+        Some(self.time.duration_since(create_time).unwrap_or_default())
+    }
+
+    /// The largest block of free address space in
+    /// [`ProcessState::memory_regions`], if there's any.
+    ///
+    /// This is either a free region, or a gap between two regions. When
+    /// allocations fail, this is the most that could have been allocated in
+    /// one piece.
+    pub fn largest_free_vm_block(&self) -> Option<Range<u64>> {
+        let free = self
+            .memory_regions
+            .iter()
+            .filter(|region| region.is_free())
+            .map(MemoryRegion::range);
+        let gaps = self.memory_regions.windows(2).filter_map(|pair| {
+            let (end, next) = (pair[0].range().end, pair[1].base_address);
+            (next > end).then_some(end..next)
+        });
+        free.chain(gaps)
+            .filter(|range| !range.is_empty())
+            .max_by_key(|range| range.end - range.start)
+    }
+
     /// `true` if the minidump was written in response to a process crash.
     pub fn crashed(&self) -> bool {
         self.exception_info.is_some()
     }
+
+    /// The stack of the thread that crashed (or requested the dump), if known.
+    ///
+    /// See [`ProcessState::requesting_thread`].
+    pub fn crashing_thread(&self) -> Option<&CallStack> {
+        self.requesting_thread.and_then(|idx| self.threads.get(idx))
+    }
+
+    /// Apply a [`ProcessStatePatch`] from
+    /// [`PendingProcessorStats::patches`][crate::PendingProcessorStats::patches].
+    ///
+    /// Patches for threads or frames that don't exist are ignored.
+    pub fn apply_patch(&mut self, patch: ProcessStatePatch) {
+        match patch {
+            ProcessStatePatch::Unwalked(state) => *self = *state,
+            ProcessStatePatch::Frame(walked) => {
+                if let Some(stack) = self.threads.get_mut(walked.thread_idx) {
+                    if walked.frame_idx <= stack.frames.len() {
+                        stack.frames.truncate(walked.frame_idx);
+                        stack.frames.push(walked.frame);
+                    }
+                }
+            }
+        }
+    }
+
     /// Write a human-readable description of the process state to `f`.
     ///
     /// This is very verbose, it implements the output format used by
     /// minidump_stackwalk.
     pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        self.print_internal(f, false)
+        self.print_internal(f, false, false)
     }
 
     /// Write a brief human-readable description of the process state to `f`.
     ///
     /// Only includes the summary at the top and a backtrace of the crashing thread.
     pub fn print_brief<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        self.print_internal(f, true)
+        self.print_internal(f, true, false)
     }
 
-    fn print_internal<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
+    /// Like [`print`][Self::print], but also include the floating point and
+    /// vector registers of frames that have them.
+    ///
+    /// Currently that's only the first frame of each thread of an ARM64
+    /// minidump (see [`MinidumpContext::fp_register_names`]), or of an amd64
+    /// minidump with AVX state (see [`CallStack::extended_state`]).
+    pub fn print_with_fp_registers<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        self.print_internal(f, false, true)
+    }
+
+    fn print_internal<T: Write>(
+        &self,
+        f: &mut T,
+        brief: bool,
+        fp_registers: bool,
+    ) -> io::Result<()> {
         writeln!(f, "Operating system: {}", self.system_info.os.long_name())?;
         if let Some(ref ver) = self.system_info.format_os_version() {
             writeln!(f, "                  {}", ver)?;
         }
-        writeln!(f, "CPU: {}", self.system_info.cpu)?;
+        match self.system_info.endian {
+            scroll::Endian::Little => writeln!(f, "CPU: {}", self.system_info.cpu)?,
+            scroll::Endian::Big => writeln!(f, "CPU: {} (big-endian)", self.system_info.cpu)?,
+        }
         if let Some(ref info) = self.system_info.cpu_info {
             writeln!(f, "     {}", info)?;
         }
+        if let Some(ref cpu_features) = self.system_info.cpu_features {
+            if !cpu_features.features.is_empty() {
+                let names = cpu_features
+                    .features
+                    .iter()
+                    .map(|feature| feature.name())
+                    .collect::<Vec<_>>();
+                writeln!(f, "     features: {}", names.join(","))?;
+            }
+        }
+        if let Some(ref name) = self.system_info.cpu_microarchitecture {
+            writeln!(f, "     microarchitecture: {}", name)?;
+        }
         writeln!(
             f,
             "     {} CPU{}",
@@ -667,6 +1825,9 @@ impl ProcessState {
                 lsb.id, lsb.release, lsb.codename, lsb.description
             )?;
         }
+        if let Some(ref boot_args) = self.mac_boot_args {
+            writeln!(f, "Boot args: {}", boot_args)?;
+        }
         writeln!(f)?;
 
         if let Some(ref crash_info) = self.exception_info {
@@ -677,6 +1838,42 @@ Crash address: {:#x}
 ",
                 crash_info.reason, crash_info.address
             )?;
+            if let Some(details) = &crash_info.crash_address_details {
+                writeln!(
+                    f,
+                    "Crash address region: {}",
+                    details.description(crash_info.address)
+                )?;
+            }
+            if let Some(null_deref) = crash_info.null_deref {
+                writeln!(f, "Null pointer dereference: {}", null_deref.description())?;
+            } else if let Some(register) = crash_info.address_register {
+                writeln!(
+                    f,
+                    "Crash address register: {}",
+                    register.description(crash_info.address)
+                )?;
+            }
+            if let Some(poisoned) = crash_info.poisoned_address {
+                writeln!(f, "Crash address kind: {}", poisoned.description())?;
+            }
+            if !crash_info.nested_exceptions.is_empty() {
+                writeln!(f, "Nested exceptions:")?;
+                for (idx, nested) in crash_info.nested_exceptions.iter().enumerate() {
+                    writeln!(f, "  {}. {} at {:#x}", idx, nested.reason, nested.address)?;
+                }
+            }
+            if !brief {
+                writeln!(f, "Exception code: {:#010x}", crash_info.code)?;
+                writeln!(f, "Exception flags: {:#010x}", crash_info.flags)?;
+                if !crash_info.parameters.is_empty() {
+                    write!(f, "Exception parameters:")?;
+                    for parameter in &crash_info.parameters {
+                        write!(f, " {:#x}", parameter)?;
+                    }
+                    writeln!(f)?;
+                }
+            }
 
             if let Some(ref crashing_instruction_str) = crash_info.instruction_str {
                 writeln!(f, "Crashing instruction: `{}`", crashing_instruction_str)?;
@@ -704,6 +1901,82 @@ Crash address: {:#x}
         if let Some(ref assertion) = self.assertion {
             writeln!(f, "Assertion: {}", assertion)?;
         }
+        for message in self.mac_crash_messages() {
+            writeln!(f, "Crash info message: {}", message)?;
+        }
+        if self.crashing_thread_heuristic {
+            writeln!(
+                f,
+                "Crashing thread: found by matching the exception context (thread id was invalid)"
+            )?;
+        }
+        if let Some(guess) = &self.crashing_thread_guess {
+            let reasons = guess
+                .reasons
+                .iter()
+                .map(|reason| reason.to_string())
+                .collect::<Vec<_>>();
+            writeln!(
+                f,
+                "Crashing thread: guessed (no usable exception stream): {}",
+                reasons.join(", ")
+            )?;
+        }
+        if let Some(ref function) = self.likely_abort {
+            writeln!(f, "Likely abort: crashing thread is in {}", function)?;
+        }
+        if let Some(analysis) = self.rop_chain.as_ref().filter(|analysis| analysis.likely) {
+            writeln!(
+                f,
+                "Possible ROP chain: {} of {} frames look like gadgets",
+                analysis.gadget_frames.len(),
+                analysis.frames_examined
+            )?;
+        }
+        if let Some(ref analysis) = self.stack_overflow {
+            if analysis.is_stack_overflow() {
+                writeln!(f, "Crash type: stack overflow")?;
+            } else {
+                writeln!(f, "Crashing thread is deeply recursive")?;
+            }
+            for evidence in &analysis.evidence {
+                writeln!(f, "  {}", evidence.description())?;
+            }
+            if let Some(ref recursion) = analysis.recursion {
+                writeln!(
+                    f,
+                    "  recursion: {} frames at {}+0x{:x}{}",
+                    recursion.frames,
+                    recursion.module,
+                    recursion.offset,
+                    recursion
+                        .function_name
+                        .as_ref()
+                        .map(|name| format!(" ({})", name))
+                        .unwrap_or_default()
+                )?;
+            }
+        }
+        if let Some(ref analysis) = self.exploitability {
+            writeln!(f, "Exploitability: {}", analysis.rating.name())?;
+            for signal in &analysis.signals {
+                writeln!(f, "  {}", signal.description())?;
+            }
+        }
+        if let Some(ref report) = self.sanitizer_report {
+            writeln!(
+                f,
+                "Sanitizer report ({}, at 0x{:x}):",
+                report.kind.name(),
+                report.address
+            )?;
+            for line in report.text.lines() {
+                writeln!(f, "  {}", line)?;
+            }
+            if report.truncated {
+                writeln!(f, "  <truncated>")?;
+            }
+        }
         if let Some(ref info) = self.mac_crash_info {
             writeln!(f, "Mac Crash Info:")?;
             for (idx, record) in info.iter().enumerate() {
@@ -736,8 +2009,7 @@ Crash address: {:#x}
             }
             writeln!(f)?;
         }
-        if let Some(ref time) = self.process_create_time {
-            let uptime = self.time.duration_since(*time).unwrap_or_default();
+        if let Some(uptime) = self.uptime() {
             writeln!(f, "Process uptime: {} seconds", uptime.as_secs())?;
         } else {
             writeln!(f, "Process uptime: not available")?;
@@ -757,7 +2029,17 @@ Crash address: {:#x}
                     "requested dump, did not crash"
                 }
             )?;
-            stack.print(f)?;
+            CallStack::print_frames(
+                f,
+                &stack.frames,
+                fp_registers,
+                stack.extended_state.as_ref(),
+            )?;
+            print_truncation(f, stack)?;
+            writeln!(f)?;
+        }
+        for dump in &self.crash_memory {
+            print_memory_dump(f, dump)?;
             writeln!(f)?;
         }
 
@@ -780,7 +2062,25 @@ Crash address: {:#x}
                 i,
                 stack.thread_name.as_deref().unwrap_or("")
             )?;
-            stack.print(f)?;
+            if stack.info == CallStackInfo::NotWalked {
+                writeln!(f, "<not walked>")?;
+                continue;
+            }
+            CallStack::print_frames(
+                f,
+                &stack.frames,
+                fp_registers,
+                stack.extended_state.as_ref(),
+            )?;
+            print_truncation(f, stack)?;
+        }
+        for backtrace in &self.captured_backtraces {
+            write!(f, "\nCaptured backtrace (from {})", backtrace.source)?;
+            if let Some(thread_id) = backtrace.thread_id {
+                write!(f, " of thread {:#x}", thread_id)?;
+            }
+            writeln!(f)?;
+            CallStack::print_frames(f, &backtrace.frames, fp_registers, None)?;
         }
         write!(
             f,
@@ -809,6 +2109,17 @@ Loaded modules:
             }
             writeln!(f)?;
         }
+        if !self.unlisted_modules.is_empty() {
+            write!(
+                f,
+                "
+Modules loaded by the dynamic linker, but missing from the module list:
+"
+            )?;
+            for module in &self.unlisted_modules {
+                writeln!(f, "{:#010x}  {}", module.load_address, module.name)?;
+            }
+        }
         write!(
             f,
             "
@@ -863,6 +2174,17 @@ Unknown streams encountered:
                 )?;
             }
         }
+        if !self.integrity_issues.is_empty() {
+            write!(
+                f,
+                "
+Dump integrity problems (the minidump may be truncated or corrupt):
+"
+            )?;
+            for issue in &self.integrity_issues {
+                writeln!(f, "{}", issue)?;
+            }
+        }
         Ok(())
     }
 
@@ -877,6 +2199,102 @@ Unknown streams encountered:
         // Curry self for use in `map`
         let json_hex = |val: u64| -> String { self.json_hex(val) };
 
+        let frame_json = |idx: usize, frame: &StackFrame| {
+            let mut json = json!({
+                "frame": idx,
+                // optional
+                "module": frame.module.as_ref().map(|module| basename(&module.name)),
+                // optional
+                "function": frame.function_name,
+                // optional
+                "normalized_function": frame.normalized_function_name,
+                // optional
+                "file": frame.source_file_name,
+                // optional
+                "line": frame.source_line,
+                "offset": json_hex(frame.instruction),
+                // optional
+                "inlines": if !frame.inlines.is_empty() {
+                    Some(frame.inlines.iter().map(|frame| {
+                        json!({
+                            "function": frame.function_name,
+                            // optional
+                            "normalized_function": frame.normalized_function_name,
+                            "file": frame.source_file_name,
+                            "line": frame.source_line,
+                        })
+                    }).collect::<Vec<_>>())
+                } else {
+                    None
+                },
+                // optional
+                "module_offset": frame
+                    .module
+                    .as_ref()
+                    .map(|module| frame.instruction - module.raw.base_of_image)
+                    .map(json_hex),
+                // optional
+                "unloaded_modules": if frame.unloaded_modules.is_empty() {
+                    None
+                } else {
+                    Some(frame.unloaded_modules.iter().map(|(module, offsets)| json!({
+                        "module": module,
+                        "offsets": offsets.iter().copied().map(json_hex).collect::<Vec<_>>(),
+                    })).collect::<Vec<_>>())
+                },
+                // optional
+                "unloaded_module_candidates": if frame.unloaded_module_candidates.is_empty() {
+                    None
+                } else {
+                    Some(frame.unloaded_module_candidates.iter().map(|candidate| json!({
+                        "module": candidate.name,
+                        "base_address": json_hex(candidate.base_address),
+                        "offset": json_hex(candidate.offset),
+                        "checksum": format!("0x{:08x}", candidate.checksum),
+                        "time_date_stamp": candidate.time_date_stamp,
+                    })).collect::<Vec<_>>())
+                },
+                // optional
+                "unloaded_module_symbols": frame.unloaded_module_symbols.as_ref().map(|candidate| json!({
+                    "module": candidate.name,
+                    "offset": json_hex(candidate.offset),
+                })),
+                // optional
+                "function_offset": frame
+                    .function_base
+                    .map(|func_base| frame.instruction - func_base)
+                    .map(json_hex),
+                "missing_symbols": frame.function_name.is_none(),
+                // optional, no_module | missing_symbol_file | fetch_failed
+                //   | corrupt_symbol_file | address_not_covered
+                "symbol_miss_reason": frame.symbol_miss_reason.as_ref().map(SymbolMissReason::name),
+                // optional
+                "symbol_fetch_status": match frame.symbol_miss_reason {
+                    Some(SymbolMissReason::FetchFailed { status }) => status,
+                    _ => None,
+                },
+                // none | scan | cfi_scan | frame_pointer | cfi | context | prewalked
+                "trust": frame.trust.json_name(),
+                // optional
+                "warnings": if frame.warnings.is_empty() {
+                    None
+                } else {
+                    Some(&frame.warnings)
+                },
+                // optional
+                "annotations": if frame.annotations.is_empty() {
+                    None
+                } else {
+                    Some(&frame.annotations)
+                },
+            });
+            // Left out entirely unless asked for, it's a lot of output.
+            if self.report_frame_registers {
+                json["registers"] = json_registers(&frame.context);
+            }
+            json
+        };
+
         let mut output = json!({
             // Currently unused, we either produce no output or successful output.
             // OK | ERROR_* | SYMBOL_SUPPLIER_INTERRUPTED
@@ -887,10 +2305,35 @@ Unknown streams encountered:
                 "os_ver": sys.format_os_version(),
                 // x86 | amd64 | arm | ppc | sparc
                 "cpu_arch": sys.cpu.to_string(),
+                // little | big
+                "endianness": match sys.endian {
+                    scroll::Endian::Little => "little",
+                    scroll::Endian::Big => "big",
+                },
                 "cpu_info": sys.cpu_info,
+                // optional
+                "cpu_description": sys.cpu_description,
+                // optional
+                "cpu_microarchitecture": sys.cpu_microarchitecture,
                 "cpu_count": sys.cpu_count,
                 // optional
                 "cpu_microcode_version": sys.cpu_microcode_version,
+                // optional
+                "cpu_features": sys.cpu_features.as_ref().map(|cpu_features| json!({
+                    // optional
+                    "implementer": cpu_features.implementer.map(|id| format!("{:#x}", id)),
+                    // optional
+                    "part": cpu_features.part.map(|id| format!("{:#x}", id)),
+                    // optional
+                    "vendor": cpu_features.vendor_name(),
+                    // optional
+                    "part_name": cpu_features.part_name(),
+                    "features": cpu_features.features.iter().map(|feature| feature.name()).collect::<Vec<_>>(),
+                })),
+                // optional
+                "available_physical_memory": sys.available_physical_memory.map(json_hex),
+                // optional
+                "commit_limit": sys.commit_limit.map(json_hex),
             },
             "crash_info": {
                 "type": self.exception_info.as_ref().map(|info| info.reason).map(|reason| reason.to_string()),
@@ -906,11 +2349,130 @@ Unknown streams encountered:
                         }).collect::<Vec<_>>()
                     })
                 }),
+                // optional
+                "address_memory_kind": self.exception_info.as_ref().and_then(|info| info.address_memory_kind.as_ref()).map(memory_kind_json),
+                // optional
+                "null_deref": self.exception_info.as_ref().and_then(|info| info.null_deref).map(|null_deref| json!({
+                    "offset": json_hex(null_deref.offset),
+                    // optional
+                    "access": null_deref.access.map(|access| format!("{:?}", access)),
+                    // optional
+                    "register": null_deref.register.map(|register| json!({
+                        "name": register.name,
+                        "value": json_hex(register.value),
+                    })),
+                    "description": null_deref.description(),
+                })),
+                // optional
+                "address_register": self.exception_info.as_ref().and_then(|info| {
+                    info.address_register.map(|register| json!({
+                        "name": register.name,
+                        "value": json_hex(register.value),
+                        "offset": json_hex(info.address - register.value),
+                    }))
+                }),
+                // optional
+                "crash_address_details": self.exception_info.as_ref().and_then(|info| {
+                    info.crash_address_details.as_ref().map(|details| json!({
+                        "region": details.region.name(),
+                        // optional
+                        "module": match &details.region {
+                            CrashAddressRegion::Module { name, .. } => Some(name),
+                            _ => None,
+                        },
+                        // optional
+                        "module_offset": match details.region {
+                            CrashAddressRegion::Module { offset, .. } => Some(json_hex(offset)),
+                            _ => None,
+                        },
+                        // optional
+                        "thread_id": match details.region {
+                            CrashAddressRegion::Stack { thread_id } => thread_id,
+                            _ => None,
+                        },
+                        // optional
+                        "protection": details.protection.map(|protection| protection.to_string()),
+                        // optional
+                        "nearest_mapped": details.nearest_mapped.map(|nearest| json!({
+                            "address": json_hex(nearest.address),
+                            "distance": json_hex(nearest.distance),
+                        })),
+                        "executed_non_executable": details.executed_non_executable,
+                        "description": details.description(info.address),
+                    }))
+                }),
+                // optional
+                "crash_address_kind": self.exception_info.as_ref().and_then(|info| info.poisoned_address).map(|poisoned| poisoned.pattern.kind),
+                // optional
+                "crash_address_kind_description": self.exception_info.as_ref().and_then(|info| info.poisoned_address).map(|poisoned| poisoned.description()),
+                // optional
+                "last_error_value": self.requesting_thread.and_then(|idx| self.threads.get(idx)).and_then(|thread| thread.last_error_value).map(|error| error.to_string()),
+                // optional
+                "exception_code": self.exception_info.as_ref().map(|info| format!("0x{:08x}", info.code)),
+                // optional
+                "exception_flags": self.exception_info.as_ref().map(|info| format!("0x{:08x}", info.flags)),
+                // optional
+                "exception_parameters": self.exception_info.as_ref().map(|info| {
+                    info.parameters.iter().map(|&parameter| json_hex(parameter)).collect::<Vec<_>>()
+                }),
+                // optional
+                "windows_parameters": self.exception_info.as_ref().and_then(|info| info.windows_parameters.as_ref()).map(|params| windows_parameters_json(params, json_hex)),
+                // optional
+                "nested_exceptions": self.exception_info.as_ref().filter(|info| !info.nested_exceptions.is_empty()).map(|info| {
+                    info.nested_exceptions.iter().map(|nested| json!({
+                        "type": nested.reason.to_string(),
+                        "code": format!("0x{:08x}", nested.code),
+                        "flags": format!("0x{:08x}", nested.flags),
+                        "address": json_hex(nested.address),
+                    })).collect::<Vec<_>>()
+                }),
                 // thread index | null
                 "crashing_thread": self.requesting_thread,
+                "crashing_thread_heuristic": self.crashing_thread_heuristic,
+                // optional
+                "crashing_thread_guess": self.crashing_thread_guess.as_ref().map(|guess| json!({
+                    // pc_near_null | pc_not_executable | in_abort_function | signal_handler_frame
+                    "reasons": guess.reasons.iter().map(CrashingThreadGuessReason::name).collect::<Vec<_>>(),
+                })),
                 "assertion": self.assertion,
+                // optional
+                "likely_abort": self.likely_abort,
+                // optional
+                "rop_chain": self.rop_chain.as_ref().map(|analysis| json!({
+                    "likely": analysis.likely,
+                    "score": analysis.score,
+                    "gadget_frames": analysis.gadget_frames,
+                    "frames_examined": analysis.frames_examined,
+                })),
+                // optional
+                "crash_type": self.stack_overflow.as_ref().filter(|analysis| analysis.is_stack_overflow()).map(|_| "stack_overflow"),
+                // optional
+                "stack_overflow": self.stack_overflow.as_ref().map(|analysis| json!({
+                    "evidence": analysis.evidence.iter().map(|evidence| evidence.name()).collect::<Vec<_>>(),
+                    // optional
+                    "recursion": analysis.recursion.as_ref().map(|recursion| json!({
+                        "module": recursion.module,
+                        "offset": json_hex(recursion.offset),
+                        "function": recursion.function_name,
+                        "frames": recursion.frames,
+                    })),
+                })),
+                // optional
+                "exploitability": self.exploitability.as_ref().map(|analysis| analysis.rating.name()),
+                // optional
+                "exploitability_reasons": self.exploitability.as_ref().map(|analysis| {
+                    analysis.signals.iter().map(|signal| signal.name()).collect::<Vec<_>>()
+                }),
             },
             // optional
+            "sanitizer_report": self.sanitizer_report.as_ref().map(|report| json!({
+                // full | summary
+                "kind": report.kind.name(),
+                "address": json_hex(report.address),
+                "text": report.text,
+                "truncated": report.truncated,
+            })),
+            // optional
             "lsb_release": self.linux_standard_base.as_ref().map(|lsb| json!({
                 "id": lsb.id,
                 "release": lsb.release,
@@ -920,6 +2482,7 @@ Unknown streams encountered:
             // optional
             "mac_crash_info": self.mac_crash_info.as_ref().map(|info| json!({
                 "num_records": info.len(),
+                "messages": self.mac_crash_messages(),
                 // All of these fields are optional
                 "records": info.iter().map(|record| json!({
                     "thread": record.thread().copied().map(json_hex),
@@ -934,11 +2497,35 @@ Unknown streams encountered:
                 })).collect::<Vec<_>>()
             })),
 
+            // optional
+            "mac_boot_args": self.mac_boot_args,
+
+            // optional
+            "process_memory_usage": self.process_memory_usage.as_ref().map(|usage| json!({
+                // All of these fields are optional
+                "working_set_size": usage.working_set_size.map(json_hex),
+                "peak_working_set_size": usage.peak_working_set_size.map(json_hex),
+                "pagefile_usage": usage.pagefile_usage.map(json_hex),
+                "peak_pagefile_usage": usage.peak_pagefile_usage.map(json_hex),
+                "private_usage": usage.private_usage.map(json_hex),
+                "virtual_size": usage.virtual_size.map(json_hex),
+                "peak_virtual_size": usage.peak_virtual_size.map(json_hex),
+                "page_fault_count": usage.page_fault_count,
+            })),
+
+            // optional
+            "linux_proc_status": self.linux_proc_status.as_ref().map(|status| json!({
+                // All of these fields are optional
+                "vm_rss": status.vm_rss.map(json_hex),
+                "vm_peak": status.vm_peak.map(json_hex),
+                "threads": status.threads,
+            })),
+
             // the first module is always the main one
             "main_module": 0,
             // [UNSTABLE:evil_json]
             "modules_contains_cert_info": !self.cert_info.is_empty(),
-            "modules": self.modules.iter().map(|module| {
+            "modules": self.modules.iter().enumerate().map(|(module_index, module)| {
                 let full_name = module.code_file();
                 let name = basename(&full_name);
 
@@ -961,8 +2548,18 @@ Unknown streams encountered:
                     "filename": &name,
                     "code_id": module.code_identifier().unwrap_or_default().as_str(),
                     "version": module.version(),
+                    // optional
+                    "file_version": module.file_version(),
+                    // optional
+                    "product_version": module.product_version(),
                     // [UNSTABLE:evil_json]
                     "cert_subject": self.cert_info.get(name),
+                    // optional
+                    "annotations": self.module_annotations.get(name),
+                    // optional, if crashpad recorded annotation objects for the module
+                    "crashpad_annotations": self.crashpad_annotations_json(module_index),
+                    // optional, null unless it's true
+                    "corrupt_range": self.modules.range_is_suspect(module_index).then_some(true),
 
                     // These are all just metrics for debugging minidump-processor's execution
 
@@ -974,78 +2571,154 @@ Unknown streams encountered:
                     "corrupt_symbols": stats.corrupt_symbols,
                     // optional, url of symbol file
                     "symbol_url": stats.symbol_url,
+                    // optional, how many frames in the module are unsymbolicated for each reason
+                    "symbol_misses": self.symbol_miss_counts(module.raw.base_of_image),
                 })
             }).collect::<Vec<_>>(),
             "pid": self.process_id,
+            // optional, seconds since the Unix epoch
+            "process_create_time": self.process_create_time.map(|time| {
+                time.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_secs()
+            }),
+            // optional, misc_info | thread_info | crashpad_annotation
+            "process_create_time_source": self.process_create_time_source.map(|source| source.name()),
+            // optional
+            "uptime_seconds": self.uptime().map(|uptime| uptime.as_secs()),
             "thread_count": self.threads.len(),
             "threads": self.threads.iter().map(|thread| json!({
                 "frame_count": thread.frames.len(),
+                "frames_truncated": thread.walk_end_reason == Some(WalkEndReason::MaxFramesReached),
+                "walk_timed_out": thread.walk_end_reason == Some(WalkEndReason::WalkTimedOut),
+                "not_walked": thread.info == CallStackInfo::NotWalked,
+                // optional
+                "repeated_frames": thread.repeated_frames.map(|repeated| json!({
+                    "first_frame": repeated.first_frame,
+                    "frame_count": repeated.frame_count,
+                    "repeated": repeated.repeated,
+                })),
+                // optional
+                "walk_end_reason": thread.walk_end_reason.map(|reason| reason.name()),
+                // optional, only for stack_memory_exhausted
+                "stack_memory_end": match thread.walk_end_reason {
+                    Some(WalkEndReason::StackMemoryExhausted { address }) => Some(json_hex(address)),
+                    _ => None,
+                },
                 // optional
                 "last_error_value": thread.last_error_value.map(|error| error.to_string()),
                 // optional
                 "thread_name": thread.thread_name,
-                "frames": thread.frames.iter().enumerate().map(|(idx, frame)| json!({
-                    "frame": idx,
-                    // optional
-                    "module": frame.module.as_ref().map(|module| basename(&module.name)),
-                    // optional
-                    "function": frame.function_name,
-                    // optional
-                    "file": frame.source_file_name,
-                    // optional
-                    "line": frame.source_line,
-                    "offset": json_hex(frame.instruction),
-                    // optional
-                    "inlines": if !frame.inlines.is_empty() {
-                        Some(frame.inlines.iter().map(|frame| {
-                            json!({
-                                "function": frame.function_name,
-                                "file": frame.source_file_name,
-                                "line": frame.source_line,
-                            })
-                        }).collect::<Vec<_>>())
-                    } else {
-                        None
-                    },
-                    // optional
-                    "module_offset": frame
-                        .module
-                        .as_ref()
-                        .map(|module| frame.instruction - module.raw.base_of_image)
-                        .map(json_hex),
-                    // optional
-                    "unloaded_modules": if frame.unloaded_modules.is_empty() {
-                        None
-                    } else {
-                        Some(frame.unloaded_modules.iter().map(|(module, offsets)| json!({
-                            "module": module,
-                            "offsets": offsets.iter().copied().map(json_hex).collect::<Vec<_>>(),
-                        })).collect::<Vec<_>>())
-                    },
-                    // optional
-                    "function_offset": frame
-                        .function_base
-                        .map(|func_base| frame.instruction - func_base)
-                        .map(json_hex),
-                    "missing_symbols": frame.function_name.is_none(),
-                    // none | scan | cfi_scan | frame_pointer | cfi | context | prewalked
-                    "trust": frame.trust.json_name(),
-                })).collect::<Vec<_>>(),
+                // optional
+                "stack_memory_kind": thread.stack_memory_kind.as_ref().map(memory_kind_json),
+                "frames": thread.frames.iter().enumerate().map(|(idx, frame)| frame_json(idx, frame)).collect::<Vec<_>>(),
             })).collect::<Vec<_>>(),
 
+            // optional
+            "captured_backtraces": if self.captured_backtraces.is_empty() {
+                None
+            } else {
+                Some(self.captured_backtraces.iter().map(|backtrace| json!({
+                    "source": backtrace.source,
+                    // optional
+                    "thread_id": backtrace.thread_id.map(json_hex),
+                    "frame_count": backtrace.frames.len(),
+                    "frames": backtrace.frames.iter().enumerate().map(|(idx, frame)| frame_json(idx, frame)).collect::<Vec<_>>(),
+                })).collect::<Vec<_>>())
+            },
+
             "unloaded_modules": self.unloaded_modules.iter().map(|module| json!({
                 "base_addr": json_hex(module.raw.base_of_image),
                 "code_id": module.code_identifier().unwrap_or_default().as_str(),
                 "end_addr": json_hex(module.raw.base_of_image + module.raw.size_of_image as u64),
                 "filename": module.name,
                 "cert_subject": self.cert_info.get(&module.name),
+                // optional
+                "annotations": self.module_annotations.get(&module.name),
             })).collect::<Vec<_>>(),
+            // optional
+            "unlisted_modules": if self.unlisted_modules.is_empty() {
+                None
+            } else {
+                Some(self.unlisted_modules.iter().map(|module| json!({
+                    "base_addr": json_hex(module.load_address),
+                    "filename": module.name,
+                })).collect::<Vec<_>>())
+            },
+            // optional
+            "memory_info": self.report_memory_info.then(|| {
+                self.memory_regions.iter().map(|region| json!({
+                    "base_address": json_hex(region.base_address),
+                    "size": json_hex(region.size),
+                    "kind": memory_kind_json(&region.kind),
+                    // optional
+                    "state": region.state.map(|state| format!("{:?}", state)),
+                    // optional
+                    "protection": match (&region.protection, &region.permissions) {
+                        (Some(protection), _) => Some(format!("{:?}", protection)),
+                        (None, permissions) => permissions.clone(),
+                    },
+                    // optional
+                    "type": region.memory_type.map(|ty| format!("{:?}", ty)),
+                })).collect::<Vec<_>>()
+            }),
+            // optional
+            "largest_free_vm_block": self.largest_free_vm_block().map(|block| json!({
+                "base_address": json_hex(block.start),
+                "size": json_hex(block.end - block.start),
+            })),
+            // optional
+            "integrity_issues": if self.integrity_issues.is_empty() {
+                None
+            } else {
+                Some(self.integrity_issues.iter().map(|issue| json!({
+                    "kind": issue.name(),
+                    "description": issue.to_string(),
+                    // optional
+                    "stream_type": issue.stream_type().map(|ty| json_hex(ty as u64)),
+                    // optional
+                    "offset": issue.offset().map(json_hex),
+                })).collect::<Vec<_>>())
+            },
+            // optional
+            "symbol_stats": self.report_symbol_stats.then(|| {
+                let mut stats = self.symbol_stats.iter().collect::<Vec<_>>();
+                stats.sort_by_key(|&(module, _)| module);
+                stats.into_iter().map(|(module, stats)| {
+                    let (origin, path) = match &stats.origin {
+                        Some(SymbolOrigin::LocalFile(path)) => (Some("local"), Some(path.display().to_string())),
+                        Some(SymbolOrigin::Cache(path)) => (Some("cache"), Some(path.display().to_string())),
+                        Some(SymbolOrigin::Url(_)) => (Some("url"), None),
+                        None => (None, None),
+                    };
+                    json!({
+                        "module": module,
+                        "loaded_symbols": stats.loaded_symbols,
+                        "corrupt_symbols": stats.corrupt_symbols,
+                        // optional
+                        "lookup_time_ms": stats.lookup_time.map(|time| time.as_secs_f64() * 1000.0),
+                        // optional
+                        "origin": origin,
+                        // optional
+                        "path": path,
+                        // optional
+                        "symbol_url": stats.symbol_url,
+                        "cache_hit": stats.cache_hit,
+                        // optional
+                        "bytes_fetched": stats.bytes_fetched,
+                        // optional
+                        "parse_error": stats.parse_error,
+                        "fetch_failed": stats.fetch_failed,
+                        // optional
+                        "fetch_status": stats.fetch_status,
+                    })
+                }).collect::<Vec<_>>()
+            }),
         });
 
         if let Some(requesting_thread) = self.requesting_thread {
             // Copy the crashing thread into a top-level "crashing_thread" field and:
             // * Add a "threads_index" field to indicate which thread it was
             // * Add a "registers" field to its first frame
+            //   (and a "flags_decoded" one, if we can decode its flags)
             //
             // Note that we currently make crashing_thread a strict superset
             // of a normal "threads" entry, while the original schema strips
@@ -1068,6 +2741,9 @@ Unknown streams encountered:
                 let frame = frames[0].as_object_mut().unwrap();
 
                 frame.insert(String::from("registers"), registers);
+                if let Some(flags) = f.context.decoded_flags() {
+                    frame.insert(String::from("flags_decoded"), json!(flags));
+                }
                 thread_obj.insert(String::from("threads_index"), json!(requesting_thread));
 
                 output
@@ -1077,6 +2753,14 @@ Unknown streams encountered:
             }
         }
 
+        // Add the extra fields, without letting them replace any of ours
+        let output_obj = output.as_object_mut().unwrap();
+        for (key, value) in &self.extra_json {
+            if !output_obj.contains_key(key) {
+                output_obj.insert(key.clone(), value.clone());
+            }
+        }
+
         if pretty {
             serde_json::to_writer_pretty(f, &output)
         } else {
@@ -1085,6 +2769,56 @@ Unknown streams encountered:
     }
 
     // Convert an integer to a hex string, with leading 0's for uniform width.
+    /// The crashpad annotation objects of the module at `module_index`, as JSON.
+    fn crashpad_annotations_json(&self, module_index: usize) -> Option<serde_json::Value> {
+        let annotations = self
+            .crashpad_info
+            .as_ref()?
+            .module_list
+            .iter()
+            .filter(|info| info.module_index == module_index)
+            .flat_map(|info| &info.annotation_objects)
+            .map(|(name, annotation)| {
+                let value = match annotation {
+                    MinidumpAnnotation::String(string) => json!({
+                        "type": annotation.ty(),
+                        "value": string,
+                    }),
+                    _ => json!({
+                        "type": annotation.ty(),
+                        "bytes": annotation
+                            .value()
+                            .iter()
+                            .map(|byte| format!("{:02x}", byte))
+                            .collect::<String>(),
+                    }),
+                };
+                (name.clone(), value)
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        (!annotations.is_empty()).then_some(serde_json::Value::Object(annotations))
+    }
+
+    /// How many frames in the module at `base_address` have each
+    /// [`SymbolMissReason`], counting captured backtraces too, as JSON.
+    fn symbol_miss_counts(&self, base_address: u64) -> Option<serde_json::Value> {
+        let thread_frames = self.threads.iter().flat_map(|stack| &stack.frames);
+        let captured_frames = self
+            .captured_backtraces
+            .iter()
+            .flat_map(|backtrace| &backtrace.frames);
+        let mut counts = BTreeMap::new();
+        for frame in thread_frames.chain(captured_frames) {
+            let in_module =
+                matches!(&frame.module, Some(module) if module.raw.base_of_image == base_address);
+            if let (true, Some(reason)) = (in_module, frame.symbol_miss_reason) {
+                *counts.entry(reason.name()).or_insert(0u64) += 1;
+            }
+        }
+        (!counts.is_empty()).then(|| json!(counts))
+    }
+
     fn json_hex(&self, val: u64) -> String {
         match self.system_info.cpu {
             Cpu::X86 | Cpu::Ppc | Cpu::Sparc | Cpu::Arm | Cpu::Mips => {
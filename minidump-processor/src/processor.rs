@@ -2,20 +2,35 @@
 // file at the top-level directory of this distribution.
 
 use std::borrow::Cow;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::ops::{Deref, RangeInclusive};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
 use std::time::{Duration, SystemTime};
 
-use minidump::{self, *};
+use debugid::{CodeId, DebugId};
+use futures_util::{Stream, StreamExt};
+use tracing::Instrument;
 
-use crate::evil;
-use crate::process_state::{CallStack, CallStackInfo, LinuxStandardBase, ProcessState};
+use minidump::{self, *};
+use minidump_common::poison::poison_pattern;
+use minidump_common::utils::basename;
+
+use crate::process_state::{
+    CallStack, CallStackInfo, CapturedBacktrace, CrashingThreadGuess, CrashingThreadGuessReason,
+    LinuxProcStatus, LinuxStandardBase, MemoryDump, MemoryDumpKind, MemoryRegion,
+    ProcessCreateTimeSource, ProcessMemoryUsage, ProcessState, SymbolMissReason, UnlistedModule,
+    UnloadedModuleCandidate, MEMORY_DUMP_SIZE,
+};
 use crate::stackwalker;
 use crate::symbols::*;
 use crate::system_info::SystemInfo;
-use crate::{arg_recovery, FrameTrust, StackFrame};
+use crate::{
+    arg_recovery, EvilJsonProvider, ExploitabilityAnalysis, ExtraInfoProvider, FrameAnalyzer,
+    FrameTrust, NameNormalization, RopChainAnalysis, SanitizerReport, StackFrame,
+    StackOverflowAnalysis, ThreadFilter,
+};
 
 /// Configuration of the processor's exact behaviour.
 ///
@@ -57,14 +72,300 @@ pub struct ProcessorOptions<'a> {
     /// **\[UNSTABLE\]** The evil "raw json" mozilla's legacy infrastructure relies on.
     ///
     /// Please don't use this. If you have to use this, you know who you are.
+    ///
+    /// This is a shorthand for putting an [`EvilJsonProvider`] at the start
+    /// of [`extra_info`][Self::extra_info].
     pub evil_json: Option<&'a Path>,
 
+    /// Sources of information that isn't in the minidump, to merge into the
+    /// [`ProcessState`].
+    ///
+    /// Where several providers have something for the same thing (like a
+    /// thread's name), later ones win. See [`ExtraInfoProvider`].
+    pub extra_info: Vec<&'a dyn ExtraInfoProvider>,
+
     /// **\[UNSTABLE\]** Whether to try to heuristically recover function arguments in backtraces.
     ///
     /// Currently this only work for x86, and assumes everything is either cdecl or thiscall
-    /// (inferred from whether the symbol name looks like a static function or a method).
+    /// (inferred from whether the symbol name looks like a static function or a method),
+    /// and for the stack-passed arguments of x86-64 frames whose caller wasn't found by
+    /// scanning the stack.
     pub recover_function_args: bool,
 
+    /// **\[UNSTABLE\]** Whether to check recovered caller frames against
+    /// architecture-specific register invariants.
+    ///
+    /// For instance a misaligned stack pointer on ARM64, or a MIPS `$gp` that
+    /// doesn't point into any module. Violations are reported in
+    /// [`StackFrame::warnings`] but don't stop the stack walk.
+    pub validate_registers: bool,
+
+    /// **\[UNSTABLE\]** Whether stack scanning should only accept a return
+    /// address that passes every check we have for one, trading frames for
+    /// precision.
+    ///
+    /// Normally a scanned value only needs to point into a loaded module (and
+    /// not be contradicted by that module's symbols, if we have them). In
+    /// paranoid mode:
+    ///
+    /// * all architectures: the module must have symbols, and they must place
+    ///   the address in a function
+    /// * x86 and x86-64: if the callee's frame pointer is known, it must either
+    ///   point at the slot right below the return address (a saved frame
+    ///   pointer) or above it (a frame pointer that was left untouched)
+    /// * x86 and x86-64: if the minidump has memory info, the address must be
+    ///   in executable memory, and if it captured the code before the
+    ///   address, that code must end in a `call`
+    /// * ARM: the address must be 4-byte aligned, or have the Thumb bit set
+    /// * ARM64 and MIPS: the address must be 4-byte aligned
+    ///
+    /// Frames found this way are still [`FrameTrust::Scan`][crate::FrameTrust::Scan].
+    pub paranoid_scan: bool,
+
+    /// **\[UNSTABLE\]** Whether to sanity check the caller registers that
+    /// call frame info recovers.
+    ///
+    /// CFI evaluation can succeed while still producing nonsense, usually
+    /// because the CFI itself is wrong. Frames whose registers look
+    /// implausible (a stack pointer below the callee's, a frame pointer into
+    /// the stack below the stack pointer, or a callee-saved register that was
+    /// restored to zero) are kept, but downgraded to
+    /// [`FrameTrust::CallFrameInfoLowConfidence`][crate::FrameTrust::CallFrameInfoLowConfidence].
+    pub check_cfi_plausibility: bool,
+
+    /// **\[UNSTABLE\]** Whether to stop walking a thread's stack once it
+    /// reaches the bottom of the stack its thread descriptor declares.
+    ///
+    /// Stacks grow down, so no caller can have a stack pointer past the end
+    /// of the thread's stack. A walk that finds one stops there (dropping the
+    /// bogus frame) with
+    /// [`WalkEndReason::ReachedStackBottom`][crate::WalkEndReason::ReachedStackBottom],
+    /// as does a walk that finds a caller right at the end of the stack
+    /// (keeping that frame, as nothing can have called it). This is a more
+    /// principled way to end a walk than our usual heuristics (like noticing
+    /// a nullish instruction pointer), which still apply when the thread
+    /// declares no stack.
+    pub stop_at_stack_bottom: bool,
+
+    /// **\[UNSTABLE\]** Whether to check frames recovered by call frame info
+    /// against the frame pointer chain, when both are available.
+    ///
+    /// Two independent methods agreeing on the caller's instruction and stack
+    /// pointers is good evidence that a frame is right: a frame that
+    /// [`check_cfi_plausibility`][Self::check_cfi_plausibility] downgraded
+    /// gets its full trust back. When they disagree, the CFI frame is still
+    /// used, but the disagreement is reported in [`StackFrame::warnings`].
+    ///
+    /// This is only useful on code that keeps frame pointers, and does
+    /// nothing on MIPS or for Windows x86-64, which have no frame pointer
+    /// unwinding.
+    pub cross_validate_cfi: bool,
+
+    /// **\[UNSTABLE\]** Whether to confine each thread's stack walk to the
+    /// stack range its thread descriptor declares.
+    ///
+    /// Stacks are sometimes captured as part of a larger memory region (for
+    /// instance when thread stacks are adjacent), and nothing normally stops
+    /// a walk from wandering into a neighbouring thread's stack. With this
+    /// set, stack memory outside the declared range isn't read, and the walk
+    /// stops at the first frame whose stack pointer is outside it.
+    ///
+    /// This breaks walks that legitimately leave the stack, like signal
+    /// handlers running on an alternate signal stack, so unlike the other
+    /// experimental options it's off in every preset.
+    pub constrain_to_thread_stack: bool,
+
+    /// **\[UNSTABLE\]** Whether to score the crashing thread's stack for how
+    /// much it looks like a return-oriented-programming chain.
+    ///
+    /// This is a heuristic for exploit analysis: a long run of tightly packed
+    /// scanned frames, whose return addresses don't follow call instructions,
+    /// is what a stack looks like while a ROP chain is executing. The result
+    /// goes in [`ProcessState::rop_chain`]; see [`RopChainAnalysis`] for the
+    /// details.
+    pub detect_rop_chains: bool,
+
+    /// **\[UNSTABLE\]** Whether to rate how likely the crash is to be exploitable.
+    ///
+    /// This looks for signs like writes to wild addresses, an instruction
+    /// pointer outside of any module, or a failed stack cookie check, and
+    /// rates the crash by the worst one it finds. The result goes in
+    /// [`ProcessState::exploitability`]; see [`ExploitabilityAnalysis`] for
+    /// the details.
+    pub analyze_exploitability: bool,
+
+    /// **\[UNSTABLE\]** Names of functions that never return, like `abort`
+    /// or `__assert_fail`, whose frames should be flagged.
+    ///
+    /// Frames in these functions (or with one of them inlined) get
+    /// [`StackFrame::noreturn`] set, and if the crashing thread has any such
+    /// frames [`ProcessState::likely_abort`] is set, as the crash was
+    /// probably an assertion or panic rather than a hardware fault.
+    ///
+    /// Names are matched exactly, ignoring any parameter list. Empty disables
+    /// this analysis. [`ProcessorOptions::default_noreturn_functions`] is a
+    /// good starting point to add your own functions to.
+    pub noreturn_functions: Vec<String>,
+
+    /// **\[UNSTABLE\]** Whether to guess which thread crashed when the
+    /// minidump has no exception stream, or one whose thread can't be found.
+    ///
+    /// Dumps written by watchdogs or buggy crash handlers can lack a usable
+    /// exception stream, leaving no crashing thread. With this set, threads
+    /// are scored for looking like they faulted: an instruction pointer near
+    /// null or outside executable memory, a frame in a function that aborts
+    /// (one of [`noreturn_functions`][Self::noreturn_functions], or
+    /// [`ProcessorOptions::default_noreturn_functions`] if that's empty), or
+    /// a signal handler frame. The best scoring thread becomes the crashing
+    /// thread, as long as no other thread scores as well, and
+    /// [`ProcessState::crashing_thread_guess`] says why. Nothing is guessed
+    /// when the exception stream names a thread in the dump.
+    ///
+    /// The guess needs the threads' frames, so when there's a guess to make,
+    /// every thread is walked and [`thread_filter`][Self::thread_filter] is
+    /// only applied afterwards, with the guessed thread as the crashing one.
+    pub guess_crashing_thread: bool,
+
+    /// **\[UNSTABLE\]** Whether to look for an AddressSanitizer report in
+    /// the captured memory.
+    ///
+    /// Processes built with ASan abort when it catches a memory error, after
+    /// printing a report of what went wrong, which is often still in memory.
+    /// The first report found goes in [`ProcessState::sanitizer_report`]
+    /// (or just its `SUMMARY:` line, if that's all there is); see
+    /// [`SanitizerReport`] for the details.
+    pub extract_sanitizer_report: bool,
+
+    /// **\[UNSTABLE\]** Which rules to normalize symbolicated function names
+    /// with, so that names from different toolchains can be compared.
+    ///
+    /// Normalized names go in [`StackFrame::normalized_function_name`] and
+    /// [`InlineFrame::normalized_function_name`], and the raw names are left
+    /// as they are. See [`NameNormalization`] for the rules.
+    pub name_normalization: NameNormalization,
+
+    /// Which threads to walk the stacks of.
+    ///
+    /// Threads the filter excludes are still in [`ProcessState::threads`],
+    /// but with no frames and [`CallStackInfo::NotWalked`]. See
+    /// [`ThreadFilter`]. Every preset walks every thread.
+    pub thread_filter: ThreadFilter,
+
+    /// Whether to stop walks that go in circles.
+    ///
+    /// Every caller has to be further up the stack than its callee, so a
+    /// corrupted loop (like frame pointers that lead back to the same return
+    /// address) creeps up the stack a few bytes at a time. A walk that finds
+    /// the same group of frames (of up to 8 frames) 32 times in a row, moving
+    /// up by the same two pointers' worth or less each time, would otherwise
+    /// only stop once it hit
+    /// [`max_frames_per_thread`][Self::max_frames_per_thread]. Instead it
+    /// stops with
+    /// [`WalkEndReason::CycleDetected`][crate::WalkEndReason::CycleDetected],
+    /// keeping just one copy of the group (see
+    /// [`CallStack::repeated_frames`]). Recursion moves further up the stack
+    /// each time around, unless it's a single frame with nothing on the
+    /// stack but a return address and saved frame pointer, so this is on in
+    /// every preset.
+    pub stop_on_unwind_cycles: bool,
+
+    /// The most frames to walk on any one thread.
+    ///
+    /// Corrupted stacks can send the stack scanner through a very long run
+    /// of garbage frames. A walk that gets this many frames stops there, with
+    /// [`WalkEndReason::MaxFramesReached`][crate::WalkEndReason::MaxFramesReached].
+    /// The context frame is always kept, so values less than 1 are treated
+    /// as 1.
+    pub max_frames_per_thread: usize,
+
+    /// The most frames in a row to accept from stack scanning.
+    ///
+    /// Once a walk falls back to scanning, it can keep finding plausible
+    /// looking return addresses in whatever is left of the stack. A walk
+    /// that finds this many [`FrameTrust::Scan`] or [`FrameTrust::CfiScan`]
+    /// frames in a row, with no better trusted frame between them, stops
+    /// there with
+    /// [`WalkEndReason::ScanLimitReached`][crate::WalkEndReason::ScanLimitReached].
+    pub max_consecutive_scan_frames: usize,
+
+    /// How long to spend walking any one thread's stack, if it should be
+    /// limited.
+    ///
+    /// A slow symbol provider or a pathological stack can make a single
+    /// thread take a very long time. A walk that runs over this stops after
+    /// the frame it's on (so no frame is left half-done), with
+    /// [`WalkEndReason::WalkTimedOut`][crate::WalkEndReason::WalkTimedOut].
+    /// The frames found so far are kept.
+    pub frame_walk_timeout: Option<Duration>,
+
+    /// Crash addresses below this are reported as null pointer dereferences.
+    ///
+    /// A crash accessing a small address is almost always a field access
+    /// through a null pointer, at the field's offset. These go in
+    /// [`ExceptionInfo::null_deref`][crate::ExceptionInfo::null_deref], along
+    /// with the register the null pointer was probably in. 0 disables this.
+    pub null_deref_threshold: u64,
+
+    /// Whether to include detailed statistics on looking up each module's
+    /// symbols in the JSON output.
+    ///
+    /// This is the [`ProcessState::symbol_stats`] of every module whose
+    /// symbols were looked up: how long it took, where the symbols came from,
+    /// and how many bytes were downloaded. Off in every preset, because the
+    /// timings differ from run to run.
+    pub report_symbol_stats: bool,
+
+    /// Whether to include the process's memory map in the JSON output.
+    ///
+    /// This is the [`ProcessState::memory_regions`] from the minidump's
+    /// memory info list or Linux maps, which full memory dumps can have
+    /// thousands of. The largest free block of address space is always
+    /// included, as it's much smaller. Off in every preset.
+    pub report_memory_info: bool,
+
+    /// Whether to include the registers of every frame in the JSON output,
+    /// not just the crashing thread's first frame.
+    ///
+    /// Each frame gets the registers that were recovered for it (and are
+    /// known to be valid), which is useful for debugging a bad unwind, but
+    /// makes for a lot of output. Off in every preset.
+    pub report_frame_registers: bool,
+
+    /// Whether to dump the memory around the crashing thread's instruction
+    /// pointer and at its stack pointer in the human output.
+    ///
+    /// Each dump is [`MEMORY_DUMP_SIZE`] bytes, with bytes that aren't in the
+    /// minidump marked, and addresses that are in modules annotated with
+    /// where. Off in every preset.
+    pub dump_crash_memory: bool,
+
+    /// How many threads to walk the stacks of at once.
+    ///
+    /// Each thread's walk is independent, and mostly spends its time waiting
+    /// on the symbol provider, so walking several at once can make processing
+    /// much faster (especially with a network symbol server). Higher values
+    /// mean more symbol lookups in flight at a time. Values less than 1 are
+    /// treated as 1, to walk one thread at a time.
+    ///
+    /// Regardless of the order they're walked in, [`ProcessState::threads`]
+    /// stays in the order of the minidump's thread list. The logging for
+    /// each thread is in a `thread` span with its index and id, and the live
+    /// frames of [`ProcessorOptions::stat_reporter`] say which thread they're
+    /// from, so threads walked at the same time can be told apart there too.
+    pub thread_concurrency: usize,
+
+    /// Custom analysis to run on every frame of every thread's stack, as
+    /// it's walked.
+    ///
+    /// The analyzer sees each frame right after it's symbolicated, along with
+    /// its callee, the thread's stack memory, and the module list, and can
+    /// attach whatever annotations it likes to [`StackFrame::annotations`],
+    /// which are included in the JSON output. Frames that weren't walked
+    /// (like [`ProcessState::captured_backtraces`]) aren't analyzed.
+    ///
+    /// See [`FrameAnalyzer`] for what it needs to put up with.
+    pub frame_analyzer: Option<&'a dyn FrameAnalyzer>,
+
     /// Set this value to subscribe to live statistics during the processing.
     ///
     /// See [`PendingProcessorStats`] and [`PendingProcessorStatSubscriptions`].
@@ -102,6 +403,12 @@ struct PendingProcessorStatsInner {
     new_walked_frames: Vec<WalkedFrame>,
     /// The partial ProcessState, before stackwalking
     unwalked_result: Option<ProcessState>,
+    /// Patches that haven't been taken from the patch stream yet
+    patches: VecDeque<ProcessStatePatch>,
+    /// Whether processing is done, so the patch stream should end once drained
+    patches_finished: bool,
+    /// The task waiting on the patch stream, if any
+    patch_waker: Option<Waker>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -139,6 +446,15 @@ pub struct PendingProcessorStatSubscriptions {
     ///
     /// The values can be read with [`PendingProcessorStats::drain_new_frames`].
     pub live_frames: bool,
+    /// Subscribe to a stream of [`ProcessStatePatch`]es, for progressively
+    /// building up the ProcessState as the stackwalker makes progress.
+    ///
+    /// This combines [`PendingProcessorStatSubscriptions::unwalked_result`] and
+    /// [`PendingProcessorStatSubscriptions::live_frames`] into a single ordered
+    /// stream, for consumers that would rather await updates than poll for them.
+    ///
+    /// The stream can be read with [`PendingProcessorStats::patches`].
+    pub patches: bool,
 }
 
 /// A StackFrame that has been walked, with metadata on which thread it's part of,
@@ -157,6 +473,32 @@ pub struct WalkedFrame {
     pub frame: StackFrame,
 }
 
+/// An update to a [`ProcessState`] that is still being processed.
+///
+/// This is the payload for [`PendingProcessorStatSubscriptions::patches`].
+/// Applying every patch in order with [`ProcessState::apply_patch`] builds
+/// up the ProcessState the processor is working on:
+///
+/// * The first patch is always [`ProcessStatePatch::Unwalked`].
+/// * Then there is a [`ProcessStatePatch::Frame`] for each frame of each
+///   thread, as it is walked and symbolicated. Frames of different threads
+///   may be interleaved, but each thread's frames arrive in order, starting
+///   with its context frame (which replaces the unsymbolicated one).
+///
+/// Some post-processing analysis happens after all the frames have been
+/// walked, so the final [`ProcessState`] should replace the patched one
+/// once processing is done.
+#[derive(Debug, Clone)]
+pub enum ProcessStatePatch {
+    /// The ProcessState before stackwalking (or symbolication), with only
+    /// the context frame of each thread. Applying this replaces the state.
+    Unwalked(Box<ProcessState>),
+    /// A frame of a thread has been walked and symbolicated. Applying this
+    /// sets frame number `frame_idx` of thread number `thread_idx`, and drops
+    /// any frames after it.
+    Frame(Box<WalkedFrame>),
+}
+
 impl PendingProcessorStats {
     /// Subscribe to the given stats.
     ///
@@ -226,6 +568,31 @@ impl PendingProcessorStats {
         stats.unwalked_result.take()
     }
 
+    /// Get a stream of the [`ProcessStatePatch`]es for the processing.
+    ///
+    /// The stream ends once processing is done (successfully or not).
+    /// Patches are only delivered once, so this should only be called once.
+    ///
+    /// This will panic if you didn't subscribe to
+    /// [`PendingProcessorStatSubscriptions::patches`].
+    pub fn patches(&self) -> impl Stream<Item = ProcessStatePatch> + '_ {
+        assert!(
+            self.subscriptions.patches,
+            "tried to get patches, but wasn't subscribed!"
+        );
+        futures_util::stream::poll_fn(move |cx| {
+            let mut stats = self.stats.lock().unwrap();
+            if let Some(patch) = stats.patches.pop_front() {
+                Poll::Ready(Some(patch))
+            } else if stats.patches_finished {
+                Poll::Ready(None)
+            } else {
+                stats.patch_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
+
     /// Record how many threads there are in total.
     pub(crate) fn set_total_threads(&self, total_threads: u64) {
         // Only bother doing this if the user cares
@@ -260,6 +627,13 @@ impl PendingProcessorStats {
                 });
             }
         }
+        if self.subscriptions.patches {
+            self.add_patch(ProcessStatePatch::Frame(Box::new(WalkedFrame {
+                thread_idx,
+                frame_idx,
+                frame: frame.clone(),
+            })));
+        }
     }
 
     /// Record this unwalked [`ProcessState`].
@@ -269,14 +643,74 @@ impl PendingProcessorStats {
             let mut stats = self.stats.lock().unwrap();
             stats.unwalked_result = Some(state.clone());
         }
+        if self.subscriptions.patches {
+            self.add_patch(ProcessStatePatch::Unwalked(Box::new(state.clone())));
+        }
+    }
+
+    /// Send a patch to the patch stream.
+    fn add_patch(&self, patch: ProcessStatePatch) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.patches.push_back(patch);
+        if let Some(waker) = stats.patch_waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Record that processing is done, ending the patch stream.
+    pub(crate) fn finish_patches(&self) {
+        if self.subscriptions.patches {
+            let mut stats = self.stats.lock().unwrap();
+            stats.patches_finished = true;
+            if let Some(waker) = stats.patch_waker.take() {
+                waker.wake();
+            }
+        }
     }
 }
 
 impl ProcessorOptions<'_> {
+    /// The default [`ProcessorOptions::max_frames_per_thread`], which is
+    /// the same limit Breakpad has.
+    pub const DEFAULT_MAX_FRAMES_PER_THREAD: usize = 1024;
+
+    /// The default [`ProcessorOptions::max_consecutive_scan_frames`].
+    pub const DEFAULT_MAX_CONSECUTIVE_SCAN_FRAMES: usize = 64;
+
+    /// The default [`ProcessorOptions::null_deref_threshold`], the size of
+    /// the region Windows never maps at address 0.
+    pub const DEFAULT_NULL_DEREF_THRESHOLD: u64 = 0x10000;
+
+    /// The default [`ProcessorOptions::thread_concurrency`].
+    pub const DEFAULT_THREAD_CONCURRENCY: usize = 16;
+
     /// "Do the normal stuff everyone should want"
     ///
     /// * `evil_json: None`
     /// * `recover_function_args: false`
+    /// * `validate_registers: false`
+    /// * `paranoid_scan: false`
+    /// * `check_cfi_plausibility: false`
+    /// * `cross_validate_cfi: false`
+    /// * `constrain_to_thread_stack: false`
+    /// * `stop_at_stack_bottom: false`
+    /// * `detect_rop_chains: false`
+    /// * `analyze_exploitability: false`
+    /// * `noreturn_functions: []`
+    /// * `guess_crashing_thread: false`
+    /// * `extract_sanitizer_report: false`
+    /// * `name_normalization: NameNormalization::default()`
+    /// * `stop_on_unwind_cycles: true`
+    /// * `max_frames_per_thread: DEFAULT_MAX_FRAMES_PER_THREAD`
+    /// * `max_consecutive_scan_frames: DEFAULT_MAX_CONSECUTIVE_SCAN_FRAMES`
+    /// * `frame_walk_timeout: None`
+    /// * `null_deref_threshold: DEFAULT_NULL_DEREF_THRESHOLD`
+    /// * `report_symbol_stats: false`
+    /// * `report_memory_info: false`
+    /// * `report_frame_registers: false`
+    /// * `dump_crash_memory: false`
+    /// * `thread_filter: ThreadFilter::All`
+    /// * `thread_concurrency: DEFAULT_THREAD_CONCURRENCY`
     ///
     /// Unlike stable_all, you shouldn't expect this to change its results much.
     ///
@@ -289,7 +723,32 @@ impl ProcessorOptions<'_> {
     pub fn stable_basic() -> Self {
         ProcessorOptions {
             evil_json: None,
+            extra_info: Vec::new(),
             recover_function_args: false,
+            validate_registers: false,
+            paranoid_scan: false,
+            check_cfi_plausibility: false,
+            cross_validate_cfi: false,
+            constrain_to_thread_stack: false,
+            stop_at_stack_bottom: false,
+            detect_rop_chains: false,
+            analyze_exploitability: false,
+            noreturn_functions: Vec::new(),
+            guess_crashing_thread: false,
+            extract_sanitizer_report: false,
+            name_normalization: NameNormalization::default(),
+            max_frames_per_thread: Self::DEFAULT_MAX_FRAMES_PER_THREAD,
+            max_consecutive_scan_frames: Self::DEFAULT_MAX_CONSECUTIVE_SCAN_FRAMES,
+            frame_walk_timeout: None,
+            null_deref_threshold: Self::DEFAULT_NULL_DEREF_THRESHOLD,
+            report_symbol_stats: false,
+            report_memory_info: false,
+            report_frame_registers: false,
+            dump_crash_memory: false,
+            thread_filter: ThreadFilter::All,
+            stop_on_unwind_cycles: true,
+            thread_concurrency: Self::DEFAULT_THREAD_CONCURRENCY,
+            frame_analyzer: None,
             stat_reporter: None,
         }
     }
@@ -298,6 +757,29 @@ impl ProcessorOptions<'_> {
     ///
     /// * `evil_json: None`
     /// * `recover_function_args: false`
+    /// * `validate_registers: false`
+    /// * `paranoid_scan: false`
+    /// * `check_cfi_plausibility: false`
+    /// * `cross_validate_cfi: false`
+    /// * `constrain_to_thread_stack: false`
+    /// * `stop_at_stack_bottom: false`
+    /// * `detect_rop_chains: false`
+    /// * `analyze_exploitability: false`
+    /// * `noreturn_functions: []`
+    /// * `guess_crashing_thread: false`
+    /// * `extract_sanitizer_report: false`
+    /// * `name_normalization: NameNormalization::default()`
+    /// * `stop_on_unwind_cycles: true`
+    /// * `max_frames_per_thread: DEFAULT_MAX_FRAMES_PER_THREAD`
+    /// * `max_consecutive_scan_frames: DEFAULT_MAX_CONSECUTIVE_SCAN_FRAMES`
+    /// * `frame_walk_timeout: None`
+    /// * `null_deref_threshold: DEFAULT_NULL_DEREF_THRESHOLD`
+    /// * `report_symbol_stats: false`
+    /// * `report_memory_info: false`
+    /// * `report_frame_registers: false`
+    /// * `dump_crash_memory: false`
+    /// * `thread_filter: ThreadFilter::All`
+    /// * `thread_concurrency: DEFAULT_THREAD_CONCURRENCY`
     ///
     /// (At this precise moment this is identical to stable_basic, but may diverge
     /// as we introduce more features.)
@@ -308,7 +790,32 @@ impl ProcessorOptions<'_> {
     pub fn stable_all() -> Self {
         ProcessorOptions {
             evil_json: None,
+            extra_info: Vec::new(),
             recover_function_args: false,
+            validate_registers: false,
+            paranoid_scan: false,
+            check_cfi_plausibility: false,
+            cross_validate_cfi: false,
+            constrain_to_thread_stack: false,
+            stop_at_stack_bottom: false,
+            detect_rop_chains: false,
+            analyze_exploitability: false,
+            noreturn_functions: Vec::new(),
+            guess_crashing_thread: false,
+            extract_sanitizer_report: false,
+            name_normalization: NameNormalization::default(),
+            max_frames_per_thread: Self::DEFAULT_MAX_FRAMES_PER_THREAD,
+            max_consecutive_scan_frames: Self::DEFAULT_MAX_CONSECUTIVE_SCAN_FRAMES,
+            frame_walk_timeout: None,
+            null_deref_threshold: Self::DEFAULT_NULL_DEREF_THRESHOLD,
+            report_symbol_stats: false,
+            report_memory_info: false,
+            report_frame_registers: false,
+            dump_crash_memory: false,
+            thread_filter: ThreadFilter::All,
+            stop_on_unwind_cycles: true,
+            thread_concurrency: Self::DEFAULT_THREAD_CONCURRENCY,
+            frame_analyzer: None,
             stat_reporter: None,
         }
     }
@@ -317,6 +824,29 @@ impl ProcessorOptions<'_> {
     ///
     /// * `evil_json: None`
     /// * `recover_function_args: true`
+    /// * `validate_registers: true`
+    /// * `paranoid_scan: true`
+    /// * `check_cfi_plausibility: true`
+    /// * `cross_validate_cfi: true`
+    /// * `constrain_to_thread_stack: false`
+    /// * `stop_at_stack_bottom: true`
+    /// * `detect_rop_chains: true`
+    /// * `analyze_exploitability: true`
+    /// * `noreturn_functions: default_noreturn_functions()`
+    /// * `guess_crashing_thread: true`
+    /// * `extract_sanitizer_report: true`
+    /// * `name_normalization: NameNormalization::all()`
+    /// * `stop_on_unwind_cycles: true`
+    /// * `max_frames_per_thread: DEFAULT_MAX_FRAMES_PER_THREAD`
+    /// * `max_consecutive_scan_frames: DEFAULT_MAX_CONSECUTIVE_SCAN_FRAMES`
+    /// * `frame_walk_timeout: None`
+    /// * `null_deref_threshold: DEFAULT_NULL_DEREF_THRESHOLD`
+    /// * `report_symbol_stats: false`
+    /// * `report_memory_info: false`
+    /// * `report_frame_registers: false`
+    /// * `dump_crash_memory: false`
+    /// * `thread_filter: ThreadFilter::All`
+    /// * `thread_concurrency: DEFAULT_THREAD_CONCURRENCY`
     ///
     /// (evil_json is still "disabled" because you need to give it needs a path.)
     ///
@@ -324,11 +854,73 @@ impl ProcessorOptions<'_> {
     pub fn unstable_all() -> Self {
         ProcessorOptions {
             evil_json: None,
+            extra_info: Vec::new(),
             recover_function_args: true,
+            validate_registers: true,
+            paranoid_scan: true,
+            check_cfi_plausibility: true,
+            cross_validate_cfi: true,
+            constrain_to_thread_stack: false,
+            stop_at_stack_bottom: true,
+            detect_rop_chains: true,
+            analyze_exploitability: true,
+            noreturn_functions: Self::default_noreturn_functions(),
+            guess_crashing_thread: true,
+            extract_sanitizer_report: true,
+            name_normalization: NameNormalization::all(),
+            max_frames_per_thread: Self::DEFAULT_MAX_FRAMES_PER_THREAD,
+            max_consecutive_scan_frames: Self::DEFAULT_MAX_CONSECUTIVE_SCAN_FRAMES,
+            frame_walk_timeout: None,
+            null_deref_threshold: Self::DEFAULT_NULL_DEREF_THRESHOLD,
+            report_symbol_stats: false,
+            report_memory_info: false,
+            report_frame_registers: false,
+            dump_crash_memory: false,
+            thread_filter: ThreadFilter::All,
+            stop_on_unwind_cycles: true,
+            thread_concurrency: Self::DEFAULT_THREAD_CONCURRENCY,
+            frame_analyzer: None,
             stat_reporter: None,
         }
     }
 
+    /// A list of common functions that never return, for
+    /// [`ProcessorOptions::noreturn_functions`].
+    ///
+    /// This covers the abort, assertion, and panic machinery of the major C
+    /// and C++ runtimes, and Rust.
+    pub fn default_noreturn_functions() -> Vec<String> {
+        [
+            // C runtimes
+            "abort",
+            "__GI_abort",
+            "__assert_fail",
+            "__assert_rtn",
+            "__assert2",
+            "_assert",
+            "_wassert",
+            "__stack_chk_fail",
+            "__fortify_fail",
+            "__chk_fail",
+            "abort_message",
+            "_invoke_watson",
+            "RaiseFailFastException",
+            // C++
+            "std::terminate",
+            "__cxa_pure_virtual",
+            // Rust
+            "std::process::abort",
+            "core::panicking::panic",
+            "core::panicking::panic_fmt",
+            "std::panicking::begin_panic",
+            "std::panicking::rust_panic_with_hook",
+            "rust_panic",
+        ]
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+    }
+
     /// Check if any of the enabled features are deprecated or disabled
     /// and emit warnings if they are.
     fn check_deprecated_and_disabled(&self) {
@@ -426,12 +1018,33 @@ pub async fn process_minidump_with_options<'a, T, P>(
     symbol_provider: &P,
     options: ProcessorOptions<'_>,
 ) -> Result<ProcessState, ProcessError>
+where
+    T: Deref<Target = [u8]> + 'a,
+    P: SymbolProvider + Sync,
+{
+    let stat_reporter = options.stat_reporter;
+    let result = process_minidump_inner(dump, symbol_provider, options).await;
+    if let Some(reporter) = stat_reporter {
+        reporter.finish_patches();
+    }
+    result
+}
+
+async fn process_minidump_inner<'a, T, P>(
+    dump: &Minidump<'a, T>,
+    symbol_provider: &P,
+    options: ProcessorOptions<'_>,
+) -> Result<ProcessState, ProcessError>
 where
     T: Deref<Target = [u8]> + 'a,
     P: SymbolProvider + Sync,
 {
     options.check_deprecated_and_disabled();
 
+    // Memoize symbolication for the rest of this dump: the same addresses
+    // get looked up repeatedly while walking and scanning.
+    let symbol_provider = &SymbolCache::new(symbol_provider);
+
     // Thread list is required for processing.
     let thread_list = dump
         .get_stream::<MinidumpThreadList>()
@@ -459,7 +1072,10 @@ where
         .get_stream::<MinidumpLinuxCpuInfo>()
         .unwrap_or_default();
     let _linux_environ = dump.get_stream::<MinidumpLinuxEnviron>().ok();
-    let _linux_proc_status = dump.get_stream::<MinidumpLinuxProcStatus>().ok();
+    let linux_proc_status = dump
+        .get_stream::<MinidumpLinuxProcStatus>()
+        .ok()
+        .map(|status| LinuxProcStatus::from_proc_status(&status));
 
     // Extract everything we care about from linux streams here.
     // We don't eagerly process them in the minidump crate because there's just
@@ -502,31 +1118,63 @@ where
     let cpu_info = dump_system_info
         .cpu_info()
         .map(|string| string.into_owned());
+    let cpu_features = dump_system_info.cpu_features(Some(&linux_cpu_info));
+    let cpu_microarchitecture = dump_system_info
+        .cpu_microarchitecture(Some(&linux_cpu_info))
+        .map(String::from);
+    let cpu_description = dump_system_info.cpu_description(Some(&linux_cpu_info));
+
+    let system_memory_info = dump.get_stream::<MinidumpSystemMemoryInfo>().ok();
 
     let system_info = SystemInfo {
         os: dump_system_info.os,
         os_version: Some(os_version),
         os_build,
         cpu: dump_system_info.cpu,
+        endian: dump.endian,
         cpu_info,
         cpu_microcode_version,
+        cpu_features,
+        cpu_microarchitecture,
+        cpu_description,
         cpu_count: dump_system_info.raw.number_of_processors as usize,
+        available_physical_memory: system_memory_info
+            .as_ref()
+            .and_then(MinidumpSystemMemoryInfo::available_physical_memory),
+        commit_limit: system_memory_info
+            .as_ref()
+            .and_then(MinidumpSystemMemoryInfo::commit_limit),
     };
 
     let mac_crash_info = dump
         .get_stream::<MinidumpMacCrashInfo>()
         .ok()
         .map(|info| info.raw);
+    let mac_boot_args = dump
+        .get_stream::<MinidumpMacBootargs>()
+        .ok()
+        .and_then(|bootargs| bootargs.bootargs);
+
+    let crashpad_info = dump.get_stream::<MinidumpCrashpadInfo>().ok();
+
+    let process_memory_usage = dump
+        .get_stream::<MinidumpProcessVmCounters>()
+        .ok()
+        .map(|counters| ProcessMemoryUsage::from_vm_counters(&counters));
 
     let misc_info = dump.get_stream::<MinidumpMiscInfo>().ok();
+    let process_id = misc_info
+        .as_ref()
+        .and_then(|misc_info| misc_info.raw.process_id().cloned());
     // Process create time is optional.
-    let (process_id, process_create_time) = if let Some(misc_info) = misc_info.as_ref() {
-        (
-            misc_info.raw.process_id().cloned(),
-            misc_info.process_create_time(),
-        )
-    } else {
-        (None, None)
+    let thread_infos = dump.get_stream::<MinidumpThreadInfoList>().ok();
+    let (process_create_time, process_create_time_source) = match process_create_time(
+        misc_info.as_ref(),
+        thread_infos.as_ref(),
+        crashpad_info.as_ref(),
+    ) {
+        Some((time, source)) => (Some(time), Some(source)),
+        None => (None, None),
     };
     // If Breakpad info exists in dump, get dump and requesting thread ids.
     let breakpad_info = dump.get_stream::<MinidumpBreakpadInfo>();
@@ -536,7 +1184,10 @@ where
         (None, None)
     };
     // Get assertion
-    let assertion = None;
+    let assertion = dump
+        .get_stream::<MinidumpAssertion>()
+        .ok()
+        .map(|assertion| describe_assertion(&assertion));
     let modules = match dump.get_stream::<MinidumpModuleList>() {
         Ok(module_list) => module_list,
         // Just give an empty list, simplifies things.
@@ -547,29 +1198,70 @@ where
         // Just give an empty list, simplifies things.
         Err(_) => MinidumpUnloadedModuleList::new(),
     };
-    let memory_list = dump.get_stream::<MinidumpMemoryList>().unwrap_or_default();
+    let unlisted_modules = dump
+        .get_stream::<MinidumpLinuxDsoDebug>()
+        .map(|dso_debug| unlisted_modules(&dso_debug, &modules))
+        .unwrap_or_default();
+    let memory_list = dump.get_memory().unwrap_or_default();
     let memory_info_list = dump.get_stream::<MinidumpMemoryInfoList>().ok();
     let linux_maps = dump.get_stream::<MinidumpLinuxMaps>().ok();
-    let _memory_info = UnifiedMemoryInfoList::new(memory_info_list, linux_maps).unwrap_or_default();
+    let memory_info = UnifiedMemoryInfoList::new(memory_info_list, linux_maps);
+    let memory_regions = memory_regions(memory_info.as_ref(), &thread_list);
+    let memory_kind_at = |address| {
+        memory_info
+            .as_ref()
+            .map(|info| info.memory_kind_at_address(address, Some(&thread_list)))
+    };
 
     // Get exception info if it exists.
     let exception_stream = dump.get_stream::<MinidumpException>().ok();
     let exception_ref = exception_stream.as_ref();
 
     let exception_info = exception_ref.map(|exception| {
-        let reason = exception.get_crash_reason(system_info.os, system_info.cpu);
-        let address = exception.get_crash_address(system_info.os, system_info.cpu);
+        let (os, cpu) = (system_info.os, system_info.cpu);
+        let mut reason = exception.get_crash_reason(os, cpu);
+        let mut address = exception.get_crash_address(os, cpu);
+        let mut windows_parameters = exception.windows_parameters(os);
+
+        // The outer records of a chain of nested exceptions are often generic
+        // wrappers, so describe the crash with the innermost one that has a code.
+        let mut nested_exceptions = Vec::new();
+        let nested = match os {
+            system_info::Os::Windows => exception.nested_exceptions(&memory_list, cpu),
+            _ => Vec::new(),
+        };
+        if !nested.is_empty() {
+            let chain = std::iter::once(&exception.raw.exception_record).chain(&nested);
+            nested_exceptions = chain
+                .map(|record| crate::NestedException {
+                    code: record.exception_code,
+                    flags: record.exception_flags,
+                    reason: exception.get_nested_crash_reason(record, os, cpu),
+                    address: exception.get_nested_crash_address(record, os, cpu),
+                })
+                .collect();
+            if let Some(record) = nested
+                .iter()
+                .rev()
+                .find(|record| record.exception_code != 0)
+            {
+                reason = exception.get_nested_crash_reason(record, os, cpu);
+                address = exception.get_nested_crash_address(record, os, cpu);
+                windows_parameters = WindowsExceptionParameters::from_record(record);
+            }
+        }
 
-        let (instruction_str, memory_accesses) = exception
-            .context(&dump_system_info, misc_info.as_ref())
+        let context = exception.context(&dump_system_info, misc_info.as_ref());
+        let (instruction_str, memory_accesses) = context
+            .as_ref()
             .map(|context| {
                 let instruction_str =
-                    crate::op_analysis::pretty_print_thread_instruction(&context, &memory_list)
+                    crate::op_analysis::pretty_print_thread_instruction(context, &memory_list)
                         .map_err(|e| tracing::warn!("failed to pretty print instruction: {}", e))
                         .ok();
 
                 let memory_accesses =
-                    crate::op_analysis::get_thread_memory_access(&context, &memory_list)
+                    crate::op_analysis::get_thread_memory_access(context, &memory_list)
                         .map_err(|e| {
                             tracing::warn!("failed to get instruction memory access: {}", e)
                         })
@@ -584,29 +1276,98 @@ where
             address,
             instruction_str,
             memory_accesses,
+            address_memory_kind: None,
+            poisoned_address: None,
+            null_deref: None,
+            address_register: None,
+            crash_address_details: None,
+            code: exception.raw.exception_record.exception_code,
+            flags: exception.raw.exception_record.exception_flags,
+            parameters: exception.parameters().to_vec(),
+            windows_parameters,
+            nested_exceptions,
         };
 
         // If we detect that the crash was caused by a non-canonical memory access, overwrite the bogus exception address
         // with the one we've detected from disassembling the current instruction
         fix_non_canonical_crash_address(&system_info, &mut exception_info);
+        exception_info.address_memory_kind = memory_kind_at(exception_info.address);
+        exception_info.poisoned_address = poisoned_address(&exception_info);
+        exception_info.crash_address_details = crash_address_details(
+            &exception_info,
+            context.as_deref(),
+            &modules,
+            &thread_list,
+            memory_info.as_ref(),
+        );
+        exception_info.null_deref = null_deref(
+            &exception_info,
+            context.as_deref(),
+            options.null_deref_threshold,
+        );
+        exception_info.address_register = address_register(&exception_info, context.as_deref());
 
         exception_info
     });
 
     let crashing_thread_id = exception_ref.map(|e| e.get_crashing_thread_id());
+    let exception_thread_found = matches!(crashing_thread_id, Some(id)
+        if thread_list.threads.iter().any(|thread| thread.raw.thread_id == id));
 
     let exception_context =
         exception_ref.and_then(|e| e.context(&dump_system_info, misc_info.as_ref()));
 
+    // Some malformed dumps have an exception stream whose thread id doesn't
+    // match any thread. Try to find the crashing thread from the exception
+    // context instead.
+    let mut crashing_thread_heuristic = false;
+    let crashing_thread_id = match (crashing_thread_id, exception_context.as_deref()) {
+        (Some(id), Some(context))
+            if !thread_list
+                .threads
+                .iter()
+                .any(|thread| thread.raw.thread_id == id) =>
+        {
+            let guess = guess_crashing_thread(
+                context,
+                &thread_list,
+                dump_thread_id,
+                &dump_system_info,
+                misc_info.as_ref(),
+                &memory_list,
+            );
+            if let Some(guess) = guess {
+                tracing::warn!(
+                    "exception stream's thread id {:#x} doesn't match any thread, using {:#x}",
+                    id,
+                    guess
+                );
+            }
+            crashing_thread_heuristic = guess.is_some();
+            guess.or(Some(id))
+        }
+        (id, _) => id,
+    };
+
     // Get the evil JSON file (thread names and module certificates)
-    let evil = options
-        .evil_json
-        .and_then(evil::handle_evil)
-        .unwrap_or_default();
+    let evil = options.evil_json.and_then(EvilJsonProvider::from_path);
+    let extra_info = evil
+        .iter()
+        .map(|evil| evil as &dyn ExtraInfoProvider)
+        .chain(options.extra_info.iter().copied())
+        .collect::<Vec<_>>();
 
     let mut requesting_thread = None;
 
-    let threads = thread_list
+    // If the crashing thread will have to be guessed, it isn't known yet which
+    // threads the filter wants.
+    let crashing_thread_listed = matches!(crashing_thread_id.or(requesting_thread_id), Some(id)
+        if dump_thread_id != Some(id)
+            && thread_list.threads.iter().any(|thread| thread.raw.thread_id == id));
+    let defer_thread_filter =
+        options.guess_crashing_thread && !crashing_thread_listed && !exception_thread_found;
+
+    let threads: Vec<CallStack> = thread_list
         .threads
         .iter()
         .enumerate()
@@ -622,6 +1383,8 @@ where
             // If this thread requested the dump then try to use the exception
             // context if it exists. (prefer the exception stream's thread id over
             // the breakpad info stream's thread id.)
+            let uses_exception_context = crashing_thread_id.or(requesting_thread_id) == Some(id)
+                && exception_context.is_some();
             let context = if crashing_thread_id.or(requesting_thread_id) == Some(id) {
                 requesting_thread = Some(i);
                 exception_context.as_deref().or(thread_context.as_deref())
@@ -629,11 +1392,37 @@ where
                 thread_context.as_deref()
             };
 
-            let name = thread_names
-                .get_name(thread.raw.thread_id)
-                .map(|cow| cow.into_owned())
-                .or_else(|| evil.thread_names.get(&thread.raw.thread_id).cloned());
+            let name = extra_info
+                .iter()
+                .rev()
+                .find_map(|extra| extra.thread_name_override(id))
+                .or_else(|| {
+                    thread_list
+                        .thread_name(id, &thread_names)
+                        .map(|cow| cow.into_owned())
+                })
+                .or_else(|| {
+                    extra_info
+                        .iter()
+                        .rev()
+                        .find_map(|extra| extra.thread_name(id))
+                });
 
+            let is_crashing = requesting_thread == Some(i);
+            if !defer_thread_filter
+                && !options
+                    .thread_filter
+                    .includes(id, name.as_deref(), is_crashing)
+            {
+                return CallStack {
+                    thread_name: name,
+                    stack_range: thread.declared_stack_range(),
+                    ..CallStack::with_info(id, CallStackInfo::NotWalked)
+                };
+            }
+
+            let stack_memory_kind =
+                context.and_then(|context| memory_kind_at(context.get_stack_pointer()));
             let (info, frames) = if let Some(context) = context {
                 let ctx = context.clone();
                 (
@@ -643,41 +1432,117 @@ where
             } else {
                 (CallStackInfo::MissingContext, vec![])
             };
+            let extended_state = if uses_exception_context {
+                exception_ref.and_then(|e| e.extended_state(&dump_system_info, misc_info.as_ref()))
+            } else {
+                thread.extended_state(&dump_system_info, misc_info.as_ref())
+            };
 
             CallStack {
                 frames,
                 info,
                 thread_id: id,
                 thread_name: name,
-                last_error_value: thread.last_error(system_info.cpu, &memory_list),
+                last_error_value: (system_info.os == system_info::Os::Windows)
+                    .then(|| thread.last_error(system_info.cpu, &memory_list))
+                    .flatten(),
+                stack_range: thread.declared_stack_range(),
+                stack_memory_kind,
+                repeated_frames: None,
+                walk_end_reason: None,
+                extended_state,
             }
         })
         .collect();
 
+    // Any backtraces captured by the process itself are missing register state,
+    // so their frames borrow the layout of some thread's context (or an empty
+    // one for the dump's CPU, if no thread has one).
+    let captured_backtraces = threads
+        .iter()
+        .find_map(|thread| thread.frames.first())
+        .map(|frame| frame.context.clone())
+        .or_else(|| empty_context(system_info.cpu))
+        .zip(mac_crash_info.as_deref())
+        .map(|(context, records)| captured_mac_backtraces(records, &context))
+        .unwrap_or_default();
+
     // Collect up info on unimplemented/unknown modules
     let unknown_streams = dump.unknown_streams().collect();
     let unimplemented_streams = dump.unimplemented_streams().collect();
+    let integrity_issues = dump.check_integrity();
 
     // Get symbol stats from the symbolizer
     let symbol_stats = symbol_provider.stats();
 
+    // Merge in what the extra info providers know that the minidump doesn't
+    let module_names = modules
+        .iter()
+        .map(|module| basename(&module.code_file()).to_string())
+        .chain(unloaded_modules.iter().map(|module| module.name.clone()))
+        .collect::<BTreeSet<_>>();
+    let mut cert_info = HashMap::new();
+    let mut module_annotations = HashMap::new();
+    for name in module_names {
+        if let Some(cert) = extra_info
+            .iter()
+            .rev()
+            .find_map(|extra| extra.module_cert(&name))
+        {
+            cert_info.insert(name.clone(), cert);
+        }
+        let annotations = extra_info
+            .iter()
+            .flat_map(|extra| extra.module_annotations(&name))
+            .collect::<BTreeMap<_, _>>();
+        if !annotations.is_empty() {
+            module_annotations.insert(name, annotations);
+        }
+    }
+    let extra_json = extra_info
+        .iter()
+        .flat_map(|extra| extra.json_fields())
+        .collect();
+
     let mut state = ProcessState {
         process_id,
         time: SystemTime::UNIX_EPOCH + Duration::from_secs(dump.header.time_date_stamp as u64),
         process_create_time,
-        cert_info: evil.certs,
+        process_create_time_source,
+        cert_info,
+        module_annotations,
+        extra_json,
         exception_info,
         assertion,
+        likely_abort: None,
+        rop_chain: None,
+        exploitability: None,
+        stack_overflow: None,
+        sanitizer_report: None,
         requesting_thread,
+        crashing_thread_heuristic,
+        crashing_thread_guess: None,
         system_info,
         linux_standard_base,
         mac_crash_info,
+        mac_boot_args,
+        crashpad_info,
+        process_memory_usage,
+        linux_proc_status,
+        captured_backtraces,
         threads,
         modules,
         unloaded_modules,
+        unlisted_modules,
+        memory_regions,
+        crash_memory: Vec::new(),
         unknown_streams,
         unimplemented_streams,
         symbol_stats,
+        report_symbol_stats: options.report_symbol_stats,
+        report_memory_info: options.report_memory_info,
+        report_frame_registers: options.report_frame_registers,
+        integrity_issues,
     };
 
     // Report the unwalked result
@@ -687,89 +1552,961 @@ where
 
     {
         let memory_list = &memory_list;
+        let memory_info = memory_info.as_ref();
         let modules = &state.modules;
         let system_info = &state.system_info;
         let unloaded_modules = &state.unloaded_modules;
         let options = &options;
 
-        futures_util::future::join_all(
+        // Every walk borrows its own entry of `state.threads`, so the order
+        // the walks finish in doesn't matter. Each is in a span naming its
+        // thread, so that the logs of walks running at the same time can be
+        // told apart.
+        futures_util::stream::iter(
             state
                 .threads
                 .iter_mut()
                 .zip(thread_list.threads.iter())
                 .enumerate()
-                .map(|(i, (stack, thread))| async move {
-                    let mut stack_memory = thread.stack_memory(memory_list);
-                    // Always chose the memory region that is referenced by the context,
-                    // as the `exception_context` may refer to a different memory region than
-                    // the `thread_context`, which in turn would fail to stack walk.
-                    let stack_ptr = stack
-                        .frames
-                        .get(0)
-                        .map(|ctx_frame| ctx_frame.context.get_stack_pointer());
-                    if let Some(stack_ptr) = stack_ptr {
-                        let contains_stack_ptr = stack_memory
-                            .as_ref()
-                            .and_then(|memory| memory.get_memory_at_address::<u64>(stack_ptr))
-                            .is_some();
-                        if !contains_stack_ptr {
-                            stack_memory = memory_list
-                                .memory_at_address(stack_ptr)
-                                .map(Cow::Borrowed)
-                                .or(stack_memory);
+                .map(|(i, (stack, thread))| {
+                    let span = tracing::info_span!(
+                        "thread",
+                        idx = i,
+                        tid = stack.thread_id,
+                        tname = stack.thread_name.as_deref().unwrap_or("")
+                    );
+                    async move {
+                        let declared_stack_memory;
+                        let mut stack_memory = if options.constrain_to_thread_stack {
+                            declared_stack_memory = thread.declared_stack_memory(memory_list);
+                            declared_stack_memory.as_ref().map(UnifiedMemory::Memory)
+                        } else {
+                            thread.stack_memory(memory_list)
+                        };
+                        // Always chose the memory region that is referenced by the context,
+                        // as the `exception_context` may refer to a different memory region than
+                        // the `thread_context`, which in turn would fail to stack walk.
+                        // (Unless we were asked to stay on the thread's stack.)
+                        let stack_ptr = stack
+                            .frames
+                            .get(0)
+                            .map(|ctx_frame| ctx_frame.context.get_stack_pointer())
+                            .filter(|_| !options.constrain_to_thread_stack);
+                        if let Some(stack_ptr) = stack_ptr {
+                            let contains_stack_ptr = stack_memory
+                                .as_ref()
+                                .and_then(|memory| memory.get_memory_at_address::<u64>(stack_ptr))
+                                .is_some();
+                            if !contains_stack_ptr {
+                                stack_memory =
+                                    memory_list.memory_at_address(stack_ptr).or(stack_memory);
+                            }
                         }
-                    }
 
-                    stackwalker::walk_stack(
-                        i,
-                        options,
-                        stack,
-                        stack_memory.as_deref(),
-                        modules,
-                        system_info,
-                        symbol_provider,
-                    )
-                    .await;
-
-                    for frame in &mut stack.frames {
-                        // If the frame doesn't have a loaded module, try to find an unloaded module
-                        // that overlaps with its address range. The may be multiple, so record all
-                        // of them and the offsets this frame has in them.
-                        if frame.module.is_none() {
-                            let mut offsets = BTreeMap::new();
-                            for unloaded in unloaded_modules.modules_at_address(frame.instruction) {
-                                let offset = frame.instruction - unloaded.raw.base_of_image;
-                                offsets
-                                    .entry(unloaded.name.clone())
-                                    .or_insert_with(BTreeSet::new)
-                                    .insert(offset);
-                            }
+                        // The stack may be continued by other regions of the memory list
+                        // (explicitly added memory that overlaps it, say), so let the
+                        // walker read past the artificial edge between them.
+                        let merged_stack = stack_memory
+                            .filter(|_| !options.constrain_to_thread_stack)
+                            .and_then(|memory| {
+                                let merged =
+                                    memory_list.merged_memory_at_address(memory.base_address())?;
+                                let covers_stack = merged.base_address() <= memory.base_address()
+                                    && merged.base_address().saturating_add(merged.size())
+                                        >= memory.base_address().saturating_add(memory.size());
+                                (merged.region_count() > 1 && covers_stack).then_some(merged)
+                            });
+                        let merged_stack_memory =
+                            merged_stack.as_ref().map(MergedMemory::as_memory);
+                        if let Some(memory) = &merged_stack_memory {
+                            stack_memory = Some(UnifiedMemory::Memory(memory));
+                        }
 
-                            frame.unloaded_modules = offsets;
+                        stackwalker::walk_stack(
+                            i,
+                            options,
+                            stack,
+                            stack_memory.as_ref(),
+                            &stackwalker::DumpMemory {
+                                memory_list: Some(memory_list),
+                                memory_info,
+                            },
+                            modules,
+                            system_info,
+                            symbol_provider,
+                        )
+                        .await;
+
+                        annotate_unloaded_modules(&mut stack.frames, unloaded_modules);
+                        symbolicate_unloaded_frames(
+                            &mut stack.frames,
+                            unloaded_modules,
+                            modules,
+                            symbol_provider,
+                        )
+                        .await;
+
+                        if options.recover_function_args {
+                            arg_recovery::fill_arguments(
+                                stack,
+                                stack_memory.as_ref(),
+                                system_info.os,
+                            );
                         }
-                    }
 
-                    if options.recover_function_args {
-                        arg_recovery::fill_arguments(stack, stack_memory.as_deref());
-                    }
+                        // Report the unwalked result
+                        if let Some(reporter) = options.stat_reporter {
+                            reporter.inc_processed_threads();
+                        }
 
-                    // Report the unwalked result
-                    if let Some(reporter) = options.stat_reporter {
-                        reporter.inc_processed_threads();
+                        stack
                     }
-
-                    stack
+                    .instrument(span)
                 }),
         )
+        .buffer_unordered(options.thread_concurrency.max(1))
+        .collect::<Vec<_>>()
         .await
     };
 
-    let symbol_stats = symbol_provider.stats();
+    if options.guess_crashing_thread && state.requesting_thread.is_none() && !exception_thread_found
+    {
+        let guess = guess_faulting_thread(
+            &state,
+            &thread_list,
+            dump_thread_id,
+            &dump_system_info,
+            misc_info.as_ref(),
+            memory_info.as_ref(),
+            &options,
+        );
+        if let Some((idx, guess)) = guess {
+            tracing::warn!(
+                "no usable exception stream, guessing thread {:#x} crashed",
+                guess.thread_id
+            );
+            state.requesting_thread = Some(idx);
+            state.crashing_thread_guess = Some(guess);
+        }
+    }
+
+    if defer_thread_filter {
+        for (i, stack) in state.threads.iter_mut().enumerate() {
+            let is_crashing = state.requesting_thread == Some(i);
+            if stack.info != CallStackInfo::DumpThreadSkipped
+                && !options.thread_filter.includes(
+                    stack.thread_id,
+                    stack.thread_name.as_deref(),
+                    is_crashing,
+                )
+            {
+                *stack = CallStack {
+                    thread_name: stack.thread_name.take(),
+                    stack_range: stack.stack_range.clone(),
+                    ..CallStack::with_info(stack.thread_id, CallStackInfo::NotWalked)
+                };
+            }
+        }
+    }
+
+    if !options.noreturn_functions.is_empty() {
+        mark_noreturn_frames(&mut state, &options.noreturn_functions);
+    }
+
+    if options.detect_rop_chains {
+        state.rop_chain = state
+            .crashing_thread()
+            .map(|stack| RopChainAnalysis::from_stack(stack, &memory_list));
+        if let Some(analysis) = state.rop_chain.as_ref().filter(|analysis| analysis.likely) {
+            tracing::warn!(
+                "crashing thread looks like a ROP chain ({} of {} frames look like gadgets)",
+                analysis.gadget_frames.len(),
+                analysis.frames_examined
+            );
+        }
+    }
+
+    let crashing_stack_range = state
+        .requesting_thread
+        .and_then(|idx| thread_list.threads.get(idx))
+        .map(|thread| {
+            let start = thread.raw.stack.start_of_memory_range;
+            start..start.saturating_add(thread.raw.stack.memory.data_size as u64)
+        });
+
+    state.stack_overflow = StackOverflowAnalysis::from_state(&state, crashing_stack_range.clone());
+
+    if options.dump_crash_memory {
+        state.crash_memory = crash_memory_dumps(&state, &memory_list);
+    }
+
+    if options.analyze_exploitability {
+        state.exploitability = ExploitabilityAnalysis::from_state(
+            &state,
+            crashing_stack_range,
+            options.null_deref_threshold,
+            &memory_list,
+        );
+    }
+
+    if options.extract_sanitizer_report {
+        state.sanitizer_report = SanitizerReport::from_memory(&memory_list);
+    }
+
+    for backtrace in &mut state.captured_backtraces {
+        stackwalker::fill_prewalked_frames(&mut backtrace.frames, &state.modules, symbol_provider)
+            .await;
+        annotate_unloaded_modules(&mut backtrace.frames, &state.unloaded_modules);
+        symbolicate_unloaded_frames(
+            &mut backtrace.frames,
+            &state.unloaded_modules,
+            &state.modules,
+            symbol_provider,
+        )
+        .await;
+    }
+
+    if options.name_normalization.is_enabled() {
+        let thread_frames = state.threads.iter_mut().flat_map(|stack| &mut stack.frames);
+        let captured_frames = state
+            .captured_backtraces
+            .iter_mut()
+            .flat_map(|backtrace| &mut backtrace.frames);
+        for frame in thread_frames.chain(captured_frames) {
+            normalize_function_names(frame, &options.name_normalization);
+        }
+    }
+
+    let mut symbol_stats = symbol_provider.stats();
+    remove_unloaded_module_misses(&mut symbol_stats, &state.modules, &state.unloaded_modules);
+    let thread_frames = state.threads.iter_mut().flat_map(|stack| &mut stack.frames);
+    let captured_frames = state
+        .captured_backtraces
+        .iter_mut()
+        .flat_map(|backtrace| &mut backtrace.frames);
+    for frame in thread_frames.chain(captured_frames) {
+        frame.symbol_miss_reason = symbol_miss_reason(frame, &symbol_stats, symbol_provider).await;
+    }
     state.symbol_stats = symbol_stats;
 
     Ok(state)
 }
 
+/// Whether `module`'s symbols have a function (or public symbol) covering
+/// `address`, even if it has no name.
+async fn symbols_cover_address<P>(
+    module: &MinidumpModule,
+    address: u64,
+    symbol_provider: &P,
+) -> bool
+where
+    P: SymbolProvider + Sync,
+{
+    struct CoverageFrame {
+        instruction: u64,
+        covered: bool,
+    }
+    impl FrameSymbolizer for CoverageFrame {
+        fn get_instruction(&self) -> u64 {
+            self.instruction
+        }
+        fn set_function(&mut self, _name: &str, _base: u64, _parameter_size: u32) {
+            self.covered = true;
+        }
+        fn set_source_file(&mut self, _file: &str, _line: u32, _base: u64) {}
+    }
+
+    let mut frame = CoverageFrame {
+        instruction: address,
+        covered: false,
+    };
+    let _ = symbol_provider.fill_symbol(module, &mut frame).await;
+    frame.covered
+}
+
+/// Dump the memory around the crashing thread's instruction pointer, and at
+/// its stack pointer, for [`ProcessState::crash_memory`].
+fn crash_memory_dumps(state: &ProcessState, memory_list: &UnifiedMemoryList) -> Vec<MemoryDump> {
+    let context = match state
+        .crashing_thread()
+        .and_then(|stack| stack.frames.first())
+    {
+        Some(frame) => &frame.context,
+        None => return Vec::new(),
+    };
+    let byte_at = |addr: u64| -> Option<u8> {
+        memory_list
+            .memory_at_address(addr)?
+            .get_memory_at_address(addr)
+    };
+    let module_offset = |addr: u64| -> Option<String> {
+        let module = state.modules.module_at_address(addr)?;
+        Some(format!(
+            "{}+{:#x}",
+            basename(&module.code_file()),
+            addr - module.base_address()
+        ))
+    };
+    let dump = |kind: MemoryDumpKind, address: u64, start: u64| -> MemoryDump {
+        let bytes = (0..MEMORY_DUMP_SIZE as u64)
+            .map(|offset| start.checked_add(offset).and_then(byte_at))
+            .collect();
+        MemoryDump {
+            kind,
+            address,
+            start,
+            bytes,
+            annotations: BTreeMap::new(),
+        }
+    };
+
+    let pc = context.get_instruction_pointer();
+    let mut pc_dump = dump(
+        MemoryDumpKind::InstructionPointer,
+        pc,
+        pc.saturating_sub(MEMORY_DUMP_SIZE as u64 / 2) & !0xf,
+    );
+    if let Some(location) = module_offset(pc) {
+        pc_dump.annotations.insert(pc, location);
+    }
+
+    let sp = context.get_stack_pointer();
+    let mut sp_dump = dump(MemoryDumpKind::StackPointer, sp, sp);
+    let pointer_size = match state.system_info.cpu.pointer_width() {
+        system_info::PointerWidth::Bits64 => 8,
+        _ => 4,
+    };
+    for (i, chunk) in sp_dump.bytes.chunks_exact(pointer_size).enumerate() {
+        // Pointers are assumed to be little-endian, as they are on every
+        // architecture we walk stacks for.
+        let value = chunk
+            .iter()
+            .rev()
+            .try_fold(0u64, |value, byte| Some(value << 8 | u64::from((*byte)?)));
+        if let Some(location) = value.and_then(module_offset) {
+            let addr = sp + (i * pointer_size) as u64;
+            sp_dump.annotations.insert(addr, location);
+        }
+    }
+
+    vec![pc_dump, sp_dump]
+}
+
+/// Find the id of the thread `exception_context` most likely came from.
+///
+/// Threads are ranked by how well their context matches the exception
+/// context: the same instruction and stack pointer is best, then just the
+/// same stack pointer, then a stack pointer that's at least in the thread's
+/// stack memory. The thread that wrote the dump is never chosen, and neither
+/// is anything if several threads match equally well, since then there's
+/// no telling which one crashed.
+fn guess_crashing_thread(
+    exception_context: &MinidumpContext,
+    thread_list: &MinidumpThreadList,
+    dump_thread_id: Option<u32>,
+    system_info: &MinidumpSystemInfo,
+    misc_info: Option<&MinidumpMiscInfo>,
+    memory_list: &UnifiedMemoryList,
+) -> Option<u32> {
+    let pc = exception_context.get_instruction_pointer();
+    let sp = exception_context.get_stack_pointer();
+
+    let mut best = None;
+    let mut best_score = 0;
+    let mut tied = false;
+    for thread in &thread_list.threads {
+        let id = thread.raw.thread_id;
+        if dump_thread_id == Some(id) {
+            continue;
+        }
+        let context = thread.context(system_info, misc_info);
+        let score = match context.as_deref() {
+            Some(ctx) if ctx.get_stack_pointer() == sp && ctx.get_instruction_pointer() == pc => 3,
+            Some(ctx) if ctx.get_stack_pointer() == sp => 2,
+            _ => match thread.stack_memory(memory_list) {
+                Some(stack)
+                    if sp >= stack.base_address() && sp - stack.base_address() < stack.size() =>
+                {
+                    1
+                }
+                _ => 0,
+            },
+        };
+        if score > best_score {
+            best = Some(id);
+            best_score = score;
+            tied = false;
+        } else if score == best_score {
+            tied = true;
+        }
+    }
+
+    if tied {
+        None
+    } else {
+        best
+    }
+}
+
+/// Functions that run signal handlers (or dispatch exceptions to handlers),
+/// for [`guess_faulting_thread`].
+const SIGNAL_HANDLER_FUNCTIONS: &[&str] = &[
+    // Linux
+    "__restore_rt",
+    "__kernel_rt_sigreturn",
+    "__kernel_sigreturn",
+    // macOS
+    "_sigtramp",
+    // Windows
+    "KiUserExceptionDispatcher",
+];
+
+/// Find the thread that most likely crashed, for
+/// [`ProcessorOptions::guess_crashing_thread`], and its index in
+/// `state.threads`.
+///
+/// Frames are only known for threads that were walked, so the other threads
+/// can only be judged by their instruction pointer. As in
+/// [`guess_crashing_thread`], the thread that wrote the dump is never chosen,
+/// and neither is anything if several threads score equally well.
+fn guess_faulting_thread(
+    state: &ProcessState,
+    thread_list: &MinidumpThreadList,
+    dump_thread_id: Option<u32>,
+    system_info: &MinidumpSystemInfo,
+    misc_info: Option<&MinidumpMiscInfo>,
+    memory_info: Option<&UnifiedMemoryInfoList>,
+    options: &ProcessorOptions,
+) -> Option<(usize, CrashingThreadGuess)> {
+    let default_abort_functions;
+    let abort_functions = if options.noreturn_functions.is_empty() {
+        default_abort_functions = ProcessorOptions::default_noreturn_functions();
+        &default_abort_functions
+    } else {
+        &options.noreturn_functions
+    };
+
+    let mut best = None;
+    let mut best_score = 0;
+    let mut tied = false;
+    for (idx, (thread, stack)) in thread_list.threads.iter().zip(&state.threads).enumerate() {
+        let id = thread.raw.thread_id;
+        if dump_thread_id == Some(id) {
+            continue;
+        }
+
+        let mut reasons = Vec::new();
+        if let Some(context) = thread.context(system_info, misc_info) {
+            let pc = context.get_instruction_pointer();
+            if pc < options.null_deref_threshold {
+                reasons.push(CrashingThreadGuessReason::PcNearNull);
+            }
+            if let Some(memory_info) = memory_info {
+                let executable = matches!(memory_info.memory_info_at_address(pc),
+                    Some(info) if info.is_executable());
+                if !executable {
+                    reasons.push(CrashingThreadGuessReason::PcNotExecutable);
+                }
+            }
+        }
+        let function_names = stack.frames.iter().flat_map(|frame| {
+            let inlined = frame.inlines.iter().map(|inline| &inline.function_name);
+            inlined.chain(frame.function_name.as_ref())
+        });
+        let mut in_abort = None;
+        let mut in_signal_handler = None;
+        for name in function_names {
+            if in_abort.is_none() && is_noreturn_function(name, abort_functions) {
+                in_abort = Some(name.clone());
+            }
+            let bare_name = name.split_once('(').map_or(name.as_str(), |(name, _)| name);
+            if in_signal_handler.is_none() && SIGNAL_HANDLER_FUNCTIONS.contains(&bare_name.trim()) {
+                in_signal_handler = Some(name.clone());
+            }
+        }
+        if let Some(function) = in_abort {
+            reasons.push(CrashingThreadGuessReason::InAbortFunction { function });
+        }
+        if let Some(function) = in_signal_handler {
+            reasons.push(CrashingThreadGuessReason::SignalHandlerFrame { function });
+        }
+
+        // Frames in abort functions and signal handlers are much more telling
+        // than an instruction pointer that's merely somewhere odd.
+        let score = reasons
+            .iter()
+            .map(|reason| match reason {
+                CrashingThreadGuessReason::PcNearNull
+                | CrashingThreadGuessReason::PcNotExecutable => 1,
+                CrashingThreadGuessReason::InAbortFunction { .. }
+                | CrashingThreadGuessReason::SignalHandlerFrame { .. } => 2,
+            })
+            .sum::<u32>();
+        if score > best_score {
+            best = Some((
+                idx,
+                CrashingThreadGuess {
+                    thread_id: id,
+                    reasons,
+                },
+            ));
+            best_score = score;
+            tied = false;
+        } else if score == best_score {
+            tied = true;
+        }
+    }
+
+    if tied {
+        None
+    } else {
+        best
+    }
+}
+
+/// Whether `function_name` is one of `noreturn_functions`, ignoring any parameter list.
+fn is_noreturn_function(function_name: &str, noreturn_functions: &[String]) -> bool {
+    let name = function_name
+        .split_once('(')
+        .map_or(function_name, |(name, _params)| name)
+        .trim();
+    noreturn_functions.iter().any(|f| f == name)
+}
+
+/// Set [`StackFrame::noreturn`] on every frame in a noreturn function, and
+/// [`ProcessState::likely_abort`] to the first one on the crashing thread.
+fn mark_noreturn_frames(state: &mut ProcessState, noreturn_functions: &[String]) {
+    for stack in &mut state.threads {
+        for frame in &mut stack.frames {
+            let inlined = frame.inlines.iter().map(|inline| &inline.function_name);
+            frame.noreturn = frame
+                .function_name
+                .iter()
+                .chain(inlined)
+                .any(|name| is_noreturn_function(name, noreturn_functions));
+        }
+    }
+
+    state.likely_abort = state
+        .requesting_thread
+        .and_then(|idx| state.threads.get(idx))
+        .and_then(|stack| stack.frames.iter().find(|frame| frame.noreturn))
+        .map(|frame| {
+            // Prefer the innermost inlined name that matched.
+            frame
+                .inlines
+                .iter()
+                .map(|inline| &inline.function_name)
+                .chain(frame.function_name.iter())
+                .find(|name| is_noreturn_function(name, noreturn_functions))
+                .cloned()
+                .unwrap_or_default()
+        });
+}
+
+/// Fill in the normalized function names of `frame` and its inlines.
+fn normalize_function_names(frame: &mut StackFrame, rules: &NameNormalization) {
+    let normalize =
+        |name: &str| Some(rules.normalize(name)).filter(|normalized| normalized != name);
+    frame.normalized_function_name = frame.function_name.as_deref().and_then(normalize);
+    for inline in &mut frame.inlines {
+        inline.normalized_function_name = normalize(&inline.function_name);
+    }
+}
+
+/// Find the shared libraries in the dynamic linker's list that aren't in `modules`.
+///
+/// A library is listed if a module contains its load address or has its path. The
+/// main executable's entry (which has no name) is skipped.
+fn unlisted_modules(
+    dso_debug: &MinidumpLinuxDsoDebug,
+    modules: &MinidumpModuleList,
+) -> Vec<UnlistedModule> {
+    dso_debug
+        .iter()
+        .filter(|&(addr, name)| {
+            !name.is_empty()
+                && modules.module_at_address(addr).is_none()
+                && !modules.iter().any(|module| module.code_file() == name)
+        })
+        .map(|(addr, name)| UnlistedModule {
+            name: name.to_owned(),
+            load_address: addr,
+        })
+        .collect()
+}
+
+/// The regions of `memory_info`, in order by address, for
+/// [`ProcessState::memory_regions`].
+///
+/// Regions from a Windows memory info list get their state, protection, and
+/// type, and regions from Linux maps get their permissions.
+fn memory_regions(
+    memory_info: Option<&UnifiedMemoryInfoList>,
+    threads: &MinidumpThreadList,
+) -> Vec<MemoryRegion> {
+    let regions = memory_info
+        .into_iter()
+        .flat_map(UnifiedMemoryInfoList::by_addr);
+    regions
+        .filter_map(|info| {
+            let range = info.memory_range()?;
+            let mut region = MemoryRegion {
+                base_address: range.start,
+                size: (range.end - range.start).saturating_add(1),
+                kind: info.kind(Some(threads)),
+                state: None,
+                protection: None,
+                memory_type: None,
+                permissions: None,
+            };
+            match info {
+                UnifiedMemoryInfo::Info(info) => {
+                    region.state = Some(info.state);
+                    region.protection = Some(info.protection);
+                    region.memory_type = Some(info.ty);
+                }
+                UnifiedMemoryInfo::Map(map) => {
+                    let flag = |set: bool, c: char| if set { c } else { '-' };
+                    let permissions = [
+                        flag(map.is_read, 'r'),
+                        flag(map.is_write, 'w'),
+                        flag(map.is_exec, 'x'),
+                        if map.is_shared { 's' } else { 'p' },
+                    ];
+                    region.permissions = Some(permissions.iter().collect());
+                }
+            }
+            Some(region)
+        })
+        .collect()
+}
+
+/// Describe an assertion the same way Breakpad's processor does, e.g.
+/// `Invalid parameter passed to library function expr in function foo, in file bar.cc at line 42`.
+fn describe_assertion(assertion: &MinidumpAssertion) -> String {
+    let mut description = match assertion.assertion_type() {
+        Some(format::AssertionType::InvalidParameter) => {
+            String::from("Invalid parameter passed to library function")
+        }
+        Some(format::AssertionType::PureVirtualCall) => {
+            String::from("Pure virtual function called")
+        }
+        _ => format!("Unknown assertion type {:#010x}", assertion.raw._type),
+    };
+    if let Some(expression) = assertion.expression().filter(|s| !s.is_empty()) {
+        description.push(' ');
+        description.push_str(&expression);
+    }
+    if let Some(function) = assertion.function().filter(|s| !s.is_empty()) {
+        description.push_str(" in function ");
+        description.push_str(&function);
+    }
+    if let Some(file) = assertion.file().filter(|s| !s.is_empty()) {
+        description.push_str(", in file ");
+        description.push_str(&file);
+    }
+    if assertion.line() != 0 {
+        description.push_str(&format!(" at line {}", assertion.line()));
+    }
+    description
+}
+
+/// When the process started, and where that came from.
+///
+/// MISC_INFO's process times are preferred, then the creation time of the
+/// earliest-created thread, then a Crashpad `StartupTime` annotation.
+fn process_create_time(
+    misc_info: Option<&MinidumpMiscInfo>,
+    thread_infos: Option<&MinidumpThreadInfoList>,
+    crashpad_info: Option<&MinidumpCrashpadInfo>,
+) -> Option<(SystemTime, ProcessCreateTimeSource)> {
+    if let Some(time) = misc_info.and_then(MinidumpMiscInfo::process_create_time) {
+        return Some((time, ProcessCreateTimeSource::MiscInfo));
+    }
+    let earliest_thread = thread_infos
+        .into_iter()
+        .flat_map(MinidumpThreadInfoList::iter)
+        .filter_map(MinidumpThreadInfo::create_time)
+        .min();
+    if let Some(time) = earliest_thread {
+        return Some((time, ProcessCreateTimeSource::ThreadInfo));
+    }
+    // Clients usually annotate their own module rather than the process.
+    let crashpad_info = crashpad_info?;
+    let startup_time = std::iter::once(&crashpad_info.simple_annotations)
+        .chain(
+            crashpad_info
+                .module_list
+                .iter()
+                .map(|m| &m.simple_annotations),
+        )
+        .find_map(|annotations| annotations.get("StartupTime"))?;
+    // Accept fractional seconds, but only keep the whole ones.
+    let seconds = startup_time.trim().split('.').next()?.parse::<u64>().ok()?;
+    let time = SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(seconds))?;
+    Some((time, ProcessCreateTimeSource::CrashpadAnnotation))
+}
+
+/// Extract the backtraces recorded in the `__crash_info` section of macOS binaries.
+///
+/// These are freeform strings, but they're usually formatted like the
+/// backtraces in Apple's crash reports, with one frame per line:
+///
+/// `0   CoreFoundation    0x00007fff2050e8ab __exceptionPreprocess + 242`
+///
+/// So we just take the first hex number on each line as that frame's address.
+fn captured_mac_backtraces(
+    records: &[RawMacCrashInfo],
+    template: &MinidumpContext,
+) -> Vec<CapturedBacktrace> {
+    records
+        .iter()
+        .filter_map(|record| {
+            let addresses = parse_backtrace_addresses(record.backtrace()?);
+            if addresses.is_empty() {
+                return None;
+            }
+            let frames = addresses
+                .into_iter()
+                .enumerate()
+                .map(|(idx, address)| prewalked_frame(template, idx, address))
+                .collect();
+            Some(CapturedBacktrace {
+                source: "mac_crash_info",
+                thread_id: record.thread().copied(),
+                frames,
+            })
+        })
+        .collect()
+}
+
+fn parse_backtrace_addresses(backtrace: &str) -> Vec<u64> {
+    backtrace
+        .lines()
+        .filter_map(|line| {
+            line.split_whitespace().find_map(|token| {
+                token
+                    .strip_prefix("0x")
+                    .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+            })
+        })
+        .collect()
+}
+
+/// Record the unloaded modules that overlap each frame that isn't in a loaded module.
+///
+/// There may be several (and a module may have been loaded at several
+/// addresses), so this records all of them and the offsets the frame has in
+/// them.
+fn annotate_unloaded_modules(
+    frames: &mut [StackFrame],
+    unloaded_modules: &MinidumpUnloadedModuleList,
+) {
+    for frame in frames.iter_mut().filter(|frame| frame.module.is_none()) {
+        let mut offsets = BTreeMap::new();
+        let mut candidates = Vec::new();
+        for unloaded in unloaded_modules.unloaded_modules_at_address(frame.instruction) {
+            let offset = frame.instruction - unloaded.raw.base_of_image;
+            offsets
+                .entry(unloaded.name.clone())
+                .or_insert_with(BTreeSet::new)
+                .insert(offset);
+            candidates.push(UnloadedModuleCandidate {
+                name: unloaded.name.clone(),
+                base_address: unloaded.raw.base_of_image,
+                offset,
+                checksum: unloaded.checksum(),
+                time_date_stamp: unloaded.time_date_stamp(),
+            });
+        }
+
+        frame.unloaded_modules = offsets;
+        frame.unloaded_module_candidates = candidates;
+    }
+}
+
+/// An unloaded module, as a [`Module`] to look up symbols for.
+///
+/// Unloaded modules only record their name, size, checksum, and timestamp,
+/// so their code identifier is derived from those, and they have no debug
+/// file or identifier of their own. Their symbols are looked up by code file
+/// and identifier instead (see
+/// [`breakpad_symbols::code_info_breakpad_sym_lookup`]). If a copy of the same module (the same
+/// code file and identifier) is still loaded, its debug file and identifier
+/// are borrowed, so the two share their symbols.
+struct UnloadedModuleLookup<'a> {
+    unloaded: &'a MinidumpUnloadedModule,
+    loaded: Option<&'a MinidumpModule>,
+}
+
+impl Module for UnloadedModuleLookup<'_> {
+    fn base_address(&self) -> u64 {
+        self.unloaded.base_address()
+    }
+    fn size(&self) -> u64 {
+        self.unloaded.size()
+    }
+    fn code_file(&self) -> Cow<'_, str> {
+        match self.loaded {
+            Some(loaded) => loaded.code_file(),
+            None => self.unloaded.code_file(),
+        }
+    }
+    fn code_identifier(&self) -> Option<CodeId> {
+        self.unloaded.code_identifier()
+    }
+    fn debug_file(&self) -> Option<Cow<'_, str>> {
+        self.loaded?.debug_file()
+    }
+    fn debug_identifier(&self) -> Option<DebugId> {
+        self.loaded?.debug_identifier()
+    }
+    fn version(&self) -> Option<Cow<'_, str>> {
+        self.loaded?.version()
+    }
+}
+
+/// Symbolicate the frames that are in an unloaded module, with that module's
+/// symbols.
+///
+/// The candidates [`annotate_unloaded_modules`] found are tried in order, and
+/// the first whose symbols have the frame's address wins, and is recorded in
+/// [`StackFrame::unloaded_module_symbols`].
+async fn symbolicate_unloaded_frames<P>(
+    frames: &mut [StackFrame],
+    unloaded_modules: &MinidumpUnloadedModuleList,
+    modules: &MinidumpModuleList,
+    symbol_provider: &P,
+) where
+    P: SymbolProvider + Sync,
+{
+    for frame in frames.iter_mut().filter(|frame| frame.module.is_none()) {
+        // The candidates were collected from the same iterator, so they line up.
+        let candidates = unloaded_modules
+            .unloaded_modules_at_address(frame.instruction)
+            .zip(frame.unloaded_module_candidates.clone())
+            .collect::<Vec<_>>();
+        for (unloaded, candidate) in candidates {
+            let name = basename(&unloaded.name);
+            let code_id = unloaded.code_identifier();
+            let mut loaded_copies = modules
+                .iter()
+                .filter(|module| basename(&module.code_file()).eq_ignore_ascii_case(name))
+                .peekable();
+            let any_copy_loaded = loaded_copies.peek().is_some();
+            let loaded = loaded_copies.find(|module| module.code_identifier() == code_id);
+            // Symbol statistics are kept by module name, so a lookup of a
+            // different version of a module that's loaded would muddle the
+            // statistics of the loaded one.
+            if loaded.is_none() && any_copy_loaded {
+                continue;
+            }
+
+            let lookup = UnloadedModuleLookup { unloaded, loaded };
+            if symbol_provider.fill_symbol(&lookup, frame).await.is_ok()
+                && frame.function_name.is_some()
+            {
+                frame.unloaded_module_symbols = Some(candidate);
+                break;
+            }
+        }
+    }
+}
+
+/// Remove the statistics of the unloaded modules whose symbols weren't found.
+///
+/// Every frame in an unloaded module has its symbols looked up, on the off
+/// chance the symbol server has them, and failing to find them isn't worth
+/// reporting. The lookups [`symbolicate_unloaded_frames`] skips, of modules
+/// with a loaded copy, can't have added anything here.
+fn remove_unloaded_module_misses(
+    symbol_stats: &mut HashMap<String, SymbolStats>,
+    modules: &MinidumpModuleList,
+    unloaded_modules: &MinidumpUnloadedModuleList,
+) {
+    for unloaded in unloaded_modules.iter() {
+        let name = basename(&unloaded.name);
+        let loaded = modules
+            .iter()
+            .any(|module| basename(&module.code_file()).eq_ignore_ascii_case(name));
+        if !loaded && matches!(symbol_stats.get(name), Some(stats) if !stats.loaded_symbols) {
+            symbol_stats.remove(name);
+        }
+    }
+}
+
+/// Why `frame` has no function name, judging by what the symbolizer knows
+/// about its module.
+///
+/// Returns `None` if the frame has a function name, the symbolizer never
+/// looked up the symbols of its module, or the symbols cover the frame's
+/// address after all.
+async fn symbol_miss_reason<P>(
+    frame: &StackFrame,
+    symbol_stats: &HashMap<String, SymbolStats>,
+    symbol_provider: &P,
+) -> Option<SymbolMissReason>
+where
+    P: SymbolProvider + Sync,
+{
+    if frame.function_name.is_some() {
+        return None;
+    }
+    let module = match &frame.module {
+        Some(module) => module,
+        None => return Some(SymbolMissReason::NoModule),
+    };
+    let stats = symbol_stats.get(basename(&module.code_file()))?;
+    Some(if stats.corrupt_symbols {
+        SymbolMissReason::CorruptSymbolFile
+    } else if stats.loaded_symbols {
+        if symbols_cover_address(module, frame.instruction, symbol_provider).await {
+            return None;
+        }
+        SymbolMissReason::AddressNotCovered
+    } else if stats.fetch_failed {
+        SymbolMissReason::FetchFailed {
+            status: stats.fetch_status,
+        }
+    } else {
+        SymbolMissReason::MissingSymbolFile
+    })
+}
+
+/// A context for `cpu` with no valid registers, if the stack walker supports it.
+fn empty_context(cpu: system_info::Cpu) -> Option<MinidumpContext> {
+    use system_info::Cpu;
+    let raw = match cpu {
+        Cpu::X86 => MinidumpRawContext::X86(Default::default()),
+        Cpu::X86_64 => MinidumpRawContext::Amd64(Default::default()),
+        Cpu::Arm => MinidumpRawContext::Arm(Default::default()),
+        Cpu::Arm64 => MinidumpRawContext::Arm64(Default::default()),
+        Cpu::Mips | Cpu::Mips64 => MinidumpRawContext::Mips(Default::default()),
+        _ => return None,
+    };
+    Some(MinidumpContext {
+        raw,
+        valid: MinidumpContextValidity::Some(HashSet::new()),
+    })
+}
+
+/// Make a frame with no valid registers for an address some other backtracer found.
+fn prewalked_frame(template: &MinidumpContext, frame_idx: usize, address: u64) -> StackFrame {
+    let mut context = template.clone();
+    context.valid = MinidumpContextValidity::Some(HashSet::new());
+    let mut frame = StackFrame::from_context(context, FrameTrust::PreWalked);
+    frame.resume_address = address;
+    // Every frame but the first is a return address, so back up into the
+    // call instruction, the same as the real stack walker does.
+    frame.instruction = if frame_idx == 0 {
+        address
+    } else {
+        address.saturating_sub(1)
+    };
+    frame
+}
+
 /// Fix the crash address if a non-canonical access caused a crash
 ///
 /// Amd64 has the concept of a "canonical addressing", which requires that the upper 16 bits of
@@ -824,6 +2561,190 @@ fn fix_non_canonical_crash_address(
     tracing::warn!("somehow got a non-canonical address exception in an instruction that doesn't appear to access one");
 }
 
+/// The first of the crash address and the addresses the crashing instruction
+/// accessed that matches a [`PoisonPattern`].
+///
+/// The accessed addresses are computed from the registers the instruction
+/// dereferences, so this catches poisoned pointers in those registers too.
+fn poisoned_address(exception_info: &crate::ExceptionInfo) -> Option<crate::PoisonedAddress> {
+    let accessed = exception_info.memory_accesses.iter().flatten();
+    std::iter::once(exception_info.address)
+        .chain(accessed.map(|access| access.address))
+        .find_map(|address| {
+            poison_pattern(address).map(|pattern| crate::PoisonedAddress { address, pattern })
+        })
+}
+
+/// The kind of access that faulted, if the crash is a memory access fault.
+///
+/// The access is `None` if the fault doesn't say. Faults at the crashing
+/// context's instruction pointer are execute faults.
+fn fault_access(
+    exception_info: &crate::ExceptionInfo,
+    context: Option<&MinidumpContext>,
+) -> Option<Option<minidump_common::errors::ExceptionCodeWindowsAccessType>> {
+    let access = crate::exploitability::access_violation(exception_info.reason)?;
+    let access = match (access, exception_info.windows_parameters) {
+        (Some(access), _) => Some(access),
+        (None, Some(WindowsExceptionParameters::AccessViolation { access, .. })) => access,
+        _ => None,
+    };
+    let pc = context.map(|context| context.get_instruction_pointer());
+    Some(access.or_else(|| {
+        (pc == Some(exception_info.address))
+            .then_some(minidump_common::errors::ExceptionCodeWindowsAccessType::EXEC)
+    }))
+}
+
+/// Work out which region of memory the crash address is in.
+///
+/// Modules and thread stacks are recognized even without memory info, but
+/// everything else (and protections) needs it. Returns `None` if nothing
+/// knows anything about the address.
+fn crash_address_details(
+    exception_info: &crate::ExceptionInfo,
+    context: Option<&MinidumpContext>,
+    modules: &MinidumpModuleList,
+    threads: &MinidumpThreadList,
+    memory_info: Option<&UnifiedMemoryInfoList>,
+) -> Option<crate::CrashAddressDetails> {
+    let address = exception_info.address;
+    let info = memory_info.and_then(|list| list.memory_info_at_address(address));
+    let kind = memory_info.map(|list| list.memory_kind_at_address(address, Some(threads)));
+    let mapped = matches!(&kind, Some(kind) if *kind != MemoryKind::Unmapped);
+
+    let stack_owner = threads
+        .threads
+        .iter()
+        .find(|thread| matches!(thread.declared_stack_range(), Some(range) if range.contains(&address)))
+        .map(|thread| thread.raw.thread_id);
+    let region = if let Some(module) = modules.module_at_address(address) {
+        crate::CrashAddressRegion::Module {
+            name: basename(&module.code_file()).to_string(),
+            offset: address - module.base_address(),
+        }
+    } else if let Some(thread_id) = stack_owner {
+        crate::CrashAddressRegion::Stack {
+            thread_id: Some(thread_id),
+        }
+    } else {
+        match kind? {
+            MemoryKind::Stack { thread_id } => crate::CrashAddressRegion::Stack { thread_id },
+            MemoryKind::Heap | MemoryKind::Anonymous => crate::CrashAddressRegion::Heap,
+            MemoryKind::MappedFile(_) | MemoryKind::Guard => crate::CrashAddressRegion::Mapped,
+            MemoryKind::Unmapped => crate::CrashAddressRegion::Unmapped,
+        }
+    };
+
+    let protection = info
+        .as_ref()
+        .filter(|_| mapped)
+        .map(|info| crate::RegionProtection {
+            read: info.is_readable(),
+            write: info.is_writable(),
+            execute: info.is_executable(),
+        });
+    let nearest_mapped = memory_info.filter(|_| !mapped).and_then(|list| {
+        list.by_addr()
+            .filter(|info| info.kind(None) != MemoryKind::Unmapped)
+            .filter_map(|info| info.memory_range())
+            .map(|range| {
+                let nearest = if range.start > address {
+                    range.start
+                } else {
+                    range.end
+                };
+                crate::NearestMapping {
+                    address: nearest,
+                    distance: nearest.abs_diff(address),
+                }
+            })
+            .min_by_key(|nearest| nearest.distance)
+    });
+    let executed = matches!(
+        fault_access(exception_info, context),
+        Some(Some(
+            minidump_common::errors::ExceptionCodeWindowsAccessType::EXEC
+        ))
+    );
+
+    Some(crate::CrashAddressDetails {
+        region,
+        protection,
+        nearest_mapped,
+        executed_non_executable: executed && matches!(protection, Some(p) if !p.execute),
+    })
+}
+
+/// Registers that [`null_deref`] and [`address_register`] shouldn't blame for
+/// holding a pointer:
+/// instruction pointers (which are the crash address of any execute fault)
+/// and flags.
+const NOT_POINTER_REGISTERS: &[&str] = &[
+    "eip", "rip", "pc", "srr0", "eflags", "cpsr", "fpsr", "fpcr", "fpscr",
+];
+
+/// Describe the crash as a null pointer dereference, if it's a memory access
+/// fault at an address below `threshold`.
+///
+/// The register blamed for holding the null pointer is the one closest below
+/// the crash address, preferring ones that are exactly 0.
+fn null_deref(
+    exception_info: &crate::ExceptionInfo,
+    context: Option<&MinidumpContext>,
+    threshold: u64,
+) -> Option<crate::NullDeref> {
+    let address = exception_info.address;
+    let access = fault_access(exception_info, context)?;
+    if address >= threshold {
+        return None;
+    }
+
+    let register = context.and_then(|context| {
+        context
+            .valid_registers()
+            .filter(|&(name, value)| value <= address && !NOT_POINTER_REGISTERS.contains(&name))
+            .min_by_key(|&(_, value)| (value != 0, address - value))
+            .map(|(name, value)| crate::AddressRegister { name, value })
+    });
+
+    Some(crate::NullDeref {
+        offset: address,
+        access,
+        register,
+    })
+}
+
+/// How far below the crash address [`address_register`] looks for a register.
+const MAX_ADDRESS_REGISTER_OFFSET: u64 = 0x1000;
+
+/// The register the crash address was probably computed from, if the crash is
+/// a memory access fault.
+///
+/// That's the null pointer's register for null pointer dereferences (so
+/// [`null_deref`] has to have run), and otherwise the register closest
+/// below the crash address, if one is at most [`MAX_ADDRESS_REGISTER_OFFSET`]
+/// below it.
+fn address_register(
+    exception_info: &crate::ExceptionInfo,
+    context: Option<&MinidumpContext>,
+) -> Option<crate::AddressRegister> {
+    fault_access(exception_info, context)?;
+    if let Some(null_deref) = &exception_info.null_deref {
+        return null_deref.register;
+    }
+    let address = exception_info.address;
+    context?
+        .valid_registers()
+        .filter(|&(name, value)| {
+            value <= address
+                && address - value <= MAX_ADDRESS_REGISTER_OFFSET
+                && !NOT_POINTER_REGISTERS.contains(&name)
+        })
+        .min_by_key(|&(_, value)| address - value)
+        .map(|(name, value)| crate::AddressRegister { name, value })
+}
+
 /// Report whether the given exception represents a non-canonical access on the given OS
 ///
 /// Different operating systems have different ways of reporting non-canonical address accesses
@@ -873,3 +2794,244 @@ fn is_non_canonical_exception(os: system_info::Os, exception_info: &crate::Excep
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_backtrace_addresses() {
+        let backtrace = "\
+0   CoreFoundation                      0x00007fff2050e8ab __exceptionPreprocess + 242
+1   libobjc.A.dylib                     0x00007fff20247805 objc_exception_throw + 48
+
+2   ???                                 not-an-address
+3   AppKit 0x7fff22ed7b7c -[NSApplication run] + 586
+";
+        assert_eq!(
+            parse_backtrace_addresses(backtrace),
+            vec![0x7fff2050e8ab, 0x7fff20247805, 0x7fff22ed7b7c]
+        );
+    }
+
+    #[test]
+    fn test_poisoned_address() {
+        let exception_info = |address, accessed: &[u64]| crate::ExceptionInfo {
+            reason: CrashReason::Unknown(0, 0),
+            address,
+            instruction_str: None,
+            memory_accesses: Some(
+                accessed
+                    .iter()
+                    .map(|&address| crate::op_analysis::MemoryAccess {
+                        address,
+                        size: Some(8),
+                    })
+                    .collect(),
+            ),
+            address_memory_kind: None,
+            poisoned_address: None,
+            null_deref: None,
+            address_register: None,
+            crash_address_details: None,
+            code: 0,
+            flags: 0,
+            parameters: Vec::new(),
+            windows_parameters: None,
+            nested_exceptions: Vec::new(),
+        };
+        let kind = |info| poisoned_address(&info).map(|poisoned| poisoned.pattern.kind);
+
+        assert_eq!(
+            kind(exception_info(0xdddddddd, &[])),
+            Some("msvc_freed_heap_poison")
+        );
+        // Field accesses through poisoned pointers.
+        assert_eq!(
+            kind(exception_info(0xe5e5e5f5, &[])),
+            Some("mozjemalloc_freed_poison")
+        );
+        // The low 32 bits of a 64-bit address.
+        assert_eq!(
+            kind(exception_info(0x00007ff6cdcdcdd0, &[])),
+            Some("msvc_uninitialized_heap_poison")
+        );
+        assert_eq!(
+            kind(exception_info(0xdead000000000122, &[])),
+            Some("kernel_list_poison")
+        );
+        // Only the instruction's operand is poisoned.
+        let info = exception_info(0x7ff612340000, &[0x10, 0xfeeefeee + 0x38]);
+        let poisoned = poisoned_address(&info).unwrap();
+        assert_eq!(poisoned.address, 0xfeeefeee + 0x38);
+        assert_eq!(poisoned.pattern.kind, "windows_freed_heap_poison");
+
+        assert_eq!(kind(exception_info(0x7ff612340000, &[0x10])), None);
+        assert_eq!(kind(exception_info(0xdddddddd - 8, &[])), None);
+        assert_eq!(kind(exception_info(0x0000000000000100, &[])), None);
+    }
+
+    #[test]
+    fn test_null_deref() {
+        use minidump::format::CONTEXT_AMD64;
+        use minidump_common::errors::ExceptionCodeWindowsAccessType;
+
+        let exception_info = |reason, address| crate::ExceptionInfo {
+            reason,
+            address,
+            instruction_str: None,
+            memory_accesses: None,
+            address_memory_kind: None,
+            poisoned_address: None,
+            null_deref: None,
+            address_register: None,
+            crash_address_details: None,
+            code: 0,
+            flags: 0,
+            parameters: Vec::new(),
+            windows_parameters: None,
+            nested_exceptions: Vec::new(),
+        };
+        let raw = CONTEXT_AMD64 {
+            rip: 0x7ff612340000,
+            rsp: 0x7ffd00001000,
+            rax: 0x30,
+            rbx: 0x7ff612345678,
+            rcx: 0x10,
+            rdx: 0x38,
+            ..CONTEXT_AMD64::default()
+        };
+        let context = MinidumpContext::from_raw(MinidumpRawContext::Amd64(raw.clone()));
+        let write = CrashReason::WindowsAccessViolation(ExceptionCodeWindowsAccessType::WRITE);
+
+        // Registers that are exactly 0 are the likeliest null pointers...
+        let info = exception_info(write, 0x38);
+        let deref = null_deref(&info, Some(&context), 0x10000).unwrap();
+        assert_eq!(deref.access, Some(ExceptionCodeWindowsAccessType::WRITE));
+        assert_eq!(deref.register.unwrap().value, 0);
+
+        // ...otherwise the closest register below the address is.
+        let raw = CONTEXT_AMD64 {
+            rbp: 0x1000,
+            rsi: 0x1000,
+            rdi: 0x1000,
+            r8: 0x1000,
+            r9: 0x1000,
+            r10: 0x1000,
+            r11: 0x1000,
+            r12: 0x1000,
+            r13: 0x1000,
+            r14: 0x1000,
+            r15: 0x1000,
+            ..raw
+        };
+        let context = MinidumpContext::from_raw(MinidumpRawContext::Amd64(raw));
+        let deref = null_deref(&info, Some(&context), 0x10000).unwrap();
+        assert_eq!(
+            deref.register,
+            Some(crate::AddressRegister {
+                name: "rdx",
+                value: 0x38
+            })
+        );
+        let info = exception_info(write, 0x40);
+        let deref = null_deref(&info, Some(&context), 0x10000).unwrap();
+        assert_eq!(
+            deref.description(),
+            "write at offset 0x40 (rdx+0x8 where rdx == 0x38)"
+        );
+
+        // Only memory access faults below the threshold count.
+        assert!(null_deref(&exception_info(write, 0x10000), Some(&context), 0x10000).is_none());
+        assert!(null_deref(&exception_info(write, 0x38), Some(&context), 0).is_none());
+        let illegal = CrashReason::WindowsGeneral(
+            minidump_common::errors::ExceptionCodeWindows::EXCEPTION_ILLEGAL_INSTRUCTION,
+        );
+        assert!(null_deref(&exception_info(illegal, 0x38), Some(&context), 0x10000).is_none());
+    }
+
+    #[test]
+    fn test_address_register() {
+        use minidump::format::CONTEXT_AMD64;
+        use minidump_common::errors::ExceptionCodeWindowsAccessType;
+
+        let read = CrashReason::WindowsAccessViolation(ExceptionCodeWindowsAccessType::READ);
+        let exception_info = |reason, address| crate::ExceptionInfo {
+            reason,
+            address,
+            instruction_str: None,
+            memory_accesses: None,
+            address_memory_kind: None,
+            poisoned_address: None,
+            null_deref: None,
+            address_register: None,
+            crash_address_details: None,
+            code: 0,
+            flags: 0,
+            parameters: Vec::new(),
+            windows_parameters: None,
+            nested_exceptions: Vec::new(),
+        };
+        let raw = CONTEXT_AMD64 {
+            rip: 0x7ff612340000,
+            rax: 0x4141414141414141,
+            rbx: 0x7ff612345678,
+            rcx: 0x7ff612345600,
+            ..CONTEXT_AMD64::default()
+        };
+        let context = MinidumpContext::from_raw(MinidumpRawContext::Amd64(raw));
+
+        // Wild pointers get the closest register below them...
+        let info = exception_info(read, 0x7ff612345678 + 0x38);
+        let register = address_register(&info, Some(&context)).unwrap();
+        assert_eq!(register.name, "rbx");
+        assert_eq!(
+            register.description(info.address),
+            "rbx+0x38 where rbx == 0x7ff612345678"
+        );
+        assert_eq!(
+            address_register(&exception_info(read, 0x4141414141414141), Some(&context))
+                .unwrap()
+                .name,
+            "rax"
+        );
+
+        // ...if there's one close enough.
+        let info = exception_info(read, 0x7ff612345678 + 0x2000);
+        assert_eq!(address_register(&info, Some(&context)), None);
+        // The instruction pointer is the address of every execute fault.
+        let info = exception_info(read, 0x7ff612340000);
+        assert_eq!(address_register(&info, Some(&context)), None);
+        // It has to be a memory access fault.
+        let illegal = CrashReason::WindowsGeneral(
+            minidump_common::errors::ExceptionCodeWindows::EXCEPTION_ILLEGAL_INSTRUCTION,
+        );
+        let info = exception_info(illegal, 0x7ff612345678);
+        assert_eq!(address_register(&info, Some(&context)), None);
+
+        // Null pointer dereferences use the null pointer's register.
+        let mut info = exception_info(read, 0x38);
+        info.null_deref = null_deref(&info, Some(&context), 0x10000);
+        let register = address_register(&info, Some(&context)).unwrap();
+        assert_eq!(register.value, 0);
+        assert_eq!(Some(register), info.null_deref.unwrap().register);
+    }
+
+    #[test]
+    fn test_is_noreturn_function() {
+        let functions = ProcessorOptions::default_noreturn_functions();
+        assert!(is_noreturn_function("abort", &functions));
+        assert!(is_noreturn_function("__assert_fail", &functions));
+        assert!(is_noreturn_function("std::terminate()", &functions));
+        assert!(is_noreturn_function(
+            "core::panicking::panic_fmt(core::fmt::Arguments)",
+            &functions
+        ));
+        assert!(!is_noreturn_function("abortable_task", &functions));
+        assert!(!is_noreturn_function("my_abort", &functions));
+
+        let mut functions = functions;
+        functions.push("my_abort".to_string());
+        assert!(is_noreturn_function("my_abort", &functions));
+    }
+}
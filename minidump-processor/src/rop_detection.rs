@@ -0,0 +1,210 @@
+//! Detecting stacks that look like return-oriented-programming (ROP) chains.
+//!
+//! A ROP exploit fills the stack with the addresses of "gadgets": short
+//! instruction sequences ending in a return, usually found in the middle of
+//! legitimate functions. Once the chain is running, each gadget returns into
+//! the next, so a stack walk of the crashing thread sees a long run of
+//! frames that:
+//!
+//! * were only found by stack scanning (no CFI or frame pointer explains them),
+//! * are packed tightly together, as gadgets pop at most a few values each, and
+//! * don't return to the instruction after a call, which every legitimate
+//!   return address does.
+//!
+//! The last check needs the code around the return address to be in the
+//! minidump, which it often isn't, and is only implemented for x86, x86-64
+//! and ARM64. When the code is missing, the first two checks have to do.
+//!
+//! This is a heuristic for exploit analysts, not a verdict: false positives
+//! are expected for stacks that scanning picked garbage out of.
+
+use minidump::{MinidumpContext, MinidumpRawContext, UnifiedMemoryList};
+
+use crate::process_state::CallStack;
+use crate::stackwalker::follows_call;
+use crate::FrameTrust;
+
+/// The most stack space (in pointer-sized words) a gadget frame can take up.
+const MAX_GADGET_WORDS: u64 = 4;
+/// The fewest gadget frames a stack needs to be flagged.
+const MIN_GADGET_FRAMES: usize = 3;
+/// The fraction of frames that must look like gadgets for a stack to be flagged.
+const MIN_GADGET_SCORE: f64 = 0.5;
+
+/// How much the crashing thread's stack looks like a ROP chain.
+///
+/// See [`ProcessorOptions::detect_rop_chains`][crate::ProcessorOptions::detect_rop_chains].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RopChainAnalysis {
+    /// The indices of every frame that looks like a gadget.
+    pub gadget_frames: Vec<usize>,
+    /// How many frames were examined (every frame but the context frame).
+    pub frames_examined: usize,
+    /// The fraction of examined frames that look like gadgets, from 0 to 1.
+    pub score: f64,
+    /// Whether there are enough gadget frames for the stack to likely be a ROP chain.
+    pub likely: bool,
+}
+
+impl RopChainAnalysis {
+    /// Score `stack` for how much it looks like a ROP chain.
+    ///
+    /// `memory_list` is used to check whether return addresses follow call
+    /// instructions, if the dump contains the code they point into.
+    pub fn from_stack(stack: &CallStack, memory_list: &UnifiedMemoryList) -> RopChainAnalysis {
+        let mut gadget_frames = Vec::new();
+        for (idx, pair) in stack.frames.windows(2).enumerate() {
+            let (callee, frame) = (&pair[0], &pair[1]);
+            let scanned = matches!(frame.trust, FrameTrust::Scan | FrameTrust::CfiScan);
+            if !scanned {
+                continue;
+            }
+
+            let word_size = pointer_size(&frame.context);
+            let spacing = frame
+                .context
+                .get_stack_pointer()
+                .wrapping_sub(callee.context.get_stack_pointer());
+            if spacing > MAX_GADGET_WORDS * word_size {
+                continue;
+            }
+
+            if follows_call(&frame.context, frame.resume_address, memory_list) != Some(true) {
+                gadget_frames.push(idx + 1);
+            }
+        }
+
+        let frames_examined = stack.frames.len().saturating_sub(1);
+        let score = if frames_examined == 0 {
+            0.0
+        } else {
+            gadget_frames.len() as f64 / frames_examined as f64
+        };
+        RopChainAnalysis {
+            likely: gadget_frames.len() >= MIN_GADGET_FRAMES && score >= MIN_GADGET_SCORE,
+            gadget_frames,
+            frames_examined,
+            score,
+        }
+    }
+}
+
+fn pointer_size(context: &MinidumpContext) -> u64 {
+    match context.raw {
+        MinidumpRawContext::Amd64(_)
+        | MinidumpRawContext::Arm64(_)
+        | MinidumpRawContext::OldArm64(_)
+        | MinidumpRawContext::Ppc64(_) => 8,
+        _ => 4,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CallStackInfo, StackFrame};
+    use minidump::format::{CONTEXT_AMD64, MINIDUMP_MEMORY_DESCRIPTOR};
+    use minidump::{MinidumpMemory, MinidumpMemoryList};
+
+    const CODE_BASE: u64 = 0x1000;
+
+    /// A stack with a context frame at `sp` of 0x8000, then a frame for each
+    /// of `frames`: (trust, stack pointer, return address).
+    fn stack(frames: &[(FrameTrust, u64, u64)]) -> CallStack {
+        let frame = |trust, sp, ip| {
+            let raw = CONTEXT_AMD64 {
+                rsp: sp,
+                rip: ip,
+                ..CONTEXT_AMD64::default()
+            };
+            let mut frame = StackFrame::from_context(
+                MinidumpContext::from_raw(MinidumpRawContext::Amd64(raw)),
+                trust,
+            );
+            frame.resume_address = ip;
+            frame
+        };
+        let mut stack = CallStack::with_info(1, CallStackInfo::Ok);
+        stack
+            .frames
+            .push(frame(FrameTrust::Context, 0x8000, 0x1000));
+        for &(trust, sp, ip) in frames {
+            stack.frames.push(frame(trust, sp, ip));
+        }
+        stack
+    }
+
+    fn code_memory(bytes: &[u8]) -> UnifiedMemoryList<'_> {
+        UnifiedMemoryList::Memory(MinidumpMemoryList::from_regions(vec![MinidumpMemory {
+            desc: MINIDUMP_MEMORY_DESCRIPTOR::default(),
+            base_address: CODE_BASE,
+            size: bytes.len() as u64,
+            bytes,
+            endian: scroll::LE,
+        }]))
+    }
+
+    #[test]
+    fn test_rop_chain() {
+        let memory = UnifiedMemoryList::default();
+        let gadgets = stack(&[
+            (FrameTrust::Scan, 0x8008, 0x1010),
+            (FrameTrust::Scan, 0x8010, 0x1020),
+            (FrameTrust::Scan, 0x8020, 0x1030),
+            (FrameTrust::CfiScan, 0x8028, 0x1040),
+        ]);
+        let analysis = RopChainAnalysis::from_stack(&gadgets, &memory);
+        assert_eq!(analysis.gadget_frames, vec![1, 2, 3, 4]);
+        assert_eq!(analysis.frames_examined, 4);
+        assert_eq!(analysis.score, 1.0);
+        assert!(analysis.likely);
+
+        // Frames found by CFI, or spread out, aren't gadgets.
+        let normal = stack(&[
+            (FrameTrust::CallFrameInfo, 0x8008, 0x1010),
+            (FrameTrust::Scan, 0x8100, 0x1020),
+            (FrameTrust::Scan, 0x8108, 0x1030),
+            (FrameTrust::FramePointer, 0x8110, 0x1040),
+        ]);
+        let analysis = RopChainAnalysis::from_stack(&normal, &memory);
+        assert_eq!(analysis.gadget_frames, vec![3]);
+        assert_eq!(analysis.score, 0.25);
+        assert!(!analysis.likely);
+    }
+
+    #[test]
+    fn test_rop_chain_call_sites() {
+        // E8 rel32 at 0x100b..0x1010, a `call rax` (FF D0) at 0x101e..0x1020,
+        // a `call [rax]` (FF 10) at 0x102c..0x102e, a `call far [rax]`
+        // (FF 18) at 0x103e..0x1040, and nops everywhere else.
+        let mut code = vec![0x90u8; 0x40];
+        code[0x0b] = 0xE8;
+        code[0x1e] = 0xFF;
+        code[0x1f] = 0xD0;
+        code[0x2c] = 0xFF;
+        code[0x2d] = 0x10;
+        code[0x3e] = 0xFF;
+        code[0x3f] = 0x18;
+        let memory = code_memory(&code);
+        let context = &stack(&[]).frames[0].context;
+
+        assert_eq!(follows_call(context, 0x1010, &memory), Some(true));
+        assert_eq!(follows_call(context, 0x1020, &memory), Some(true));
+        assert_eq!(follows_call(context, 0x102e, &memory), Some(true));
+        assert_eq!(follows_call(context, 0x1040, &memory), Some(true));
+        // The `call [rax]` is two bytes long, so it doesn't end here.
+        assert_eq!(follows_call(context, 0x1030, &memory), Some(false));
+        assert_eq!(follows_call(context, 0x2000, &memory), None);
+
+        // Frames that return after calls aren't gadgets, even if scanned.
+        let chain = stack(&[
+            (FrameTrust::Scan, 0x8008, 0x1010),
+            (FrameTrust::Scan, 0x8010, 0x1020),
+            (FrameTrust::Scan, 0x8018, 0x1030),
+            (FrameTrust::Scan, 0x8020, 0x1038),
+        ]);
+        let analysis = RopChainAnalysis::from_stack(&chain, &memory);
+        assert_eq!(analysis.gadget_frames, vec![3, 4]);
+        assert!(!analysis.likely);
+    }
+}
@@ -0,0 +1,350 @@
+//! Finding AddressSanitizer reports in the captured memory.
+//!
+//! When AddressSanitizer (ASan) catches a memory error it prints a report
+//! and aborts the process, so all the minidump says about the crash is that
+//! something called `abort`. The report is usually still sitting in the
+//! process's memory though (ASan keeps the one it's writing in a buffer),
+//! and it says what actually went wrong: the kind of error, the bad access,
+//! and where the memory was allocated and freed.
+//!
+//! A full report starts with a header like
+//! `==1234==ERROR: AddressSanitizer: heap-use-after-free on address ...`
+//! and ends with a `SUMMARY: AddressSanitizer: ...` line. If the header
+//! isn't in the minidump, the summary line is looked for on its own, as it
+//! also shows up in abort messages.
+
+use minidump::UnifiedMemoryList;
+
+/// What comes after the pid in a report's header.
+const REPORT_MARKER: &[u8] = b"==ERROR: AddressSanitizer:";
+/// The start of the one line summary at the end of a report.
+const SUMMARY_MARKER: &[u8] = b"SUMMARY: AddressSanitizer:";
+/// The most digits a pid in a report's header can have.
+const MAX_PID_DIGITS: u64 = 10;
+/// The longest summary line to read.
+const MAX_SUMMARY_SIZE: usize = 1024;
+
+/// The most report text to read, in bytes.
+pub const MAX_SANITIZER_REPORT_SIZE: usize = 64 * 1024;
+
+/// How much of a report was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SanitizerReportKind {
+    /// The report from its header on.
+    Full,
+    /// Only the report's `SUMMARY:` line.
+    Summary,
+}
+
+impl SanitizerReportKind {
+    /// A short name for this kind, as used in the JSON output.
+    pub fn name(self) -> &'static str {
+        match self {
+            SanitizerReportKind::Full => "full",
+            SanitizerReportKind::Summary => "summary",
+        }
+    }
+}
+
+/// An AddressSanitizer report found in the captured memory.
+///
+/// See [`ProcessorOptions::extract_sanitizer_report`][crate::ProcessorOptions::extract_sanitizer_report].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizerReport {
+    /// Whether this is the whole report or just its summary line.
+    pub kind: SanitizerReportKind,
+    /// The address the text starts at.
+    pub address: u64,
+    /// The text of the report, without any trailing newline.
+    pub text: String,
+    /// Whether the text was cut short, because it ran past the end of the
+    /// captured memory or past [`MAX_SANITIZER_REPORT_SIZE`].
+    ///
+    /// Truncated text is cut after its last complete line.
+    pub truncated: bool,
+}
+
+impl SanitizerReport {
+    /// Look for a report in `memory_list`.
+    ///
+    /// The first report with a header (by address) is preferred, then the
+    /// first summary line.
+    pub fn from_memory(memory_list: &UnifiedMemoryList) -> Option<SanitizerReport> {
+        find_marker(memory_list, REPORT_MARKER)
+            .into_iter()
+            .find_map(|marker| {
+                let start = header_start(memory_list, marker)?;
+                read_report(memory_list, start, SanitizerReportKind::Full)
+            })
+            .or_else(|| {
+                find_marker(memory_list, SUMMARY_MARKER)
+                    .into_iter()
+                    .find_map(|start| read_report(memory_list, start, SanitizerReportKind::Summary))
+            })
+    }
+}
+
+/// The addresses at which `pattern` appears in `memory_list`, in order by
+/// address.
+///
+/// Unlike [`UnifiedMemoryList::find_bytes`], this also finds the matches that
+/// straddle two adjacent regions, as a report ASan wrote in one go can still
+/// be split across them.
+fn find_marker(memory_list: &UnifiedMemoryList, pattern: &[u8]) -> Vec<u64> {
+    let mut found: Vec<u64> = memory_list.find_bytes(pattern).collect();
+    let overlap = pattern.len().saturating_sub(1);
+    let regions: Vec<_> = memory_list.by_addr().collect();
+    for pair in regions.windows(2) {
+        let (before, after) = (&pair[0], &pair[1]);
+        let edge = after.base_address();
+        if overlap == 0 || before.base_address().checked_add(before.size()) != Some(edge) {
+            continue;
+        }
+        // Any match in the last `overlap` bytes of one region and the first
+        // `overlap` bytes of the next has to cross the edge between them.
+        let tail = &before.bytes()[before.bytes().len().saturating_sub(overlap)..];
+        let head = &after.bytes()[..after.bytes().len().min(overlap)];
+        let window = [tail, head].concat();
+        let window_start = edge - tail.len() as u64;
+        found.extend(
+            window
+                .windows(pattern.len())
+                .enumerate()
+                .filter(|(_, candidate)| *candidate == pattern)
+                .map(|(offset, _)| window_start + offset as u64),
+        );
+    }
+    found.sort_unstable();
+    found.dedup();
+    found
+}
+
+/// Where the header a `REPORT_MARKER` at `marker` belongs to starts, if it's
+/// preceded by `==<pid>`.
+///
+/// This skips the format string ASan prints headers with (`==%d==ERROR:`),
+/// which is in the dump whenever ASan's data is.
+fn header_start(memory_list: &UnifiedMemoryList, marker: u64) -> Option<u64> {
+    let byte_at = |address: u64| {
+        memory_list
+            .memory_at_address(address)?
+            .get_memory_at_address::<u8>(address)
+    };
+    let mut digits = 0;
+    while digits < MAX_PID_DIGITS
+        && matches!(
+            marker.checked_sub(digits + 1).and_then(byte_at),
+            Some(byte) if byte.is_ascii_digit()
+        )
+    {
+        digits += 1;
+    }
+    if digits == 0 {
+        return None;
+    }
+    let start = marker.checked_sub(digits + 2)?;
+    if byte_at(start) == Some(b'=') && byte_at(start + 1) == Some(b'=') {
+        Some(start)
+    } else {
+        None
+    }
+}
+
+/// Read the report text of the given kind at `start`.
+fn read_report(
+    memory_list: &UnifiedMemoryList,
+    start: u64,
+    kind: SanitizerReportKind,
+) -> Option<SanitizerReport> {
+    let max_len = match kind {
+        SanitizerReportKind::Full => MAX_SANITIZER_REPORT_SIZE,
+        SanitizerReportKind::Summary => MAX_SUMMARY_SIZE,
+    };
+    let bytes = read_cstring_bytes(memory_list, start, max_len)?;
+    // The read stops at a NUL, the cap, or the end of the captured memory,
+    // and only the first means the text is all there.
+    let mut terminated = bytes.last() == Some(&0);
+    let len = bytes.len() - usize::from(terminated);
+    let mut text = String::from_utf8_lossy(&bytes[..len]).into_owned();
+
+    if kind == SanitizerReportKind::Summary {
+        if let Some(end) = text.find('\n') {
+            text.truncate(end);
+            terminated = true;
+        }
+    } else if !terminated {
+        // Don't end on half a line (or half a character).
+        if let Some(end) = text.rfind('\n') {
+            text.truncate(end);
+        }
+    }
+    let len = text.trim_end().len();
+    text.truncate(len);
+
+    Some(SanitizerReport {
+        kind,
+        address: start,
+        text,
+        truncated: !terminated,
+    })
+}
+
+/// Read up to `max_len` bytes at `start`, through as many adjacent regions as
+/// it takes, stopping after the first NUL.
+///
+/// This reads the regions one at a time rather than merging them, so it
+/// works the same for both kinds of memory list. Returns `None` if `start`
+/// isn't in the captured memory.
+fn read_cstring_bytes(
+    memory_list: &UnifiedMemoryList,
+    start: u64,
+    max_len: usize,
+) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut address = start;
+    while bytes.len() < max_len {
+        let chunk = match memory_list.memory_at_address(address) {
+            Some(region) => {
+                let offset = (address - region.base_address()) as usize;
+                let rest = region.bytes().get(offset..).unwrap_or_default();
+                &rest[..rest.len().min(max_len - bytes.len())]
+            }
+            None => break,
+        };
+        if chunk.is_empty() {
+            break;
+        }
+        if let Some(nul) = chunk.iter().position(|&b| b == 0) {
+            bytes.extend_from_slice(&chunk[..=nul]);
+            break;
+        }
+        bytes.extend_from_slice(chunk);
+        address += chunk.len() as u64;
+    }
+    if bytes.is_empty() {
+        None
+    } else {
+        Some(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use minidump::format::MINIDUMP_MEMORY_DESCRIPTOR;
+    use minidump::{MinidumpMemory, MinidumpMemoryList};
+
+    const REPORT: &str =
+        "==4242==ERROR: AddressSanitizer: heap-use-after-free on address 0x602000000010\n\
+         READ of size 4 at 0x602000000010 thread T0\n    \
+         #0 0x55d0 in main test.c:5\n\
+         SUMMARY: AddressSanitizer: heap-use-after-free test.c:5 in main\n";
+
+    /// A memory list with a region of each of `regions`: (base, contents).
+    fn memory<'a>(regions: &[(u64, &'a [u8])]) -> UnifiedMemoryList<'a> {
+        let regions = regions
+            .iter()
+            .map(|&(base_address, bytes)| MinidumpMemory {
+                desc: MINIDUMP_MEMORY_DESCRIPTOR::default(),
+                base_address,
+                size: bytes.len() as u64,
+                bytes,
+                endian: scroll::LE,
+            })
+            .collect();
+        UnifiedMemoryList::Memory(MinidumpMemoryList::from_regions(regions))
+    }
+
+    #[test]
+    fn test_full_report() {
+        let bytes = [
+            b"junk==%d==ERROR: AddressSanitizer: \0",
+            REPORT.as_bytes(),
+            b"\0",
+        ]
+        .concat();
+        let report = SanitizerReport::from_memory(&memory(&[(0x1000, &bytes)])).unwrap();
+        assert_eq!(report.kind, SanitizerReportKind::Full);
+        assert_eq!(report.address, 0x1000 + 36);
+        assert_eq!(report.text, REPORT.trim_end());
+        assert!(!report.truncated);
+    }
+
+    #[test]
+    fn test_report_across_regions() {
+        let (first, second) = REPORT.as_bytes().split_at(100);
+        let second = [second, b"\0"].concat();
+
+        // Regions that touch are read as one.
+        let memory_list = memory(&[(0x1000, first), (0x1000 + 100, &second)]);
+        let report = SanitizerReport::from_memory(&memory_list).unwrap();
+        assert_eq!(report.text, REPORT.trim_end());
+        assert!(!report.truncated);
+
+        // A gap cuts the report after the last complete line before it.
+        let memory_list = memory(&[(0x1000, first), (0x2000, &second)]);
+        let report = SanitizerReport::from_memory(&memory_list).unwrap();
+        assert_eq!(report.kind, SanitizerReportKind::Full);
+        assert_eq!(report.address, 0x1000);
+        assert_eq!(report.text, REPORT.lines().next().unwrap());
+        assert!(report.truncated);
+    }
+
+    #[test]
+    fn test_marker_across_regions() {
+        // Split in the middle of "AddressSanitizer".
+        let (first, second) = REPORT.as_bytes().split_at(20);
+        let second = [second, b"\0"].concat();
+        let memory_list = memory(&[(0x1000, first), (0x1000 + 20, &second)]);
+        let report = SanitizerReport::from_memory(&memory_list).unwrap();
+        assert_eq!(report.kind, SanitizerReportKind::Full);
+        assert_eq!(report.address, 0x1000);
+        assert_eq!(report.text, REPORT.trim_end());
+        assert!(!report.truncated);
+
+        // Split in the middle of the summary's marker, with no header.
+        let bytes = b"abort: SUMMARY: AddressSanitizer: heap-use-after-free\0";
+        let (first, second) = bytes.split_at(12);
+        let memory_list = memory(&[(0x1000, first), (0x1000 + 12, second)]);
+        let report = SanitizerReport::from_memory(&memory_list).unwrap();
+        assert_eq!(report.kind, SanitizerReportKind::Summary);
+        assert_eq!(report.address, 0x1007);
+        assert_eq!(
+            report.text,
+            "SUMMARY: AddressSanitizer: heap-use-after-free"
+        );
+        assert!(!report.truncated);
+    }
+
+    #[test]
+    fn test_summary_only() {
+        let bytes =
+            b"abort: SUMMARY: AddressSanitizer: heap-use-after-free test.c:5 in main\nmore\0";
+        let report = SanitizerReport::from_memory(&memory(&[(0x1000, bytes)])).unwrap();
+        assert_eq!(report.kind, SanitizerReportKind::Summary);
+        assert_eq!(report.address, 0x1007);
+        assert_eq!(
+            report.text,
+            "SUMMARY: AddressSanitizer: heap-use-after-free test.c:5 in main"
+        );
+        assert!(!report.truncated);
+
+        let bytes = b"SUMMARY: AddressSanitizer: heap-use-after";
+        let report = SanitizerReport::from_memory(&memory(&[(0x1000, bytes)])).unwrap();
+        assert_eq!(report.text, "SUMMARY: AddressSanitizer: heap-use-after");
+        assert!(report.truncated);
+    }
+
+    #[test]
+    fn test_no_report() {
+        let bytes = b"==%d==ERROR: AddressSanitizer: %s\0";
+        assert_eq!(
+            SanitizerReport::from_memory(&memory(&[(0x1000, bytes)])),
+            None
+        );
+        assert_eq!(
+            SanitizerReport::from_memory(&UnifiedMemoryList::default()),
+            None
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Recognizing crashes caused by running out of stack.
+//!
+//! A thread that overflows its stack crashes as soon as it touches the page
+//! below it: on Windows that's a guard page (and the exception is usually
+//! `EXCEPTION_STACK_OVERFLOW`), elsewhere it's an unmapped gap or a guard
+//! mapping. Either way the crash address is just below the crashing thread's
+//! stack, and just below its stack pointer, which ordinary memory accesses
+//! never touch.
+//!
+//! Runaway recursion is the usual culprit, so a stack with the same return
+//! address repeated many times backs up the diagnosis, but isn't enough on
+//! its own: plenty of recursive code crashes for other reasons. Deep
+//! recursion is still reported when it's the only sign (it's worth knowing
+//! about, and the guard page may just not be in the dump), but the crash
+//! isn't called a stack overflow then.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use minidump::{CrashReason, MemoryKind, Module};
+use minidump_common::errors::ExceptionCodeWindows;
+use minidump_common::utils::basename;
+
+use crate::process_state::ProcessState;
+
+/// How far below the stack (or stack pointer) a crash address can be and
+/// still count as running off the end of it.
+const OVERFLOW_WINDOW: u64 = 0x10000;
+/// How many times a frame must repeat for the stack count as deeply recursive.
+const MIN_RECURSION_FRAMES: usize = 16;
+
+/// One reason to believe a crash was a stack overflow.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StackOverflowEvidence {
+    /// The exception itself says so (Windows' `EXCEPTION_STACK_OVERFLOW`).
+    Exception,
+    /// The crash address is in a guard page at the end of the crashing thread's stack.
+    GuardPage,
+    /// The crash address is just below the crashing thread's stack.
+    BelowStack,
+    /// The crash address is just below the crashing thread's stack pointer.
+    BelowStackPointer,
+}
+
+impl StackOverflowEvidence {
+    /// The name of this evidence in the JSON output, like `"guard_page"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StackOverflowEvidence::Exception => "exception",
+            StackOverflowEvidence::GuardPage => "guard_page",
+            StackOverflowEvidence::BelowStack => "below_stack",
+            StackOverflowEvidence::BelowStackPointer => "below_stack_pointer",
+        }
+    }
+
+    /// A description of this evidence for the human output.
+    pub fn description(&self) -> &'static str {
+        match self {
+            StackOverflowEvidence::Exception => "the exception is a stack overflow",
+            StackOverflowEvidence::GuardPage => "crash address is in the stack's guard page",
+            StackOverflowEvidence::BelowStack => "crash address is just below the stack",
+            StackOverflowEvidence::BelowStackPointer => {
+                "crash address is just below the stack pointer"
+            }
+        }
+    }
+}
+
+/// A frame that repeats many times on the crashing thread's stack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recursion {
+    /// The name of the module the frame is in.
+    pub module: String,
+    /// The offset of the frame's instruction in the module.
+    pub offset: u64,
+    /// The frame's function, if it was symbolicated.
+    pub function_name: Option<String>,
+    /// How many frames have this module and offset.
+    pub frames: usize,
+}
+
+/// Why the crash looks like a stack overflow, or at least like it was
+/// heading for one.
+///
+/// See [`ProcessState::stack_overflow`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StackOverflowAnalysis {
+    /// Every reason the crash looks like a stack overflow. This is only
+    /// empty if the crashing thread is deeply recursive, but nothing else
+    /// points to a stack overflow.
+    pub evidence: Vec<StackOverflowEvidence>,
+    /// The most repeated frame of the crashing thread, if it repeats enough
+    /// for the stack to look deeply recursive.
+    pub recursion: Option<Recursion>,
+}
+
+impl StackOverflowAnalysis {
+    /// Check whether the crash in `state` was a stack overflow, or the
+    /// crashing thread is deeply recursive.
+    ///
+    /// `stack_range` is the address range of the crashing thread's stack, if
+    /// known.
+    ///
+    /// Returns `None` if neither is the case, or there's no exception or
+    /// crashing thread to check.
+    pub fn from_state(
+        state: &ProcessState,
+        stack_range: Option<Range<u64>>,
+    ) -> Option<StackOverflowAnalysis> {
+        let exception = state.exception_info.as_ref()?;
+        let stack = state.crashing_thread()?;
+        let address = exception.address;
+        let just_below = |bound: u64| address < bound && bound - address <= OVERFLOW_WINDOW;
+
+        let mut evidence = Vec::new();
+        if exception.reason
+            == CrashReason::WindowsGeneral(ExceptionCodeWindows::EXCEPTION_STACK_OVERFLOW)
+        {
+            evidence.push(StackOverflowEvidence::Exception);
+        }
+        if let Some(range) = &stack_range {
+            let in_or_below = range.contains(&address) || just_below(range.start);
+            if exception.address_memory_kind == Some(MemoryKind::Guard) && in_or_below {
+                evidence.push(StackOverflowEvidence::GuardPage);
+            }
+            if just_below(range.start) {
+                evidence.push(StackOverflowEvidence::BelowStack);
+            }
+        }
+        if let Some(frame) = stack.frames.first() {
+            if just_below(frame.context.get_stack_pointer()) {
+                evidence.push(StackOverflowEvidence::BelowStackPointer);
+            }
+        }
+        // Frames the walk stopped repeating only have one copy left, but they
+        // count for every time they were walked.
+        let repeats = |idx: usize| match stack.repeated_frames {
+            Some(repeated)
+                if (repeated.first_frame..repeated.first_frame + repeated.frame_count)
+                    .contains(&idx) =>
+            {
+                repeated.repeated
+            }
+            _ => 1,
+        };
+        let mut counts = HashMap::new();
+        for (idx, frame) in stack.frames.iter().enumerate() {
+            if let Some(module) = &frame.module {
+                let key = (
+                    module.base_address(),
+                    frame.instruction - module.base_address(),
+                );
+                counts.entry(key).or_insert((0, idx)).0 += repeats(idx);
+            }
+        }
+        let recursion = counts
+            .into_iter()
+            .filter(|&(_, (count, _))| count >= MIN_RECURSION_FRAMES)
+            // Ties go to the frame nearest the top of the stack.
+            .max_by_key(|&(_, (count, idx))| (count, std::cmp::Reverse(idx)))
+            .map(|((_, offset), (frames, idx))| {
+                let frame = &stack.frames[idx];
+                Recursion {
+                    module: frame
+                        .module
+                        .as_ref()
+                        .map(|module| basename(&module.code_file()).to_string())
+                        .unwrap_or_default(),
+                    offset,
+                    function_name: frame.function_name.clone(),
+                    frames,
+                }
+            });
+
+        if evidence.is_empty() && recursion.is_none() {
+            return None;
+        }
+        Some(StackOverflowAnalysis {
+            evidence,
+            recursion,
+        })
+    }
+
+    /// Whether the crash itself looks like a stack overflow, rather than the
+    /// stack only being deeply recursive.
+    pub fn is_stack_overflow(&self) -> bool {
+        !self.evidence.is_empty()
+    }
+}
@@ -6,15 +6,18 @@
 // so that it can be copied between the two with minimal changes. It's not
 // worth the effort to *actually* unify the implementations.
 
-use crate::process_state::{FrameTrust, StackFrame};
+use crate::process_state::{FrameTrust, StackFrame, WalkEndReason};
 use crate::stackwalker::unwind::Unwind;
-use crate::stackwalker::CfiStackWalker;
-use crate::{SymbolProvider, SystemInfo};
+use crate::stackwalker::{
+    cross_validate_cfi, frame_pointer_seems_coherent, no_caller_reason,
+    x86_return_address_seems_valid, CfiStackWalker, DumpMemory,
+};
+use crate::{ProcessorOptions, SymbolProvider, SystemInfo};
 use minidump::format::CONTEXT_AMD64;
 use minidump::system_info::Os;
 use minidump::{
-    MinidumpContext, MinidumpContextValidity, MinidumpMemory, MinidumpModuleList,
-    MinidumpRawContext,
+    CpuContext, MinidumpContext, MinidumpContextValidity, MinidumpModuleList, MinidumpRawContext,
+    UnifiedMemory,
 };
 use std::collections::HashSet;
 use tracing::trace;
@@ -31,8 +34,9 @@ async fn get_caller_by_cfi<P>(
     ctx: &CONTEXT_AMD64,
     callee: &StackFrame,
     grand_callee: Option<&StackFrame>,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> Option<StackFrame>
 where
@@ -72,6 +76,13 @@ where
     symbol_provider
         .walk_frame(module, &mut stack_walker)
         .await?;
+    let trust = if options.check_cfi_plausibility
+        && stack_walker.caller_seems_implausible(Some(FRAME_POINTER_REGISTER), CALLEE_SAVED_REGS)
+    {
+        FrameTrust::CallFrameInfoLowConfidence
+    } else {
+        FrameTrust::CallFrameInfo
+    };
     let caller_ip = stack_walker.caller_ctx.rip;
     let caller_sp = stack_walker.caller_ctx.rsp;
 
@@ -92,7 +103,7 @@ where
         raw: MinidumpRawContext::Amd64(stack_walker.caller_ctx),
         valid: MinidumpContextValidity::Some(stack_walker.caller_validity),
     };
-    Some(StackFrame::from_context(context, FrameTrust::CallFrameInfo))
+    Some(StackFrame::from_context(context, trust))
 }
 
 fn callee_forwarded_regs(valid: &MinidumpContextValidity) -> HashSet<&'static str> {
@@ -109,7 +120,7 @@ fn callee_forwarded_regs(valid: &MinidumpContextValidity) -> HashSet<&'static st
 fn get_caller_by_frame_pointer<P>(
     ctx: &CONTEXT_AMD64,
     callee: &StackFrame,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
     _modules: &MinidumpModuleList,
     system_info: &SystemInfo,
     _symbol_provider: &P,
@@ -223,8 +234,10 @@ where
 async fn get_caller_by_scan<P>(
     ctx: &CONTEXT_AMD64,
     callee: &StackFrame,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
+    dump_memory: &DumpMemory<'_, '_>,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> Option<StackFrame>
 where
@@ -268,7 +281,11 @@ where
     for i in 0..scan_range {
         let address_of_ip = last_sp.checked_add(i * POINTER_WIDTH)?;
         let caller_ip = stack_memory.get_memory_at_address(address_of_ip as u64)?;
-        if instruction_seems_valid(caller_ip, modules, symbol_provider).await {
+        if instruction_seems_valid(caller_ip, modules, options, symbol_provider).await
+            && (!options.paranoid_scan
+                || (frame_pointer_seems_coherent(last_bp, address_of_ip, POINTER_WIDTH)
+                    && x86_return_address_seems_valid(&callee.context, caller_ip, dump_memory)))
+        {
             // ip is pushed by CALL, so sp is just address_of_ip + ptr
             let caller_sp = address_of_ip.checked_add(POINTER_WIDTH)?;
 
@@ -380,6 +397,7 @@ where
 async fn instruction_seems_valid<P>(
     instruction: Pointer,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> bool
 where
@@ -389,13 +407,14 @@ where
         return false;
     }
 
-    super::instruction_seems_valid_by_symbols(instruction as u64, modules, symbol_provider).await
+    super::instruction_seems_valid_by_symbols(instruction as u64, modules, options, symbol_provider)
+        .await
 }
 
 fn stack_seems_valid(
     caller_sp: Pointer,
     callee_sp: Pointer,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
 ) -> bool {
     // The stack shouldn't *grow* when we unwind
     if caller_sp <= callee_sp {
@@ -430,45 +449,58 @@ impl Unwind for CONTEXT_AMD64 {
         &self,
         callee: &StackFrame,
         grand_callee: Option<&StackFrame>,
-        stack_memory: Option<&MinidumpMemory<'_>>,
+        stack_memory: Option<&UnifiedMemory<'_, '_>>,
+        dump_memory: &DumpMemory<'_, '_>,
         modules: &MinidumpModuleList,
         system_info: &SystemInfo,
+        options: &ProcessorOptions<'_>,
         syms: &P,
-    ) -> Option<StackFrame>
+    ) -> Result<StackFrame, WalkEndReason>
     where
         P: SymbolProvider + Sync,
     {
-        let stack = stack_memory.as_ref()?;
+        let stack = stack_memory
+            .as_ref()
+            .ok_or(WalkEndReason::MissingStackMemory)?;
 
         // .await doesn't like closures, so don't use Option chaining
         let mut frame = None;
         if frame.is_none() {
-            frame = get_caller_by_cfi(self, callee, grand_callee, stack, modules, syms).await;
+            frame =
+                get_caller_by_cfi(self, callee, grand_callee, stack, modules, options, syms).await;
+        }
+        if options.cross_validate_cfi {
+            if let Some(cfi_frame) = frame.as_mut() {
+                let fp_frame =
+                    get_caller_by_frame_pointer(self, callee, stack, modules, system_info, syms);
+                cross_validate_cfi(cfi_frame, fp_frame.as_ref());
+            }
         }
         if frame.is_none() {
             frame = get_caller_by_frame_pointer(self, callee, stack, modules, system_info, syms);
         }
         if frame.is_none() {
-            frame = get_caller_by_scan(self, callee, stack, modules, syms).await;
+            frame =
+                get_caller_by_scan(self, callee, stack, dump_memory, modules, options, syms).await;
         }
-        let mut frame = frame?;
+        let mut frame = frame.ok_or_else(|| no_caller_reason(callee, stack))?;
 
         // We now check the frame to see if it looks like unwinding is complete,
         // based on the frame we computed having a nonsense value. Returning
-        // None signals to the unwinder to stop unwinding.
+        // an error signals to the unwinder to stop unwinding.
 
         // if the instruction is within the first ~page of memory, it's basically
         // null, and we can assume unwinding is complete.
         if frame.context.get_instruction_pointer() < 4096 {
             trace!("instruction pointer was nullish, assuming unwind complete");
-            return None;
+            return Err(WalkEndReason::NullReturnAddress);
         }
         // If the new stack pointer is at a lower address than the old,
         // then that's clearly incorrect. Treat this as end-of-stack to
         // enforce progress and avoid infinite loops.
         if frame.context.get_stack_pointer() <= self.rsp {
             trace!("stack pointer went backwards, assuming unwind complete");
-            return None;
+            return Err(WalkEndReason::StackPointerWentBackwards);
         }
 
         // Ok, the frame now seems well and truly valid, do final cleanup.
@@ -481,6 +513,29 @@ impl Unwind for CONTEXT_AMD64 {
         let ip = frame.context.get_instruction_pointer() as u64;
         frame.instruction = ip - 1;
 
-        Some(frame)
+        Ok(frame)
+    }
+
+    fn validate_registers(
+        &self,
+        valid: &MinidumpContextValidity,
+        _modules: &MinidumpModuleList,
+    ) -> Vec<String> {
+        let mut problems = Vec::new();
+        // Every push/pop/call/ret moves rsp by 8, so it can never be misaligned.
+        if let Some(sp) = self.get_register(STACK_POINTER_REGISTER, valid) {
+            if sp % POINTER_WIDTH != 0 {
+                problems.push(format!("{} is not 8-byte aligned", STACK_POINTER_REGISTER));
+            }
+        }
+        if let Some(ip) = self.get_register(INSTRUCTION_REGISTER, valid) {
+            if is_non_canonical(ip) {
+                problems.push(format!(
+                    "{} is a non-canonical address",
+                    INSTRUCTION_REGISTER
+                ));
+            }
+        }
+        problems
     }
 }
@@ -1,13 +1,21 @@
 // Copyright 2015 Ted Mielczarek. See the COPYRIGHT
 // file at the top-level directory of this distribution.
 
-use crate::stackwalker::walk_stack;
+use crate::stackwalker::{walk_stack, DumpMemory};
 use crate::{process_state::*, ProcessorOptions};
-use crate::{string_symbol_supplier, Symbolizer, SystemInfo};
+use crate::{
+    string_symbol_supplier, FileError, FileKind, FillSymbolError, FrameSymbolizer, FrameWalker,
+    SymbolProvider, Symbolizer, SystemInfo,
+};
+use async_trait::async_trait;
 use minidump::format::CONTEXT_AMD64;
+use minidump::strings::LinuxOsStr;
 use minidump::system_info::{Cpu, Os};
 use minidump::*;
 use std::collections::HashMap;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Mutex;
 use test_assembler::*;
 
 struct TestFixture {
@@ -15,6 +23,7 @@ struct TestFixture {
     pub modules: MinidumpModuleList,
     pub system_info: SystemInfo,
     pub symbols: HashMap<String, String>,
+    pub stack_range: Option<Range<u64>>,
 }
 
 impl TestFixture {
@@ -32,15 +41,59 @@ impl TestFixture {
                 os_version: None,
                 os_build: None,
                 cpu: Cpu::X86_64,
+                endian: scroll::LE,
                 cpu_info: None,
                 cpu_microcode_version: None,
+                cpu_features: None,
+                cpu_microarchitecture: None,
+                cpu_description: None,
                 cpu_count: 1,
+                available_physical_memory: None,
+                commit_limit: None,
             },
             symbols: HashMap::new(),
+            stack_range: None,
         }
     }
 
     pub async fn walk_stack(&self, stack: Section) -> CallStack {
+        self.walk_stack_with_options(stack, &ProcessorOptions::default())
+            .await
+    }
+
+    pub async fn walk_stack_with_options(
+        &self,
+        stack: Section,
+        options: &ProcessorOptions<'_>,
+    ) -> CallStack {
+        let symbolizer = Symbolizer::new(string_symbol_supplier(self.symbols.clone()));
+        self.walk_stack_with_provider(stack, options, &symbolizer)
+            .await
+    }
+
+    pub async fn walk_stack_with_provider<P>(
+        &self,
+        stack: Section,
+        options: &ProcessorOptions<'_>,
+        symbol_provider: &P,
+    ) -> CallStack
+    where
+        P: SymbolProvider + Sync,
+    {
+        self.walk_stack_with_dump_memory(stack, options, &DumpMemory::default(), symbol_provider)
+            .await
+    }
+
+    pub async fn walk_stack_with_dump_memory<P>(
+        &self,
+        stack: Section,
+        options: &ProcessorOptions<'_>,
+        dump_memory: &DumpMemory<'_, '_>,
+        symbol_provider: &P,
+    ) -> CallStack
+    where
+        P: SymbolProvider + Sync,
+    {
         let context = MinidumpContext {
             raw: MinidumpRawContext::Amd64(self.raw.clone()),
             valid: MinidumpContextValidity::All,
@@ -55,18 +108,18 @@ impl TestFixture {
             bytes: &stack,
             endian: scroll::LE,
         };
-        let symbolizer = Symbolizer::new(string_symbol_supplier(self.symbols.clone()));
-        let options = ProcessorOptions::default();
         let mut stack = CallStack::with_context(context);
+        stack.stack_range = self.stack_range.clone();
 
         walk_stack(
             0,
-            &options,
+            options,
             &mut stack,
-            Some(&stack_memory),
+            Some(&UnifiedMemory::Memory(&stack_memory)),
+            dump_memory,
             &self.modules,
             &self.system_info,
-            &symbolizer,
+            symbol_provider,
         )
         .await;
 
@@ -330,6 +383,137 @@ async fn test_scan_without_symbols() {
     }
 }
 
+#[tokio::test]
+async fn test_max_frames_per_thread() {
+    // A stack full of return addresses scans into a frame for each of them.
+    let mut f = TestFixture::new();
+    let stack_start = 0x8000000080000000u64;
+    let make_stack = || {
+        let mut stack = Section::new();
+        stack.start().set_const(stack_start);
+        for _ in 0..10 {
+            stack = stack.D64(0x00007500b0000100u64);
+        }
+        stack.append_repeated(0, 16)
+    };
+    f.raw.rip = 0x00007400c0000200;
+    f.raw.rsp = stack_start;
+
+    let s = f.walk_stack(make_stack()).await;
+    assert_eq!(s.frames.len(), 11);
+    assert_eq!(s.info, CallStackInfo::Ok);
+
+    let options = ProcessorOptions {
+        max_frames_per_thread: 4,
+        ..ProcessorOptions::default()
+    };
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 4);
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::MaxFramesReached));
+
+    // A walk that ends right at the limit wasn't cut short.
+    let options = ProcessorOptions {
+        max_frames_per_thread: 11,
+        ..ProcessorOptions::default()
+    };
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 11);
+    assert_eq!(s.info, CallStackInfo::Ok);
+
+    // The context frame is always kept.
+    let options = ProcessorOptions {
+        max_frames_per_thread: 0,
+        ..ProcessorOptions::default()
+    };
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 1);
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::MaxFramesReached));
+}
+
+#[tokio::test]
+async fn test_frame_walk_timeout() {
+    let mut f = TestFixture::new();
+    let stack_start = 0x8000000080000000u64;
+    let make_stack = || {
+        let mut stack = Section::new();
+        stack.start().set_const(stack_start);
+        for _ in 0..10 {
+            stack = stack.D64(0x00007500b0000100u64);
+        }
+        stack.append_repeated(0, 16)
+    };
+    f.raw.rip = 0x00007400c0000200;
+    f.raw.rsp = stack_start;
+
+    let options = ProcessorOptions {
+        frame_walk_timeout: Some(std::time::Duration::from_secs(1000)),
+        ..ProcessorOptions::default()
+    };
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 11);
+    assert_eq!(s.info, CallStackInfo::Ok);
+
+    // Out of time right away, but the context frame is complete.
+    let options = ProcessorOptions {
+        frame_walk_timeout: Some(std::time::Duration::ZERO),
+        ..ProcessorOptions::default()
+    };
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 1);
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::WalkTimedOut));
+}
+
+#[tokio::test]
+async fn test_max_consecutive_scan_frames() {
+    let mut f = TestFixture::new();
+    let stack_start = 0x8000000080000000u64;
+    let frame1_rbp = stack_start + 16;
+    let make_stack = || {
+        let mut stack = Section::new();
+        stack.start().set_const(stack_start);
+        stack = stack
+            // frame 0
+            .D64(0)
+            .D64(0)
+            // frame 1, found by the frame pointer
+            .D64(stack_start + 0x48) // saved rbp
+            .D64(0x00007500b0000100u64); // return address
+                                         // frames 2 and up, all found by scanning
+        for _ in 0..5 {
+            stack = stack.D64(0x00007500b0000200u64);
+        }
+        stack.append_repeated(0, 16)
+    };
+    f.raw.rip = 0x00007400c0000200;
+    f.raw.rsp = stack_start;
+    f.raw.rbp = frame1_rbp;
+
+    let s = f.walk_stack(make_stack()).await;
+    assert_eq!(s.frames.len(), 7);
+    assert_eq!(s.frames[1].trust, FrameTrust::FramePointer);
+    assert!(s.frames[2..]
+        .iter()
+        .all(|frame| frame.trust == FrameTrust::Scan));
+    assert_eq!(s.info, CallStackInfo::Ok);
+
+    // Only the scanned frames after the frame pointer one count.
+    let options = ProcessorOptions {
+        max_consecutive_scan_frames: 3,
+        ..ProcessorOptions::default()
+    };
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 5);
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::ScanLimitReached));
+
+    let options = ProcessorOptions {
+        max_consecutive_scan_frames: 0,
+        ..ProcessorOptions::default()
+    };
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 2);
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::ScanLimitReached));
+}
+
 #[tokio::test]
 async fn test_scan_with_symbols() {
     // Test that we can refine our scanning using symbols. Specifically we
@@ -409,6 +593,148 @@ async fn test_scan_with_symbols() {
     }
 }
 
+#[tokio::test]
+async fn test_paranoid_scan() {
+    // A paranoid scan shouldn't give pointers into modules without symbols
+    // the benefit of the doubt, and should reject return addresses that don't
+    // agree with the callee's frame pointer.
+    let mut f = TestFixture::new();
+    let stack_start = 0x8000000080000000u64;
+    let return_address = 0x00007400c0000300u64;
+    let frame1_rsp = stack_start + 32;
+    let frame1_rbp = frame1_rsp + 32;
+    let make_stack = || {
+        let stack = Section::new();
+        stack.start().set_const(stack_start);
+        stack
+            // frame 0
+            .append_repeated(0, 16) // space
+            .D64(0x00007500b0000100u64) // in a module we have no symbols for
+            .D64(return_address) // actual return address
+            // frame 1
+            .append_repeated(0, 32) // end of stack
+    };
+
+    f.raw.rip = 0x00007400c0000200;
+    f.raw.rbp = frame1_rbp;
+    f.raw.rsp = stack_start;
+
+    f.add_symbols(
+        String::from("module1"),
+        String::from("FUNC 100 400 10 monotreme\n"),
+    );
+
+    let paranoid = ProcessorOptions {
+        paranoid_scan: true,
+        ..ProcessorOptions::default()
+    };
+
+    // Normally the junk is mistaken for a frame of its own.
+    let s = f.walk_stack(make_stack()).await;
+    assert_eq!(s.frames.len(), 3);
+    assert_eq!(s.frames[1].trust, FrameTrust::Scan);
+    assert_eq!(s.frames[1].instruction + 1, 0x00007500b0000100);
+
+    let s = f.walk_stack_with_options(make_stack(), &paranoid).await;
+    assert_eq!(s.frames.len(), 2);
+    {
+        let frame = &s.frames[1];
+        assert_eq!(frame.trust, FrameTrust::Scan);
+        if let MinidumpRawContext::Amd64(ctx) = &frame.context.raw {
+            assert_eq!(ctx.rip, return_address);
+            assert_eq!(ctx.rsp, frame1_rsp);
+        } else {
+            unreachable!();
+        }
+    }
+
+    // A frame pointer below the return address that doesn't point at a saved
+    // frame pointer means the return address can't belong to the callee.
+    f.raw.rbp = stack_start + 4;
+    let s = f.walk_stack_with_options(make_stack(), &paranoid).await;
+    assert_eq!(s.frames.len(), 1);
+}
+
+#[tokio::test]
+async fn test_paranoid_scan_code() {
+    // A paranoid scan should reject return addresses that aren't executable
+    // or don't follow a call, if the minidump has the memory to tell.
+    let mut f = TestFixture::new();
+    let stack_start = 0x8000000080000000u64;
+    let code_start = 0x00007400c0000200u64;
+    let return_address = 0x00007400c0000300u64;
+    let make_stack = || {
+        let stack = Section::new();
+        stack.start().set_const(stack_start);
+        stack
+            // frame 0
+            .append_repeated(0, 16) // space
+            .D64(0x00007400c0000280u64) // in a function, but not after a call
+            .D64(return_address) // actual return address
+            // frame 1
+            .append_repeated(0, 32) // end of stack
+    };
+
+    f.raw.rip = code_start;
+    f.raw.rbp = stack_start + 16 + 64;
+    f.raw.rsp = stack_start;
+
+    f.add_symbols(
+        String::from("module1"),
+        String::from("FUNC 100 400 10 monotreme\n"),
+    );
+
+    let paranoid = ProcessorOptions {
+        paranoid_scan: true,
+        ..ProcessorOptions::default()
+    };
+
+    // call qword ptr [rax + 8]
+    let mut code = vec![0; 0x200];
+    code[0xfd..0x100].copy_from_slice(&[0xff, 0x50, 0x08]);
+    let memory_list =
+        UnifiedMemoryList::Memory(MinidumpMemoryList::from_regions(vec![MinidumpMemory {
+            desc: Default::default(),
+            base_address: code_start,
+            size: code.len() as u64,
+            bytes: &code,
+            endian: scroll::LE,
+        }]));
+    let maps = |line: &'static [u8]| {
+        let map = MinidumpLinuxMapInfo::from_line(LinuxOsStr::from_bytes(line)).unwrap();
+        UnifiedMemoryInfoList::Maps(MinidumpLinuxMaps::from_regions(vec![map]))
+    };
+    let executable = maps(b"7400c0000000-7400c0010000 r-xp 00000000 00:00 0 module1");
+    let not_executable = maps(b"7400c0000000-7400c0010000 r--p 00000000 00:00 0 module1");
+
+    // Without the code, the junk is mistaken for a frame of its own.
+    let s = f.walk_stack_with_options(make_stack(), &paranoid).await;
+    assert_eq!(s.frames.len(), 3);
+    assert_eq!(s.frames[1].instruction + 1, 0x00007400c0000280);
+
+    let dump_memory = DumpMemory {
+        memory_list: Some(&memory_list),
+        memory_info: Some(&executable),
+    };
+    let symbolizer = Symbolizer::new(string_symbol_supplier(f.symbols.clone()));
+    let s = f
+        .walk_stack_with_dump_memory(make_stack(), &paranoid, &dump_memory, &symbolizer)
+        .await;
+    assert_eq!(s.frames.len(), 2);
+    assert_eq!(s.frames[1].trust, FrameTrust::Scan);
+    assert_eq!(s.frames[1].instruction + 1, return_address);
+
+    // Code that isn't executable isn't returned to.
+    let dump_memory = DumpMemory {
+        memory_list: Some(&memory_list),
+        memory_info: Some(&not_executable),
+    };
+    let s = f
+        .walk_stack_with_dump_memory(make_stack(), &paranoid, &dump_memory, &symbolizer)
+        .await;
+    assert_eq!(s.frames.len(), 1);
+}
+
 const CALLEE_SAVE_REGS: &[&str] = &["rip", "rbx", "rbp", "rsp", "r12", "r13", "r14", "r15"];
 
 fn init_cfi_state() -> (TestFixture, Section, CONTEXT_AMD64, MinidumpContextValidity) {
@@ -650,6 +976,191 @@ async fn test_cfi_at_4006() {
     check_cfi(f, stack, expected, expected_valid).await;
 }
 
+#[tokio::test]
+async fn test_cfi_plausibility() {
+    let options = ProcessorOptions {
+        check_cfi_plausibility: true,
+        ..ProcessorOptions::default()
+    };
+    let make_stack = |saved_rbx: u64| {
+        let (mut f, stack, _, _) = init_cfi_state();
+        let stack = stack
+            .D64(saved_rbx) // saved %rbx
+            .D64(0x00007400c0005510) // return address
+            .append_repeated(0, 1000);
+        f.raw.set_register("rip", 0x00007400c0004001);
+        (f, stack)
+    };
+
+    // A plausible result is trusted as usual.
+    let (f, stack) = make_stack(0x5a5beeb38de23be8);
+    let s = f.walk_stack_with_options(stack, &options).await;
+    assert_eq!(s.frames.len(), 2);
+    assert_eq!(s.frames[1].trust, FrameTrust::CallFrameInfo);
+
+    // But CFI restoring %rbx to zero is suspicious.
+    let (f, stack) = make_stack(0);
+    let s = f.walk_stack_with_options(stack, &options).await;
+    assert_eq!(s.frames.len(), 2);
+    assert_eq!(s.frames[1].trust, FrameTrust::CallFrameInfoLowConfidence);
+
+    // Unless we aren't checking.
+    let (f, stack) = make_stack(0);
+    let s = f.walk_stack(stack).await;
+    assert_eq!(s.frames[1].trust, FrameTrust::CallFrameInfo);
+}
+
+#[tokio::test]
+async fn test_cross_validate_cfi() {
+    let mut f = TestFixture::new();
+    let symbols = [
+        // A standard frame pointer prologue, which also restores %rbx to zero.
+        "FUNC 4000 1000 10 enchiridion\n",
+        "STACK CFI INIT 4000 100 .cfa: $rbp 16 + .ra: .cfa 8 - ^ $rbp: .cfa 16 - ^ ",
+        "$rbx: .cfa 24 - ^\n",
+        // Bogus CFI that gets the caller's stack pointer wrong.
+        "FUNC 6000 1000 10 discourses\n",
+        "STACK CFI INIT 6000 100 .cfa: $rsp 8 + .ra: .cfa 8 - ^\n",
+        "FUNC 5000 1000 10 epictetus\n",
+        "STACK CFI INIT 5000 1000 .cfa: $rsp .ra 0\n",
+    ];
+    f.add_symbols(String::from("module1"), symbols.concat());
+    let return_address = 0x00007400c0005510;
+
+    let make_stack = |f: &mut TestFixture| {
+        let frame0_rbp = Label::new();
+        let frame1_rbp = Label::new();
+        let stack = Section::new();
+        stack.start().set_const(0x8000000080000000);
+        let stack = stack
+            .D64(return_address) // what the bogus CFI takes for the return address
+            .D64(0) // saved %rbx
+            .mark(&frame0_rbp)
+            .D64(&frame1_rbp) // caller-pushed %rbp
+            .D64(return_address) // actual return address
+            .append_repeated(0, 32) // body of frame1
+            .mark(&frame1_rbp)
+            .D64(0);
+        f.raw.rsp = stack.start().value().unwrap();
+        f.raw.rbp = frame0_rbp.value().unwrap();
+        f.raw.rbx = 0x5a5beeb38de23be8;
+        stack
+    };
+    let plausibility = ProcessorOptions {
+        check_cfi_plausibility: true,
+        ..ProcessorOptions::default()
+    };
+    let cross_validate = ProcessorOptions {
+        check_cfi_plausibility: true,
+        cross_validate_cfi: true,
+        ..ProcessorOptions::default()
+    };
+
+    // The plausibility check dislikes %rbx being zeroed...
+    f.raw.rip = 0x00007400c0004010;
+    let stack = make_stack(&mut f);
+    let s = f.walk_stack_with_options(stack, &plausibility).await;
+    assert_eq!(s.frames.len(), 2);
+    assert_eq!(s.frames[1].trust, FrameTrust::CallFrameInfoLowConfidence);
+
+    // ...but the frame pointer agrees with the CFI, so it's trusted after all.
+    let stack = make_stack(&mut f);
+    let s = f.walk_stack_with_options(stack, &cross_validate).await;
+    assert_eq!(s.frames.len(), 2);
+    assert_eq!(s.frames[1].trust, FrameTrust::CallFrameInfo);
+    assert!(s.frames[1].warnings.is_empty());
+
+    // When they disagree, the CFI wins, with a warning.
+    f.raw.rip = 0x00007400c0006010;
+    let stack = make_stack(&mut f);
+    let s = f.walk_stack_with_options(stack, &cross_validate).await;
+    assert_eq!(s.frames[1].trust, FrameTrust::CallFrameInfo);
+    assert_eq!(s.frames[1].context.get_stack_pointer(), 0x8000000080000008);
+    assert_eq!(s.frames[1].warnings.len(), 1);
+}
+
+#[tokio::test]
+async fn test_constrain_to_thread_stack() {
+    let mut f = TestFixture::new();
+    let symbols = [
+        "FUNC 4000 1000 10 enchiridion\n",
+        // Bogus CFI that puts the caller's stack far past the end of ours.
+        "STACK CFI INIT 4000 100 .cfa: $rsp 65536 + .ra: $rsp ^\n",
+        "FUNC 5000 1000 10 epictetus\n",
+        "STACK CFI INIT 5000 1000 .cfa: $rsp .ra 0\n",
+    ];
+    f.add_symbols(String::from("module1"), symbols.concat());
+    f.raw.rip = 0x00007400c0004000;
+    f.raw.rsp = 0x8000000080000000;
+    let make_stack = || {
+        let stack = Section::new();
+        stack.start().set_const(0x8000000080000000);
+        stack
+            .D64(0x00007400c0005510) // return address
+            .append_repeated(0, 1000)
+    };
+
+    let s = f.walk_stack(make_stack()).await;
+    assert_eq!(s.frames.len(), 2);
+    assert_eq!(s.frames[1].context.get_stack_pointer(), 0x8000000080010000);
+
+    let options = ProcessorOptions {
+        constrain_to_thread_stack: true,
+        ..ProcessorOptions::default()
+    };
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 1);
+}
+
+#[tokio::test]
+async fn test_stop_at_stack_bottom() {
+    let mut f = TestFixture::new();
+    let symbols = [
+        "FUNC 4000 1000 10 enchiridion\n",
+        "STACK CFI INIT 4000 1000 .cfa: $rsp 16 + .ra: .cfa 8 - ^\n",
+    ];
+    f.add_symbols(String::from("module1"), symbols.concat());
+    f.raw.rip = 0x00007400c0004000;
+    f.raw.rsp = 0x8000000080000000;
+    let make_stack = || {
+        let mut stack = Section::new();
+        stack.start().set_const(0x8000000080000000);
+        // Four frames of recursion.
+        for _ in 0..4 {
+            stack = stack.D64(0).D64(0x00007400c0004010);
+        }
+        stack
+    };
+
+    let s = f.walk_stack(make_stack()).await;
+    assert_eq!(s.frames.len(), 5);
+    assert_eq!(s.info, CallStackInfo::Ok);
+
+    // The thread's stack is only big enough for the first two callers.
+    let options = ProcessorOptions {
+        stop_at_stack_bottom: true,
+        ..ProcessorOptions::default()
+    };
+    f.stack_range = Some(0x8000000080000000..0x8000000080000020);
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 3);
+    assert_eq!(s.frames[2].context.get_stack_pointer(), 0x8000000080000020);
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::ReachedStackBottom));
+
+    // A caller right at the bottom of the stack is the last one, whatever
+    // walking past it would have found.
+    f.stack_range = Some(0x8000000080000000..0x8000000080000040);
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 5);
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::ReachedStackBottom));
+
+    // Without a declared stack, we walk as usual.
+    f.stack_range = None;
+    let s = f.walk_stack_with_options(make_stack(), &options).await;
+    assert_eq!(s.frames.len(), 5);
+    assert_eq!(s.info, CallStackInfo::Ok);
+}
+
 #[tokio::test]
 async fn test_frame_pointer_overflow() {
     // Make sure we don't explode when trying frame pointer analysis on a value
@@ -813,3 +1324,93 @@ async fn test_scan_walk_overflow() {
         }
     }
 }
+
+/// A [`Symbolizer`] that remembers the grand callee parameter size each CFI
+/// evaluation was given.
+struct RecordingProvider {
+    symbolizer: Symbolizer,
+    grand_callee_parameter_sizes: Mutex<Vec<(u64, u32)>>,
+}
+
+#[async_trait]
+impl SymbolProvider for RecordingProvider {
+    async fn fill_symbol(
+        &self,
+        module: &(dyn Module + Sync),
+        frame: &mut (dyn FrameSymbolizer + Send),
+    ) -> Result<(), FillSymbolError> {
+        self.symbolizer.fill_symbol(module, frame).await
+    }
+    async fn walk_frame(
+        &self,
+        module: &(dyn Module + Sync),
+        walker: &mut (dyn FrameWalker + Send),
+    ) -> Option<()> {
+        if walker.has_grand_callee() {
+            self.grand_callee_parameter_sizes.lock().unwrap().push((
+                walker.get_instruction(),
+                walker.get_grand_callee_parameter_size(),
+            ));
+        }
+        self.symbolizer.walk_frame(module, walker).await
+    }
+    async fn get_file_path(
+        &self,
+        module: &(dyn Module + Sync),
+        file_kind: FileKind,
+    ) -> Result<PathBuf, FileError> {
+        self.symbolizer.get_file_path(module, file_kind).await
+    }
+}
+
+#[tokio::test]
+async fn test_cfi_grand_callee_parameter_size() {
+    // FUNC records from PDBs carry parameter sizes on x64 too, with no STACK
+    // WIN records to go with them. They should still reach the frames, and
+    // CFI evaluation of the frames' callers.
+    let mut f = TestFixture::new();
+    let symbols = [
+        "FUNC 4000 100 10 has_stack_params\n",
+        "STACK CFI INIT 4000 100 .cfa: $rsp 8 + .ra: .cfa 8 - ^\n",
+        "FUNC 5000 100 0 caller\n",
+        "STACK CFI INIT 5000 100 .cfa: $rsp 8 + .ra: .cfa 8 - ^\n",
+        "FUNC 6000 100 0 outermost\n",
+        "STACK CFI INIT 6000 100 .cfa: $rsp .ra 0\n",
+    ];
+    f.add_symbols(String::from("module1"), symbols.concat());
+
+    let mut stack = Section::new();
+    let stack_start = 0x8000000080000000;
+    stack.start().set_const(stack_start);
+    stack = stack
+        // frame 0
+        .D64(0x00007400c0005010) // return address
+        // frame 1
+        .D64(0x00007400c0006010) // return address
+        // frame 2
+        .append_repeated(0, 16);
+
+    f.raw.rip = 0x00007400c0004010;
+    f.raw.rsp = stack_start;
+
+    let provider = RecordingProvider {
+        symbolizer: Symbolizer::new(string_symbol_supplier(f.symbols.clone())),
+        grand_callee_parameter_sizes: Mutex::new(Vec::new()),
+    };
+    let s = f
+        .walk_stack_with_provider(stack, &ProcessorOptions::default(), &provider)
+        .await;
+    assert_eq!(s.frames.len(), 3);
+    assert_eq!(s.frames[0].parameter_size, Some(0x10));
+    assert_eq!(s.frames[1].parameter_size, Some(0));
+    assert_eq!(s.frames[1].trust, FrameTrust::CallFrameInfo);
+    assert_eq!(s.frames[2].trust, FrameTrust::CallFrameInfo);
+
+    // Unwinding frame 1 (looked up by the address of its call instruction)
+    // has frame 0 as its grand callee, and frame 2 has frame 1.
+    let sizes = provider.grand_callee_parameter_sizes.lock().unwrap();
+    assert_eq!(
+        *sizes,
+        vec![(0x00007400c000500f, 0x10), (0x00007400c000600f, 0)]
+    );
+}
@@ -4,13 +4,13 @@
 // NOTE: arm64_old.rs and arm64.rs should be identical except for the names of
 // their context types.
 
-use crate::process_state::{FrameTrust, StackFrame};
+use crate::process_state::{FrameTrust, StackFrame, WalkEndReason};
 use crate::stackwalker::unwind::Unwind;
-use crate::stackwalker::CfiStackWalker;
-use crate::{SymbolProvider, SystemInfo};
+use crate::stackwalker::{cross_validate_cfi, no_caller_reason, CfiStackWalker, DumpMemory};
+use crate::{ProcessorOptions, SymbolProvider, SystemInfo};
 use minidump::{
-    CpuContext, MinidumpContext, MinidumpContextValidity, MinidumpMemory, MinidumpModuleList,
-    MinidumpRawContext, Module,
+    CpuContext, MinidumpContext, MinidumpContextValidity, MinidumpModuleList, MinidumpRawContext,
+    Module, UnifiedMemory,
 };
 use std::collections::HashSet;
 use tracing::trace;
@@ -32,8 +32,9 @@ async fn get_caller_by_cfi<P>(
     ctx: &ArmContext,
     callee: &StackFrame,
     grand_callee: Option<&StackFrame>,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> Option<StackFrame>
 where
@@ -67,6 +68,13 @@ where
     symbol_provider
         .walk_frame(module, &mut stack_walker)
         .await?;
+    let trust = if options.check_cfi_plausibility
+        && stack_walker.caller_seems_implausible(Some(FRAME_POINTER), CALLEE_SAVED_REGS)
+    {
+        FrameTrust::CallFrameInfoLowConfidence
+    } else {
+        FrameTrust::CallFrameInfo
+    };
 
     let caller_pc = stack_walker.caller_ctx.get_register_always(PROGRAM_COUNTER);
     let caller_sp = stack_walker.caller_ctx.get_register_always(STACK_POINTER);
@@ -114,7 +122,7 @@ where
         raw: MinidumpRawContext::Arm64(stack_walker.caller_ctx),
         valid: new_valid,
     };
-    Some(StackFrame::from_context(context, FrameTrust::CallFrameInfo))
+    Some(StackFrame::from_context(context, trust))
 }
 
 fn callee_forwarded_regs(valid: &MinidumpContextValidity) -> HashSet<&'static str> {
@@ -132,7 +140,7 @@ fn get_caller_by_frame_pointer<P>(
     ctx: &ArmContext,
     callee: &StackFrame,
     _grand_callee: Option<&StackFrame>,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
     modules: &MinidumpModuleList,
     _symbol_provider: &P,
 ) -> Option<StackFrame>
@@ -318,8 +326,9 @@ fn ptr_auth_strip(modules: &MinidumpModuleList, ptr: Pointer) -> Pointer {
 async fn get_caller_by_scan<P>(
     ctx: &ArmContext,
     callee: &StackFrame,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> Option<StackFrame>
 where
@@ -350,7 +359,7 @@ where
     for i in 0..scan_range {
         let address_of_pc = last_sp.checked_add(i * POINTER_WIDTH)?;
         let caller_pc = stack_memory.get_memory_at_address(address_of_pc as u64)?;
-        if instruction_seems_valid(caller_pc, modules, symbol_provider).await {
+        if instruction_seems_valid(caller_pc, modules, options, symbol_provider).await {
             // pc is pushed by CALL, so sp is just address_of_pc + ptr
             let caller_sp = address_of_pc.checked_add(POINTER_WIDTH)?;
 
@@ -408,6 +417,7 @@ where
 async fn instruction_seems_valid<P>(
     instruction: Pointer,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> bool
 where
@@ -417,7 +427,13 @@ where
         return false;
     }
 
-    super::instruction_seems_valid_by_symbols(instruction as u64, modules, symbol_provider).await
+    // Instructions are always 4-byte aligned.
+    if options.paranoid_scan && instruction & 3 != 0 {
+        return false;
+    }
+
+    super::instruction_seems_valid_by_symbols(instruction as u64, modules, options, symbol_provider)
+        .await
 }
 
 fn is_non_canonical(instruction: Pointer) -> bool {
@@ -431,7 +447,7 @@ fn is_non_canonical(instruction: Pointer) -> bool {
 fn stack_seems_valid(
     caller_sp: Pointer,
     callee_sp: Pointer,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
 ) -> bool {
     // The stack shouldn't *grow* when we unwind
     if caller_sp < callee_sp {
@@ -451,38 +467,50 @@ impl Unwind for ArmContext {
         &self,
         callee: &StackFrame,
         grand_callee: Option<&StackFrame>,
-        stack_memory: Option<&MinidumpMemory<'_>>,
+        stack_memory: Option<&UnifiedMemory<'_, '_>>,
+        _dump_memory: &DumpMemory<'_, '_>,
         modules: &MinidumpModuleList,
         _system_info: &SystemInfo,
+        options: &ProcessorOptions<'_>,
         syms: &P,
-    ) -> Option<StackFrame>
+    ) -> Result<StackFrame, WalkEndReason>
     where
         P: SymbolProvider + Sync,
     {
-        let stack = stack_memory.as_ref()?;
+        let stack = stack_memory
+            .as_ref()
+            .ok_or(WalkEndReason::MissingStackMemory)?;
 
         // .await doesn't like closures, so don't use Option chaining
         let mut frame = None;
         if frame.is_none() {
-            frame = get_caller_by_cfi(self, callee, grand_callee, stack, modules, syms).await;
+            frame =
+                get_caller_by_cfi(self, callee, grand_callee, stack, modules, options, syms).await;
+        }
+        if options.cross_validate_cfi {
+            if let Some(cfi_frame) = frame.as_mut() {
+                let fp_frame =
+                    get_caller_by_frame_pointer(self, callee, grand_callee, stack, modules, syms);
+                cross_validate_cfi(cfi_frame, fp_frame.as_ref());
+            }
         }
         if frame.is_none() {
             frame = get_caller_by_frame_pointer(self, callee, grand_callee, stack, modules, syms);
         }
         if frame.is_none() {
-            frame = get_caller_by_scan(self, callee, stack, modules, syms).await;
+            frame = get_caller_by_scan(self, callee, stack, modules, options, syms).await;
         }
-        let mut frame = frame?;
+        let mut frame = frame.ok_or_else(|| no_caller_reason(callee, stack))?;
 
         // We now check the frame to see if it looks like unwinding is complete,
         // based on the frame we computed having a nonsense value. Returning
-        // None signals to the unwinder to stop unwinding.
+        // an error signals to the unwinder to stop unwinding.
 
         // if the instruction is within the first ~page of memory, it's basically
         // null, and we can assume unwinding is complete.
         if frame.context.get_instruction_pointer() < 4096 {
             trace!("instruction pointer was nullish, assuming unwind complete");
-            return None;
+            return Err(WalkEndReason::NullReturnAddress);
         }
 
         // If the new stack pointer is at a lower address than the old,
@@ -500,7 +528,7 @@ impl Unwind for ArmContext {
             let is_leaf = callee.trust == FrameTrust::Context && sp == last_sp;
             if !is_leaf {
                 trace!("stack pointer went backwards, assuming unwind complete");
-                return None;
+                return Err(WalkEndReason::StackPointerWentBackwards);
             }
         }
 
@@ -514,6 +542,27 @@ impl Unwind for ArmContext {
         let ip = frame.context.get_instruction_pointer() as u64;
         frame.instruction = ip - 4;
 
-        Some(frame)
+        Ok(frame)
+    }
+
+    fn validate_registers(
+        &self,
+        valid: &MinidumpContextValidity,
+        _modules: &MinidumpModuleList,
+    ) -> Vec<String> {
+        let mut problems = Vec::new();
+        // The hardware faults on any sp-relative access if sp isn't 16-byte aligned.
+        if let Some(sp) = self.get_register(STACK_POINTER, valid) {
+            if sp % 16 != 0 {
+                problems.push(format!("{} is not 16-byte aligned", STACK_POINTER));
+            }
+        }
+        // All A64 instructions are 4 bytes wide (and 4-byte aligned).
+        if let Some(pc) = self.get_register(PROGRAM_COUNTER, valid) {
+            if pc % 4 != 0 {
+                problems.push(format!("{} is not 4-byte aligned", PROGRAM_COUNTER));
+            }
+        }
+        problems
     }
 }
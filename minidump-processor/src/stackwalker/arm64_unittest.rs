@@ -4,7 +4,7 @@
 // NOTE: we don't bother testing arm64_old, it should have identical code at
 // all times!
 
-use crate::stackwalker::walk_stack;
+use crate::stackwalker::{walk_stack, DumpMemory};
 use crate::{process_state::*, ProcessorOptions};
 use crate::{string_symbol_supplier, Symbolizer, SystemInfo};
 use minidump::system_info::{Cpu, Os};
@@ -62,6 +62,15 @@ impl TestFixture {
     }
 
     pub async fn walk_stack(&self, stack: Section) -> CallStack {
+        self.walk_stack_with_options(stack, &ProcessorOptions::default())
+            .await
+    }
+
+    pub async fn walk_stack_with_options(
+        &self,
+        stack: Section,
+        options: &ProcessorOptions<'_>,
+    ) -> CallStack {
         let context = MinidumpContext {
             raw: MinidumpRawContext::Arm64(self.raw.clone()),
             valid: MinidumpContextValidity::All,
@@ -81,19 +90,25 @@ impl TestFixture {
             os_version: None,
             os_build: None,
             cpu: Cpu::Arm64,
+            endian: scroll::LE,
             cpu_info: None,
             cpu_microcode_version: None,
+            cpu_features: None,
+            cpu_microarchitecture: None,
+            cpu_description: None,
             cpu_count: 1,
+            available_physical_memory: None,
+            commit_limit: None,
         };
         let symbolizer = Symbolizer::new(string_symbol_supplier(self.symbols.clone()));
-        let options = ProcessorOptions::default();
         let mut stack = CallStack::with_context(context);
 
         walk_stack(
             0,
-            &options,
+            options,
             &mut stack,
-            Some(&stack_memory),
+            Some(&UnifiedMemory::Memory(&stack_memory)),
+            &DumpMemory::default(),
             &self.modules,
             &system_info,
             &symbolizer,
@@ -459,6 +474,58 @@ async fn test_frame_pointer() {
     }
 }
 
+#[tokio::test]
+async fn test_validate_registers_misaligned_sp() {
+    // A frame pointer walk that recovers a caller sp which isn't 16-byte
+    // aligned should be flagged, but shouldn't stop the walk.
+    let mut f = TestFixture::new();
+    let mut stack = Section::new();
+    stack.start().set_const(0x80000000);
+
+    let return_address1 = 0x50000100u64;
+    let return_address2 = 0x50000900u64;
+    let frame0_fp = Label::new();
+    let frame1_fp = Label::new();
+    let frame2_fp = Label::new();
+
+    stack = stack
+        // frame 0
+        .append_repeated(0, 72) // space (an extra 8 bytes to misalign frame 1)
+        .mark(&frame0_fp)
+        .D64(&frame1_fp)
+        .D64(return_address1)
+        // frame 1
+        .append_repeated(0, 64)
+        .mark(&frame1_fp)
+        .D64(&frame2_fp)
+        .D64(return_address2)
+        // frame 2
+        .append_repeated(0, 64)
+        .mark(&frame2_fp)
+        .D64(0)
+        .D64(0);
+
+    f.raw.set_register("pc", 0x40005510);
+    f.raw.set_register("lr", 0x1fe0fe10);
+    f.raw.set_register("fp", frame0_fp.value().unwrap());
+    f.raw.set_register("sp", stack.start().value().unwrap());
+
+    let options = ProcessorOptions {
+        validate_registers: true,
+        ..ProcessorOptions::default()
+    };
+    let s = f.walk_stack_with_options(stack, &options).await;
+    assert_eq!(s.frames.len(), 3);
+    assert!(s.frames[0].warnings.is_empty());
+
+    let frame = &s.frames[1];
+    assert_eq!(frame.trust, FrameTrust::FramePointer);
+    assert_eq!(
+        frame.warnings,
+        vec![String::from("sp is not 16-byte aligned")]
+    );
+}
+
 #[tokio::test]
 async fn test_frame_pointer_stackless_leaf() {
     // Same as test_frame_pointer but frame0 is a stackless leaf.
@@ -1,7 +1,7 @@
 // Copyright 2015 Ted Mielczarek. See the COPYRIGHT
 // file at the top-level directory of this distribution.
 
-use crate::stackwalker::walk_stack;
+use crate::stackwalker::{walk_stack, DumpMemory};
 use crate::{process_state::*, ProcessorOptions};
 use crate::{string_symbol_supplier, Symbolizer, SystemInfo};
 use minidump::format::CONTEXT_ARM;
@@ -32,9 +32,15 @@ impl TestFixture {
                 os_version: None,
                 os_build: None,
                 cpu: Cpu::Arm,
+                endian: scroll::LE,
                 cpu_info: None,
                 cpu_microcode_version: None,
+                cpu_features: None,
+                cpu_microarchitecture: None,
+                cpu_description: None,
                 cpu_count: 1,
+                available_physical_memory: None,
+                commit_limit: None,
             },
             symbols: HashMap::new(),
         }
@@ -63,7 +69,8 @@ impl TestFixture {
             0,
             &options,
             &mut stack,
-            Some(&stack_memory),
+            Some(&UnifiedMemory::Memory(&stack_memory)),
+            &DumpMemory::default(),
             &self.modules,
             &self.system_info,
             &symbolizer,
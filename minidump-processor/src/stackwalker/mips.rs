@@ -2,16 +2,17 @@ use std::collections::HashSet;
 use std::convert::TryFrom;
 
 use minidump::format::ContextFlagsCpu;
+use minidump::system_info::PointerWidth;
 use minidump::{
-    CpuContext, Endian, MinidumpContext, MinidumpContextValidity, MinidumpMemory,
-    MinidumpModuleList, MinidumpRawContext,
+    CpuContext, Endian, MinidumpContext, MinidumpContextValidity, MinidumpModuleList,
+    MinidumpRawContext, UnifiedMemory,
 };
 use scroll::ctx::{SizeWith, TryFromCtx};
 use tracing::trace;
 
 use crate::stackwalker::unwind::Unwind;
-use crate::stackwalker::CfiStackWalker;
-use crate::{FrameTrust, StackFrame, SymbolProvider, SystemInfo};
+use crate::stackwalker::{no_caller_reason, CfiStackWalker, DumpMemory};
+use crate::{FrameTrust, ProcessorOptions, StackFrame, SymbolProvider, SystemInfo, WalkEndReason};
 
 type MipsContext = minidump::format::CONTEXT_MIPS;
 type Pointer = <MipsContext as CpuContext>::Register;
@@ -26,8 +27,9 @@ async fn get_caller_by_cfi<'a, C, P>(
     ctx: &'a C,
     callee: &'a StackFrame,
     grand_callee: Option<&'a StackFrame>,
-    stack_memory: &'a MinidumpMemory<'_>,
+    stack_memory: &'a UnifiedMemory<'_, '_>,
     modules: &'a MinidumpModuleList,
+    options: &'a ProcessorOptions<'_>,
     symbol_provider: &'a P,
 ) -> Option<StackFrame>
 where
@@ -65,6 +67,13 @@ where
     symbol_provider
         .walk_frame(module, &mut stack_walker)
         .await?;
+    let trust = if options.check_cfi_plausibility
+        && stack_walker.caller_seems_implausible(None, CALLEE_SAVED_REGS)
+    {
+        FrameTrust::CallFrameInfoLowConfidence
+    } else {
+        FrameTrust::CallFrameInfo
+    };
     let caller_pc = stack_walker.caller_ctx.get_register_always(PROGRAM_COUNTER);
     let caller_sp = stack_walker.caller_ctx.get_register_always(STACK_POINTER);
 
@@ -81,7 +90,7 @@ where
         raw: stack_walker.caller_ctx.into_ctx(),
         valid: MinidumpContextValidity::Some(stack_walker.caller_validity),
     };
-    Some(StackFrame::from_context(context, FrameTrust::CallFrameInfo))
+    Some(StackFrame::from_context(context, trust))
 }
 
 fn callee_forwarded_regs(valid: &MinidumpContextValidity) -> HashSet<&'static str> {
@@ -95,19 +104,20 @@ fn callee_forwarded_regs(valid: &MinidumpContextValidity) -> HashSet<&'static st
     }
 }
 
-async fn get_caller_by_scan32<P>(
-    ctx: &Mips32Context,
+async fn get_caller_by_scan<P>(
+    last_sp: u64,
+    pointer_width: PointerWidth,
     callee: &StackFrame,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> Option<StackFrame>
 where
     P: SymbolProvider + Sync,
 {
-    const MAX_STACK_SIZE: u32 = 1024;
-    const MIN_ARGS: u32 = 4;
-    const POINTER_WIDTH: u32 = 4;
+    const MAX_STACK_SIZE: u64 = 1024;
+    const MIN_ARGS: u64 = 4;
     trace!("trying scan");
     // Stack scanning is just walking from the end of the frame until we encounter
     // a value on the stack that looks like a pointer into some code (it's an address
@@ -115,87 +125,32 @@ where
     // we assume it's a `ra` value that was saved on the stack by the callee in
     // its function prologue, following a `jal` (call) instruction of the caller.
     // The next frame is then assumed to end just before that `ra` value.
-    let valid = &callee.context.valid;
-    let mut last_sp = ctx.get_register(STACK_POINTER, valid)?;
-
-    let mut count = MAX_STACK_SIZE / POINTER_WIDTH;
+    let pointer_size = u64::from(pointer_width.size_in_bytes()?);
+    // Addresses that don't fit in a pointer have overflowed.
+    let max_address = u64::MAX >> (64 - 8 * pointer_size);
+    let checked_add =
+        |addr: u64, offset: u64| addr.checked_add(offset).filter(|&a| a <= max_address);
+
+    let mut last_sp = last_sp;
+    let mut count = MAX_STACK_SIZE / pointer_size;
     // In case of mips32 ABI the stack frame of a non-leaf function
     // must have a minimum stack frame size for 4 arguments (4 words).
     // Move stack pointer for 4 words to avoid reporting non-existing frames
     // for all frames except the topmost one.
     // There is no way of knowing if topmost frame belongs to a leaf or
     // a non-leaf function.
-    if callee.trust != FrameTrust::Context {
-        last_sp = last_sp.checked_add(MIN_ARGS * POINTER_WIDTH)?;
+    if pointer_width == PointerWidth::Bits32 && callee.trust != FrameTrust::Context {
+        last_sp = checked_add(last_sp, MIN_ARGS * pointer_size)?;
         count -= MIN_ARGS;
     }
 
     for i in 0..count {
-        let address_of_pc = last_sp.checked_add(i * POINTER_WIDTH)?;
-        let caller_pc: u32 = stack_memory.get_memory_at_address(address_of_pc as u64)?;
-        //trace!("unwind: trying addr 0x{address_of_pc:08x}: 0x{caller_pc:08x}");
-        if instruction_seems_valid(caller_pc as u64, modules, symbol_provider).await {
-            // `ra` is usually saved directly at the bottom of the frame,
-            // so sp is just address_of_pc + ptr
-            let caller_sp = address_of_pc.checked_add(POINTER_WIDTH)?;
-
-            // Don't do any more validation, and don't try to restore fp
-            // (that's what breakpad does!)
-
-            trace!(
-                "scan seems valid -- caller_pc: 0x{caller_pc:016x}, caller_sp: 0x{caller_sp:016x}"
-            );
-
-            let mut caller_ctx = MipsContext::default();
-            caller_ctx.set_register(PROGRAM_COUNTER, caller_pc as u64);
-            caller_ctx.set_register(STACK_POINTER, caller_sp as u64);
-
-            let mut valid = HashSet::new();
-            valid.insert(PROGRAM_COUNTER);
-            valid.insert(STACK_POINTER);
-
-            let context = MinidumpContext {
-                raw: MinidumpRawContext::Mips(caller_ctx),
-                valid: MinidumpContextValidity::Some(valid),
-            };
-            return Some(StackFrame::from_context(context, FrameTrust::Scan));
-        }
-    }
-
-    None
-}
-
-async fn get_caller_by_scan64<P>(
-    ctx: &MipsContext,
-    callee: &StackFrame,
-    stack_memory: &MinidumpMemory<'_>,
-    modules: &MinidumpModuleList,
-    symbol_provider: &P,
-) -> Option<StackFrame>
-where
-    P: SymbolProvider + Sync,
-{
-    const MAX_STACK_SIZE: u64 = 1024;
-    const POINTER_WIDTH: u64 = 8;
-    trace!("trying scan");
-    // Stack scanning is just walking from the end of the frame until we encounter
-    // a value on the stack that looks like a pointer into some code (it's an address
-    // in a range covered by one of our modules). If we find such an instruction,
-    // we assume it's a `ra` value that was saved on the stack by the callee in
-    // its function prologue, following a `jal` (call) instruction of the caller.
-    // The next frame is then assumed to end just before that `ra` value.
-    let valid = &callee.context.valid;
-    let last_sp = ctx.get_register(STACK_POINTER, valid)?;
-
-    let count = MAX_STACK_SIZE / POINTER_WIDTH;
-
-    for i in 0..count {
-        let address_of_pc = last_sp.checked_add(i * POINTER_WIDTH)?;
-        let caller_pc = stack_memory.get_memory_at_address(address_of_pc)?;
-        if instruction_seems_valid(caller_pc, modules, symbol_provider).await {
+        let address_of_pc = checked_add(last_sp, i * pointer_size)?;
+        let caller_pc = stack_memory.read_pointer_at(address_of_pc, pointer_width)?;
+        if instruction_seems_valid(caller_pc, modules, options, symbol_provider).await {
             // `ra` is usually saved directly at the bottom of the frame,
             // so sp is just address_of_pc + ptr
-            let caller_sp = address_of_pc.checked_add(POINTER_WIDTH)?;
+            let caller_sp = checked_add(address_of_pc, pointer_size)?;
 
             // Don't do any more validation, and don't try to restore fp
             // (that's what breakpad does!)
@@ -226,6 +181,7 @@ where
 async fn instruction_seems_valid<P>(
     instruction: Pointer,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> bool
 where
@@ -235,7 +191,13 @@ where
         return false;
     }
 
-    super::instruction_seems_valid_by_symbols(instruction as u64, modules, symbol_provider).await
+    // Instructions are always 4-byte aligned.
+    if options.paranoid_scan && instruction & 3 != 0 {
+        return false;
+    }
+
+    super::instruction_seems_valid_by_symbols(instruction as u64, modules, options, symbol_provider)
+        .await
 }
 
 #[async_trait::async_trait]
@@ -244,52 +206,87 @@ impl Unwind for MipsContext {
         &self,
         callee: &StackFrame,
         grand_callee: Option<&StackFrame>,
-        stack_memory: Option<&MinidumpMemory<'_>>,
+        stack_memory: Option<&UnifiedMemory<'_, '_>>,
+        _dump_memory: &DumpMemory<'_, '_>,
         modules: &MinidumpModuleList,
         _system_info: &SystemInfo,
+        options: &ProcessorOptions<'_>,
         syms: &P,
-    ) -> Option<StackFrame>
+    ) -> Result<StackFrame, WalkEndReason>
     where
         P: SymbolProvider + Sync,
     {
         let ctx = Mips32Context::try_from(self.clone());
-        let stack = stack_memory.as_ref()?;
+        let stack = stack_memory
+            .as_ref()
+            .ok_or(WalkEndReason::MissingStackMemory)?;
 
         // .await doesn't like closures, so don't use Option chaining
         let mut frame = None;
         if frame.is_none() {
             match &ctx {
                 Ok(mips32) => {
-                    frame =
-                        get_caller_by_cfi(mips32, callee, grand_callee, stack, modules, syms).await
+                    frame = get_caller_by_cfi(
+                        mips32,
+                        callee,
+                        grand_callee,
+                        stack,
+                        modules,
+                        options,
+                        syms,
+                    )
+                    .await
                 }
                 Err(mips64) => {
-                    frame =
-                        get_caller_by_cfi(mips64, callee, grand_callee, stack, modules, syms).await
+                    frame = get_caller_by_cfi(
+                        mips64,
+                        callee,
+                        grand_callee,
+                        stack,
+                        modules,
+                        options,
+                        syms,
+                    )
+                    .await
                 }
             }
         }
         if frame.is_none() {
-            match &ctx {
-                Ok(mips32) => {
-                    frame = get_caller_by_scan32(mips32, callee, stack, modules, syms).await
-                }
-                Err(mips64) => {
-                    frame = get_caller_by_scan64(mips64, callee, stack, modules, syms).await
-                }
+            let valid = &callee.context.valid;
+            let (last_sp, pointer_width) = match &ctx {
+                Ok(mips32) => (
+                    mips32.get_register(STACK_POINTER, valid).map(u64::from),
+                    PointerWidth::Bits32,
+                ),
+                Err(mips64) => (
+                    mips64.get_register(STACK_POINTER, valid),
+                    PointerWidth::Bits64,
+                ),
+            };
+            if let Some(last_sp) = last_sp {
+                frame = get_caller_by_scan(
+                    last_sp,
+                    pointer_width,
+                    callee,
+                    stack,
+                    modules,
+                    options,
+                    syms,
+                )
+                .await;
             }
         }
-        let mut frame = frame?;
+        let mut frame = frame.ok_or_else(|| no_caller_reason(callee, stack))?;
 
         // We now check the frame to see if it looks like unwinding is complete,
         // based on the frame we computed having a nonsense value. Returning
-        // None signals to the unwinder to stop unwinding.
+        // an error signals to the unwinder to stop unwinding.
 
         // if the instruction is within the first ~page of memory, it's basically
         // null, and we can assume unwinding is complete.
         if frame.context.get_instruction_pointer() < 4096 {
             trace!("instruction pointer was nullish, assuming unwind complete");
-            return None;
+            return Err(WalkEndReason::NullReturnAddress);
         }
 
         // If the new stack pointer is at a lower address than the old,
@@ -307,7 +304,7 @@ impl Unwind for MipsContext {
             let is_leaf = callee.trust == FrameTrust::Context && sp == last_sp;
             if !is_leaf {
                 trace!("stack pointer went backwards, assuming unwind complete");
-                return None;
+                return Err(WalkEndReason::StackPointerWentBackwards);
             }
         }
 
@@ -317,7 +314,29 @@ impl Unwind for MipsContext {
         let ip = frame.context.get_instruction_pointer() as u64;
         frame.instruction = ip - 8;
 
-        Some(frame)
+        Ok(frame)
+    }
+
+    fn validate_registers(
+        &self,
+        valid: &MinidumpContextValidity,
+        modules: &MinidumpModuleList,
+    ) -> Vec<String> {
+        let mut problems = Vec::new();
+        // Both the o32 and n64 ABIs keep sp at least 8-byte aligned.
+        if let Some(sp) = self.get_register(STACK_POINTER, valid) {
+            if sp % 8 != 0 {
+                problems.push(format!("{} is not 8-byte aligned", STACK_POINTER));
+            }
+        }
+        // $gp is used to address the global offset table of the current module,
+        // so if we have a value for it, it should point into some module.
+        if let Some(gp) = self.get_register("gp", valid) {
+            if gp != 0 && modules.module_at_address(gp).is_none() {
+                problems.push(String::from("gp doesn't point into any module"));
+            }
+        }
+        problems
     }
 }
 
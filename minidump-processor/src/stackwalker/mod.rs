@@ -20,6 +20,38 @@ use tracing::trace;
 use self::unwind::Unwind;
 use std::collections::HashSet;
 use std::convert::TryFrom;
+use std::time::Instant;
+
+/// The most frames a repeating group of frames can have for the walk to
+/// notice it repeating.
+const MAX_CYCLE_FRAMES: usize = 8;
+/// How many times in a row a group of frames can repeat, each time only
+/// [`MAX_CYCLE_STEP_WORDS`] further up the stack, before the walk is stopped
+/// as going in circles.
+const MAX_CYCLE_REPEATS: usize = 32;
+/// The most pointers' worth of stack a repeating group of frames can move up
+/// each time around for the walk to take it for a cycle.
+///
+/// The walkers never accept a caller whose stack pointer isn't above its
+/// callee's, so a corrupted loop (frame pointers that lead back to the same
+/// return addresses, say) still creeps up the stack, by the same few bytes
+/// every time. Genuine recursion repeats the same frames too, but moves
+/// further than this each time around unless it's a single frame with
+/// nothing but a return address and a saved frame pointer.
+const MAX_CYCLE_STEP_WORDS: u64 = 2;
+
+/// The parts of the minidump beyond the thread's stack that the stack walk
+/// can consult, for the checks [`ProcessorOptions::paranoid_scan`] makes.
+///
+/// Either can be `None` if the minidump doesn't have it, in which case the
+/// checks that need it are skipped.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DumpMemory<'a, 'mdmp> {
+    /// All of the memory the minidump captured.
+    pub memory_list: Option<&'a UnifiedMemoryList<'mdmp>>,
+    /// The process's memory regions and their protections.
+    pub memory_info: Option<&'a UnifiedMemoryInfoList<'mdmp>>,
+}
 
 struct CfiStackWalker<'a, C: CpuContext> {
     instruction: u64,
@@ -32,7 +64,7 @@ struct CfiStackWalker<'a, C: CpuContext> {
     caller_ctx: C,
     caller_validity: HashSet<&'static str>,
 
-    stack_memory: &'a MinidumpMemory<'a>,
+    stack_memory: &'a UnifiedMemory<'a, 'a>,
 }
 
 impl<'a, C> FrameWalker for CfiStackWalker<'a, C>
@@ -85,16 +117,83 @@ where
     }
 }
 
+impl<'a, C> CfiStackWalker<'a, C>
+where
+    C: CpuContext,
+    u64: TryFrom<C::Register>,
+{
+    /// Whether the caller registers CFI evaluation produced look implausible,
+    /// for [`ProcessorOptions::check_cfi_plausibility`].
+    ///
+    /// `frame_pointer` is the architecture's frame pointer register (if it
+    /// has a dedicated one), and `callee_saved_regs` the registers CFI is
+    /// responsible for restoring. The results are suspicious if:
+    ///
+    /// * the caller's stack pointer is below the callee's, as if calling
+    ///   the callee shrunk the stack
+    /// * the caller's frame pointer points into the stack below the caller's
+    ///   stack pointer, where only the callee's frames live
+    /// * a callee-saved register that was nonzero in the callee was restored
+    ///   to zero
+    fn caller_seems_implausible(
+        &self,
+        frame_pointer: Option<&str>,
+        callee_saved_regs: &[&str],
+    ) -> bool {
+        let callee_reg = |reg: &str| {
+            self.callee_ctx
+                .get_register(reg, self.callee_validity)
+                .and_then(|val| u64::try_from(val).ok())
+        };
+        let caller_reg = |reg: &str| {
+            if self.caller_validity.contains(reg) {
+                u64::try_from(self.caller_ctx.get_register_always(reg)).ok()
+            } else {
+                None
+            }
+        };
+
+        let stack_pointer_reg = self.caller_ctx.stack_pointer_register_name();
+        let caller_sp = caller_reg(stack_pointer_reg);
+        if let (Some(caller_sp), Some(callee_sp)) = (caller_sp, callee_reg(stack_pointer_reg)) {
+            if caller_sp < callee_sp {
+                trace!("cfi result is implausible: caller sp is below callee sp");
+                return true;
+            }
+        }
+
+        if let (Some(fp), Some(caller_sp)) = (frame_pointer.and_then(caller_reg), caller_sp) {
+            if fp >= self.stack_memory.base_address() && fp < caller_sp {
+                trace!("cfi result is implausible: caller fp is below caller sp");
+                return true;
+            }
+        }
+
+        let zeroed = callee_saved_regs.iter().find(|&&reg| {
+            matches!(callee_reg(reg), Some(val) if val != 0) && caller_reg(reg) == Some(0)
+        });
+        if let Some(reg) = zeroed {
+            trace!("cfi result is implausible: {} was restored to zero", reg);
+            return true;
+        }
+
+        false
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(name = "unwind_frame", level = "trace", skip_all, fields(idx = _frame_idx, fname = callee_frame.function_name.as_deref().unwrap_or("")))]
 async fn get_caller_frame<P>(
     _frame_idx: usize,
     callee_frame: &StackFrame,
     grand_callee_frame: Option<&StackFrame>,
-    stack_memory: Option<&MinidumpMemory<'_>>,
+    stack_memory: Option<&UnifiedMemory<'_, '_>>,
+    dump_memory: &DumpMemory<'_, '_>,
     modules: &MinidumpModuleList,
     system_info: &SystemInfo,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
-) -> Option<StackFrame>
+) -> Result<StackFrame, WalkEndReason>
 where
     P: SymbolProvider + Sync,
 {
@@ -109,8 +208,10 @@ where
                 callee_frame,
                 grand_callee_frame,
                 stack_memory,
+                dump_memory,
                 modules,
                 system_info,
+                options,
                 symbol_provider,
             )
             .await
@@ -120,8 +221,10 @@ where
                 callee_frame,
                 grand_callee_frame,
                 stack_memory,
+                dump_memory,
                 modules,
                 system_info,
+                options,
                 symbol_provider,
             )
             .await
@@ -131,8 +234,10 @@ where
                 callee_frame,
                 grand_callee_frame,
                 stack_memory,
+                dump_memory,
                 modules,
                 system_info,
+                options,
                 symbol_provider,
             )
             .await
@@ -142,8 +247,10 @@ where
                 callee_frame,
                 grand_callee_frame,
                 stack_memory,
+                dump_memory,
                 modules,
                 system_info,
+                options,
                 symbol_provider,
             )
             .await
@@ -153,8 +260,10 @@ where
                 callee_frame,
                 grand_callee_frame,
                 stack_memory,
+                dump_memory,
                 modules,
                 system_info,
+                options,
                 symbol_provider,
             )
             .await
@@ -164,16 +273,252 @@ where
                 callee_frame,
                 grand_callee_frame,
                 stack_memory,
+                dump_memory,
                 modules,
                 system_info,
+                options,
                 symbol_provider,
             )
             .await
         }
+        _ => Err(WalkEndReason::UnsupportedCpu),
+    }
+}
+
+/// Why none of the ways of finding `callee`'s caller in `stack_memory` worked.
+///
+/// Stack scanning gives up when it reaches the end of the stack memory, so if
+/// the memory ends within the range the callee's stack would be scanned, it
+/// was probably running out of memory that stopped the walk.
+pub(crate) fn no_caller_reason(
+    callee: &StackFrame,
+    stack_memory: &UnifiedMemory<'_, '_>,
+) -> WalkEndReason {
+    // These match the scan ranges the walkers use.
+    let scan_words = if callee.trust == FrameTrust::Context {
+        160
+    } else {
+        40
+    };
+    let pointer_width = match callee.context.raw {
+        MinidumpRawContext::Amd64(_)
+        | MinidumpRawContext::Arm64(_)
+        | MinidumpRawContext::OldArm64(_)
+        | MinidumpRawContext::Ppc64(_) => 8,
+        _ => 4,
+    };
+    let end = stack_memory
+        .base_address()
+        .saturating_add(stack_memory.size());
+    let sp = callee.context.get_stack_pointer();
+    if sp.saturating_add(scan_words * pointer_width) > end {
+        WalkEndReason::StackMemoryExhausted { address: end }
+    } else {
+        WalkEndReason::NoCallerFound
+    }
+}
+
+/// Run the architecture-specific register checks on a freshly unwound frame,
+/// recording any problems in the frame's `warnings`.
+fn validate_caller_frame(frame: &mut StackFrame, modules: &MinidumpModuleList) {
+    let valid = &frame.context.valid;
+    let problems = match frame.context.raw {
+        MinidumpRawContext::Arm(ref ctx) => ctx.validate_registers(valid, modules),
+        MinidumpRawContext::Arm64(ref ctx) => ctx.validate_registers(valid, modules),
+        MinidumpRawContext::OldArm64(ref ctx) => ctx.validate_registers(valid, modules),
+        MinidumpRawContext::Amd64(ref ctx) => ctx.validate_registers(valid, modules),
+        MinidumpRawContext::X86(ref ctx) => ctx.validate_registers(valid, modules),
+        MinidumpRawContext::Mips(ref ctx) => ctx.validate_registers(valid, modules),
+        _ => Vec::new(),
+    };
+    for problem in &problems {
+        trace!("caller frame failed register validation: {}", problem);
+    }
+    frame.warnings.extend(problems);
+}
+
+/// Compare a frame recovered by CFI against the one frame pointers recover,
+/// for [`ProcessorOptions::cross_validate_cfi`].
+///
+/// If they agree on the caller's instruction and stack pointers, a
+/// low-confidence CFI frame is restored to full confidence. If they
+/// disagree, the CFI result is kept (as breakpad would) but the
+/// disagreement is recorded in the frame's `warnings`.
+pub(crate) fn cross_validate_cfi(cfi_frame: &mut StackFrame, fp_frame: Option<&StackFrame>) {
+    let fp_frame = match fp_frame {
+        Some(fp_frame) => fp_frame,
+        None => return,
+    };
+    let cfi_ip = cfi_frame.context.get_instruction_pointer();
+    let cfi_sp = cfi_frame.context.get_stack_pointer();
+    let fp_ip = fp_frame.context.get_instruction_pointer();
+    let fp_sp = fp_frame.context.get_stack_pointer();
+
+    if cfi_ip == fp_ip && cfi_sp == fp_sp {
+        trace!("frame pointer agrees with cfi");
+        if cfi_frame.trust == FrameTrust::CallFrameInfoLowConfidence {
+            cfi_frame.trust = FrameTrust::CallFrameInfo;
+        }
+    } else {
+        let problem = format!(
+            "frame pointer disagrees with cfi (pc {:#x} vs {:#x}, sp {:#x} vs {:#x})",
+            fp_ip, cfi_ip, fp_sp, cfi_sp
+        );
+        trace!("{}", problem);
+        cfi_frame.warnings.push(problem);
+    }
+}
+
+/// Whether a return address found at `address_of_ip` is consistent with the
+/// callee's frame pointer, for [`ProcessorOptions::paranoid_scan`] on x86
+/// and x86-64.
+///
+/// Either the callee pushed the caller's bp right below the return address
+/// (so its own bp points at that slot), or it left bp alone (so bp still
+/// points into the caller's frame, above the return address). Any other
+/// valid bp suggests we found a stale return address from some older frame.
+fn frame_pointer_seems_coherent(
+    last_bp: Option<u64>,
+    address_of_ip: u64,
+    pointer_width: u64,
+) -> bool {
+    match last_bp {
+        Some(bp) => bp == address_of_ip.wrapping_sub(pointer_width) || bp > address_of_ip,
+        None => true,
+    }
+}
+
+/// Whether a scanned return address could really be one, for
+/// [`ProcessorOptions::paranoid_scan`] on x86 and x86-64.
+///
+/// The address must be in memory the process could execute, right after an
+/// instruction that could have made the call (see [`follows_call`]). Either
+/// check passes if the minidump doesn't have what it needs (the memory info,
+/// or the code before the address).
+fn x86_return_address_seems_valid(
+    context: &MinidumpContext,
+    return_address: u64,
+    dump_memory: &DumpMemory<'_, '_>,
+) -> bool {
+    if let Some(memory_info) = dump_memory.memory_info {
+        let executable = matches!(
+            memory_info.memory_info_at_address(return_address),
+            Some(region) if region.is_executable()
+        );
+        if !executable {
+            trace!("return address 0x{:016x} isn't executable", return_address);
+            return false;
+        }
+    }
+    if let Some(memory_list) = dump_memory.memory_list {
+        if follows_call(context, return_address, memory_list) == Some(false) {
+            trace!(
+                "return address 0x{:016x} doesn't follow a call",
+                return_address
+            );
+            return false;
+        }
+    }
+    true
+}
+
+/// Whether the instruction right before `return_address` is a call, for
+/// [`ProcessorOptions::paranoid_scan`] and
+/// [`RopChainAnalysis`][crate::RopChainAnalysis].
+///
+/// Returns `None` if the code isn't all in `memory_list` (and what is doesn't
+/// end in a call), or we don't know how to decode calls for `context`'s CPU.
+/// Only x86, x86-64 and ARM64 are supported.
+pub(crate) fn follows_call(
+    context: &MinidumpContext,
+    return_address: u64,
+    memory_list: &UnifiedMemoryList<'_>,
+) -> Option<bool> {
+    match context.raw {
+        MinidumpRawContext::X86(_) | MinidumpRawContext::Amd64(_) => {
+            follows_x86_call(return_address, memory_list)
+        }
+        MinidumpRawContext::Arm64(_) | MinidumpRawContext::OldArm64(_) => {
+            let address = return_address.checked_sub(4)?;
+            let insn: u32 = memory_list
+                .memory_at_address(address)?
+                .get_memory_at_address(address)?;
+            // BL imm26, or BLR Xn.
+            Some(insn & 0xFC00_0000 == 0x9400_0000 || insn & 0xFFFF_FC1F == 0xD63F_0000)
+        }
         _ => None,
     }
 }
 
+/// [`follows_call`] for x86 and x86-64.
+///
+/// This recognizes `call rel32` and every form of `call r/m` (near or far),
+/// which covers all the calls compilers emit. A REX prefix makes no
+/// difference, as it comes before the opcode.
+fn follows_x86_call(return_address: u64, memory_list: &UnifiedMemoryList<'_>) -> Option<bool> {
+    let byte_before = |distance: u64| {
+        let address = return_address.checked_sub(distance)?;
+        memory_list
+            .memory_at_address(address)?
+            .get_memory_at_address::<u8>(address)
+    };
+    let mut missing_code = false;
+
+    // call rel32: e8 followed by a 4-byte displacement.
+    match byte_before(5) {
+        Some(0xe8) => return Some(true),
+        Some(_) => {}
+        None => missing_code = true,
+    }
+
+    // call r/m: ff /2 (or ff /3, a far call) followed by a ModRM byte, an
+    // optional SIB byte, and an optional displacement.
+    for len in 2..=7 {
+        match (byte_before(len), byte_before(len - 1)) {
+            (Some(0xff), Some(modrm)) if matches!((modrm >> 3) & 7, 2 | 3) => {
+                let sib = if len > 2 { byte_before(len - 2) } else { None };
+                if x86_modrm_instruction_len(modrm, sib) == Some(len) {
+                    return Some(true);
+                }
+            }
+            (Some(_), Some(_)) => {}
+            _ => missing_code = true,
+        }
+    }
+
+    (!missing_code).then_some(false)
+}
+
+/// How long an instruction with a one-byte opcode and the given ModRM byte
+/// (and SIB byte, if there is one) is, in bytes, ignoring prefixes and
+/// immediates.
+fn x86_modrm_instruction_len(modrm: u8, sib: Option<u8>) -> Option<u64> {
+    let mode = modrm >> 6;
+    let rm = modrm & 7;
+    let len = match (mode, rm) {
+        // A register.
+        (0b11, _) => 2,
+        // [base + index * scale], where a base of 0b101 means a disp32.
+        (0b00, 0b100) => {
+            if sib? & 7 == 0b101 {
+                7
+            } else {
+                3
+            }
+        }
+        // [disp32], or [rip + disp32] on x86-64.
+        (0b00, 0b101) => 6,
+        (0b00, _) => 2,
+        // With a disp8.
+        (0b01, 0b100) => 4,
+        (0b01, _) => 3,
+        // With a disp32.
+        (_, 0b100) => 7,
+        (_, _) => 6,
+    };
+    Some(len)
+}
+
 async fn fill_source_line_info<P>(
     frame: &mut StackFrame,
     modules: &MinidumpModuleList,
@@ -198,12 +543,28 @@ async fn fill_source_line_info<P>(
     }
 }
 
+/// Symbolicate frames that were provided by something other than our stack
+/// walker (see [`FrameTrust::PreWalked`]).
+pub async fn fill_prewalked_frames<P>(
+    frames: &mut [StackFrame],
+    modules: &MinidumpModuleList,
+    symbol_provider: &P,
+) where
+    P: SymbolProvider + Sync,
+{
+    for frame in frames {
+        fill_source_line_info(frame, modules, symbol_provider).await;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(name = "unwind_thread", level = "trace", skip_all, fields(idx = thread_idx, tid = stack.thread_id, tname = stack.thread_name.as_deref().unwrap_or("")))]
 pub async fn walk_stack<P>(
     thread_idx: usize,
     options: &ProcessorOptions<'_>,
     stack: &mut CallStack,
-    stack_memory: Option<&MinidumpMemory<'_>>,
+    stack_memory: Option<&UnifiedMemory<'_, '_>>,
+    dump_memory: &DumpMemory<'_, '_>,
     modules: &MinidumpModuleList,
     system_info: &SystemInfo,
     symbol_provider: &P,
@@ -215,6 +576,23 @@ pub async fn walk_stack<P>(
         stack.thread_id,
         stack.thread_name.as_deref().unwrap_or(""),
     );
+    let stack_bottom = stack
+        .stack_range
+        .as_ref()
+        .filter(|_| options.stop_at_stack_bottom)
+        .map(|range| range.end);
+
+    let deadline = options
+        .frame_walk_timeout
+        .and_then(|timeout| Instant::now().checked_add(timeout));
+
+    // For each group length, how many frames in a row (at the end of the
+    // stack) have the same instruction as the frame that many frames before,
+    // and how far up the stack from it they are.
+    let mut cycle_runs = [CycleRun::default(); MAX_CYCLE_FRAMES];
+    let max_cycle_step = u64::from(system_info.cpu.pointer_width().size_in_bytes().unwrap_or(8))
+        * MAX_CYCLE_STEP_WORDS;
+
     // Begin with the context frame, and keep getting callers until there are no more.
     let mut has_new_frame = !stack.frames.is_empty();
     while has_new_frame {
@@ -224,12 +602,23 @@ pub async fn walk_stack<P>(
 
         fill_source_line_info(frame, modules, symbol_provider).await;
 
+        // Let the caller have a look at it
+        if let Some(analyzer) = options.frame_analyzer {
+            let (callees, frame) = stack.frames.split_at_mut(frame_idx);
+            let frame = &mut frame[0];
+            let annotations = analyzer
+                .analyze_frame(frame, callees.last(), stack_memory, modules)
+                .await;
+            frame.annotations.extend(annotations);
+        }
+        let frame = &stack.frames[frame_idx];
+
         // Report the frame as walked and symbolicated
         if let Some(reporter) = options.stat_reporter {
             reporter.add_walked_frame(thread_idx, frame_idx, frame);
         }
 
-        // Walk the new frame
+        // Walk the new frame, unless we've run out of time
         let callee_frame = &stack.frames.last().unwrap();
         let grand_callee_frame = stack
             .frames
@@ -240,21 +629,108 @@ pub async fn walk_stack<P>(
             Some(name) => trace!("unwinding {}", name),
             None => trace!("unwinding 0x{:016x}", callee_frame.instruction),
         }
-        let new_frame = get_caller_frame(
-            frame_idx,
-            callee_frame,
-            grand_callee_frame,
-            stack_memory,
-            modules,
-            system_info,
-            symbol_provider,
-        )
-        .await;
-
-        // Check if we're done
-        if let Some(new_frame) = new_frame {
-            stack.frames.push(new_frame);
+        let new_frame = if matches!(deadline, Some(deadline) if Instant::now() >= deadline) {
+            trace!("stopping unwind: ran out of time");
+            Err(WalkEndReason::WalkTimedOut)
         } else {
+            get_caller_frame(
+                frame_idx,
+                callee_frame,
+                grand_callee_frame,
+                stack_memory,
+                dump_memory,
+                modules,
+                system_info,
+                options,
+                symbol_provider,
+            )
+            .await
+        };
+
+        // Stay on this thread's stack if we were asked to.
+        let new_frame = new_frame.and_then(|frame| {
+            if !options.constrain_to_thread_stack || frame_is_on_stack(&frame, stack_memory) {
+                Ok(frame)
+            } else {
+                Err(WalkEndReason::LeftThreadStack)
+            }
+        });
+
+        // No caller can be past the bottom of the stack (but one can be right at it).
+        let new_frame = new_frame.and_then(|frame| match stack_bottom {
+            Some(bottom) if frame.context.get_stack_pointer() > bottom => {
+                trace!("stopping unwind: caller sp is past the bottom of the stack");
+                Err(WalkEndReason::ReachedStackBottom)
+            }
+            _ => Ok(frame),
+        });
+
+        // Give up on runaway walks.
+        let new_frame = new_frame.and_then(|frame| {
+            if stack.frames.len() >= options.max_frames_per_thread.max(1) {
+                trace!("stopping unwind: reached the maximum number of frames");
+                Err(WalkEndReason::MaxFramesReached)
+            } else {
+                Ok(frame)
+            }
+        });
+
+        // Don't scan our way through noise forever.
+        let new_frame = new_frame.and_then(|frame| {
+            let is_scan =
+                |frame: &StackFrame| matches!(frame.trust, FrameTrust::Scan | FrameTrust::CfiScan);
+            if !is_scan(&frame) {
+                return Ok(frame);
+            }
+            let scanned = stack.frames.iter().rev().take_while(|frame| is_scan(frame));
+            if scanned.count() >= options.max_consecutive_scan_frames {
+                trace!("stopping unwind: too many scanned frames in a row");
+                Err(WalkEndReason::ScanLimitReached)
+            } else {
+                Ok(frame)
+            }
+        });
+
+        // Keep the new frame, and check if we're done
+        let end_reason = match new_frame {
+            Ok(mut new_frame) => {
+                if options.validate_registers {
+                    validate_caller_frame(&mut new_frame, modules);
+                }
+                let at_stack_bottom = stack_bottom == Some(new_frame.context.get_stack_pointer());
+                stack.frames.push(new_frame);
+
+                // Stop if we're going in circles, keeping one copy of the circle.
+                let cycle = options
+                    .stop_on_unwind_cycles
+                    .then(|| find_cycle(&stack.frames, &mut cycle_runs, max_cycle_step))
+                    .flatten();
+                if at_stack_bottom {
+                    // Nothing can call the frame at the very bottom of the stack.
+                    trace!("stopping unwind: caller sp is at the bottom of the stack");
+                    Some(WalkEndReason::ReachedStackBottom)
+                } else if let Some(frame_count) = cycle {
+                    trace!(
+                        "stopping unwind: the last {} frames repeated {} times",
+                        frame_count,
+                        MAX_CYCLE_REPEATS
+                    );
+                    let len = stack.frames.len() - frame_count * (MAX_CYCLE_REPEATS - 1);
+                    stack.frames.truncate(len);
+                    stack.repeated_frames = Some(RepeatedFrames {
+                        first_frame: len - frame_count,
+                        frame_count,
+                        repeated: MAX_CYCLE_REPEATS,
+                    });
+                    Some(WalkEndReason::CycleDetected)
+                } else {
+                    None
+                }
+            }
+            Err(reason) => Some(reason),
+        };
+        if let Some(reason) = end_reason {
+            stack.walk_end_reason = Some(reason);
             has_new_frame = false;
         }
     }
@@ -265,11 +741,82 @@ pub async fn walk_stack<P>(
     );
 }
 
+/// A run of frames repeating the frames before them, for [`find_cycle`].
+#[derive(Clone, Copy, Debug, Default)]
+struct CycleRun {
+    /// How many frames in a row (at the end of the stack) repeat.
+    frames: usize,
+    /// How far up the stack each of them is from the frame it repeats.
+    step: u64,
+}
+
+/// Check whether the last frame of `frames` (just walked) completes
+/// [`MAX_CYCLE_REPEATS`] repetitions of a group of frames, returning how many
+/// frames are in the group if so.
+///
+/// `runs` tracks, for each group length, how many frames in a row at the end
+/// of `frames` repeat the frame that many frames before them: they have the
+/// same instruction, and a stack pointer the same distance further up the
+/// stack, which is at most `max_step`. It's updated for the new frame.
+fn find_cycle(
+    frames: &[StackFrame],
+    runs: &mut [CycleRun; MAX_CYCLE_FRAMES],
+    max_step: u64,
+) -> Option<usize> {
+    let last = frames.len() - 1;
+    let sp = |idx: usize| frames[idx].context.get_stack_pointer();
+    for (i, run) in runs.iter_mut().enumerate() {
+        let frame_count = i + 1;
+        let step = match last.checked_sub(frame_count) {
+            Some(prev) if frames[prev].instruction == frames[last].instruction => sp(last)
+                .checked_sub(sp(prev))
+                .filter(|&step| step <= max_step),
+            _ => None,
+        };
+        *run = match step {
+            Some(step) if run.frames > 0 && run.step == step => CycleRun {
+                frames: run.frames + 1,
+                step,
+            },
+            Some(step) => CycleRun { frames: 1, step },
+            None => CycleRun::default(),
+        };
+    }
+    // The shortest group is the one that's actually repeating: a group of
+    // one frame repeating also looks like a group of two repeating.
+    (1..=MAX_CYCLE_FRAMES)
+        .find(|&frame_count| runs[frame_count - 1].frames >= frame_count * (MAX_CYCLE_REPEATS - 1))
+}
+
+/// Whether `frame`'s stack pointer is in `stack_memory`, for
+/// [`ProcessorOptions::constrain_to_thread_stack`].
+///
+/// The stack pointer is allowed to be one past the end of the stack, as the
+/// outermost frame's may be.
+fn frame_is_on_stack(frame: &StackFrame, stack_memory: Option<&UnifiedMemory<'_, '_>>) -> bool {
+    let stack_memory = match stack_memory {
+        Some(stack_memory) => stack_memory,
+        None => return true,
+    };
+    let sp = frame.context.get_stack_pointer();
+    let base = stack_memory.base_address();
+    if sp >= base && sp - base <= stack_memory.size() {
+        true
+    } else {
+        trace!(
+            "stopping unwind: caller sp 0x{:016x} left the thread's stack",
+            sp
+        );
+        false
+    }
+}
+
 /// Checks if we can dismiss the validity of an instruction based on our symbols,
 /// to refine the quality of each unwinder's instruction_seems_valid implementation.
 async fn instruction_seems_valid_by_symbols<P>(
     instruction: u64,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> bool
 where
@@ -334,8 +881,8 @@ where
             // If the symbol provider returns an Error, this means that we
             // didn't have any symbols for the *module*. Just assume the
             // instruction is valid in this case so that scanning works
-            // when we have no symbols.
-            true
+            // when we have no symbols (unless we've been asked to be paranoid).
+            !options.paranoid_scan
         }
     } else {
         // We couldn't even map this address to a module. Reject the pointer
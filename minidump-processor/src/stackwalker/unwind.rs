@@ -1,23 +1,39 @@
 // Copyright 2015 Ted Mielczarek. See the COPYRIGHT
 // file at the top-level directory of this distribution.
 
-use crate::process_state::StackFrame;
-use crate::{SymbolProvider, SystemInfo};
-use minidump::{MinidumpMemory, MinidumpModuleList};
+use crate::process_state::{StackFrame, WalkEndReason};
+use crate::stackwalker::DumpMemory;
+use crate::{ProcessorOptions, SymbolProvider, SystemInfo};
+use minidump::{MinidumpContextValidity, MinidumpModuleList, UnifiedMemory};
 
 /// A trait for things that can unwind to a caller.
 #[async_trait::async_trait]
 pub trait Unwind {
-    /// Get the caller frame of this frame.
+    /// Get the caller frame of this frame, or why there isn't one.
+    #[allow(clippy::too_many_arguments)]
     async fn get_caller_frame<P>(
         &self,
         callee: &StackFrame,
         grand_callee: Option<&StackFrame>,
-        stack_memory: Option<&MinidumpMemory<'_>>,
+        stack_memory: Option<&UnifiedMemory<'_, '_>>,
+        dump_memory: &DumpMemory<'_, '_>,
         modules: &MinidumpModuleList,
         system_info: &SystemInfo,
+        options: &ProcessorOptions<'_>,
         symbol_provider: &P,
-    ) -> Option<StackFrame>
+    ) -> Result<StackFrame, WalkEndReason>
     where
         P: SymbolProvider + Sync;
+
+    /// Check architecture-specific invariants on the registers of a caller
+    /// context recovered by `get_caller_frame`, describing each one that
+    /// doesn't hold.
+    ///
+    /// Unlike the checks `get_caller_frame` performs, a violation here
+    /// doesn't end the walk, it just makes the frame more suspicious.
+    fn validate_registers(
+        &self,
+        valid: &MinidumpContextValidity,
+        modules: &MinidumpModuleList,
+    ) -> Vec<String>;
 }
@@ -6,14 +6,17 @@
 // so that it can be copied between the two with minimal changes. It's not
 // worth the effort to *actually* unify the implementations.
 
-use crate::process_state::{FrameTrust, StackFrame};
+use crate::process_state::{FrameTrust, StackFrame, WalkEndReason};
 use crate::stackwalker::unwind::Unwind;
-use crate::stackwalker::CfiStackWalker;
-use crate::{SymbolProvider, SystemInfo};
+use crate::stackwalker::{
+    cross_validate_cfi, frame_pointer_seems_coherent, no_caller_reason,
+    x86_return_address_seems_valid, CfiStackWalker, DumpMemory,
+};
+use crate::{ProcessorOptions, SymbolProvider, SystemInfo};
 use minidump::format::CONTEXT_X86;
 use minidump::{
-    MinidumpContext, MinidumpContextValidity, MinidumpMemory, MinidumpModuleList,
-    MinidumpRawContext,
+    CpuContext, MinidumpContext, MinidumpContextValidity, MinidumpModuleList, MinidumpRawContext,
+    UnifiedMemory,
 };
 use std::collections::HashSet;
 use tracing::trace;
@@ -29,8 +32,9 @@ async fn get_caller_by_cfi<P>(
     ctx: &CONTEXT_X86,
     callee: &StackFrame,
     grand_callee: Option<&StackFrame>,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> Option<StackFrame>
 where
@@ -70,6 +74,13 @@ where
     symbol_provider
         .walk_frame(module, &mut stack_walker)
         .await?;
+    let trust = if options.check_cfi_plausibility
+        && stack_walker.caller_seems_implausible(Some(FRAME_POINTER_REGISTER), CALLEE_SAVED_REGS)
+    {
+        FrameTrust::CallFrameInfoLowConfidence
+    } else {
+        FrameTrust::CallFrameInfo
+    };
     let caller_ip = stack_walker.caller_ctx.eip;
     let caller_sp = stack_walker.caller_ctx.esp;
 
@@ -103,7 +114,7 @@ where
         raw: MinidumpRawContext::X86(stack_walker.caller_ctx),
         valid: MinidumpContextValidity::Some(stack_walker.caller_validity),
     };
-    Some(StackFrame::from_context(context, FrameTrust::CallFrameInfo))
+    Some(StackFrame::from_context(context, trust))
 }
 
 fn callee_forwarded_regs(valid: &MinidumpContextValidity) -> HashSet<&'static str> {
@@ -120,7 +131,7 @@ fn callee_forwarded_regs(valid: &MinidumpContextValidity) -> HashSet<&'static st
 fn get_caller_by_frame_pointer<P>(
     ctx: &CONTEXT_X86,
     callee: &StackFrame,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
     _modules: &MinidumpModuleList,
     _symbol_provider: &P,
 ) -> Option<StackFrame>
@@ -200,8 +211,10 @@ where
 async fn get_caller_by_scan<P>(
     ctx: &CONTEXT_X86,
     callee: &StackFrame,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
+    dump_memory: &DumpMemory<'_, '_>,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> Option<StackFrame>
 where
@@ -245,7 +258,18 @@ where
     for i in 0..scan_range {
         let address_of_ip = last_sp.checked_add(i * POINTER_WIDTH)?;
         let caller_ip = stack_memory.get_memory_at_address(address_of_ip as u64)?;
-        if instruction_seems_valid(caller_ip, modules, symbol_provider).await {
+        if instruction_seems_valid(caller_ip, modules, options, symbol_provider).await
+            && (!options.paranoid_scan
+                || (frame_pointer_seems_coherent(
+                    last_bp.map(u64::from),
+                    address_of_ip as u64,
+                    POINTER_WIDTH as u64,
+                ) && x86_return_address_seems_valid(
+                    &callee.context,
+                    caller_ip as u64,
+                    dump_memory,
+                )))
+        {
             // ip is pushed by CALL, so sp is just address_of_ip + ptr
             let caller_sp = address_of_ip.checked_add(POINTER_WIDTH)?;
 
@@ -354,6 +378,7 @@ where
 async fn instruction_seems_valid<P>(
     instruction: Pointer,
     modules: &MinidumpModuleList,
+    options: &ProcessorOptions<'_>,
     symbol_provider: &P,
 ) -> bool
 where
@@ -363,7 +388,8 @@ where
         return false;
     }
 
-    super::instruction_seems_valid_by_symbols(instruction as u64, modules, symbol_provider).await
+    super::instruction_seems_valid_by_symbols(instruction as u64, modules, options, symbol_provider)
+        .await
 }
 
 /*
@@ -372,7 +398,7 @@ where
 fn stack_seems_valid(
     caller_sp: Pointer,
     callee_sp: Pointer,
-    stack_memory: &MinidumpMemory<'_>,
+    stack_memory: &UnifiedMemory<'_, '_>,
 ) -> bool {
     // The stack shouldn't *grow* when we unwind
     if caller_sp <= callee_sp {
@@ -392,45 +418,57 @@ impl Unwind for CONTEXT_X86 {
         &self,
         callee: &StackFrame,
         grand_callee: Option<&StackFrame>,
-        stack_memory: Option<&MinidumpMemory<'_>>,
+        stack_memory: Option<&UnifiedMemory<'_, '_>>,
+        dump_memory: &DumpMemory<'_, '_>,
         modules: &MinidumpModuleList,
         _system_info: &SystemInfo,
+        options: &ProcessorOptions<'_>,
         syms: &P,
-    ) -> Option<StackFrame>
+    ) -> Result<StackFrame, WalkEndReason>
     where
         P: SymbolProvider + Sync,
     {
-        let stack = stack_memory.as_ref()?;
+        let stack = stack_memory
+            .as_ref()
+            .ok_or(WalkEndReason::MissingStackMemory)?;
 
         // .await doesn't like closures, so don't use Option chaining
         let mut frame = None;
         if frame.is_none() {
-            frame = get_caller_by_cfi(self, callee, grand_callee, stack, modules, syms).await;
+            frame =
+                get_caller_by_cfi(self, callee, grand_callee, stack, modules, options, syms).await;
+        }
+        if options.cross_validate_cfi {
+            if let Some(cfi_frame) = frame.as_mut() {
+                let fp_frame = get_caller_by_frame_pointer(self, callee, stack, modules, syms);
+                cross_validate_cfi(cfi_frame, fp_frame.as_ref());
+            }
         }
         if frame.is_none() {
             frame = get_caller_by_frame_pointer(self, callee, stack, modules, syms);
         }
         if frame.is_none() {
-            frame = get_caller_by_scan(self, callee, stack, modules, syms).await;
+            frame =
+                get_caller_by_scan(self, callee, stack, dump_memory, modules, options, syms).await;
         }
-        let mut frame = frame?;
+        let mut frame = frame.ok_or_else(|| no_caller_reason(callee, stack))?;
 
         // We now check the frame to see if it looks like unwinding is complete,
         // based on the frame we computed having a nonsense value. Returning
-        // None signals to the unwinder to stop unwinding.
+        // an error signals to the unwinder to stop unwinding.
 
         // if the instruction is within the first ~page of memory, it's basically
         // null, and we can assume unwinding is complete.
         if frame.context.get_instruction_pointer() < 4096 {
             trace!("instruction pointer was nullish, assuming unwind complete");
-            return None;
+            return Err(WalkEndReason::NullReturnAddress);
         }
         // If the new stack pointer is at a lower address than the old,
         // then that's clearly incorrect. Treat this as end-of-stack to
         // enforce progress and avoid infinite loops.
         if frame.context.get_stack_pointer() <= self.esp as u64 {
             trace!("stack pointer went backwards, assuming unwind complete");
-            return None;
+            return Err(WalkEndReason::StackPointerWentBackwards);
         }
 
         // Ok, the frame now seems well and truly valid, do final cleanup.
@@ -443,6 +481,20 @@ impl Unwind for CONTEXT_X86 {
         let ip = frame.context.get_instruction_pointer() as u64;
         frame.instruction = ip - 1;
 
-        Some(frame)
+        Ok(frame)
+    }
+
+    fn validate_registers(
+        &self,
+        valid: &MinidumpContextValidity,
+        _modules: &MinidumpModuleList,
+    ) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Some(sp) = self.get_register(STACK_POINTER_REGISTER, valid) {
+            if sp % POINTER_WIDTH != 0 {
+                problems.push(format!("{} is not 4-byte aligned", STACK_POINTER_REGISTER));
+            }
+        }
+        problems
     }
 }
@@ -1,7 +1,7 @@
 // Copyright 2015 Ted Mielczarek. See the COPYRIGHT
 // file at the top-level directory of this distribution.
 
-use crate::stackwalker::walk_stack;
+use crate::stackwalker::{walk_stack, DumpMemory};
 use crate::{process_state::*, ProcessorOptions};
 use crate::{string_symbol_supplier, Symbolizer, SystemInfo};
 use minidump::format::CONTEXT_X86;
@@ -50,9 +50,15 @@ impl TestFixture {
             os_version: None,
             os_build: None,
             cpu: Cpu::X86,
+            endian: scroll::LE,
             cpu_info: None,
             cpu_microcode_version: None,
+            cpu_features: None,
+            cpu_microarchitecture: None,
+            cpu_description: None,
             cpu_count: 1,
+            available_physical_memory: None,
+            commit_limit: None,
         };
         let symbolizer = Symbolizer::new(string_symbol_supplier(self.symbols.clone()));
         let options = ProcessorOptions::default();
@@ -62,7 +68,8 @@ impl TestFixture {
             0,
             &options,
             &mut stack,
-            Some(&stack_memory),
+            Some(&UnifiedMemory::Memory(&stack_memory)),
+            &DumpMemory::default(),
             &self.modules,
             &system_info,
             &symbolizer,
@@ -116,6 +123,7 @@ async fn test_traditional() {
     f.raw.ebp = frame0_ebp.value().unwrap() as u32;
     let s = f.walk_stack(stack).await;
     assert_eq!(s.frames.len(), 2);
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::NullReturnAddress));
     {
         let f0 = &s.frames[0];
         assert_eq!(f0.trust, FrameTrust::Context);
@@ -978,3 +986,112 @@ async fn test_frame_pointer_barely_no_overflow() {
         }
     }
 }
+
+// A frame pointer chain that goes through the same two return addresses,
+// moving further up the stack each time, looks just like recursion, so all
+// of it should be walked.
+#[tokio::test]
+async fn test_frame_pointer_recursion() {
+    let mut f = TestFixture::new();
+    let frame_ebps = (0..100).map(|_| Label::new()).collect::<Vec<_>>();
+    let mut stack = Section::new();
+    stack.start().set_const(0x80000000);
+    for (i, ebp) in frame_ebps.iter().enumerate() {
+        stack = stack.mark(ebp);
+        stack = match frame_ebps.get(i + 1) {
+            Some(caller_ebp) => stack
+                .D32(caller_ebp) // saved %ebp
+                .D32(if i % 2 == 0 { 0x40001001 } else { 0x40002001 }), // return address
+            None => stack.D32(0).D32(0), // stack end
+        };
+    }
+    f.raw.eip = 0x4000c7a5;
+    f.raw.esp = stack.start().value().unwrap() as u32;
+    f.raw.ebp = frame_ebps[0].value().unwrap() as u32;
+    let s = f.walk_stack(stack).await;
+
+    assert_eq!(s.info, CallStackInfo::Ok);
+    assert_eq!(s.repeated_frames, None);
+    assert_eq!(s.frames.len(), 100);
+    assert_eq!(s.frames[98].instruction, 0x40002000);
+    assert_eq!(s.frames[99].instruction, 0x40001000);
+}
+
+// A frame pointer chain that keeps coming back to the same return address,
+// only creeping up the stack by a saved %ebp and a return address each time,
+// is going in circles, and only one copy of the frame should be kept.
+#[tokio::test]
+async fn test_frame_pointer_cycle() {
+    let make_stack = |padding: usize| {
+        let frame_ebps = (0..100).map(|_| Label::new()).collect::<Vec<_>>();
+        let mut stack = Section::new();
+        stack.start().set_const(0x80000000);
+        for (i, ebp) in frame_ebps.iter().enumerate() {
+            stack = stack.mark(ebp);
+            stack = match frame_ebps.get(i + 1) {
+                Some(caller_ebp) => stack
+                    .D32(caller_ebp) // saved %ebp
+                    .D32(0x40001001) // return address
+                    .append_repeated(0, padding),
+                None => stack.D32(0).D32(0), // stack end
+            };
+        }
+        (stack, frame_ebps[0].value().unwrap() as u32)
+    };
+
+    let mut f = TestFixture::new();
+    f.raw.eip = 0x4000c7a5;
+    f.raw.esp = 0x80000000;
+    let (stack, ebp) = make_stack(0);
+    f.raw.ebp = ebp;
+    let s = f.walk_stack(stack).await;
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::CycleDetected));
+    assert_eq!(s.frames.len(), 2);
+    assert_eq!(s.frames[1].instruction, 0x40001000);
+    assert_eq!(
+        s.repeated_frames,
+        Some(RepeatedFrames {
+            first_frame: 1,
+            frame_count: 1,
+            repeated: 32,
+        })
+    );
+
+    // Moving any further up the stack each time could be recursion.
+    let (stack, ebp) = make_stack(4);
+    f.raw.ebp = ebp;
+    let s = f.walk_stack(stack).await;
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::NullReturnAddress));
+    assert_eq!(s.repeated_frames, None);
+    assert_eq!(s.frames.len(), 100);
+}
+
+// A stack with nothing that looks like a return address should say whether
+// the walk ran out of stack memory or just didn't find anything.
+#[tokio::test]
+async fn test_walk_end_reason_no_caller() {
+    let mut f = TestFixture::new();
+    f.raw.eip = 0x4000c7a5;
+    f.raw.esp = 0x80000000;
+
+    // Scanning runs off the end of this one.
+    let mut stack = Section::new();
+    stack.start().set_const(0x80000000);
+    stack = stack.append_repeated(0, 16);
+    let s = f.walk_stack(stack).await;
+    assert_eq!(s.frames.len(), 1);
+    assert_eq!(
+        s.walk_end_reason,
+        Some(WalkEndReason::StackMemoryExhausted {
+            address: 0x80000010
+        })
+    );
+
+    // But this one has plenty of (useless) memory.
+    let mut stack = Section::new();
+    stack.start().set_const(0x80000000);
+    stack = stack.append_repeated(0, 0x1000);
+    let s = f.walk_stack(stack).await;
+    assert_eq!(s.frames.len(), 1);
+    assert_eq!(s.walk_end_reason, Some(WalkEndReason::NoCallerFound));
+}
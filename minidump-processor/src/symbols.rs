@@ -22,6 +22,7 @@
 //! * [FrameSymbolizer][] - callbacks that symbolication uses to return its results.
 //!     * Implemented by [StackFrame][crate::process_state::StackFrame]
 //!     * Implemented by DummyFrame (private, for a stack scanning heuristic)
+//!     * Implemented by RecordingFrame (private, for memoizing symbolication)
 //! * [FrameWalker][] - callbacks that cfi eval uses to read callee state and write caller state.
 //!     * Implemented by CfiStackWalker (private)
 //!
@@ -91,13 +92,15 @@
 
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 use async_trait::async_trait;
+use debugid::DebugId;
 use minidump::Module;
 
 pub use breakpad_symbols::{
     FileError, FileKind, FillSymbolError, FrameSymbolizer, FrameWalker, PendingSymbolStats,
-    SymbolError, SymbolFile, SymbolStats, SymbolSupplier, Symbolizer,
+    SymbolError, SymbolFile, SymbolKey, SymbolOrigin, SymbolStats, SymbolSupplier, Symbolizer,
 };
 
 /// The [`SymbolProvider`] is the main extension point for minidump processing.
@@ -266,6 +269,150 @@ impl SymbolProvider for MultiSymbolProvider {
     }
 }
 
+/// Everything a [`SymbolProvider`] reported to a [`FrameSymbolizer`] for one address.
+#[derive(Clone, Debug, Default)]
+struct CachedSymbol {
+    function: Option<(String, u64, u32)>,
+    source_file: Option<(String, u32, u64)>,
+    inlines: Vec<(String, Option<String>, Option<u32>)>,
+}
+
+impl CachedSymbol {
+    /// Report the recorded results to `frame` as if they came from the provider.
+    fn replay(&self, frame: &mut (dyn FrameSymbolizer + Send)) {
+        if let Some((name, base, parameter_size)) = &self.function {
+            frame.set_function(name, *base, *parameter_size);
+        }
+        if let Some((file, line, base)) = &self.source_file {
+            frame.set_source_file(file, *line, *base);
+        }
+        for (name, file, line) in &self.inlines {
+            frame.add_inline_frame(name, file.as_deref(), *line);
+        }
+    }
+}
+
+/// A [`FrameSymbolizer`] that forwards to the real frame while recording
+/// the results for [`SymbolCache`].
+struct RecordingFrame<'a> {
+    frame: &'a mut (dyn FrameSymbolizer + Send),
+    symbol: CachedSymbol,
+}
+
+impl FrameSymbolizer for RecordingFrame<'_> {
+    fn get_instruction(&self) -> u64 {
+        self.frame.get_instruction()
+    }
+    fn set_function(&mut self, name: &str, base: u64, parameter_size: u32) {
+        self.symbol.function = Some((name.to_owned(), base, parameter_size));
+        self.frame.set_function(name, base, parameter_size);
+    }
+    fn set_source_file(&mut self, file: &str, line: u32, base: u64) {
+        self.symbol.source_file = Some((file.to_owned(), line, base));
+        self.frame.set_source_file(file, line, base);
+    }
+    fn add_inline_frame(&mut self, name: &str, file: Option<&str>, line: Option<u32>) {
+        self.symbol
+            .inlines
+            .push((name.to_owned(), file.map(str::to_owned), line));
+        self.frame.add_inline_frame(name, file, line);
+    }
+}
+
+/// The key of a [`SymbolCache`] entry: (debug_id, module base, address).
+///
+/// The module base is included so that modules without a debug_id (or
+/// an unloaded module sharing an address with a loaded one) don't collide.
+type SymbolCacheKey = (Option<DebugId>, u64, u64);
+
+/// A [`SymbolProvider`] that memoizes `fill_symbol` for the duration of
+/// processing a single minidump.
+///
+/// The same address tends to come up over and over within a dump (recursion,
+/// several threads parked in the same function, stack scanning re-checking
+/// candidate return addresses), so each (module, address) pair is only ever
+/// symbolicated once, including its inline frames. Everything else is passed
+/// straight through.
+///
+/// Failures aren't remembered: they may be transient (a symbol server that
+/// timed out, say), and the provider is the one that knows whether it's worth
+/// trying again. [`Symbolizer`] already remembers modules it couldn't find
+/// symbols for, so a retry there is cheap.
+pub(crate) struct SymbolCache<'a, P> {
+    provider: &'a P,
+    /// The results for each key `fill_symbol` succeeded for.
+    symbols: Mutex<HashMap<SymbolCacheKey, CachedSymbol>>,
+}
+
+impl<'a, P> SymbolCache<'a, P> {
+    pub(crate) fn new(provider: &'a P) -> Self {
+        Self {
+            provider,
+            symbols: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl<P> SymbolProvider for SymbolCache<'_, P>
+where
+    P: SymbolProvider + Sync,
+{
+    async fn fill_symbol(
+        &self,
+        module: &(dyn Module + Sync),
+        frame: &mut (dyn FrameSymbolizer + Send),
+    ) -> Result<(), FillSymbolError> {
+        let key = (
+            module.debug_identifier(),
+            module.base_address(),
+            frame.get_instruction(),
+        );
+        let cached = self.symbols.lock().unwrap().get(&key).cloned();
+        match cached {
+            Some(symbol) => {
+                symbol.replay(frame);
+                Ok(())
+            }
+            None => {
+                let mut recorder = RecordingFrame {
+                    frame,
+                    symbol: CachedSymbol::default(),
+                };
+                let result = self.provider.fill_symbol(module, &mut recorder).await;
+                if result.is_ok() {
+                    self.symbols.lock().unwrap().insert(key, recorder.symbol);
+                }
+                result
+            }
+        }
+    }
+
+    async fn walk_frame(
+        &self,
+        module: &(dyn Module + Sync),
+        walker: &mut (dyn FrameWalker + Send),
+    ) -> Option<()> {
+        self.provider.walk_frame(module, walker).await
+    }
+
+    async fn get_file_path(
+        &self,
+        module: &(dyn Module + Sync),
+        file_kind: FileKind,
+    ) -> Result<PathBuf, FileError> {
+        self.provider.get_file_path(module, file_kind).await
+    }
+
+    fn stats(&self) -> HashMap<String, SymbolStats> {
+        self.provider.stats()
+    }
+
+    fn pending_stats(&self) -> PendingSymbolStats {
+        self.provider.pending_stats()
+    }
+}
+
 #[async_trait]
 impl SymbolProvider for Symbolizer {
     async fn fill_symbol(
@@ -350,3 +497,91 @@ pub fn simple_symbol_supplier(symbol_paths: Vec<PathBuf>) -> impl SymbolSupplier
 pub fn string_symbol_supplier(modules: HashMap<String, String>) -> impl SymbolSupplier {
     breakpad_symbols::StringSymbolSupplier::new(modules)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::StackFrame;
+    use minidump::{MinidumpContext, MinidumpModule, MinidumpRawContext};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Symbolicates everything in "with_syms" as `func`, inlining `inlined`,
+    /// and counts how often it was asked.
+    #[derive(Default)]
+    struct CountingProvider {
+        fills: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SymbolProvider for CountingProvider {
+        async fn fill_symbol(
+            &self,
+            module: &(dyn Module + Sync),
+            frame: &mut (dyn FrameSymbolizer + Send),
+        ) -> Result<(), FillSymbolError> {
+            self.fills.fetch_add(1, Ordering::SeqCst);
+            if module.code_file() != "with_syms" {
+                return Err(FillSymbolError {});
+            }
+            frame.set_function("func", 0x1000, 4);
+            frame.set_source_file("func.c", 10, 0x1000);
+            frame.add_inline_frame("inlined", Some("inlined.h"), Some(20));
+            Ok(())
+        }
+        async fn walk_frame(
+            &self,
+            _module: &(dyn Module + Sync),
+            _walker: &mut (dyn FrameWalker + Send),
+        ) -> Option<()> {
+            None
+        }
+        async fn get_file_path(
+            &self,
+            _module: &(dyn Module + Sync),
+            _file_kind: FileKind,
+        ) -> Result<PathBuf, FileError> {
+            Err(FileError::NotFound)
+        }
+    }
+
+    fn frame_at(instruction: u64) -> StackFrame {
+        let context = MinidumpContext::from_raw(MinidumpRawContext::Amd64(Default::default()));
+        let mut frame = StackFrame::from_context(context, crate::FrameTrust::Context);
+        frame.instruction = instruction;
+        frame
+    }
+
+    #[tokio::test]
+    async fn test_symbol_cache() {
+        let provider = CountingProvider::default();
+        let cache = SymbolCache::new(&provider);
+        let with_syms = MinidumpModule::new(0x1000, 0x1000, "with_syms");
+        let without_syms = MinidumpModule::new(0x2000, 0x1000, "without_syms");
+
+        for _ in 0..3 {
+            let mut frame = frame_at(0x1010);
+            assert!(cache.fill_symbol(&with_syms, &mut frame).await.is_ok());
+            assert_eq!(frame.function_name.as_deref(), Some("func"));
+            assert_eq!(frame.function_base, Some(0x1000));
+            assert_eq!(frame.parameter_size, Some(4));
+            assert_eq!(frame.source_file_name.as_deref(), Some("func.c"));
+            assert_eq!(frame.source_line, Some(10));
+            assert_eq!(frame.inlines.len(), 1);
+            assert_eq!(frame.inlines[0].function_name, "inlined");
+        }
+        assert_eq!(provider.fills.load(Ordering::SeqCst), 1);
+
+        // A new address in the same module is a miss.
+        let mut frame = frame_at(0x1020);
+        assert!(cache.fill_symbol(&with_syms, &mut frame).await.is_ok());
+        assert_eq!(provider.fills.load(Ordering::SeqCst), 2);
+
+        // Failures aren't remembered, as they may be transient.
+        for _ in 0..3 {
+            let mut frame = frame_at(0x2010);
+            assert!(cache.fill_symbol(&without_syms, &mut frame).await.is_err());
+            assert_eq!(frame.function_name, None);
+        }
+        assert_eq!(provider.fills.load(Ordering::SeqCst), 5);
+    }
+}
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use minidump::system_info::{Cpu, Os};
+use minidump::system_info::{ArmCpuFeatures, Cpu, Os};
 
 /// Information about the system that produced a `Minidump`.
 #[derive(Debug, Clone)]
@@ -19,16 +19,31 @@ pub struct SystemInfo {
     pub os_build: Option<String>,
     /// The CPU on which the dump was produced
     pub cpu: Cpu,
+    /// The byte order of the minidump, which is that of the CPU that produced it
+    pub endian: scroll::Endian,
     /// A string further identifying the specific CPU
     ///
     /// For example,  "GenuineIntel level 6 model 13 stepping 8", if present.
     pub cpu_info: Option<String>,
     /// The microcode version of the cpu
     pub cpu_microcode_version: Option<u64>,
+    /// The model and features of the cpu, if it's an ARM or ARM64 cpu
+    pub cpu_features: Option<ArmCpuFeatures>,
+    /// The name of the cpu's microarchitecture, like "Tiger Lake" or "Cortex-A76",
+    /// if it's a known one
+    pub cpu_microarchitecture: Option<String>,
+    /// A friendly description of the cpu, like
+    /// "GenuineIntel family 6 model 140 stepping 1 (Tiger Lake)", if present
+    pub cpu_description: Option<String>,
     /// The number of processors in the system
     ///
     /// Will be greater than one for multi-core systems.
     pub cpu_count: usize,
+    /// The amount of free physical memory in the system, in bytes, if known
+    pub available_physical_memory: Option<u64>,
+    /// The most memory the system could commit without growing the page file,
+    /// in bytes, if known
+    pub commit_limit: Option<u64>,
 }
 
 impl SystemInfo {
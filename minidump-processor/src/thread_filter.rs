@@ -0,0 +1,118 @@
+//! Choosing which threads' stacks to walk.
+//!
+//! Walking a thread's stack means fetching symbols for every module it passes
+//! through, which for dumps with hundreds of threads can take minutes. When
+//! only a few of the threads matter, a [`ThreadFilter`] in
+//! [`ProcessorOptions::thread_filter`][crate::ProcessorOptions::thread_filter]
+//! skips the walks of the rest. Skipped threads are still listed in
+//! [`ProcessState::threads`][crate::ProcessState::threads] (so thread indices
+//! are the same as without the filter), but with no frames, and
+//! [`CallStackInfo::NotWalked`][crate::CallStackInfo::NotWalked].
+
+/// Which threads to walk the stacks of.
+///
+/// The default walks every thread.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ThreadFilter {
+    /// Walk every thread.
+    #[default]
+    All,
+    /// Only walk the crashing thread.
+    CrashingThread,
+    /// Walk the crashing thread, and the threads with any of these ids or
+    /// names.
+    ///
+    /// Names are globs, where `*` matches any run of characters and `?` any
+    /// one character, so `"Watchdog*"` matches `"Watchdog"` and
+    /// `"WatchdogThread"`. They're matched against the name the thread ends
+    /// up with in the output (from the minidump's thread names, or an
+    /// [`ExtraInfoProvider`][crate::ExtraInfoProvider]), so threads without
+    /// a name never match.
+    Selected { ids: Vec<u32>, names: Vec<String> },
+}
+
+impl ThreadFilter {
+    /// Whether the thread with the given id and name should be walked.
+    pub fn includes(&self, thread_id: u32, thread_name: Option<&str>, is_crashing: bool) -> bool {
+        match self {
+            ThreadFilter::All => true,
+            ThreadFilter::CrashingThread => is_crashing,
+            ThreadFilter::Selected { ids, names } => {
+                is_crashing
+                    || ids.contains(&thread_id)
+                    || matches!(thread_name, Some(thread_name)
+                        if names.iter().any(|name| glob_matches(name, thread_name)))
+            }
+        }
+    }
+}
+
+/// Whether `text` matches the glob `pattern`, where `*` matches any run of
+/// characters and `?` any one character.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // Where the last `*` was, and where in `text` it's matching up to.
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Have the last `*` match one more character, and try again.
+                Some((star, star_t)) => {
+                    backtrack = Some((star, star_t + 1));
+                    p = star + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("Watchdog", "Watchdog"));
+        assert!(!glob_matches("Watchdog", "WatchdogThread"));
+        assert!(glob_matches("Watchdog*", "Watchdog"));
+        assert!(glob_matches("Watchdog*", "WatchdogThread"));
+        assert!(glob_matches("*Thread", "WatchdogThread"));
+        assert!(glob_matches("*dog*", "WatchdogThread"));
+        assert!(glob_matches("Thread ?", "Thread 1"));
+        assert!(!glob_matches("Thread ?", "Thread 10"));
+        assert!(glob_matches("a*b*c", "aXbYbZc"));
+        assert!(!glob_matches("a*b*c", "aXbYbZ"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("?", ""));
+    }
+
+    #[test]
+    fn test_includes() {
+        let filter = ThreadFilter::Selected {
+            ids: vec![0x10],
+            names: vec!["Watchdog*".to_owned()],
+        };
+        assert!(filter.includes(0x10, None, false));
+        assert!(filter.includes(0x20, Some("WatchdogThread"), false));
+        assert!(filter.includes(0x30, None, true));
+        assert!(!filter.includes(0x40, Some("Worker"), false));
+        assert!(!filter.includes(0x40, None, false));
+
+        assert!(ThreadFilter::CrashingThread.includes(0x30, None, true));
+        assert!(!ThreadFilter::CrashingThread.includes(0x10, Some("Watchdog"), false));
+        assert!(ThreadFilter::All.includes(0x40, None, false));
+    }
+}
@@ -0,0 +1,374 @@
+//! Saving the state of a stack walk so it can be continued later, or elsewhere.
+//!
+//! A [`WalkCursor`] captures one frame of a walked [`CallStack`], which is
+//! everything the stack walker needs to find that frame's callers. It can be
+//! serialized (it implements serde's traits, and there are JSON helpers), sent
+//! to another worker that has the same minidump and symbols, and continued
+//! there with [`resume_walk`].
+//!
+//! The cursor only contains:
+//!
+//! * the thread's id and index, and the frame's index in the thread's stack
+//! * the frame's valid general purpose registers, its instruction address,
+//!   and its [`FrameTrust`]
+//! * the base address of the thread's stack memory, which identifies the
+//!   memory region in the minidump to resume with
+//!
+//! In particular it doesn't contain the memory itself or any symbols (the
+//! resumed frame is symbolicated again), and it doesn't contain the frame's
+//! callee. The callee is only used by x86 `STACK WIN` unwinding (for the
+//! callee's parameter size), so resuming an x86 walk at a frame whose callee
+//! had stack parameters may unwind that one frame differently.
+
+use std::collections::{BTreeMap, HashSet};
+use std::convert::TryFrom;
+
+use minidump::{
+    CpuContext, MinidumpContext, MinidumpContextValidity, MinidumpModuleList, MinidumpRawContext,
+    UnifiedMemory, UnifiedMemoryList,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::process_state::{CallStack, FrameTrust, StackFrame};
+use crate::stackwalker::{walk_stack, DumpMemory};
+use crate::{ProcessorOptions, SymbolProvider, SystemInfo};
+
+/// An error encountered while restoring a [`WalkCursor`].
+#[derive(Debug, thiserror::Error)]
+pub enum WalkCursorError {
+    #[error("Failed to parse the walk cursor")]
+    ParseError(#[from] serde_json::Error),
+    #[error("The cursor's context has no register named {0}")]
+    UnknownRegister(String),
+    #[error("The value of register {0} doesn't fit in the cursor's context")]
+    RegisterOutOfRange(String),
+}
+
+impl WalkCursorError {
+    /// Returns just the name of the error, as a more human-friendly version of
+    /// an error-code for error logging.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WalkCursorError::ParseError(_) => "ParseError",
+            WalkCursorError::UnknownRegister(_) => "UnknownRegister",
+            WalkCursorError::RegisterOutOfRange(_) => "RegisterOutOfRange",
+        }
+    }
+}
+
+/// The kind of CPU context a [`WalkCursor`] holds.
+///
+/// These are the [`MinidumpRawContext`] variants the stack walker supports.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalkCursorCpu {
+    X86,
+    Amd64,
+    Arm,
+    Arm64,
+    OldArm64,
+    Mips,
+}
+
+/// A frame of a stack walk that can be serialized and resumed with [`resume_walk`].
+///
+/// See the [module-level docs][self] for what is (and isn't) saved.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalkCursor {
+    /// The id of the thread being walked.
+    pub thread_id: u32,
+    /// The index of the thread in the minidump's thread list.
+    pub thread_idx: usize,
+    /// The index of the frame in the thread's stack.
+    pub depth: usize,
+    /// The kind of CPU context the registers belong to.
+    pub cpu: WalkCursorCpu,
+    /// The frame's [`StackFrame::instruction`].
+    pub instruction: u64,
+    /// How the frame was found.
+    pub trust: FrameTrust,
+    /// The values of the frame's valid registers.
+    pub registers: BTreeMap<String, u64>,
+    /// The base address of the memory the thread's stack was walked in.
+    pub stack_base: Option<u64>,
+}
+
+impl WalkCursor {
+    /// Save frame `depth` of `stack`, which was walked in `stack_memory`.
+    ///
+    /// `thread_idx` is the index of the stack's thread in the thread list.
+    ///
+    /// Returns `None` if the stack has no such frame, or is for a CPU the
+    /// stack walker doesn't support.
+    pub fn new(
+        stack: &CallStack,
+        thread_idx: usize,
+        depth: usize,
+        stack_memory: Option<&UnifiedMemory<'_, '_>>,
+    ) -> Option<WalkCursor> {
+        let frame = stack.frames.get(depth)?;
+        let cpu = match frame.context.raw {
+            MinidumpRawContext::X86(_) => WalkCursorCpu::X86,
+            MinidumpRawContext::Amd64(_) => WalkCursorCpu::Amd64,
+            MinidumpRawContext::Arm(_) => WalkCursorCpu::Arm,
+            MinidumpRawContext::Arm64(_) => WalkCursorCpu::Arm64,
+            MinidumpRawContext::OldArm64(_) => WalkCursorCpu::OldArm64,
+            MinidumpRawContext::Mips(_) => WalkCursorCpu::Mips,
+            MinidumpRawContext::Ppc(_)
+            | MinidumpRawContext::Ppc64(_)
+            | MinidumpRawContext::Sparc(_) => return None,
+        };
+        let registers = frame
+            .context
+            .valid_registers()
+            .map(|(reg, val)| (reg.to_owned(), val))
+            .collect();
+
+        Some(WalkCursor {
+            thread_id: stack.thread_id,
+            thread_idx,
+            depth,
+            cpu,
+            instruction: frame.instruction,
+            trust: frame.trust,
+            registers,
+            stack_base: stack_memory.map(|memory| memory.base_address()),
+        })
+    }
+
+    /// Serialize the cursor as JSON.
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self).expect("walk cursors are always serializable")
+    }
+
+    /// Parse a cursor serialized by [`WalkCursor::to_json_string`].
+    pub fn from_json_str(json: &str) -> Result<WalkCursor, WalkCursorError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Find the memory the cursor's stack was walked in.
+    pub fn stack_memory<'a, 'mdmp>(
+        &self,
+        memory_list: &'a UnifiedMemoryList<'mdmp>,
+    ) -> Option<UnifiedMemory<'a, 'mdmp>> {
+        memory_list.memory_at_address(self.stack_base?)
+    }
+
+    /// Rebuild the (unsymbolicated) frame the cursor was saved at.
+    pub fn frame(&self) -> Result<StackFrame, WalkCursorError> {
+        let (raw, valid) = match self.cpu {
+            WalkCursorCpu::X86 => restore(&self.registers, MinidumpRawContext::X86)?,
+            WalkCursorCpu::Amd64 => restore(&self.registers, MinidumpRawContext::Amd64)?,
+            WalkCursorCpu::Arm => restore(&self.registers, MinidumpRawContext::Arm)?,
+            WalkCursorCpu::Arm64 => restore(&self.registers, MinidumpRawContext::Arm64)?,
+            WalkCursorCpu::OldArm64 => restore(&self.registers, MinidumpRawContext::OldArm64)?,
+            WalkCursorCpu::Mips => restore(&self.registers, MinidumpRawContext::Mips)?,
+        };
+        let context = MinidumpContext {
+            raw,
+            valid: MinidumpContextValidity::Some(valid),
+        };
+        let mut frame = StackFrame::from_context(context, self.trust);
+        frame.instruction = self.instruction;
+        Ok(frame)
+    }
+}
+
+/// Build a context of type `C` with `registers` set (and valid).
+fn restore<C>(
+    registers: &BTreeMap<String, u64>,
+    wrap: impl FnOnce(C) -> MinidumpRawContext,
+) -> Result<(MinidumpRawContext, HashSet<&'static str>), WalkCursorError>
+where
+    C: CpuContext + Default,
+    C::Register: TryFrom<u64>,
+{
+    let mut ctx = C::default();
+    let mut valid = HashSet::new();
+    for (name, &val) in registers {
+        let reg = ctx
+            .memoize_register(name)
+            .ok_or_else(|| WalkCursorError::UnknownRegister(name.clone()))?;
+        let val = C::Register::try_from(val)
+            .map_err(|_| WalkCursorError::RegisterOutOfRange(name.clone()))?;
+        ctx.set_register(reg, val)
+            .ok_or_else(|| WalkCursorError::UnknownRegister(name.clone()))?;
+        valid.insert(reg);
+    }
+    Ok((wrap(ctx), valid))
+}
+
+/// Continue a stack walk from `cursor`.
+///
+/// `stack_memory` should be the memory the walk started in (see
+/// [`WalkCursor::stack_memory`]), and the other arguments should be the same
+/// as the ones the walk was started with.
+///
+/// The returned stack starts with the cursor's frame (so its frames are
+/// offset by [`WalkCursor::depth`] from the original walk's), freshly
+/// symbolicated, followed by its callers. The frames before the cursor count
+/// towards [`ProcessorOptions::max_frames_per_thread`], so the resumed walk
+/// stops where the original one would have.
+pub async fn resume_walk<P>(
+    cursor: &WalkCursor,
+    options: &ProcessorOptions<'_>,
+    stack_memory: Option<&UnifiedMemory<'_, '_>>,
+    dump_memory: &DumpMemory<'_, '_>,
+    modules: &MinidumpModuleList,
+    system_info: &SystemInfo,
+    symbol_provider: &P,
+) -> Result<CallStack, WalkCursorError>
+where
+    P: SymbolProvider + Sync,
+{
+    let frame = cursor.frame()?;
+    let mut stack = CallStack::with_context(frame.context.clone());
+    stack.frames[0] = frame;
+    stack.thread_id = cursor.thread_id;
+
+    let mut options = options.clone();
+    options.max_frames_per_thread = options.max_frames_per_thread.saturating_sub(cursor.depth);
+    walk_stack(
+        cursor.thread_idx,
+        &options,
+        &mut stack,
+        stack_memory,
+        dump_memory,
+        modules,
+        system_info,
+        symbol_provider,
+    )
+    .await;
+    Ok(stack)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{string_symbol_supplier, Symbolizer};
+    use minidump::format::CONTEXT_AMD64;
+    use minidump::system_info::{Cpu, Os};
+    use minidump::{MinidumpMemory, MinidumpModule};
+    use std::collections::HashMap;
+    use test_assembler::*;
+
+    #[tokio::test]
+    async fn test_resume_walk() {
+        let modules = MinidumpModuleList::from_modules(vec![
+            MinidumpModule::new(0x00007400c0000000, 0x10000, "module1"),
+            MinidumpModule::new(0x00007500b0000000, 0x10000, "module2"),
+        ]);
+        let system_info = SystemInfo {
+            os: Os::Linux,
+            os_version: None,
+            os_build: None,
+            cpu: Cpu::X86_64,
+            endian: scroll::LE,
+            cpu_info: None,
+            cpu_microcode_version: None,
+            cpu_features: None,
+            cpu_microarchitecture: None,
+            cpu_description: None,
+            cpu_count: 1,
+            available_physical_memory: None,
+            commit_limit: None,
+        };
+        let symbolizer = Symbolizer::new(string_symbol_supplier(HashMap::new()));
+        let options = ProcessorOptions::default();
+
+        // Three frames found by scanning.
+        let stack = Section::new();
+        stack.start().set_const(0x8000000080000000);
+        let stack = stack
+            .append_repeated(0, 16)
+            .D64(0x00007500b0000100)
+            .append_repeated(0, 16)
+            .D64(0x00007500b0000900)
+            .append_repeated(0, 32);
+        let base = stack.start().value().unwrap();
+        let size = stack.size();
+        let bytes = stack.get_contents().unwrap();
+        let memory = MinidumpMemory {
+            desc: Default::default(),
+            base_address: base,
+            size,
+            bytes: &bytes,
+            endian: scroll::LE,
+        };
+        let memory = UnifiedMemory::Memory(&memory);
+
+        let raw = CONTEXT_AMD64 {
+            rip: 0x00007400c0000200,
+            rsp: base,
+            ..CONTEXT_AMD64::default()
+        };
+        let mut full =
+            CallStack::with_context(MinidumpContext::from_raw(MinidumpRawContext::Amd64(raw)));
+        full.thread_id = 7;
+        walk_stack(
+            0,
+            &options,
+            &mut full,
+            Some(&memory),
+            &DumpMemory::default(),
+            &modules,
+            &system_info,
+            &symbolizer,
+        )
+        .await;
+        assert_eq!(full.frames.len(), 3);
+
+        let cursor = WalkCursor::new(&full, 4, 1, Some(&memory)).unwrap();
+        assert_eq!(cursor.stack_base, Some(base));
+        let json = cursor.to_json_string();
+        let cursor = WalkCursor::from_json_str(&json).unwrap();
+        assert_eq!(cursor.thread_idx, 4);
+        assert_eq!(cursor.depth, 1);
+        assert_eq!(cursor.trust, FrameTrust::Scan);
+
+        let resumed = resume_walk(
+            &cursor,
+            &options,
+            Some(&memory),
+            &DumpMemory::default(),
+            &modules,
+            &system_info,
+            &symbolizer,
+        )
+        .await
+        .unwrap();
+        assert_eq!(resumed.thread_id, 7);
+        assert_eq!(resumed.frames.len(), 2);
+        for (resumed, original) in resumed.frames.iter().zip(&full.frames[1..]) {
+            assert_eq!(resumed.instruction, original.instruction);
+            assert_eq!(resumed.trust, original.trust);
+            assert_eq!(
+                resumed.context.get_stack_pointer(),
+                original.context.get_stack_pointer()
+            );
+        }
+
+        // The frames before the cursor count towards the frame limit.
+        let mut limited = options.clone();
+        limited.max_frames_per_thread = 2;
+        let resumed = resume_walk(
+            &cursor,
+            &limited,
+            Some(&memory),
+            &DumpMemory::default(),
+            &modules,
+            &system_info,
+            &symbolizer,
+        )
+        .await
+        .unwrap();
+        assert_eq!(resumed.frames.len(), 1);
+
+        let mut bad = cursor;
+        bad.registers.insert(String::from("xyz"), 0);
+        assert!(matches!(
+            bad.frame(),
+            Err(WalkCursorError::UnknownRegister(reg)) if reg == "xyz"
+        ));
+    }
+}
@@ -1,13 +1,17 @@
 // Copyright 2015 Ted Mielczarek. See the COPYRIGHT
 // file at the top-level directory of this distribution.
 
+use minidump::format::MINIDUMP_STREAM_TYPE;
 use minidump::system_info::{Cpu, Os};
 use minidump::{
-    Error, Minidump, MinidumpContext, MinidumpContextValidity, MinidumpRawContext, Module,
+    CorruptionIssue, Error, MemoryKind, Minidump, MinidumpContext, MinidumpContextValidity,
+    MinidumpModuleList, MinidumpRawContext, Module, WindowsExceptionParameters,
 };
 use minidump_processor::{
-    simple_symbol_supplier, CallStackInfo, FrameTrust, LinuxStandardBase, ProcessState, Symbolizer,
+    simple_symbol_supplier, CallStackInfo, FrameTrust, LinuxProcStatus, LinuxStandardBase,
+    MiniProcessState, ModuleMap, ProcessState, Symbolizer, UnlistedModule,
 };
+use scroll::Pwrite;
 use std::path::{Path, PathBuf};
 
 use minidump_synth::*;
@@ -133,6 +137,264 @@ async fn test_processor_symbols() {
     );
 }
 
+#[tokio::test]
+async fn test_patches() {
+    use futures_util::StreamExt;
+    use minidump_processor::{
+        PendingProcessorStatSubscriptions, PendingProcessorStats, ProcessStatePatch,
+        ProcessorOptions,
+    };
+
+    let dump = read_test_minidump().unwrap();
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![testdata_symbol_path()]));
+    let mut subscriptions = PendingProcessorStatSubscriptions::default();
+    subscriptions.patches = true;
+    let stats = PendingProcessorStats::new(subscriptions);
+    let mut options = ProcessorOptions::default();
+    options.stat_reporter = Some(&stats);
+
+    let (state, patches) = tokio::join!(
+        minidump_processor::process_minidump_with_options(&dump, &symbolizer, options),
+        stats.patches().collect::<Vec<_>>(),
+    );
+    let state = state.unwrap();
+
+    let mut patches = patches.into_iter();
+    let mut patched = match patches.next() {
+        Some(ProcessStatePatch::Unwalked(unwalked)) => *unwalked,
+        other => panic!("expected the unwalked state first, got {:?}", other),
+    };
+    // Nothing's been symbolicated yet.
+    assert_eq!(patched.threads[0].frames.len(), 1);
+    assert_eq!(patched.threads[0].frames[0].function_name, None);
+
+    for patch in patches {
+        patched.apply_patch(patch);
+    }
+    assert_eq!(patched.threads.len(), state.threads.len());
+    for (patched, thread) in patched.threads.iter().zip(&state.threads) {
+        assert_eq!(patched.frames.len(), thread.frames.len());
+        for (patched, frame) in patched.frames.iter().zip(&thread.frames) {
+            assert_eq!(patched.instruction, frame.instruction);
+            assert_eq!(patched.function_name, frame.function_name);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_group_by_signature() {
+    use minidump_processor::{crash_signature, group_by_signature};
+
+    let dump = read_test_minidump().unwrap();
+    let state = minidump_processor::process_minidump(
+        &dump,
+        &Symbolizer::new(simple_symbol_supplier(vec![testdata_symbol_path()])),
+    )
+    .await
+    .unwrap();
+    let signature = crash_signature(&state);
+    assert!(
+        signature.starts_with(
+            "EXCEPTION_ACCESS_VIOLATION_WRITE | `anonymous namespace'::CrashFunction | "
+        ),
+        "{}",
+        signature
+    );
+
+    let mut no_crash = state.clone();
+    no_crash.exception_info = None;
+    no_crash.requesting_thread = None;
+    assert_eq!(
+        crash_signature(&no_crash),
+        "No crash | <no crashing thread>"
+    );
+
+    let states = [
+        ("a", &state),
+        ("b", &no_crash),
+        ("c", &state),
+        ("d", &state),
+    ];
+    let groups = group_by_signature(states, 2, crash_signature);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[&signature].count, 3);
+    assert_eq!(groups[&signature].examples, vec!["a", "c"]);
+    assert_eq!(
+        groups["No crash | <no crashing thread>"].examples,
+        vec!["b"]
+    );
+
+    // Any key will do.
+    let groups = group_by_signature(states, 10, |state| state.threads.len());
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[&state.threads.len()].count, 4);
+}
+
+#[tokio::test]
+async fn test_compare_with_breakpad() {
+    use minidump_processor::breakpad_compare::{compare_with_breakpad, BreakpadStacks};
+
+    let dump = read_test_minidump().unwrap();
+    let state = minidump_processor::process_minidump(
+        &dump,
+        &Symbolizer::new(simple_symbol_supplier(vec![testdata_symbol_path()])),
+    )
+    .await
+    .unwrap();
+
+    // The crashing thread from breakpad's `minidump_stackwalk -m` on test.dmp.
+    let breakpad_output = r"OS|Windows NT|5.1.2600 Service Pack 2
+CPU|x86|GenuineIntel family 6 model 13 stepping 8|1
+GPU|||
+Crash|EXCEPTION_ACCESS_VIOLATION_WRITE|0x45|0
+Module|test_app.exe||test_app.pdb|5A9832E5287241C1838ED98914E9B7FF1|0x00400000|0x0042cfff|1
+
+0|0|test_app.exe|`anonymous namespace'::CrashFunction|c:\test_app.cc|58|0x3
+0|1|test_app.exe|main|c:\test_app.cc|65|0x5
+0|2|test_app.exe|__tmainCRTStartup|f:\sp\vctools\crt_bld\self_x86\crt\src\crt0.c|327|0x12
+0|3|kernel32.dll||||0x16fd7
+";
+    let breakpad = BreakpadStacks::parse(breakpad_output);
+    assert_eq!(breakpad.crashing_thread, Some(0));
+    assert_eq!(breakpad.threads.len(), 1);
+    assert_eq!(
+        breakpad.threads[0][2].source_file.as_deref(),
+        Some("crt0.c")
+    );
+    assert_eq!(breakpad.threads[0][3].offset, 0x16fd7);
+
+    let comparison = compare_with_breakpad(&state, &breakpad);
+    assert_eq!(comparison.our_crashing_thread, Some(0));
+    assert_eq!(comparison.breakpad_crashing_thread, Some(0));
+    assert!(comparison.threads[0].mismatches.is_empty());
+
+    // Now make breakpad disagree about a frame.
+    let breakpad_output =
+        breakpad_output.replace("|main|c:\\test_app.cc|65|", "|main|c:\\test_app.cc|64|");
+    let comparison = compare_with_breakpad(&state, &BreakpadStacks::parse(&breakpad_output));
+    assert!(!comparison.is_match());
+    let mismatches = &comparison.threads[0].mismatches;
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].frame_index, 1);
+    assert_eq!(mismatches[0].ours.as_ref().unwrap().source_line, Some(65));
+    assert_eq!(
+        mismatches[0].breakpad.as_ref().unwrap().source_line,
+        Some(64)
+    );
+}
+
+#[test]
+fn test_frame_trust_ordering() {
+    let ordered = [
+        FrameTrust::None,
+        FrameTrust::Scan,
+        FrameTrust::CfiScan,
+        FrameTrust::FramePointer,
+        FrameTrust::CallFrameInfoLowConfidence,
+        FrameTrust::CallFrameInfo,
+        FrameTrust::PreWalked,
+        FrameTrust::Context,
+    ];
+    assert!(ordered.windows(2).all(|pair| pair[0] < pair[1]));
+    // These numbers are part of the API.
+    assert_eq!(FrameTrust::None as u8, 0);
+    assert_eq!(FrameTrust::FramePointer as u8, 50);
+    assert_eq!(FrameTrust::CallFrameInfo as u8, 70);
+    assert_eq!(FrameTrust::Context as u8, 100);
+}
+
+#[test]
+fn test_module_map() {
+    let dump = read_test_minidump().unwrap();
+    let modules = dump.get_stream::<MinidumpModuleList>().unwrap();
+    let map = ModuleMap::from_modules(&modules);
+    assert_eq!(map.modules.len(), modules.iter().count());
+
+    let main = &map.modules[0];
+    assert_eq!(main.name, "test_app.exe");
+    assert_eq!(main.base_address, 0x400000);
+    assert_eq!(main.size, 0x2d000);
+    assert_eq!(main.debug_file.as_deref(), Some("test_app.pdb"));
+    assert_eq!(
+        main.debug_id.as_deref(),
+        Some("5A9832E5287241C1838ED98914E9B7FF1")
+    );
+    assert_eq!(main.code_id.as_deref(), Some("45d35f6c2d000"));
+    assert!(map
+        .modules
+        .windows(2)
+        .all(|w| w[0].base_address < w[1].base_address));
+
+    let mut tsv = Vec::new();
+    map.print_tsv(&mut tsv).unwrap();
+    let tsv = String::from_utf8(tsv).unwrap();
+    let mut lines = tsv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("name\tbase_address\tsize\tdebug_file\tdebug_id\tcode_id")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("test_app.exe\t0x400000\t0x2d000\ttest_app.pdb\t5A9832E5287241C1838ED98914E9B7FF1\t45d35f6c2d000")
+    );
+
+    let mut json = Vec::new();
+    map.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json[0]["name"], "test_app.exe");
+    assert_eq!(json[0]["base_address"], "0x400000");
+    assert_eq!(json[0]["size"], "0x2d000");
+}
+
+#[tokio::test]
+async fn test_mini_process_state() {
+    let dump = read_test_minidump().unwrap();
+    let state = minidump_processor::process_minidump(
+        &dump,
+        &Symbolizer::new(simple_symbol_supplier(vec![testdata_symbol_path()])),
+    )
+    .await
+    .unwrap();
+
+    let mini = MiniProcessState::from_process_state(&state);
+    assert_eq!(
+        mini.crash_reason.as_deref(),
+        Some("EXCEPTION_ACCESS_VIOLATION_WRITE")
+    );
+    assert_eq!(mini.crash_address, Some(0x45));
+    assert_eq!(mini.os, "Windows NT");
+    assert_eq!(mini.cpu, "x86");
+
+    // Only the modules the crashing thread's stack is in are kept.
+    let names = mini
+        .modules
+        .iter()
+        .map(|m| m.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["test_app.exe", "kernel32.dll"]);
+
+    let thread = mini.crashing_thread.as_ref().unwrap();
+    assert_eq!(thread.frames.len(), state.threads[0].frames.len());
+    let f0 = &thread.frames[0];
+    assert_eq!(
+        f0.function.as_deref(),
+        Some("`anonymous namespace'::CrashFunction")
+    );
+    assert_eq!(f0.source_file.as_deref(), Some("test_app.cc"));
+    assert_eq!(f0.source_line, Some(58));
+    assert_eq!(f0.module_index, Some(0));
+    assert_eq!(thread.frames[3].module_index, Some(1));
+
+    let mut json = Vec::new();
+    mini.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["schema_version"], 1);
+    assert_eq!(json["crash_address"], "0x45");
+    assert_eq!(json["crashing_thread"]["frames"][0]["trust"], "context");
+    assert_eq!(json["crashing_thread"]["frames"][0]["line"], 58);
+    assert_eq!(json["modules"][1]["name"], "kernel32.dll");
+}
+
 fn minimal_minidump() -> SynthMinidump {
     let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
     let stack = Memory::with_section(
@@ -169,6 +431,118 @@ microcode : 0x1e34a6789
     assert_eq!(state.system_info.cpu_microcode_version, Some(0x1e34a6789));
 }
 
+#[tokio::test]
+async fn test_process_memory_usage() {
+    let mut section = Section::with_endian(Endian::Little)
+        .D16(1) // revision
+        .D16(0) // padding
+        .D32(77); // page_fault_count
+    for size in 1..=9u64 {
+        section = section.D64(size * 0x1000);
+    }
+    let dump = minimal_minidump().add_stream(SimpleStream {
+        stream_type: minidump_common::format::MINIDUMP_STREAM_TYPE::ProcessVmCountersStream as u32,
+        section,
+    });
+    let state = read_synth_dump(dump).await;
+
+    let usage = state.process_memory_usage.as_ref().unwrap();
+    assert_eq!(usage.page_fault_count, Some(77));
+    assert_eq!(usage.peak_working_set_size, Some(0x1000));
+    assert_eq!(usage.working_set_size, Some(0x2000));
+    assert_eq!(usage.pagefile_usage, Some(0x7000));
+    assert_eq!(usage.peak_pagefile_usage, Some(0x8000));
+    assert_eq!(usage.private_usage, Some(0x9000));
+    assert_eq!(usage.virtual_size, None);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(
+        json["process_memory_usage"]["working_set_size"],
+        "0x00002000"
+    );
+    assert_eq!(json["process_memory_usage"]["page_fault_count"], 77);
+    assert!(json["process_memory_usage"]["virtual_size"].is_null());
+}
+
+#[tokio::test]
+async fn test_crashpad_annotations() {
+    let name = DumpString::new("module.dll", Endian::Little);
+    let module = minidump_synth::Module::new(Endian::Little, 0x10000, 0x1000, &name, 0, 0, None);
+    let module_info = ModuleCrashpadInfo::new(0, Endian::Little)
+        .add_annotation_object("key", AnnotationValue::String("value".to_owned()))
+        .add_annotation_object("blob", AnnotationValue::Custom(0x8001, vec![0xa, 0xb]));
+    let dump = minimal_minidump()
+        .add_module(module)
+        .add(name)
+        .add_crashpad_info(CrashpadInfo::new(Endian::Little).add_module(module_info));
+    let state = read_synth_dump(dump).await;
+
+    let crashpad_info = state.crashpad_info.as_ref().unwrap();
+    assert_eq!(crashpad_info.module_list[0].annotation_objects.len(), 2);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let annotations = &json["modules"][0]["crashpad_annotations"];
+    assert_eq!(annotations["key"]["type"], 1);
+    assert_eq!(annotations["key"]["value"], "value");
+    assert_eq!(annotations["blob"]["type"], 0x8001);
+    assert_eq!(annotations["blob"]["bytes"], "0a0b");
+}
+
+#[tokio::test]
+async fn test_unlisted_modules() {
+    let foo = DumpString::new("/lib/libfoo.so", Endian::Little);
+    let bar = DumpString::new("/lib/libbar.so", Endian::Little);
+    let exe = DumpString::new("", Endian::Little);
+    let module = minidump_synth::Module::new(Endian::Little, 0x10000, 0x1000, &foo, 0, 0, None);
+    // minimal_minidump is x86, so these are 32-bit link_map entries.
+    let map = Section::with_endian(Endian::Little)
+        .D32(0)
+        .D32(exe.file_offset())
+        .D32(0)
+        .D32(0x10000)
+        .D32(foo.file_offset())
+        .D32(0x10800)
+        .D32(0x50000)
+        .D32(bar.file_offset())
+        .D32(0x50800);
+    let dso_debug = Section::with_endian(Endian::Little)
+        .D32(1) // version
+        .D32(map.file_offset()) // map
+        .D32(3) // dso_count
+        .D32(0) // brk
+        .D32(0) // ldbase
+        .D32(0); // dynamic
+    let dump = minimal_minidump()
+        .add_module(module)
+        .add_stream(SimpleStream {
+            stream_type: minidump_common::format::MINIDUMP_STREAM_TYPE::LinuxDsoDebug as u32,
+            section: dso_debug,
+        })
+        .add(map)
+        .add(foo)
+        .add(bar)
+        .add(exe);
+    let state = read_synth_dump(dump).await;
+
+    assert_eq!(
+        state.unlisted_modules,
+        vec![UnlistedModule {
+            name: String::from("/lib/libbar.so"),
+            load_address: 0x50000,
+        }]
+    );
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["unlisted_modules"][0]["filename"], "/lib/libbar.so");
+    assert_eq!(json["unlisted_modules"][0]["base_addr"], "0x00050000");
+}
+
 #[tokio::test]
 async fn test_linux_lsb_release() {
     // Whitespace intentionally wonky to test robustness
@@ -233,12 +607,91 @@ async fn test_linux_environ() {
 #[tokio::test]
 async fn test_linux_proc_status() {
     // Whitespace intentionally wonky to test robustness
-
-    // TODO: add tests for values we care about
-    let input = b"";
+    let input = b"Name:   firefox\nVmPeak:\t 2048 kB\n  VmRSS:     512 kB  \nThreads:\t87\n";
 
     let dump = minimal_minidump().set_linux_proc_status(input);
-    let _state = read_synth_dump(dump).await;
+    let state = read_synth_dump(dump).await;
+    assert_eq!(
+        state.linux_proc_status,
+        Some(LinuxProcStatus {
+            vm_rss: Some(512 * 1024),
+            vm_peak: Some(2048 * 1024),
+            threads: Some(87),
+        })
+    );
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(
+        json["linux_proc_status"],
+        serde_json::json!({
+            "vm_rss": "0x00080000",
+            "vm_peak": "0x00200000",
+            "threads": 87,
+        })
+    );
+
+    // No stream, no proc status.
+    let state = read_synth_dump(minimal_minidump()).await;
+    assert_eq!(state.linux_proc_status, None);
+}
+
+#[tokio::test]
+async fn test_big_endian() {
+    let context = minidump_synth::x86_context(Endian::Big, 0xabcd1234, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Big).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Big, 0x1234, &stack, &context);
+    let system_info = SystemInfo::new(Endian::Big);
+    let dump = SynthMinidump::with_endian(Endian::Big)
+        .add_thread(thread)
+        .add_system_info(system_info)
+        .add(context)
+        .add_memory(stack);
+    let state = read_synth_dump(dump).await;
+
+    assert_eq!(state.system_info.endian, scroll::Endian::Big);
+    assert_eq!(state.threads[0].frames[0].instruction, 0xabcd1234);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["system_info"]["endianness"], "big");
+}
+
+#[tokio::test]
+async fn test_assertion() {
+    let field = |section: Section, s: &str| {
+        let mut units = s.encode_utf16().collect::<Vec<_>>();
+        units.resize(128, 0);
+        units
+            .into_iter()
+            .fold(section, |section, unit| section.D16(unit))
+    };
+    let section = Section::with_endian(Endian::Little);
+    let section = field(section, "x != 0");
+    let section = field(section, "foo");
+    let section = field(section, "bar.cc");
+    let section = section
+        .D32(42) // line
+        .D32(1); // type, invalid parameter
+    let dump = minimal_minidump().add_stream(SimpleStream {
+        stream_type: minidump_common::format::MINIDUMP_STREAM_TYPE::AssertionInfoStream as u32,
+        section,
+    });
+    let state = read_synth_dump(dump).await;
+
+    let expected =
+        "Invalid parameter passed to library function x != 0 in function foo, in file bar.cc at line 42";
+    assert_eq!(state.assertion.as_deref(), Some(expected));
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["crash_info"]["assertion"], expected);
 }
 
 #[tokio::test]
@@ -268,3 +721,2696 @@ async fn test_no_frames() {
 
     state.print_json(&mut std::io::sink(), true).unwrap();
 }
+
+#[tokio::test]
+async fn test_crashing_thread_heuristic() {
+    let make_thread = |id: u32, base: u64, eip: u32, esp: u32| {
+        let context = minidump_synth::x86_context(Endian::Little, eip, esp);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            base,
+        );
+        let thread = Thread::new(Endian::Little, id, &stack, &context);
+        (thread, context, stack)
+    };
+    let (thread1, context1, stack1) = make_thread(0x1234, 0x10000, 0xabcd1234, 0x10010);
+    let (thread2, context2, stack2) = make_thread(0x5678, 0x20000, 0xabcd5678, 0x20010);
+
+    // The exception happened on the second thread, somewhere deeper in its
+    // stack than where the thread list says it is.
+    let exception_context = minidump_synth::x86_context(Endian::Little, 0xdeadbeef, 0x20008);
+    let mut ex = Exception::new(Endian::Little);
+    ex.thread_id = 0x9999;
+    ex.set_context(&exception_context);
+
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread1)
+        .add_thread(thread2)
+        .add_exception(ex)
+        .add_system_info(SystemInfo::new(Endian::Little))
+        .add(context1)
+        .add(context2)
+        .add(exception_context)
+        .add_memory(stack1)
+        .add_memory(stack2);
+
+    let state = read_synth_dump(dump).await;
+    assert_eq!(state.requesting_thread, Some(1));
+    assert!(state.crashing_thread_heuristic);
+    assert_eq!(state.threads[1].frames[0].instruction, 0xdeadbeef);
+}
+
+#[tokio::test]
+async fn test_memory_kinds() {
+    let maps = b"\
+1000-1fff rw-p 00000000 00:00 0
+5000-5fff ---p 00000000 00:00 0
+";
+    let mut ex = Exception::new(Endian::Little);
+    ex.thread_id = 0x1234;
+    ex.exception_record.exception_address = 0x5008;
+    let dump = minimal_minidump().set_linux_maps(maps).add_exception(ex);
+    let state = read_synth_dump(dump).await;
+
+    let exception_info = state.exception_info.as_ref().unwrap();
+    assert_eq!(exception_info.address, 0x5008);
+    assert_eq!(exception_info.address_memory_kind, Some(MemoryKind::Guard));
+    assert_eq!(
+        state.threads[0].stack_memory_kind,
+        Some(MemoryKind::Stack {
+            thread_id: Some(0x1234)
+        })
+    );
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["crash_info"]["address_memory_kind"]["kind"], "guard");
+    let stack_kind = &json["threads"][0]["stack_memory_kind"];
+    assert_eq!(stack_kind["kind"], "stack");
+    assert_eq!(stack_kind["thread_id"], 0x1234);
+
+    // Without memory info, nothing is known.
+    let state = read_synth_dump(minimal_minidump()).await;
+    assert_eq!(state.threads[0].stack_memory_kind, None);
+}
+
+#[tokio::test]
+async fn test_windows_exception_parameters() {
+    let mut ex = Exception::new(Endian::Little);
+    ex.thread_id = 0x1234;
+    ex.exception_record.exception_code =
+        minidump_common::errors::ExceptionCodeWindows::EXCEPTION_ACCESS_VIOLATION as u32;
+    ex.exception_record.number_parameters = 2;
+    ex.exception_record.exception_information[0] = 1;
+    ex.exception_record.exception_information[1] = 0x20;
+    let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let system_info = SystemInfo::new(Endian::Little)
+        .set_platform_id(minidump_common::format::PlatformId::VER_PLATFORM_WIN32_NT as u32);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_exception(ex)
+        .add_system_info(system_info)
+        .add(context)
+        .add_memory(stack);
+    let state = read_synth_dump(dump).await;
+
+    let info = state.exception_info.as_ref().unwrap();
+    assert_eq!(info.reason.to_string(), "EXCEPTION_ACCESS_VIOLATION_WRITE");
+    assert_eq!(info.address, 0x20);
+    assert_eq!(
+        info.windows_parameters,
+        Some(WindowsExceptionParameters::AccessViolation {
+            access: Some(minidump_common::errors::ExceptionCodeWindowsAccessType::WRITE),
+            address: Some(0x20),
+        })
+    );
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(
+        json["crash_info"]["windows_parameters"],
+        serde_json::json!({
+            "kind": "access_violation",
+            "access": "WRITE",
+            "address": "0x00000020",
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_last_error_value() {
+    const TEB: u64 = 0x7ffd_e000;
+    let windows_dump = |amd64: bool, with_teb: bool| {
+        let (context, offset, arch) = if amd64 {
+            (
+                minidump_synth::amd64_context(Endian::Little, 0xabcd1234, 0x1010),
+                0x68,
+                minidump_common::format::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64,
+            )
+        } else {
+            (
+                minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010),
+                0x34,
+                minidump_common::format::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_INTEL,
+            )
+        };
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x1000,
+        );
+        // ERROR_ACCESS_DENIED
+        let teb = Memory::with_section(
+            Section::with_endian(Endian::Little)
+                .append_repeated(0xff, offset)
+                .D32(5)
+                .append_repeated(0xff, 0x100),
+            TEB,
+        );
+        let thread = Thread::with_teb(Endian::Little, 0x1234, TEB, &stack, &context);
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(arch as u16)
+            .set_platform_id(minidump_common::format::PlatformId::VER_PLATFORM_WIN32_NT as u32);
+        let mut ex = Exception::new(Endian::Little);
+        ex.thread_id = 0x1234;
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add_exception(ex)
+            .add_system_info(system_info)
+            .add(context)
+            .add_memory(stack);
+        if with_teb {
+            dump.add_memory(teb)
+        } else {
+            dump
+        }
+    };
+
+    for amd64 in [false, true] {
+        let state = read_synth_dump(windows_dump(amd64, true)).await;
+        assert_eq!(
+            state.threads[0].last_error_value.map(|e| e.to_string()),
+            Some(String::from("ERROR_ACCESS_DENIED"))
+        );
+        let mut json = Vec::new();
+        state.print_json(&mut json, false).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json["crash_info"]["last_error_value"],
+            "ERROR_ACCESS_DENIED"
+        );
+        assert_eq!(
+            json["threads"][0]["last_error_value"],
+            "ERROR_ACCESS_DENIED"
+        );
+
+        // Without the TEB's memory, there's no value.
+        let state = read_synth_dump(windows_dump(amd64, false)).await;
+        assert_eq!(state.threads[0].last_error_value, None);
+        let mut json = Vec::new();
+        state.print_json(&mut json, false).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json["crash_info"]["last_error_value"],
+            serde_json::Value::Null
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_integrity_issues() {
+    let state = read_synth_dump(minimal_minidump()).await;
+    assert!(state.integrity_issues.is_empty());
+
+    // Claim more memory regions than the memory list holds.
+    let mut bytes = minimal_minidump().finish().unwrap();
+    let memory_list = Minidump::read(&bytes[..])
+        .unwrap()
+        .all_streams()
+        .find(|stream| stream.stream_type == MINIDUMP_STREAM_TYPE::MemoryListStream as u32)
+        .unwrap();
+    bytes
+        .pwrite_with(7u32, memory_list.location.rva as usize, scroll::LE)
+        .unwrap();
+    let dump = Minidump::read(bytes).unwrap();
+    let state = minidump_processor::process_minidump(
+        &dump,
+        &Symbolizer::new(simple_symbol_supplier(vec![])),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        state.integrity_issues,
+        vec![CorruptionIssue::ImpossibleCount {
+            stream_type: MINIDUMP_STREAM_TYPE::MemoryListStream as u32,
+            count: 7,
+            available: 1,
+        }]
+    );
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let issue = &json["integrity_issues"][0];
+    assert_eq!(issue["kind"], "ImpossibleCount");
+    assert_eq!(issue["stream_type"], "0x00000005");
+    assert_eq!(issue["offset"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn test_arm_cpu_features() {
+    let system_info = SystemInfo::new(Endian::Little)
+        .set_processor_architecture(
+            minidump_common::format::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_ARM64 as u16,
+        )
+        .set_platform_id(minidump_common::format::PlatformId::Linux as u32)
+        .set_cpu_info(CpuInfo::ArmCpuInfo {
+            cpuid: 0x611f0221,
+            elf_hwcaps: 0,
+        });
+    // Processing needs a thread, but what's in it doesn't matter here.
+    let context = minidump_synth::amd64_context(Endian::Little, 0xabcd1234, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(system_info)
+        .add(context)
+        .add_memory(stack)
+        .set_linux_cpu_info(b"Features : fp asimd atomics\n");
+    let state = read_synth_dump(dump).await;
+    let cpu_features = state.system_info.cpu_features.as_ref().unwrap();
+    assert_eq!(cpu_features.part_name(), Some("M1 Icestorm"));
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(
+        json["system_info"]["cpu_features"],
+        serde_json::json!({
+            "implementer": "0x61",
+            "part": "0x22",
+            "vendor": "Apple",
+            "part_name": "M1 Icestorm",
+            "features": ["fp", "asimd", "atomics"],
+        })
+    );
+
+    // Other cpus have no ARM features.
+    let state = read_synth_dump(minimal_minidump()).await;
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["system_info"]["cpu_features"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn test_unloaded_module_candidates() {
+    let system_info = SystemInfo::new(Endian::Little).set_processor_architecture(
+        minidump_common::format::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16,
+    );
+    // The crashing instruction is in a module that was loaded and unloaded
+    // twice at the same address, and in a larger module that overlaps both.
+    let context = minidump_synth::amd64_context(Endian::Little, 0x20800, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let name1 = DumpString::new("plugin.dll", Endian::Little);
+    let name2 = DumpString::new("big.dll", Endian::Little);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(system_info)
+        .add(context)
+        .add_memory(stack)
+        .add_unloaded_module(UnloadedModule::new(
+            Endian::Little,
+            0x20000,
+            0x1000,
+            &name1,
+            0x1111,
+            0xaaaa,
+        ))
+        .add_unloaded_module(UnloadedModule::new(
+            Endian::Little,
+            0x10000,
+            0x20000,
+            &name2,
+            0x2222,
+            0xbbbb,
+        ))
+        .add_unloaded_module(UnloadedModule::new(
+            Endian::Little,
+            0x20000,
+            0x1000,
+            &name1,
+            0x3333,
+            0xcccc,
+        ))
+        .add(name1)
+        .add(name2);
+    let state = read_synth_dump(dump).await;
+    let frame = &state.threads[0].frames[0];
+    assert!(frame.module.is_none());
+    let candidates = frame
+        .unloaded_module_candidates
+        .iter()
+        .map(|c| (c.name.as_str(), c.offset, c.time_date_stamp))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        candidates,
+        vec![
+            ("plugin.dll", 0x800, 0x1111),
+            ("big.dll", 0x10800, 0x2222),
+            ("plugin.dll", 0x800, 0x3333),
+        ]
+    );
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let frame = &json["threads"][0]["frames"][0];
+    assert_eq!(
+        frame["unloaded_module_candidates"],
+        serde_json::json!([
+            {
+                "module": "plugin.dll",
+                "base_address": "0x0000000000020000",
+                "offset": "0x0000000000000800",
+                "checksum": "0x0000aaaa",
+                "time_date_stamp": 0x1111,
+            },
+            {
+                "module": "big.dll",
+                "base_address": "0x0000000000010000",
+                "offset": "0x0000000000010800",
+                "checksum": "0x0000bbbb",
+                "time_date_stamp": 0x2222,
+            },
+            {
+                "module": "plugin.dll",
+                "base_address": "0x0000000000020000",
+                "offset": "0x0000000000000800",
+                "checksum": "0x0000cccc",
+                "time_date_stamp": 0x3333,
+            },
+        ])
+    );
+    // The grouped view still dedupes the repeated load.
+    assert_eq!(
+        frame["unloaded_modules"],
+        serde_json::json!([
+            { "module": "big.dll", "offsets": ["0x0000000000010800"] },
+            { "module": "plugin.dll", "offsets": ["0x0000000000000800"] },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_stack_continued_in_another_region() {
+    let system_info = SystemInfo::new(Endian::Little).set_processor_architecture(
+        minidump_common::format::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16,
+    );
+    let name = DumpString::new("/lib/libfoo.so", Endian::Little);
+    let module =
+        minidump_synth::Module::new(Endian::Little, 0x40000000, 0x10000, &name, 0, 0, None);
+    // The thread's stack ends just past the stack pointer, but another
+    // region of the memory list picks up where it leaves off, with the
+    // return address a scan should find.
+    let context = minidump_synth::amd64_context(Endian::Little, 0x40000100, 0x80f8);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x100),
+        0x8000,
+    );
+    let rest_of_stack = Memory::with_section(
+        Section::with_endian(Endian::Little)
+            .D64(0x40000200)
+            .append_repeated(0, 0xf8),
+        0x8100,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(system_info)
+        .add_module(module)
+        .add(name)
+        .add(context)
+        .add_memory(stack)
+        .add_memory(rest_of_stack);
+    let state = read_synth_dump(dump).await;
+    let frames = &state.threads[0].frames;
+    assert!(frames.len() >= 2);
+    assert_eq!(frames[1].trust, FrameTrust::Scan);
+    assert_eq!(frames[1].resume_address, 0x40000200);
+}
+
+#[tokio::test]
+async fn test_arm64_fp_registers() {
+    let system_info = SystemInfo::new(Endian::Little).set_processor_architecture(
+        minidump_common::format::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_ARM64 as u16,
+    );
+    let mut context = Section::with_endian(Endian::Little)
+        .D32(minidump_common::format::ContextFlagsArm64::CONTEXT_ARM64_FULL.bits())
+        .D32(0) // cpsr
+        .append_repeated(0, 31 * 8) // iregs
+        .D64(0x1010) // sp
+        .D64(0xabcd1234) // pc
+        .D64(0x7ff8000000000000) // q0 (a NaN in d0)
+        .D64(0);
+    for _ in 1..32 {
+        context = context.D64(0).D64(0);
+    }
+    let context = context
+        .D32(0x3000000) // fpcr
+        .D32(0x8000001) // fpsr
+        .append_repeated(0, 8 * 4 + 8 * 8 + 2 * 4 + 2 * 8); // debug registers
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(system_info)
+        .add(context)
+        .add_memory(stack);
+    let state = read_synth_dump(dump).await;
+    let fp = state.threads[0].frames[0].context.fp_registers().unwrap();
+    assert_eq!(fp.q[0], 0x7ff8000000000000);
+    assert_eq!(fp.fpsr, 0x8000001);
+
+    let mut output = Vec::new();
+    state.print_with_fp_registers(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("    q0 = 0x00000000000000007ff8000000000000"));
+    assert!(output.contains("  fpsr = 0x08000001"));
+
+    // They're left out of the normal report.
+    let mut output = Vec::new();
+    state.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(!output.contains("fpsr"));
+}
+
+#[tokio::test]
+async fn test_arm64_sve_registers() {
+    use minidump_common::format as md;
+
+    let system_info = SystemInfo::new(Endian::Little)
+        .set_processor_architecture(md::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_ARM64 as u16);
+
+    // 128-bit vectors, right after the CONTEXT_ARM64.
+    let context_size = 912;
+    let sve_size = 16 * 32 + 2 * 17;
+    let mut xstate_data = md::XSTATE_CONFIG_FEATURE_MSC_INFO {
+        context_size: context_size + sve_size,
+        enabled_features: 1 << md::XSTATE_ARM64_SVE,
+        ..Default::default()
+    };
+    xstate_data.features[md::XSTATE_ARM64_SVE] = md::XSTATE_FEATURE {
+        offset: context_size,
+        size: sve_size,
+    };
+    let mut misc = MiscStream::new(Endian::Little);
+    misc.misc_5 = Some(MiscInfo5Fields {
+        xstate_data,
+        process_cookie: None,
+    });
+
+    let context = Section::with_endian(Endian::Little)
+        .D32(
+            md::ContextFlagsArm64::CONTEXT_ARM64_FULL.bits()
+                | md::ContextFlagsArm64::CONTEXT_ARM64_XSTATE.bits(),
+        )
+        .D32(0) // cpsr
+        .append_repeated(0, 31 * 8) // iregs
+        .D64(0x1010) // sp
+        .D64(0xabcd1234) // pc
+        .append_repeated(0, 32 * 16) // float_regs
+        .D32(0) // fpcr
+        .D32(0) // fpsr
+        .append_repeated(0, 8 * 4 + 8 * 8 + 2 * 4 + 2 * 8) // debug registers
+        .D64(0x1111) // z0
+        .D64(0x2222)
+        .append_repeated(0, 16 * 31) // z1-z31
+        .D16(0xffff) // p0
+        .append_repeated(0, 2 * 16); // p1-p15, ffr
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(system_info)
+        .add_stream(misc)
+        .add(context)
+        .add_memory(stack);
+    let state = read_synth_dump(dump).await;
+    let sve = match state.threads[0].extended_state {
+        Some(minidump::MinidumpExtendedState::Arm64Sve(ref sve)) => sve,
+        ref other => panic!("unexpected extended state {:?}", other),
+    };
+    assert_eq!(sve.vector_length, 16);
+    assert_eq!(sve.p(0).unwrap(), [0xff, 0xff]);
+
+    let mut output = Vec::new();
+    state.print_with_fp_registers(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(&format!("    z0 = 0x{:016x}{:016x}", 0x2222, 0x1111)));
+    assert!(output.contains("    p0 = 0xffff"));
+    assert!(output.contains("   ffr = 0x0000"));
+}
+
+#[tokio::test]
+async fn test_amd64_ymm_registers() {
+    use minidump_common::format as md;
+
+    let system_info = SystemInfo::new(Endian::Little)
+        .set_processor_architecture(md::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16);
+
+    // The AVX state is at its standard XSAVE offset, which is inside the
+    // CONTEXT_AMD64 (256 bytes into vector_register).
+    let mut xstate_data = md::XSTATE_CONFIG_FEATURE_MSC_INFO {
+        context_size: 576 + 256,
+        enabled_features: 1 << md::XstateFeatureIndex::GSSE_AND_AVX as usize,
+        ..Default::default()
+    };
+    xstate_data.features[md::XstateFeatureIndex::GSSE_AND_AVX as usize] = md::XSTATE_FEATURE {
+        offset: 576,
+        size: 256,
+    };
+    let mut misc = MiscStream::new(Endian::Little);
+    misc.misc_5 = Some(MiscInfo5Fields {
+        xstate_data,
+        process_cookie: None,
+    });
+
+    let context = Section::with_endian(Endian::Little)
+        .append_repeated(0, 8 * 6) // p[1-6]_home
+        .D32(0x10001f) // context_flags
+        .D32(0) // mx_csr
+        .append_repeated(0, 2 * 6) // cs,ds,es,fs,gs,ss
+        .D32(0) // eflags
+        .append_repeated(0, 8 * 6) // dr0,1,2,3,6,7
+        .append_repeated(0, 8 * 4) // rax,rcx,rdx,rbx
+        .D64(0x1010) // rsp
+        .append_repeated(0, 8 * 11) // rbp-r15
+        .D64(0xabcd1234) // rip
+        .append_repeated(0, 160) // float_save, up to xmm0
+        .D64(0x1111) // xmm0
+        .D64(0)
+        .append_repeated(0, 512 - 160 - 16)
+        .append_repeated(0, 64) // vector_register, up to the AVX state
+        .D64(0x2222) // ymm0, upper half
+        .D64(0)
+        .append_repeated(0, 16 * 15)
+        .append_repeated(0, 16 * 26 - 64 - 256) // the rest of vector_register
+        .append_repeated(0, 8 * 6); // trailing stuff
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(system_info)
+        .add_stream(misc)
+        .add(context)
+        .add_memory(stack);
+    let state = read_synth_dump(dump).await;
+    let xstate = match state.threads[0].extended_state {
+        Some(minidump::MinidumpExtendedState::Amd64(ref xstate)) => xstate,
+        ref other => panic!("unexpected extended state {:?}", other),
+    };
+    assert_eq!(xstate.ymm.unwrap()[0], [0x1111, 0x2222]);
+    assert_eq!(xstate.zmm, None);
+
+    let mut output = Vec::new();
+    state.print_with_fp_registers(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(&format!("  ymm0 = 0x{:032x}{:032x}", 0x2222, 0x1111)));
+    assert!(output.contains("  ymm15 = 0x"));
+}
+
+#[tokio::test]
+async fn test_nested_exceptions() {
+    let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let system_info = SystemInfo::new(Endian::Little)
+        .set_platform_id(minidump_common::format::PlatformId::VER_PLATFORM_WIN32_NT as u32);
+
+    // An EXCEPTION_NONCONTINUABLE_EXCEPTION wrapping the access violation
+    // that actually happened.
+    let mut ex = Exception::new(Endian::Little);
+    ex.thread_id = 0x1234;
+    ex.exception_record.exception_code = 0xc0000025;
+    ex.exception_record.exception_address = 0xabcd1234;
+    ex.exception_record.exception_record = 0x8000;
+    let mut record = Section::with_endian(Endian::Little)
+        .D32(0xc0000005) // exception_code
+        .D32(0) // exception_flags
+        .D32(0) // exception_record
+        .D32(0xabcd0000) // exception_address
+        .D32(2) // number_parameters
+        .D32(0) // read
+        .D32(0x44);
+    for _ in 2..15 {
+        record = record.D32(0);
+    }
+    let record = Memory::with_section(record, 0x8000);
+
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_exception(ex)
+        .add_system_info(system_info)
+        .add(context)
+        .add_memory(stack)
+        .add_memory(record);
+    let state = read_synth_dump(dump).await;
+    let info = state.exception_info.as_ref().unwrap();
+    assert_eq!(info.reason.to_string(), "EXCEPTION_ACCESS_VIOLATION_READ");
+    assert_eq!(info.address, 0x44);
+    assert_eq!(info.nested_exceptions.len(), 2);
+    assert_eq!(
+        info.nested_exceptions[0].reason.to_string(),
+        "EXCEPTION_NONCONTINUABLE_EXCEPTION"
+    );
+    assert_eq!(info.nested_exceptions[0].address, 0xabcd1234);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let crash_info = &json["crash_info"];
+    assert_eq!(crash_info["type"], "EXCEPTION_ACCESS_VIOLATION_READ");
+    assert_eq!(crash_info["address"], "0x00000044");
+    assert_eq!(crash_info["windows_parameters"]["access"], "READ");
+    let nested = crash_info["nested_exceptions"].as_array().unwrap();
+    assert_eq!(nested.len(), 2);
+    assert_eq!(nested[0]["code"], "0xc0000025");
+    assert_eq!(nested[1]["type"], "EXCEPTION_ACCESS_VIOLATION_READ");
+    assert_eq!(nested[1]["address"], "0x00000044");
+}
+
+#[tokio::test]
+async fn test_overlapping_modules() {
+    let context = minidump_synth::x86_context(Endian::Little, 0x10800, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let small = DumpString::new("small.dll", Endian::Little);
+    let large = DumpString::new("large.dll", Endian::Little);
+    let fine = DumpString::new("fine.dll", Endian::Little);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(SystemInfo::new(Endian::Little))
+        .add_module(minidump_synth::Module::new(
+            Endian::Little,
+            0x10000,
+            0x1000,
+            &small,
+            0,
+            0,
+            None,
+        ))
+        .add_module(minidump_synth::Module::new(
+            Endian::Little,
+            0x8000,
+            0x10000,
+            &large,
+            0,
+            0,
+            None,
+        ))
+        .add_module(minidump_synth::Module::new(
+            Endian::Little,
+            0x20000,
+            0x1000,
+            &fine,
+            0,
+            0,
+            None,
+        ))
+        .add(small)
+        .add(large)
+        .add(fine)
+        .add(context)
+        .add_memory(stack);
+    let state = read_synth_dump(dump).await;
+
+    // The larger module wins the lookup.
+    let frame = &state.threads[0].frames[0];
+    assert_eq!(frame.module.as_ref().unwrap().code_file(), "large.dll");
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let corrupt = json["modules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|module| module["corrupt_range"].clone())
+        .collect::<Vec<_>>();
+    // It's left out of the modules that are fine.
+    assert_eq!(
+        corrupt,
+        [
+            serde_json::Value::Bool(true),
+            serde_json::Value::Bool(true),
+            serde_json::Value::Null
+        ]
+    );
+    let issues = json["integrity_issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["kind"], "OverlappingModules");
+}
+
+#[tokio::test]
+async fn test_flags_decoded() {
+    async fn process(eflags: u32) -> ProcessState {
+        let context = Section::with_endian(Endian::Little)
+            .D32(0x1007f) // context_flags: CONTEXT_ALL
+            .append_repeated(0, 4 * 6) // dr0,1,2,3,6,7
+            .append_repeated(0, 112) // float_save
+            .append_repeated(0, 4 * 11) // gs-ebp
+            .D32(0xabcd1234) // eip
+            .D32(0) // cs
+            .D32(eflags)
+            .D32(0x1010) // esp
+            .D32(0) // ss
+            .append_repeated(0, 512); // extended_registers
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x1000,
+        );
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        let mut ex = Exception::new(Endian::Little);
+        ex.thread_id = 0x1234;
+        ex.exception_record.exception_code = 0xc0000005;
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add_exception(ex)
+            .add_system_info(SystemInfo::new(Endian::Little))
+            .add(context)
+            .add_memory(stack);
+        read_synth_dump(dump).await
+    }
+
+    for (eflags, expected) in [
+        (0, "[flags 0x0]"),
+        (0x246, "PF ZF IF [flags 0x246]"),
+        (0x3a97, "CF PF AF SF IF OF IOPL=3 [flags 0x3a97]"),
+    ] {
+        let state = process(eflags).await;
+
+        let mut json = Vec::new();
+        state.print_json(&mut json, false).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json["crashing_thread"]["frames"][0]["flags_decoded"],
+            expected
+        );
+        assert_eq!(json["threads"][0]["frames"][0].get("flags_decoded"), None);
+
+        let mut output = Vec::new();
+        state.print(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(&format!("    Flags: {}\n", expected)));
+    }
+}
+
+#[tokio::test]
+async fn test_thread_concurrency() {
+    use minidump_processor::ProcessorOptions;
+
+    let dump = read_test_minidump().unwrap();
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![testdata_symbol_path()]));
+    let mut outputs = Vec::new();
+    for thread_concurrency in [0, 1, 2, ProcessorOptions::DEFAULT_THREAD_CONCURRENCY] {
+        let mut options = ProcessorOptions::default();
+        options.thread_concurrency = thread_concurrency;
+        let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+            .await
+            .unwrap();
+        let mut json = Vec::new();
+        state.print_json(&mut json, false).unwrap();
+        outputs.push(json);
+    }
+    // However many threads are walked at once, the results (and the order of
+    // the threads) are the same.
+    assert!(outputs.windows(2).all(|pair| pair[0] == pair[1]));
+    let json: serde_json::Value = serde_json::from_slice(&outputs[0]).unwrap();
+    assert_eq!(json["thread_count"], 2);
+}
+
+#[tokio::test]
+async fn test_frames_truncated() {
+    use minidump_processor::{ProcessorOptions, WalkEndReason};
+
+    let dump = read_test_minidump().unwrap();
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![testdata_symbol_path()]));
+    let mut options = ProcessorOptions::default();
+    options.max_frames_per_thread = 2;
+    let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+        .await
+        .unwrap();
+    let stack = &state.threads[0];
+    assert_eq!(stack.frames.len(), 2);
+    assert_eq!(stack.walk_end_reason, Some(WalkEndReason::MaxFramesReached));
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["threads"][0]["frames_truncated"], true);
+    assert_eq!(json["threads"][0]["frame_count"], 2);
+
+    let mut output = Vec::new();
+    state.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("<stopped walking after 2 frames>\n"));
+}
+
+#[tokio::test]
+async fn test_walk_timed_out() {
+    use minidump_processor::{ProcessorOptions, WalkEndReason};
+
+    let dump = read_test_minidump().unwrap();
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![testdata_symbol_path()]));
+    let mut options = ProcessorOptions::default();
+    options.frame_walk_timeout = Some(std::time::Duration::ZERO);
+    let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+        .await
+        .unwrap();
+    let stack = &state.threads[0];
+    assert_eq!(stack.frames.len(), 1);
+    assert_eq!(stack.walk_end_reason, Some(WalkEndReason::WalkTimedOut));
+    // The frame that was walked is complete.
+    assert!(stack.frames[0].function_name.is_some());
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["threads"][0]["walk_timed_out"], true);
+    assert_eq!(json["threads"][0]["frames_truncated"], false);
+}
+
+#[tokio::test]
+async fn test_scan_limit_reached() {
+    use minidump_processor::{ProcessorOptions, WalkEndReason};
+
+    // No frame pointer, so the caller can only be found by scanning.
+    let context = minidump_synth::x86_context(Endian::Little, 0x10100, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little)
+            .append_repeated(0, 0x10)
+            .D32(0x10200) // return address
+            .append_repeated(0, 0x1000 - 0x14),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let name = DumpString::new("module.dll", Endian::Little);
+    let module = minidump_synth::Module::new(Endian::Little, 0x10000, 0x1000, &name, 0, 0, None);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(SystemInfo::new(Endian::Little))
+        .add_module(module)
+        .add(name)
+        .add(context)
+        .add_memory(stack);
+    let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+    let mut options = ProcessorOptions::default();
+    options.max_consecutive_scan_frames = 0;
+    let state = minidump_processor::process_minidump_with_options(
+        &dump,
+        &Symbolizer::new(simple_symbol_supplier(vec![])),
+        options,
+    )
+    .await
+    .unwrap();
+    let stack = &state.threads[0];
+    assert_eq!(stack.walk_end_reason, Some(WalkEndReason::ScanLimitReached));
+    let frame_count = stack.frames.len();
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["threads"][0]["walk_end_reason"], "scan_limit_reached");
+    assert_eq!(json["threads"][0]["frames_truncated"], false);
+
+    let mut output = Vec::new();
+    state.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(&format!(
+        "<stopped walking after {} frames: too many frames found by scanning in a row>\n",
+        frame_count
+    )));
+}
+
+#[tokio::test]
+async fn test_exploitability() {
+    use minidump_processor::{ExploitabilityRating, ExploitabilitySignal, ProcessorOptions};
+
+    let process = |address: u64, null_deref_threshold: u64| async move {
+        let mut ex = Exception::new(Endian::Little);
+        ex.thread_id = 0x1234;
+        ex.exception_record.exception_code =
+            minidump_common::errors::ExceptionCodeWindows::EXCEPTION_ACCESS_VIOLATION as u32;
+        ex.exception_record.number_parameters = 2;
+        ex.exception_record.exception_information[0] = 1;
+        ex.exception_record.exception_information[1] = address;
+        let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x1000,
+        );
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_platform_id(minidump_common::format::PlatformId::VER_PLATFORM_WIN32_NT as u32);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add_exception(ex)
+            .add_system_info(system_info)
+            .add(context)
+            .add_memory(stack);
+        let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+        let mut options = ProcessorOptions::default();
+        options.analyze_exploitability = true;
+        options.null_deref_threshold = null_deref_threshold;
+        minidump_processor::process_minidump_with_options(
+            &dump,
+            &Symbolizer::new(simple_symbol_supplier(vec![])),
+            options,
+        )
+        .await
+        .unwrap()
+    };
+
+    let default_threshold = ProcessorOptions::DEFAULT_NULL_DEREF_THRESHOLD;
+    let state = process(0x41414141, default_threshold).await;
+    let analysis = state.exploitability.as_ref().unwrap();
+    assert_eq!(analysis.rating, ExploitabilityRating::High);
+    assert_eq!(
+        analysis.signals,
+        vec![
+            ExploitabilitySignal::WriteAccessViolation,
+            ExploitabilitySignal::PcOutsideModules,
+        ]
+    );
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["crash_info"]["exploitability"], "high");
+    assert_eq!(
+        json["crash_info"]["exploitability_reasons"],
+        serde_json::json!(["write_access_violation", "pc_outside_modules"])
+    );
+
+    // Null pointer writes don't count, but the wild instruction pointer still does.
+    let state = process(0x20, default_threshold).await;
+    let analysis = state.exploitability.as_ref().unwrap();
+    assert_eq!(
+        analysis.signals,
+        vec![ExploitabilitySignal::PcOutsideModules]
+    );
+
+    // What's a null pointer write is up to the processor's threshold.
+    let state = process(0x20000, 0x100000).await;
+    assert!(state.exception_info.as_ref().unwrap().null_deref.is_some());
+    let analysis = state.exploitability.as_ref().unwrap();
+    assert_eq!(
+        analysis.signals,
+        vec![ExploitabilitySignal::PcOutsideModules]
+    );
+
+    // Off by default.
+    let dump = minimal_minidump();
+    let state = read_synth_dump(dump).await;
+    assert!(state.exploitability.is_none());
+}
+
+#[tokio::test]
+async fn test_stack_overflow() {
+    use minidump_processor::StackOverflowEvidence;
+
+    let overflow_dump =
+        |crash_address: u64, exception_code: u32| {
+            // A stack full of the same return address, as runaway recursion leaves.
+            let mut section = Section::with_endian(Endian::Little);
+            for _ in 0..0x400 {
+                section = section.D32(0x40001005);
+            }
+            let stack = Memory::with_section(section, 0x100000);
+            let context = minidump_synth::x86_context(Endian::Little, 0x40001000, 0x100000);
+            let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+            let module_name = DumpString::new("recursive.dll", Endian::Little);
+            let mut ex = Exception::new(Endian::Little);
+            ex.thread_id = 0x1234;
+            ex.exception_record.exception_code = exception_code;
+            ex.exception_record.exception_address = crash_address;
+            SynthMinidump::with_endian(Endian::Little)
+                .add_thread(thread)
+                .add_exception(ex)
+                .add_system_info(SystemInfo::new(Endian::Little).set_platform_id(
+                    minidump_common::format::PlatformId::VER_PLATFORM_WIN32_NT as u32,
+                ))
+                .add_module(minidump_synth::Module::new(
+                    Endian::Little,
+                    0x40000000,
+                    0x10000,
+                    &module_name,
+                    0,
+                    0,
+                    None,
+                ))
+                .add(module_name)
+                .add(context)
+                .add_memory(stack)
+        };
+
+    let state = read_synth_dump(overflow_dump(0xffff0, 0)).await;
+    let analysis = state.stack_overflow.as_ref().unwrap();
+    assert_eq!(
+        analysis.evidence,
+        vec![
+            StackOverflowEvidence::BelowStack,
+            StackOverflowEvidence::BelowStackPointer
+        ]
+    );
+    let recursion = analysis.recursion.as_ref().unwrap();
+    assert_eq!(recursion.module, "recursive.dll");
+    assert_eq!(recursion.offset, 0x1004);
+    assert!(recursion.frames >= 16);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["crash_info"]["crash_type"], "stack_overflow");
+    assert_eq!(
+        json["crash_info"]["stack_overflow"]["evidence"],
+        serde_json::json!(["below_stack", "below_stack_pointer"])
+    );
+    assert_eq!(
+        json["crash_info"]["stack_overflow"]["recursion"]["module"],
+        "recursive.dll"
+    );
+
+    let mut output = Vec::new();
+    state.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(
+        output.contains("Crash type: stack overflow\n  crash address is just below the stack\n")
+    );
+
+    // With memory info, the guard page is visible too.
+    let maps = b"f0000-fffff ---p 00000000 00:00 0\n";
+    let state = read_synth_dump(overflow_dump(0xffff0, 0).set_linux_maps(maps)).await;
+    let analysis = state.stack_overflow.as_ref().unwrap();
+    assert_eq!(analysis.evidence[0], StackOverflowEvidence::GuardPage);
+
+    // Windows says so itself.
+    let code = minidump_common::errors::ExceptionCodeWindows::EXCEPTION_STACK_OVERFLOW as u32;
+    let state = read_synth_dump(overflow_dump(0x40001000, code)).await;
+    let analysis = state.stack_overflow.as_ref().unwrap();
+    assert_eq!(analysis.evidence, vec![StackOverflowEvidence::Exception]);
+
+    // Recursion alone is reported, but isn't enough to call it an overflow.
+    let state = read_synth_dump(overflow_dump(0x20, 0)).await;
+    let analysis = state.stack_overflow.as_ref().unwrap();
+    assert!(!analysis.is_stack_overflow());
+    assert!(analysis.evidence.is_empty());
+    assert_eq!(analysis.recursion.as_ref().unwrap().offset, 0x1004);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["crash_info"]["crash_type"], serde_json::Value::Null);
+    assert_eq!(
+        json["crash_info"]["stack_overflow"]["evidence"],
+        serde_json::json!([])
+    );
+    assert_eq!(
+        json["crash_info"]["stack_overflow"]["recursion"]["module"],
+        "recursive.dll"
+    );
+
+    let mut output = Vec::new();
+    state.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(!output.contains("Crash type: stack overflow"));
+    assert!(output.contains("Crashing thread is deeply recursive\n  recursion: "));
+}
+
+#[tokio::test]
+async fn test_poisoned_crash_address() {
+    let mut ex = Exception::new(Endian::Little);
+    ex.thread_id = 0x1234;
+    ex.exception_record.exception_address = 0xdddddde5;
+    let state = read_synth_dump(minimal_minidump().add_exception(ex)).await;
+
+    let poisoned = state
+        .exception_info
+        .as_ref()
+        .and_then(|info| info.poisoned_address)
+        .unwrap();
+    assert_eq!(poisoned.pattern.kind, "msvc_freed_heap_poison");
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(
+        json["crash_info"]["crash_address_kind"],
+        "msvc_freed_heap_poison"
+    );
+    assert_eq!(
+        json["crash_info"]["crash_address_kind_description"],
+        "0xdddddde5 looks like freed memory from the MSVC debug heap (use-after-free)"
+    );
+
+    let mut output = Vec::new();
+    state.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(
+        "Crash address: 0xdddddde5\nCrash address kind: 0xdddddde5 looks like freed memory"
+    ));
+}
+
+#[tokio::test]
+async fn test_null_deref() {
+    let process = |access: u64, address: u64| {
+        let mut ex = Exception::new(Endian::Little);
+        ex.thread_id = 0x1234;
+        ex.exception_record.exception_code =
+            minidump_common::errors::ExceptionCodeWindows::EXCEPTION_ACCESS_VIOLATION as u32;
+        ex.exception_record.number_parameters = 2;
+        ex.exception_record.exception_information[0] = access;
+        ex.exception_record.exception_information[1] = address;
+        let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x1000,
+        );
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        ex.set_context(&context);
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_platform_id(minidump_common::format::PlatformId::VER_PLATFORM_WIN32_NT as u32);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add_exception(ex)
+            .add_system_info(system_info)
+            .add(context)
+            .add_memory(stack);
+        read_synth_dump(dump)
+    };
+    let to_json = |state: &ProcessState| {
+        let mut json = Vec::new();
+        state.print_json(&mut json, false).unwrap();
+        serde_json::from_slice::<serde_json::Value>(&json).unwrap()
+    };
+
+    let state = process(0, 0x38).await;
+    let json = to_json(&state);
+    let null_deref = &json["crash_info"]["null_deref"];
+    assert_eq!(null_deref["offset"], "0x00000038");
+    assert_eq!(null_deref["access"], "READ");
+    assert_eq!(null_deref["register"]["value"], "0x00000000");
+    assert_eq!(
+        json["crash_info"]["address_register"]["name"],
+        null_deref["register"]["name"]
+    );
+    let mut output = Vec::new();
+    state.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Null pointer dereference: read at offset 0x38 ("));
+
+    let state = process(1, 0x8).await;
+    assert_eq!(
+        to_json(&state)["crash_info"]["null_deref"]["access"],
+        "WRITE"
+    );
+
+    let state = process(8, 0).await;
+    let json = to_json(&state);
+    assert_eq!(json["crash_info"]["null_deref"]["access"], "EXEC");
+    assert_eq!(json["crash_info"]["null_deref"]["offset"], "0x00000000");
+
+    // A wild pointer isn't a null dereference, and no register is close below this one.
+    let state = process(0, 0x41414141).await;
+    let exception_info = state.exception_info.unwrap();
+    assert_eq!(exception_info.null_deref, None);
+    assert_eq!(exception_info.address_register, None);
+}
+
+#[tokio::test]
+async fn test_crash_address_details() {
+    use minidump_processor::{CrashAddressRegion, NearestMapping};
+
+    let maps = b"\
+1000-1fff rw-p 00000000 00:00 0
+8000-8fff rw-p 00000000 00:00 0 [heap]
+10000-10fff r-xp 00000000 00:00 0 /lib/libfoo.so
+";
+    let crash_at = |address: u64, access: u64, maps: Option<&[u8]>| {
+        let name = DumpString::new("/lib/libfoo.so", Endian::Little);
+        let module =
+            minidump_synth::Module::new(Endian::Little, 0x10000, 0x1000, &name, 0, 0, None);
+        let mut ex = Exception::new(Endian::Little);
+        ex.thread_id = 0x1234;
+        ex.exception_record.exception_code =
+            minidump_common::errors::ExceptionCodeWindows::EXCEPTION_ACCESS_VIOLATION as u32;
+        ex.exception_record.number_parameters = 2;
+        ex.exception_record.exception_information[0] = access;
+        ex.exception_record.exception_information[1] = address;
+        let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x1000,
+        );
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_platform_id(minidump_common::format::PlatformId::VER_PLATFORM_WIN32_NT as u32);
+        let mut dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add_exception(ex)
+            .add_system_info(system_info)
+            .add_module(module)
+            .add(name)
+            .add(context)
+            .add_memory(stack);
+        if let Some(maps) = maps {
+            dump = dump.set_linux_maps(maps);
+        }
+        read_synth_dump(dump)
+    };
+
+    // Modules and stacks are recognized without any memory info...
+    let state = crash_at(0x10100, 0, None).await;
+    let details = state.exception_info.unwrap().crash_address_details.unwrap();
+    assert_eq!(
+        details.region,
+        CrashAddressRegion::Module {
+            name: String::from("libfoo.so"),
+            offset: 0x100
+        }
+    );
+    assert_eq!(details.protection, None);
+    let state = crash_at(0x1800, 0, None).await;
+    let details = state.exception_info.unwrap().crash_address_details.unwrap();
+    assert_eq!(
+        details.region,
+        CrashAddressRegion::Stack {
+            thread_id: Some(0x1234)
+        }
+    );
+    // ...but nothing else is.
+    let state = crash_at(0x8100, 0, None).await;
+    assert_eq!(state.exception_info.unwrap().crash_address_details, None);
+
+    let state = crash_at(0x10100, 0, Some(maps)).await;
+    let details = state.exception_info.unwrap().crash_address_details.unwrap();
+    assert_eq!(details.protection.unwrap().to_string(), "r-x");
+    assert!(!details.executed_non_executable);
+
+    let state = crash_at(0x8100, 1, Some(maps)).await;
+    let details = state.exception_info.unwrap().crash_address_details.unwrap();
+    assert_eq!(details.region, CrashAddressRegion::Heap);
+    assert_eq!(details.protection.unwrap().to_string(), "rw-");
+    assert_eq!(details.nearest_mapped, None);
+    assert!(!details.executed_non_executable);
+
+    // Just past the end of the heap.
+    let state = crash_at(0x9010, 0, Some(maps)).await;
+    let details = state.exception_info.unwrap().crash_address_details.unwrap();
+    assert_eq!(details.region, CrashAddressRegion::Unmapped);
+    assert_eq!(details.protection, None);
+    assert_eq!(
+        details.nearest_mapped,
+        Some(NearestMapping {
+            address: 0x8fff,
+            distance: 0x11
+        })
+    );
+
+    // Executing the heap.
+    let state = crash_at(0x8100, 8, Some(maps)).await;
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let details = &json["crash_info"]["crash_address_details"];
+    assert_eq!(details["region"], "heap");
+    assert_eq!(details["protection"], "rw-");
+    assert_eq!(details["executed_non_executable"], true);
+    let mut output = Vec::new();
+    state.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(
+        "Crash address: 0x8100\nCrash address region: heap (rw-), executed but not executable\n"
+    ));
+}
+
+#[tokio::test]
+async fn test_symbol_stats() {
+    use minidump_processor::{ProcessorOptions, SymbolOrigin};
+
+    let dump = read_test_minidump().unwrap();
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![testdata_symbol_path()]));
+    let json = |state: &ProcessState| {
+        let mut json = Vec::new();
+        state.print_json(&mut json, false).unwrap();
+        serde_json::from_slice::<serde_json::Value>(&json).unwrap()
+    };
+
+    // The stats are always collected, but only in the JSON when asked for.
+    let state = minidump_processor::process_minidump(&dump, &symbolizer)
+        .await
+        .unwrap();
+    let stats = &state.symbol_stats["test_app.exe"];
+    assert!(
+        matches!(&stats.origin, Some(SymbolOrigin::LocalFile(path)) if path.ends_with("test_app.sym"))
+    );
+    assert!(!stats.cache_hit);
+    assert!(stats.lookup_time.is_some());
+    assert!(json(&state)["symbol_stats"].is_null());
+
+    let mut options = ProcessorOptions::default();
+    options.report_symbol_stats = true;
+    let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+        .await
+        .unwrap();
+    let json = json(&state);
+    let stats = json["symbol_stats"].as_array().unwrap();
+    assert_eq!(stats.len(), state.symbol_stats.len());
+    let test_app = stats
+        .iter()
+        .find(|stats| stats["module"] == "test_app.exe")
+        .unwrap();
+    assert_eq!(test_app["loaded_symbols"], true);
+    assert_eq!(test_app["origin"], "local");
+    assert_eq!(test_app["cache_hit"], false);
+    assert!(test_app["lookup_time_ms"].is_f64());
+    let missing = stats
+        .iter()
+        .find(|stats| stats["loaded_symbols"] == false)
+        .unwrap();
+    assert!(missing["origin"].is_null());
+    assert!(missing["parse_error"].is_null());
+}
+
+#[tokio::test]
+async fn test_unloaded_modules_up_the_stack() {
+    // An x86 context with a frame pointer, so the walker can find a caller
+    // that isn't in any loaded module.
+    let context = Section::with_endian(Endian::Little)
+        .D32(0x1007f) // context_flags: CONTEXT_ALL
+        .append_repeated(0, 4 * 6) // dr0,1,2,3,6,7
+        .append_repeated(0, 112) // float_save
+        .append_repeated(0, 4 * 10) // gs-eax
+        .D32(0x1020) // ebp
+        .D32(0x20800) // eip
+        .D32(0) // cs
+        .D32(0) // eflags
+        .D32(0x1010) // esp
+        .D32(0) // ss
+        .append_repeated(0, 512); // extended_registers
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little)
+            .append_repeated(0, 0x20)
+            .D32(0) // caller's ebp
+            .D32(0x30100) // return address, in another unloaded module
+            .append_repeated(0, 0x1000 - 0x28),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let plugin = DumpString::new("plugin.dll", Endian::Little);
+    let callback = DumpString::new("callback.dll", Endian::Little);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(SystemInfo::new(Endian::Little))
+        .add(context)
+        .add_memory(stack)
+        .add_unloaded_module(UnloadedModule::new(
+            Endian::Little,
+            0x20000,
+            0x1000,
+            &plugin,
+            0,
+            0,
+        ))
+        .add_unloaded_module(UnloadedModule::new(
+            Endian::Little,
+            0x30000,
+            0x1000,
+            &callback,
+            0,
+            0,
+        ))
+        .add(plugin)
+        .add(callback);
+    let state = read_synth_dump(dump).await;
+
+    let frames = &state.threads[0].frames;
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1].trust, FrameTrust::FramePointer);
+    let unloaded = |idx: usize| {
+        frames[idx]
+            .unloaded_modules
+            .iter()
+            .map(|(name, offsets)| (name.as_str(), offsets.iter().copied().collect::<Vec<_>>()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(unloaded(0), vec![("plugin.dll", vec![0x800])]);
+    assert_eq!(unloaded(1), vec![("callback.dll", vec![0xff])]);
+
+    let mut output = Vec::new();
+    state.print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(" 0  0x20800 (in unloaded plugin.dll + 0x800)\n"));
+    assert!(output.contains(" 1  0x300ff (in unloaded callback.dll + 0xff)\n"));
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(
+        json["threads"][0]["frames"][1]["unloaded_modules"],
+        serde_json::json!([{ "module": "callback.dll", "offsets": ["0x000000ff"] }])
+    );
+}
+
+#[tokio::test]
+async fn test_symbol_miss_reason() {
+    use minidump_processor::{string_symbol_supplier, SymbolMissReason};
+    use std::collections::HashMap;
+
+    // The crashing frame is at 0xabcd1234, in foo.dll if there is one.
+    async fn process(
+        symbols: Option<&str>,
+        with_module: bool,
+    ) -> (Option<SymbolMissReason>, serde_json::Value) {
+        let name = DumpString::new("foo.dll", Endian::Little);
+        let module =
+            minidump_synth::Module::new(Endian::Little, 0xabcd0000, 0x10000, &name, 0, 0, None);
+        let mut dump = minimal_minidump();
+        if with_module {
+            dump = dump.add_module(module).add(name);
+        }
+        let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+        let symbols = symbols
+            .map(|symbols| HashMap::from([("foo.dll".to_owned(), symbols.to_owned())]))
+            .unwrap_or_default();
+        let symbolizer = Symbolizer::new(string_symbol_supplier(symbols));
+        let state = minidump_processor::process_minidump(&dump, &symbolizer)
+            .await
+            .unwrap();
+        let mut json = Vec::new();
+        state.print_json(&mut json, false).unwrap();
+        let json = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+        (state.threads[0].frames[0].symbol_miss_reason, json)
+    }
+
+    let (reason, json) = process(None, false).await;
+    assert_eq!(reason, Some(SymbolMissReason::NoModule));
+    assert_eq!(
+        json["threads"][0]["frames"][0]["symbol_miss_reason"],
+        "no_module"
+    );
+
+    let (reason, json) = process(None, true).await;
+    assert_eq!(reason, Some(SymbolMissReason::MissingSymbolFile));
+    assert_eq!(
+        json["modules"][0]["symbol_misses"],
+        serde_json::json!({ "missing_symbol_file": 1 })
+    );
+
+    let (reason, _) = process(Some("this is not a symbol file\n"), true).await;
+    assert_eq!(reason, Some(SymbolMissReason::CorruptSymbolFile));
+
+    // The symbols only cover the start of the module.
+    let symbols = "MODULE windows x86 0 foo.pdb\nFUNC 0 10 0 start\n";
+    let (reason, json) = process(Some(symbols), true).await;
+    assert_eq!(reason, Some(SymbolMissReason::AddressNotCovered));
+    assert_eq!(
+        json["threads"][0]["frames"][0]["symbol_miss_reason"],
+        "address_not_covered"
+    );
+
+    // Symbolicated frames have no reason.
+    let symbols = "MODULE windows x86 0 foo.pdb\nFUNC 1000 1000 0 crash\n";
+    let (reason, json) = process(Some(symbols), true).await;
+    assert_eq!(reason, None);
+    assert!(json["threads"][0]["frames"][0]["symbol_miss_reason"].is_null());
+    assert!(json["modules"][0]["symbol_misses"].is_null());
+}
+
+#[tokio::test]
+async fn test_frame_analyzer() {
+    use minidump::UnifiedMemory;
+    use minidump_processor::{FrameAnalyzer, ProcessorOptions, StackFrame};
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Records the value at each frame's stack pointer, and its callee's instruction.
+    #[derive(Default)]
+    struct StackPeeker {
+        frames: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl FrameAnalyzer for StackPeeker {
+        async fn analyze_frame(
+            &self,
+            frame: &StackFrame,
+            callee: Option<&StackFrame>,
+            stack_memory: Option<&UnifiedMemory<'_, '_>>,
+            _modules: &MinidumpModuleList,
+        ) -> BTreeMap<String, String> {
+            self.frames.fetch_add(1, Ordering::Relaxed);
+            let sp = frame.context.get_stack_pointer();
+            let value = stack_memory.and_then(|memory| memory.get_memory_at_address::<u32>(sp));
+            BTreeMap::from([
+                (
+                    "callee".to_owned(),
+                    callee.map_or("none".to_owned(), |callee| {
+                        format!("{:#x}", callee.instruction)
+                    }),
+                ),
+                ("sp_value".to_owned(), format!("{:#x}", value.unwrap_or(0))),
+            ])
+        }
+    }
+
+    let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little)
+            .append_repeated(0, 0x10)
+            .D32(0xfeedface)
+            // A return address for scanning to find
+            .D32(0xabcd5678)
+            .D32(0xdeadbeef)
+            .append_repeated(0, 0x1000 - 0x1c),
+        0x1000,
+    );
+    let name = DumpString::new("foo.dll", Endian::Little);
+    let module =
+        minidump_synth::Module::new(Endian::Little, 0xabcd0000, 0x10000, &name, 0, 0, None);
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_module(module)
+        .add(name)
+        .add_system_info(SystemInfo::new(Endian::Little))
+        .add(context)
+        .add_memory(stack);
+    let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+
+    let analyzer = StackPeeker::default();
+    let mut options = ProcessorOptions::default();
+    options.frame_analyzer = Some(&analyzer);
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![]));
+    let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+        .await
+        .unwrap();
+
+    let frames = &state.threads[0].frames;
+    assert_eq!(analyzer.frames.load(Ordering::Relaxed), frames.len());
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].annotations["callee"], "none");
+    assert_eq!(frames[0].annotations["sp_value"], "0xfeedface");
+    // The caller found by scanning gets the context frame as its callee.
+    assert_eq!(frames[1].instruction, 0xabcd5677);
+    assert_eq!(frames[1].annotations["callee"], "0xabcd1234");
+    assert_eq!(frames[1].annotations["sp_value"], "0xdeadbeef");
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+    assert_eq!(
+        json["threads"][0]["frames"][0]["annotations"],
+        serde_json::json!({ "callee": "none", "sp_value": "0xfeedface" })
+    );
+}
+
+#[tokio::test]
+async fn test_extra_info() {
+    use minidump_processor::{ExtraInfoProvider, JsonFields, ProcessorOptions};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    struct Pipeline;
+
+    impl ExtraInfoProvider for Pipeline {
+        fn module_annotations(&self, module_name: &str) -> BTreeMap<String, String> {
+            let mut annotations = BTreeMap::new();
+            if module_name == "foo.dll" {
+                annotations.insert("owner".to_owned(), "graphics".to_owned());
+            }
+            annotations
+        }
+
+        fn module_cert(&self, module_name: &str) -> Option<String> {
+            (module_name == "foo.dll").then(|| "Foo Corp".to_owned())
+        }
+
+        fn thread_name(&self, thread_id: u32) -> Option<String> {
+            (thread_id == 0x1234 || thread_id == 0x5678).then(|| "main".to_owned())
+        }
+
+        fn thread_name_override(&self, thread_id: u32) -> Option<String> {
+            (thread_id == 0x9abc).then(|| "renamed".to_owned())
+        }
+    }
+
+    let name = DumpString::new("foo.dll", Endian::Little);
+    let module = minidump_synth::Module::new(Endian::Little, 0x10000, 0x1000, &name, 0, 0, None);
+    // The minidump's own names beat the provider's, unless it overrides them.
+    let mut dump = minimal_minidump().add_module(module).add(name);
+    for (id, name) in [(0x5678, "worker"), (0x9abc, "io")] {
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x100),
+            0x2000 * id as u64,
+        );
+        let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x2000 * id);
+        let name = DumpString::new(name, Endian::Little);
+        dump = dump
+            .add_thread(Thread::new(Endian::Little, id, &stack, &context))
+            .add_thread_name(ThreadName::new(Endian::Little, id, Some(&name)))
+            .add(context)
+            .add_memory(stack)
+            .add(name);
+    }
+    let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+
+    let fields = JsonFields::new(
+        json!({ "release_channel": "beta", "status": "not ours to change" })
+            .as_object()
+            .unwrap()
+            .clone(),
+    );
+    let mut options = ProcessorOptions::default();
+    options.extra_info = vec![&Pipeline, &fields];
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![]));
+    let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+        .await
+        .unwrap();
+    assert_eq!(state.threads[0].thread_name.as_deref(), Some("main"));
+    assert_eq!(state.threads[1].thread_name.as_deref(), Some("worker"));
+    assert_eq!(state.threads[2].thread_name.as_deref(), Some("renamed"));
+    assert_eq!(state.cert_info["foo.dll"], "Foo Corp");
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+    assert_eq!(json["release_channel"], "beta");
+    assert_eq!(json["status"], "OK");
+    assert_eq!(
+        json["modules"][0]["annotations"],
+        json!({ "owner": "graphics" })
+    );
+    assert_eq!(json["modules"][0]["cert_subject"], "Foo Corp");
+    assert_eq!(json["threads"][0]["thread_name"], "main");
+}
+
+#[tokio::test]
+async fn test_captured_mac_backtrace() {
+    use minidump_processor::{string_symbol_supplier, ProcessorOptions, ThreadFilter};
+    use std::collections::HashMap;
+
+    // A version 4 crash info record whose backtrace has two frames in foo.
+    let strings = [
+        "",
+        "abort() called",
+        "",
+        "0   foo    0x0000000000011000 abort_it + 0\n1   foo    0x0000000000012008 main + 8\n",
+        "",
+    ];
+    let mut record = Section::with_endian(Endian::Little)
+        .D64(MINIDUMP_STREAM_TYPE::MozMacosCrashInfoStream as u64)
+        .D64(4)
+        .D64(0x1234)
+        .D64(0);
+    for string in strings {
+        record = record.append_bytes(string.as_bytes()).D8(0);
+    }
+    let record = Memory::with_section(record, 0);
+    let mut header = Section::with_endian(Endian::Little)
+        .D32(MINIDUMP_STREAM_TYPE::MozMacosCrashInfoStream as u32)
+        .D32(1)
+        .D32(32)
+        .D32(record.file_size())
+        .D32(record.file_offset());
+    for _ in 1..20 {
+        header = header.D32(0).D32(0);
+    }
+
+    // The only thread isn't walked, so there's no context to borrow.
+    let context = minidump_synth::amd64_context(Endian::Little, 0x11000, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x100),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let name = DumpString::new("foo", Endian::Little);
+    let module = minidump_synth::Module::new(Endian::Little, 0x10000, 0x10000, &name, 0, 0, None);
+    let system_info = SystemInfo::new(Endian::Little).set_processor_architecture(
+        minidump_common::format::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16,
+    );
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add(context)
+        .add_memory(stack)
+        .add_system_info(system_info)
+        .add_module(module)
+        .add(name)
+        .add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::MozMacosCrashInfoStream as u32,
+            section: header,
+        })
+        .add(record);
+    let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+
+    let symbols = HashMap::from([(
+        "foo".to_owned(),
+        "MODULE mac x86_64 0 foo\nFUNC 1000 10 0 abort_it\nFUNC 2000 10 0 main\n".to_owned(),
+    )]);
+    let symbolizer = Symbolizer::new(string_symbol_supplier(symbols));
+    let mut options = ProcessorOptions::default();
+    options.thread_filter = ThreadFilter::Selected {
+        ids: vec![],
+        names: vec![],
+    };
+    let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+        .await
+        .unwrap();
+    assert_eq!(state.threads[0].info, CallStackInfo::NotWalked);
+
+    assert_eq!(state.captured_backtraces.len(), 1);
+    let backtrace = &state.captured_backtraces[0];
+    assert_eq!(backtrace.source, "mac_crash_info");
+    assert_eq!(backtrace.thread_id, Some(0x1234));
+    let frames = backtrace
+        .frames
+        .iter()
+        .map(|frame| {
+            (
+                frame.instruction,
+                frame.trust,
+                frame.function_name.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        frames,
+        vec![
+            (0x11000, FrameTrust::PreWalked, Some("abort_it")),
+            (0x12007, FrameTrust::PreWalked, Some("main")),
+        ]
+    );
+    assert!(matches!(
+        backtrace.frames[0].context.raw,
+        MinidumpRawContext::Amd64(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_mac_boot_args_and_crash_messages() {
+    use minidump::RawMacCrashInfo;
+    use minidump_common::format::{
+        MINIDUMP_MAC_CRASH_INFO_RECORD_4, MINIDUMP_MAC_CRASH_INFO_RECORD_STRINGS_4,
+    };
+    use minidump_processor::MAX_MAC_CRASH_MESSAGE_LEN;
+
+    let bootargs = DumpString::new("-v keepsyms=1", Endian::Little);
+    let dump = minimal_minidump()
+        .add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::MozMacosBootargsStream as u32,
+            section: Section::with_endian(Endian::Little)
+                .D32(MINIDUMP_STREAM_TYPE::MozMacosBootargsStream as u32)
+                .D64(bootargs.file_offset()),
+        })
+        .add(bootargs);
+    let mut state = read_synth_dump(dump).await;
+    assert_eq!(state.mac_boot_args.as_deref(), Some("-v keepsyms=1"));
+    assert!(state.mac_crash_messages().is_empty());
+
+    let record = |message: &str, message2: &str| {
+        RawMacCrashInfo::V4(
+            MINIDUMP_MAC_CRASH_INFO_RECORD_4 {
+                stream_type: 0,
+                version: 4,
+                thread: 0,
+                dialog_mode: 0,
+            },
+            MINIDUMP_MAC_CRASH_INFO_RECORD_STRINGS_4 {
+                module_path: String::new(),
+                message: message.to_owned(),
+                signature_string: String::new(),
+                backtrace: String::new(),
+                message2: message2.to_owned(),
+            },
+        )
+    };
+    let long = "é".repeat(MAX_MAC_CRASH_MESSAGE_LEN);
+    state.mac_crash_info = Some(vec![
+        record("abort() called\n", "Library not loaded: @rpath/Foo"),
+        record("abort() called", "  "),
+        record(&long, ""),
+    ]);
+    let messages = state.mac_crash_messages();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0], "abort() called");
+    assert_eq!(messages[1], "Library not loaded: @rpath/Foo");
+    assert!(messages[2].ends_with("..."));
+    assert_eq!(messages[2].len(), MAX_MAC_CRASH_MESSAGE_LEN + 3);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+    assert_eq!(json["mac_boot_args"], "-v keepsyms=1");
+    assert_eq!(
+        json["mac_crash_info"]["messages"][1],
+        "Library not loaded: @rpath/Foo"
+    );
+
+    let mut human = Vec::new();
+    state.print(&mut human).unwrap();
+    let human = String::from_utf8(human).unwrap();
+    assert!(human.contains("Boot args: -v keepsyms=1"));
+    assert!(human.contains("Crash info message: Library not loaded: @rpath/Foo"));
+}
+
+#[tokio::test]
+async fn test_process_create_time_sources() {
+    use minidump_processor::ProcessCreateTimeSource;
+    use std::time::Duration;
+
+    // The synthetic minidump's header says it was written at this time.
+    const DUMP_TIME: u64 = 1262805309;
+    let thread_infos = || {
+        let filetime = (DUMP_TIME - 9 + 11_644_473_600) * 10_000_000;
+        let thread_info = |thread_id: u32, create_time: u64| {
+            Section::with_endian(Endian::Little)
+                .D32(thread_id)
+                .D32(0) // dump_flags
+                .D32(0) // dump_error
+                .D32(0) // exit_status
+                .D64(create_time)
+                .D64(0) // exit_time
+                .D64(0) // kernel_time
+                .D64(0) // user_time
+                .D64(0) // start_address
+                .D64(0) // affinity
+        };
+        ExListStream::new(
+            MINIDUMP_STREAM_TYPE::ThreadInfoListStream,
+            std::mem::size_of::<minidump_common::format::MINIDUMP_THREAD_INFO>(),
+            Endian::Little,
+        )
+        .add(thread_info(0x1234, filetime + 50_000_000))
+        .add(thread_info(0x1235, filetime))
+        .add(thread_info(0x1236, 0))
+    };
+
+    let state = read_synth_dump(minimal_minidump().add_stream(thread_infos())).await;
+    assert_eq!(
+        state.process_create_time_source,
+        Some(ProcessCreateTimeSource::ThreadInfo)
+    );
+    assert_eq!(state.uptime(), Some(Duration::from_secs(9)));
+
+    let crashpad_info = CrashpadInfo::new(Endian::Little).add_module(
+        ModuleCrashpadInfo::new(0, Endian::Little)
+            .add_simple_annotation("StartupTime", &format!("{}.5", DUMP_TIME - 300)),
+    );
+    let state = read_synth_dump(minimal_minidump().add_crashpad_info(crashpad_info)).await;
+    assert_eq!(
+        state.process_create_time_source,
+        Some(ProcessCreateTimeSource::CrashpadAnnotation)
+    );
+    assert_eq!(state.uptime(), Some(Duration::from_secs(300)));
+
+    // MISC_INFO wins over everything else.
+    let mut misc = MiscStream::new(Endian::Little);
+    misc.process_times = Some(MiscFieldsProcessTimes {
+        process_create_time: (DUMP_TIME - 4) as u32,
+        ..Default::default()
+    });
+    let dump = minimal_minidump()
+        .add_stream(misc)
+        .add_stream(thread_infos());
+    let state = read_synth_dump(dump).await;
+    assert_eq!(
+        state.process_create_time_source,
+        Some(ProcessCreateTimeSource::MiscInfo)
+    );
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+    assert_eq!(json["process_create_time"], DUMP_TIME - 4);
+    assert_eq!(json["process_create_time_source"], "misc_info");
+    assert_eq!(json["uptime_seconds"], 4);
+
+    let state = read_synth_dump(minimal_minidump()).await;
+    assert_eq!(state.process_create_time, None);
+    assert_eq!(state.uptime(), None);
+}
+
+#[tokio::test]
+async fn test_memory_info() {
+    use minidump::format::{MemoryProtection, MemoryState, MemoryType};
+    use minidump_processor::ProcessorOptions;
+    use serde_json::json;
+
+    let info = |base: u64, size: u64, state: MemoryState, protection, ty: u32| {
+        minidump_synth::MemoryInfo::new(
+            Endian::Little,
+            base,
+            base,
+            0,
+            size,
+            state.bits(),
+            protection,
+            ty,
+        )
+    };
+    let dump = minimal_minidump()
+        .add_memory_info(info(
+            0x10000,
+            0x1000,
+            MemoryState::MEM_COMMIT,
+            MemoryProtection::PAGE_READWRITE.bits(),
+            MemoryType::MEM_PRIVATE.bits(),
+        ))
+        .add_memory_info(info(
+            0x11000,
+            0x5000,
+            MemoryState::MEM_FREE,
+            MemoryProtection::PAGE_NOACCESS.bits(),
+            0,
+        ))
+        .add_memory_info(info(
+            0x20000,
+            0x2000,
+            MemoryState::MEM_COMMIT,
+            MemoryProtection::PAGE_EXECUTE_READ.bits(),
+            MemoryType::MEM_IMAGE.bits(),
+        ));
+    let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+    let mut options = ProcessorOptions::default();
+    options.report_memory_info = true;
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![]));
+    let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+        .await
+        .unwrap();
+    assert_eq!(state.memory_regions.len(), 3);
+    assert!(state.memory_regions[1].is_free());
+    // The gap after the free region is bigger than the free region.
+    assert_eq!(state.largest_free_vm_block(), Some(0x16000..0x20000));
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+    let regions = json["memory_info"].as_array().unwrap();
+    assert_eq!(regions[2]["base_address"], "0x00020000");
+    assert_eq!(regions[2]["size"], "0x00002000");
+    assert_eq!(regions[2]["state"], "MEM_COMMIT");
+    assert_eq!(regions[2]["protection"], "PAGE_EXECUTE_READ");
+    assert_eq!(regions[2]["type"], "MEM_IMAGE");
+    assert_eq!(
+        json["largest_free_vm_block"],
+        json!({ "base_address": "0x00016000", "size": "0x0000a000" })
+    );
+
+    let maps = b"\
+1000-1fff rw-p 00000000 00:00 0
+2000-2fff r-xs 00000000 08:01 1234 /usr/lib/libfoo.so
+";
+    let state = read_synth_dump(minimal_minidump().set_linux_maps(maps)).await;
+    assert_eq!(state.memory_regions.len(), 2);
+    assert_eq!(state.memory_regions[1].range(), 0x2000..0x3000);
+    assert_eq!(state.memory_regions[1].permissions.as_deref(), Some("r-xs"));
+    assert_eq!(
+        state.memory_regions[1].kind,
+        MemoryKind::MappedFile(Some("/usr/lib/libfoo.so".to_owned()))
+    );
+    assert_eq!(state.largest_free_vm_block(), None);
+
+    // The memory map is left out of the JSON unless it's asked for.
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+    assert_eq!(json["memory_info"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn test_frame_registers() {
+    use minidump_processor::ProcessorOptions;
+
+    let dump = read_test_minidump().unwrap();
+    let mut options = ProcessorOptions::default();
+    options.report_frame_registers = true;
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![testdata_symbol_path()]));
+    let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+        .await
+        .unwrap();
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+    let frames = json["threads"][0]["frames"].as_array().unwrap();
+    // The context frame has every register, callers only the ones that
+    // could be recovered.
+    assert_eq!(frames[0]["registers"]["eax"], "0x00000045");
+    let caller = frames[1]["registers"].as_object().unwrap();
+    assert_eq!(caller["eip"], "0x00404200");
+    assert!(!caller.contains_key("eax"));
+
+    // Without the option, only the crashing thread's first frame has them.
+    let state = minidump_processor::process_minidump(&dump, &symbolizer)
+        .await
+        .unwrap();
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+    assert!(json["threads"][0]["frames"][0].get("registers").is_none());
+    assert!(json["crashing_thread"]["frames"][0]
+        .get("registers")
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_exception_parameters() {
+    // A .NET exception, with its HRESULT in the first parameter.
+    let mut ex = Exception::new(Endian::Little);
+    ex.thread_id = 0x1234;
+    ex.exception_record.exception_code = 0xe0434352;
+    ex.exception_record.exception_flags = 0x1;
+    ex.exception_record.number_parameters = 5;
+    ex.exception_record.exception_information[0] = 0x80131500;
+    ex.exception_record.exception_information[4] = 0x7ff8_0000_0000;
+    let state = read_synth_dump(minimal_minidump().add_exception(ex)).await;
+
+    let info = state.exception_info.as_ref().unwrap();
+    assert_eq!(info.code, 0xe0434352);
+    assert_eq!(info.flags, 0x1);
+    assert_eq!(info.parameters, [0x80131500, 0, 0, 0, 0x7ff8_0000_0000]);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["crash_info"]["exception_code"], "0xe0434352");
+    assert_eq!(json["crash_info"]["exception_flags"], "0x00000001");
+    assert_eq!(
+        json["crash_info"]["exception_parameters"],
+        serde_json::json!([
+            "0x80131500",
+            "0x00000000",
+            "0x00000000",
+            "0x00000000",
+            "0x7ff800000000",
+        ])
+    );
+
+    // Only the full human report has them.
+    let mut human = Vec::new();
+    state.print(&mut human).unwrap();
+    let human = String::from_utf8(human).unwrap();
+    assert!(human.contains("Exception parameters: 0x80131500 0x0 0x0 0x0 0x7ff800000000"));
+    let mut brief = Vec::new();
+    state.print_brief(&mut brief).unwrap();
+    assert!(!String::from_utf8(brief).unwrap().contains("Exception code"));
+}
+
+#[tokio::test]
+async fn test_crash_memory() {
+    use minidump_processor::{MemoryDumpKind, ProcessorOptions};
+
+    let name = DumpString::new("/lib/libfoo.so", Endian::Little);
+    let module = minidump_synth::Module::new(Endian::Little, 0x10000, 0x1000, &name, 0, 0, None);
+    let context = minidump_synth::x86_context(Endian::Little, 0x10020, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little)
+            .append_repeated(0, 0x14)
+            .D32(0x10100)
+            .append_repeated(0, 0xfe8),
+        0x1000,
+    );
+    let code = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0x90, 0x40),
+        0x10000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let mut ex = Exception::new(Endian::Little);
+    ex.thread_id = 0x1234;
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(SystemInfo::new(Endian::Little))
+        .add_module(module)
+        .add_exception(ex)
+        .add(name)
+        .add(context)
+        .add_memory(stack)
+        .add_memory(code);
+    let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+    let mut options = ProcessorOptions::default();
+    options.dump_crash_memory = true;
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![]));
+    let state = minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+        .await
+        .unwrap();
+
+    assert_eq!(state.crash_memory.len(), 2);
+    let (pc_dump, sp_dump) = (&state.crash_memory[0], &state.crash_memory[1]);
+    assert_eq!(pc_dump.kind, MemoryDumpKind::InstructionPointer);
+    assert_eq!(pc_dump.start, 0xffe0);
+    assert_eq!(pc_dump.bytes.len(), 128);
+    // Only the 0x40 bytes of code at the start of the module are in the dump.
+    assert!(pc_dump.bytes[..0x20].iter().all(Option::is_none));
+    assert!(pc_dump.bytes[0x20..0x60].iter().all(|&b| b == Some(0x90)));
+    assert!(pc_dump.bytes[0x60..].iter().all(Option::is_none));
+    assert_eq!(pc_dump.annotations[&0x10020], "libfoo.so+0x20");
+
+    assert_eq!(sp_dump.kind, MemoryDumpKind::StackPointer);
+    assert_eq!(sp_dump.start, 0x1010);
+    assert_eq!(sp_dump.bytes[4..8], [Some(0), Some(1), Some(1), Some(0)]);
+    assert_eq!(sp_dump.annotations.len(), 1);
+    assert_eq!(sp_dump.annotations[&0x1014], "libfoo.so+0x100");
+
+    let mut human = Vec::new();
+    state.print(&mut human).unwrap();
+    let human = String::from_utf8(human).unwrap();
+    assert!(human.contains("Memory around instruction pointer (0x10020):"));
+    assert!(human.contains("  0x000000000000fff0  <not in minidump>\n"));
+    assert!(human.contains(&format!(
+        "  0x0000000000010020 {}  {}  libfoo.so+0x20\n",
+        " 90".repeat(16),
+        ".".repeat(16)
+    )));
+    assert!(human.contains("  0x0000000000010040  <not in minidump>\n"));
+    assert!(human.contains("Memory at stack pointer (0x1010):"));
+    assert!(human.contains(
+        "  0x0000000000001010  00 00 00 00 00 01 01 00 00 00 00 00 00 00 00 00  \
+         ................  libfoo.so+0x100\n"
+    ));
+}
+
+#[tokio::test]
+async fn test_thread_filter() {
+    use minidump_processor::{CallStackInfo, ProcessorOptions, ThreadFilter};
+    use minidump_synth::ThreadName;
+
+    let watchdog = DumpString::new("WatchdogThread", Endian::Little);
+    let worker = DumpString::new("Worker", Endian::Little);
+    let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let mut ex = Exception::new(Endian::Little);
+    ex.thread_id = 1;
+    let mut dump = SynthMinidump::with_endian(Endian::Little)
+        .add_system_info(SystemInfo::new(Endian::Little))
+        .add_exception(ex)
+        .add_thread_name(ThreadName::new(Endian::Little, 2, Some(&watchdog)))
+        .add_thread_name(ThreadName::new(Endian::Little, 4, Some(&worker)))
+        .add(watchdog)
+        .add(worker);
+    for id in 1..=4 {
+        dump = dump.add_thread(Thread::new(Endian::Little, id, &stack, &context));
+    }
+    let dump = Minidump::read(dump.add(context).add_memory(stack).finish().unwrap()).unwrap();
+    let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![]));
+
+    let walked = |filter: ThreadFilter| {
+        let mut options = ProcessorOptions::default();
+        options.thread_filter = filter;
+        let dump = &dump;
+        let symbolizer = &symbolizer;
+        async move {
+            minidump_processor::process_minidump_with_options(dump, symbolizer, options)
+                .await
+                .unwrap()
+        }
+    };
+
+    let state = walked(ThreadFilter::Selected {
+        ids: vec![3],
+        names: vec!["Watchdog*".to_owned()],
+    })
+    .await;
+    // Threads that aren't walked are still listed, so the indices don't change.
+    assert_eq!(state.threads.len(), 4);
+    assert_eq!(state.requesting_thread, Some(0));
+    for (stack, walked) in state.threads.iter().zip([true, true, true, false]) {
+        assert_eq!(stack.info == CallStackInfo::NotWalked, !walked);
+        assert_eq!(stack.frames.is_empty(), !walked);
+    }
+    assert_eq!(state.threads[3].thread_name.as_deref(), Some("Worker"));
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["threads"][2]["not_walked"], false);
+    assert_eq!(json["threads"][3]["not_walked"], true);
+    assert_eq!(json["threads"][3]["frame_count"], 0);
+
+    let mut human = Vec::new();
+    state.print(&mut human).unwrap();
+    assert!(String::from_utf8(human)
+        .unwrap()
+        .contains("Thread 3 Worker\n<not walked>\n"));
+
+    let state = walked(ThreadFilter::CrashingThread).await;
+    let not_walked = state
+        .threads
+        .iter()
+        .filter(|stack| stack.info == CallStackInfo::NotWalked)
+        .count();
+    assert_eq!(not_walked, 3);
+    assert!(!state.crashing_thread().unwrap().frames.is_empty());
+}
+
+#[tokio::test]
+async fn test_unloaded_module_symbols() {
+    use minidump_processor::string_symbol_supplier;
+    use std::collections::HashMap;
+
+    // The crashing instruction is at offset 0x800 of plugin.dll, which was
+    // unloaded, and then maybe loaded again somewhere else.
+    async fn process(loaded_time_date_stamp: Option<u32>, symbols_name: &str) -> ProcessState {
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(
+                minidump_common::format::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16,
+            )
+            .set_platform_id(minidump_common::format::PlatformId::VER_PLATFORM_WIN32_NT as u32);
+        let context = minidump_synth::amd64_context(Endian::Little, 0x20800, 0x1010);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x1000,
+        );
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        let unloaded_name = DumpString::new("plugin.dll", Endian::Little);
+        let loaded_name = DumpString::new("C:\\app\\plugin.dll", Endian::Little);
+        let mut dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add_system_info(system_info)
+            .add(context)
+            .add_memory(stack)
+            .add_unloaded_module(UnloadedModule::new(
+                Endian::Little,
+                0x20000,
+                0x1000,
+                &unloaded_name,
+                0x1111,
+                0xaaaa,
+            ))
+            .add(unloaded_name);
+        if let Some(time_date_stamp) = loaded_time_date_stamp {
+            dump = dump.add_module(minidump_synth::Module::new(
+                Endian::Little,
+                0x50000,
+                0x1000,
+                &loaded_name,
+                time_date_stamp,
+                0xaaaa,
+                None,
+            ));
+        }
+        let dump = Minidump::read(dump.add(loaded_name).finish().unwrap()).unwrap();
+        let symbols = HashMap::from([(
+            symbols_name.to_owned(),
+            "MODULE windows x86_64 0 plugin.pdb\nFUNC 800 20 0 plugin_callback\n".to_owned(),
+        )]);
+        let symbolizer = Symbolizer::new(string_symbol_supplier(symbols));
+        minidump_processor::process_minidump(&dump, &symbolizer)
+            .await
+            .unwrap()
+    }
+
+    // A copy of the same module is still loaded, so its symbols are used.
+    let state = process(Some(0x1111), "C:\\app\\plugin.dll").await;
+    let frame = &state.threads[0].frames[0];
+    assert!(frame.module.is_none());
+    assert_eq!(frame.function_name.as_deref(), Some("plugin_callback"));
+    assert_eq!(frame.function_base, Some(0x20800));
+    let unloaded = frame.unloaded_module_symbols.as_ref().unwrap();
+    assert_eq!(
+        (unloaded.name.as_str(), unloaded.offset),
+        ("plugin.dll", 0x800)
+    );
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let frame = &json["threads"][0]["frames"][0];
+    assert_eq!(frame["function"], "plugin_callback");
+    assert_eq!(frame["function_offset"], "0x0000000000000000");
+    assert_eq!(frame["missing_symbols"], false);
+    assert_eq!(
+        frame["unloaded_module_symbols"],
+        serde_json::json!({ "module": "plugin.dll", "offset": "0x0000000000000800" })
+    );
+
+    let mut human = Vec::new();
+    state.print(&mut human).unwrap();
+    let human = String::from_utf8(human).unwrap();
+    assert!(human.contains(" 0  0x20800 (in unloaded plugin.dll!plugin_callback + 0x0)\n"));
+
+    // With no copy loaded, the unloaded module is looked up on its own.
+    let state = process(None, "plugin.dll").await;
+    let frame = &state.threads[0].frames[0];
+    assert_eq!(frame.function_name.as_deref(), Some("plugin_callback"));
+    assert!(frame.unloaded_module_symbols.is_some());
+
+    // A different version of the module is loaded now, whose symbols won't
+    // do, so the frame is left alone.
+    let state = process(Some(0x2222), "C:\\app\\plugin.dll").await;
+    let frame = &state.threads[0].frames[0];
+    assert_eq!(frame.function_name, None);
+    assert_eq!(frame.unloaded_module_symbols, None);
+}
+
+#[tokio::test]
+async fn test_unloaded_module_symbols_on_disk() {
+    // The crashing instruction is at offset 0x800 of plugin.dll, which was
+    // unloaded and has no debug file or identifier.
+    let system_info = SystemInfo::new(Endian::Little)
+        .set_processor_architecture(
+            minidump_common::format::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16,
+        )
+        .set_platform_id(minidump_common::format::PlatformId::VER_PLATFORM_WIN32_NT as u32);
+    let context = minidump_synth::amd64_context(Endian::Little, 0x20800, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let name = DumpString::new("plugin.dll", Endian::Little);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(system_info)
+        .add(context)
+        .add_memory(stack)
+        .add_unloaded_module(UnloadedModule::new(
+            Endian::Little,
+            0x20000,
+            0x1000,
+            &name,
+            0x1111,
+            0xaaaa,
+        ))
+        .add(name);
+    let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+
+    let symbols_path = std::env::temp_dir().join(format!(
+        "rust-minidump-unloaded-symbols-{}",
+        std::process::id()
+    ));
+    let process = || async {
+        let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![symbols_path.clone()]));
+        minidump_processor::process_minidump(&dump, &symbolizer)
+            .await
+            .unwrap()
+    };
+
+    // The symbols aren't there, and the failed lookup isn't reported.
+    let state = process().await;
+    let frame = &state.threads[0].frames[0];
+    assert_eq!(frame.function_name, None);
+    assert_eq!(frame.unloaded_module_symbols, None);
+    assert!(!state.symbol_stats.contains_key("plugin.dll"));
+
+    // The symbols are found by the module's code file and identifier.
+    let sym_dir = symbols_path.join("plugin.dll").join("000011111000");
+    std::fs::create_dir_all(&sym_dir).unwrap();
+    std::fs::write(
+        sym_dir.join("plugin.sym"),
+        "MODULE windows x86_64 0 plugin.pdb\nFUNC 800 20 0 plugin_callback\n",
+    )
+    .unwrap();
+    let state = process().await;
+    std::fs::remove_dir_all(&symbols_path).unwrap();
+    let frame = &state.threads[0].frames[0];
+    assert_eq!(frame.function_name.as_deref(), Some("plugin_callback"));
+    assert!(frame.unloaded_module_symbols.is_some());
+    assert!(state.symbol_stats["plugin.dll"].loaded_symbols);
+}
+
+#[tokio::test]
+async fn test_guess_crashing_thread() {
+    use minidump_processor::{
+        string_symbol_supplier, CallStackInfo, CrashingThreadGuessReason, ProcessorOptions,
+        ThreadFilter,
+    };
+    use std::collections::HashMap;
+
+    // Threads with the given ids and instruction pointers, where 0xabcd1000
+    // is in `work`, 0xabcd2000 in `abort`, and 0xabcd3000 in a signal
+    // handler trampoline, and maybe an exception stream
+    // for some thread id (with no context of its own).
+    async fn process(
+        threads: &[(u32, u32)],
+        exception_thread_id: Option<u32>,
+        guess: bool,
+    ) -> ProcessState {
+        let mut options = ProcessorOptions::default();
+        options.guess_crashing_thread = guess;
+        process_with(threads, exception_thread_id, options).await
+    }
+
+    async fn process_with(
+        threads: &[(u32, u32)],
+        exception_thread_id: Option<u32>,
+        options: ProcessorOptions<'_>,
+    ) -> ProcessState {
+        let name = DumpString::new("foo.dll", Endian::Little);
+        let module =
+            minidump_synth::Module::new(Endian::Little, 0xabcd0000, 0x10000, &name, 0, 0, None);
+        let mut dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(SystemInfo::new(Endian::Little))
+            .add_module(module)
+            .add(name);
+        for (i, &(id, eip)) in threads.iter().enumerate() {
+            let base = 0x10000 * (i as u64 + 1);
+            let context = minidump_synth::x86_context(Endian::Little, eip, base as u32 + 0x10);
+            let stack = Memory::with_section(
+                Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+                base,
+            );
+            let thread = Thread::new(Endian::Little, id, &stack, &context);
+            dump = dump.add_thread(thread).add(context).add_memory(stack);
+        }
+        if let Some(id) = exception_thread_id {
+            let mut ex = Exception::new(Endian::Little);
+            ex.thread_id = id;
+            dump = dump.add_exception(ex);
+        }
+        let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+
+        let symbols = HashMap::from([(
+            "foo.dll".to_owned(),
+            "MODULE windows x86 0 foo.pdb\n\
+             FUNC 1000 100 0 work\n\
+             FUNC 2000 100 0 abort\n\
+             FUNC 3000 100 0 __restore_rt\n"
+                .to_owned(),
+        )]);
+        let symbolizer = Symbolizer::new(string_symbol_supplier(symbols));
+        minidump_processor::process_minidump_with_options(&dump, &symbolizer, options)
+            .await
+            .unwrap()
+    }
+
+    // A thread that jumped to null.
+    let threads = [(0x1, 0xabcd1000), (0x2, 0x10), (0x3, 0xabcd1000)];
+    let state = process(&threads, None, true).await;
+    assert_eq!(state.requesting_thread, Some(1));
+    let guess = state.crashing_thread_guess.as_ref().unwrap();
+    assert_eq!(guess.thread_id, 0x2);
+    assert_eq!(guess.reasons, vec![CrashingThreadGuessReason::PcNearNull]);
+    assert!(!state.crashing_thread_heuristic);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["crash_info"]["crashing_thread"], 1);
+    assert_eq!(
+        json["crash_info"]["crashing_thread_guess"],
+        serde_json::json!({ "reasons": ["pc_near_null"] })
+    );
+
+    let mut human = Vec::new();
+    state.print(&mut human).unwrap();
+    let human = String::from_utf8(human).unwrap();
+    assert!(human.contains(
+        "Crashing thread: guessed (no usable exception stream): instruction pointer near null\n"
+    ));
+
+    // The guess is made before only the crashing thread is kept.
+    let threads = [(0x1, 0xabcd1000), (0x2, 0xabcd1000), (0x3, 0xabcd2000)];
+    let mut options = ProcessorOptions::default();
+    options.guess_crashing_thread = true;
+    options.thread_filter = ThreadFilter::CrashingThread;
+    let state = process_with(&threads, None, options).await;
+    assert_eq!(state.requesting_thread, Some(2));
+    let infos = state
+        .threads
+        .iter()
+        .map(|stack| stack.info.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        infos,
+        vec![
+            CallStackInfo::NotWalked,
+            CallStackInfo::NotWalked,
+            CallStackInfo::Ok
+        ]
+    );
+    assert_eq!(
+        state.threads[2].frames[0].function_name.as_deref(),
+        Some("abort")
+    );
+
+    // Only when asked to.
+    let state = process(&threads, None, false).await;
+    assert_eq!(state.requesting_thread, None);
+    assert_eq!(state.crashing_thread_guess, None);
+
+    // Being in an abort function is more telling than a bad instruction pointer.
+    let threads = [(0x1, 0xabcd1000), (0x2, 0x10), (0x3, 0xabcd2000)];
+    let state = process(&threads, None, true).await;
+    assert_eq!(state.requesting_thread, Some(2));
+    assert_eq!(
+        state.crashing_thread_guess.unwrap().reasons,
+        vec![CrashingThreadGuessReason::InAbortFunction {
+            function: "abort".to_owned()
+        }]
+    );
+
+    let threads = [(0x1, 0xabcd1000), (0x2, 0xabcd3000)];
+    let state = process(&threads, None, true).await;
+    assert_eq!(state.requesting_thread, Some(1));
+    assert_eq!(
+        state.crashing_thread_guess.unwrap().reasons,
+        vec![CrashingThreadGuessReason::SignalHandlerFrame {
+            function: "__restore_rt".to_owned()
+        }]
+    );
+
+    // An exception stream for a thread that isn't in the dump is no help.
+    let threads = [(0x1, 0xabcd1000), (0x2, 0x10), (0x3, 0xabcd2000)];
+    let state = process(&threads, Some(0x9999), true).await;
+    assert_eq!(state.requesting_thread, Some(2));
+    assert!(state.crashing_thread_guess.is_some());
+
+    // But a usable one is never second-guessed.
+    let state = process(&threads, Some(0x1), true).await;
+    assert_eq!(state.requesting_thread, Some(0));
+    assert_eq!(state.crashing_thread_guess, None);
+
+    // Nothing is guessed when there's no telling the threads apart.
+    let threads = [(0x1, 0x10), (0x2, 0x20)];
+    let state = process(&threads, None, true).await;
+    assert_eq!(state.requesting_thread, None);
+    assert_eq!(state.crashing_thread_guess, None);
+}
+
+#[tokio::test]
+async fn test_sanitizer_report() {
+    use minidump_processor::{ProcessorOptions, SanitizerReportKind};
+
+    let report = "==4242==ERROR: AddressSanitizer: heap-use-after-free on address 0x602000000010\n\
+                  SUMMARY: AddressSanitizer: heap-use-after-free test.c:5 in main\n";
+    let process = |extract: bool| async move {
+        let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x1000,
+        );
+        let heap = Memory::with_section(
+            Section::with_endian(Endian::Little)
+                .append_bytes(report.as_bytes())
+                .D8(0),
+            0x60000000,
+        );
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add_system_info(SystemInfo::new(Endian::Little))
+            .add(context)
+            .add_memory(stack)
+            .add_memory(heap);
+        let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+        let mut options = ProcessorOptions::default();
+        options.extract_sanitizer_report = extract;
+        minidump_processor::process_minidump_with_options(
+            &dump,
+            &Symbolizer::new(simple_symbol_supplier(vec![])),
+            options,
+        )
+        .await
+        .unwrap()
+    };
+
+    let state = process(true).await;
+    let found = state.sanitizer_report.as_ref().unwrap();
+    assert_eq!(found.kind, SanitizerReportKind::Full);
+    assert_eq!(found.address, 0x60000000);
+    assert_eq!(found.text, report.trim_end());
+    assert!(!found.truncated);
+
+    let mut json = Vec::new();
+    state.print_json(&mut json, false).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["sanitizer_report"]["kind"], "full");
+    assert_eq!(json["sanitizer_report"]["address"], "0x60000000");
+    assert_eq!(json["sanitizer_report"]["truncated"], false);
+
+    let state = process(false).await;
+    assert_eq!(state.sanitizer_report, None);
+}
+
+#[tokio::test]
+async fn test_sanitizer_report_memory64() {
+    use minidump_processor::{ProcessorOptions, SanitizerReportKind};
+
+    // Full memory dumps are all Memory64 regions, which are read one at a
+    // time, and the report (and its header) can be split across them.
+    let report = "==4242==ERROR: AddressSanitizer: heap-use-after-free on address 0x602000000010\n\
+                  SUMMARY: AddressSanitizer: heap-use-after-free test.c:5 in main\n";
+    let (first, second) = report.as_bytes().split_at(20);
+    let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+    let stack = Memory::with_section(
+        Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+        0x1000,
+    );
+    let heap0 = Memory::with_section(
+        Section::with_endian(Endian::Little).append_bytes(first),
+        0x60000000,
+    );
+    let heap1 = Memory::with_section(
+        Section::with_endian(Endian::Little)
+            .append_bytes(second)
+            .D8(0),
+        0x60000000 + first.len() as u64,
+    );
+    let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+    let dump = SynthMinidump::with_endian(Endian::Little)
+        .add_thread(thread)
+        .add_system_info(SystemInfo::new(Endian::Little))
+        .add(context)
+        .add(stack)
+        .add_memory64(heap0)
+        .add_memory64(heap1);
+    let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+    let mut options = ProcessorOptions::default();
+    options.extract_sanitizer_report = true;
+    let state = minidump_processor::process_minidump_with_options(
+        &dump,
+        &Symbolizer::new(simple_symbol_supplier(vec![])),
+        options,
+    )
+    .await
+    .unwrap();
+
+    let found = state.sanitizer_report.as_ref().unwrap();
+    assert_eq!(found.kind, SanitizerReportKind::Full);
+    assert_eq!(found.address, 0x60000000);
+    assert_eq!(found.text, report.trim_end());
+    assert!(!found.truncated);
+}
@@ -13,9 +13,9 @@ use std::{boxed::Box, path::PathBuf};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use minidump::*;
 use minidump_processor::{
-    http_symbol_supplier, simple_symbol_supplier, MultiSymbolProvider,
-    PendingProcessorStatSubscriptions, PendingProcessorStats, ProcessorOptions, SymbolProvider,
-    Symbolizer,
+    http_symbol_supplier, simple_symbol_supplier, JsonFields, MultiSymbolProvider,
+    NameNormalization, PendingProcessorStatSubscriptions, PendingProcessorStats, ProcessorOptions,
+    SymbolProvider, Symbolizer, ThreadFilter,
 };
 
 use clap::{
@@ -118,7 +118,11 @@ struct Cli {
     ///  
     /// stable-all enables: nothing (currently identical to stable-basic)
     ///  
-    /// unstable-all enables: `--recover-function-args`
+    /// unstable-all enables: `--recover-function-args`, `--guess-crashing-thread`,
+    /// `--paranoid-scan`, `--validate-registers`, `--noreturn-functions`,
+    /// `--name-normalization`, `--check-cfi-plausibility`, `--detect-rop-chains`,
+    /// `--cross-validate-cfi`, `--stop-at-stack-bottom`, `--analyze-exploitability`,
+    /// `--extract-sanitizer-report`
     ///  
     /// minidump-stackwalk wants to be a reliable and stable tool, but we also want to be able
     /// to introduce new features which may be experimental or expensive. To balance these two
@@ -174,6 +178,13 @@ struct Cli {
     #[arg(long)]
     brief: bool,
 
+    /// Include floating point and vector registers in the --human report
+    ///
+    /// These are only available for the first frame of each thread, and currently
+    /// only for ARM64 minidumps and amd64 minidumps that include AVX state.
+    #[arg(long)]
+    fp_registers: bool,
+
     /// Disable all interactive progress feedback
     ///
     /// We'll generally try to auto-detect when this should be disabled, but this is here in
@@ -189,12 +200,167 @@ struct Cli {
     #[arg(long)]
     evil_json: Option<PathBuf>,
 
+    /// An input JSON file with extra fields to add to the JSON output
+    ///
+    /// The file must contain a JSON object, whose fields are added to the top level of the
+    /// output (for instance a release channel or experiment flags the crash reporter sent
+    /// alongside the minidump). Fields the output already has are never replaced.
+    #[arg(long)]
+    extra_json: Option<PathBuf>,
+
     /// **UNSTABLE** Heuristically recover function arguments
     ///
     /// This is an experimental feature, which currently only shows up in --human output.
     #[arg(long)]
     recover_function_args: bool,
 
+    /// **UNSTABLE** Guess which thread crashed when the minidump doesn't say
+    ///
+    /// If the minidump has no exception stream, or one for a thread that isn't in it, the
+    /// thread that looks most like it faulted (an instruction pointer near null or outside
+    /// executable memory, or a frame in an abort function or signal handler) is reported as
+    /// the crashing thread, along with why it was picked.
+    #[arg(long)]
+    guess_crashing_thread: bool,
+
+    /// **UNSTABLE** Only accept scanned return addresses that pass every check
+    ///
+    /// Stack scanning normally takes any value that points into a loaded module to be a return
+    /// address. With this it must also be in a function the module's symbols know about, and
+    /// on x86 and x86-64 it must be in executable memory, right after a call (if the minidump
+    /// has the memory to tell). This finds fewer frames, but fewer bogus ones too.
+    #[arg(long)]
+    paranoid_scan: bool,
+
+    /// **UNSTABLE** Check unwound frames' registers against what the architecture allows
+    ///
+    /// For instance a misaligned stack pointer on ARM64, or a MIPS `$gp` that doesn't point into
+    /// any module. Problems are listed in the frame's warnings, but don't stop the stack walk.
+    #[arg(long)]
+    validate_registers: bool,
+
+    /// **UNSTABLE** Flag frames in functions that never return, like `abort`
+    ///
+    /// Frames in the abort, assertion, and panic functions of the major C and C++ runtimes and
+    /// Rust are marked as noreturn, and if the crashing thread has any, the crash is reported as a
+    /// likely abort rather than a hardware fault.
+    #[arg(long)]
+    noreturn_functions: bool,
+
+    /// **UNSTABLE** Normalize function names, so names from different toolchains can be compared
+    ///
+    /// Anonymous namespaces and lambdas are spelled the same way whichever compiler produced the
+    /// symbols, and template arguments are removed. The normalized names are output alongside the
+    /// raw ones.
+    #[arg(long)]
+    name_normalization: bool,
+
+    /// **UNSTABLE** Lower the trust of CFI frames whose registers look implausible
+    ///
+    /// A caller stack pointer below the callee's, a frame pointer into the stack below the stack
+    /// pointer, or a callee-saved register restored to zero usually means the call frame info is
+    /// wrong. Such frames are kept, but found by `cfi_low_confidence`.
+    #[arg(long)]
+    check_cfi_plausibility: bool,
+
+    /// **UNSTABLE** Keep each thread's stack walk within its declared stack
+    ///
+    /// Memory outside the stack range the thread's descriptor declares isn't read, and the walk
+    /// stops at the first frame whose stack pointer leaves it. This keeps walks from wandering
+    /// into a neighbouring thread's stack, but breaks ones that legitimately leave the stack, like
+    /// signal handlers running on an alternate signal stack, so unstable-all doesn't enable it.
+    #[arg(long)]
+    constrain_to_thread_stack: bool,
+
+    /// **UNSTABLE** Score the crashing thread's stack for looking like a ROP chain
+    ///
+    /// A long run of tightly packed scanned frames, whose return addresses don't follow call
+    /// instructions, is what a stack looks like while a return-oriented-programming exploit is
+    /// running.
+    #[arg(long)]
+    detect_rop_chains: bool,
+
+    /// **UNSTABLE** Check frames found with call frame info against the frame pointer chain
+    ///
+    /// When both agree on the caller, a frame that `--check-cfi-plausibility` downgraded gets its
+    /// full trust back. When they disagree, the CFI frame is still used, but the disagreement is
+    /// listed in the frame's warnings.
+    #[arg(long)]
+    cross_validate_cfi: bool,
+
+    /// **UNSTABLE** Stop a thread's stack walk at the bottom of its declared stack
+    ///
+    /// No caller can have a stack pointer past the end of the thread's stack, so a walk that finds
+    /// one (or whose last frame is right at the end) stops there, instead of relying on the usual
+    /// heuristics for where stacks end.
+    #[arg(long)]
+    stop_at_stack_bottom: bool,
+
+    /// **UNSTABLE** Rate how likely the crash is to be exploitable
+    ///
+    /// This looks for signs like writes to wild addresses, an instruction pointer outside of any
+    /// module, or a failed stack cookie check, and rates the crash by the worst one it finds.
+    #[arg(long)]
+    analyze_exploitability: bool,
+
+    /// **UNSTABLE** Look for an AddressSanitizer report in the captured memory
+    ///
+    /// Processes built with ASan abort after printing a report of the memory error they caught,
+    /// which is often still in memory. The first report found (or just its `SUMMARY:` line, if
+    /// that's all there is) is added to the output.
+    #[arg(long)]
+    extract_sanitizer_report: bool,
+
+    /// The maximum amount of time (in seconds) to spend walking any one thread's stack
+    ///
+    /// A thread whose stack walk runs over this keeps the frames found so far, and is
+    /// marked as timed out in the output. By default there is no limit.
+    #[arg(long)]
+    frame_walk_timeout: Option<u64>,
+
+    /// Include detailed statistics on looking up each module's symbols in the --json output
+    ///
+    /// This adds a `symbol_stats` section saying how long each lookup took, where the
+    /// symbols came from, and how many bytes were downloaded.
+    #[arg(long)]
+    symbol_stats: bool,
+
+    /// Include the process's memory map in the --json output
+    ///
+    /// This adds a `memory_info` section with every region of the process's address space
+    /// that the minidump describes, which can be large for full memory dumps.
+    #[arg(long)]
+    memory_info: bool,
+
+    /// Include the registers of every frame in the --json output
+    ///
+    /// Normally only the first frame of the crashing thread has its registers in the output.
+    /// This gives every frame the registers that were recovered for it.
+    #[arg(long)]
+    frame_registers: bool,
+
+    /// Include hexdumps of the memory around the crashing instruction and stack pointers in the
+    /// human output
+    ///
+    /// Bytes the minidump doesn't have are shown as `??`, and addresses that point into modules
+    /// are annotated with the module and offset.
+    #[arg(long)]
+    memory_dump: bool,
+
+    /// Only walk the stacks of these threads (and the crashing thread)
+    ///
+    /// A comma-separated list of thread ids (in decimal, or hex with a `0x` prefix) and thread
+    /// names, which can use `*` and `?` wildcards. The other threads are still listed, but
+    /// without any frames.
+    #[arg(long, value_delimiter = ',')]
+    threads: Vec<String>,
+
+    /// Only walk the stack of the crashing thread
+    ///
+    /// The other threads are still listed, but without any frames.
+    #[arg(long, conflicts_with = "threads")]
+    only_crashing_thread: bool,
+
     /// base URL from which URLs to symbol files can be constructed
     ///
     /// If multiple symbols-url values are provided, they will each be tried in order until
@@ -357,6 +523,11 @@ async fn main() {
         std::process::exit(1);
     }
 
+    if cli.fp_registers && (!human || cli.brief) {
+        error!("The --fp-registers flag is only valid for a non-brief --human report");
+        std::process::exit(1);
+    }
+
     if cli.brief && !(human || raw_dump) {
         error!("Robots cannot be brief! (The --brief flag is only valid for --human, --cyborg, and --dump)");
         std::process::exit(1);
@@ -370,9 +541,57 @@ async fn main() {
         _ => unimplemented!("unknown --features value"),
     };
 
+    let extra_json = cli.extra_json.as_ref().map(|path| {
+        JsonFields::from_path(path).unwrap_or_else(|e| {
+            error!("Could not read --extra-json file {}: {}", path.display(), e);
+            std::process::exit(1);
+        })
+    });
+
     // Now overload the defaults
     options.evil_json = cli.evil_json.as_deref();
+    if let Some(extra_json) = &extra_json {
+        options.extra_info.push(extra_json);
+    }
     options.recover_function_args = cli.recover_function_args;
+    options.guess_crashing_thread |= cli.guess_crashing_thread;
+    options.paranoid_scan |= cli.paranoid_scan;
+    options.validate_registers |= cli.validate_registers;
+    if cli.noreturn_functions && options.noreturn_functions.is_empty() {
+        options.noreturn_functions = ProcessorOptions::default_noreturn_functions();
+    }
+    if cli.name_normalization {
+        options.name_normalization = NameNormalization::all();
+    }
+    options.check_cfi_plausibility |= cli.check_cfi_plausibility;
+    options.constrain_to_thread_stack |= cli.constrain_to_thread_stack;
+    options.detect_rop_chains |= cli.detect_rop_chains;
+    options.cross_validate_cfi |= cli.cross_validate_cfi;
+    options.stop_at_stack_bottom |= cli.stop_at_stack_bottom;
+    options.analyze_exploitability |= cli.analyze_exploitability;
+    options.extract_sanitizer_report |= cli.extract_sanitizer_report;
+    options.frame_walk_timeout = cli.frame_walk_timeout.map(Duration::from_secs);
+    options.report_symbol_stats = cli.symbol_stats;
+    options.report_memory_info = cli.memory_info;
+    options.report_frame_registers = cli.frame_registers;
+    options.dump_crash_memory = cli.memory_dump;
+    if cli.only_crashing_thread {
+        options.thread_filter = ThreadFilter::CrashingThread;
+    } else if !cli.threads.is_empty() {
+        let mut ids = Vec::new();
+        let mut names = Vec::new();
+        for thread in cli.threads {
+            let id = match thread.strip_prefix("0x") {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => thread.parse().ok(),
+            };
+            match id {
+                Some(id) => ids.push(id),
+                None => names.push(thread),
+            }
+        }
+        options.thread_filter = ThreadFilter::Selected { ids, names };
+    }
 
     // Register for instractive updates, if we want them
     let interactive_enabled = !json && !cli.no_interactive && cli.output_file.is_none();
@@ -387,7 +606,11 @@ async fn main() {
 
     // Ok now let's do the thing!!!!
 
-    match Minidump::read_path(cli.minidump) {
+    // Read what we can of damaged dumps, and report the damage.
+    let mut read_options = MinidumpReadOptions::default();
+    read_options.check_integrity = true;
+
+    match Minidump::read_path_with_options(cli.minidump, &read_options) {
         Ok(dump) => {
             let mut stdout;
             let mut output_f;
@@ -462,6 +685,8 @@ async fn main() {
                     if human {
                         if cli.brief {
                             state.print_brief(&mut output).unwrap();
+                        } else if cli.fp_registers {
+                            state.print_with_fp_registers(&mut output).unwrap();
                         } else {
                             state.print(&mut output).unwrap();
                         }
@@ -630,12 +855,13 @@ where
     let system_info = dump.get_stream::<MinidumpSystemInfo>().ok();
     let memory_list = dump.get_stream::<MinidumpMemoryList<'_>>().ok();
     let memory64_list = dump.get_stream::<MinidumpMemory64List<'_>>().ok();
+    let memory = dump.get_memory();
     let misc_info = dump.get_stream::<MinidumpMiscInfo>().ok();
 
     if let Ok(thread_list) = dump.get_stream::<MinidumpThreadList<'_>>() {
         thread_list.print(
             output,
-            memory_list.as_ref(),
+            memory.as_ref(),
             system_info.as_ref(),
             misc_info.as_ref(),
             brief,
@@ -668,9 +894,33 @@ where
     if let Some(misc_info) = misc_info {
         misc_info.print(output)?;
     }
+    if let Ok(memory_info) = dump.get_stream::<MinidumpSystemMemoryInfo>() {
+        memory_info.print(output)?;
+    }
+    if let Ok(vm_counters) = dump.get_stream::<MinidumpProcessVmCounters>() {
+        vm_counters.print(output)?;
+    }
     if let Ok(thread_names) = dump.get_stream::<MinidumpThreadNames>() {
         thread_names.print(output)?;
     }
+    if let Ok(thread_infos) = dump.get_stream::<MinidumpThreadInfoList>() {
+        thread_infos.print(output)?;
+    }
+    if let Ok(function_table) = dump.get_stream::<MinidumpFunctionTable>() {
+        function_table.print(output)?;
+    }
+    if let Ok(handles) = dump.get_stream::<MinidumpHandleDataStream>() {
+        handles.print(output)?;
+    }
+    if let Ok(handle_operations) = dump.get_stream::<MinidumpHandleOperationList>() {
+        handle_operations.print(output)?;
+    }
+    if let Ok(tokens) = dump.get_stream::<MinidumpTokenInfoList>() {
+        tokens.print(output)?;
+    }
+    if let Ok(ipt_trace) = dump.get_stream::<MinidumpIptTrace>() {
+        ipt_trace.print(output)?;
+    }
     if let Ok(breakpad_info) = dump.get_stream::<MinidumpBreakpadInfo>() {
         breakpad_info.print(output)?;
     }
@@ -682,6 +932,18 @@ where
     if let Ok(mac_info) = dump.get_stream::<MinidumpMacCrashInfo>() {
         mac_info.print(output)?;
     }
+    if let Ok(bootargs) = dump.get_stream::<MinidumpMacBootargs>() {
+        bootargs.print(output)?;
+    }
+    if let Ok(dso_debug) = dump.get_stream::<MinidumpLinuxDsoDebug>() {
+        dso_debug.print(output)?;
+    }
+    if let Ok(auxv) = dump.get_stream::<MinidumpLinuxAuxv>() {
+        auxv.print(output)?;
+    }
+    if let Ok(limits) = dump.get_stream::<MinidumpLinuxLimits>() {
+        limits.print(output)?;
+    }
 
     // Handle Linux streams that are just a dump of some system "file".
     macro_rules! streams {
@@ -712,6 +974,9 @@ where
         }
     }
 
+    // Finally list every stream, to make unexpected ones easy to spot.
+    dump.print_all_streams(output, if brief { 0 } else { 256 })?;
+
     Ok(())
 }
 
@@ -205,6 +205,30 @@ fn test_evil_json() {
     assert_eq!(stderr, "");
 }
 
+#[test]
+fn test_extra_json() {
+    let bin = env!("CARGO_BIN_EXE_minidump-stackwalk");
+    let output = Command::new(bin)
+        .arg("--json")
+        .arg("--pretty")
+        .arg("--extra-json")
+        .arg("../testdata/extra.json")
+        .arg("../testdata/test.dmp")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains(r#""release_channel": "beta""#));
+    assert!(stdout.contains(r#""new-allocator""#));
+    assert!(stdout.contains(r#""status": "OK""#));
+    assert_eq!(stderr, "");
+}
+
 #[test]
 fn test_human() {
     let bin = env!("CARGO_BIN_EXE_minidump-stackwalk");
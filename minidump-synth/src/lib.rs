@@ -16,7 +16,7 @@
 // Some test_assembler types do not have Debug, so be a bit more lenient here.
 #![allow(missing_debug_implementations)]
 
-use encoding::all::UTF_16LE;
+use encoding::all::{UTF_16BE, UTF_16LE};
 use encoding::{EncoderTrap, Encoding};
 use minidump_common::format as md;
 use scroll::ctx::SizeWith;
@@ -861,7 +861,11 @@ pub struct DumpString {
 impl DumpString {
     /// Create a new `DumpString` with `s` as its contents, using `endian` endianness.
     pub fn new(s: &str, endian: Endian) -> DumpString {
-        let u16_s = UTF_16LE.encode(s, EncoderTrap::Strict).unwrap();
+        let u16_s = match endian {
+            Endian::Little => UTF_16LE.encode(s, EncoderTrap::Strict),
+            Endian::Big => UTF_16BE.encode(s, EncoderTrap::Strict),
+        }
+        .unwrap();
         let section = Section::with_endian(endian)
             .D32(u16_s.len() as u32)
             .append_bytes(&u16_s);
@@ -1032,6 +1036,14 @@ pub struct Thread {
 
 impl Thread {
     pub fn new<T>(endian: Endian, id: u32, stack: &Memory, context: &T) -> Thread
+    where
+        T: DumpSection,
+    {
+        Thread::with_teb(endian, id, 0, stack, context)
+    }
+
+    /// Like [`Thread::new`], but with the address of the thread's TEB.
+    pub fn with_teb<T>(endian: Endian, id: u32, teb: u64, stack: &Memory, context: &T) -> Thread
     where
         T: DumpSection,
     {
@@ -1040,7 +1052,7 @@ impl Thread {
             .D32(0) // suspend_count
             .D32(0) // priority_class
             .D32(0) // priority
-            .D64(0) // teb
+            .D64(teb)
             .cite_memory(stack)
             .cite_location(context);
         Thread { section }
@@ -1540,7 +1552,14 @@ impl AnnotationObject {
                 md::MINIDUMP_ANNOTATION::TYPE_STRING,
                 Some(DumpUtf8String::new(&s, endian).into()),
             ),
-            AnnotationValue::Custom(ty, bytes) => (ty, Some(Section::new().append_bytes(&bytes))),
+            AnnotationValue::Custom(ty, bytes) => (
+                ty,
+                Some(
+                    Section::with_endian(endian)
+                        .D32(bytes.len() as u32)
+                        .append_bytes(&bytes),
+                ),
+            ),
         };
 
         let mut section = Section::with_endian(endian)
@@ -1768,6 +1787,10 @@ pub enum CpuInfo {
         feature_information: u32,
         amd_extended_cpu_features: u32,
     },
+    /// Breakpad's cpu info for ARM and ARM64.
+    ArmCpuInfo { cpuid: u32, elf_hwcaps: u32 },
+    /// Windows' cpu info for everything but x86.
+    OtherCpuInfo { processor_features: [u64; 2] },
 }
 
 impl SystemInfo {
@@ -1804,6 +1827,11 @@ impl SystemInfo {
         self.platform_id = platform_id;
         self
     }
+
+    pub fn set_cpu_info(mut self, cpu: CpuInfo) -> Self {
+        self.cpu = cpu;
+        self
+    }
 }
 
 impl_dumpsection!(SystemInfo);
@@ -1838,6 +1866,14 @@ impl From<SystemInfo> for Section {
                 .D32(version_information)
                 .D32(feature_information)
                 .D32(amd_extended_cpu_features),
+            // The cpu info is a union, so pad the smaller variants out to the size of the largest.
+            CpuInfo::ArmCpuInfo { cpuid, elf_hwcaps } => {
+                section.D32(cpuid).D32(elf_hwcaps).append_repeated(0, 16)
+            }
+            CpuInfo::OtherCpuInfo { processor_features } => section
+                .D64(processor_features[0])
+                .D64(processor_features[1])
+                .append_repeated(0, 8),
         }
     }
 }
@@ -1855,6 +1891,8 @@ pub struct Exception {
     pub exception_record: ExceptionRecord,
     // TODO: implement this LOCATION_DESCRIPTOR properly
     pub thread_context: (u32, u32),
+    /// Overrides `thread_context` when set with [`Exception::set_context`].
+    context: Option<(Label, Label)>,
 }
 
 pub struct ExceptionRecord {
@@ -1881,8 +1919,14 @@ impl Exception {
                 exception_information: [0; 15],
             },
             thread_context: (0, 0),
+            context: None,
         }
     }
+
+    /// Cite `context` as the exception's thread context.
+    pub fn set_context<T: DumpSection>(&mut self, context: &T) {
+        self.context = Some((context.file_size(), context.file_offset()));
+    }
 }
 
 impl_dumpsection!(Exception);
@@ -1904,9 +1948,12 @@ impl From<Exception> for Section {
             section = section.D64(chunk);
         }
 
-        section = section
-            .D32(info.thread_context.0)
-            .D32(info.thread_context.1);
+        section = match info.context {
+            Some(context) => section.cite_location(&context),
+            None => section
+                .D32(info.thread_context.0)
+                .D32(info.thread_context.1),
+        };
 
         section
     }
@@ -3,10 +3,13 @@ use libfuzzer_sys::fuzz_target;
 
 use minidump::{
     MinidumpAssertion, MinidumpBreakpadInfo, MinidumpCrashpadInfo, MinidumpException,
-    MinidumpLinuxCpuInfo, MinidumpLinuxEnviron, MinidumpLinuxLsbRelease, MinidumpLinuxMaps,
-    MinidumpLinuxProcStatus, MinidumpMacCrashInfo, MinidumpMemory64List, MinidumpMemoryInfoList,
-    MinidumpMemoryList, MinidumpMiscInfo, MinidumpModuleList, MinidumpSystemInfo,
-    MinidumpThreadList, MinidumpThreadNames, MinidumpUnloadedModuleList,
+    MinidumpFunctionTable, MinidumpHandleDataStream, MinidumpHandleOperationList, MinidumpIptTrace,
+    MinidumpLinuxAuxv, MinidumpLinuxCpuInfo, MinidumpLinuxDsoDebug, MinidumpLinuxEnviron,
+    MinidumpLinuxLimits, MinidumpLinuxLsbRelease, MinidumpLinuxMaps, MinidumpLinuxProcStatus,
+    MinidumpMacCrashInfo, MinidumpMemory64List, MinidumpMemoryInfoList, MinidumpMemoryList,
+    MinidumpMiscInfo, MinidumpModuleList, MinidumpProcessVmCounters, MinidumpSystemInfo,
+    MinidumpSystemMemoryInfo, MinidumpThreadInfoList, MinidumpThreadList, MinidumpThreadNames,
+    MinidumpTokenInfoList, MinidumpUnloadedModuleList,
 };
 
 fuzz_target!(|data: &[u8]| {
@@ -15,8 +18,15 @@ fuzz_target!(|data: &[u8]| {
         let _ = dump.get_stream::<MinidumpBreakpadInfo>();
         let _ = dump.get_stream::<MinidumpCrashpadInfo>();
         let _ = dump.get_stream::<MinidumpException>();
+        let _ = dump.get_stream::<MinidumpFunctionTable>();
+        let _ = dump.get_stream::<MinidumpHandleDataStream>();
+        let _ = dump.get_stream::<MinidumpHandleOperationList>();
+        let _ = dump.get_stream::<MinidumpIptTrace>();
+        let _ = dump.get_stream::<MinidumpLinuxAuxv>();
         let _ = dump.get_stream::<MinidumpLinuxCpuInfo>();
+        let _ = dump.get_stream::<MinidumpLinuxDsoDebug>();
         let _ = dump.get_stream::<MinidumpLinuxEnviron>();
+        let _ = dump.get_stream::<MinidumpLinuxLimits>();
         let _ = dump.get_stream::<MinidumpLinuxLsbRelease>();
         let _ = dump.get_stream::<MinidumpLinuxMaps>();
         let _ = dump.get_stream::<MinidumpLinuxProcStatus>();
@@ -26,9 +36,13 @@ fuzz_target!(|data: &[u8]| {
         let _ = dump.get_stream::<MinidumpMemoryList>();
         let _ = dump.get_stream::<MinidumpMiscInfo>();
         let _ = dump.get_stream::<MinidumpModuleList>();
+        let _ = dump.get_stream::<MinidumpProcessVmCounters>();
         let _ = dump.get_stream::<MinidumpSystemInfo>();
+        let _ = dump.get_stream::<MinidumpSystemMemoryInfo>();
         let _ = dump.get_stream::<MinidumpThreadNames>();
+        let _ = dump.get_stream::<MinidumpThreadInfoList>();
         let _ = dump.get_stream::<MinidumpThreadList>();
+        let _ = dump.get_stream::<MinidumpTokenInfoList>();
         let _ = dump.get_stream::<MinidumpUnloadedModuleList>();
     }
 });
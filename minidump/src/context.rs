@@ -13,7 +13,7 @@ use std::mem;
 use tracing::warn;
 
 use crate::iostuff::*;
-use crate::{MinidumpMiscInfo, MinidumpSystemInfo};
+use crate::{MinidumpMiscInfo, MinidumpSystemInfo, XStateConfig};
 use minidump_common::format as md;
 use minidump_common::format::ContextFlagsCpu;
 
@@ -997,6 +997,369 @@ pub enum MinidumpContextValidity {
     Some(HashSet<&'static str>),
 }
 
+/// The floating point and NEON registers of an ARM64 context, from
+/// [`MinidumpContext::fp_registers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arm64FpRegisters {
+    /// `[q0, q1, ..., q31]`. The low 64 bits of each are `d0..d31`, and the
+    /// low 32 bits are `s0..s31`.
+    pub q: [u128; 32],
+    /// FPU status register.
+    pub fpsr: u32,
+    /// FPU control register.
+    pub fpcr: u32,
+}
+
+impl Arm64FpRegisters {
+    /// The names of the registers, in the order [`MinidumpContext::format_register`]
+    /// prints them.
+    pub const REGISTERS: &'static [&'static str] = &[
+        "q0", "q1", "q2", "q3", "q4", "q5", "q6", "q7", "q8", "q9", "q10", "q11", "q12", "q13",
+        "q14", "q15", "q16", "q17", "q18", "q19", "q20", "q21", "q22", "q23", "q24", "q25", "q26",
+        "q27", "q28", "q29", "q30", "q31", "fpsr", "fpcr",
+    ];
+
+    /// Get the value of the register named `reg` (a `q` register, `fpsr` or
+    /// `fpcr`), or `None` if there's no such register.
+    pub fn get(&self, reg: &str) -> Option<u128> {
+        match reg {
+            "fpsr" => Some(self.fpsr.into()),
+            "fpcr" => Some(self.fpcr.into()),
+            _ => {
+                let index = reg.strip_prefix('q')?.parse::<usize>().ok()?;
+                self.q.get(index).copied()
+            }
+        }
+    }
+
+    /// Return a String containing the value of `reg` formatted to its natural
+    /// width, or `None` if there's no such register.
+    pub fn format_register(&self, reg: &str) -> Option<String> {
+        match reg {
+            "fpsr" => Some(format!("0x{:08x}", self.fpsr)),
+            "fpcr" => Some(format!("0x{:08x}", self.fpcr)),
+            _ => self.get(reg).map(|val| format!("0x{:032x}", val)),
+        }
+    }
+}
+
+/// The AVX and AVX-512 registers of an amd64 context, which are saved in the
+/// extended (XSTATE) part of the context; see [`MinidumpExtendedState`].
+///
+/// Each component is only present if the minidump's
+/// [misc info][crate::MinidumpMiscInfo::xstate_config] says where it is and
+/// the context is big enough to contain it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Amd64ExtendedState {
+    /// `[ymm0, ymm1, ..., ymm15]`, each as `[bits 0..128, bits 128..256]`.
+    /// The low halves are the `xmm` registers.
+    pub ymm: Option<[[u128; 2]; 16]>,
+    /// `[zmm0, zmm1, ..., zmm31]`, each as its four 128-bit lanes, lowest first.
+    /// The low halves of `zmm0..zmm15` are the `ymm` registers.
+    pub zmm: Option<[[u128; 4]; 32]>,
+    /// The AVX-512 opmask registers, `[k0, k1, ..., k7]`.
+    pub opmask: Option<[u64; 8]>,
+}
+
+/// The names of the registers [`MinidumpExtendedState::format_register`]
+/// accepts for an amd64 context with AVX state.
+const AMD64_AVX_REGISTERS: &[&str] = &[
+    "ymm0", "ymm1", "ymm2", "ymm3", "ymm4", "ymm5", "ymm6", "ymm7", "ymm8", "ymm9", "ymm10",
+    "ymm11", "ymm12", "ymm13", "ymm14", "ymm15",
+];
+
+/// The names of the registers [`MinidumpExtendedState::format_register`]
+/// accepts for an amd64 context with AVX-512 state.
+const AMD64_AVX512_REGISTERS: &[&str] = &[
+    "zmm0", "zmm1", "zmm2", "zmm3", "zmm4", "zmm5", "zmm6", "zmm7", "zmm8", "zmm9", "zmm10",
+    "zmm11", "zmm12", "zmm13", "zmm14", "zmm15", "zmm16", "zmm17", "zmm18", "zmm19", "zmm20",
+    "zmm21", "zmm22", "zmm23", "zmm24", "zmm25", "zmm26", "zmm27", "zmm28", "zmm29", "zmm30",
+    "zmm31", "k0", "k1", "k2", "k3", "k4", "k5", "k6", "k7",
+];
+
+impl Amd64ExtendedState {
+    /// Read the AVX and AVX-512 components that `config` locates in `bytes`,
+    /// the complete context that `ctx` was read from.
+    ///
+    /// Components that don't fit in `bytes` are skipped, so a truncated
+    /// context just loses the registers that were cut off. Unless the context
+    /// says it has XSTATE beyond the end of [`md::CONTEXT_AMD64`], components
+    /// out there aren't looked for at all.
+    fn read(
+        bytes: &[u8],
+        endian: scroll::Endian,
+        ctx: &md::CONTEXT_AMD64,
+        config: &XStateConfig,
+    ) -> Option<Amd64ExtendedState> {
+        use md::XstateFeatureIndex::*;
+
+        // See the docs of CONTEXT_AMD64 for the 256.
+        const XSAVE_OFFSET: usize = 256;
+        let has_xstate = ctx.context_flags & md::CONTEXT_HAS_XSTATE != 0;
+        let component = |index: md::XstateFeatureIndex| -> Option<&[u8]> {
+            let feature = config.feature(index)?;
+            let start = XSAVE_OFFSET.checked_add(feature.offset as usize)?;
+            let end = start.checked_add(feature.size as usize)?;
+            if !has_xstate && end > mem::size_of::<md::CONTEXT_AMD64>() {
+                return None;
+            }
+            let component = bytes.get(start..end);
+            if component.is_none() {
+                warn!(
+                    "amd64 context is truncated, ignoring its XSTATE feature {:?}",
+                    index
+                );
+            }
+            component
+        };
+        let lanes = |bytes: &[u8], count: usize| -> Option<Vec<u128>> {
+            (0..count)
+                .map(|i| bytes.pread_with::<u128>(i * 16, endian).ok())
+                .collect()
+        };
+
+        let xmm = ctx
+            .float_save
+            .pread_with::<md::XMM_SAVE_AREA32>(0, endian)
+            .ok()?
+            .xmm_registers;
+        let ymm_hi = component(GSSE_AND_AVX).and_then(|bytes| lanes(bytes, 16));
+        let ymm = ymm_hi
+            .as_ref()
+            .map(|hi| std::array::from_fn(|i| [xmm[i], hi[i]]));
+
+        let zmm_hi = component(AVX512_ZMM_H).and_then(|bytes| lanes(bytes, 32));
+        let hi16_zmm = component(ACK512_ZMM).and_then(|bytes| lanes(bytes, 64));
+        let zmm = match (ymm, zmm_hi, hi16_zmm) {
+            (Some(ymm), Some(zmm_hi), Some(hi16_zmm)) => Some(std::array::from_fn(|i| {
+                if i < 16 {
+                    [ymm[i][0], ymm[i][1], zmm_hi[2 * i], zmm_hi[2 * i + 1]]
+                } else {
+                    let j = (i - 16) * 4;
+                    [
+                        hi16_zmm[j],
+                        hi16_zmm[j + 1],
+                        hi16_zmm[j + 2],
+                        hi16_zmm[j + 3],
+                    ]
+                }
+            })),
+            _ => None,
+        };
+
+        let opmask = component(AVX512_KMASK).and_then(|bytes| {
+            (0..8)
+                .map(|i| bytes.pread_with::<u64>(i * 8, endian).ok())
+                .collect::<Option<Vec<_>>>()
+        });
+        let opmask = opmask.map(|k| std::array::from_fn(|i| k[i]));
+
+        let state = Amd64ExtendedState { ymm, zmm, opmask };
+        (state != Amd64ExtendedState::default()).then_some(state)
+    }
+
+    /// Return a String containing the value of `reg` formatted to its natural
+    /// width, or `None` if there's no such register (or it wasn't saved).
+    fn format_register(&self, reg: &str) -> Option<String> {
+        let index = |prefix: &str| reg.strip_prefix(prefix)?.parse::<usize>().ok();
+        if let Some(i) = index("ymm") {
+            let [lo, hi] = self.ymm?.get(i).copied()?;
+            Some(format!("0x{:032x}{:032x}", hi, lo))
+        } else if let Some(i) = index("zmm") {
+            let [l0, l1, l2, l3] = self.zmm?.get(i).copied()?;
+            Some(format!("0x{:032x}{:032x}{:032x}{:032x}", l3, l2, l1, l0))
+        } else if let Some(i) = index("k") {
+            let k = self.opmask?.get(i).copied()?;
+            Some(format!("0x{:016x}", k))
+        } else {
+            None
+        }
+    }
+}
+
+/// The SVE registers of an ARM64 context, which are saved in the extended
+/// (XSTATE) part of the context; see [`MinidumpExtendedState`].
+///
+/// They're only present if the context's `context_flags` include
+/// [`CONTEXT_ARM64_XSTATE`][md::ContextFlagsArm64::CONTEXT_ARM64_XSTATE] and
+/// the minidump's [misc info][crate::MinidumpMiscInfo::xstate_config] says
+/// where they are (see [`md::XSTATE_ARM64_SVE`]).
+///
+/// The feature is expected to hold `z0..z31`, then `p0..p15`, then `ffr`,
+/// each in the byte order of the dump. Its size is the only record of the
+/// vector length, which is `size * 8 / 273` bytes, and must be a multiple of
+/// 16 between 16 and 256, as the architecture requires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Arm64SveRegisters {
+    /// The vector length, in bytes. This is the size of each `z` register,
+    /// and the `p` registers and `ffr` are an eighth of it.
+    pub vector_length: usize,
+    data: Vec<u8>,
+}
+
+/// The names of the registers [`MinidumpExtendedState::format_register`]
+/// accepts for an ARM64 context with SVE state.
+const ARM64_SVE_REGISTERS: &[&str] = &[
+    "z0", "z1", "z2", "z3", "z4", "z5", "z6", "z7", "z8", "z9", "z10", "z11", "z12", "z13", "z14",
+    "z15", "z16", "z17", "z18", "z19", "z20", "z21", "z22", "z23", "z24", "z25", "z26", "z27",
+    "z28", "z29", "z30", "z31", "p0", "p1", "p2", "p3", "p4", "p5", "p6", "p7", "p8", "p9", "p10",
+    "p11", "p12", "p13", "p14", "p15", "ffr",
+];
+
+impl Arm64SveRegisters {
+    /// Read the SVE registers that `config` locates in `bytes`, the complete
+    /// context that `ctx` was read from.
+    fn read(
+        bytes: &[u8],
+        ctx: &md::CONTEXT_ARM64,
+        config: &XStateConfig,
+    ) -> Option<Arm64SveRegisters> {
+        let flags = md::ContextFlagsArm64::from_bits_truncate(ctx.context_flags);
+        if !flags.contains(md::ContextFlagsArm64::CONTEXT_ARM64_XSTATE) {
+            return None;
+        }
+        let feature = config.features.get(&md::XSTATE_ARM64_SVE)?;
+        let size = feature.size as usize;
+        let vector_length = match (1..=16).map(|n| n * 16).find(|len| len * 273 == size * 8) {
+            Some(vector_length) => vector_length,
+            None => {
+                warn!(
+                    "arm64 context has SVE state of an unexpected size ({} bytes), ignoring it",
+                    size
+                );
+                return None;
+            }
+        };
+        let start = feature.offset as usize;
+        let data = start
+            .checked_add(size)
+            .and_then(|end| bytes.get(start..end));
+        if data.is_none() {
+            warn!("arm64 context is truncated, ignoring its SVE state");
+        }
+        Some(Arm64SveRegisters {
+            vector_length,
+            data: data?.to_vec(),
+        })
+    }
+
+    /// The `z` register `n`, or `None` if `n` isn't less than 32.
+    pub fn z(&self, n: usize) -> Option<&[u8]> {
+        (n < 32).then(|| &self.data[n * self.vector_length..][..self.vector_length])
+    }
+
+    /// The predicate register `p<n>`, or `None` if `n` isn't less than 16.
+    pub fn p(&self, n: usize) -> Option<&[u8]> {
+        let len = self.vector_length / 8;
+        (n < 16).then(|| &self.data[32 * self.vector_length + n * len..][..len])
+    }
+
+    /// The first fault register.
+    pub fn ffr(&self) -> &[u8] {
+        let len = self.vector_length / 8;
+        &self.data[32 * self.vector_length + 16 * len..][..len]
+    }
+
+    /// Return a String containing the value of `reg` formatted to its natural
+    /// width, or `None` if there's no such register.
+    fn format_register(&self, reg: &str) -> Option<String> {
+        let index = |prefix: &str| reg.strip_prefix(prefix)?.parse::<usize>().ok();
+        let bytes = if reg == "ffr" {
+            self.ffr()
+        } else if let Some(n) = index("z") {
+            self.z(n)?
+        } else if let Some(n) = index("p") {
+            self.p(n)?
+        } else {
+            return None;
+        };
+        // The registers are little-endian, so print them from the last byte down.
+        let digits: String = bytes.iter().rev().map(|b| format!("{:02x}", b)).collect();
+        Some(format!("0x{}", digits))
+    }
+}
+
+/// The registers saved in the extended part of a context, beyond the ones in
+/// its [`MinidumpRawContext`].
+///
+/// These are read separately from the [`MinidumpContext`], with
+/// [`MinidumpThread::extended_state`][crate::MinidumpThread::extended_state] or
+/// [`MinidumpException::extended_state`][crate::MinidumpException::extended_state].
+/// Only contexts read from the minidump can have them: unwinding never
+/// recovers them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MinidumpExtendedState {
+    /// The AVX and AVX-512 registers of an amd64 context.
+    Amd64(Box<Amd64ExtendedState>),
+    /// The SVE registers of an ARM64 context.
+    Arm64Sve(Arm64SveRegisters),
+}
+
+impl MinidumpExtendedState {
+    /// Read the extended state of the context in `bytes`.
+    ///
+    /// `bytes` is the complete context, as [`MinidumpContext::read`] takes it.
+    /// Returns `None` if the context doesn't have any extended state we
+    /// understand, or if `misc` doesn't say where it is.
+    pub fn read(
+        bytes: &[u8],
+        endian: scroll::Endian,
+        system_info: &MinidumpSystemInfo,
+        misc: Option<&MinidumpMiscInfo>,
+    ) -> Option<MinidumpExtendedState> {
+        use md::ProcessorArchitecture::*;
+
+        match md::ProcessorArchitecture::from_u16(system_info.raw.processor_architecture) {
+            Some(PROCESSOR_ARCHITECTURE_AMD64) => {
+                let ctx: md::CONTEXT_AMD64 = bytes.pread_with(0, endian).ok()?;
+                match misc.and_then(MinidumpMiscInfo::xstate_config) {
+                    Some(config) => Amd64ExtendedState::read(bytes, endian, &ctx, &config)
+                        .map(|state| MinidumpExtendedState::Amd64(Box::new(state))),
+                    None => {
+                        if ctx.context_flags & md::CONTEXT_HAS_XSTATE != 0 {
+                            warn!("Cpu context has extra XSTATE that is being ignored, because there's no MiscInfo describing it");
+                        }
+                        None
+                    }
+                }
+            }
+            Some(PROCESSOR_ARCHITECTURE_ARM64) => {
+                let ctx: md::CONTEXT_ARM64 = bytes.pread_with(0, endian).ok()?;
+                let config = misc.and_then(MinidumpMiscInfo::xstate_config)?;
+                Arm64SveRegisters::read(bytes, &ctx, &config).map(MinidumpExtendedState::Arm64Sve)
+            }
+            _ => None,
+        }
+    }
+
+    /// The names of the registers in this state, which
+    /// [`format_register`][Self::format_register] accepts.
+    ///
+    /// For amd64 these are the `zmm` and `k` registers if the AVX-512 state
+    /// was saved, or otherwise the `ymm` registers. For ARM64 they're the
+    /// `z`, `p` and `ffr` registers.
+    pub fn register_names(&self) -> &'static [&'static str] {
+        match self {
+            MinidumpExtendedState::Amd64(state) if state.zmm.is_some() => AMD64_AVX512_REGISTERS,
+            MinidumpExtendedState::Amd64(state) if state.ymm.is_some() => AMD64_AVX_REGISTERS,
+            MinidumpExtendedState::Amd64(_) => &[],
+            MinidumpExtendedState::Arm64Sve(_) => ARM64_SVE_REGISTERS,
+        }
+    }
+
+    /// Return a String containing the value of `reg` formatted to its natural
+    /// width, or `None` if there's no such register (or it wasn't saved).
+    pub fn format_register(&self, reg: &str) -> Option<String> {
+        match self {
+            MinidumpExtendedState::Amd64(state) => state.format_register(reg),
+            MinidumpExtendedState::Arm64Sve(sve) => sve.format_register(reg),
+        }
+    }
+}
+
 /// CPU context such as register states.
 ///
 /// MinidumpContext carries a CPU-specific MDRawContext structure, which
@@ -1018,17 +1381,165 @@ pub struct MinidumpContext {
 }
 
 /// Errors encountered while reading a `MinidumpContext`.
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum ContextError {
     /// Failed to read data.
+    #[error("Failed to read context data")]
     ReadFailure,
     /// Encountered an unknown CPU context.
+    #[error("Unknown cpu context")]
     UnknownCpuContext,
+    /// The `context_flags` of the context don't describe the kind of context
+    /// that the system info says should be there (or describe a kind of context
+    /// we don't know about), so the context can't be safely interpreted.
+    #[error("Unsupported context_flags {flags:#x} for a {arch} context")]
+    UnsupportedContextFlags { arch: &'static str, flags: u32 },
 }
 
 //======================================================
 // Implementations
 
+/// Check that `context_flags` describes a context for `arch`.
+///
+/// The CPU type bits must be exactly one of `expected`, otherwise we would be
+/// interpreting some other structure as this one. Unknown CPU type bits, or
+/// register-set bits outside of `known` (which includes the CPU type bits),
+/// mean the context has some extension we don't model. The base registers
+/// are still where we expect them, so those only get a warning.
+fn check_context_flags(
+    arch: &'static str,
+    context_flags: u32,
+    expected: &[ContextFlagsCpu],
+    known: u32,
+) -> Result<(), ContextError> {
+    let cpu = context_flags & md::CONTEXT_CPU_MASK;
+    let known_cpu = cpu & ContextFlagsCpu::all().bits();
+    if !expected.iter().any(|flags| flags.bits() == known_cpu) {
+        let err = ContextError::UnsupportedContextFlags {
+            arch,
+            flags: context_flags,
+        };
+        warn!("{}", err);
+        return Err(err);
+    }
+
+    let unknown = (cpu & !known_cpu) | (context_flags & !md::CONTEXT_CPU_MASK & !known);
+    if unknown != 0 {
+        warn!(
+            "{} context has unknown context_flags {:#x} (in {:#x}) that are being ignored",
+            arch, unknown, context_flags
+        );
+    }
+    Ok(())
+}
+
+/// x86 `eflags` flags, lowest bit first.
+const X86_EFLAGS: &[(u32, &str)] = &[
+    (0, "CF"),
+    (2, "PF"),
+    (4, "AF"),
+    (6, "ZF"),
+    (7, "SF"),
+    (8, "TF"),
+    (9, "IF"),
+    (10, "DF"),
+    (11, "OF"),
+    (14, "NT"),
+    (16, "RF"),
+    (17, "VM"),
+    (18, "AC"),
+    (19, "VIF"),
+    (20, "VIP"),
+    (21, "ID"),
+];
+
+/// ARM `cpsr` flags, in the order they're usually written: condition flags,
+/// then execution state, then interrupt masks.
+const ARM_CPSR: &[(u32, &str)] = &[
+    (31, "N"),
+    (30, "Z"),
+    (29, "C"),
+    (28, "V"),
+    (27, "Q"),
+    (24, "J"),
+    (9, "E"),
+    (8, "A"),
+    (7, "I"),
+    (6, "F"),
+    (5, "T"),
+];
+
+/// ARM64 `PSTATE` flags, in the same order as [`ARM_CPSR`].
+const ARM64_PSTATE: &[(u32, &str)] = &[
+    (31, "N"),
+    (30, "Z"),
+    (29, "C"),
+    (28, "V"),
+    (21, "SS"),
+    (20, "IL"),
+    (9, "D"),
+    (8, "A"),
+    (7, "I"),
+    (6, "F"),
+];
+
+fn describe_flags(value: u32, names: &[(u32, &str)], extra: Option<String>) -> String {
+    let mut out = String::new();
+    let set = names.iter().filter(|&&(bit, _)| value & (1 << bit) != 0);
+    for name in set.map(|&(_, name)| name.to_owned()).chain(extra) {
+        out.push_str(&name);
+        out.push(' ');
+    }
+    out.push_str(&format!("[flags {:#x}]", value));
+    out
+}
+
+/// Describe the bits of an x86 or amd64 `eflags` register.
+///
+/// Set flags are listed by name (with the I/O privilege level as `IOPL=n`
+/// if it's nonzero), followed by the raw value: `PF ZF IF [flags 0x246]`.
+pub fn decode_x86_eflags(eflags: u32) -> String {
+    let iopl = (eflags >> 12) & 0b11;
+    let extra = (iopl != 0).then(|| format!("IOPL={}", iopl));
+    describe_flags(eflags, X86_EFLAGS, extra)
+}
+
+/// Describe the bits of an ARM `cpsr` register.
+///
+/// Set flags are listed by name, followed by the processor mode and the raw
+/// value: `Z C T usr [flags 0x60000030]`.
+pub fn decode_arm_cpsr(cpsr: u32) -> String {
+    let mode = match cpsr & 0x1f {
+        0x10 => String::from("usr"),
+        0x11 => String::from("fiq"),
+        0x12 => String::from("irq"),
+        0x13 => String::from("svc"),
+        0x16 => String::from("mon"),
+        0x17 => String::from("abt"),
+        0x1a => String::from("hyp"),
+        0x1b => String::from("und"),
+        0x1f => String::from("sys"),
+        mode => format!("mode={:#x}", mode),
+    };
+    describe_flags(cpsr, ARM_CPSR, Some(mode))
+}
+
+/// Describe the bits of an ARM64 `PSTATE` (the `cpsr` field of its context).
+///
+/// Set flags are listed by name, followed by the exception level and stack
+/// pointer selection (`EL0t`, `EL1h`, ...) and the raw value:
+/// `Z C EL0t [flags 0x60000000]`. If the state is actually an aarch32 one,
+/// that is reported as `aarch32` instead of an exception level.
+pub fn decode_arm64_pstate(pstate: u32) -> String {
+    let mode = if pstate & (1 << 4) != 0 {
+        String::from("aarch32")
+    } else {
+        let sp = if pstate & 1 != 0 { 'h' } else { 't' };
+        format!("EL{}{}", (pstate >> 2) & 0b11, sp)
+    };
+    describe_flags(pstate, ARM64_PSTATE, Some(mode))
+}
+
 impl MinidumpContext {
     /// Return a MinidumpContext given a `MinidumpRawContext`.
     pub fn from_raw(raw: MinidumpRawContext) -> MinidumpContext {
@@ -1062,116 +1573,125 @@ impl MinidumpContext {
                     .gread_with(&mut offset, endian)
                     .or(Err(ContextError::ReadFailure))?;
 
-                let flags = ContextFlagsCpu::from_flags(ctx.context_flags as u32);
-                if flags == ContextFlagsCpu::CONTEXT_X86 {
-                    if ctx.context_flags & md::CONTEXT_HAS_XSTATE != 0 {
-                        // FIXME: uses MISC_INFO_5 to parse out extra sections here
-                        warn!("Cpu context has extra XSTATE that is being ignored");
-                    }
-                    Ok(MinidumpContext::from_raw(MinidumpRawContext::X86(ctx)))
-                } else {
-                    Err(ContextError::ReadFailure)
+                check_context_flags(
+                    "x86",
+                    ctx.context_flags as u32,
+                    &[ContextFlagsCpu::CONTEXT_X86],
+                    md::ContextFlagsX86::all().bits(),
+                )?;
+                if ctx.context_flags & md::CONTEXT_HAS_XSTATE != 0 {
+                    // FIXME: uses MISC_INFO_5 to parse out extra sections here
+                    warn!("Cpu context has extra XSTATE that is being ignored");
                 }
+                Ok(MinidumpContext::from_raw(MinidumpRawContext::X86(ctx)))
             }
             Some(PROCESSOR_ARCHITECTURE_AMD64) => {
                 let ctx: md::CONTEXT_AMD64 = bytes
                     .gread_with(&mut offset, endian)
                     .or(Err(ContextError::ReadFailure))?;
 
-                let flags = ContextFlagsCpu::from_flags(ctx.context_flags as u32);
-                if flags == ContextFlagsCpu::CONTEXT_AMD64 {
-                    if ctx.context_flags & md::CONTEXT_HAS_XSTATE != 0 {
-                        // FIXME: uses MISC_INFO_5 to parse out extra sections here
-                        warn!("Cpu context has extra XSTATE that is being ignored");
-                    }
-                    Ok(MinidumpContext::from_raw(MinidumpRawContext::Amd64(ctx)))
-                } else {
-                    Err(ContextError::ReadFailure)
-                }
+                check_context_flags(
+                    "amd64",
+                    ctx.context_flags as u32,
+                    &[ContextFlagsCpu::CONTEXT_AMD64],
+                    md::ContextFlagsAmd64::all().bits() | md::CONTEXT_HAS_XSTATE,
+                )?;
+                // The XSTATE is read separately, see MinidumpExtendedState.
+                Ok(MinidumpContext::from_raw(MinidumpRawContext::Amd64(ctx)))
             }
             Some(PROCESSOR_ARCHITECTURE_PPC) => {
                 let ctx: md::CONTEXT_PPC = bytes
                     .gread_with(&mut offset, endian)
                     .or(Err(ContextError::ReadFailure))?;
 
-                let flags = ContextFlagsCpu::from_flags(ctx.context_flags as u32);
-                if flags == ContextFlagsCpu::CONTEXT_PPC {
-                    Ok(MinidumpContext::from_raw(MinidumpRawContext::Ppc(ctx)))
-                } else {
-                    Err(ContextError::ReadFailure)
-                }
+                check_context_flags(
+                    "ppc",
+                    ctx.context_flags as u32,
+                    &[ContextFlagsCpu::CONTEXT_PPC],
+                    !md::CONTEXT_CPU_MASK,
+                )?;
+                Ok(MinidumpContext::from_raw(MinidumpRawContext::Ppc(ctx)))
             }
             Some(PROCESSOR_ARCHITECTURE_PPC64) => {
                 let ctx: md::CONTEXT_PPC64 = bytes
                     .gread_with(&mut offset, endian)
                     .or(Err(ContextError::ReadFailure))?;
 
-                let flags = ContextFlagsCpu::from_flags(ctx.context_flags as u32);
-                if flags == ContextFlagsCpu::CONTEXT_PPC64 {
-                    Ok(MinidumpContext::from_raw(MinidumpRawContext::Ppc64(ctx)))
-                } else {
-                    Err(ContextError::ReadFailure)
-                }
+                check_context_flags(
+                    "ppc64",
+                    ctx.context_flags as u32,
+                    &[ContextFlagsCpu::CONTEXT_PPC64],
+                    !md::CONTEXT_CPU_MASK,
+                )?;
+                Ok(MinidumpContext::from_raw(MinidumpRawContext::Ppc64(ctx)))
             }
             Some(PROCESSOR_ARCHITECTURE_SPARC) => {
                 let ctx: md::CONTEXT_SPARC = bytes
                     .gread_with(&mut offset, endian)
                     .or(Err(ContextError::ReadFailure))?;
 
-                let flags = ContextFlagsCpu::from_flags(ctx.context_flags as u32);
-                if flags == ContextFlagsCpu::CONTEXT_SPARC {
-                    Ok(MinidumpContext::from_raw(MinidumpRawContext::Sparc(ctx)))
-                } else {
-                    Err(ContextError::ReadFailure)
-                }
+                check_context_flags(
+                    "sparc",
+                    ctx.context_flags as u32,
+                    &[ContextFlagsCpu::CONTEXT_SPARC],
+                    !md::CONTEXT_CPU_MASK,
+                )?;
+                Ok(MinidumpContext::from_raw(MinidumpRawContext::Sparc(ctx)))
             }
             Some(PROCESSOR_ARCHITECTURE_ARM) => {
                 let ctx: md::CONTEXT_ARM = bytes
                     .gread_with(&mut offset, endian)
                     .or(Err(ContextError::ReadFailure))?;
 
-                let flags = ContextFlagsCpu::from_flags(ctx.context_flags as u32);
-                if flags == ContextFlagsCpu::CONTEXT_ARM {
-                    Ok(MinidumpContext::from_raw(MinidumpRawContext::Arm(ctx)))
-                } else {
-                    Err(ContextError::ReadFailure)
-                }
+                check_context_flags(
+                    "arm",
+                    ctx.context_flags as u32,
+                    &[ContextFlagsCpu::CONTEXT_ARM],
+                    md::ContextFlagsArm::all().bits(),
+                )?;
+                Ok(MinidumpContext::from_raw(MinidumpRawContext::Arm(ctx)))
             }
             Some(PROCESSOR_ARCHITECTURE_ARM64) => {
                 let ctx: md::CONTEXT_ARM64 = bytes
                     .gread_with(&mut offset, endian)
                     .or(Err(ContextError::ReadFailure))?;
 
-                let flags = ContextFlagsCpu::from_flags(ctx.context_flags as u32);
-                if flags == ContextFlagsCpu::CONTEXT_ARM64 {
-                    Ok(MinidumpContext::from_raw(MinidumpRawContext::Arm64(ctx)))
-                } else {
-                    Err(ContextError::ReadFailure)
-                }
+                check_context_flags(
+                    "arm64",
+                    ctx.context_flags as u32,
+                    &[ContextFlagsCpu::CONTEXT_ARM64],
+                    md::ContextFlagsArm64::all().bits(),
+                )?;
+                Ok(MinidumpContext::from_raw(MinidumpRawContext::Arm64(ctx)))
             }
             Some(PROCESSOR_ARCHITECTURE_ARM64_OLD) => {
                 let ctx: md::CONTEXT_ARM64_OLD = bytes
                     .gread_with(&mut offset, endian)
                     .or(Err(ContextError::ReadFailure))?;
 
-                let flags = ContextFlagsCpu::from_flags(ctx.context_flags as u32);
-                if flags == ContextFlagsCpu::CONTEXT_ARM64_OLD {
-                    Ok(MinidumpContext::from_raw(MinidumpRawContext::OldArm64(ctx)))
-                } else {
-                    Err(ContextError::ReadFailure)
-                }
+                check_context_flags(
+                    "arm64",
+                    ctx.context_flags as u32,
+                    &[ContextFlagsCpu::CONTEXT_ARM64_OLD],
+                    md::ContextFlagsArm64Old::all().bits(),
+                )?;
+                Ok(MinidumpContext::from_raw(MinidumpRawContext::OldArm64(ctx)))
             }
             Some(PROCESSOR_ARCHITECTURE_MIPS) => {
                 let ctx: md::CONTEXT_MIPS = bytes
                     .gread_with(&mut offset, endian)
                     .or(Err(ContextError::ReadFailure))?;
 
-                let flags = ContextFlagsCpu::from_flags(ctx.context_flags as u32);
-                if flags == ContextFlagsCpu::CONTEXT_MIPS {
-                    Ok(MinidumpContext::from_raw(MinidumpRawContext::Mips(ctx)))
-                } else {
-                    Err(ContextError::ReadFailure)
-                }
+                check_context_flags(
+                    "mips",
+                    ctx.context_flags as u32,
+                    &[
+                        ContextFlagsCpu::CONTEXT_MIPS,
+                        ContextFlagsCpu::CONTEXT_MIPS64,
+                    ],
+                    !md::CONTEXT_CPU_MASK,
+                )?;
+                Ok(MinidumpContext::from_raw(MinidumpRawContext::Mips(ctx)))
             }
             _ => Err(ContextError::UnknownCpuContext),
         }
@@ -1251,7 +1771,18 @@ impl MinidumpContext {
         }
     }
 
+    /// Return a String containing the value of `reg` formatted to its natural width.
+    ///
+    /// Besides the [general purpose registers][Self::general_purpose_registers],
+    /// this accepts the names of any [floating point registers][Self::fp_register_names]
+    /// the context has.
     pub fn format_register(&self, reg: &str) -> String {
+        if let Some(val) = self
+            .fp_registers()
+            .and_then(|regs| regs.format_register(reg))
+        {
+            return val;
+        }
         match self.raw {
             MinidumpRawContext::Amd64(ref ctx) => ctx.format_register(reg),
             MinidumpRawContext::Arm(ref ctx) => ctx.format_register(reg),
@@ -1279,6 +1810,76 @@ impl MinidumpContext {
         }
     }
 
+    /// The floating point and NEON registers of an ARM64 context.
+    ///
+    /// Returns `None` for other cpus, if the context's `context_flags` say they
+    /// weren't saved, or if this context was recovered by unwinding (which
+    /// only recovers general purpose registers).
+    pub fn fp_registers(&self) -> Option<Arm64FpRegisters> {
+        if self.valid != MinidumpContextValidity::All {
+            return None;
+        }
+        match self.raw {
+            MinidumpRawContext::Arm64(ref ctx) => {
+                let flags = md::ContextFlagsArm64::from_bits_truncate(ctx.context_flags);
+                flags
+                    .contains(md::ContextFlagsArm64::CONTEXT_ARM64_FLOATING_POINT)
+                    .then_some(Arm64FpRegisters {
+                        q: ctx.float_regs,
+                        fpsr: ctx.fpsr,
+                        fpcr: ctx.fpcr,
+                    })
+            }
+            MinidumpRawContext::OldArm64(ref ctx) => {
+                let flags = md::ContextFlagsArm64Old::from_bits_truncate(ctx.context_flags as u32);
+                flags
+                    .contains(md::ContextFlagsArm64Old::CONTEXT_ARM64_OLD_FLOATING_POINT)
+                    .then_some(Arm64FpRegisters {
+                        q: ctx.float_regs,
+                        fpsr: ctx.fpsr,
+                        fpcr: ctx.fpcr,
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    /// The names of the floating point registers in this context, which
+    /// [`format_register`][Self::format_register] accepts.
+    ///
+    /// This is empty if [`fp_registers`][Self::fp_registers] is `None`.
+    pub fn fp_register_names(&self) -> &'static [&'static str] {
+        match self.fp_registers() {
+            Some(_) => Arm64FpRegisters::REGISTERS,
+            None => &[],
+        }
+    }
+
+    /// The flags register of this context, decoded into the names of the set
+    /// flags: `eflags` for x86 and amd64 (see [`decode_x86_eflags`]), `cpsr`
+    /// for ARM (see [`decode_arm_cpsr`]), and `PSTATE` for ARM64 (see
+    /// [`decode_arm64_pstate`]).
+    ///
+    /// Returns `None` for other cpus, or if the flags aren't known, as in
+    /// contexts recovered by unwinding.
+    pub fn decoded_flags(&self) -> Option<String> {
+        if let MinidumpRawContext::X86(ref raw) = self.raw {
+            return raw
+                .register_is_valid("eflags", &self.valid)
+                .then(|| decode_x86_eflags(raw.eflags));
+        }
+        if self.valid != MinidumpContextValidity::All {
+            return None;
+        }
+        match self.raw {
+            MinidumpRawContext::Amd64(ref raw) => Some(decode_x86_eflags(raw.eflags)),
+            MinidumpRawContext::Arm(ref raw) => Some(decode_arm_cpsr(raw.cpsr)),
+            MinidumpRawContext::Arm64(ref raw) => Some(decode_arm64_pstate(raw.cpsr)),
+            MinidumpRawContext::OldArm64(ref raw) => Some(decode_arm64_pstate(raw.cpsr)),
+            _ => None,
+        }
+    }
+
     pub fn registers(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
         self.general_purpose_registers()
             .iter()
@@ -1360,7 +1961,7 @@ impl MinidumpContext {
   ebp                          = {:#x}
   eip                          = {:#x}
   cs                           = {:#x}
-  eflags                       = {:#x}
+  eflags                       = {}
   esp                          = {:#x}
   ss                           = {:#x}
   extended_registers[{:3}]      = 0x"#,
@@ -1378,7 +1979,7 @@ impl MinidumpContext {
                     raw.ebp,
                     raw.eip,
                     raw.cs,
-                    raw.eflags,
+                    decode_x86_eflags(raw.eflags),
                     raw.esp,
                     raw.ss,
                     raw.extended_registers.len(),
@@ -1410,7 +2011,7 @@ impl MinidumpContext {
   fs            = {:#x}
   gs            = {:#x}
   ss            = {:#x}
-  eflags        = {:#x}
+  eflags        = {}
   dr0           = {:#x}
   dr1           = {:#x}
   dr2           = {:#x}
@@ -1450,7 +2051,7 @@ impl MinidumpContext {
                     raw.fs,
                     raw.gs,
                     raw.ss,
-                    raw.eflags,
+                    decode_x86_eflags(raw.eflags),
                     raw.dr0,
                     raw.dr1,
                     raw.dr2,
@@ -1492,10 +2093,11 @@ impl MinidumpContext {
                 }
                 write!(
                     f,
-                    r#"  cpsr                = {:#x}
+                    r#"  cpsr                = {}
   float_save.fpscr     = {:#x}
 "#,
-                    raw.cpsr, raw.float_save.fpscr
+                    decode_arm_cpsr(raw.cpsr),
+                    raw.float_save.fpscr
                 )?;
                 for (i, reg) in raw.float_save.regs.iter().enumerate() {
                     writeln!(f, "  float_save.regs[{:2}] = {:#x}", i, reg)?;
@@ -1519,7 +2121,11 @@ impl MinidumpContext {
                 writeln!(f, "  x30 (lr)             = {:#x}", raw.iregs[30])?;
                 writeln!(f, "  sp                   = {:#x}", raw.sp)?;
                 writeln!(f, "  pc                   = {:#x}", raw.pc)?;
-                writeln!(f, "  cpsr                 = {:#x}", raw.cpsr)?;
+                writeln!(
+                    f,
+                    "  cpsr                 = {}",
+                    decode_arm64_pstate(raw.cpsr)
+                )?;
                 writeln!(f, "  fpsr                 = {:#x}", raw.fpsr)?;
                 writeln!(f, "  fpcr                 = {:#x}", raw.fpcr)?;
                 for (i, reg) in raw.float_regs.iter().enumerate() {
@@ -1553,7 +2159,11 @@ impl MinidumpContext {
                 writeln!(f, "  x30 (lr)             = {:#x}", raw.iregs[30])?;
                 writeln!(f, "  sp                   = {:#x}", raw.sp)?;
                 writeln!(f, "  pc                   = {:#x}", raw.pc)?;
-                writeln!(f, "  cpsr                 = {:#x}", raw.cpsr)?;
+                writeln!(
+                    f,
+                    "  cpsr                 = {}",
+                    decode_arm64_pstate(raw.cpsr)
+                )?;
                 writeln!(f, "  fpsr                 = {:#x}", raw.fpsr)?;
                 writeln!(f, "  fpcr                 = {:#x}", raw.fpcr)?;
                 for (i, reg) in raw.float_regs.iter().enumerate() {
@@ -1627,4 +2237,314 @@ mod tests {
         assert_eq!(context.memoize_register("fp"), Some("fp"));
         assert_eq!(context.memoize_register("foo"), None);
     }
+
+    #[test]
+    fn test_arm64_fp_registers() {
+        let mut raw = md::CONTEXT_ARM64 {
+            context_flags: md::ContextFlagsArm64::CONTEXT_ARM64_FULL.bits(),
+            fpsr: 0x8000010,
+            fpcr: 0x3000000,
+            ..Default::default()
+        };
+        raw.float_regs[0] = 0x7ff8000000000000;
+        raw.float_regs[31] = 0x0123456789abcdef_fedcba9876543210;
+        let context = MinidumpContext::from_raw(MinidumpRawContext::Arm64(raw.clone()));
+
+        let fp = context.fp_registers().unwrap();
+        assert_eq!(fp.q[0], 0x7ff8000000000000);
+        assert_eq!(fp.get("q31"), Some(0x0123456789abcdef_fedcba9876543210));
+        assert_eq!(fp.get("fpcr"), Some(0x3000000));
+        assert_eq!(fp.get("q32"), None);
+        assert_eq!(fp.get("x0"), None);
+        assert_eq!(context.fp_register_names().len(), 34);
+        assert_eq!(
+            context.format_register("q0"),
+            "0x00000000000000007ff8000000000000"
+        );
+        assert_eq!(context.format_register("fpsr"), "0x08000010");
+        assert_eq!(context.format_register("x0"), "0x0000000000000000");
+
+        // Not saved
+        raw.context_flags = md::ContextFlagsArm64::CONTEXT_ARM64_INTEGER.bits();
+        let context = MinidumpContext::from_raw(MinidumpRawContext::Arm64(raw));
+        assert_eq!(context.fp_registers(), None);
+        assert!(context.fp_register_names().is_empty());
+
+        // Not an ARM64 context
+        let context =
+            MinidumpContext::from_raw(MinidumpRawContext::Amd64(md::CONTEXT_AMD64::default()));
+        assert_eq!(context.fp_registers(), None);
+    }
+
+    #[test]
+    fn test_amd64_xstate() {
+        use scroll::Pwrite;
+
+        let mut raw = md::CONTEXT_AMD64 {
+            context_flags: 0x10001f | md::CONTEXT_HAS_XSTATE,
+            ..Default::default()
+        };
+        raw.float_save[160..176].copy_from_slice(&0x1111u128.to_le_bytes());
+        let mut bytes = vec![0; mem::size_of::<md::CONTEXT_AMD64>()];
+        bytes.pwrite_with(&raw, 0, scroll::LE).unwrap();
+
+        // The layout Windows uses; the offsets are from the start of float_save.
+        let mut config = XStateConfig {
+            context_size: 2688,
+            features: Default::default(),
+        };
+        for (index, offset, size) in [
+            (2, 576, 256),
+            (5, 1088, 64),
+            (6, 1152, 512),
+            (7, 1664, 1024),
+        ] {
+            config
+                .features
+                .insert(index, md::XSTATE_FEATURE { offset, size });
+        }
+        bytes.resize(256 + 2688, 0);
+        let mut write_u128 = |offset: usize, val: u128| {
+            bytes[offset..offset + 16].copy_from_slice(&val.to_le_bytes());
+        };
+        write_u128(256 + 576, 0x2222);
+        write_u128(256 + 1152 + 32 + 16, 0x3333);
+        write_u128(256 + 1664 + 64 + 48, 0x4444);
+        bytes[256 + 1088 + 24] = 0x55;
+
+        let xstate = Amd64ExtendedState::read(&bytes, scroll::LE, &raw, &config).unwrap();
+        let ymm = xstate.ymm.unwrap();
+        assert_eq!(ymm[0], [0x1111, 0x2222]);
+        assert_eq!(ymm[1], [0, 0]);
+        let zmm = xstate.zmm.unwrap();
+        assert_eq!(zmm[0], [0x1111, 0x2222, 0, 0]);
+        assert_eq!(zmm[1], [0, 0, 0, 0x3333]);
+        assert_eq!(zmm[17], [0, 0, 0, 0x4444]);
+        assert_eq!(xstate.opmask.unwrap()[3], 0x55);
+        let state = MinidumpExtendedState::Amd64(Box::new(xstate));
+        assert_eq!(state.register_names().len(), 40);
+        assert_eq!(
+            state.format_register("ymm0").unwrap(),
+            format!("0x{:032x}{:032x}", 0x2222, 0x1111)
+        );
+        assert_eq!(
+            state.format_register("zmm17").unwrap(),
+            format!("0x{:032x}{:096x}", 0x4444, 0)
+        );
+        assert_eq!(state.format_register("k3").unwrap(), "0x0000000000000055");
+        assert_eq!(state.format_register("zmm32"), None);
+        assert_eq!(state.format_register("rip"), None);
+
+        // Truncated in the middle of the AVX-512 state
+        let truncated = &bytes[..256 + 1200];
+        let xstate = Amd64ExtendedState::read(truncated, scroll::LE, &raw, &config).unwrap();
+        assert_eq!(xstate.ymm.unwrap()[0], [0x1111, 0x2222]);
+        assert_eq!(xstate.opmask.unwrap()[3], 0x55);
+        assert_eq!(xstate.zmm, None);
+        let state = MinidumpExtendedState::Amd64(Box::new(xstate));
+        assert_eq!(state.register_names(), AMD64_AVX_REGISTERS);
+        assert_eq!(state.format_register("zmm0"), None);
+
+        // Without CONTEXT_HAS_XSTATE, only the AVX state inside CONTEXT_AMD64 is used
+        raw.context_flags = 0x10001f;
+        let xstate = Amd64ExtendedState::read(&bytes, scroll::LE, &raw, &config).unwrap();
+        assert!(xstate.ymm.is_some());
+        assert_eq!(xstate.opmask, None);
+        assert_eq!(xstate.zmm, None);
+
+        // Nothing saved
+        config.features.clear();
+        assert_eq!(
+            Amd64ExtendedState::read(&bytes, scroll::LE, &raw, &config),
+            None
+        );
+    }
+
+    #[test]
+    fn test_arm64_sve() {
+        let mut raw = md::CONTEXT_ARM64 {
+            context_flags: md::ContextFlagsArm64::CONTEXT_ARM64_FULL.bits()
+                | md::ContextFlagsArm64::CONTEXT_ARM64_XSTATE.bits(),
+            ..Default::default()
+        };
+        let base = mem::size_of::<md::CONTEXT_ARM64>();
+        let mut bytes = vec![0; base];
+
+        // 32-byte (256-bit) vectors: z0..z31, then p0..p15 and ffr of 4 bytes each.
+        let mut config = XStateConfig {
+            context_size: 0,
+            features: Default::default(),
+        };
+        let size = 32 * 32 + 17 * 4;
+        config.features.insert(
+            md::XSTATE_ARM64_SVE,
+            md::XSTATE_FEATURE {
+                offset: base as u32,
+                size,
+            },
+        );
+        bytes.resize(base + size as usize, 0);
+        bytes[base + 31 * 32] = 0x11;
+        bytes[base + 31 * 32 + 31] = 0x22;
+        bytes[base + 32 * 32 + 3 * 4] = 0x33;
+        bytes[base + 32 * 32 + 16 * 4 + 3] = 0x44;
+
+        let sve = Arm64SveRegisters::read(&bytes, &raw, &config).unwrap();
+        assert_eq!(sve.vector_length, 32);
+        assert_eq!(sve.z(31).unwrap()[0], 0x11);
+        assert_eq!(sve.z(31).unwrap().len(), 32);
+        assert_eq!(sve.z(32), None);
+        assert_eq!(sve.p(3).unwrap(), [0x33, 0, 0, 0]);
+        assert_eq!(sve.p(16), None);
+        assert_eq!(sve.ffr(), [0, 0, 0, 0x44]);
+        let state = MinidumpExtendedState::Arm64Sve(sve);
+        assert_eq!(state.register_names().len(), 49);
+        assert_eq!(
+            state.format_register("z31").unwrap(),
+            format!("0x22{:060x}11", 0)
+        );
+        assert_eq!(state.format_register("p3").unwrap(), "0x00000033");
+        assert_eq!(state.format_register("ffr").unwrap(), "0x44000000");
+        assert_eq!(state.format_register("z32"), None);
+        assert_eq!(state.format_register("x0"), None);
+
+        // Truncated
+        assert_eq!(
+            Arm64SveRegisters::read(&bytes[..bytes.len() - 1], &raw, &config),
+            None
+        );
+
+        // A size that isn't a valid vector length
+        config.features.get_mut(&md::XSTATE_ARM64_SVE).unwrap().size -= 4;
+        assert_eq!(Arm64SveRegisters::read(&bytes, &raw, &config), None);
+        config.features.get_mut(&md::XSTATE_ARM64_SVE).unwrap().size = size;
+
+        // Not flagged as present
+        raw.context_flags = md::ContextFlagsArm64::CONTEXT_ARM64_FULL.bits();
+        assert_eq!(Arm64SveRegisters::read(&bytes, &raw, &config), None);
+    }
+
+    #[test]
+    fn test_check_context_flags() {
+        let amd64 = &[ContextFlagsCpu::CONTEXT_AMD64];
+        let amd64_known = md::ContextFlagsAmd64::all().bits() | md::CONTEXT_HAS_XSTATE;
+        let mips = &[
+            ContextFlagsCpu::CONTEXT_MIPS,
+            ContextFlagsCpu::CONTEXT_MIPS64,
+        ];
+
+        // Exactly what we expect
+        assert!(check_context_flags("amd64", 0x10001f, amd64, amd64_known).is_ok());
+        assert!(check_context_flags("mips", 0x40002, mips, !md::CONTEXT_CPU_MASK).is_ok());
+        assert!(check_context_flags("mips", 0x80002, mips, !md::CONTEXT_CPU_MASK).is_ok());
+
+        // Unknown extensions still let the base registers be read
+        assert!(check_context_flags("amd64", 0x100080, amd64, amd64_known).is_ok());
+        assert!(check_context_flags("amd64", 0x210001f, amd64, amd64_known).is_ok());
+
+        // The wrong kind of context, or more than one kind
+        for flags in [0x1001f, 0x11001f, 0x1f] {
+            match check_context_flags("amd64", flags, amd64, amd64_known) {
+                Err(ContextError::UnsupportedContextFlags { arch, flags: f }) => {
+                    assert_eq!(arch, "amd64");
+                    assert_eq!(f, flags);
+                }
+                other => panic!("unexpected result for {:#x}: {:?}", flags, other),
+            }
+        }
+        assert!(check_context_flags("mips", 0xc0002, mips, !md::CONTEXT_CPU_MASK).is_err());
+    }
+
+    #[test]
+    fn test_decode_x86_eflags() {
+        assert_eq!(decode_x86_eflags(0x246), "PF ZF IF [flags 0x246]");
+        assert_eq!(
+            decode_x86_eflags(0x13a97),
+            "CF PF AF SF IF OF RF IOPL=3 [flags 0x13a97]"
+        );
+        assert_eq!(decode_x86_eflags(0), "[flags 0x0]");
+
+        let x86 = md::CONTEXT_X86 {
+            context_flags: md::ContextFlagsX86::CONTEXT_X86_FULL.bits(),
+            eflags: 0x202,
+            ..Default::default()
+        };
+        let mut context = MinidumpContext::from_raw(MinidumpRawContext::X86(x86));
+        assert_eq!(context.decoded_flags().unwrap(), "IF [flags 0x202]");
+        let mut output = Vec::new();
+        context.print(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("eflags                       = IF [flags 0x202]\n"));
+
+        // Unwound x86 contexts only know their flags if the unwinder did.
+        context.valid = MinidumpContextValidity::Some(["eip", "esp"].iter().copied().collect());
+        assert_eq!(context.decoded_flags(), None);
+        context.valid = MinidumpContextValidity::Some(["eflags"].iter().copied().collect());
+        assert_eq!(context.decoded_flags().unwrap(), "IF [flags 0x202]");
+
+        let amd64 = md::CONTEXT_AMD64 {
+            eflags: 0x10257,
+            ..Default::default()
+        };
+        let mut context = MinidumpContext::from_raw(MinidumpRawContext::Amd64(amd64));
+        assert_eq!(
+            context.decoded_flags().unwrap(),
+            "CF PF AF ZF IF RF [flags 0x10257]"
+        );
+        context.valid = MinidumpContextValidity::Some(["rip", "rsp"].iter().copied().collect());
+        assert_eq!(context.decoded_flags(), None);
+    }
+
+    #[test]
+    fn test_decode_arm_cpsr() {
+        assert_eq!(decode_arm_cpsr(0x60000030), "Z C T usr [flags 0x60000030]");
+        assert_eq!(
+            decode_arm_cpsr(0x800001d3),
+            "N A I F svc [flags 0x800001d3]"
+        );
+        assert_eq!(decode_arm_cpsr(0x1c), "mode=0x1c [flags 0x1c]");
+
+        let raw = md::CONTEXT_ARM {
+            context_flags: md::ContextFlagsArm::CONTEXT_ARM_FULL.bits(),
+            cpsr: 0x20000010,
+            ..Default::default()
+        };
+        let context = MinidumpContext::from_raw(MinidumpRawContext::Arm(raw));
+        assert_eq!(context.decoded_flags().unwrap(), "C usr [flags 0x20000010]");
+    }
+
+    #[test]
+    fn test_decode_arm64_pstate() {
+        assert_eq!(
+            decode_arm64_pstate(0x60000000),
+            "Z C EL0t [flags 0x60000000]"
+        );
+        assert_eq!(
+            decode_arm64_pstate(0x800003c5),
+            "N D A I F EL1h [flags 0x800003c5]"
+        );
+        assert_eq!(decode_arm64_pstate(0x10), "aarch32 [flags 0x10]");
+
+        let raw = md::CONTEXT_ARM64 {
+            context_flags: md::ContextFlagsArm64::CONTEXT_ARM64_FULL.bits(),
+            cpsr: 0x80000000,
+            ..Default::default()
+        };
+        let context = MinidumpContext::from_raw(MinidumpRawContext::Arm64(raw));
+        assert_eq!(
+            context.decoded_flags().unwrap(),
+            "N EL0t [flags 0x80000000]"
+        );
+        let mut output = Vec::new();
+        context.print(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("cpsr                 = N EL0t [flags 0x80000000]\n"));
+
+        let raw = md::CONTEXT_ARM64_OLD {
+            cpsr: 0x4,
+            ..Default::default()
+        };
+        let context = MinidumpContext::from_raw(MinidumpRawContext::OldArm64(raw));
+        assert_eq!(context.decoded_flags().unwrap(), "EL1t [flags 0x4]");
+    }
 }
@@ -47,9 +47,9 @@
 //!     // Conditionally analyze a stream
 //!     if let Ok(threads) = dump.get_stream::<MinidumpThreadList>() {
 //!         // Use `Default` to try to make progress when a stream is missing.
-//!         // This is especially natural for MinidumpMemoryList because
+//!         // This is especially natural for memory lists because
 //!         // everything needs to handle memory lookups failing anyway.
-//!         let mem = dump.get_stream::<MinidumpMemoryList>().unwrap_or_default();
+//!         let mem = dump.get_memory().unwrap_or_default();
 //!
 //!         for thread in &threads.threads {
 //!             let stack = thread.stack_memory(&mem);
@@ -72,19 +72,31 @@
 //! * [`MinidumpBreakpadInfo`][]
 //! * [`MinidumpCrashpadInfo`][]
 //! * [`MinidumpException`][]
+//! * [`MinidumpFunctionTable`][]
+//! * [`MinidumpHandleDataStream`][]
+//! * [`MinidumpHandleOperationList`][]
+//! * [`MinidumpIptTrace`][]
+//! * [`MinidumpLinuxAuxv`][]
 //! * [`MinidumpLinuxCpuInfo`][]
+//! * [`MinidumpLinuxDsoDebug`][]
 //! * [`MinidumpLinuxEnviron`][]
+//! * [`MinidumpLinuxLimits`][]
 //! * [`MinidumpLinuxLsbRelease`][]
 //! * [`MinidumpLinuxMaps`][]
 //! * [`MinidumpLinuxProcStatus`][]
+//! * [`MinidumpMacBootargs`][]
 //! * [`MinidumpMacCrashInfo`][]
 //! * [`MinidumpMemoryList`][]
 //! * [`MinidumpMemoryInfoList`][]
 //! * [`MinidumpMiscInfo`][]
 //! * [`MinidumpModuleList`][]
+//! * [`MinidumpProcessVmCounters`][]
 //! * [`MinidumpSystemInfo`][]
+//! * [`MinidumpSystemMemoryInfo`][]
+//! * [`MinidumpThreadInfoList`][]
 //! * [`MinidumpThreadList`][]
 //! * [`MinidumpThreadNames`][]
+//! * [`MinidumpTokenInfoList`][]
 //! * [`MinidumpUnloadedModuleList`][]
 //!
 //!
@@ -345,15 +357,15 @@
 //! ```
 //!
 //! This design allows newer versions of the stream to be introduced, and for fields
-//! to be added to the end of an entry type. I am not aware of an instance where
-//! this flexibility has been used yet, but in theory you could identify "versions"
-//! of the stream format by size, and older versions don't need to worry about
-//! unknown future revisions, because they can just ignore the trailing bytes of
-//! each entry.
+//! to be added to the end of an entry type. Microsoft has made use of this to extend
+//! `MINIDUMP_THREAD_INFO`, so we accept entries larger than we expect, and ignore
+//! the trailing bytes of each entry.
 //!
 //! Known members of this family:
 //!
+//! * [`MinidumpHandleOperationList`][] (entries are [`AVRF_HANDLE_OPERATION`][format::AVRF_HANDLE_OPERATION])
 //! * [`MinidumpMemoryInfoList`][] (entries are [`MINIDUMP_MEMORY_INFO`][format::MINIDUMP_MEMORY_INFO])
+//! * [`MinidumpThreadInfoList`][] (entries are [`MINIDUMP_THREAD_INFO`][format::MINIDUMP_THREAD_INFO])
 //! * [`MinidumpUnloadedModuleList`][] (entries are [`MINIDUMP_UNLOADED_MODULE`][format::MINIDUMP_UNLOADED_MODULE])
 //!
 //!
@@ -378,7 +390,7 @@
 //! Known members of this family:
 //!
 //! * [`MinidumpLinuxCpuInfo`][] (separator is `:`)
-//! * [`MinidumpLinuxEnviron`][] (separator is `=`)
+//! * [`MinidumpLinuxEnviron`][] (separator is `=`, and entries are NUL-delimited)
 //! * [`MinidumpLinuxLsbRelease`][] (separator is `=`)
 //! * [`MinidumpLinuxProcStatus`][] (separator is `:`)
 //!
@@ -399,12 +411,16 @@ pub use scroll::Endian;
 mod context;
 mod iostuff;
 mod minidump;
+mod redact;
+#[cfg(feature = "serde")]
+mod serde_impls;
 
 pub use minidump_common::format;
 pub use minidump_common::traits::Module;
 
 pub use crate::iostuff::Readable;
 pub use crate::minidump::*;
+pub use crate::redact::{MemoryAction, MinidumpRedactor};
 
 pub mod strings;
 pub mod system_info;
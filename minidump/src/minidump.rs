@@ -4,10 +4,11 @@
 use debugid::{CodeId, DebugId};
 use encoding::all::{UTF_16BE, UTF_16LE};
 use encoding::{DecoderTrap, Encoding};
+use memchr::memmem;
 use memmap2::Mmap;
 use num_traits::FromPrimitive;
 use scroll::ctx::{SizeWith, TryFromCtx};
-use scroll::{self, Pread, BE, LE};
+use scroll::{self, Pread, Pwrite, BE, LE};
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -28,7 +29,10 @@ use uuid::Uuid;
 
 pub use crate::context::*;
 use crate::strings::*;
-use crate::system_info::{Cpu, Os, PointerWidth};
+use crate::system_info::{
+    arm_part_name, arm_vendor_name, ArmCpuFeatures, ArmFeature, Cpu, Os, PointerWidth,
+};
+use minidump_common::cpu::{x86_microarchitecture, X86CpuSignature};
 use minidump_common::errors::{self as err};
 use minidump_common::format::{self as md};
 use minidump_common::format::{CvSignature, MINIDUMP_STREAM_TYPE};
@@ -61,13 +65,18 @@ pub struct Minidump<'a, T>
 where
     T: Deref<Target = [u8]> + 'a,
 {
-    data: T,
+    pub(crate) data: T,
     /// The raw minidump header from the file.
     pub header: md::MINIDUMP_HEADER,
-    streams: HashMap<u32, (u32, md::MINIDUMP_DIRECTORY)>,
+    /// The stream directory, in the order it appears in the file.
+    pub(crate) directory: Vec<md::MINIDUMP_DIRECTORY>,
+    /// Map from stream type to the index in `directory` of the last stream of that type.
+    streams: HashMap<u32, usize>,
     system_info: Option<MinidumpSystemInfo>,
     /// The endianness of this minidump file.
     pub endian: scroll::Endian,
+    /// Problems tolerated while reading, see [`MinidumpReadOptions::check_integrity`].
+    read_issues: Vec<CorruptionIssue>,
     _phantom: PhantomData<&'a [u8]>,
 }
 
@@ -124,6 +133,118 @@ impl Error {
     }
 }
 
+/// Options for [`Minidump::read_with_options`][].
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct MinidumpReadOptions {
+    /// Tolerate damage to the stream directory instead of failing to read.
+    ///
+    /// If the header claims more streams than fit in the file, only the ones
+    /// that do are read, and the problem is recorded for
+    /// [`Minidump::check_integrity`][] to report.
+    pub check_integrity: bool,
+}
+
+/// A structural problem with a minidump, found by [`Minidump::check_integrity`][].
+///
+/// These don't necessarily stop the minidump from being read, but they mean
+/// some of its contents are missing or can't be trusted.
+#[derive(Clone, Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CorruptionIssue {
+    #[error("Stream directory claims {count} streams, but only {available} fit in the file")]
+    DirectoryTruncated { count: u32, available: u32 },
+    #[error("Stream {index} (type {stream_type:#x}) at {rva:#x} with {size} bytes extends past the end of the file")]
+    StreamOutOfBounds {
+        index: usize,
+        stream_type: u32,
+        rva: u32,
+        size: u32,
+    },
+    #[error("Streams {first} (type {first_type:#x}) and {second} (type {second_type:#x}) overlap at {offset:#x}")]
+    OverlappingStreams {
+        first: usize,
+        first_type: u32,
+        second: usize,
+        second_type: u32,
+        offset: u32,
+    },
+    #[error(
+        "Stream of type {stream_type:#x} claims {count} entries, but only {available} fit in it"
+    )]
+    ImpossibleCount {
+        stream_type: u32,
+        count: u64,
+        available: u64,
+    },
+    #[error("Memory at {base_address:#x} in stream type {stream_type:#x} has contents at {rva:#x} with {size} bytes, past the end of the file")]
+    MemoryOutOfBounds {
+        stream_type: u32,
+        base_address: u64,
+        rva: u64,
+        size: u64,
+    },
+    #[error("Module at {base_address:#x} has its name at {rva:#x}, past the end of the file")]
+    ModuleNameOutOfBounds { base_address: u64, rva: u32 },
+    #[error("Module {index} at {base_address:#x} has an implausible size of {size:#x} bytes")]
+    ImplausibleModuleSize {
+        index: usize,
+        base_address: u64,
+        size: u64,
+    },
+    #[error("Modules {first} and {second} overlap at {address:#x}, so addresses are only looked up in module {first}")]
+    OverlappingModules {
+        first: usize,
+        second: usize,
+        address: u64,
+    },
+}
+
+impl CorruptionIssue {
+    /// Returns just the name of the issue, like [`Error::name`][].
+    pub fn name(&self) -> &'static str {
+        match self {
+            CorruptionIssue::DirectoryTruncated { .. } => "DirectoryTruncated",
+            CorruptionIssue::StreamOutOfBounds { .. } => "StreamOutOfBounds",
+            CorruptionIssue::OverlappingStreams { .. } => "OverlappingStreams",
+            CorruptionIssue::ImpossibleCount { .. } => "ImpossibleCount",
+            CorruptionIssue::MemoryOutOfBounds { .. } => "MemoryOutOfBounds",
+            CorruptionIssue::ModuleNameOutOfBounds { .. } => "ModuleNameOutOfBounds",
+            CorruptionIssue::ImplausibleModuleSize { .. } => "ImplausibleModuleSize",
+            CorruptionIssue::OverlappingModules { .. } => "OverlappingModules",
+        }
+    }
+
+    /// The type of the stream the issue is in, if it's in one.
+    pub fn stream_type(&self) -> Option<u32> {
+        match *self {
+            CorruptionIssue::DirectoryTruncated { .. } => None,
+            CorruptionIssue::StreamOutOfBounds { stream_type, .. }
+            | CorruptionIssue::ImpossibleCount { stream_type, .. }
+            | CorruptionIssue::MemoryOutOfBounds { stream_type, .. } => Some(stream_type),
+            CorruptionIssue::OverlappingStreams { second_type, .. } => Some(second_type),
+            CorruptionIssue::ModuleNameOutOfBounds { .. }
+            | CorruptionIssue::ImplausibleModuleSize { .. }
+            | CorruptionIssue::OverlappingModules { .. } => {
+                Some(MINIDUMP_STREAM_TYPE::ModuleListStream as u32)
+            }
+        }
+    }
+
+    /// The offset in the file of the damaged data, if there's a single one.
+    pub fn offset(&self) -> Option<u64> {
+        match *self {
+            CorruptionIssue::DirectoryTruncated { .. }
+            | CorruptionIssue::ImpossibleCount { .. }
+            | CorruptionIssue::ImplausibleModuleSize { .. }
+            | CorruptionIssue::OverlappingModules { .. } => None,
+            CorruptionIssue::StreamOutOfBounds { rva, .. }
+            | CorruptionIssue::ModuleNameOutOfBounds { rva, .. } => Some(rva as u64),
+            CorruptionIssue::OverlappingStreams { offset, .. } => Some(offset as u64),
+            CorruptionIssue::MemoryOutOfBounds { rva, .. } => Some(rva),
+        }
+    }
+}
+
 /// The fundamental unit of data in a `Minidump`.
 pub trait MinidumpStream<'a>: Sized {
     /// The stream type constant used in the `md::MDRawDirectory` entry.
@@ -208,6 +329,11 @@ pub struct MinidumpLinuxMapInfo<'a> {
     pub is_shared: bool,
     /// Whether the memory region is private (copy-on-write).
     pub is_private: bool,
+    /// The region's `VmFlags`, if the stream has them.
+    ///
+    /// These are only in `/proc/self/smaps`, which some tools write instead
+    /// of `/proc/self/maps`. See [`MinidumpLinuxMapInfo::has_vm_flag`].
+    pub vm_flags: Option<Cow<'a, LinuxOsStr>>,
 
     // Fields in the format we ignore (not yet useful)
     // * offset
@@ -229,6 +355,8 @@ pub enum MinidumpLinuxMapKind<'a> {
     Vdso,
     /// This is an anonymous mmap.
     AnonymousMap,
+    /// This is an anonymous mmap that was given a name (with `PR_SET_VMA_ANON_NAME`).
+    NamedAnonymousMap(Cow<'a, LinuxOsStr>),
     /// Some other special kind that we don't know/care about.
     UnknownSpecial(Cow<'a, LinuxOsStr>),
     /// This is a mapped file/device at the given path.
@@ -237,6 +365,70 @@ pub enum MinidumpLinuxMapKind<'a> {
     DeletedFile(Cow<'a, LinuxOsStr>),
 }
 
+/// What a region of memory in the crashed process was being used for.
+///
+/// See [`UnifiedMemoryInfoList::memory_kind_at_address`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryKind {
+    /// A thread's stack. The thread is known if it's in the minidump's thread
+    /// list, or if `/proc/self/maps` says so.
+    Stack { thread_id: Option<u32> },
+    /// The process's heap.
+    ///
+    /// Only Linux's `[heap]` can be identified, allocations on Windows (or by
+    /// `mmap`) are just [`MemoryKind::Anonymous`].
+    Heap,
+    /// A mapped file (or executable image), with its path if known.
+    ///
+    /// Special mappings like `[vdso]` use their name as the path.
+    MappedFile(Option<String>),
+    /// Memory that isn't backed by a file.
+    Anonymous,
+    /// A guard page, or any other mapping that can't be accessed at all.
+    Guard,
+    /// Nothing was mapped here (or the address space was only reserved).
+    Unmapped,
+}
+
+impl MemoryKind {
+    /// A short name for this kind of memory, like `"stack"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MemoryKind::Stack { .. } => "stack",
+            MemoryKind::Heap => "heap",
+            MemoryKind::MappedFile(_) => "mapped_file",
+            MemoryKind::Anonymous => "anonymous",
+            MemoryKind::Guard => "guard",
+            MemoryKind::Unmapped => "unmapped",
+        }
+    }
+}
+
+impl fmt::Display for MemoryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryKind::Stack {
+                thread_id: Some(thread_id),
+            } => write!(f, "stack of thread {:#x}", thread_id),
+            MemoryKind::MappedFile(Some(path)) => write!(f, "mapped file {}", path),
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+/// The thread whose declared stack overlaps `range`, if any.
+fn stack_owner(range: Option<Range<u64>>, threads: Option<&MinidumpThreadList>) -> Option<u32> {
+    let range = range?;
+    threads?
+        .threads
+        .iter()
+        .find(|thread| {
+            matches!(thread.declared_stack_range(),
+                Some(stack) if stack.start <= range.end && range.start < stack.end)
+        })
+        .map(|thread| thread.raw.thread_id)
+}
+
 #[derive(Debug, Clone)]
 pub struct MinidumpMemoryInfoList<'a> {
     /// The memory regions, in the order they were stored in the minidump.
@@ -275,6 +467,36 @@ pub enum CodeView {
     Unknown(Vec<u8>),
 }
 
+/// The version information of a module, from its `VS_FIXEDFILEINFO`.
+///
+/// Only Windows modules have this, the structure is zeroed for other modules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// The version of the file, most significant part first.
+    pub file_version: [u16; 4],
+    /// The version of the product the file is distributed with, most significant part first.
+    pub product_version: [u16; 4],
+    /// The flags that are set, limited to the ones `file_flags_mask` says are valid.
+    pub file_flags: md::VersionFileFlags,
+    /// The operating system the file was designed for, see [`VersionInfo::os`].
+    pub file_os: u32,
+    /// The general type of the file, see [`VersionInfo::file_type`].
+    pub file_type: u32,
+    /// The specific type of the file (e.g. the kind of driver or font), if `file_type` has any.
+    pub file_subtype: u32,
+}
+
+/// The convention a module's code identifier follows, see [`MinidumpModule::code_identifier_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeIdentifierKind {
+    /// A PE file's timestamp followed by its image size, as used by Microsoft's symbol server.
+    PeTimestampAndSize,
+    /// An ELF file's build id, as hex.
+    ElfBuildId,
+    /// A Mach-O file's `LC_UUID`.
+    MachOUuid,
+}
+
 /// An executable or shared library loaded in the process at the time the `Minidump` was written.
 #[derive(Debug, Clone)]
 pub struct MinidumpModule {
@@ -298,6 +520,8 @@ pub struct MinidumpModuleList {
     modules: Vec<MinidumpModule>,
     /// Map from address range to index in modules. Use `MinidumpModuleList::module_at_address`.
     modules_by_addr: RangeMap<u64, usize>,
+    /// Problems with the modules' address ranges, see `MinidumpModuleList::range_issues`.
+    range_issues: Vec<CorruptionIssue>,
 }
 
 /// A mapping of thread ids to their names.
@@ -306,6 +530,190 @@ pub struct MinidumpThreadNames {
     names: BTreeMap<u32, String>,
 }
 
+/// The raw descriptor of a handle, in whichever version the minidump contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawHandleDescriptor {
+    HandleDescriptor(md::MINIDUMP_HANDLE_DESCRIPTOR),
+    HandleDescriptor2(md::MINIDUMP_HANDLE_DESCRIPTOR_2),
+}
+
+/// A handle the process had open when the `Minidump` was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinidumpHandleDescriptor {
+    /// The raw descriptor direct from the minidump file.
+    pub raw: RawHandleDescriptor,
+    /// The name of the object's type (like "File" or "Event"), if present.
+    pub type_name: Option<String>,
+    /// The name of the object, if present.
+    pub object_name: Option<String>,
+}
+
+/// The handles the process had open when the `Minidump` was written.
+#[derive(Debug, Clone, Default)]
+pub struct MinidumpHandleDataStream {
+    /// The handles, in the order they were stored in the minidump.
+    handles: Vec<MinidumpHandleDescriptor>,
+}
+
+/// An operation on a handle, recorded by Application Verifier's handle tracing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinidumpHandleOperation {
+    /// The `AVRF_HANDLE_OPERATION` direct from the minidump file.
+    pub raw: md::AVRF_HANDLE_OPERATION,
+    /// What kind of operation this was, if known.
+    pub operation_type: Option<md::HandleTraceOperation>,
+}
+
+/// The operations on handles recorded by Application Verifier's handle tracing.
+///
+/// Only present when handle tracing was enabled for the process.
+#[derive(Debug, Clone, Default)]
+pub struct MinidumpHandleOperationList {
+    /// The operations, in the order they were stored in the minidump.
+    operations: Vec<MinidumpHandleOperation>,
+}
+
+/// A dynamic function table, registered by the process with `RtlAddFunctionTable`.
+///
+/// These describe how to unwind code outside of any module, like JIT-compiled code.
+#[derive(Debug, Clone)]
+pub struct MinidumpFunctionTableDescriptor<'a> {
+    /// The `MINIDUMP_FUNCTION_TABLE_DESCRIPTOR` direct from the minidump file.
+    pub raw: md::MINIDUMP_FUNCTION_TABLE_DESCRIPTOR,
+    /// The OS's native description of the table (a `DYNAMIC_FUNCTION_TABLE` on Windows).
+    pub native_descriptor: &'a [u8],
+    /// The table's function entries, `entry_size` bytes each.
+    pub raw_entries: &'a [u8],
+    /// The size of each function entry, in bytes.
+    pub entry_size: usize,
+    endian: scroll::Endian,
+}
+
+/// A list of `MinidumpFunctionTableDescriptor`s contained in a `Minidump`.
+#[derive(Debug, Clone)]
+pub struct MinidumpFunctionTable<'a> {
+    /// The tables, in the order they were stored in the minidump.
+    tables: Vec<MinidumpFunctionTableDescriptor<'a>>,
+    /// Map from address range to index in tables. Use
+    /// [`MinidumpFunctionTable::table_for_address`].
+    tables_by_addr: RangeMap<u64, usize>,
+}
+
+/// The raw memory usage counters, in whichever version the minidump contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawProcessVmCounters {
+    V1(md::MINIDUMP_PROCESS_VM_COUNTERS_1),
+    V2(md::MINIDUMP_PROCESS_VM_COUNTERS_2),
+}
+
+/// Memory usage counters for the process, useful for triaging out-of-memory crashes.
+///
+/// Every counter is `None` if the minidump's version of the stream doesn't have it,
+/// or says it isn't valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinidumpProcessVmCounters {
+    /// The counters direct from the minidump file.
+    pub raw: RawProcessVmCounters,
+}
+
+/// A security token of the process or one of its threads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinidumpTokenInfo<'a> {
+    /// The `MINIDUMP_TOKEN_INFO_HEADER` direct from the minidump file.
+    pub raw: md::MINIDUMP_TOKEN_INFO_HEADER,
+    /// The token's data, which isn't decoded.
+    pub data: &'a [u8],
+}
+
+/// The security tokens captured in a `Minidump`.
+#[derive(Debug, Clone, Default)]
+pub struct MinidumpTokenInfoList<'a> {
+    /// The tokens, in the order they were stored in the minidump.
+    tokens: Vec<MinidumpTokenInfo<'a>>,
+}
+
+/// The Intel Processor Trace data captured in a `Minidump`, split up by thread.
+///
+/// The trace packets aren't decoded here: hand each thread's
+/// [`trace`][MinidumpIptThreadTrace::trace] to a decoder like libipt. Traces
+/// can be tens of megabytes per thread, so they're borrowed from the
+/// minidump's data rather than copied.
+#[derive(Debug, Clone)]
+pub struct MinidumpIptTrace<'a> {
+    /// The `IPT_TRACE_DATA` header direct from the minidump file.
+    pub raw: md::IPT_TRACE_DATA,
+    /// The threads' traces, in the order they were stored in the minidump.
+    threads: Vec<MinidumpIptThreadTrace<'a>>,
+}
+
+/// One thread's Intel Processor Trace packets, from a [`MinidumpIptTrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinidumpIptThreadTrace<'a> {
+    /// The `IPT_TRACE_HEADER` direct from the minidump file.
+    pub raw: md::IPT_TRACE_HEADER,
+    /// The trace packets.
+    pub trace: &'a [u8],
+}
+
+/// System-wide memory information, for telling whether the whole machine was low on memory.
+///
+/// Each group of fields is `None` if the stream is too short to contain it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinidumpSystemMemoryInfo {
+    /// The version of the stream's format.
+    pub revision: u16,
+    pub flags: u16,
+    pub basic_info: Option<md::MINIDUMP_SYSTEM_BASIC_INFORMATION>,
+    pub file_cache_info: Option<md::MINIDUMP_SYSTEM_FILECACHE_INFORMATION>,
+    pub basic_perf_info: Option<md::MINIDUMP_SYSTEM_BASIC_PERFORMANCE_INFORMATION>,
+    pub perf_info: Option<md::MINIDUMP_SYSTEM_PERFORMANCE_INFORMATION>,
+}
+
+/// The dynamic linker's `r_debug` data, in the pointer width of the minidump.
+#[derive(Debug, Clone)]
+pub enum RawDsoDebug {
+    DsoDebug32(md::DSO_DEBUG_32),
+    DsoDebug64(md::DSO_DEBUG_64),
+}
+
+/// A shared library in the dynamic linker's `link_map`, from [`MinidumpLinuxDsoDebug`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinidumpLinuxLinkMapEntry {
+    /// The difference between the library's addresses in memory and in its file.
+    ///
+    /// For position-independent libraries this is the address they were loaded at.
+    pub addr: u64,
+    /// The path of the library. This is empty for the main executable.
+    pub name: String,
+    /// The address of the library's dynamic section.
+    pub ld: u64,
+}
+
+/// The shared libraries the dynamic linker had loaded, from the `LinuxDsoDebug` stream
+/// Breakpad writes.
+///
+/// This is a snapshot of the linker's `link_map`, so it can list libraries that are missing
+/// from the [`MinidumpModuleList`], like ones that were in the middle of being `dlopen`ed.
+#[derive(Debug, Clone)]
+pub struct MinidumpLinuxDsoDebug<'a> {
+    /// The `r_debug` data direct from the minidump file.
+    pub raw: RawDsoDebug,
+    /// The contents of the linker's dynamic section, which follow `raw` in the stream.
+    pub dynamic_section: &'a [u8],
+    /// The shared libraries, in the order of the linker's list.
+    entries: Vec<MinidumpLinuxLinkMapEntry>,
+}
+
+/// The auxiliary vector the kernel passed to the process, from the `LinuxAuxv` stream.
+///
+/// This describes how the program was loaded: where the vdso is, the program's entry
+/// point, whether it's running in secure (setuid) mode, and so on.
+#[derive(Debug, Clone, Default)]
+pub struct MinidumpLinuxAuxv {
+    /// The `(key, value)` entries, in the order they were stored in the minidump.
+    entries: Vec<(u64, u64)>,
+}
+
 /// An executable or shared library that was once loaded into the process, but was unloaded
 /// by the time the `Minidump` was written.
 #[derive(Debug, Clone)]
@@ -339,6 +747,23 @@ pub struct MinidumpThread<'a> {
     endian: scroll::Endian,
 }
 
+/// The state of a Windows `CRITICAL_SECTION`, as read from a minidump's memory.
+///
+/// See [`CriticalSection::read`] and [`MinidumpThreadList::critical_section_owner`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CriticalSection {
+    /// The address of the `CRITICAL_SECTION` in the crashing process.
+    pub address: u64,
+    /// The raw `LockCount` field.
+    ///
+    /// Its interpretation changed in Windows Vista, so it's not decoded here.
+    pub lock_count: i32,
+    /// How many times the owning thread has acquired the lock.
+    pub recursion_count: i32,
+    /// The id of the thread that holds the lock, or `None` if it isn't held.
+    pub owning_thread: Option<u32>,
+}
+
 /// A list of `MinidumpThread`s contained in a `Minidump`.
 #[derive(Debug)]
 pub struct MinidumpThreadList<'a> {
@@ -348,6 +773,27 @@ pub struct MinidumpThreadList<'a> {
     thread_ids: HashMap<u32, usize>,
 }
 
+/// Extra information about a thread, like its start address and CPU times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinidumpThreadInfo {
+    /// The `MINIDUMP_THREAD_INFO` direct from the minidump file.
+    pub raw: md::MINIDUMP_THREAD_INFO,
+    /// What went wrong while writing the thread's information.
+    pub dump_flags: md::ThreadInfoDumpFlags,
+}
+
+/// A list of `MinidumpThreadInfo`s contained in a `Minidump`.
+///
+/// Use [`MinidumpThread::info`] or [`MinidumpThreadInfoList::get_thread_info`] to look up
+/// a thread's info.
+#[derive(Debug, Clone, Default)]
+pub struct MinidumpThreadInfoList {
+    /// The thread infos, in the order they were present in the `Minidump`.
+    thread_infos: Vec<MinidumpThreadInfo>,
+    /// A map of thread id to index in `thread_infos`.
+    thread_ids: HashMap<u32, usize>,
+}
+
 /// Information about the system that generated the minidump.
 #[derive(Debug, Clone)]
 pub struct MinidumpSystemInfo {
@@ -364,11 +810,17 @@ pub struct MinidumpSystemInfo {
     /// An x86 (not x64!) CPU vendor name that is stored in `raw` but in a way
     /// that's
     cpu_info: Option<String>,
+    /// The ARM cpu model and features stored in `raw`.
+    cpu_features: Option<ArmCpuFeatures>,
+    /// The x86 or amd64 cpu vendor string stored in `raw`, if it has one.
+    x86_vendor: Option<String>,
+    /// The x86 or amd64 cpu family, model, and stepping.
+    x86_signature: Option<X86CpuSignature>,
 }
 
 /// A region of memory from the process that wrote the minidump.
 /// This is the underlying generic type for [MinidumpMemory] and [MinidumpMemory64].
-#[derive(Clone, Debug)]
+#[derive(Copy, Clone, Debug)]
 pub struct MinidumpMemoryBase<'a, Descriptor> {
     /// The raw `MINIDUMP_MEMORY_DESCRIPTOR` from the minidump.
     pub desc: Descriptor,
@@ -376,7 +828,7 @@ pub struct MinidumpMemoryBase<'a, Descriptor> {
     pub base_address: u64,
     /// The length of this range of memory.
     pub size: u64,
-    /// The contents of the memory.
+    /// The contents of the memory, borrowed from the minidump's data.
     pub bytes: &'a [u8],
     /// The endianness of the minidump which is used for memory accesses.
     pub endian: scroll::Endian,
@@ -411,6 +863,15 @@ pub struct MinidumpMacCrashInfo {
     pub raw: Vec<RawMacCrashInfo>,
 }
 
+/// The kernel boot arguments of a macOS system (the `kern.bootargs` sysctl).
+#[derive(Debug, Clone)]
+pub struct MinidumpMacBootargs {
+    /// The `MINIDUMP_MAC_BOOTARGS` direct from the minidump.
+    pub raw: md::MINIDUMP_MAC_BOOTARGS,
+    /// The boot arguments, if they could be read.
+    pub bootargs: Option<String>,
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum RawMiscInfo {
@@ -428,6 +889,20 @@ pub struct MinidumpMiscInfo {
     pub raw: RawMiscInfo,
 }
 
+/// Which extended processor state (XSTATE) features the minidump's thread
+/// contexts contain, and where.
+///
+/// See [`MinidumpMiscInfo::xstate_config`], and the docs of
+/// [`md::XSTATE_CONFIG_FEATURE_MSC_INFO`] for how to interpret the offsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XStateConfig {
+    /// The size of the XSAVE context.
+    pub context_size: u32,
+    /// The offset and size of every enabled feature, keyed by its index
+    /// (see [`md::XstateFeatureIndex`]).
+    pub features: BTreeMap<usize, md::XSTATE_FEATURE>,
+}
+
 /// Additional information about process state.
 ///
 /// MinidumpBreakpadInfo wraps MINIDUMP_BREAKPAD_INFO, which is an optional stream
@@ -466,6 +941,28 @@ pub struct MinidumpLinuxProcStatus<'a> {
     data: &'a [u8],
 }
 
+/// The resource limits of the process, extracted from /proc/self/limits
+///
+/// This is the `MozLinuxLimits` stream written by Firefox's crash reporter.
+#[derive(Default, Debug)]
+pub struct MinidumpLinuxLimits<'a> {
+    data: &'a [u8],
+    limits: Vec<MinidumpLinuxLimit<'a>>,
+}
+
+/// A resource limit from [`MinidumpLinuxLimits`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinidumpLinuxLimit<'a> {
+    /// The kernel's description of the limit, like "Max open files".
+    pub name: &'a str,
+    /// The soft limit, or `None` if it's unlimited.
+    pub soft: Option<u64>,
+    /// The hard limit, or `None` if it's unlimited.
+    pub hard: Option<u64>,
+    /// The unit of the limit (like "bytes" or "files"), if it has one.
+    pub unit: Option<&'a str>,
+}
+
 /// The reason for a process crash.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum CrashReason {
@@ -519,6 +1016,89 @@ pub enum CrashReason {
     Unknown(u32, u32),
 }
 
+/// The decoded parameters of a Windows exception.
+///
+/// Most Windows exception codes say nothing about the contents of their
+/// `exception_information`, but these document what it means.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WindowsExceptionParameters {
+    /// The parameters of an `EXCEPTION_ACCESS_VIOLATION`.
+    AccessViolation {
+        /// The kind of access that faulted, if it's one we know.
+        access: Option<err::ExceptionCodeWindowsAccessType>,
+        /// The address that was accessed, if the exception has it.
+        address: Option<u64>,
+    },
+    /// The parameters of an `EXCEPTION_IN_PAGE_ERROR`.
+    InPageError {
+        /// The kind of access that faulted, if it's one we know.
+        access: Option<err::ExceptionCodeWindowsInPageErrorType>,
+        /// The address that was accessed.
+        address: u64,
+        /// The NTSTATUS explaining why the page couldn't be read in.
+        nt_status: u64,
+    },
+    /// The parameters of a `STATUS_STACK_BUFFER_OVERRUN`, which (despite
+    /// the name) is what every `__fastfail` raises.
+    FastFail {
+        /// The code passed to `__fastfail`, see [`err::FastFailCode`][].
+        code: u64,
+    },
+}
+
+impl WindowsExceptionParameters {
+    /// Decode the parameters of `record`, if its exception code documents them
+    /// and it has enough of them.
+    pub fn from_record(record: &md::MINIDUMP_EXCEPTION) -> Option<WindowsExceptionParameters> {
+        let info = &record.exception_information;
+        let count = record.number_parameters;
+        let code = record.exception_code;
+        if code == err::ExceptionCodeWindows::EXCEPTION_ACCESS_VIOLATION as u32 && count >= 1 {
+            // exception_information[0] is 0 if the violation was caused by
+            // an attempt to read data, 1 if it was an attempt to write data,
+            // and 8 if this was a data execution violation.
+            // exception_information[1] is the address that caused the fault.
+            Some(WindowsExceptionParameters::AccessViolation {
+                access: err::ExceptionCodeWindowsAccessType::from_u64(info[0]),
+                address: (count >= 2).then_some(info[1]),
+            })
+        } else if code == err::ExceptionCodeWindows::EXCEPTION_IN_PAGE_ERROR as u32 && count >= 3 {
+            // Like EXCEPTION_ACCESS_VIOLATION, but with the underlying NTSTATUS
+            // code, which explains why the error occurred, in exception_information[2].
+            Some(WindowsExceptionParameters::InPageError {
+                access: err::ExceptionCodeWindowsInPageErrorType::from_u64(info[0]),
+                address: info[1],
+                nt_status: info[2],
+            })
+        } else if code == err::NtStatusWindows::STATUS_STACK_BUFFER_OVERRUN as u32 && count >= 1 {
+            // STATUS_STACK_BUFFER_OVERRUN are caused by __fastfail()
+            // invocations and the fast-fail code is stored in
+            // exception_information[0].
+            Some(WindowsExceptionParameters::FastFail { code: info[0] })
+        } else {
+            None
+        }
+    }
+
+    /// The known fast-fail code of a [`WindowsExceptionParameters::FastFail`][].
+    pub fn fast_fail_code(&self) -> Option<err::FastFailCode> {
+        match *self {
+            WindowsExceptionParameters::FastFail { code } => err::FastFailCode::from_u64(code),
+            _ => None,
+        }
+    }
+
+    /// The known NTSTATUS of a [`WindowsExceptionParameters::InPageError`][].
+    pub fn nt_status(&self) -> Option<err::NtStatusWindows> {
+        match *self {
+            WindowsExceptionParameters::InPageError { nt_status, .. } => {
+                err::NtStatusWindows::from_u64(nt_status)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Information about the exception that caused the minidump to be generated.
 ///
 /// `MinidumpException` wraps `MINIDUMP_EXCEPTION_STREAM`, which contains information
@@ -545,7 +1125,7 @@ pub struct MinidumpException<'a> {
 }
 
 /// A list of memory regions included in a minidump.
-/// This is the underlying generic type for [MinidumpMemoryList] and [MinidumpMemory64List].
+/// This is the underlying generic type for [MinidumpMemoryList].
 #[derive(Debug)]
 pub struct MinidumpMemoryListBase<'a, Descriptor> {
     /// The memory regions, in the order they were stored in the  minidump.
@@ -558,7 +1138,73 @@ pub struct MinidumpMemoryListBase<'a, Descriptor> {
 pub type MinidumpMemoryList<'a> = MinidumpMemoryListBase<'a, md::MINIDUMP_MEMORY_DESCRIPTOR>;
 
 /// A list of large memory regions included in a minidump (usually a full dump).
-pub type MinidumpMemory64List<'a> = MinidumpMemoryListBase<'a, md::MINIDUMP_MEMORY_DESCRIPTOR64>;
+///
+/// Full-memory dumps can have tens of thousands of regions adding up to many
+/// gigabytes, so unlike [`MinidumpMemoryList`] this only keeps the table of
+/// descriptors around. A [`MinidumpMemory64`] is built for a region when it is
+/// looked up or iterated over, with its `bytes` borrowed from the [`Minidump`].
+///
+/// The contents of the regions are found with a 64-bit RVA, so unlike
+/// everything else in a minidump they can be past the first 4GB of the file.
+#[derive(Debug, Clone)]
+pub struct MinidumpMemory64List<'a> {
+    /// The raw descriptors, in the order they were stored in the minidump.
+    descriptors: Vec<md::MINIDUMP_MEMORY_DESCRIPTOR64>,
+    /// The RVA of the first region's contents. The contents of every other
+    /// region immediately follow those of the previous one.
+    base_rva: u64,
+    /// Map from address range to the index and RVA of a region.
+    regions_by_addr: RangeMap<u64, (usize, u64)>,
+    /// The entire minidump, which the contents of the regions are borrowed from.
+    data: &'a [u8],
+    /// The endianness of the minidump.
+    endian: scroll::Endian,
+}
+
+/// Provides a unified interface for reading the memory regions captured in a minidump.
+///
+/// Currently this is one of [`MinidumpMemoryList`], which most minidumps
+/// contain, or [`MinidumpMemory64List`], which full-memory dumps contain
+/// instead. The latter can easily be several gigabytes, but because every
+/// region just borrows from the backing [`Minidump`], a dump opened with
+/// [`Minidump::read_path`] only pages in the parts of the file that are
+/// actually looked at (for instance, the handful of stack words an unwinder
+/// reads). Iteration and lookup never copy region contents, and a
+/// `MinidumpMemory64List` doesn't even build its regions until they're needed.
+///
+/// Get one with [`Minidump::get_memory`]. Prefer using this type's methods
+/// over pattern matching.
+#[derive(Debug)]
+pub enum UnifiedMemoryList<'a> {
+    Memory(MinidumpMemoryList<'a>),
+    Memory64(MinidumpMemory64List<'a>),
+}
+
+/// A [`UnifiedMemoryList`] entry, a region of memory from the process that
+/// wrote the minidump.
+///
+/// `'a` is the lifetime of the list the region was found in, while `'mdmp`
+/// is the lifetime of the [`Minidump`] its contents are borrowed from.
+#[derive(Debug, Copy, Clone)]
+pub enum UnifiedMemory<'a, 'mdmp> {
+    Memory(&'a MinidumpMemory<'mdmp>),
+    Memory64(MinidumpMemory64<'mdmp>),
+}
+
+/// A run of adjacent memory regions joined together, from
+/// [`UnifiedMemoryList::merged_memory_at_address`].
+///
+/// Minidumps sometimes split one range of memory over several regions (a
+/// thread's stack and some explicitly added memory that continues it, say).
+/// Anything that reads its way through memory, like a stack scan, would
+/// otherwise stop at the artificial edge between them.
+#[derive(Debug, Clone)]
+pub struct MergedMemory<'a> {
+    base_address: u64,
+    bytes: Cow<'a, [u8]>,
+    region_count: usize,
+    endian: scroll::Endian,
+}
 
 /// Information about an assertion that caused a crash.
 #[derive(Debug)]
@@ -574,10 +1220,32 @@ pub enum MinidumpAnnotation {
     Invalid,
     /// A `NUL`-terminated C-string.
     String(String),
-    /// Clients may declare their own custom types.
-    UserDefined(md::MINIDUMP_ANNOTATION),
-    /// An unsupported annotation from a future crashpad version.
-    Unsupported(md::MINIDUMP_ANNOTATION),
+    /// Clients may declare their own custom types, carried as raw bytes.
+    UserDefined(md::MINIDUMP_ANNOTATION, Vec<u8>),
+    /// An unsupported annotation from a future crashpad version, carried as raw bytes.
+    Unsupported(md::MINIDUMP_ANNOTATION, Vec<u8>),
+}
+
+impl MinidumpAnnotation {
+    /// The annotation's type id, as in `MINIDUMP_ANNOTATION::ty`.
+    pub fn ty(&self) -> u16 {
+        match self {
+            Self::Invalid => md::MINIDUMP_ANNOTATION::TYPE_INVALID,
+            Self::String(_) => md::MINIDUMP_ANNOTATION::TYPE_STRING,
+            Self::UserDefined(raw, _) | Self::Unsupported(raw, _) => raw.ty,
+        }
+    }
+
+    /// The annotation's value, as the bytes stored in the minidump.
+    ///
+    /// For string annotations these are the UTF-8 bytes of the string.
+    pub fn value(&self) -> &[u8] {
+        match self {
+            Self::Invalid => &[],
+            Self::String(string) => string.as_bytes(),
+            Self::UserDefined(_, bytes) | Self::Unsupported(_, bytes) => bytes,
+        }
+    }
 }
 
 impl PartialEq for MinidumpAnnotation {
@@ -585,13 +1253,17 @@ impl PartialEq for MinidumpAnnotation {
         match (self, other) {
             (Self::Invalid, Self::Invalid) => true,
             (Self::String(a), Self::String(b)) => a == b,
+            (Self::UserDefined(a, a_bytes), Self::UserDefined(b, b_bytes))
+            | (Self::Unsupported(a, a_bytes), Self::Unsupported(b, b_bytes)) => {
+                a.ty == b.ty && a_bytes == b_bytes
+            }
             _ => false,
         }
     }
 }
 
 /// Additional Crashpad-specific information about a module carried within a minidump file.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct MinidumpModuleCrashpadInfo {
     /// The raw crashpad module extension information.
     pub raw: md::MINIDUMP_MODULE_CRASHPAD_INFO,
@@ -603,7 +1275,7 @@ pub struct MinidumpModuleCrashpadInfo {
 }
 
 /// Additional Crashpad-specific information carried within a minidump file.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct MinidumpCrashpadInfo {
     pub raw: md::MINIDUMP_CRASHPAD_INFO,
     pub simple_annotations: BTreeMap<String, String>,
@@ -653,6 +1325,36 @@ fn location_slice<'a>(
         .ok_or(Error::StreamReadFailure)
 }
 
+/// Write `bytes` to `f` as a hex string, 16 bytes (and their ASCII) per line.
+fn write_hexdump<T: Write>(f: &mut T, bytes: &[u8]) -> io::Result<()> {
+    const PARAGRAPH_SIZE: usize = 16;
+    let mut offset = 0;
+    for paragraph in bytes.chunks(PARAGRAPH_SIZE) {
+        write!(f, "    {:08x}: ", offset)?;
+        let mut byte_iter = paragraph.iter().fuse();
+        for _ in 0..PARAGRAPH_SIZE {
+            if let Some(byte) = byte_iter.next() {
+                write!(f, "{:02x} ", byte)?;
+            } else {
+                write!(f, "   ")?;
+            }
+        }
+        for &byte in paragraph.iter() {
+            let ascii_char = if !byte.is_ascii() || byte.is_ascii_control() {
+                '.'
+            } else {
+                char::from(byte)
+            };
+
+            write!(f, "{}", ascii_char)?;
+        }
+        writeln!(f)?;
+
+        offset += PARAGRAPH_SIZE;
+    }
+    Ok(())
+}
+
 /// Read a u32 length-prefixed UTF-16 string from `bytes` at `offset`.
 fn read_string_utf16(offset: &mut usize, bytes: &[u8], endian: scroll::Endian) -> Option<String> {
     let u: u32 = bytes.gread_with(offset, endian).ok()?;
@@ -809,6 +1511,63 @@ fn ensure_count_in_bound(
     Ok((number_of_entries, expected_size))
 }
 
+impl VersionInfo {
+    /// Interpret `raw`, if it has the expected signature and version.
+    pub fn from_raw(raw: &md::VS_FIXEDFILEINFO) -> Option<VersionInfo> {
+        if raw.signature != md::VS_FFI_SIGNATURE || raw.struct_version != md::VS_FFI_STRUCVERSION {
+            return None;
+        }
+        let version = |hi: u32, lo: u32| {
+            [
+                (hi >> 16) as u16,
+                (hi & 0xffff) as u16,
+                (lo >> 16) as u16,
+                (lo & 0xffff) as u16,
+            ]
+        };
+        Some(VersionInfo {
+            file_version: version(raw.file_version_hi, raw.file_version_lo),
+            product_version: version(raw.product_version_hi, raw.product_version_lo),
+            file_flags: md::VersionFileFlags::from_bits_truncate(
+                raw.file_flags & raw.file_flags_mask,
+            ),
+            file_os: raw.file_os,
+            file_type: raw.file_type,
+            file_subtype: raw.file_subtype,
+        })
+    }
+
+    /// The operating system the file was designed for, if it's a known one.
+    pub fn os(&self) -> Option<md::VersionFileOs> {
+        md::VersionFileOs::from_u32(self.file_os)
+    }
+
+    /// The general type of the file, if it's a known one.
+    pub fn file_type(&self) -> Option<md::VersionFileType> {
+        md::VersionFileType::from_u32(self.file_type)
+    }
+
+    /// The file version as a dotted string like "5.1.2600.2180", or `None` if it's all zeroes.
+    pub fn file_version_string(&self) -> Option<String> {
+        format_version(&self.file_version)
+    }
+
+    /// The product version as a dotted string like "5.1.2600.2180", or `None` if it's all zeroes.
+    pub fn product_version_string(&self) -> Option<String> {
+        format_version(&self.product_version)
+    }
+}
+
+fn format_version(version: &[u16; 4]) -> Option<String> {
+    if version.iter().all(|&part| part == 0) {
+        return None;
+    }
+    Some(format!(
+        "{}.{}.{}.{}",
+        version[0], version[1], version[2], version[3]
+    ))
+}
+
 impl MinidumpModule {
     /// Create a `MinidumpModule` with some basic info.
     ///
@@ -861,6 +1620,23 @@ impl MinidumpModule {
         })
     }
 
+    /// The module's version information, if it has any.
+    pub fn version_info(&self) -> Option<VersionInfo> {
+        VersionInfo::from_raw(&self.raw.version_info)
+    }
+
+    /// The module's file version, like "5.1.2600.2180", if it has one.
+    ///
+    /// This is the same as [`Module::version`].
+    pub fn file_version(&self) -> Option<String> {
+        self.version_info()?.file_version_string()
+    }
+
+    /// The version of the product the module is distributed with, if it has one.
+    pub fn product_version(&self) -> Option<String> {
+        self.version_info()?.product_version_string()
+    }
+
     /// Write a human-readable description of this `MinidumpModule` to `f`.
     ///
     /// This is very verbose, it is the format used by `minidump_dump`.
@@ -1007,17 +1783,63 @@ impl MinidumpModule {
         Ok(())
     }
 
-    fn memory_range(&self) -> Option<Range<u64>> {
-        if self.size() == 0 {
-            return None;
+    /// Which convention [`Module::code_identifier`] follows for this module,
+    /// or `None` if it has no code identifier.
+    ///
+    /// This is mostly decided by the format of the module's CodeView record,
+    /// and only falls back to the minidump's OS when the record is ambiguous,
+    /// so modules that don't match the OS (like the ELF libraries in a Wine
+    /// process's Windows minidump) still get a useful code identifier.
+    pub fn code_identifier_kind(&self) -> Option<CodeIdentifierKind> {
+        match self.codeview_info {
+            // Mach-O modules use PDB70 records with the UUID as the signature.
+            Some(CodeView::Pdb70(_)) if matches!(self.os, Os::MacOs | Os::Ios) => {
+                Some(CodeIdentifierKind::MachOUuid)
+            }
+            // Without an OS to go by, a PDB70 record that doesn't point to a
+            // .pdb file is most likely from a Mach-O module.
+            Some(CodeView::Pdb70(ref raw))
+                if matches!(self.os, Os::Unknown(_)) && !has_pdb_extension(&raw.pdb_file_name) =>
+            {
+                Some(CodeIdentifierKind::MachOUuid)
+            }
+            Some(CodeView::Pdb20(_)) | Some(CodeView::Pdb70(_)) => {
+                Some(CodeIdentifierKind::PeTimestampAndSize)
+            }
+            // Return None instead of sentinel CodeIds for empty
+            // `build_id`s. Non-executable mapped files like fonts or .jar
+            // files will usually fall under this case.
+            Some(CodeView::Elf(ref raw)) if raw.build_id.iter().all(|byte| *byte == 0) => None,
+            Some(CodeView::Elf(_)) => Some(CodeIdentifierKind::ElfBuildId),
+            // Fall back to the timestamp + size-based debug-id for Windows.
+            // Some Module records from Windows have no codeview record, but
+            // the CodeId generated here is valid and can be looked up on
+            // the Microsoft symbol server.
+            // One example might be `wow64cpu.dll` with code-id `378BC3CDa000`.
+            // This can however lead to "false positive" code-ids for modules
+            // that have no timestamp, in which case the code-id looks extremely
+            // low-entropy. The same can happen though if they *do* have a
+            // codeview record.
+            None if self.os == Os::Windows => Some(CodeIdentifierKind::PeTimestampAndSize),
+            // Occasionally things will make it into the module stream that
+            // shouldn't be there, and so no meaningful CodeId can be found from
+            // those. One of those things are SysV shared memory segments which
+            // have no CodeView record.
+            _ => None,
         }
-        Some(Range::new(
-            self.base_address(),
-            self.base_address().checked_add(self.size())? - 1,
-        ))
     }
 }
 
+/// Whether a nul-terminated file name ends with ".pdb" (in any case).
+fn has_pdb_extension(file_name: &[u8]) -> bool {
+    let len = file_name
+        .iter()
+        .position(|&c| c == 0)
+        .unwrap_or(file_name.len());
+    let name = &file_name[..len];
+    name.len() >= 4 && name[len - 4..].eq_ignore_ascii_case(b".pdb")
+}
+
 impl Module for MinidumpModule {
     fn base_address(&self) -> u64 {
         self.raw.base_of_image
@@ -1030,47 +1852,20 @@ impl Module for MinidumpModule {
     }
 
     fn code_identifier(&self) -> Option<CodeId> {
-        match self.codeview_info {
-            Some(CodeView::Pdb70(ref raw)) if matches!(self.os, Os::MacOs | Os::Ios) => {
+        match (self.code_identifier_kind()?, &self.codeview_info) {
+            (CodeIdentifierKind::MachOUuid, Some(CodeView::Pdb70(raw))) => {
                 // MacOs uses PDB70 instead of its own dedicated format.
                 // See the following issue for a potential MacOs-specific format:
                 // https://github.com/rust-minidump/rust-minidump/issues/455
                 Some(CodeId::new(format!("{:#}", raw.signature)))
             }
-            Some(CodeView::Pdb20(_)) | Some(CodeView::Pdb70(_)) => Some(CodeId::new(format!(
+            (CodeIdentifierKind::ElfBuildId, Some(CodeView::Elf(raw))) => {
+                Some(CodeId::from_binary(&raw.build_id))
+            }
+            _ => Some(CodeId::new(format!(
                 "{0:08X}{1:x}",
                 self.raw.time_date_stamp, self.raw.size_of_image
             ))),
-            Some(CodeView::Elf(ref raw)) => {
-                // Return None instead of sentinel CodeIds for empty
-                // `build_id`s. Non-executable mapped files like fonts or .jar
-                // files will usually fall under this case.
-                if raw.build_id.iter().all(|byte| *byte == 0) {
-                    None
-                } else {
-                    Some(CodeId::from_binary(&raw.build_id))
-                }
-            }
-            None if self.os == Os::Windows => {
-                // Fall back to the timestamp + size-based debug-id for Windows.
-                // Some Module records from Windows have no codeview record, but
-                // the CodeId generated here is valid and can be looked up on
-                // the Microsoft symbol server.
-                // One example might be `wow64cpu.dll` with code-id `378BC3CDa000`.
-                // This can however lead to "false positive" code-ids for modules
-                // that have no timestamp, in which case the code-id looks extremely
-                // low-entropy. The same can happen though if they *do* have a
-                // codeview record.
-                Some(CodeId::new(format!(
-                    "{0:08X}{1:x}",
-                    self.raw.time_date_stamp, self.raw.size_of_image
-                )))
-            }
-            // Occasionally things will make it into the module stream that
-            // shouldn't be there, and so no meaningful CodeId can be found from
-            // those. One of those things are SysV shared memory segments which
-            // have no CodeView record.
-            _ => None,
         }
     }
     fn debug_file(&self) -> Option<Cow<'_, str>> {
@@ -1086,20 +1881,7 @@ impl Module for MinidumpModule {
         self.debug_id
     }
     fn version(&self) -> Option<Cow<'_, str>> {
-        if self.raw.version_info.signature == md::VS_FFI_SIGNATURE
-            && self.raw.version_info.struct_version == md::VS_FFI_STRUCVERSION
-        {
-            let ver = format!(
-                "{}.{}.{}.{}",
-                self.raw.version_info.file_version_hi >> 16,
-                self.raw.version_info.file_version_hi & 0xffff,
-                self.raw.version_info.file_version_lo >> 16,
-                self.raw.version_info.file_version_lo & 0xffff
-            );
-            Some(Cow::Owned(ver))
-        } else {
-            None
-        }
+        self.file_version().map(Cow::Owned)
     }
 }
 
@@ -1118,6 +1900,19 @@ impl MinidumpUnloadedModule {
         }
     }
 
+    /// The checksum of the module's image, as recorded when it was unloaded.
+    pub fn checksum(&self) -> u32 {
+        self.raw.checksum
+    }
+
+    /// The timestamp of the module's image, as recorded when it was unloaded.
+    ///
+    /// On Windows this is the linker timestamp from the PE header, which
+    /// distinguishes different builds of a module with the same name.
+    pub fn time_date_stamp(&self) -> u32 {
+        self.raw.time_date_stamp
+    }
+
     /// Read additional data to construct a `MinidumpUnloadedModule` from `bytes` using the information
     /// from the module list in `raw`.
     pub fn read(
@@ -1224,7 +2019,7 @@ fn linux_list_iter(
     })
 }
 
-fn read_stream_list<'a, T>(
+pub(crate) fn read_stream_list<'a, T>(
     offset: &mut usize,
     bytes: &'a [u8],
     endian: scroll::Endian,
@@ -1268,6 +2063,102 @@ where
     Ok(raw_entries)
 }
 
+/// Read the entries of a `u32`-counted list stream that actually fit in it,
+/// recording an issue if the count claims more.
+fn check_stream_list<'a, T>(
+    issues: &mut Vec<CorruptionIssue>,
+    stream_type: u32,
+    bytes: &'a [u8],
+    endian: scroll::Endian,
+) -> Vec<T>
+where
+    T: TryFromCtx<'a, scroll::Endian, [u8], Error = scroll::Error>,
+    T: SizeWith<scroll::Endian>,
+{
+    let count: u32 = match bytes.pread_with(0, endian) {
+        Ok(count) => count,
+        Err(_) => return Vec::new(),
+    };
+    let entry_size = <T>::size_with(&endian);
+    // Lists may have 4 bytes of padding after the count.
+    let mut offset = mem::size_of::<u32>();
+    if bytes.len() as u64 == 8 + count as u64 * entry_size as u64 {
+        offset += 4;
+    }
+    let available = (bytes.len() - offset) / entry_size;
+    if count as usize > available {
+        issues.push(CorruptionIssue::ImpossibleCount {
+            stream_type,
+            count: count as u64,
+            available: available as u64,
+        });
+    }
+    (0..(count as usize).min(available))
+        .filter_map(|_| bytes.gread_with(&mut offset, endian).ok())
+        .collect()
+}
+
+fn check_memory_location(
+    issues: &mut Vec<CorruptionIssue>,
+    stream_type: u32,
+    desc: &md::MINIDUMP_MEMORY_DESCRIPTOR,
+    file_len: u64,
+) {
+    let location = &desc.memory;
+    if location.rva as u64 + location.data_size as u64 > file_len {
+        issues.push(CorruptionIssue::MemoryOutOfBounds {
+            stream_type,
+            base_address: desc.start_of_memory_range,
+            rva: location.rva as u64,
+            size: location.data_size as u64,
+        });
+    }
+}
+
+fn check_memory64_list(
+    issues: &mut Vec<CorruptionIssue>,
+    bytes: &[u8],
+    endian: scroll::Endian,
+    file_len: u64,
+) {
+    let stream_type = MINIDUMP_STREAM_TYPE::Memory64ListStream as u32;
+    let mut offset = 0;
+    let (count, mut rva): (u64, u64) = match (
+        bytes.gread_with(&mut offset, endian),
+        bytes.gread_with(&mut offset, endian),
+    ) {
+        (Ok(count), Ok(rva)) => (count, rva),
+        _ => return,
+    };
+    let entry_size = <md::MINIDUMP_MEMORY_DESCRIPTOR64>::size_with(&endian);
+    let available = ((bytes.len() - offset) / entry_size) as u64;
+    if count > available {
+        issues.push(CorruptionIssue::ImpossibleCount {
+            stream_type,
+            count,
+            available,
+        });
+    }
+    // The contents of the regions are stored back to back, so once one is
+    // out of bounds, so is every one after it.
+    for _ in 0..count.min(available) {
+        let desc: md::MINIDUMP_MEMORY_DESCRIPTOR64 = match bytes.gread_with(&mut offset, endian) {
+            Ok(desc) => desc,
+            Err(_) => return,
+        };
+        if rva.saturating_add(desc.data_size) > file_len {
+            issues.push(CorruptionIssue::MemoryOutOfBounds {
+                stream_type,
+                base_address: desc.start_of_memory_range,
+                rva,
+                size: desc.data_size,
+            });
+            return;
+        }
+        rva += desc.data_size;
+    }
+}
+
 fn read_ex_stream_list<'a, T>(
     offset: &mut usize,
     bytes: &'a [u8],
@@ -1301,14 +2192,14 @@ where
 
     let expected_size_of_entry = <T>::size_with(&endian);
 
-    if size_of_entry as usize != expected_size_of_entry {
-        // For now, conservatively bail out if entries don't have
-        // the expected size. In theory we can assume entries are
-        // always extended with new trailing fields, and this information
-        // would let us walk over trailing fields we don't know about?
-        // But without an example let's be safe.
+    if (size_of_entry as usize) < expected_size_of_entry {
+        // Entries are only ever extended with new trailing fields (Microsoft
+        // has done this to MINIDUMP_THREAD_INFO), so larger entries are fine
+        // and we just walk over the fields we don't know about. Smaller ones
+        // are missing fields we need, so bail out.
         return Err(Error::StreamReadFailure);
     }
+    let trailing_bytes = size_of_entry as usize - expected_size_of_entry;
 
     let (number_of_entries, _) = ensure_count_in_bound(
         bytes,
@@ -1330,6 +2221,7 @@ where
             .gread_with(offset, endian)
             .or(Err(Error::StreamReadFailure))?;
         raw_entries.push(raw);
+        *offset += trailing_bytes;
     }
     Ok(raw_entries)
 }
@@ -1365,6 +2257,9 @@ impl<'a> MinidumpStream<'a> for MinidumpThreadNames {
 }
 
 impl MinidumpThreadNames {
+    /// Get the name of the thread with id `thread_id`, if it has one.
+    ///
+    /// See also [`MinidumpThreadList::thread_name`].
     pub fn get_name(&self, thread_id: u32) -> Option<Cow<str>> {
         self.names
             .get(&thread_id)
@@ -1397,2916 +2292,6398 @@ MINIDUMP_THREAD_NAME
     }
 }
 
-impl MinidumpModuleList {
-    /// Return an empty `MinidumpModuleList`.
-    pub fn new() -> MinidumpModuleList {
-        MinidumpModuleList {
-            modules: vec![],
-            modules_by_addr: RangeMap::new(),
-        }
+/// Read the `MINIDUMP_STRING` at `rva`, where an `rva` of 0 means there is no string.
+fn read_optional_string_utf16(
+    rva: md::RVA,
+    all: &[u8],
+    endian: scroll::Endian,
+    handle: u64,
+) -> Option<String> {
+    if rva == 0 {
+        return None;
     }
-    /// Create a `MinidumpModuleList` from a list of `MinidumpModule`s.
-    pub fn from_modules(modules: Vec<MinidumpModule>) -> MinidumpModuleList {
-        let modules_by_addr = modules
-            .iter()
-            .enumerate()
-            .map(|(i, module)| (module.memory_range(), i))
-            .into_rangemap_safe();
-        MinidumpModuleList {
-            modules,
-            modules_by_addr,
-        }
+    let mut offset = rva as usize;
+    let string = read_string_utf16(&mut offset, all, endian);
+    if string.is_none() {
+        warn!(
+            "Couldn't read string at {:#x} for handle {:#x}",
+            rva, handle
+        );
     }
+    string
+}
 
-    /// Returns the module corresponding to the main executable.
-    pub fn main_module(&self) -> Option<&MinidumpModule> {
-        // The main code module is the first one present in a minidump file's
-        // MINIDUMP_MODULEList.
-        if !self.modules.is_empty() {
-            Some(&self.modules[0])
-        } else {
-            None
+impl<'a> MinidumpStream<'a> for MinidumpHandleDataStream {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::HandleDataStream as u32;
+
+    fn read(
+        bytes: &'a [u8],
+        all: &'a [u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<Self, Error> {
+        let header: md::MINIDUMP_HANDLE_DATA_STREAM = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let size_of_descriptor = header.size_of_descriptor as usize;
+        let (count, _) = ensure_count_in_bound(
+            bytes,
+            header.number_of_descriptors as usize,
+            size_of_descriptor,
+            header.size_of_header as usize,
+        )?;
+
+        // The descriptor's size tells us which version it is. Entries may have
+        // trailing fields we don't know about, so always step by the declared size.
+        let is_v2 = size_of_descriptor >= md::MINIDUMP_HANDLE_DESCRIPTOR_2::size_with(&endian);
+        if !is_v2 && size_of_descriptor < md::MINIDUMP_HANDLE_DESCRIPTOR::size_with(&endian) {
+            return Err(Error::StreamReadFailure);
+        }
+
+        let mut handles = Vec::with_capacity(count);
+        for i in 0..count {
+            let offset = header.size_of_header as usize + i * size_of_descriptor;
+            let raw = if is_v2 {
+                RawHandleDescriptor::HandleDescriptor2(
+                    bytes
+                        .pread_with(offset, endian)
+                        .or(Err(Error::StreamReadFailure))?,
+                )
+            } else {
+                RawHandleDescriptor::HandleDescriptor(
+                    bytes
+                        .pread_with(offset, endian)
+                        .or(Err(Error::StreamReadFailure))?,
+                )
+            };
+            let (type_name_rva, object_name_rva) = match &raw {
+                RawHandleDescriptor::HandleDescriptor(raw) => {
+                    (raw.type_name_rva, raw.object_name_rva)
+                }
+                RawHandleDescriptor::HandleDescriptor2(raw) => {
+                    (raw.type_name_rva, raw.object_name_rva)
+                }
+            };
+            let mut handle = MinidumpHandleDescriptor {
+                raw,
+                type_name: None,
+                object_name: None,
+            };
+            // Better to just lose an unreadable name than the whole handle.
+            handle.type_name =
+                read_optional_string_utf16(type_name_rva, all, endian, handle.handle());
+            handle.object_name =
+                read_optional_string_utf16(object_name_rva, all, endian, handle.handle());
+            handles.push(handle);
         }
+        Ok(MinidumpHandleDataStream { handles })
     }
+}
 
-    /// Return a `MinidumpModule` whose address range covers `address`.
-    pub fn module_at_address(&self, address: u64) -> Option<&MinidumpModule> {
-        self.modules_by_addr
-            .get(address)
-            .map(|&index| &self.modules[index])
+macro_rules! handle_descriptor_field {
+    ($(#[$attr:meta])* $field:ident: $ty:ty) => {
+        $(#[$attr])*
+        pub fn $field(&self) -> $ty {
+            match &self.raw {
+                RawHandleDescriptor::HandleDescriptor(raw) => raw.$field,
+                RawHandleDescriptor::HandleDescriptor2(raw) => raw.$field,
+            }
+        }
+    };
+}
+
+impl MinidumpHandleDescriptor {
+    handle_descriptor_field!(
+        /// The operating system handle value.
+        handle: u64
+    );
+    handle_descriptor_field!(
+        /// The attributes of the handle.
+        attributes: u32
+    );
+    handle_descriptor_field!(
+        /// The access rights granted when the handle was opened.
+        granted_access: u32
+    );
+    handle_descriptor_field!(
+        /// The number of handles to the object.
+        handle_count: u32
+    );
+    handle_descriptor_field!(
+        /// The number of kernel references to the object.
+        pointer_count: u32
+    );
+
+    /// The RVA of the object's `MINIDUMP_HANDLE_OBJECT_INFORMATION`, if present.
+    ///
+    /// Only version 2 descriptors have this.
+    pub fn object_info_rva(&self) -> Option<md::RVA> {
+        match &self.raw {
+            RawHandleDescriptor::HandleDescriptor(_) => None,
+            RawHandleDescriptor::HandleDescriptor2(raw) => {
+                Some(raw.object_info_rva).filter(|&rva| rva != 0)
+            }
+        }
     }
 
-    /// Iterate over the modules in arbitrary order.
-    pub fn iter(&self) -> impl Iterator<Item = &MinidumpModule> {
-        self.modules.iter()
+    /// Write a human-readable description of this `MinidumpHandleDescriptor` to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        let name = match self.raw {
+            RawHandleDescriptor::HandleDescriptor(_) => "MINIDUMP_HANDLE_DESCRIPTOR",
+            RawHandleDescriptor::HandleDescriptor2(_) => "MINIDUMP_HANDLE_DESCRIPTOR_2",
+        };
+        write!(
+            f,
+            "{}
+  handle          = {:#x}
+  type_name       = {}
+  object_name     = {}
+  attributes      = {:#x}
+  granted_access  = {:#x}
+  handle_count    = {}
+  pointer_count   = {}
+",
+            name,
+            self.handle(),
+            self.type_name.as_deref().unwrap_or("(null)"),
+            self.object_name.as_deref().unwrap_or("(null)"),
+            self.attributes(),
+            self.granted_access(),
+            self.handle_count(),
+            self.pointer_count(),
+        )?;
+        if let RawHandleDescriptor::HandleDescriptor2(raw) = &self.raw {
+            writeln!(f, "  object_info_rva = {:#x}", raw.object_info_rva)?;
+        }
+        writeln!(f)
     }
+}
 
-    /// Iterate over the modules in order by memory address.
-    pub fn by_addr(&self) -> impl DoubleEndedIterator<Item = &MinidumpModule> {
-        self.modules_by_addr
-            .ranges_values()
-            .map(move |&(_, index)| &self.modules[index])
+impl MinidumpHandleDataStream {
+    /// Iterate over the handles in the order contained in the minidump.
+    pub fn iter(&self) -> impl Iterator<Item = &MinidumpHandleDescriptor> {
+        self.handles.iter()
     }
 
-    /// Write a human-readable description of this `MinidumpModuleList` to `f`.
-    ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
+    /// Write a human-readable description of this `MinidumpHandleDataStream` to `f`.
     pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
         write!(
             f,
-            "MinidumpModuleList
-  module_count = {}
+            "MinidumpHandleDataStream
+  handle_count = {}
 
 ",
-            self.modules.len()
+            self.handles.len()
         )?;
-        for (i, module) in self.modules.iter().enumerate() {
-            writeln!(f, "module[{}]", i)?;
-            module.print(f)?;
+        for (i, handle) in self.handles.iter().enumerate() {
+            writeln!(f, "handle[{}]", i)?;
+            handle.print(f)?;
         }
         Ok(())
     }
 }
 
-impl Default for MinidumpModuleList {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl<'a> MinidumpStream<'a> for MinidumpModuleList {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::ModuleListStream as u32;
+impl<'a> MinidumpStream<'a> for MinidumpHandleOperationList {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::HandleOperationListStream as u32;
 
     fn read(
         bytes: &'a [u8],
-        all: &'a [u8],
+        _all: &'a [u8],
         endian: scroll::Endian,
-        system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpModuleList, Error> {
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<Self, Error> {
         let mut offset = 0;
-        let raw_modules: Vec<md::MINIDUMP_MODULE> = read_stream_list(&mut offset, bytes, endian)?;
-        // read auxiliary data for each module
-        let mut modules = Vec::with_capacity(raw_modules.len());
-        for raw in raw_modules.into_iter() {
-            if raw.size_of_image == 0 || raw.size_of_image as u64 > (u64::MAX - raw.base_of_image) {
-                // Bad image size.
-                // TODO: just drop this module, keep the rest?
-                return Err(Error::ModuleReadFailure);
-            }
-            modules.push(MinidumpModule::read(raw, all, endian, system_info)?);
-        }
-        Ok(MinidumpModuleList::from_modules(modules))
+        let raw_operations: Vec<md::AVRF_HANDLE_OPERATION> =
+            read_ex_stream_list(&mut offset, bytes, endian)?;
+        let operations = raw_operations
+            .into_iter()
+            .map(|raw| MinidumpHandleOperation {
+                operation_type: md::HandleTraceOperation::from_u32(raw.operation_type),
+                raw,
+            })
+            .collect();
+        Ok(MinidumpHandleOperationList { operations })
     }
 }
 
-impl MinidumpUnloadedModuleList {
-    /// Return an empty `MinidumpModuleList`.
-    pub fn new() -> MinidumpUnloadedModuleList {
-        MinidumpUnloadedModuleList {
-            modules: vec![],
-            modules_by_addr: vec![],
-        }
+impl MinidumpHandleOperation {
+    /// The return addresses of the thread's stack when it performed the
+    /// operation, innermost first.
+    ///
+    /// These are raw addresses, for a caller to symbolicate.
+    pub fn backtrace(&self) -> &[u64] {
+        let trace = &self.raw.back_trace_information;
+        let depth = (trace.depth as usize).min(trace.return_addresses.len());
+        &trace.return_addresses[..depth]
     }
-    /// Create a `MinidumpModuleList` from a list of `MinidumpModule`s.
-    pub fn from_modules(modules: Vec<MinidumpUnloadedModule>) -> MinidumpUnloadedModuleList {
-        let mut modules_by_addr = (0..modules.len())
-            .filter_map(|i| modules[i].memory_range().map(|r| (r, i)))
-            .collect::<Vec<_>>();
-
-        modules_by_addr.sort_by_key(|(range, _idx)| *range);
 
-        MinidumpUnloadedModuleList {
-            modules,
-            modules_by_addr,
+    /// A short name for the operation's type, like "OPEN".
+    pub fn operation_name(&self) -> &'static str {
+        match self.operation_type {
+            Some(md::HandleTraceOperation::OperationDbUnused) => "UNUSED",
+            Some(md::HandleTraceOperation::OperationDbOPEN) => "OPEN",
+            Some(md::HandleTraceOperation::OperationDbCLOSE) => "CLOSE",
+            Some(md::HandleTraceOperation::OperationDbBADREF) => "BADREF",
+            None => "UNKNOWN",
         }
     }
+}
 
-    /// Return an iterator of `MinidumpUnloadedModules` whose address range covers `address`.
-    pub fn modules_at_address(
-        &self,
-        address: u64,
-    ) -> impl Iterator<Item = &MinidumpUnloadedModule> {
-        // We have all of our modules sorted by memory range (base address being the
-        // high-order value), and we need to get the range of values that overlap
-        // with our target address. I'm a bit too tired to work out the exact
-        // combination of binary searches to do this, so let's just use `filter`
-        // for now (unloaded_modules should be a bounded list anyway).
-        self.modules_by_addr
-            .iter()
-            .filter(move |(range, _idx)| range.contains(address))
-            .map(move |(_range, idx)| &self.modules[*idx])
-    }
-
-    /// Iterate over the modules in arbitrary order.
-    pub fn iter(&self) -> impl Iterator<Item = &MinidumpUnloadedModule> {
-        self.modules.iter()
-    }
-
-    /// Iterate over the modules in order by memory address.
-    pub fn by_addr(&self) -> impl Iterator<Item = &MinidumpUnloadedModule> {
-        self.modules_by_addr
-            .iter()
-            .map(move |&(_, index)| &self.modules[index])
+impl MinidumpHandleOperationList {
+    /// Iterate over the operations in the order contained in the minidump.
+    pub fn iter(&self) -> impl Iterator<Item = &MinidumpHandleOperation> {
+        self.operations.iter()
     }
 
-    /// Write a human-readable description of this `MinidumpModuleList` to `f`.
-    ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
+    /// Write a human-readable description of this `MinidumpHandleOperationList` to `f`.
     pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
         write!(
             f,
-            "MinidumpUnloadedModuleList
-  module_count = {}
+            "MinidumpHandleOperationList
+  operation_count = {}
 
 ",
-            self.modules.len()
+            self.operations.len()
         )?;
-        for (i, module) in self.modules.iter().enumerate() {
-            writeln!(f, "module[{}]", i)?;
-            module.print(f)?;
+        writeln!(
+            f,
+            "  {:>5}  {:>18}  {:>10}  {:>10}  {:<7}  backtrace",
+            "index", "handle", "process_id", "thread_id", "type"
+        )?;
+        for (i, operation) in self.operations.iter().enumerate() {
+            write!(
+                f,
+                "  {:>5}  {:>#18x}  {:>#10x}  {:>#10x}  {:<7} ",
+                i,
+                operation.raw.handle,
+                operation.raw.process_id,
+                operation.raw.thread_id,
+                operation.operation_name(),
+            )?;
+            for address in operation.backtrace() {
+                write!(f, " {:#x}", address)?;
+            }
+            writeln!(f)?;
         }
-        Ok(())
-    }
-}
-
-impl Default for MinidumpUnloadedModuleList {
-    fn default() -> Self {
-        Self::new()
+        writeln!(f)
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpUnloadedModuleList {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::UnloadedModuleListStream as u32;
+impl<'a> MinidumpStream<'a> for MinidumpFunctionTable<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::FunctionTable as u32;
 
     fn read(
         bytes: &'a [u8],
-        all: &'a [u8],
+        _all: &'a [u8],
         endian: scroll::Endian,
         _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpUnloadedModuleList, Error> {
-        let mut offset = 0;
-        let raw_modules: Vec<md::MINIDUMP_UNLOADED_MODULE> =
-            read_ex_stream_list(&mut offset, bytes, endian)?;
-        // read auxiliary data for each module
-        let mut modules = Vec::with_capacity(raw_modules.len());
-        for raw in raw_modules.into_iter() {
-            if raw.size_of_image == 0
-                || raw.size_of_image as u64 > (u64::max_value() - raw.base_of_image)
-            {
-                // Bad image size.
-                // TODO: just drop this module, keep the rest?
-                return Err(Error::ModuleReadFailure);
-            }
-            modules.push(MinidumpUnloadedModule::read(raw, all, endian)?);
+    ) -> Result<MinidumpFunctionTable<'a>, Error> {
+        let header: md::MINIDUMP_FUNCTION_TABLE_STREAM = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        if (header.size_of_descriptor as usize)
+            < md::MINIDUMP_FUNCTION_TABLE_DESCRIPTOR::size_with(&endian)
+        {
+            return Err(Error::StreamReadFailure);
         }
-        Ok(MinidumpUnloadedModuleList::from_modules(modules))
+
+        let mut offset = (header.size_of_header as usize)
+            .checked_add(header.size_of_align_pad as usize)
+            .ok_or(Error::StreamReadFailure)?;
+        let entry_size = header.size_of_function_entry as usize;
+        let mut tables = Vec::new();
+        for _ in 0..header.number_of_descriptors {
+            let raw: md::MINIDUMP_FUNCTION_TABLE_DESCRIPTOR = bytes
+                .pread_with(offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+            offset += header.size_of_descriptor as usize;
+
+            let mut take = |len: usize| -> Result<&'a [u8], Error> {
+                let end = offset.checked_add(len).ok_or(Error::StreamReadFailure)?;
+                let slice = bytes.get(offset..end).ok_or(Error::StreamReadFailure)?;
+                offset = end;
+                Ok(slice)
+            };
+            let native_descriptor = take(header.size_of_native_descriptor as usize)?;
+            let entries_len = (raw.entry_count as usize)
+                .checked_mul(entry_size)
+                .ok_or(Error::StreamReadFailure)?;
+            let raw_entries = take(entries_len)?;
+            // Padding may be missing after the last table, so don't insist on it.
+            offset = offset.saturating_add(raw.size_of_align_pad as usize);
+
+            tables.push(MinidumpFunctionTableDescriptor {
+                raw,
+                native_descriptor,
+                raw_entries,
+                entry_size,
+                endian,
+            });
+        }
+        Ok(MinidumpFunctionTable::from_tables(tables))
     }
 }
 
-impl<'a> MinidumpMemory<'a> {
-    pub fn read(
-        desc: &md::MINIDUMP_MEMORY_DESCRIPTOR,
-        data: &'a [u8],
-        endian: scroll::Endian,
-    ) -> Result<MinidumpMemory<'a>, Error> {
-        if desc.memory.rva == 0 || desc.memory.data_size == 0 {
-            // Windows will sometimes emit null stack RVAs, indicating that
-            // we need to lookup the address in a memory region. It's ok to
-            // emit an error for that here, the thread processing code will
-            // catch it.
-            return Err(Error::MemoryReadFailure);
+impl<'a> MinidumpFunctionTableDescriptor<'a> {
+    /// The range of addresses covered by this table, if it isn't empty.
+    pub fn memory_range(&self) -> Option<Range<u64>> {
+        if self.raw.maximum_address <= self.raw.minimum_address {
+            return None;
         }
-        let bytes = location_slice(data, &desc.memory).or(Err(Error::StreamReadFailure))?;
-        Ok(MinidumpMemory {
-            desc: *desc,
-            base_address: desc.start_of_memory_range,
-            size: desc.memory.data_size as u64,
-            bytes,
-            endian,
-        })
+        Some(Range::new(
+            self.raw.minimum_address,
+            self.raw.maximum_address - 1,
+        ))
     }
 
-    /// Write a human-readable description of this `MinidumpMemory` to `f`.
+    /// The number of function entries in the table.
+    pub fn entry_count(&self) -> usize {
+        self.raw.entry_count as usize
+    }
+
+    /// Iterate over the raw bytes of each function entry.
     ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
-        write!(
-            f,
-            "MINIDUMP_MEMORY_DESCRIPTOR
-  start_of_memory_range = {:#x}
-  memory.data_size      = {:#x}
-  memory.rva            = {:#x}
-",
-            self.desc.start_of_memory_range, self.desc.memory.data_size, self.desc.memory.rva,
-        )?;
-        if !brief {
-            writeln!(f, "Memory")?;
-            self.print_contents(f)?;
-        }
-        writeln!(f)
+    /// The layout of an entry depends on the CPU; see [`Self::runtime_functions`]
+    /// for x86-64.
+    pub fn raw_entries(&self) -> impl Iterator<Item = &'a [u8]> {
+        // With an entry size of 0 there are no entry bytes, but `chunks` would panic.
+        self.raw_entries.chunks(self.entry_size.max(1))
     }
-}
 
-impl<'a> MinidumpMemory64<'a> {
-    /// Write a human-readable description of this `MinidumpMemory64` to `f`.
+    /// Iterate over the function entries as x86-64 `RUNTIME_FUNCTION`s.
     ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
+    /// Their addresses are relative to `raw.base_address`. If the entries are too small
+    /// to be `RUNTIME_FUNCTION`s, this is empty.
+    pub fn runtime_functions(&self) -> impl Iterator<Item = md::IMAGE_RUNTIME_FUNCTION_ENTRY> + '_ {
+        self.raw_entries()
+            .filter_map(move |entry| entry.pread_with(0, self.endian).ok())
+    }
+
+    /// Write a human-readable description of this `MinidumpFunctionTableDescriptor` to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
         write!(
             f,
-            "MINIDUMP_MEMORY_DESCRIPTOR64
-  start_of_memory_range = {:#x}
-  memory.data_size      = {:#x}
+            "MINIDUMP_FUNCTION_TABLE_DESCRIPTOR
+  minimum_address   = {:#x}
+  maximum_address   = {:#x}
+  base_address      = {:#x}
+  entry_count       = {}
+  size_of_align_pad = {}
 ",
-            self.desc.start_of_memory_range, self.desc.data_size,
+            self.raw.minimum_address,
+            self.raw.maximum_address,
+            self.raw.base_address,
+            self.raw.entry_count,
+            self.raw.size_of_align_pad,
         )?;
-        if !brief {
-            writeln!(f, "Memory")?;
-            self.print_contents(f)?;
+        for (i, function) in self.runtime_functions().enumerate() {
+            writeln!(
+                f,
+                "  entry[{}] = begin {:#x} end {:#x} unwind_info {:#x}",
+                i, function.begin_address, function.end_address, function.unwind_info_address,
+            )?;
         }
         writeln!(f)
     }
 }
 
-impl<'a, Descriptor> MinidumpMemoryBase<'a, Descriptor> {
-    /// Get `mem::size_of::<T>()` bytes of memory at `addr` from this region.
-    ///
-    /// Return `None` if the requested address range falls out of the bounds
-    /// of this memory region.
-    pub fn get_memory_at_address<T>(&self, addr: u64) -> Option<T>
-    where
-        T: TryFromCtx<'a, scroll::Endian, [u8], Error = scroll::Error>,
-    {
-        // XXX: Instead of checking the base+size validity on each access, maybe
-        // move this check to a different place?
-        let _end = self.base_address.checked_add(self.size)?;
-        let start = addr.checked_sub(self.base_address)? as usize;
-
-        self.bytes.pread_with::<T>(start, self.endian).ok()
-    }
-
-    /// Write the contents of this `MinidumpMemory` to `f` as a hex string.
-    pub fn print_contents<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        const PARAGRAPH_SIZE: usize = 16;
-        let mut offset = 0;
-        for paragraph in self.bytes.chunks(PARAGRAPH_SIZE) {
-            write!(f, "    {:08x}: ", offset)?;
-            let mut byte_iter = paragraph.iter().fuse();
-            for _ in 0..PARAGRAPH_SIZE {
-                if let Some(byte) = byte_iter.next() {
-                    write!(f, "{:02x} ", byte)?;
-                } else {
-                    write!(f, "   ")?;
-                }
-            }
-            for &byte in paragraph.iter() {
-                let ascii_char = if !byte.is_ascii() || byte.is_ascii_control() {
-                    '.'
-                } else {
-                    char::from(byte)
-                };
-
-                write!(f, "{}", ascii_char)?;
-            }
-            writeln!(f)?;
-
-            offset += PARAGRAPH_SIZE;
-        }
-        Ok(())
-    }
-
-    pub fn memory_range(&self) -> Option<Range<u64>> {
-        if self.size == 0 {
-            return None;
-        }
-        Some(Range::new(
-            self.base_address,
-            self.base_address.checked_add(self.size)? - 1,
-        ))
+impl<'a> Default for MinidumpFunctionTable<'a> {
+    fn default() -> Self {
+        Self::from_tables(vec![])
     }
 }
 
-impl<'mdmp, Descriptor> MinidumpMemoryListBase<'mdmp, Descriptor> {
-    /// Return an empty `MinidumpMemoryListBase`.
-    pub fn new() -> MinidumpMemoryListBase<'mdmp, Descriptor> {
-        MinidumpMemoryListBase {
-            regions: vec![],
-            regions_by_addr: RangeMap::new(),
-        }
-    }
-
-    /// Create a `MinidumpMemoryListBase` from a list of `MinidumpMemoryBase`s.
-    pub fn from_regions(
-        regions: Vec<MinidumpMemoryBase<'mdmp, Descriptor>>,
-    ) -> MinidumpMemoryListBase<'mdmp, Descriptor> {
-        let regions_by_addr = regions
+impl<'a> MinidumpFunctionTable<'a> {
+    /// Create a `MinidumpFunctionTable` from a list of `MinidumpFunctionTableDescriptor`s.
+    pub fn from_tables(
+        tables: Vec<MinidumpFunctionTableDescriptor<'a>>,
+    ) -> MinidumpFunctionTable<'a> {
+        let tables_by_addr = tables
             .iter()
             .enumerate()
-            .map(|(i, region)| (region.memory_range(), i))
+            .map(|(i, table)| (table.memory_range(), i))
             .into_rangemap_safe();
-        MinidumpMemoryListBase {
-            regions,
-            regions_by_addr,
+        MinidumpFunctionTable {
+            tables,
+            tables_by_addr,
         }
     }
 
-    /// Return a `MinidumpMemoryBase` containing memory at `address`, if one exists.
-    pub fn memory_at_address(
-        &self,
-        address: u64,
-    ) -> Option<&MinidumpMemoryBase<'mdmp, Descriptor>> {
-        self.regions_by_addr
+    /// Return the function table covering `address`, if one exists.
+    pub fn table_for_address(&self, address: u64) -> Option<&MinidumpFunctionTableDescriptor<'a>> {
+        self.tables_by_addr
             .get(address)
-            .map(|&index| &self.regions[index])
+            .map(|&index| &self.tables[index])
     }
 
-    /// Iterate over the memory regions in the order contained in the minidump.
-    ///
-    /// The iterator returns items of [MinidumpMemoryBase] as `&'slf MinidumpMemoryBase<'mdmp, Descriptor>`.
-    /// That is the lifetime of the item is bound to the lifetime of the iterator itself
-    /// (`'slf`), while the slice inside [MinidumpMemoryBase] pointing at the memory itself has
-    /// the lifetime of the [Minidump] struct ('mdmp).
-    pub fn iter<'slf>(
-        &'slf self,
-    ) -> impl Iterator<Item = &'slf MinidumpMemoryBase<'mdmp, Descriptor>> {
-        self.regions.iter()
+    /// Iterate over the tables in the order contained in the minidump.
+    pub fn iter(&self) -> impl Iterator<Item = &MinidumpFunctionTableDescriptor<'a>> {
+        self.tables.iter()
     }
 
-    /// Iterate over the memory regions in order by memory address.
-    pub fn by_addr<'slf>(
-        &'slf self,
-    ) -> impl Iterator<Item = &'slf MinidumpMemoryBase<'mdmp, Descriptor>> {
-        self.regions_by_addr
+    /// Iterate over the tables in order by memory address.
+    pub fn by_addr(&self) -> impl Iterator<Item = &MinidumpFunctionTableDescriptor<'a>> {
+        self.tables_by_addr
             .ranges_values()
-            .map(move |&(_, index)| &self.regions[index])
+            .map(move |&(_, index)| &self.tables[index])
     }
-}
 
-impl<'mdmp> MinidumpMemoryList<'mdmp> {
-    /// Write a human-readable description of this `MinidumpMemoryList` to `f`.
-    ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
+    /// Write a human-readable description of this `MinidumpFunctionTable` to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
         write!(
             f,
-            "MinidumpMemoryList
-  region_count = {}
+            "MinidumpFunctionTable
+  table_count = {}
 
 ",
-            self.regions.len()
+            self.tables.len()
         )?;
-        for (i, region) in self.regions.iter().enumerate() {
-            writeln!(f, "region[{}]", i)?;
-            region.print(f, brief)?;
+        for (i, table) in self.tables.iter().enumerate() {
+            writeln!(f, "table[{}]", i)?;
+            table.print(f)?;
         }
         Ok(())
     }
 }
 
-impl<'mdmp> MinidumpMemory64List<'mdmp> {
-    /// Write a human-readable description of this `MinidumpMemory64List` to `f`.
-    ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
-        write!(
-            f,
-            "MinidumpMemory64List
-  region_count = {}
+impl<'a> MinidumpStream<'a> for MinidumpProcessVmCounters {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::ProcessVmCountersStream as u32;
 
-",
-            self.regions.len()
-        )?;
-        for (i, region) in self.regions.iter().enumerate() {
-            writeln!(f, "region[{}]", i)?;
-            region.print(f, brief)?;
-        }
-        Ok(())
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<Self, Error> {
+        let revision: u16 = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        // Later revisions should only add fields, so read them as the latest one we know.
+        let raw = if revision >= 2 {
+            RawProcessVmCounters::V2(
+                bytes
+                    .pread_with(0, endian)
+                    .or(Err(Error::StreamReadFailure))?,
+            )
+        } else {
+            RawProcessVmCounters::V1(
+                bytes
+                    .pread_with(0, endian)
+                    .or(Err(Error::StreamReadFailure))?,
+            )
+        };
+        Ok(MinidumpProcessVmCounters { raw })
     }
 }
 
-impl<'a, Descriptor> Default for MinidumpMemoryListBase<'a, Descriptor> {
-    fn default() -> Self {
-        Self::new()
+/// Format a number of bytes with a binary unit, like "1.5 MiB".
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["bytes", "KiB", "MiB", "GiB", "TiB", "PiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpMemoryList<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MemoryListStream as u32;
-
-    fn read(
-        bytes: &'a [u8],
-        all: &'a [u8],
-        endian: scroll::Endian,
-        _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpMemoryList<'a>, Error> {
-        let mut offset = 0;
-        let descriptors: Vec<md::MINIDUMP_MEMORY_DESCRIPTOR> =
-            read_stream_list(&mut offset, bytes, endian)?;
-        // read memory contents for each region
-        let mut regions = Vec::with_capacity(descriptors.len());
-        for raw in descriptors.into_iter() {
-            if let Ok(memory) = MinidumpMemory::read(&raw, all, endian) {
-                regions.push(memory);
-            } else {
-                // Just skip over corrupt entries and try to limp along.
-                continue;
+macro_rules! vm_counters_field {
+    ($(#[$attr:meta])* v2_only $field:ident, $flag:ident) => {
+        $(#[$attr])*
+        pub fn $field(&self) -> Option<u64> {
+            match &self.raw {
+                RawProcessVmCounters::V1(_) => None,
+                RawProcessVmCounters::V2(raw) => md::ProcessVmCountersFlags::from_bits_truncate(raw.flags)
+                    .contains(md::ProcessVmCountersFlags::$flag)
+                    .then_some(raw.$field),
             }
         }
-        Ok(MinidumpMemoryList::from_regions(regions))
-    }
+    };
+    ($(#[$attr:meta])* $field:ident, $flag:ident) => {
+        $(#[$attr])*
+        pub fn $field(&self) -> Option<u64> {
+            match &self.raw {
+                RawProcessVmCounters::V1(raw) => Some(raw.$field as u64),
+                RawProcessVmCounters::V2(raw) => md::ProcessVmCountersFlags::from_bits_truncate(raw.flags)
+                    .contains(md::ProcessVmCountersFlags::$flag)
+                    .then_some(raw.$field as u64),
+            }
+        }
+    };
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpMemory64List<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::Memory64ListStream as u32;
-
-    fn read(
-        bytes: &'a [u8],
-        all: &'a [u8],
-        endian: scroll::Endian,
-        _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpMemory64List<'a>, Error> {
-        let mut offset = 0;
-        let u: u64 = bytes
-            .gread_with(&mut offset, endian)
-            .or(Err(Error::StreamReadFailure))?;
-
-        let mut rva: u64 = bytes
-            .gread_with(&mut offset, endian)
-            .or(Err(Error::StreamReadFailure))?;
-
-        let (count, counted_size) = ensure_count_in_bound(
-            bytes,
-            u.try_into().map_err(|_| Error::StreamReadFailure)?,
-            md::MINIDUMP_MEMORY_DESCRIPTOR64::size_with(&endian),
-            offset,
-        )?;
+impl MinidumpProcessVmCounters {
+    vm_counters_field!(
+        /// The number of page faults.
+        page_fault_count, MINIDUMP_PROCESS_VM_COUNTERS
+    );
+    vm_counters_field!(
+        /// The peak working set size, in bytes.
+        peak_working_set_size, MINIDUMP_PROCESS_VM_COUNTERS
+    );
+    vm_counters_field!(
+        /// The current working set size, in bytes.
+        working_set_size, MINIDUMP_PROCESS_VM_COUNTERS
+    );
+    vm_counters_field!(
+        /// The peak paged pool usage, in bytes.
+        quota_peak_paged_pool_usage, MINIDUMP_PROCESS_VM_COUNTERS
+    );
+    vm_counters_field!(
+        /// The current paged pool usage, in bytes.
+        quota_paged_pool_usage, MINIDUMP_PROCESS_VM_COUNTERS
+    );
+    vm_counters_field!(
+        /// The peak nonpaged pool usage, in bytes.
+        quota_peak_non_paged_pool_usage, MINIDUMP_PROCESS_VM_COUNTERS
+    );
+    vm_counters_field!(
+        /// The current nonpaged pool usage, in bytes.
+        quota_non_paged_pool_usage, MINIDUMP_PROCESS_VM_COUNTERS
+    );
+    vm_counters_field!(
+        /// The commit charge (memory the system has committed for the process), in bytes.
+        pagefile_usage, MINIDUMP_PROCESS_VM_COUNTERS
+    );
+    vm_counters_field!(
+        /// The peak commit charge, in bytes.
+        peak_pagefile_usage, MINIDUMP_PROCESS_VM_COUNTERS
+    );
+    vm_counters_field!(
+        /// The private memory of the process, in bytes.
+        private_usage, MINIDUMP_PROCESS_VM_COUNTERS_EX
+    );
+    vm_counters_field!(
+        /// The peak virtual address space size, in bytes.
+        v2_only peak_virtual_size, MINIDUMP_PROCESS_VM_COUNTERS_VIRTUALSIZE
+    );
+    vm_counters_field!(
+        /// The current virtual address space size, in bytes.
+        v2_only virtual_size, MINIDUMP_PROCESS_VM_COUNTERS_VIRTUALSIZE
+    );
+    vm_counters_field!(
+        /// The private working set size, in bytes.
+        v2_only private_working_set_size, MINIDUMP_PROCESS_VM_COUNTERS_EX2
+    );
+    vm_counters_field!(
+        /// The shared commit usage, in bytes.
+        v2_only shared_commit_usage, MINIDUMP_PROCESS_VM_COUNTERS_EX2
+    );
+    vm_counters_field!(
+        /// The shared commit usage of the process's job, in bytes.
+        v2_only job_shared_commit_usage, MINIDUMP_PROCESS_VM_COUNTERS_JOB
+    );
+    vm_counters_field!(
+        /// The private commit usage of the process's job, in bytes.
+        v2_only job_private_commit_usage, MINIDUMP_PROCESS_VM_COUNTERS_JOB
+    );
+    vm_counters_field!(
+        /// The peak private commit usage of the process's job, in bytes.
+        v2_only job_peak_private_commit_usage, MINIDUMP_PROCESS_VM_COUNTERS_JOB
+    );
+    vm_counters_field!(
+        /// The private commit limit of the process's job, in bytes.
+        v2_only job_private_commit_limit, MINIDUMP_PROCESS_VM_COUNTERS_JOB
+    );
+    vm_counters_field!(
+        /// The total commit limit of the process's job, in bytes.
+        v2_only job_total_commit_limit, MINIDUMP_PROCESS_VM_COUNTERS_JOB
+    );
 
-        if bytes.len() != counted_size {
-            return Err(Error::StreamSizeMismatch {
-                expected: counted_size,
-                actual: bytes.len(),
-            });
+    /// Write a human-readable description of this `MinidumpProcessVmCounters` to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        let (name, revision) = match &self.raw {
+            RawProcessVmCounters::V1(raw) => ("MINIDUMP_PROCESS_VM_COUNTERS_1", raw.revision),
+            RawProcessVmCounters::V2(raw) => ("MINIDUMP_PROCESS_VM_COUNTERS_2", raw.revision),
+        };
+        writeln!(f, "{}", name)?;
+        writeln!(f, "  {:29} = {}", "revision", revision)?;
+        if let RawProcessVmCounters::V2(raw) = &self.raw {
+            writeln!(f, "  {:29} = {:#x}", "flags", raw.flags)?;
+        }
+        write!(f, "  {:29} = ", "page_fault_count")?;
+        match self.page_fault_count() {
+            Some(count) => writeln!(f, "{}", count)?,
+            None => writeln!(f, "(invalid)")?,
         }
 
-        let mut raw_entries = Vec::with_capacity(count);
-        for _ in 0..count {
-            let raw: md::MINIDUMP_MEMORY_DESCRIPTOR64 = bytes
-                .gread_with(&mut offset, endian)
-                .or(Err(Error::StreamReadFailure))?;
-            raw_entries.push(raw);
+        macro_rules! write_size {
+            ($field:ident) => {
+                write!(f, "  {:29} = ", stringify!($field))?;
+                match self.$field() {
+                    Some(size) => writeln!(f, "{:#x} ({})", size, format_size(size))?,
+                    None => writeln!(f, "(invalid)")?,
+                }
+            };
         }
-
-        let mut regions = Vec::with_capacity(raw_entries.len());
-        for raw in raw_entries {
-            let start = rva;
-            let end = rva
-                .checked_add(raw.data_size)
-                .ok_or(Error::StreamReadFailure)?;
-            let bytes = all
-                .get(start as usize..end as usize)
-                .ok_or(Error::StreamReadFailure)?;
-
-            regions.push(MinidumpMemory64 {
-                desc: raw,
-                base_address: raw.start_of_memory_range,
-                size: raw.data_size,
-                bytes,
-                endian,
-            });
-
-            rva = end;
+        write_size!(peak_working_set_size);
+        write_size!(working_set_size);
+        write_size!(quota_peak_paged_pool_usage);
+        write_size!(quota_paged_pool_usage);
+        write_size!(quota_peak_non_paged_pool_usage);
+        write_size!(quota_non_paged_pool_usage);
+        write_size!(pagefile_usage);
+        write_size!(peak_pagefile_usage);
+        write_size!(private_usage);
+        if let RawProcessVmCounters::V2(_) = &self.raw {
+            write_size!(peak_virtual_size);
+            write_size!(virtual_size);
+            write_size!(private_working_set_size);
+            write_size!(shared_commit_usage);
+            write_size!(job_shared_commit_usage);
+            write_size!(job_private_commit_usage);
+            write_size!(job_peak_private_commit_usage);
+            write_size!(job_private_commit_limit);
+            write_size!(job_total_commit_limit);
         }
-        Ok(MinidumpMemory64List::from_regions(regions))
+        writeln!(f)
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpMemoryInfoList<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MemoryInfoListStream as u32;
+impl<'a> MinidumpStream<'a> for MinidumpTokenInfoList<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::TokenStream as u32;
 
     fn read(
         bytes: &'a [u8],
         _all: &'a [u8],
         endian: scroll::Endian,
         _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpMemoryInfoList<'a>, Error> {
-        let mut offset = 0;
-        let raw_regions: Vec<md::MINIDUMP_MEMORY_INFO> =
-            read_ex_stream_list(&mut offset, bytes, endian)?;
-        let regions = raw_regions
-            .into_iter()
-            .map(|raw| MinidumpMemoryInfo {
-                allocation_protection: md::MemoryProtection::from_bits_truncate(
-                    raw.allocation_protection,
-                ),
-                state: md::MemoryState::from_bits_truncate(raw.state),
-                protection: md::MemoryProtection::from_bits_truncate(raw.protection),
-                ty: md::MemoryType::from_bits_truncate(raw._type),
-                raw,
-                _phantom: PhantomData,
-            })
-            .collect();
-        Ok(MinidumpMemoryInfoList::from_regions(regions))
-    }
-}
+    ) -> Result<MinidumpTokenInfoList<'a>, Error> {
+        let header: md::MINIDUMP_TOKEN_INFO_LIST = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let element_header_size = header.element_header_size as usize;
+        if element_header_size < md::MINIDUMP_TOKEN_INFO_HEADER::size_with(&endian) {
+            return Err(Error::StreamReadFailure);
+        }
 
-impl<'a> Default for MinidumpMemoryInfoList<'a> {
-    fn default() -> Self {
-        Self::new()
+        // Each token is its header followed by its data, and knows its own size.
+        let mut offset = header.list_header_size as usize;
+        let mut tokens = Vec::new();
+        for _ in 0..header.token_list_entries {
+            let raw: md::MINIDUMP_TOKEN_INFO_HEADER = bytes
+                .pread_with(offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+            let token_size = raw.token_size as usize;
+            if token_size < element_header_size {
+                return Err(Error::StreamReadFailure);
+            }
+            let end = offset
+                .checked_add(token_size)
+                .ok_or(Error::StreamReadFailure)?;
+            let data = bytes
+                .get(offset + element_header_size..end)
+                .ok_or(Error::StreamReadFailure)?;
+            tokens.push(MinidumpTokenInfo { raw, data });
+            offset = end;
+        }
+        Ok(MinidumpTokenInfoList { tokens })
     }
 }
 
-impl<'mdmp> MinidumpMemoryInfoList<'mdmp> {
-    /// Return an empty `MinidumpMemoryList`.
-    pub fn new() -> MinidumpMemoryInfoList<'mdmp> {
-        MinidumpMemoryInfoList {
-            regions: vec![],
-            regions_by_addr: RangeMap::new(),
+impl<'a> MinidumpTokenInfo<'a> {
+    /// Format a binary Windows security identifier (SID) in the standard `S-1-...` form.
+    ///
+    /// Returns `None` if `sid` isn't a valid SID. Bytes after the SID are ignored.
+    pub fn format_sid(sid: &[u8]) -> Option<String> {
+        let revision = *sid.first()?;
+        let sub_authority_count = *sid.get(1)? as usize;
+        // SID_MAX_SUB_AUTHORITIES is 15.
+        if revision != 1 || sub_authority_count > 15 {
+            return None;
         }
-    }
-
-    /// Create a `MinidumpMemoryList` from a list of `MinidumpMemory`s.
-    pub fn from_regions(regions: Vec<MinidumpMemoryInfo<'mdmp>>) -> MinidumpMemoryInfoList<'mdmp> {
-        let regions_by_addr = regions
+        // The identifier authority is a 48-bit big-endian value.
+        let authority = sid
+            .get(2..8)?
             .iter()
-            .enumerate()
-            .map(|(i, region)| (region.memory_range(), i))
-            .into_rangemap_safe();
-        MinidumpMemoryInfoList {
-            regions,
-            regions_by_addr,
+            .fold(0u64, |acc, &byte| (acc << 8) | byte as u64);
+        let mut string = format!("S-{}-{}", revision, authority);
+        for i in 0..sub_authority_count {
+            let sub_authority: u32 = sid.pread_with(8 + i * 4, LE).ok()?;
+            string.push_str(&format!("-{}", sub_authority));
         }
+        Some(string)
     }
+}
 
-    /// Return a `MinidumpMemory` containing memory at `address`, if one exists.
-    pub fn memory_info_at_address(&self, address: u64) -> Option<&MinidumpMemoryInfo<'mdmp>> {
-        self.regions_by_addr
-            .get(address)
-            .map(|&index| &self.regions[index])
-    }
-
-    /// Iterate over the memory regions in the order contained in the minidump.
-    ///
-    /// The iterator returns items of [MinidumpMemory] as `&'slf MinidumpMemory<'mdmp>`.
-    /// That is the lifetime of the item is bound to the lifetime of the iterator itself
-    /// (`'slf`), while the slice inside [MinidumpMemory] pointing at the memory itself has
-    /// the lifetime of the [Minidump] struct ('mdmp).
-    pub fn iter<'slf>(&'slf self) -> impl Iterator<Item = &'slf MinidumpMemoryInfo<'mdmp>> {
-        self.regions.iter()
-    }
-
-    /// Iterate over the memory regions in order by memory address.
-    pub fn by_addr<'slf>(&'slf self) -> impl Iterator<Item = &'slf MinidumpMemoryInfo<'mdmp>> {
-        self.regions_by_addr
-            .ranges_values()
-            .map(move |&(_, index)| &self.regions[index])
+impl<'a> MinidumpTokenInfoList<'a> {
+    /// Iterate over the tokens in the order contained in the minidump.
+    pub fn iter(&self) -> impl Iterator<Item = &MinidumpTokenInfo<'a>> {
+        self.tokens.iter()
     }
 
-    /// Write a human-readable description.
+    /// Write a human-readable description of this `MinidumpTokenInfoList` to `f`.
     pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
         write!(
             f,
-            "MinidumpMemoryInfoList
-  region_count = {}
+            "MinidumpTokenInfoList
+  token_count = {}
 
 ",
-            self.regions.len()
+            self.tokens.len()
         )?;
-        for (i, region) in self.regions.iter().enumerate() {
-            writeln!(f, "region[{}]", i)?;
-            region.print(f)?;
+        for (i, token) in self.tokens.iter().enumerate() {
+            write!(
+                f,
+                "token[{}]
+MINIDUMP_TOKEN_INFO_HEADER
+  token_size   = {}
+  token_id     = {:#x}
+  token_handle = {:#x}
+  data_size    = {}
+
+",
+                i,
+                token.raw.token_size,
+                token.raw.token_id,
+                token.raw.token_handle,
+                token.data.len(),
+            )?;
         }
         Ok(())
     }
 }
 
-impl<'a> MinidumpMemoryInfo<'a> {
-    /// Write a human-readable description.
-    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        write!(
-            f,
-            "MINIDUMP_MEMORY_INFO
-  base_address          = {:#x}
-  allocation_base       = {:#x}
-  allocation_protection = {:#x}
-  region_size           = {:#x}
-  state                 = {:#x}
-  protection            = {:#x}
-  _type                 = {:#x}
-",
-            self.raw.base_address,
-            self.raw.allocation_base,
-            self.allocation_protection,
-            self.raw.region_size,
-            self.state,
-            self.protection,
-            self.ty,
-        )?;
-        writeln!(f)
-    }
+impl<'a> MinidumpStream<'a> for MinidumpSystemMemoryInfo {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::SystemMemoryInfoStream as u32;
 
-    pub fn memory_range(&self) -> Option<Range<u64>> {
-        if self.raw.region_size == 0 {
-            return None;
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<Self, Error> {
+        let mut offset = 0;
+        let revision: u16 = bytes
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let flags: u16 = bytes
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        if revision != 1 {
+            // Later revisions should only add fields, so read the ones we know.
+            warn!("Unknown SystemMemoryInfo revision {}", revision);
         }
-        Some(Range::new(
-            self.raw.base_address,
-            self.raw.base_address.checked_add(self.raw.region_size)? - 1,
-        ))
-    }
 
-    /// Whether this memory range was executable.
-    pub fn is_executable(&self) -> bool {
-        self.protection.intersects(
-            md::MemoryProtection::PAGE_EXECUTE
-                | md::MemoryProtection::PAGE_EXECUTE_READ
-                | md::MemoryProtection::PAGE_EXECUTE_READWRITE
-                | md::MemoryProtection::PAGE_EXECUTE_WRITECOPY,
-        )
+        // Read as much as we can, losing only the groups of fields that are cut off.
+        let basic_info = bytes.gread_with(&mut offset, endian).ok();
+        let file_cache_info = basic_info
+            .as_ref()
+            .and_then(|_| bytes.gread_with(&mut offset, endian).ok());
+        let basic_perf_info = file_cache_info
+            .as_ref()
+            .and_then(|_| bytes.gread_with(&mut offset, endian).ok());
+        let perf_info = basic_perf_info
+            .as_ref()
+            .and_then(|_| bytes.gread_with(&mut offset, endian).ok());
+        Ok(MinidumpSystemMemoryInfo {
+            revision,
+            flags,
+            basic_info,
+            file_cache_info,
+            basic_perf_info,
+            perf_info,
+        })
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpLinuxMaps<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxMaps as u32;
+impl<'a> MinidumpStream<'a> for MinidumpIptTrace<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::IptTraceStream as u32;
 
     fn read(
         bytes: &'a [u8],
         _all: &'a [u8],
-        _endian: scroll::Endian,
+        endian: scroll::Endian,
         _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpLinuxMaps<'a>, Error> {
-        let regions = LinuxOsStr::from_bytes(bytes)
-            .lines()
-            .map(MinidumpLinuxMapInfo::from_line)
-            .filter_map(|x| x.ok())
-            .collect::<Vec<_>>();
-
-        Ok(MinidumpLinuxMaps::from_regions(regions))
-    }
-}
+    ) -> Result<Self, Error> {
+        let mut offset = 0;
+        let raw: md::IPT_TRACE_DATA = bytes
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let expected = offset + raw.trace_size as usize;
+        let mut data = bytes
+            .get(offset..expected)
+            .ok_or(Error::StreamSizeMismatch {
+                expected,
+                actual: bytes.len(),
+            })?;
 
-impl<'a> Default for MinidumpLinuxMaps<'a> {
-    fn default() -> Self {
-        Self::new()
+        let mut threads = Vec::new();
+        while !data.is_empty() {
+            let mut offset = 0;
+            let header: md::IPT_TRACE_HEADER = data
+                .gread_with(&mut offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+            let end = offset + header.trace_size as usize;
+            let trace = data.get(offset..end).ok_or(Error::StreamReadFailure)?;
+            threads.push(MinidumpIptThreadTrace { raw: header, trace });
+            data = &data[end..];
+        }
+        Ok(MinidumpIptTrace { raw, threads })
     }
 }
 
-impl<'mdmp> MinidumpLinuxMaps<'mdmp> {
-    /// Return an empty `MinidumpMemoryList`.
-    pub fn new() -> Self {
-        Self {
-            regions: vec![],
-            regions_by_addr: RangeMap::new(),
-        }
+impl<'a> MinidumpIptTrace<'a> {
+    /// Iterate over the threads' traces, in the order they were stored in the minidump.
+    pub fn iter(&self) -> impl Iterator<Item = &MinidumpIptThreadTrace<'a>> {
+        self.threads.iter()
     }
 
-    /// Create a `MinidumpMemoryList` from a list of `MinidumpMemory`s.
-    pub fn from_regions(regions: Vec<MinidumpLinuxMapInfo<'mdmp>>) -> Self {
-        let regions_by_addr = regions
+    /// The trace of the thread with id `thread_id`, if there is one.
+    pub fn thread_trace(&self, thread_id: u32) -> Option<&MinidumpIptThreadTrace<'a>> {
+        self.threads
             .iter()
-            .enumerate()
-            .map(|(i, region)| (region.memory_range(), i))
-            .into_rangemap_safe();
-        Self {
-            regions,
-            regions_by_addr,
-        }
+            .find(|thread| thread.raw.thread_id == thread_id as u64)
     }
 
-    /// Return a `MinidumpMemory` containing memory at `address`, if one exists.
-    pub fn memory_info_at_address(&self, address: u64) -> Option<&MinidumpLinuxMapInfo<'mdmp>> {
-        self.regions_by_addr
-            .get(address)
-            .map(|&index| &self.regions[index])
-    }
+    /// Write a human-readable description of this `MinidumpIptTrace` to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "IPT_TRACE_DATA
+  trace_version = {}
+  valid_trace   = {}
+  trace_size    = {:#x}
+  threads       = {}
 
-    /// Iterate over the memory regions in the order contained in the minidump.
-    pub fn iter<'slf>(&'slf self) -> impl Iterator<Item = &'slf MinidumpLinuxMapInfo<'mdmp>> {
-        self.regions.iter()
-    }
+",
+            self.raw.trace_version,
+            self.raw.valid_trace,
+            self.raw.trace_size,
+            self.threads.len(),
+        )?;
+        for (i, thread) in self.threads.iter().enumerate() {
+            write!(
+                f,
+                "thread[{}]
+IPT_TRACE_HEADER
+  thread_id              = {:#x}
+  timing_settings        = {}
+  mtc_frequency          = {}
+  frequency_to_tsc_ratio = {}
+  ring_buffer_offset     = {:#x}
+  trace_size             = {:#x}
 
-    /// Iterate over the memory regions in order by memory address.
-    pub fn by_addr<'slf>(&'slf self) -> impl Iterator<Item = &'slf MinidumpLinuxMapInfo<'mdmp>> {
-        self.regions_by_addr
-            .ranges_values()
-            .map(move |&(_, index)| &self.regions[index])
+",
+                i,
+                thread.raw.thread_id,
+                thread.raw.timing_settings,
+                thread.raw.mtc_frequency,
+                thread.raw.frequency_to_tsc_ratio,
+                thread.raw.ring_buffer_offset,
+                thread.raw.trace_size,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl MinidumpSystemMemoryInfo {
+    /// The size of a page, in bytes, if known.
+    pub fn page_size(&self) -> Option<u64> {
+        self.basic_info.as_ref().map(|info| info.page_size as u64)
     }
 
-    /// Write a human-readable description.
+    /// The amount of free physical memory, in bytes, if known.
+    pub fn available_physical_memory(&self) -> Option<u64> {
+        let pages = self.basic_perf_info.as_ref()?.available_pages;
+        pages.checked_mul(self.page_size()?)
+    }
+
+    /// The most memory the system can commit without growing the page file, in bytes,
+    /// if known.
+    pub fn commit_limit(&self) -> Option<u64> {
+        let pages = self.basic_perf_info.as_ref()?.commit_limit;
+        pages.checked_mul(self.page_size()?)
+    }
+
+    /// The amount of memory the system had committed, in bytes, if known.
+    pub fn committed_memory(&self) -> Option<u64> {
+        let pages = self.basic_perf_info.as_ref()?.committed_pages;
+        pages.checked_mul(self.page_size()?)
+    }
+
+    /// Write a human-readable description of this `MinidumpSystemMemoryInfo` to `f`.
     pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        macro_rules! write_fields {
+            ($name:literal, $info:expr, [$($field:ident),* $(,)?]) => {
+                writeln!(f, $name)?;
+                match $info {
+                    Some(info) => {
+                        $(writeln!(f, "  {:43} = {:#x}", stringify!($field), info.$field)?;)*
+                    }
+                    None => writeln!(f, "  (missing)")?,
+                }
+            };
+        }
         write!(
             f,
-            "MinidumpLinuxMapInfo
-  region_count = {}
-
+            "MINIDUMP_SYSTEM_MEMORY_INFO
+  revision = {}
+  flags    = {:#x}
 ",
-            self.regions.len()
+            self.revision, self.flags,
         )?;
-        for (i, region) in self.regions.iter().enumerate() {
-            writeln!(f, "region[{}]", i)?;
-            region.print(f)?;
+        if let Some(available) = self.available_physical_memory() {
+            writeln!(
+                f,
+                "  available_physical_memory = {}",
+                format_size(available)
+            )?;
         }
-        Ok(())
+        if let (Some(committed), Some(limit)) = (self.committed_memory(), self.commit_limit()) {
+            writeln!(
+                f,
+                "  committed_memory          = {} of {}",
+                format_size(committed),
+                format_size(limit)
+            )?;
+        }
+        write_fields!(
+            "MINIDUMP_SYSTEM_BASIC_INFORMATION",
+            &self.basic_info,
+            [
+                timer_resolution,
+                page_size,
+                number_of_physical_pages,
+                lowest_physical_page_number,
+                highest_physical_page_number,
+                allocation_granularity,
+                minimum_user_mode_address,
+                maximum_user_mode_address,
+                active_processors_affinity_mask,
+                number_of_processors,
+            ]
+        );
+        write_fields!(
+            "MINIDUMP_SYSTEM_FILECACHE_INFORMATION",
+            &self.file_cache_info,
+            [
+                current_size,
+                peak_size,
+                page_fault_count,
+                minimum_working_set,
+                maximum_working_set,
+                current_size_including_transition_in_pages,
+                peak_size_including_transition_in_pages,
+                transition_re_purpose_count,
+                flags,
+            ]
+        );
+        write_fields!(
+            "MINIDUMP_SYSTEM_BASIC_PERFORMANCE_INFORMATION",
+            &self.basic_perf_info,
+            [
+                available_pages,
+                committed_pages,
+                commit_limit,
+                peak_commitment,
+            ]
+        );
+        write_fields!(
+            "MINIDUMP_SYSTEM_PERFORMANCE_INFORMATION",
+            &self.perf_info,
+            [
+                idle_process_time,
+                io_read_transfer_count,
+                io_write_transfer_count,
+                io_other_transfer_count,
+                io_read_operation_count,
+                io_write_operation_count,
+                io_other_operation_count,
+                available_pages,
+                committed_pages,
+                commit_limit,
+                peak_commitment,
+                page_fault_count,
+                copy_on_write_count,
+                transition_count,
+                cache_transition_count,
+                demand_zero_count,
+                page_read_count,
+                page_read_io_count,
+                cache_read_count,
+                cache_io_count,
+                dirty_pages_write_count,
+                dirty_write_io_count,
+                mapped_pages_write_count,
+                mapped_write_io_count,
+                paged_pool_pages,
+                non_paged_pool_pages,
+                paged_pool_allocs,
+                paged_pool_frees,
+                non_paged_pool_allocs,
+                non_paged_pool_frees,
+                free_system_ptes,
+                resident_system_code_page,
+                total_system_driver_pages,
+                total_system_code_pages,
+                non_paged_pool_lookaside_hits,
+                paged_pool_lookaside_hits,
+                available_paged_pool_pages,
+                resident_system_cache_page,
+                resident_paged_pool_page,
+                resident_system_driver_page,
+                cc_fast_read_no_wait,
+                cc_fast_read_wait,
+                cc_fast_read_resource_miss,
+                cc_fast_read_not_possible,
+                cc_fast_mdl_read_no_wait,
+                cc_fast_mdl_read_wait,
+                cc_fast_mdl_read_resource_miss,
+                cc_fast_mdl_read_not_possible,
+                cc_map_data_no_wait,
+                cc_map_data_wait,
+                cc_map_data_no_wait_miss,
+                cc_map_data_wait_miss,
+                cc_pin_mapped_data_count,
+                cc_pin_read_no_wait,
+                cc_pin_read_wait,
+                cc_pin_read_no_wait_miss,
+                cc_pin_read_wait_miss,
+                cc_copy_read_no_wait,
+                cc_copy_read_wait,
+                cc_copy_read_no_wait_miss,
+                cc_copy_read_wait_miss,
+                cc_mdl_read_no_wait,
+                cc_mdl_read_wait,
+                cc_mdl_read_no_wait_miss,
+                cc_mdl_read_wait_miss,
+                cc_read_ahead_ios,
+                cc_lazy_write_ios,
+                cc_lazy_write_pages,
+                cc_data_flushes,
+                cc_data_pages,
+                context_switches,
+                first_level_tb_fills,
+                second_level_tb_fills,
+                system_calls,
+                cc_total_dirty_pages,
+                cc_dirty_page_threshold,
+                resident_available_pages,
+                shared_committed_pages,
+            ]
+        );
+        writeln!(f)
     }
 }
 
-impl<'a> MinidumpLinuxMapInfo<'a> {
-    /// Parses a line from /proc/self/maps into a `[MinidumpLinuxMapInfo]`.
-    pub fn from_line(line: &'a LinuxOsStr) -> Result<MinidumpLinuxMapInfo<'a>, Error> {
-        // /proc/self/maps is a listing of all the mapped ranges of memory
-        // in the (crashing) process. We can use it to find out what regions
-        // were executable (and what file they mapped to), where stacks/heap
-        // are mapped, and various other things.
-        //
-        // Format of each line (by examples):
-        //
-        // ```text
-        //
-        //        address        perms   offset   dev   inode       path/kind         deleted?
-        // 7fca3a80c0-7fca3a81a0  r-xp  10bac9000 fd:05 1196511 /usr/lib64/libtdb.so  (deleted)
-        // 7ffe2e7910-7ffe2e7b10  rw-p  000000000 00:00 0       [stack]
-        //
-        // ```
-        //
-        // * address: the start and end addresses (inclusive)
-        // * perms: permissions the process had on the memory
-        //   * r = read
-        //   * w = write
-        //   * x = execute
-        //   * s = shared
-        //   * p = private (copy on write)
-        //   * - = <ignore> (just for formatting)
-        // * offset: the offset this mapping has into the mapped file/device. (ignored)
-        // * dev: the "device" (major:minor). (ignored)
-        // * inode: the inode on the device, 0 means no inode (uninitialized memory?). (ignored)
-        // * path/kind: either a path to the mapped file/device or a special `[kind]`:
-        //   * `[stack]`       - the main thread's stack
-        //   * `[stack:<tid>]` - the stack of the thread with this tid (e.g. `[stack:123]`)
-        //   * `[heap]`        - the process's heap
-        //   * `[vdso]`        - the Virtual Dynamically-linked Shared Object
-        //   * `<blank>`       - an anonymous mmap
-        //
-        // A path has a few extra caveats:
-        //  * If suffixed with `(deleted)` that indicates the mapped file was deleted.
-        //  * If the path contains a newline (yikes), it will appear as \012. It is impossible
-        //    to distinguish this from the path literally containing the string `\012`.
-        //    (We aren't resolving these paths so don't worry about it.)
-
-        let mut tokens = line.split_ascii_whitespace();
-
-        let (base_address, final_address) = tokens
-            .next()
-            .and_then(|range| range.split_once(b'-'))
-            .and_then(|(start, end)| {
-                // Parsing numbers, so can require them to be utf8
-                let start = start
-                    .to_str()
-                    .ok()
-                    .and_then(|x| u64::from_str_radix(x, 16).ok());
-
-                let end = end
-                    .to_str()
-                    .ok()
-                    .and_then(|x| u64::from_str_radix(x, 16).ok());
+impl<'a> MinidumpStream<'a> for MinidumpLinuxDsoDebug<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxDsoDebug as u32;
 
-                start.zip(end)
-            })
-            .ok_or(Error::DataError)?;
+    fn read(
+        bytes: &'a [u8],
+        all: &'a [u8],
+        endian: scroll::Endian,
+        system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<Self, Error> {
+        let pointer_width =
+            system_info.map_or(PointerWidth::Unknown, |info| info.cpu.pointer_width());
 
-        let empty = LinuxOsStr::new();
-        let perms = tokens.next().unwrap_or(empty);
+        let mut offset = 0;
+        let (map, dso_count, raw) = match pointer_width {
+            PointerWidth::Bits32 => {
+                let raw: md::DSO_DEBUG_32 = bytes
+                    .gread_with(&mut offset, endian)
+                    .or(Err(Error::StreamReadFailure))?;
+                (raw.map, raw.dso_count, RawDsoDebug::DsoDebug32(raw))
+            }
+            // Like the rest of this crate, treat unknown CPUs as 64-bit.
+            PointerWidth::Bits64 | PointerWidth::Unknown => {
+                let raw: md::DSO_DEBUG_64 = bytes
+                    .gread_with(&mut offset, endian)
+                    .or(Err(Error::StreamReadFailure))?;
+                (raw.map, raw.dso_count, RawDsoDebug::DsoDebug64(raw))
+            }
+        };
+        let dynamic_section = &bytes[offset..];
 
-        let mut is_read = false;
-        let mut is_write = false;
-        let mut is_exec = false;
-        let mut is_shared = false;
-        let mut is_private = false;
+        let entry_size = match raw {
+            RawDsoDebug::DsoDebug32(_) => md::LINK_MAP_32::size_with(&endian),
+            RawDsoDebug::DsoDebug64(_) => md::LINK_MAP_64::size_with(&endian),
+        };
+        let map_bytes = all.get(map as usize..).ok_or(Error::StreamReadFailure)?;
+        let (dso_count, _) = ensure_count_in_bound(map_bytes, dso_count as usize, entry_size, 0)?;
 
-        // Although some of these are mutually exclusive and they come in a specific
-        // order, I see no reason to mandate this in this parser.
-        for c in perms.iter() {
-            match &c {
-                b'r' => is_read = true,
-                b'w' => is_write = true,
-                b'x' => is_exec = true,
-                b's' => is_shared = true,
-                b'p' => is_private = true,
-                b'-' => {}
-                _ => {
-                    // This shouldn't happen. That said, there's no obvious reason
-                    // to fail the entire parse if there's new info we don't know about,
-                    // so I suppose it's fine?
+        let mut offset = 0;
+        let mut entries = Vec::with_capacity(dso_count);
+        for _ in 0..dso_count {
+            let (addr, name, ld) = match raw {
+                RawDsoDebug::DsoDebug32(_) => {
+                    let link_map: md::LINK_MAP_32 = map_bytes
+                        .gread_with(&mut offset, endian)
+                        .or(Err(Error::StreamReadFailure))?;
+                    (link_map.addr as u64, link_map.name, link_map.ld as u64)
                 }
-            }
+                RawDsoDebug::DsoDebug64(_) => {
+                    let link_map: md::LINK_MAP_64 = map_bytes
+                        .gread_with(&mut offset, endian)
+                        .or(Err(Error::StreamReadFailure))?;
+                    (link_map.addr, link_map.name, link_map.ld)
+                }
+            };
+            let name = read_string_utf16(&mut (name as usize), all, endian).unwrap_or_else(|| {
+                warn!(
+                    "Couldn't read the name of the link_map entry at {:#x}",
+                    addr
+                );
+                String::new()
+            });
+            entries.push(MinidumpLinuxLinkMapEntry { addr, name, ld });
         }
 
-        // We don't care about these values
-        let _offset = tokens.next();
-        let _dev = tokens.next();
-        let _inode = tokens.next();
+        Ok(MinidumpLinuxDsoDebug {
+            raw,
+            dynamic_section,
+            entries,
+        })
+    }
+}
 
-        let kind = tokens.next();
-        let kind = match kind.map(|x| x.as_bytes()) {
-            Some(b"[stack]") => MinidumpLinuxMapKind::MainThreadStack,
-            Some(b"[heap]") => MinidumpLinuxMapKind::Heap,
-            Some(b"[vdso]") => MinidumpLinuxMapKind::Vdso,
-            Some(b"") | None => MinidumpLinuxMapKind::AnonymousMap,
-            Some(_) => {
-                // Go back to the LinuxOsStr
-                let kind = kind.unwrap();
-                // Try to parse an arbitrary [<special>]
-                if let Some(special) = kind
-                    .to_str()
-                    .ok()
-                    .and_then(|x| x.strip_prefix('['))
-                    .and_then(|x| x.strip_suffix(']'))
-                {
-                    // See if it's a [stack:<tid>] special entry
-                    if let Some(tid) = special.strip_prefix("stack:") {
-                        // As far as I know, this part *is* base 10!
-                        let tid = str::parse(tid).map_err(|_| Error::DataError)?;
-                        MinidumpLinuxMapKind::Stack(tid)
-                    } else {
-                        MinidumpLinuxMapKind::UnknownSpecial(Cow::Borrowed(kind))
-                    }
-                } else {
-                    // Finally just assume it's a path. Use the fact that we're handling
-                    // a subslice to retrieve the index of the full string (so we don't lose any whitespace).
+macro_rules! dso_debug_field {
+    ($(#[$attr:meta])* $field:ident) => {
+        $(#[$attr])*
+        pub fn $field(&self) -> u64 {
+            match &self.raw {
+                RawDsoDebug::DsoDebug32(raw) => raw.$field as u64,
+                RawDsoDebug::DsoDebug64(raw) => raw.$field,
+            }
+        }
+    };
+}
 
-                    let base = line.as_ptr() as usize;
-                    let pos = kind.as_ptr() as usize;
-                    let idx = pos - base;
+impl<'a> MinidumpLinuxDsoDebug<'a> {
+    /// The version of the linker's debugging protocol, from `r_debug.r_version`.
+    pub fn version(&self) -> u32 {
+        match &self.raw {
+            RawDsoDebug::DsoDebug32(raw) => raw.version,
+            RawDsoDebug::DsoDebug64(raw) => raw.version,
+        }
+    }
 
-                    let path = LinuxOsStr::from_bytes(&line[idx..]).trim_ascii_whitespace();
+    dso_debug_field!(
+        /// The address of the function debuggers set a breakpoint on to watch for
+        /// libraries being loaded.
+        brk
+    );
+    dso_debug_field!(
+        /// The address the linker itself was loaded at.
+        ldbase
+    );
+    dso_debug_field!(
+        /// The address of the main executable's dynamic section.
+        dynamic
+    );
 
-                    // Check if this path has the `(deleted)` suffix
-                    path.rsplit_once(b' ')
-                        .and_then(|(path, deleted)| {
-                            if deleted.as_bytes() == b"(deleted)" {
-                                // (extra trim in case there was extra space between them)
-                                Some(MinidumpLinuxMapKind::DeletedFile(Cow::Borrowed(
-                                    path.trim_ascii_whitespace(),
-                                )))
-                            } else {
-                                None
-                            }
-                        })
-                        .unwrap_or(MinidumpLinuxMapKind::File(Cow::Borrowed(path)))
-                }
-            }
-        };
+    /// The shared libraries, in the order of the linker's list.
+    pub fn entries(&self) -> &[MinidumpLinuxLinkMapEntry] {
+        &self.entries
+    }
 
-        Ok(MinidumpLinuxMapInfo {
-            base_address,
-            final_address,
-            kind,
-            is_read,
-            is_write,
-            is_exec,
-            is_private,
-            is_shared,
-            _phantom: PhantomData,
-        })
+    /// Iterate over the `(addr, name)` of each shared library, in the order of the
+    /// linker's list.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &str)> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.addr, entry.name.as_str()))
     }
-    /// Write a human-readable description of this.
+
+    /// Write a human-readable description of this `MinidumpLinuxDsoDebug` to `f`.
     pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
         write!(
             f,
-            "MINIDUMP_LINUX_MAP_INFO
-  base_address          = {:#x}
-  final_address         = {:#x}
-  kind                  = {:#?}
-  permissions           =\x20
+            "MinidumpLinuxDsoDebug
+  version          = {}
+  dso_count        = {}
+  brk              = {:#x}
+  ldbase           = {:#x}
+  dynamic          = {:#x}
+  dynamic_section  = {} bytes
+
 ",
-            self.base_address, self.final_address, self.kind,
+            self.version(),
+            self.entries.len(),
+            self.brk(),
+            self.ldbase(),
+            self.dynamic(),
+            self.dynamic_section.len(),
         )?;
+        for (i, entry) in self.entries.iter().enumerate() {
+            write!(
+                f,
+                "link_map[{}]
+  addr = {:#x}
+  name = \"{}\"
+  ld   = {:#x}
 
-        if self.is_read {
-            write!(f, "r")?;
-        } else {
-            write!(f, "-")?;
-        }
-        if self.is_write {
-            write!(f, "w")?;
-        } else {
-            write!(f, "-")?;
-        }
-        if self.is_exec {
-            write!(f, "r")?;
-        } else {
-            write!(f, "-")?;
+",
+                i, entry.addr, entry.name, entry.ld,
+            )?;
         }
-        if self.is_private {
-            write!(f, "p")?;
-        } else if self.is_shared {
-            write!(f, "s")?;
-        } else {
-            write!(f, "-")?;
-        }
-        writeln!(f)
+        Ok(())
     }
+}
 
-    pub fn memory_range(&self) -> Option<Range<u64>> {
-        // final address is inclusive afaik
-        if self.base_address > self.final_address {
-            return None;
-        }
-        Some(Range::new(self.base_address, self.final_address))
-    }
+impl<'a> MinidumpStream<'a> for MinidumpLinuxAuxv {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxAuxv as u32;
 
-    /// Whether this memory range was executable.
-    pub fn is_executable(&self) -> bool {
-        self.is_exec
-    }
-}
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        endian: scroll::Endian,
+        system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<Self, Error> {
+        let pointer_width =
+            system_info.map_or(PointerWidth::Unknown, |info| info.cpu.pointer_width());
 
-impl<'a> Default for UnifiedMemoryInfoList<'a> {
-    fn default() -> Self {
-        Self::Info(MinidumpMemoryInfoList::default())
+        let mut offset = 0;
+        let read_word = |offset: &mut usize| -> Option<u64> {
+            match pointer_width {
+                PointerWidth::Bits32 => bytes.gread_with::<u32>(offset, endian).ok().map(u64::from),
+                // Like the rest of this crate, treat unknown CPUs as 64-bit.
+                PointerWidth::Bits64 | PointerWidth::Unknown => {
+                    bytes.gread_with::<u64>(offset, endian).ok()
+                }
+            }
+        };
+
+        // The vector ends with an AT_NULL entry, but don't insist on it: a truncated
+        // vector is still useful.
+        let mut entries = Vec::new();
+        while let (Some(key), Some(value)) = (read_word(&mut offset), read_word(&mut offset)) {
+            if key == md::AuxvType::AT_NULL as u64 {
+                break;
+            }
+            entries.push((key, value));
+        }
+        Ok(MinidumpLinuxAuxv { entries })
     }
 }
 
-impl<'a> UnifiedMemoryInfoList<'a> {
-    /// Take two potential memory info sources and create an interface that unifies them.
+impl MinidumpLinuxAuxv {
+    /// Iterate over the `(key, value)` entries, in the order they were stored in the minidump.
     ///
-    /// Under normal circumstances a minidump should only contain one of these.
-    /// If both are provided, one will be arbitrarily preferred to attempt to
-    /// make progress.
-    pub fn new(
-        info: Option<MinidumpMemoryInfoList<'a>>,
-        maps: Option<MinidumpLinuxMaps<'a>>,
-    ) -> Option<Self> {
-        match (info, maps) {
-            (Some(info), Some(_maps)) => {
-                warn!("UnifiedMemoryInfoList got both kinds of info! (using InfoList)");
-                // Just pick one I guess?
-                Some(Self::Info(info))
-            }
-            (Some(info), None) => Some(Self::Info(info)),
-            (None, Some(maps)) => Some(Self::Maps(maps)),
-            (None, None) => None,
-        }
+    /// Keys are usually one of [`AuxvType`][md::AuxvType], but can be anything.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.entries.iter().copied()
     }
 
-    /// Return a `MinidumpMemory` containing memory at `address`, if one exists.
-    pub fn memory_info_at_address(&self, address: u64) -> Option<UnifiedMemoryInfo> {
-        match self {
-            Self::Info(info) => info
-                .memory_info_at_address(address)
-                .map(UnifiedMemoryInfo::Info),
-            Self::Maps(maps) => maps
-                .memory_info_at_address(address)
-                .map(UnifiedMemoryInfo::Map),
-        }
+    /// The value of the first entry with the key `ty`, if any.
+    pub fn get(&self, ty: md::AuxvType) -> Option<u64> {
+        self.iter()
+            .find(|&(key, _)| key == ty as u64)
+            .map(|(_, value)| value)
     }
 
-    /// Iterate over the memory regions in the order contained in the minidump.
-    pub fn iter(&self) -> impl Iterator<Item = UnifiedMemoryInfo> {
-        // Use `flat_map` and `chain` to create a unified stream of the two types
-        // (only one of which will conatin any values). Note that we are using
-        // the fact that `Option` can be iterated (producing 1 to 0 values).
-        let info = self
-            .info()
-            .into_iter()
-            .flat_map(|info| info.iter().map(UnifiedMemoryInfo::Info));
-        let maps = self
-            .maps()
-            .into_iter()
-            .flat_map(|maps| maps.iter().map(UnifiedMemoryInfo::Map));
+    /// The address of the vdso's ELF header (`AT_SYSINFO_EHDR`).
+    pub fn vdso_base(&self) -> Option<u64> {
+        self.get(md::AuxvType::AT_SYSINFO_EHDR)
+    }
 
-        info.chain(maps)
+    /// The program's entry point (`AT_ENTRY`).
+    pub fn entry_point(&self) -> Option<u64> {
+        self.get(md::AuxvType::AT_ENTRY)
     }
 
-    /// Iterate over the memory regions in order by memory address.
-    pub fn by_addr(&self) -> impl Iterator<Item = UnifiedMemoryInfo> {
-        let info = self
-            .info()
-            .into_iter()
-            .flat_map(|info| info.by_addr().map(UnifiedMemoryInfo::Info));
-        let maps = self
-            .maps()
-            .into_iter()
-            .flat_map(|maps| maps.by_addr().map(UnifiedMemoryInfo::Map));
+    /// The address of the program's program headers (`AT_PHDR`).
+    pub fn program_headers(&self) -> Option<u64> {
+        self.get(md::AuxvType::AT_PHDR)
+    }
 
-        info.chain(maps)
+    /// The system page size (`AT_PAGESZ`).
+    pub fn page_size(&self) -> Option<u64> {
+        self.get(md::AuxvType::AT_PAGESZ)
     }
 
-    /// Write a human-readable description of this `MinidumpMemoryList` to `f`.
-    ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        match self {
-            Self::Info(info) => info.print(f),
-            Self::Maps(maps) => maps.print(f),
-        }
+    /// Whether the program was run in secure mode (`AT_SECURE`), which is the case
+    /// for setuid and setgid programs, or ones with file capabilities.
+    pub fn secure(&self) -> Option<bool> {
+        self.get(md::AuxvType::AT_SECURE).map(|value| value != 0)
     }
 
-    /// Get the [`MinidumpLinuxMaps`] contained inside, if it exists.
+    /// The address of the string identifying the CPU (`AT_PLATFORM`).
     ///
-    /// Potentially useful for doing a more refined analysis in specific places.
-    pub fn maps(&self) -> Option<&MinidumpLinuxMaps<'a>> {
-        match &self {
-            Self::Maps(maps) => Some(maps),
-            Self::Info(_) => None,
-        }
+    /// The string itself is in the process's memory, which may not be in the minidump.
+    pub fn platform(&self) -> Option<u64> {
+        self.get(md::AuxvType::AT_PLATFORM)
     }
 
-    /// Get the [`MinidumpMemoryInfoList`] contained inside, if it exists.
-    ///
-    /// Potentially useful for doing a more refined analysis in specific places.
-    pub fn info(&self) -> Option<&MinidumpMemoryInfoList<'a>> {
-        match &self {
-            Self::Maps(_) => None,
-            Self::Info(info) => Some(info),
+    /// Write a human-readable description of this `MinidumpLinuxAuxv` to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        writeln!(f, "MinidumpLinuxAuxv")?;
+        for (key, value) in self.iter() {
+            match md::AuxvType::from_u64(key) {
+                Some(ty) => writeln!(f, "  {:20} = {:#x}", format!("{:?}", ty), value)?,
+                None => writeln!(f, "  {:20} = {:#x}", key, value)?,
+            }
         }
+        writeln!(f)
     }
 }
 
-impl<'a> UnifiedMemoryInfo<'a> {
-    /// Write a human-readable description.
-    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        match self {
-            Self::Info(info) => info.print(f),
-            Self::Map(map) => map.print(f),
+impl MinidumpModuleList {
+    /// Return an empty `MinidumpModuleList`.
+    pub fn new() -> MinidumpModuleList {
+        MinidumpModuleList {
+            modules: vec![],
+            modules_by_addr: RangeMap::new(),
+            range_issues: vec![],
         }
     }
-
-    /// The range of memory this info applies to.
-    pub fn memory_range(&self) -> Option<Range<u64>> {
-        match self {
-            Self::Info(info) => info.memory_range(),
-            Self::Map(map) => map.memory_range(),
+    /// Create a `MinidumpModuleList` from a list of `MinidumpModule`s.
+    pub fn from_modules(modules: Vec<MinidumpModule>) -> MinidumpModuleList {
+        let (modules_by_addr, range_issues) = module_ranges(
+            modules
+                .iter()
+                .map(|module| (module.base_address(), module.size())),
+        );
+        MinidumpModuleList {
+            modules,
+            modules_by_addr,
+            range_issues,
         }
     }
 
-    /// Whether this memory range was executable.
-    pub fn is_executable(&self) -> bool {
-        match self {
-            Self::Info(info) => info.is_executable(),
-            Self::Map(map) => map.is_executable(),
+    /// Returns the module corresponding to the main executable.
+    pub fn main_module(&self) -> Option<&MinidumpModule> {
+        // The main code module is the first one present in a minidump file's
+        // MINIDUMP_MODULEList.
+        if !self.modules.is_empty() {
+            Some(&self.modules[0])
+        } else {
+            None
         }
     }
-}
 
-impl<'a> MinidumpThread<'a> {
-    pub fn context(
-        &self,
-        system_info: &MinidumpSystemInfo,
-        misc: Option<&MinidumpMiscInfo>,
-    ) -> Option<Cow<MinidumpContext>> {
-        MinidumpContext::read(self.context?, self.endian, system_info, misc)
-            .ok()
-            .map(Cow::Owned)
+    /// Return a `MinidumpModule` whose address range covers `address`.
+    ///
+    /// Modules with an implausible size (empty, or over 2GiB) are never
+    /// returned. Where modules overlap, the largest of them is returned (the
+    /// first in the list, if several are the largest), and the others are
+    /// ignored for lookups entirely. These problems are described by
+    /// [`range_issues`][Self::range_issues].
+    pub fn module_at_address(&self, address: u64) -> Option<&MinidumpModule> {
+        self.modules_by_addr
+            .get(address)
+            .map(|&index| &self.modules[index])
     }
 
-    pub fn stack_memory(
-        &self,
-        memory_list: &MinidumpMemoryList<'a>,
-    ) -> Option<Cow<MinidumpMemory<'a>>> {
-        self.stack.as_ref().map(Cow::Borrowed).or_else(|| {
-            // Sometimes the raw.stack RVA is null/busted, but the start_of_memory_range
-            // value is correct. So if the `read` fails, try resolving start_of_memory_range
-            // with the MinidumpMemoryList. (This seems to specifically be a problem with
-            // Windows minidumps.)
-            let stack_addr = self.raw.stack.start_of_memory_range;
-            let memory = memory_list.memory_at_address(stack_addr)?;
-            Some(Cow::Owned(memory.clone()))
+    /// Problems with the address ranges of the modules, which make
+    /// [`module_at_address`][Self::module_at_address] less trustworthy.
+    ///
+    /// These are [`CorruptionIssue::ImplausibleModuleSize`] and
+    /// [`CorruptionIssue::OverlappingModules`], whose indices are in the order
+    /// of [`iter`][Self::iter]. [`Minidump::check_integrity`] reports them too.
+    pub fn range_issues(&self) -> &[CorruptionIssue] {
+        &self.range_issues
+    }
+
+    /// Whether the module at `index` (in the order of [`iter`][Self::iter]) is
+    /// involved in any of the [`range_issues`][Self::range_issues], so that
+    /// addresses attributed to it (or that should have been) are suspect.
+    pub fn range_is_suspect(&self, index: usize) -> bool {
+        self.range_issues.iter().any(|issue| match *issue {
+            CorruptionIssue::ImplausibleModuleSize { index: i, .. } => i == index,
+            CorruptionIssue::OverlappingModules { first, second, .. } => {
+                first == index || second == index
+            }
+            _ => false,
         })
     }
 
-    /// Write a human-readable description of this `MinidumpThread` to `f`.
+    /// Iterate over the modules in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &MinidumpModule> {
+        self.modules.iter()
+    }
+
+    /// Iterate over the modules in order by memory address.
+    pub fn by_addr(&self) -> impl DoubleEndedIterator<Item = &MinidumpModule> {
+        self.modules_by_addr
+            .ranges_values()
+            .map(move |&(_, index)| &self.modules[index])
+    }
+
+    /// Write a human-readable description of this `MinidumpModuleList` to `f`.
     ///
     /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(
-        &self,
-        f: &mut T,
-        memory: Option<&MinidumpMemoryList<'a>>,
-        system: Option<&MinidumpSystemInfo>,
-        misc: Option<&MinidumpMiscInfo>,
-        brief: bool,
-    ) -> io::Result<()> {
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
         write!(
             f,
-            r#"MINIDUMP_THREAD
-  thread_id                   = {:#x}
-  suspend_count               = {}
-  priority_class              = {:#x}
-  priority                    = {:#x}
-  teb                         = {:#x}
-  stack.start_of_memory_range = {:#x}
-  stack.memory.data_size      = {:#x}
-  stack.memory.rva            = {:#x}
-  thread_context.data_size    = {:#x}
-  thread_context.rva          = {:#x}
+            "MinidumpModuleList
+  module_count = {}
 
-"#,
-            self.raw.thread_id,
-            self.raw.suspend_count,
-            self.raw.priority_class,
-            self.raw.priority,
-            self.raw.teb,
-            self.raw.stack.start_of_memory_range,
-            self.raw.stack.memory.data_size,
-            self.raw.stack.memory.rva,
-            self.raw.thread_context.data_size,
-            self.raw.thread_context.rva,
+",
+            self.modules.len()
         )?;
-        if let Some(system_info) = system {
-            if let Some(ctx) = self.context(system_info, misc) {
-                ctx.print(f)?;
-            } else {
-                write!(f, "  (no context)\n\n")?;
+        for (i, module) in self.modules.iter().enumerate() {
+            writeln!(f, "module[{}]", i)?;
+            module.print(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Modules at least this big are assumed to have a corrupt size.
+///
+/// Neither PE nor Mach-O images can be this big, and it's half of a 32-bit
+/// address space.
+const IMPLAUSIBLE_MODULE_SIZE: u64 = 0x8000_0000;
+
+/// Map the address ranges of modules (given as their base address and size)
+/// to their indices, for [`MinidumpModuleList::module_at_address`], and find
+/// the problems with those ranges.
+///
+/// Modules with an implausible size aren't mapped at all. Where modules
+/// overlap, the largest one is mapped (the first one, if several are the
+/// largest), and the others aren't mapped at all.
+fn module_ranges(
+    modules: impl Iterator<Item = (u64, u64)>,
+) -> (RangeMap<u64, usize>, Vec<CorruptionIssue>) {
+    let mut issues = Vec::new();
+    let mut candidates = Vec::new();
+    for (index, (base_address, size)) in modules.enumerate() {
+        match base_address.checked_add(size) {
+            Some(_) if size != 0 && size < IMPLAUSIBLE_MODULE_SIZE => {
+                candidates.push((index, base_address, base_address + size - 1));
             }
-        } else {
-            write!(f, "  (no context)\n\n")?;
+            _ => issues.push(CorruptionIssue::ImplausibleModuleSize {
+                index,
+                base_address,
+                size,
+            }),
         }
+    }
+    candidates.sort_by_key(|&(index, start, end)| (std::cmp::Reverse(end - start), index));
 
-        if brief {
-            writeln!(f)?;
-            return Ok(());
+    // The mapped modules, keyed by their start address. They never overlap,
+    // so only the last one starting at or before a module's end can overlap it.
+    let mut mapped = BTreeMap::new();
+    for (index, start, end) in candidates {
+        match mapped.range(..=end).next_back() {
+            Some((&other_start, &(other_end, other))) if other_end >= start => {
+                issues.push(CorruptionIssue::OverlappingModules {
+                    first: other,
+                    second: index,
+                    address: start.max(other_start),
+                });
+            }
+            _ => {
+                mapped.insert(start, (end, index));
+            }
         }
+    }
+    issues.sort_by_key(|issue| match *issue {
+        CorruptionIssue::ImplausibleModuleSize { index, .. } => index,
+        CorruptionIssue::OverlappingModules { second, .. } => second,
+        _ => 0,
+    });
 
-        let pointer_width = system.map_or(PointerWidth::Unknown, |info| info.cpu.pointer_width());
+    let ranges = mapped
+        .into_iter()
+        .map(|(start, (end, index))| (Range::new(start, end), index))
+        .collect();
+    (RangeMap::from_sorted_vec(ranges), issues)
+}
 
-        // We might not need any memory, so try to limp forward with an empty
-        // MemoryList if we don't have one.
-        let dummy_memory = MinidumpMemoryList::default();
-        let memory = memory.unwrap_or(&dummy_memory);
-        if let Some(ref stack) = self.stack_memory(memory) {
-            writeln!(f, "Stack")?;
+impl Default for MinidumpModuleList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-            // For printing purposes, we'll treat any unknown CPU type as 64-bit
-            let chunk_size: usize = pointer_width.size_in_bytes().unwrap_or(8).into();
-            let mut offset = 0;
-            for chunk in stack.bytes.chunks_exact(chunk_size) {
-                write!(f, "    {:#010x}: ", offset)?;
+impl<'a> MinidumpStream<'a> for MinidumpModuleList {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::ModuleListStream as u32;
 
-                match pointer_width {
-                    PointerWidth::Bits32 => {
-                        let value = match self.endian {
-                            scroll::Endian::Little => u32::from_le_bytes(chunk.try_into().unwrap()),
-                            scroll::Endian::Big => u32::from_be_bytes(chunk.try_into().unwrap()),
-                        };
-                        write!(f, "{:#010x}", value)?;
-                    }
-                    PointerWidth::Unknown | PointerWidth::Bits64 => {
-                        let value = match self.endian {
-                            scroll::Endian::Little => u64::from_le_bytes(chunk.try_into().unwrap()),
-                            scroll::Endian::Big => u64::from_be_bytes(chunk.try_into().unwrap()),
-                        };
-                        write!(f, "{:#018x}", value)?;
-                    }
-                }
+    fn read(
+        bytes: &'a [u8],
+        all: &'a [u8],
+        endian: scroll::Endian,
+        system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpModuleList, Error> {
+        let mut offset = 0;
+        let raw_modules: Vec<md::MINIDUMP_MODULE> = read_stream_list(&mut offset, bytes, endian)?;
+        // read auxiliary data for each module
+        let mut modules = Vec::with_capacity(raw_modules.len());
+        for raw in raw_modules.into_iter() {
+            if raw.size_of_image == 0 || raw.size_of_image as u64 > (u64::MAX - raw.base_of_image) {
+                // Bad image size.
+                // TODO: just drop this module, keep the rest?
+                return Err(Error::ModuleReadFailure);
+            }
+            modules.push(MinidumpModule::read(raw, all, endian, system_info)?);
+        }
+        Ok(MinidumpModuleList::from_modules(modules))
+    }
+}
 
-                writeln!(f)?;
+impl MinidumpUnloadedModuleList {
+    /// Return an empty `MinidumpModuleList`.
+    pub fn new() -> MinidumpUnloadedModuleList {
+        MinidumpUnloadedModuleList {
+            modules: vec![],
+            modules_by_addr: vec![],
+        }
+    }
+    /// Create a `MinidumpModuleList` from a list of `MinidumpModule`s.
+    pub fn from_modules(modules: Vec<MinidumpUnloadedModule>) -> MinidumpUnloadedModuleList {
+        let mut modules_by_addr = (0..modules.len())
+            .filter_map(|i| modules[i].memory_range().map(|r| (r, i)))
+            .collect::<Vec<_>>();
 
-                offset += chunk_size;
-            }
-        } else {
-            writeln!(f, "No stack")?;
+        modules_by_addr.sort_by_key(|(range, _idx)| *range);
+
+        MinidumpUnloadedModuleList {
+            modules,
+            modules_by_addr,
         }
-        writeln!(f)?;
-        Ok(())
     }
 
-    /// Gets the last error code the thread recorded, just like win32's GetLastError.
+    /// Return an iterator of `MinidumpUnloadedModules` whose address range covers `address`.
+    pub fn modules_at_address(
+        &self,
+        address: u64,
+    ) -> impl Iterator<Item = &MinidumpUnloadedModule> {
+        // We have all of our modules sorted by memory range (base address being the
+        // high-order value), and we need to get the range of values that overlap
+        // with our target address. I'm a bit too tired to work out the exact
+        // combination of binary searches to do this, so let's just use `filter`
+        // for now (unloaded_modules should be a bounded list anyway).
+        self.modules_by_addr
+            .iter()
+            .filter(move |(range, _idx)| range.contains(address))
+            .map(move |(_range, idx)| &self.modules[*idx])
+    }
+
+    /// Return an iterator of every `MinidumpUnloadedModule` whose address range
+    /// covers `address`, in the order they were stored in the minidump.
     ///
-    /// The value is heuristically converted into a CrashReason because that's our
-    /// general error code handling machinery, even though this may not actually be
-    /// the reason for the crash!
-    pub fn last_error(&self, cpu: Cpu, memory: &MinidumpMemoryList) -> Option<CrashReason> {
-        // Early hacky implementation: rather than implementing all the TEB layouts,
-        // just use the fact that we know the value we want is a 13-pointers offset
-        // from the start of the TEB.
-        let teb = self.raw.teb;
-        let pointer_width = cpu.pointer_width().size_in_bytes()? as u64;
-        let offset = pointer_width.checked_mul(13)?;
-        let addr = teb.checked_add(offset)?;
-        let val: u32 = memory
-            .memory_at_address(addr)?
-            .get_memory_at_address(addr)?;
+    /// Unlike [`modules_at_address`][Self::modules_at_address], repeated loads of
+    /// the same module at the same address are all kept, so the order reflects
+    /// the order of the unload events. Use [`MinidumpUnloadedModule::checksum`]
+    /// and [`MinidumpUnloadedModule::time_date_stamp`] to tell them apart.
+    pub fn unloaded_modules_at_address(
+        &self,
+        address: u64,
+    ) -> impl Iterator<Item = &MinidumpUnloadedModule> {
+        self.modules.iter().filter(
+            move |module| matches!(module.memory_range(), Some(range) if range.contains(address)),
+        )
+    }
 
-        Some(CrashReason::from_windows_error(val))
+    /// Iterate over the modules in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &MinidumpUnloadedModule> {
+        self.modules.iter()
+    }
+
+    /// Iterate over the modules in order by memory address.
+    pub fn by_addr(&self) -> impl Iterator<Item = &MinidumpUnloadedModule> {
+        self.modules_by_addr
+            .iter()
+            .map(move |&(_, index)| &self.modules[index])
+    }
+
+    /// Write a human-readable description of this `MinidumpModuleList` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MinidumpUnloadedModuleList
+  module_count = {}
+
+",
+            self.modules.len()
+        )?;
+        for (i, module) in self.modules.iter().enumerate() {
+            writeln!(f, "module[{}]", i)?;
+            module.print(f)?;
+        }
+        Ok(())
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpThreadList<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::ThreadListStream as u32;
+impl Default for MinidumpUnloadedModuleList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpUnloadedModuleList {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::UnloadedModuleListStream as u32;
 
     fn read(
         bytes: &'a [u8],
         all: &'a [u8],
         endian: scroll::Endian,
         _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpThreadList<'a>, Error> {
+    ) -> Result<MinidumpUnloadedModuleList, Error> {
         let mut offset = 0;
-        let raw_threads: Vec<md::MINIDUMP_THREAD> = read_stream_list(&mut offset, bytes, endian)?;
-        let mut threads = Vec::with_capacity(raw_threads.len());
-        let mut thread_ids = HashMap::with_capacity(raw_threads.len());
-        for raw in raw_threads.into_iter() {
-            thread_ids.insert(raw.thread_id, threads.len());
-
-            // Defer parsing of this to the `context` method, where we will have access
-            // to other streams that are required to parse a context properly.
-            let context = location_slice(all, &raw.thread_context).ok();
-
-            // Try to get the stack memory here, but the `stack_memory` method will
-            // attempt a fallback method with access to other streams.
-            let stack = MinidumpMemory::read(&raw.stack, all, endian).ok();
-            threads.push(MinidumpThread {
-                raw,
-                context,
-                stack,
-                endian,
-            });
+        let raw_modules: Vec<md::MINIDUMP_UNLOADED_MODULE> =
+            read_ex_stream_list(&mut offset, bytes, endian)?;
+        // read auxiliary data for each module
+        let mut modules = Vec::with_capacity(raw_modules.len());
+        for raw in raw_modules.into_iter() {
+            if raw.size_of_image == 0
+                || raw.size_of_image as u64 > (u64::max_value() - raw.base_of_image)
+            {
+                // Bad image size.
+                // TODO: just drop this module, keep the rest?
+                return Err(Error::ModuleReadFailure);
+            }
+            modules.push(MinidumpUnloadedModule::read(raw, all, endian)?);
         }
-        Ok(MinidumpThreadList {
-            threads,
-            thread_ids,
-        })
+        Ok(MinidumpUnloadedModuleList::from_modules(modules))
     }
 }
 
-impl<'a> MinidumpThreadList<'a> {
-    /// Get the thread with id `id` from this thread list if it exists.
-    pub fn get_thread(&self, id: u32) -> Option<&MinidumpThread<'a>> {
-        self.thread_ids.get(&id).map(|&index| &self.threads[index])
+impl<'a> MinidumpMemory<'a> {
+    pub fn read(
+        desc: &md::MINIDUMP_MEMORY_DESCRIPTOR,
+        data: &'a [u8],
+        endian: scroll::Endian,
+    ) -> Result<MinidumpMemory<'a>, Error> {
+        if desc.memory.rva == 0 || desc.memory.data_size == 0 {
+            // Windows will sometimes emit null stack RVAs, indicating that
+            // we need to lookup the address in a memory region. It's ok to
+            // emit an error for that here, the thread processing code will
+            // catch it.
+            return Err(Error::MemoryReadFailure);
+        }
+        let bytes = location_slice(data, &desc.memory).or(Err(Error::StreamReadFailure))?;
+        Ok(MinidumpMemory {
+            desc: *desc,
+            base_address: desc.start_of_memory_range,
+            size: desc.memory.data_size as u64,
+            bytes,
+            endian,
+        })
     }
 
-    /// Write a human-readable description of this `MinidumpThreadList` to `f`.
+    /// Write a human-readable description of this `MinidumpMemory` to `f`.
     ///
     /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(
-        &self,
-        f: &mut T,
-        memory: Option<&MinidumpMemoryList<'a>>,
-        system: Option<&MinidumpSystemInfo>,
-        misc: Option<&MinidumpMiscInfo>,
-        brief: bool,
-    ) -> io::Result<()> {
+    pub fn print<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
         write!(
             f,
-            r#"MinidumpThreadList
-  thread_count = {}
-
-"#,
-            self.threads.len()
+            "MINIDUMP_MEMORY_DESCRIPTOR
+  start_of_memory_range = {:#x}
+  memory.data_size      = {:#x}
+  memory.rva            = {:#x}
+",
+            self.desc.start_of_memory_range, self.desc.memory.data_size, self.desc.memory.rva,
         )?;
-
-        for (i, thread) in self.threads.iter().enumerate() {
-            writeln!(f, "thread[{}]", i)?;
-            thread.print(f, memory, system, misc, brief)?;
+        if !brief {
+            writeln!(f, "Memory")?;
+            self.print_contents(f)?;
         }
-        Ok(())
+        writeln!(f)
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpSystemInfo {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::SystemInfoStream as u32;
-
-    fn read(
-        bytes: &[u8],
-        all: &[u8],
-        endian: scroll::Endian,
-        system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpSystemInfo, Error> {
-        if let Some(system_info) = system_info {
-            return Ok(system_info.clone());
-        }
-
-        use std::fmt::Write;
-
-        let raw: md::MINIDUMP_SYSTEM_INFO = bytes
-            .pread_with(0, endian)
-            .or(Err(Error::StreamReadFailure))?;
-        let os = Os::from_platform_id(raw.platform_id);
-        let cpu = Cpu::from_processor_architecture(raw.processor_architecture);
-
-        let mut csd_offset = raw.csd_version_rva as usize;
-        let csd_version = read_string_utf16(&mut csd_offset, all, endian);
+impl<'a> MinidumpMemory64<'a> {
+    /// Write a human-readable description of this `MinidumpMemory64` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
+        write!(
+            f,
+            "MINIDUMP_MEMORY_DESCRIPTOR64
+  start_of_memory_range = {:#x}
+  memory.data_size      = {:#x}
+",
+            self.desc.start_of_memory_range, self.desc.data_size,
+        )?;
+        if !brief {
+            writeln!(f, "Memory")?;
+            self.print_contents(f)?;
+        }
+        writeln!(f)
+    }
+}
 
-        // self.raw.cpu.data is actually a union which we resolve here.
-        let cpu_info = match cpu {
-            Cpu::X86 | Cpu::X86_64 => {
-                let mut cpu_info = String::new();
+impl<'a, Descriptor> MinidumpMemoryBase<'a, Descriptor> {
+    /// Get `mem::size_of::<T>()` bytes of memory at `addr` from this region.
+    ///
+    /// Return `None` if the requested address range falls out of the bounds
+    /// of this memory region.
+    pub fn get_memory_at_address<T>(&self, addr: u64) -> Option<T>
+    where
+        T: TryFromCtx<'a, scroll::Endian, [u8], Error = scroll::Error>,
+    {
+        // XXX: Instead of checking the base+size validity on each access, maybe
+        // move this check to a different place?
+        let _end = self.base_address.checked_add(self.size)?;
+        let start = addr.checked_sub(self.base_address)? as usize;
 
-                if let Cpu::X86 = cpu {
-                    // The vendor's ID is an ascii string but we need to flatten out the u32's into u8's
-                    let x86_info: md::X86CpuInfo = raw
-                        .cpu
-                        .data
-                        .pread_with(0, endian)
-                        .or(Err(Error::StreamReadFailure))?;
+        self.bytes.pread_with::<T>(start, self.endian).ok()
+    }
 
-                    cpu_info.extend(
-                        x86_info
-                            .vendor_id
-                            .iter()
-                            .flat_map(|i| IntoIterator::into_iter(i.to_le_bytes()))
-                            .map(char::from),
-                    );
-                    cpu_info.push(' ');
-                }
+    /// Read a pointer at `addr` from this region, widened to a `u64`.
+    ///
+    /// `width` is usually the [`Cpu::pointer_width`] of the minidump's
+    /// [`MinidumpSystemInfo`]. Return `None` if the pointer falls out of the
+    /// bounds of this memory region, or if `width` is unknown.
+    pub fn read_pointer_at(&self, addr: u64, width: PointerWidth) -> Option<u64> {
+        match width {
+            PointerWidth::Bits32 => self.get_memory_at_address::<u32>(addr).map(u64::from),
+            PointerWidth::Bits64 => self.get_memory_at_address::<u64>(addr),
+            PointerWidth::Unknown => None,
+        }
+    }
 
-                write!(
-                    &mut cpu_info,
-                    "family {} model {} stepping {}",
-                    raw.processor_level,
-                    (raw.processor_revision >> 8) & 0xff,
-                    raw.processor_revision & 0xff
-                )
-                .unwrap();
+    /// The bytes of this region from `addr`, up to `max_len` of them.
+    fn bytes_from_address(&self, addr: u64, max_len: usize) -> Option<&'a [u8]> {
+        let start: usize = addr.checked_sub(self.base_address)?.try_into().ok()?;
+        let rest = self.bytes.get(start..).filter(|rest| !rest.is_empty())?;
+        Some(&rest[..rest.len().min(max_len)])
+    }
 
-                Some(cpu_info)
-            }
-            Cpu::Arm => {
-                let arm_info: md::ARMCpuInfo = raw
-                    .cpu
-                    .data
-                    .pread_with(0, endian)
-                    .or(Err(Error::StreamReadFailure))?;
+    /// Read a NUL-terminated UTF-8 string at `addr` from this region.
+    ///
+    /// Reads stop at the first NUL, after `max_len` bytes, or at the end of
+    /// the region, whichever comes first. Invalid UTF-8 is replaced with
+    /// U+FFFD. Return `None` if `addr` isn't in this region.
+    pub fn read_cstring_at(&self, addr: u64, max_len: usize) -> Option<String> {
+        let bytes = self.bytes_from_address(addr, max_len)?;
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        Some(String::from_utf8_lossy(&bytes[..len]).into_owned())
+    }
 
-                // There is no good list of implementer id values, but the following
-                // pages provide some help:
-                //   http://comments.gmane.org/gmane.linux.linaro.devel/6903
-                //   http://forum.xda-developers.com/archive/index.php/t-480226.html
-                let vendors = [
-                    (0x41, "ARM"),
-                    (0x51, "Qualcomm"),
-                    (0x56, "Marvell"),
-                    (0x69, "Intel/Marvell"),
-                ];
-                let parts = [
-                    (0x4100c050, "Cortex-A5"),
-                    (0x4100c080, "Cortex-A8"),
-                    (0x4100c090, "Cortex-A9"),
-                    (0x4100c0f0, "Cortex-A15"),
-                    (0x4100c140, "Cortex-R4"),
-                    (0x4100c150, "Cortex-R5"),
-                    (0x4100b360, "ARM1136"),
-                    (0x4100b560, "ARM1156"),
-                    (0x4100b760, "ARM1176"),
-                    (0x4100b020, "ARM11-MPCore"),
-                    (0x41009260, "ARM926"),
-                    (0x41009460, "ARM946"),
-                    (0x41009660, "ARM966"),
-                    (0x510006f0, "Krait"),
-                    (0x510000f0, "Scorpion"),
-                ];
-                let features = [
-                    (md::ArmElfHwCaps::HWCAP_SWP, "swp"),
-                    (md::ArmElfHwCaps::HWCAP_HALF, "half"),
-                    (md::ArmElfHwCaps::HWCAP_THUMB, "thumb"),
-                    (md::ArmElfHwCaps::HWCAP_26BIT, "26bit"),
-                    (md::ArmElfHwCaps::HWCAP_FAST_MULT, "fastmult"),
-                    (md::ArmElfHwCaps::HWCAP_FPA, "fpa"),
-                    (md::ArmElfHwCaps::HWCAP_VFP, "vfpv2"),
-                    (md::ArmElfHwCaps::HWCAP_EDSP, "edsp"),
-                    (md::ArmElfHwCaps::HWCAP_JAVA, "java"),
-                    (md::ArmElfHwCaps::HWCAP_IWMMXT, "iwmmxt"),
-                    (md::ArmElfHwCaps::HWCAP_CRUNCH, "crunch"),
-                    (md::ArmElfHwCaps::HWCAP_THUMBEE, "thumbee"),
-                    (md::ArmElfHwCaps::HWCAP_NEON, "neon"),
-                    (md::ArmElfHwCaps::HWCAP_VFPv3, "vfpv3"),
-                    (md::ArmElfHwCaps::HWCAP_VFPv3D16, "vfpv3d16"),
-                    (md::ArmElfHwCaps::HWCAP_TLS, "tls"),
-                    (md::ArmElfHwCaps::HWCAP_VFPv4, "vfpv4"),
-                    (md::ArmElfHwCaps::HWCAP_IDIVA, "idiva"),
-                    (md::ArmElfHwCaps::HWCAP_IDIVT, "idivt"),
-                ];
+    /// Read a NUL-terminated UTF-16 string at `addr` from this region, in the
+    /// minidump's byte order.
+    ///
+    /// Like [`read_cstring_at`][Self::read_cstring_at], but `max_len` counts
+    /// UTF-16 code units, and unpaired surrogates are replaced with U+FFFD.
+    pub fn read_utf16_string_at(&self, addr: u64, max_len: usize) -> Option<String> {
+        let bytes = self.bytes_from_address(addr, max_len.saturating_mul(2))?;
+        let units = bytes
+            .chunks_exact(2)
+            .map(|pair| pair.pread_with::<u16>(0, self.endian).unwrap_or_default())
+            .take_while(|&unit| unit != 0);
+        Some(
+            char::decode_utf16(units)
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect(),
+        )
+    }
 
-                let mut cpu_info = format!("ARMv{}", raw.processor_level);
+    /// Write the contents of this `MinidumpMemory` to `f` as a hex string.
+    pub fn print_contents<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write_hexdump(f, self.bytes)
+    }
 
-                // Try to extract out known vendor/part names from the cpuid,
-                // falling back to just reporting the raw value.
-                let cpuid = arm_info.cpuid;
-                if cpuid != 0 {
-                    let vendor_id = (cpuid >> 24) & 0xff;
-                    let part_id = cpuid & 0xff00fff0;
+    pub fn memory_range(&self) -> Option<Range<u64>> {
+        if self.size == 0 {
+            return None;
+        }
+        Some(Range::new(
+            self.base_address,
+            self.base_address.checked_add(self.size)? - 1,
+        ))
+    }
+}
 
-                    if let Some(&(_, vendor)) = vendors.iter().find(|&&(id, _)| id == vendor_id) {
-                        write!(&mut cpu_info, " {}", vendor).unwrap();
-                    } else {
-                        write!(&mut cpu_info, " vendor({:#x})", vendor_id).unwrap();
-                    }
+/// Build the address map of a memory list from the range of each region.
+///
+/// Minidumps sometimes have overlapping or duplicate regions, so unlike
+/// [`IntoRangeMapSafe`] this doesn't drop a region just because it overlaps
+/// another. Instead every address maps to the region that extends furthest
+/// past it, so that a read near the end of one region is served by an
+/// overlapping region that covers more of it. Of identical regions, the first
+/// one in the minidump wins.
+fn memory_regions_by_addr<V: Clone + fmt::Debug + Eq>(
+    ranges: impl IntoIterator<Item = (Option<Range<u64>>, V)>,
+) -> RangeMap<u64, V> {
+    let mut ranges = ranges
+        .into_iter()
+        .filter_map(|(range, val)| Some((range?, val)))
+        .collect::<Vec<_>>();
+    // The sort is stable, so identical regions stay in minidump order.
+    ranges.sort_by_key(|(range, _)| (range.start, std::cmp::Reverse(range.end)));
+
+    let mut merged: Vec<(Range<u64>, V)> = Vec::with_capacity(ranges.len());
+    for (range, val) in ranges {
+        if let Some((last, _)) = merged.last_mut() {
+            if range.end <= last.end {
+                // Entirely covered by a region that goes at least as far.
+                continue;
+            }
+            if range.start <= last.end {
+                // This region goes further, so it takes over the overlap.
+                // (The sort guarantees it doesn't start where `last` does.)
+                last.end = range.start - 1;
+            }
+        }
+        merged.push((range, val));
+    }
+    RangeMap::from_sorted_vec(merged)
+}
 
-                    if let Some(&(_, part)) = parts.iter().find(|&&(id, _)| id == part_id) {
-                        write!(&mut cpu_info, " {}", part).unwrap();
-                    } else {
-                        write!(&mut cpu_info, " part({:#x})", part_id).unwrap();
-                    }
-                }
+impl<'mdmp, Descriptor> MinidumpMemoryListBase<'mdmp, Descriptor> {
+    /// Return an empty `MinidumpMemoryListBase`.
+    pub fn new() -> MinidumpMemoryListBase<'mdmp, Descriptor> {
+        MinidumpMemoryListBase {
+            regions: vec![],
+            regions_by_addr: RangeMap::new(),
+        }
+    }
 
-                // Report all the known hardware features.
-                let elf_hwcaps = md::ArmElfHwCaps::from_bits_truncate(arm_info.elf_hwcaps);
-                if !elf_hwcaps.is_empty() {
-                    cpu_info.push_str(" features: ");
+    /// Create a `MinidumpMemoryListBase` from a list of `MinidumpMemoryBase`s.
+    pub fn from_regions(
+        regions: Vec<MinidumpMemoryBase<'mdmp, Descriptor>>,
+    ) -> MinidumpMemoryListBase<'mdmp, Descriptor> {
+        let regions_by_addr = memory_regions_by_addr(
+            regions
+                .iter()
+                .enumerate()
+                .map(|(i, region)| (region.memory_range(), i)),
+        );
+        MinidumpMemoryListBase {
+            regions,
+            regions_by_addr,
+        }
+    }
 
-                    // Iterator::intersperse is still unstable, so do it manually
-                    let mut comma = "";
-                    for &(_, feature) in features
-                        .iter()
-                        .filter(|&&(feature, _)| elf_hwcaps.contains(feature))
-                    {
-                        cpu_info.push_str(comma);
-                        cpu_info.push_str(feature);
-                        comma = ",";
-                    }
-                }
+    /// Return a `MinidumpMemoryBase` containing memory at `address`, if one exists.
+    pub fn memory_at_address(
+        &self,
+        address: u64,
+    ) -> Option<&MinidumpMemoryBase<'mdmp, Descriptor>> {
+        self.regions_by_addr
+            .get(address)
+            .map(|&index| &self.regions[index])
+    }
 
-                Some(cpu_info)
-            }
-            _ => None,
-        };
+    /// Iterate over the memory regions in the order contained in the minidump.
+    ///
+    /// The iterator returns items of [MinidumpMemoryBase] as `&'slf MinidumpMemoryBase<'mdmp, Descriptor>`.
+    /// That is the lifetime of the item is bound to the lifetime of the iterator itself
+    /// (`'slf`), while the slice inside [MinidumpMemoryBase] pointing at the memory itself has
+    /// the lifetime of the [Minidump] struct ('mdmp).
+    pub fn iter<'slf>(
+        &'slf self,
+    ) -> impl Iterator<Item = &'slf MinidumpMemoryBase<'mdmp, Descriptor>> {
+        self.regions.iter()
+    }
 
-        Ok(MinidumpSystemInfo {
-            raw,
-            os,
-            cpu,
-            csd_version,
-            cpu_info,
-        })
+    /// Iterate over the memory regions in order by memory address.
+    pub fn by_addr<'slf>(
+        &'slf self,
+    ) -> impl Iterator<Item = &'slf MinidumpMemoryBase<'mdmp, Descriptor>> {
+        self.regions_by_addr
+            .ranges_values()
+            .map(move |&(_, index)| &self.regions[index])
     }
 }
 
-impl MinidumpSystemInfo {
-    /// Write a human-readable description of this `MinidumpSystemInfo` to `f`.
+impl<'mdmp> MinidumpMemoryList<'mdmp> {
+    /// Write a human-readable description of this `MinidumpMemoryList` to `f`.
     ///
     /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+    pub fn print<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
         write!(
             f,
-            "MINIDUMP_SYSTEM_INFO
-  processor_architecture                     = {:#x}
-  processor_level                            = {}
-  processor_revision                         = {:#x}
-  number_of_processors                       = {}
-  product_type                               = {}
-  major_version                              = {}
-  minor_version                              = {}
-  build_number                               = {}
-  platform_id                                = {:#x}
-  csd_version_rva                            = {:#x}
-  suite_mask                                 = {:#x}
-  (version)                                  = {}.{}.{} {}
-  (cpu_info)                                 = {}
+            "MinidumpMemoryList
+  region_count = {}
 
 ",
-            self.raw.processor_architecture,
-            self.raw.processor_level,
-            self.raw.processor_revision,
-            self.raw.number_of_processors,
-            self.raw.product_type,
-            self.raw.major_version,
-            self.raw.minor_version,
-            self.raw.build_number,
-            self.raw.platform_id,
-            self.raw.csd_version_rva,
-            self.raw.suite_mask,
-            self.raw.major_version,
-            self.raw.minor_version,
-            self.raw.build_number,
-            self.csd_version().as_deref().unwrap_or(""),
-            self.cpu_info().as_deref().unwrap_or(""),
+            self.regions.len()
         )?;
-        // TODO: cpu info etc
+        for (i, region) in self.regions.iter().enumerate() {
+            writeln!(f, "region[{}]", i)?;
+            region.print(f, brief)?;
+        }
         Ok(())
     }
+}
 
-    /// If the minidump was generated on:
-    /// - Windows: Returns the the name of the Service Pack.
-    /// - macOS: Returns the product build number.
-    /// - Linux: Returns the contents of `uname -srvmo`.
-    pub fn csd_version(&self) -> Option<Cow<str>> {
-        self.csd_version.as_deref().map(Cow::Borrowed)
+impl<'mdmp> MinidumpMemory64List<'mdmp> {
+    /// Return an empty `MinidumpMemory64List`.
+    pub fn new() -> MinidumpMemory64List<'mdmp> {
+        MinidumpMemory64List {
+            descriptors: vec![],
+            base_rva: 0,
+            regions_by_addr: RangeMap::new(),
+            data: &[],
+            endian: scroll::LE,
+        }
     }
 
-    /// Returns a string describing the cpu's vendor and model.
-    pub fn cpu_info(&self) -> Option<Cow<str>> {
-        self.cpu_info.as_deref().map(Cow::Borrowed)
+    /// The number of memory regions in the list.
+    pub fn len(&self) -> usize {
+        self.descriptors.len()
     }
 
-    /// Strings identifying the version and build number of the operating
-    /// system. Returns a tuple in the format of (version, build number). This
-    /// may be useful to use if the minidump was created on a Linux machine and
-    /// is an producing empty-ish version number (0.0.0).
-    ///
-    /// Tries to parse the version number from the build if it cannot be found
-    /// in the version string. If the stream already contains a valid version
-    /// number or parsing from the build string fails, this will return what's
-    /// directly stored in the stream.
-    pub fn os_parts(&self) -> (String, Option<String>) {
-        let os_version = format!(
-            "{}.{}.{}",
-            self.raw.major_version, self.raw.minor_version, self.raw.build_number
-        );
+    /// Whether the list has no memory regions.
+    pub fn is_empty(&self) -> bool {
+        self.descriptors.is_empty()
+    }
 
-        let os_build = self
-            .csd_version()
-            .map(|v| v.trim().to_owned())
-            .filter(|v| !v.is_empty());
+    /// The raw descriptors of the memory regions, in the order they were
+    /// stored in the minidump.
+    pub fn descriptors(&self) -> &[md::MINIDUMP_MEMORY_DESCRIPTOR64] {
+        &self.descriptors
+    }
 
-        if md::PlatformId::from_u32(self.raw.platform_id) != Some(md::PlatformId::Linux)
-            || os_version != "0.0.0"
-        {
-            return (os_version, os_build);
+    /// Build the region for `desc`, whose contents are at `rva`.
+    ///
+    /// The bounds of every region are checked when the list is read.
+    fn region(&self, desc: &md::MINIDUMP_MEMORY_DESCRIPTOR64, rva: u64) -> MinidumpMemory64<'mdmp> {
+        let start = rva as usize;
+        MinidumpMemory64 {
+            desc: *desc,
+            base_address: desc.start_of_memory_range,
+            size: desc.data_size,
+            bytes: &self.data[start..start + desc.data_size as usize],
+            endian: self.endian,
         }
+    }
 
-        // Try to parse the Linux build string. Breakpad and Crashpad run
-        // `uname -srvmo` to generate it. The string follows this structure:
-        // "Linux [version] [build...] [arch] Linux/GNU" where the Linux/GNU
-        // bit may not always be present.
-        let raw_build = self.csd_version().unwrap_or(Cow::Borrowed(""));
-        let mut parts = raw_build.split(' ');
-        let version = parts.nth(1).unwrap_or("0.0.0");
-        let _arch_or_os = parts.next_back().unwrap_or_default();
-        if _arch_or_os == "Linux/GNU" {
-            let _arch = parts.next_back();
-        }
-        let build = parts.collect::<Vec<&str>>().join(" ");
+    /// Return a `MinidumpMemory64` containing memory at `address`, if one exists.
+    pub fn memory_at_address(&self, address: u64) -> Option<MinidumpMemory64<'mdmp>> {
+        self.regions_by_addr
+            .get(address)
+            .map(|&(index, rva)| self.region(&self.descriptors[index], rva))
+    }
 
-        if version == "0.0.0" {
-            (os_version, os_build)
-        } else {
-            (version.into(), Some(build))
+    /// Iterate over the memory regions in the order contained in the minidump.
+    ///
+    /// Regions are built as the iterator reaches them, so walking over every
+    /// region of a huge dump mapped with [`Minidump::read_path`] only pages in
+    /// the contents that are actually read.
+    pub fn iter<'slf>(&'slf self) -> impl Iterator<Item = MinidumpMemory64<'mdmp>> + 'slf {
+        self.descriptors
+            .iter()
+            .scan(self.base_rva, move |rva, desc| {
+                let region = self.region(desc, *rva);
+                *rva += desc.data_size;
+                Some(region)
+            })
+    }
+
+    /// Iterate over the memory regions in order by memory address.
+    pub fn by_addr<'slf>(&'slf self) -> impl Iterator<Item = MinidumpMemory64<'mdmp>> + 'slf {
+        self.regions_by_addr
+            .ranges_values()
+            .map(move |&(_, (index, rva))| self.region(&self.descriptors[index], rva))
+    }
+
+    /// Write a human-readable description of this `MinidumpMemory64List` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
+        write!(
+            f,
+            "MinidumpMemory64List
+  region_count = {}
+
+",
+            self.len()
+        )?;
+        for (i, region) in self.iter().enumerate() {
+            writeln!(f, "region[{}]", i)?;
+            region.print(f, brief)?;
         }
+        Ok(())
     }
 }
 
-// Generates an accessor for a MISC_INFO field with two possible syntaxes:
-//
-// * VERSION_NUMBER: FIELD_NAME -> FIELD_TYPE
-// * VERSION_NUMBER: FIELD_NAME if FLAG -> FIELD_TYPE
-//
-// With the following definitions:
-//
-// * VERSION_NUMBER: The MISC_INFO version this field was introduced in
-// * FIELD_NAME: The name of the field to read
-// * FLAG: A MiscInfoFlag that defines if this field contains valid data
-// * FIELD_TYPE: The type of the field
-macro_rules! misc_accessors {
-    () => {};
-    (@defnoflag $name:ident $t:ty [$($variant:ident)+]) => {
-        #[allow(unreachable_patterns)]
-        pub fn $name(&self) -> Option<&$t> {
-            match self {
-                $(
-                    RawMiscInfo::$variant(ref raw) => Some(&raw.$name),
-                )+
-                _ => None,
-            }
-        }
-    };
-    (@def $name:ident $flag:ident $t:ty [$($variant:ident)+]) => {
-        #[allow(unreachable_patterns)]
-        pub fn $name(&self) -> Option<&$t> {
-            match self {
-                $(
-                    RawMiscInfo::$variant(ref raw) => if md::MiscInfoFlags::from_bits_truncate(raw.flags1).contains(md::MiscInfoFlags::$flag) { Some(&raw.$name) } else { None },
-                )+
-                _ => None,
-            }
-        }
-    };
-    (1: $name:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@defnoflag $name $t [MiscInfo MiscInfo2 MiscInfo3 MiscInfo4 MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
-    (1: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@def $name $flag $t [MiscInfo MiscInfo2 MiscInfo3 MiscInfo4 MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
+impl<'a, Descriptor> Default for MinidumpMemoryListBase<'a, Descriptor> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    (2: $name:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@defnoflag $name $t [MiscInfo2 MiscInfo3 MiscInfo4 MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
-    (2: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@def $name $flag $t [MiscInfo2 MiscInfo3 MiscInfo4 MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
+impl<'a> Default for MinidumpMemory64List<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    (3: $name:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@defnoflag $name $t [MiscInfo3 MiscInfo4 MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
-    (3: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@def $name $flag $t [MiscInfo3 MiscInfo4 MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
+impl<'a> MergedMemory<'a> {
+    /// The starting address of the joined memory.
+    pub fn base_address(&self) -> u64 {
+        self.base_address
+    }
 
-    (4: $name:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@defnoflag $name $t [MiscInfo4 MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
-    (4: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@def $name $flag $t [MiscInfo4 MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
+    /// The length of the joined memory.
+    pub fn size(&self) -> u64 {
+        self.bytes.len() as u64
+    }
 
-    (5: $name:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@defnoflag $name $t [MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
-    (5: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
-        misc_accessors!(@def $name $flag $t [MiscInfo5]);
-        misc_accessors!($($rest)*);
-    };
+    /// The contents of the joined memory.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// How many regions of the memory list were joined (1 if none were).
+    pub fn region_count(&self) -> usize {
+        self.region_count
+    }
+
+    /// View the joined memory as a single [`MinidumpMemory`], to pass to
+    /// anything that expects a region.
+    ///
+    /// The region's raw descriptor is made up: it covers the joined memory,
+    /// but doesn't point anywhere in the minidump.
+    pub fn as_memory(&self) -> MinidumpMemory<'_> {
+        MinidumpMemory {
+            desc: md::MINIDUMP_MEMORY_DESCRIPTOR {
+                start_of_memory_range: self.base_address,
+                memory: md::MINIDUMP_LOCATION_DESCRIPTOR {
+                    data_size: self.bytes.len().try_into().unwrap_or(u32::MAX),
+                    rva: 0,
+                },
+            },
+            base_address: self.base_address,
+            size: self.size(),
+            bytes: &self.bytes,
+            endian: self.endian,
+        }
+    }
 }
 
-impl RawMiscInfo {
-    // Fields are grouped by the flag that guards them.
-    misc_accessors!(
-        1: size_of_info -> u32,
-        1: flags1 -> u32,
+impl<'a> Default for UnifiedMemoryList<'a> {
+    fn default() -> Self {
+        Self::Memory(MinidumpMemoryList::default())
+    }
+}
 
-        1: process_id if MINIDUMP_MISC1_PROCESS_ID -> u32,
+impl<'mdmp> UnifiedMemoryList<'mdmp> {
+    /// Take two potential memory sources and create an interface that unifies them.
+    ///
+    /// Under normal circumstances a minidump should only contain one of these
+    /// with any regions in it. If both are provided, an empty one is ignored,
+    /// and otherwise the `MemoryList` is arbitrarily preferred to attempt to
+    /// make progress.
+    pub fn new(
+        memory: Option<MinidumpMemoryList<'mdmp>>,
+        memory64: Option<MinidumpMemory64List<'mdmp>>,
+    ) -> Option<Self> {
+        match (memory, memory64) {
+            (Some(memory), Some(memory64)) => {
+                if memory.regions.is_empty() && !memory64.is_empty() {
+                    return Some(Self::Memory64(memory64));
+                }
+                if !memory64.is_empty() {
+                    warn!("UnifiedMemoryList got both kinds of memory! (using MemoryList)");
+                }
+                Some(Self::Memory(memory))
+            }
+            (Some(memory), None) => Some(Self::Memory(memory)),
+            (None, Some(memory64)) => Some(Self::Memory64(memory64)),
+            (None, None) => None,
+        }
+    }
 
-        1: process_create_time if MINIDUMP_MISC1_PROCESS_TIMES -> u32,
-        1: process_user_time if MINIDUMP_MISC1_PROCESS_TIMES -> u32,
-        1: process_kernel_time if MINIDUMP_MISC1_PROCESS_TIMES -> u32,
+    /// Return the region containing memory at `address`, if one exists.
+    pub fn memory_at_address(&self, address: u64) -> Option<UnifiedMemory<'_, 'mdmp>> {
+        match self {
+            Self::Memory(memory) => memory.memory_at_address(address).map(UnifiedMemory::Memory),
+            Self::Memory64(memory64) => memory64
+                .memory_at_address(address)
+                .map(UnifiedMemory::Memory64),
+        }
+    }
 
-        2: processor_max_mhz if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
-        2: processor_current_mhz if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
-        2: processor_mhz_limit if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
-        2: processor_max_idle_state if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
-        2: processor_current_idle_state if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
+    /// Return the memory at `address`, joined with any regions that continue
+    /// it contiguously on either side.
+    ///
+    /// If no other region continues the one containing `address`, that
+    /// region's contents are borrowed rather than copied. Regions of a
+    /// [`MinidumpMemory64List`] are never joined (and always borrowed), because
+    /// full-memory dumps routinely have long runs of adjacent regions that
+    /// would be very expensive to copy.
+    pub fn merged_memory_at_address(&self, address: u64) -> Option<MergedMemory<'mdmp>> {
+        let memory = match self {
+            Self::Memory(memory) => memory,
+            Self::Memory64(memory64) => {
+                let region = memory64.memory_at_address(address)?;
+                return Some(MergedMemory {
+                    base_address: region.base_address,
+                    bytes: Cow::Borrowed(region.bytes),
+                    region_count: 1,
+                    endian: region.endian,
+                });
+            }
+        };
 
-        3: process_integrity_level if MINIDUMP_MISC3_PROCESS_INTEGRITY -> u32,
+        // Join the neighbours of the region containing `address` in the
+        // address map, so the joined memory agrees with `memory_at_address`
+        // wherever regions overlap.
+        let segments = memory.regions_by_addr.ranges_values().as_slice();
+        let joined = |a: &(Range<u64>, usize), b: &(Range<u64>, usize)| {
+            a.0.end.checked_add(1) == Some(b.0.start)
+        };
+        let found = segments.partition_point(|(range, _)| range.end < address);
+        let (_, index) = segments
+            .get(found)
+            .filter(|(range, _)| range.contains(address))?;
+        let region = &memory.regions[*index];
+        let mut first = found;
+        while first > 0 && joined(&segments[first - 1], &segments[first]) {
+            first -= 1;
+        }
+        let mut last = found;
+        while last + 1 < segments.len() && joined(&segments[last], &segments[last + 1]) {
+            last += 1;
+        }
+
+        let borrowed = MergedMemory {
+            base_address: region.base_address,
+            bytes: Cow::Borrowed(region.bytes),
+            region_count: 1,
+            endian: region.endian,
+        };
+        if first == last {
+            return Some(borrowed);
+        }
+        let run = &segments[first..=last];
+        let chunks = run
+            .iter()
+            .map(|(range, index)| {
+                let region = &memory.regions[*index];
+                let start = (range.start - region.base_address) as usize;
+                let end = (range.end - region.base_address) as usize;
+                region.bytes.get(start..=end)
+            })
+            .collect::<Option<Vec<_>>>();
+        match chunks {
+            Some(chunks) => Some(MergedMemory {
+                base_address: run[0].0.start,
+                bytes: Cow::Owned(chunks.concat()),
+                region_count: run.len(),
+                endian: region.endian,
+            }),
+            // A region is missing some of its contents, so don't guess at
+            // what's past it.
+            None => Some(borrowed),
+        }
+    }
 
-        3: process_execute_flags if MINIDUMP_MISC3_PROCESS_EXECUTE_FLAGS -> u32,
+    /// Iterate over the memory regions in the order contained in the minidump.
+    pub fn iter(&self) -> impl Iterator<Item = UnifiedMemory<'_, 'mdmp>> {
+        let memory = self
+            .memory()
+            .into_iter()
+            .flat_map(|memory| memory.iter().map(UnifiedMemory::Memory));
+        let memory64 = self
+            .memory64()
+            .into_iter()
+            .flat_map(|memory64| memory64.iter().map(UnifiedMemory::Memory64));
 
-        3: protected_process if MINIDUMP_MISC3_PROTECTED_PROCESS -> u32,
+        memory.chain(memory64)
+    }
 
-        3: time_zone_id if MINIDUMP_MISC3_TIMEZONE -> u32,
-        3: time_zone if MINIDUMP_MISC3_TIMEZONE -> md::TIME_ZONE_INFORMATION,
+    /// Iterate over the memory regions in order by memory address.
+    pub fn by_addr(&self) -> impl Iterator<Item = UnifiedMemory<'_, 'mdmp>> {
+        let memory = self
+            .memory()
+            .into_iter()
+            .flat_map(|memory| memory.by_addr().map(UnifiedMemory::Memory));
+        let memory64 = self
+            .memory64()
+            .into_iter()
+            .flat_map(|memory64| memory64.by_addr().map(UnifiedMemory::Memory64));
 
-        4: build_string if MINIDUMP_MISC4_BUILDSTRING -> [u16; 260],
-        4: dbg_bld_str if MINIDUMP_MISC4_BUILDSTRING -> [u16; 40],
+        memory.chain(memory64)
+    }
 
-        5: xstate_data -> md::XSTATE_CONFIG_FEATURE_MSC_INFO,
+    /// Iterate, in order by memory address, over the memory regions that
+    /// overlap the `size` bytes starting at `address`.
+    pub fn memory_overlapping(
+        &self,
+        address: u64,
+        size: u64,
+    ) -> impl Iterator<Item = UnifiedMemory<'_, 'mdmp>> {
+        let end = address.saturating_add(size);
+        self.by_addr().filter(move |region| {
+            size != 0
+                && region.base_address() < end
+                && region.base_address().saturating_add(region.size()) > address
+        })
+    }
 
-        5: process_cookie if MINIDUMP_MISC5_PROCESS_COOKIE -> u32,
-    );
-}
+    /// Iterate over the addresses at which `pattern` appears in the captured
+    /// memory, in order by address.
+    ///
+    /// Every region is searched separately, so a match that would straddle
+    /// two adjacent regions isn't found. An empty `pattern` matches nothing.
+    pub fn find_bytes<'s>(&'s self, pattern: &'s [u8]) -> impl Iterator<Item = u64> + 's {
+        FindBytes::new(self.by_addr(), memmem::Finder::new(pattern))
+    }
 
-impl<'a> MinidumpStream<'a> for MinidumpMiscInfo {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MiscInfoStream as u32;
+    /// Iterate over the addresses at which the pointer `value` appears in the
+    /// captured memory, in order by address.
+    ///
+    /// `value` is encoded with the minidump's byte order, and truncated to
+    /// `width`. Matches don't have to be aligned. See [`find_bytes`][Self::find_bytes].
+    pub fn find_pointer(&self, value: u64, width: PointerWidth) -> impl Iterator<Item = u64> + '_ {
+        let endian = self.iter().next().map_or(LE, |region| match region {
+            UnifiedMemory::Memory(memory) => memory.endian,
+            UnifiedMemory::Memory64(memory64) => memory64.endian,
+        });
+        let mut pattern = [0u8; 8];
+        let len = match width {
+            PointerWidth::Bits32 => pattern.pwrite_with(value as u32, 0, endian),
+            PointerWidth::Bits64 => pattern.pwrite_with(value, 0, endian),
+            PointerWidth::Unknown => Ok(0),
+        }
+        .unwrap_or(0);
+        FindBytes::new(
+            self.by_addr(),
+            memmem::Finder::new(&pattern[..len]).into_owned(),
+        )
+    }
 
-    fn read(
-        bytes: &[u8],
-        _all: &[u8],
-        endian: scroll::Endian,
-        _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpMiscInfo, Error> {
-        // The misc info has gone through several revisions, so try to read the largest known
-        // struct possible.
-        macro_rules! do_read {
-            ($(($t:ty, $variant:ident),)+) => {
-                $(
-                    if bytes.len() >= <$t>::size_with(&endian) {
-                        return Ok(MinidumpMiscInfo {
-                            raw: RawMiscInfo::$variant(bytes.pread_with(0, endian).or(Err(Error::StreamReadFailure))?),
-                        });
-                    }
-                )+
-            }
+    /// Write a human-readable description of the contained memory list to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T, brief: bool) -> io::Result<()> {
+        match self {
+            Self::Memory(memory) => memory.print(f, brief),
+            Self::Memory64(memory64) => memory64.print(f, brief),
         }
+    }
 
-        do_read!(
-            (md::MINIDUMP_MISC_INFO_5, MiscInfo5),
-            (md::MINIDUMP_MISC_INFO_4, MiscInfo4),
-            (md::MINIDUMP_MISC_INFO_3, MiscInfo3),
-            (md::MINIDUMP_MISC_INFO_2, MiscInfo2),
-            (md::MINIDUMP_MISC_INFO, MiscInfo),
-        );
-        Err(Error::StreamReadFailure)
+    /// Get the [`MinidumpMemoryList`] contained inside, if it exists.
+    pub fn memory(&self) -> Option<&MinidumpMemoryList<'mdmp>> {
+        match &self {
+            Self::Memory(memory) => Some(memory),
+            Self::Memory64(_) => None,
+        }
+    }
+
+    /// Get the [`MinidumpMemory64List`] contained inside, if it exists.
+    pub fn memory64(&self) -> Option<&MinidumpMemory64List<'mdmp>> {
+        match &self {
+            Self::Memory(_) => None,
+            Self::Memory64(memory64) => Some(memory64),
+        }
     }
 }
 
-// Generates an accessor for a MAC_CRASH_INFO field with two possible syntaxes:
-//
-// * VERSION_NUMBER: FIELD_NAME -> FIELD_TYPE
-// * VERSION_NUMBER: string FIELD_NAME -> FIELD_TYPE
-//
-// With the following definitions:
-//
-// * VERSION_NUMBER: The MAC_CRASH_INFO version this field was introduced in
-// * FIELD_NAME: The name of the field to read
-// * FIELD_TYPE: The type of the field
-//
-// The "string" mode will retrieve the field from the variant's _RECORD_STRINGS
-// struct, while the other mode will retrieve it from the variant's _RECORD
-// struct.
-//
-// In both cases, None will be yielded if the value is null/empty.
-macro_rules! mac_crash_accessors {
-    () => {};
-    (@deffixed $name:ident $t:ty [$($variant:ident)+]) => {
-        #[allow(unreachable_patterns)]
-        pub fn $name(&self) -> Option<&$t> {
-            match self {
-                $(
-                    RawMacCrashInfo::$variant(ref fixed, _) => {
-                        if fixed.$name == 0 {
-                            None
-                        } else {
-                            Some(&fixed.$name)
-                        }
-                    },
-                )+
-                _ => None,
-            }
+/// The iterator behind [`UnifiedMemoryList::find_bytes`].
+struct FindBytes<'a, 'mdmp, 'p, I> {
+    regions: I,
+    finder: memmem::Finder<'p>,
+    region: Option<UnifiedMemory<'a, 'mdmp>>,
+    offset: usize,
+}
+
+impl<'a, 'mdmp, 'p, I> FindBytes<'a, 'mdmp, 'p, I> {
+    fn new(regions: I, finder: memmem::Finder<'p>) -> Self {
+        FindBytes {
+            regions,
+            finder,
+            region: None,
+            offset: 0,
         }
-    };
-    (@defstrings $name:ident $t:ty [$($variant:ident)+]) => {
-        #[allow(unreachable_patterns)]
-        pub fn $name(&self) -> Option<&$t> {
-            match self {
-                $(
-                    RawMacCrashInfo::$variant(_, ref strings) => {
-                        if strings.$name.is_empty() {
-                            None
-                        } else {
-                            Some(&*strings.$name)
-                        }
-                    }
-                )+
-                _ => None,
+    }
+}
+
+impl<'a, 'mdmp, 'p, I> Iterator for FindBytes<'a, 'mdmp, 'p, I>
+where
+    I: Iterator<Item = UnifiedMemory<'a, 'mdmp>>,
+{
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.finder.needle().is_empty() {
+            return None;
+        }
+        loop {
+            if let Some(region) = &self.region {
+                let bytes = region.bytes().get(self.offset..).unwrap_or_default();
+                if let Some(found) = self.finder.find(bytes) {
+                    let offset = self.offset + found;
+                    self.offset = offset + 1;
+                    return region.base_address().checked_add(offset as u64);
+                }
             }
+            self.region = Some(self.regions.next()?);
+            self.offset = 0;
         }
-    };
-    (1: $name:ident -> $t:ty, $($rest:tt)*) => {
-        mac_crash_accessors!(@deffixed $name $t [V1 V4 V5]);
-        mac_crash_accessors!($($rest)*);
-    };
-    (1: string $name:ident -> $t:ty, $($rest:tt)*) => {
-        mac_crash_accessors!(@defstrings $name $t [V1 V4 V5]);
-        mac_crash_accessors!($($rest)*);
-    };
-    (4: $name:ident -> $t:ty, $($rest:tt)*) => {
-        mac_crash_accessors!(@deffixed $name $t [V4 V5]);
-        mac_crash_accessors!($($rest)*);
-    };
-    (4: string $name:ident -> $t:ty, $($rest:tt)*) => {
-        mac_crash_accessors!(@defstrings $name $t [V4 V5]);
-        mac_crash_accessors!($($rest)*);
-    };
-    (5: $name:ident -> $t:ty, $($rest:tt)*) => {
-        mac_crash_accessors!(@deffixed $name $t [V5]);
-        mac_crash_accessors!($($rest)*);
-    };
-    (5: string $name:ident -> $t:ty, $($rest:tt)*) => {
-        mac_crash_accessors!(@defstrings $name $t [V5]);
-        mac_crash_accessors!($($rest)*);
-    };
+    }
 }
 
-impl RawMacCrashInfo {
-    // Fields are grouped by the flag that guards them.
-    mac_crash_accessors!(
-        1: version -> u64,
+impl<'a, 'mdmp> UnifiedMemory<'a, 'mdmp> {
+    /// Get `mem::size_of::<T>()` bytes of memory at `addr` from this region.
+    ///
+    /// Return `None` if the requested address range falls out of the bounds
+    /// of this memory region.
+    pub fn get_memory_at_address<T>(&self, addr: u64) -> Option<T>
+    where
+        T: TryFromCtx<'mdmp, scroll::Endian, [u8], Error = scroll::Error>,
+    {
+        match self {
+            Self::Memory(memory) => memory.get_memory_at_address(addr),
+            Self::Memory64(memory64) => memory64.get_memory_at_address(addr),
+        }
+    }
 
-        4: thread -> u64,
-        4: dialog_mode -> u64,
+    /// Read a pointer at `addr` from this region, widened to a `u64`.
+    ///
+    /// See [`MinidumpMemoryBase::read_pointer_at`].
+    pub fn read_pointer_at(&self, addr: u64, width: PointerWidth) -> Option<u64> {
+        match self {
+            Self::Memory(memory) => memory.read_pointer_at(addr, width),
+            Self::Memory64(memory64) => memory64.read_pointer_at(addr, width),
+        }
+    }
 
-        4: string module_path -> str,
-        4: string message -> str,
-        4: string signature_string -> str,
-        4: string backtrace -> str,
-        4: string message2 -> str,
+    /// Read a NUL-terminated UTF-8 string at `addr` from this region.
+    ///
+    /// See [`MinidumpMemoryBase::read_cstring_at`].
+    pub fn read_cstring_at(&self, addr: u64, max_len: usize) -> Option<String> {
+        match self {
+            Self::Memory(memory) => memory.read_cstring_at(addr, max_len),
+            Self::Memory64(memory64) => memory64.read_cstring_at(addr, max_len),
+        }
+    }
 
-        5: abort_cause -> u64,
-    );
+    /// Read a NUL-terminated UTF-16 string at `addr` from this region.
+    ///
+    /// See [`MinidumpMemoryBase::read_utf16_string_at`].
+    pub fn read_utf16_string_at(&self, addr: u64, max_len: usize) -> Option<String> {
+        match self {
+            Self::Memory(memory) => memory.read_utf16_string_at(addr, max_len),
+            Self::Memory64(memory64) => memory64.read_utf16_string_at(addr, max_len),
+        }
+    }
+
+    /// The starting address of this range of memory.
+    pub fn base_address(&self) -> u64 {
+        match self {
+            Self::Memory(memory) => memory.base_address,
+            Self::Memory64(memory64) => memory64.base_address,
+        }
+    }
+
+    /// The length of this range of memory.
+    pub fn size(&self) -> u64 {
+        match self {
+            Self::Memory(memory) => memory.size,
+            Self::Memory64(memory64) => memory64.size,
+        }
+    }
+
+    /// The contents of the memory.
+    pub fn bytes(&self) -> &'mdmp [u8] {
+        match self {
+            Self::Memory(memory) => memory.bytes,
+            Self::Memory64(memory64) => memory64.bytes,
+        }
+    }
+
+    pub fn memory_range(&self) -> Option<Range<u64>> {
+        match self {
+            Self::Memory(memory) => memory.memory_range(),
+            Self::Memory64(memory64) => memory64.memory_range(),
+        }
+    }
+
+    /// Write the contents of this region to `f` as a hex string.
+    pub fn print_contents<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        match self {
+            Self::Memory(memory) => memory.print_contents(f),
+            Self::Memory64(memory64) => memory64.print_contents(f),
+        }
+    }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpMacCrashInfo {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MozMacosCrashInfoStream as u32;
+impl<'a> MinidumpStream<'a> for MinidumpMemoryList<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MemoryListStream as u32;
 
     fn read(
-        bytes: &[u8],
-        all: &[u8],
+        bytes: &'a [u8],
+        all: &'a [u8],
         endian: scroll::Endian,
         _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpMacCrashInfo, Error> {
-        // Get the main header of the stream
-        let header: md::MINIDUMP_MAC_CRASH_INFO = bytes
-            .pread_with(0, endian)
+    ) -> Result<MinidumpMemoryList<'a>, Error> {
+        let mut offset = 0;
+        let descriptors: Vec<md::MINIDUMP_MEMORY_DESCRIPTOR> =
+            read_stream_list(&mut offset, bytes, endian)?;
+        // read memory contents for each region
+        let mut regions = Vec::with_capacity(descriptors.len());
+        for raw in descriptors.into_iter() {
+            if let Ok(memory) = MinidumpMemory::read(&raw, all, endian) {
+                regions.push(memory);
+            } else {
+                // Just skip over corrupt entries and try to limp along.
+                continue;
+            }
+        }
+        Ok(MinidumpMemoryList::from_regions(regions))
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpMemory64List<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::Memory64ListStream as u32;
+
+    fn read(
+        bytes: &'a [u8],
+        all: &'a [u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpMemory64List<'a>, Error> {
+        let mut offset = 0;
+        let u: u64 = bytes
+            .gread_with(&mut offset, endian)
             .or(Err(Error::StreamReadFailure))?;
 
-        let strings_offset = header.record_start_size as usize;
-        let mut prev_version = None;
-        let mut infos = Vec::new();
+        let base_rva: u64 = bytes
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
 
-        // We use `take` here to better handle a corrupt record_count that is larger than the
-        // maximum supported size.
-        let records = header.records.iter().take(header.record_count as usize);
+        let (count, counted_size) = ensure_count_in_bound(
+            bytes,
+            u.try_into().map_err(|_| Error::StreamReadFailure)?,
+            md::MINIDUMP_MEMORY_DESCRIPTOR64::size_with(&endian),
+            offset,
+        )?;
 
-        for record_location in records {
-            // Peek the V1 version to get the `version` field
-            let record_slice = location_slice(all, record_location)?;
-            let base: md::MINIDUMP_MAC_CRASH_INFO_RECORD = record_slice
-                .pread_with(0, endian)
+        if bytes.len() != counted_size {
+            return Err(Error::StreamSizeMismatch {
+                expected: counted_size,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut descriptors = Vec::with_capacity(count);
+        for _ in 0..count {
+            let raw: md::MINIDUMP_MEMORY_DESCRIPTOR64 = bytes
+                .gread_with(&mut offset, endian)
                 .or(Err(Error::StreamReadFailure))?;
+            descriptors.push(raw);
+        }
 
-            // The V1 version also includes the stream type again, but that's
-            // not really important, so just warn about it and keep going.
-            if base.stream_type != header.stream_type as u64 {
-                warn!(
-                    "MozMacosCrashInfoStream records don't have the right stream type? {}",
-                    base.stream_type
-                );
-            }
-
-            // Make sure every record has the same version, because they have to
-            // share their strings_offset which make heterogeneous records impossible.
-            if let Some(prev_version) = prev_version {
-                if prev_version != base.version {
-                    warn!(
-                        "MozMacosCrashInfoStream had two different versions ({} != {})",
-                        prev_version, base.version
-                    );
-                    return Err(Error::VersionMismatch);
-                }
-            }
-            prev_version = Some(base.version);
-
-            // Now actually read the full record and its strings for the version
-            macro_rules! do_read {
-                ($base_version:expr, $strings_offset:expr, $infos:ident,
-                    $(($version:expr, $fixed:ty, $strings:ty, $variant:ident),)+) => {$(
-                    if $base_version >= $version {
-                        let offset = &mut 0;
-                        let fixed: $fixed = record_slice
-                            .gread_with(offset, endian)
-                            .or(Err(Error::StreamReadFailure))?;
-
-                        // Sanity check that we haven't blown past where the strings start.
-                        if *offset > $strings_offset {
-                            warn!("MozMacosCrashInfoStream's record_start_size was too small! ({})",
-                                $strings_offset);
-                            return Err(Error::StreamReadFailure);
-                        }
-
-                        // We could be handling a newer version of the format than we know
-                        // how to support, so jump to where the strings start, potentially
-                        // skipping over some unknown fields.
-                        *offset = $strings_offset;
-                        let num_strings = <$strings>::num_strings();
-                        let mut strings = <$strings>::default();
-
-                        // Read out all the strings we know about
-                        for i in 0..num_strings {
-                            let string = read_cstring_utf8(offset, record_slice)
-                                .ok_or(Error::StreamReadFailure)?;
-                            strings.set_string(i, string);
-                        }
-                        // If this is a newer version, there may be some extra variable length
-                        // data in this record, but we don't know what it is, so don't try to parse it.
-
-                        infos.push(RawMacCrashInfo::$variant(fixed, strings));
-                        continue;
-                    }
-                )+}
+        // Only check that every region's contents are in bounds here, the
+        // regions themselves are built on demand.
+        let mut rva = base_rva;
+        let mut ranges = Vec::with_capacity(descriptors.len());
+        for (index, raw) in descriptors.iter().enumerate() {
+            let end = rva
+                .checked_add(raw.data_size)
+                .ok_or(Error::StreamReadFailure)?;
+            if end > all.len() as u64 {
+                return Err(Error::StreamReadFailure);
             }
-
-            do_read!(
-                base.version,
-                strings_offset,
-                infos,
-                (
-                    5,
-                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_5,
-                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_STRINGS_5,
-                    V5
-                ),
-                (
-                    4,
-                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_4,
-                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_STRINGS_4,
-                    V4
-                ),
-                (
-                    1,
-                    md::MINIDUMP_MAC_CRASH_INFO_RECORD,
-                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_STRINGS,
-                    V1
-                ),
-            );
-        }
-        Ok(MinidumpMacCrashInfo { raw: infos })
-    }
-}
-
-impl MinidumpMacCrashInfo {
-    /// Write a human-readable description of this `MinidumpMacCrashInfo` to `f`.
-    ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        macro_rules! write_simple_field {
-            ($stream:ident, $field:ident, $idx:ident, $format:literal) => {
-                write!(f, "    {:18}= ", stringify!($field))?;
-                match self.raw[$idx].$field() {
-                    Some($field) => {
-                        writeln!(f, $format, $field)?;
-                    }
-                    None => writeln!(f)?,
-                }
-            };
-            ($stream:ident, $field:ident, $idx:ident) => {
-                write_simple_field!($stream, $field, $idx, "{}");
+            let range = match raw.data_size {
+                0 => None,
+                size => raw
+                    .start_of_memory_range
+                    .checked_add(size - 1)
+                    .map(|last| Range::new(raw.start_of_memory_range, last)),
             };
+            ranges.push((range, (index, rva)));
+            rva = end;
         }
-        writeln!(f, "MINIDUMP_MAC_CRASH_INFO")?;
-        writeln!(f, "  num_records = {}", self.raw.len())?;
-
-        for i in 0..self.raw.len() {
-            writeln!(f)?;
-            writeln!(f, "  RECORD[{}] ", i)?;
-            write_simple_field!(f, version, i);
-            write_simple_field!(f, thread, i);
-            write_simple_field!(f, dialog_mode, i, "{:x}");
-            write_simple_field!(f, module_path, i);
-            write_simple_field!(f, message, i);
-            write_simple_field!(f, signature_string, i);
-            write_simple_field!(f, backtrace, i);
-            write_simple_field!(f, message2, i);
-            write_simple_field!(f, abort_cause, i, "{:x}");
-        }
-
-        writeln!(f)?;
-        Ok(())
-    }
-}
-
-impl<'a> MinidumpStream<'a> for MinidumpLinuxLsbRelease<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxLsbRelease as u32;
 
-    fn read(
-        bytes: &'a [u8],
-        _all: &'a [u8],
-        _endian: scroll::Endian,
-        _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpLinuxLsbRelease<'a>, Error> {
-        Ok(Self { data: bytes })
+        Ok(MinidumpMemory64List {
+            descriptors,
+            base_rva,
+            regions_by_addr: memory_regions_by_addr(ranges),
+            data: all,
+            endian,
+        })
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpLinuxEnviron<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxEnviron as u32;
+impl<'a> MinidumpStream<'a> for MinidumpMemoryInfoList<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MemoryInfoListStream as u32;
 
-    #[allow(clippy::single_match)]
     fn read(
         bytes: &'a [u8],
         _all: &'a [u8],
-        _endian: scroll::Endian,
+        endian: scroll::Endian,
         _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpLinuxEnviron<'a>, Error> {
-        Ok(Self { data: bytes })
+    ) -> Result<MinidumpMemoryInfoList<'a>, Error> {
+        let mut offset = 0;
+        let raw_regions: Vec<md::MINIDUMP_MEMORY_INFO> =
+            read_ex_stream_list(&mut offset, bytes, endian)?;
+        let regions = raw_regions
+            .into_iter()
+            .map(|raw| MinidumpMemoryInfo {
+                allocation_protection: md::MemoryProtection::from_bits_truncate(
+                    raw.allocation_protection,
+                ),
+                state: md::MemoryState::from_bits_truncate(raw.state),
+                protection: md::MemoryProtection::from_bits_truncate(raw.protection),
+                ty: md::MemoryType::from_bits_truncate(raw._type),
+                raw,
+                _phantom: PhantomData,
+            })
+            .collect();
+        Ok(MinidumpMemoryInfoList::from_regions(regions))
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpLinuxProcStatus<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxProcStatus as u32;
-
-    #[allow(clippy::single_match)]
-    fn read(
-        bytes: &'a [u8],
-        _all: &'a [u8],
-        _endian: scroll::Endian,
-        _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpLinuxProcStatus<'a>, Error> {
-        Ok(Self { data: bytes })
+impl<'a> Default for MinidumpMemoryInfoList<'a> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpLinuxCpuInfo<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxCpuInfo as u32;
-
-    fn read(
-        bytes: &'a [u8],
-        _all: &'a [u8],
-        _endian: scroll::Endian,
-        _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpLinuxCpuInfo<'a>, Error> {
-        Ok(Self { data: bytes })
+impl<'mdmp> MinidumpMemoryInfoList<'mdmp> {
+    /// Return an empty `MinidumpMemoryList`.
+    pub fn new() -> MinidumpMemoryInfoList<'mdmp> {
+        MinidumpMemoryInfoList {
+            regions: vec![],
+            regions_by_addr: RangeMap::new(),
+        }
     }
-}
 
-impl<'a> MinidumpLinuxCpuInfo<'a> {
-    /// Get an iterator over the key-value pairs stored in the `/proc/cpuinfo` dump.
-    ///
-    /// Keys and values are `trim`ed of leading/trailing spaces, and if a key
-    /// or value was surrounded by quotes ("like this"), the quotes will be
-    /// stripped.
-    pub fn iter(&self) -> impl Iterator<Item = (&'a LinuxOsStr, &'a LinuxOsStr)> {
-        linux_list_iter(self.data, b':')
+    /// Create a `MinidumpMemoryList` from a list of `MinidumpMemory`s.
+    pub fn from_regions(regions: Vec<MinidumpMemoryInfo<'mdmp>>) -> MinidumpMemoryInfoList<'mdmp> {
+        let regions_by_addr = regions
+            .iter()
+            .enumerate()
+            .map(|(i, region)| (region.memory_range(), i))
+            .into_rangemap_safe();
+        MinidumpMemoryInfoList {
+            regions,
+            regions_by_addr,
+        }
     }
 
-    /// Get the raw bytes of the `/proc/cpuinfo` dump.
-    pub fn raw_bytes(&self) -> Cow<'a, [u8]> {
-        Cow::Borrowed(self.data)
+    /// Return a `MinidumpMemory` containing memory at `address`, if one exists.
+    pub fn memory_info_at_address(&self, address: u64) -> Option<&MinidumpMemoryInfo<'mdmp>> {
+        self.regions_by_addr
+            .get(address)
+            .map(|&index| &self.regions[index])
     }
-}
 
-impl<'a> MinidumpLinuxEnviron<'a> {
-    /// Get an iterator over the key-value pairs stored in the `/proc/self/environ` dump.
+    /// Iterate over the memory regions in the order contained in the minidump.
     ///
-    /// Keys and values are `trim`ed of leading/trailing spaces, and if a key
-    /// or value was surrounded by quotes ("like this"), the quotes will be
-    /// stripped.
-    pub fn iter(&self) -> impl Iterator<Item = (&'a LinuxOsStr, &'a LinuxOsStr)> {
-        linux_list_iter(self.data, b'=')
+    /// The iterator returns items of [MinidumpMemory] as `&'slf MinidumpMemory<'mdmp>`.
+    /// That is the lifetime of the item is bound to the lifetime of the iterator itself
+    /// (`'slf`), while the slice inside [MinidumpMemory] pointing at the memory itself has
+    /// the lifetime of the [Minidump] struct ('mdmp).
+    pub fn iter<'slf>(&'slf self) -> impl Iterator<Item = &'slf MinidumpMemoryInfo<'mdmp>> {
+        self.regions.iter()
     }
 
-    /// Get the raw bytes of the `/proc/self/environ` dump.
-    pub fn raw_bytes(&self) -> Cow<'a, [u8]> {
-        Cow::Borrowed(self.data)
+    /// Iterate over the memory regions in order by memory address.
+    pub fn by_addr<'slf>(&'slf self) -> impl Iterator<Item = &'slf MinidumpMemoryInfo<'mdmp>> {
+        self.regions_by_addr
+            .ranges_values()
+            .map(move |&(_, index)| &self.regions[index])
     }
-}
 
-impl<'a> MinidumpLinuxProcStatus<'a> {
-    /// Get an iterator over the key-value pairs stored in the `/proc/self/status` dump.
-    ///
-    /// Keys and values are `trim`ed of leading/trailing spaces, and if a key
-    /// or value was surrounded by quotes ("like this"), the quotes will be
-    /// stripped.
-    pub fn iter(&self) -> impl Iterator<Item = (&'a LinuxOsStr, &'a LinuxOsStr)> {
-        linux_list_iter(self.data, b':')
-    }
+    /// Write a human-readable description.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MinidumpMemoryInfoList
+  region_count = {}
 
-    /// Get the raw bytes of the `/proc/self/status` dump.
-    pub fn raw_bytes(&self) -> Cow<'a, [u8]> {
-        Cow::Borrowed(self.data)
+",
+            self.regions.len()
+        )?;
+        for (i, region) in self.regions.iter().enumerate() {
+            writeln!(f, "region[{}]", i)?;
+            region.print(f)?;
+        }
+        Ok(())
     }
 }
 
-impl<'a> MinidumpLinuxLsbRelease<'a> {
-    /// Get an iterator over the key-value pairs stored in the `/etc/lsb-release` dump.
-    ///
-    /// Keys and values are `trim`ed of leading/trailing spaces, and if a key
-    /// or value was surrounded by quotes ("like this"), the quotes will be
-    /// stripped.
-    pub fn iter(&self) -> impl Iterator<Item = (&'a LinuxOsStr, &'a LinuxOsStr)> {
-        linux_list_iter(self.data, b'=')
+impl<'a> MinidumpMemoryInfo<'a> {
+    /// Write a human-readable description.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MINIDUMP_MEMORY_INFO
+  base_address          = {:#x}
+  allocation_base       = {:#x}
+  allocation_protection = {:#x}
+  region_size           = {:#x}
+  state                 = {:#x}
+  protection            = {:#x}
+  _type                 = {:#x}
+",
+            self.raw.base_address,
+            self.raw.allocation_base,
+            self.allocation_protection,
+            self.raw.region_size,
+            self.state,
+            self.protection,
+            self.ty,
+        )?;
+        writeln!(f)
     }
 
-    /// Get the raw bytes of the `/etc/lsb-release` dump.
-    pub fn raw_bytes(&self) -> Cow<'a, [u8]> {
-        Cow::Borrowed(self.data)
+    pub fn memory_range(&self) -> Option<Range<u64>> {
+        if self.raw.region_size == 0 {
+            return None;
+        }
+        Some(Range::new(
+            self.raw.base_address,
+            self.raw.base_address.checked_add(self.raw.region_size)? - 1,
+        ))
     }
-}
-
-fn systemtime_from_timestamp(timestamp: u64) -> Option<SystemTime> {
-    SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(timestamp))
-}
 
-impl MinidumpMiscInfo {
-    pub fn process_create_time(&self) -> Option<SystemTime> {
-        self.raw
-            .process_create_time()
-            .and_then(|t| systemtime_from_timestamp(*t as u64))
+    /// Whether this memory range was executable.
+    pub fn is_executable(&self) -> bool {
+        self.protection.intersects(
+            md::MemoryProtection::PAGE_EXECUTE
+                | md::MemoryProtection::PAGE_EXECUTE_READ
+                | md::MemoryProtection::PAGE_EXECUTE_READWRITE
+                | md::MemoryProtection::PAGE_EXECUTE_WRITECOPY,
+        )
     }
 
-    /// Write a human-readable description of this `MinidumpMiscInfo` to `f`.
-    ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        macro_rules! write_simple_field {
-            ($stream:ident, $field:ident, $format:literal) => {
-                write!(f, "  {:29}= ", stringify!($field))?;
-                match self.raw.$field() {
-                    Some($field) => {
-                        writeln!(f, $format, $field)?;
-                    }
-                    None => writeln!(f, "(invalid)")?,
-                }
-            };
-            ($stream:ident, $field:ident) => {
-                write_simple_field!($stream, $field, "{}");
-            };
-        }
-        writeln!(f, "MINIDUMP_MISC_INFO")?;
-
-        write_simple_field!(f, size_of_info);
-        write_simple_field!(f, flags1, "{:x}");
-        write_simple_field!(f, process_id);
-        write!(f, "  process_create_time          = ")?;
-        match self.raw.process_create_time() {
-            Some(&process_create_time) => {
-                writeln!(
-                    f,
-                    "{:#x} {}",
-                    process_create_time,
-                    format_time_t(process_create_time),
-                )?;
-            }
-            None => writeln!(f, "(invalid)")?,
-        }
-        write_simple_field!(f, process_user_time);
-        write_simple_field!(f, process_kernel_time);
-
-        write_simple_field!(f, processor_max_mhz);
-        write_simple_field!(f, processor_current_mhz);
-        write_simple_field!(f, processor_mhz_limit);
-        write_simple_field!(f, processor_max_idle_state);
-        write_simple_field!(f, processor_current_idle_state);
-
-        write_simple_field!(f, process_integrity_level);
-        write_simple_field!(f, process_execute_flags, "{:x}");
-        write_simple_field!(f, protected_process);
-        write_simple_field!(f, time_zone_id);
+    /// Whether this memory range was readable.
+    pub fn is_readable(&self) -> bool {
+        self.protection.intersects(
+            md::MemoryProtection::PAGE_READONLY
+                | md::MemoryProtection::PAGE_READWRITE
+                | md::MemoryProtection::PAGE_WRITECOPY
+                | md::MemoryProtection::PAGE_EXECUTE_READ
+                | md::MemoryProtection::PAGE_EXECUTE_READWRITE
+                | md::MemoryProtection::PAGE_EXECUTE_WRITECOPY,
+        )
+    }
 
-        write!(f, "  time_zone                    = ")?;
-        match self.raw.time_zone() {
-            Some(time_zone) => {
-                writeln!(f)?;
-                writeln!(f, "    bias          = {}", time_zone.bias)?;
-                writeln!(
-                    f,
-                    "    standard_name = {}",
-                    utf16_to_string(&time_zone.standard_name[..])
-                        .unwrap_or_else(|| String::from("(invalid)"))
-                )?;
-                writeln!(
-                    f,
-                    "    standard_date = {}",
-                    format_system_time(&time_zone.standard_date)
-                )?;
-                writeln!(f, "    standard_bias = {}", time_zone.standard_bias)?;
-                writeln!(
-                    f,
-                    "    daylight_name = {}",
-                    utf16_to_string(&time_zone.daylight_name[..])
-                        .unwrap_or_else(|| String::from("(invalid)"))
-                )?;
-                writeln!(
-                    f,
-                    "    daylight_date = {}",
-                    format_system_time(&time_zone.daylight_date)
-                )?;
-                writeln!(f, "    daylight_bias = {}", time_zone.daylight_bias)?;
-            }
-            None => writeln!(f, "(invalid)")?,
-        }
+    /// Whether this memory range was writable.
+    pub fn is_writable(&self) -> bool {
+        self.protection.intersects(
+            md::MemoryProtection::PAGE_READWRITE
+                | md::MemoryProtection::PAGE_WRITECOPY
+                | md::MemoryProtection::PAGE_EXECUTE_READWRITE
+                | md::MemoryProtection::PAGE_EXECUTE_WRITECOPY,
+        )
+    }
 
-        write!(f, "  build_string                 = ")?;
-        match self
-            .raw
-            .build_string()
-            .and_then(|string| utf16_to_string(&string[..]))
+    /// What this memory range was used for.
+    ///
+    /// Private memory is only known to be a stack if it overlaps the stack
+    /// of one of `threads`.
+    pub fn kind(&self, threads: Option<&MinidumpThreadList>) -> MemoryKind {
+        if self
+            .state
+            .intersects(md::MemoryState::MEM_FREE | md::MemoryState::MEM_RESERVE)
         {
-            Some(build_string) => writeln!(f, "{}", build_string)?,
-            None => writeln!(f, "(invalid)")?,
-        }
-        write!(f, "  dbg_bld_str                  = ")?;
-        match self
-            .raw
-            .dbg_bld_str()
-            .and_then(|string| utf16_to_string(&string[..]))
+            MemoryKind::Unmapped
+        } else if self.protection.contains(md::MemoryProtection::PAGE_GUARD)
+            || self.protection & md::MemoryProtection::ACCESS_MASK
+                == md::MemoryProtection::PAGE_NOACCESS
         {
-            Some(dbg_bld_str) => writeln!(f, "{}", dbg_bld_str)?,
-            None => writeln!(f, "(invalid)")?,
-        }
-
-        write!(f, "  xstate_data                  = ")?;
-        match self.raw.xstate_data() {
-            Some(xstate_data) => {
-                writeln!(f)?;
-                for (i, feature) in xstate_data.iter() {
-                    if let Some(feature) = md::XstateFeatureIndex::from_index(i) {
-                        let feature_name = format!("{:?}", feature);
-                        write!(f, "    feature {:2} - {:22}: ", i, feature_name)?;
-                    } else {
-                        write!(f, "    feature {:2} - (unknown)           : ", i)?;
-                    }
-                    writeln!(f, " offset {:4}, size {:4}", feature.offset, feature.size)?;
-                }
+            MemoryKind::Guard
+        } else if self
+            .ty
+            .intersects(md::MemoryType::MEM_IMAGE | md::MemoryType::MEM_MAPPED)
+        {
+            MemoryKind::MappedFile(None)
+        } else if let Some(thread_id) = stack_owner(self.memory_range(), threads) {
+            MemoryKind::Stack {
+                thread_id: Some(thread_id),
             }
-            None => writeln!(f, "(invalid)")?,
+        } else {
+            MemoryKind::Anonymous
         }
-
-        write_simple_field!(f, process_cookie);
-        writeln!(f)?;
-        Ok(())
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpBreakpadInfo {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::BreakpadInfoStream as u32;
+impl<'a> MinidumpStream<'a> for MinidumpLinuxMaps<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxMaps as u32;
 
     fn read(
-        bytes: &[u8],
-        _all: &[u8],
-        endian: scroll::Endian,
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        _endian: scroll::Endian,
         _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpBreakpadInfo, Error> {
-        let raw: md::MINIDUMP_BREAKPAD_INFO = bytes
-            .pread_with(0, endian)
-            .or(Err(Error::StreamReadFailure))?;
-        let flags = md::BreakpadInfoValid::from_bits_truncate(raw.validity);
-        let dump_thread_id = if flags.contains(md::BreakpadInfoValid::DumpThreadId) {
-            Some(raw.dump_thread_id)
-        } else {
-            None
-        };
-        let requesting_thread_id = if flags.contains(md::BreakpadInfoValid::RequestingThreadId) {
-            Some(raw.requesting_thread_id)
-        } else {
-            None
-        };
-        Ok(MinidumpBreakpadInfo {
-            raw,
-            dump_thread_id,
-            requesting_thread_id,
-        })
+    ) -> Result<MinidumpLinuxMaps<'a>, Error> {
+        let mut regions: Vec<MinidumpLinuxMapInfo> = Vec::new();
+        for line in LinuxOsStr::from_bytes(bytes).lines() {
+            // In smaps format, every mapping is followed by lines of extra
+            // details. VmFlags is the only one we keep, and the rest don't
+            // parse as mappings.
+            if let Some(flags) = line.trim_ascii_whitespace().strip_prefix(b"VmFlags:") {
+                if let Some(region) = regions.last_mut() {
+                    region.vm_flags = Some(Cow::Borrowed(
+                        LinuxOsStr::from_bytes(flags).trim_ascii_whitespace(),
+                    ));
+                }
+            } else if let Ok(region) = MinidumpLinuxMapInfo::from_line(line) {
+                regions.push(region);
+            }
+        }
+
+        Ok(MinidumpLinuxMaps::from_regions(regions))
     }
 }
 
-fn option_or_invalid<T: fmt::LowerHex>(what: &Option<T>) -> Cow<'_, str> {
-    match *what {
-        Some(ref val) => Cow::Owned(format!("{:#x}", val)),
-        None => Cow::Borrowed("(invalid)"),
+impl<'a> Default for MinidumpLinuxMaps<'a> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl MinidumpBreakpadInfo {
-    /// Write a human-readable description of this `MinidumpBreakpadInfo` to `f`.
-    ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        write!(
-            f,
-            "MINIDUMP_BREAKPAD_INFO
-  validity             = {:#x}
-  dump_thread_id       = {}
-  requesting_thread_id = {}
-
-",
-            self.raw.validity,
-            option_or_invalid(&self.dump_thread_id),
-            option_or_invalid(&self.requesting_thread_id),
-        )?;
-        Ok(())
+impl<'mdmp> MinidumpLinuxMaps<'mdmp> {
+    /// Return an empty `MinidumpMemoryList`.
+    pub fn new() -> Self {
+        Self {
+            regions: vec![],
+            regions_by_addr: RangeMap::new(),
+        }
     }
-}
 
-impl CrashReason {
-    /// Get a `CrashReason` from a `MINIDUMP_EXCEPTION_STREAM` for a given `Os`.
-    fn from_exception(raw: &md::MINIDUMP_EXCEPTION_STREAM, os: Os, cpu: Cpu) -> CrashReason {
-        let record = &raw.exception_record;
-        let exception_code = record.exception_code;
-        let exception_flags = record.exception_flags;
+    /// Create a `MinidumpMemoryList` from a list of `MinidumpMemory`s.
+    pub fn from_regions(regions: Vec<MinidumpLinuxMapInfo<'mdmp>>) -> Self {
+        let regions_by_addr = regions
+            .iter()
+            .enumerate()
+            .map(|(i, region)| (region.memory_range(), i))
+            .into_rangemap_safe();
+        Self {
+            regions,
+            regions_by_addr,
+        }
+    }
 
-        let reason = match os {
-            Os::MacOs | Os::Ios => Self::from_mac_exception(raw, cpu),
-            Os::Linux | Os::Android => Self::from_linux_exception(raw, cpu),
-            Os::Windows => Self::from_windows_exception(raw, cpu),
-            _ => None,
-        };
+    /// Return a `MinidumpMemory` containing memory at `address`, if one exists.
+    pub fn memory_info_at_address(&self, address: u64) -> Option<&MinidumpLinuxMapInfo<'mdmp>> {
+        self.regions_by_addr
+            .get(address)
+            .map(|&index| &self.regions[index])
+    }
 
-        // Default to a totally generic unknown error
-        reason.unwrap_or(CrashReason::Unknown(exception_code, exception_flags))
+    /// Iterate over the memory regions in the order contained in the minidump.
+    pub fn iter<'slf>(&'slf self) -> impl Iterator<Item = &'slf MinidumpLinuxMapInfo<'mdmp>> {
+        self.regions.iter()
     }
 
-    /// Heuristically identifies what kind of windows exception code this is.
-    ///
-    /// Augments [`CrashReason::from_windows_error`] by also including
-    /// `ExceptionCodeWindows`. Appropriate for an actual crash reason.
-    pub fn from_windows_code(exception_code: u32) -> CrashReason {
-        if let Some(err) = err::ExceptionCodeWindows::from_u32(exception_code) {
-            Self::WindowsGeneral(err)
-        } else {
-            Self::from_windows_error(exception_code)
-        }
+    /// Iterate over the memory regions in order by memory address.
+    pub fn by_addr<'slf>(&'slf self) -> impl Iterator<Item = &'slf MinidumpLinuxMapInfo<'mdmp>> {
+        self.regions_by_addr
+            .ranges_values()
+            .map(move |&(_, index)| &self.regions[index])
     }
 
-    /// Heuristically identifies what kind of windows error code this is.
-    ///
-    /// Appropriate for things like LastErrorValue() which may be non-fatal.
-    pub fn from_windows_error(error_code: u32) -> CrashReason {
-        if let Some(err) = err::WinErrorWindows::from_u32(error_code) {
-            Self::WindowsWinError(err)
-        } else if let Some(err) = err::NtStatusWindows::from_u32(error_code) {
-            Self::WindowsNtStatus(err)
-        } else if let Some(err) = Self::from_windows_error_with_facility(error_code) {
-            err
-        } else {
-            Self::WindowsUnknown(error_code)
+    /// Write a human-readable description.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MinidumpLinuxMapInfo
+  region_count = {}
+
+",
+            self.regions.len()
+        )?;
+        for (i, region) in self.regions.iter().enumerate() {
+            writeln!(f, "region[{}]", i)?;
+            region.print(f)?;
         }
+        Ok(())
     }
+}
 
-    pub fn from_windows_error_with_facility(error_code: u32) -> Option<CrashReason> {
-        static SEVERITY_MASK: u32 = 0xf0000000;
-        static FACILITY_MASK: u32 = 0x0fff0000;
-        static ERROR_MASK: u32 = 0x0000ffff;
+impl<'a> MinidumpLinuxMapInfo<'a> {
+    /// Parses a line from /proc/self/maps into a `[MinidumpLinuxMapInfo]`.
+    pub fn from_line(line: &'a LinuxOsStr) -> Result<MinidumpLinuxMapInfo<'a>, Error> {
+        // /proc/self/maps is a listing of all the mapped ranges of memory
+        // in the (crashing) process. We can use it to find out what regions
+        // were executable (and what file they mapped to), where stacks/heap
+        // are mapped, and various other things.
+        //
+        // Format of each line (by examples):
+        //
+        // ```text
+        //
+        //        address        perms   offset   dev   inode       path/kind         deleted?
+        // 7fca3a80c0-7fca3a81a0  r-xp  10bac9000 fd:05 1196511 /usr/lib64/libtdb.so  (deleted)
+        // 7ffe2e7910-7ffe2e7b10  rw-p  000000000 00:00 0       [stack]
+        //
+        // ```
+        //
+        // * address: the start and end addresses (inclusive)
+        // * perms: permissions the process had on the memory
+        //   * r = read
+        //   * w = write
+        //   * x = execute
+        //   * s = shared
+        //   * p = private (copy on write)
+        //   * - = <ignore> (just for formatting)
+        // * offset: the offset this mapping has into the mapped file/device. (ignored)
+        // * dev: the "device" (major:minor). (ignored)
+        // * inode: the inode on the device, 0 means no inode (uninitialized memory?). (ignored)
+        // * path/kind: either a path to the mapped file/device or a special `[kind]`:
+        //   * `[stack]`       - the main thread's stack
+        //   * `[stack:<tid>]` - the stack of the thread with this tid (e.g. `[stack:123]`)
+        //   * `[heap]`        - the process's heap
+        //   * `[vdso]`        - the Virtual Dynamically-linked Shared Object
+        //   * `[anon:<name>]` - a named anonymous mmap (the name may contain spaces)
+        //   * `<blank>`       - an anonymous mmap
+        //
+        // A path has a few extra caveats:
+        //  * If suffixed with `(deleted)` that indicates the mapped file was deleted.
+        //  * If the path contains a newline (yikes), it will appear as \012. It is impossible
+        //    to distinguish this from the path literally containing the string `\012`.
+        //    (We aren't resolving these paths so don't worry about it.)
 
-        if (error_code & SEVERITY_MASK) != 0 {
-            // This could be an NTSTATUS or HRESULT code of a specific facility
-            if let Some(facility) =
-                err::WinErrorFacilityWindows::from_u32((error_code & FACILITY_MASK) >> 16)
-            {
-                if let Some(error) = err::WinErrorWindows::from_u32(error_code & ERROR_MASK) {
-                    return Some(Self::WindowsWinErrorWithFacility(facility, error));
-                }
-            }
-        }
+        let mut tokens = line.split_ascii_whitespace();
 
-        None
-    }
+        let (base_address, final_address) = tokens
+            .next()
+            .and_then(|range| range.split_once(b'-'))
+            .and_then(|(start, end)| {
+                // Parsing numbers, so can require them to be utf8
+                let start = start
+                    .to_str()
+                    .ok()
+                    .and_then(|x| u64::from_str_radix(x, 16).ok());
 
-    pub fn from_windows_exception(
-        raw: &md::MINIDUMP_EXCEPTION_STREAM,
-        _cpu: Cpu,
-    ) -> Option<CrashReason> {
-        use err::ExceptionCodeWindows;
+                let end = end
+                    .to_str()
+                    .ok()
+                    .and_then(|x| u64::from_str_radix(x, 16).ok());
 
-        let record = &raw.exception_record;
-        let info = &record.exception_information;
-        let exception_code = record.exception_code;
+                start.zip(end)
+            })
+            .ok_or(Error::DataError)?;
+
+        let empty = LinuxOsStr::new();
+        let perms = tokens.next().unwrap_or(empty);
 
-        let mut reason = CrashReason::from_windows_code(exception_code);
+        let mut is_read = false;
+        let mut is_write = false;
+        let mut is_exec = false;
+        let mut is_shared = false;
+        let mut is_private = false;
 
-        // Refine the output for error codes that have more info
-        match reason {
-            CrashReason::WindowsGeneral(ExceptionCodeWindows::EXCEPTION_ACCESS_VIOLATION) => {
-                // For EXCEPTION_ACCESS_VIOLATION, Windows puts the address that
-                // caused the fault in exception_information[1].
-                // exception_information[0] is 0 if the violation was caused by
-                // an attempt to read data, 1 if it was an attempt to write data,
-                // and 8 if this was a data execution violation.
-                // This information is useful in addition to the code address, which
-                // will be present in the crash thread's instruction field anyway.
-                if record.number_parameters >= 1 {
-                    // NOTE: address := info[1];
-                    if let Some(ty) = err::ExceptionCodeWindowsAccessType::from_u64(info[0]) {
-                        reason = CrashReason::WindowsAccessViolation(ty);
-                    }
-                }
-            }
-            CrashReason::WindowsGeneral(ExceptionCodeWindows::EXCEPTION_IN_PAGE_ERROR) => {
-                // For EXCEPTION_IN_PAGE_ERROR, Windows puts the address that
-                // caused the fault in exception_information[1].
-                // exception_information[0] is 0 if the violation was caused by
-                // an attempt to read data, 1 if it was an attempt to write data,
-                // and 8 if this was a data execution violation.
-                // exception_information[2] contains the underlying NTSTATUS code,
-                // which is the explanation for why this error occured.
-                // This information is useful in addition to the code address, which
-                // will be present in the crash thread's instruction field anyway.
-                if record.number_parameters >= 3 {
-                    // NOTE: address := info[1];
-                    let nt_status = info[2];
-                    if let Some(ty) = err::ExceptionCodeWindowsInPageErrorType::from_u64(info[0]) {
-                        reason = CrashReason::WindowsInPageError(ty, nt_status);
-                    }
-                }
-            }
-            CrashReason::WindowsNtStatus(err::NtStatusWindows::STATUS_STACK_BUFFER_OVERRUN) => {
-                // STATUS_STACK_BUFFER_OVERRUN are caused by __fastfail()
-                // invocations and the fast-fail code is stored in
-                // exception_information[0].
-                if record.number_parameters >= 1 {
-                    let fast_fail = info[0];
-                    reason = CrashReason::WindowsStackBufferOverrun(fast_fail);
+        // Although some of these are mutually exclusive and they come in a specific
+        // order, I see no reason to mandate this in this parser.
+        for c in perms.iter() {
+            match &c {
+                b'r' => is_read = true,
+                b'w' => is_write = true,
+                b'x' => is_exec = true,
+                b's' => is_shared = true,
+                b'p' => is_private = true,
+                b'-' => {}
+                _ => {
+                    // This shouldn't happen. That said, there's no obvious reason
+                    // to fail the entire parse if there's new info we don't know about,
+                    // so I suppose it's fine?
                 }
             }
-            _ => {
-                // Do nothing interesting
-            }
         }
 
-        Some(reason)
-    }
-
-    pub fn from_mac_exception(
-        raw: &md::MINIDUMP_EXCEPTION_STREAM,
-        cpu: Cpu,
-    ) -> Option<CrashReason> {
-        use err::ExceptionCodeMac;
-
-        let record = &raw.exception_record;
-        let info = &record.exception_information;
-        let exception_code = record.exception_code;
-        let exception_flags = record.exception_flags;
-
-        // Default to just directly reporting this reason.
-        let mac_reason = err::ExceptionCodeMac::from_u32(exception_code)?;
-        let mut reason = CrashReason::MacGeneral(mac_reason, exception_flags);
+        // We don't care about these values
+        let _offset = tokens.next();
+        let _dev = tokens.next();
+        let _inode = tokens.next();
 
-        // Refine the output for error codes that have more info
-        match mac_reason {
-            ExceptionCodeMac::EXC_BAD_ACCESS => {
-                if let Some(ty) = err::ExceptionCodeMacBadAccessKernType::from_u32(exception_flags)
+        let kind = tokens.next();
+        let kind = match kind.map(|x| x.as_bytes()) {
+            Some(b"[stack]") => MinidumpLinuxMapKind::MainThreadStack,
+            Some(b"[heap]") => MinidumpLinuxMapKind::Heap,
+            Some(b"[vdso]") => MinidumpLinuxMapKind::Vdso,
+            Some(b"") | None => MinidumpLinuxMapKind::AnonymousMap,
+            Some(_) => {
+                // Go back to the LinuxOsStr
+                let kind = kind.unwrap();
+                // Everything from the kind to the end of the line, since paths
+                // and anonymous map names may contain whitespace.
+                let rest = {
+                    let base = line.as_ptr() as usize;
+                    let pos = kind.as_ptr() as usize;
+                    LinuxOsStr::from_bytes(&line[pos - base..]).trim_ascii_whitespace()
+                };
+                // Try to parse an arbitrary [<special>]
+                if let Some(name) = rest
+                    .strip_prefix(b"[anon:")
+                    .and_then(|x| x.strip_suffix(b"]"))
                 {
-                    reason = CrashReason::MacBadAccessKern(ty);
-                } else {
-                    match cpu {
-                        Cpu::Arm64 => {
-                            if let Some(ty) =
-                                err::ExceptionCodeMacBadAccessArmType::from_u32(exception_flags)
-                            {
-                                reason = CrashReason::MacBadAccessArm(ty);
-                            }
-                        }
-                        Cpu::Ppc => {
-                            if let Some(ty) =
-                                err::ExceptionCodeMacBadAccessPpcType::from_u32(exception_flags)
-                            {
-                                reason = CrashReason::MacBadAccessPpc(ty);
-                            }
-                        }
-                        Cpu::X86 | Cpu::X86_64 => {
-                            if let Some(ty) =
-                                err::ExceptionCodeMacBadAccessX86Type::from_u32(exception_flags)
-                            {
-                                reason = CrashReason::MacBadAccessX86(ty);
-                            }
-                        }
-                        _ => {
-                            // Do nothing
-                        }
+                    MinidumpLinuxMapKind::NamedAnonymousMap(Cow::Borrowed(LinuxOsStr::from_bytes(
+                        name,
+                    )))
+                } else if let Some(special) = kind
+                    .to_str()
+                    .ok()
+                    .and_then(|x| x.strip_prefix('['))
+                    .and_then(|x| x.strip_suffix(']'))
+                {
+                    // See if it's a [stack:<tid>] special entry
+                    if let Some(tid) = special.strip_prefix("stack:") {
+                        // As far as I know, this part *is* base 10!
+                        let tid = str::parse(tid).map_err(|_| Error::DataError)?;
+                        MinidumpLinuxMapKind::Stack(tid)
+                    } else {
+                        MinidumpLinuxMapKind::UnknownSpecial(Cow::Borrowed(kind))
                     }
+                } else {
+                    // Finally just assume it's a path.
+                    let path = rest;
+
+                    // Check if this path has the `(deleted)` suffix
+                    path.rsplit_once(b' ')
+                        .and_then(|(path, deleted)| {
+                            if deleted.as_bytes() == b"(deleted)" {
+                                // (extra trim in case there was extra space between them)
+                                Some(MinidumpLinuxMapKind::DeletedFile(Cow::Borrowed(
+                                    path.trim_ascii_whitespace(),
+                                )))
+                            } else {
+                                None
+                            }
+                        })
+                        .unwrap_or(MinidumpLinuxMapKind::File(Cow::Borrowed(path)))
                 }
             }
-            ExceptionCodeMac::EXC_BAD_INSTRUCTION => match cpu {
-                Cpu::Arm64 => {
-                    if let Some(ty) =
-                        err::ExceptionCodeMacBadInstructionArmType::from_u32(exception_flags)
-                    {
-                        reason = CrashReason::MacBadInstructionArm(ty);
-                    }
-                }
-                Cpu::Ppc => {
-                    if let Some(ty) =
-                        err::ExceptionCodeMacBadInstructionPpcType::from_u32(exception_flags)
-                    {
-                        reason = CrashReason::MacBadInstructionPpc(ty);
-                    }
-                }
-                Cpu::X86 | Cpu::X86_64 => {
-                    if let Some(ty) =
-                        err::ExceptionCodeMacBadInstructionX86Type::from_u32(exception_flags)
-                    {
-                        reason = CrashReason::MacBadInstructionX86(ty);
-                    }
-                }
-                _ => {
-                    // Do nothing
-                }
+        };
+
+        Ok(MinidumpLinuxMapInfo {
+            base_address,
+            final_address,
+            kind,
+            is_read,
+            is_write,
+            is_exec,
+            is_private,
+            is_shared,
+            vm_flags: None,
+            _phantom: PhantomData,
+        })
+    }
+    /// Write a human-readable description of this.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MINIDUMP_LINUX_MAP_INFO
+  base_address          = {:#x}
+  final_address         = {:#x}
+  kind                  = {:#?}
+  permissions           =\x20
+",
+            self.base_address, self.final_address, self.kind,
+        )?;
+
+        if self.is_read {
+            write!(f, "r")?;
+        } else {
+            write!(f, "-")?;
+        }
+        if self.is_write {
+            write!(f, "w")?;
+        } else {
+            write!(f, "-")?;
+        }
+        if self.is_exec {
+            write!(f, "r")?;
+        } else {
+            write!(f, "-")?;
+        }
+        if self.is_private {
+            write!(f, "p")?;
+        } else if self.is_shared {
+            write!(f, "s")?;
+        } else {
+            write!(f, "-")?;
+        }
+        writeln!(f)?;
+        if let Some(vm_flags) = &self.vm_flags {
+            writeln!(
+                f,
+                "  vm_flags              = {}",
+                vm_flags.to_string_lossy()
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn memory_range(&self) -> Option<Range<u64>> {
+        // final address is inclusive afaik
+        if self.base_address > self.final_address {
+            return None;
+        }
+        Some(Range::new(self.base_address, self.final_address))
+    }
+
+    /// Whether this memory range was executable.
+    pub fn is_executable(&self) -> bool {
+        self.is_exec
+    }
+
+    /// Whether this memory range was readable.
+    pub fn is_readable(&self) -> bool {
+        self.is_read
+    }
+
+    /// Whether this memory range was writable.
+    pub fn is_writable(&self) -> bool {
+        self.is_write
+    }
+
+    /// Whether the region's `VmFlags` include `flag` (like `"gd"` or `"mw"`).
+    ///
+    /// Always false if the stream doesn't have `VmFlags`.
+    pub fn has_vm_flag(&self, flag: &str) -> bool {
+        matches!(&self.vm_flags, Some(flags) if flags
+            .split_ascii_whitespace()
+            .any(|f| f.as_bytes() == flag.as_bytes()))
+    }
+
+    /// What this memory range was used for.
+    ///
+    /// Anonymous mappings are only known to be a stack if they overlap the
+    /// stack of one of `threads`.
+    pub fn kind(&self, threads: Option<&MinidumpThreadList>) -> MemoryKind {
+        let inaccessible = !self.is_read && !self.is_write && !self.is_exec;
+        match &self.kind {
+            MinidumpLinuxMapKind::MainThreadStack => MemoryKind::Stack {
+                thread_id: stack_owner(self.memory_range(), threads),
             },
-            ExceptionCodeMac::EXC_ARITHMETIC => match cpu {
-                Cpu::Arm64 => {
-                    if let Some(ty) =
-                        err::ExceptionCodeMacArithmeticArmType::from_u32(exception_flags)
-                    {
-                        reason = CrashReason::MacArithmeticArm(ty);
-                    }
-                }
-                Cpu::Ppc => {
-                    if let Some(ty) =
-                        err::ExceptionCodeMacArithmeticPpcType::from_u32(exception_flags)
-                    {
-                        reason = CrashReason::MacArithmeticPpc(ty);
-                    }
-                }
-                Cpu::X86 | Cpu::X86_64 => {
-                    if let Some(ty) =
-                        err::ExceptionCodeMacArithmeticX86Type::from_u32(exception_flags)
-                    {
-                        reason = CrashReason::MacArithmeticX86(ty);
-                    }
-                }
-                _ => {
-                    // Do nothing
-                }
+            MinidumpLinuxMapKind::Stack(tid) => MemoryKind::Stack {
+                thread_id: (*tid).try_into().ok(),
             },
-            ExceptionCodeMac::EXC_SOFTWARE => {
-                if let Some(ty) = err::ExceptionCodeMacSoftwareType::from_u32(exception_flags) {
-                    reason = CrashReason::MacSoftware(ty);
-                }
+            MinidumpLinuxMapKind::Heap => MemoryKind::Heap,
+            MinidumpLinuxMapKind::Vdso => MemoryKind::MappedFile(Some(String::from("[vdso]"))),
+            MinidumpLinuxMapKind::UnknownSpecial(name) => {
+                MemoryKind::MappedFile(Some(name.to_string_lossy().into_owned()))
             }
-            ExceptionCodeMac::EXC_BREAKPOINT => match cpu {
-                Cpu::Arm64 => {
-                    if let Some(ty) =
-                        err::ExceptionCodeMacBreakpointArmType::from_u32(exception_flags)
-                    {
-                        reason = CrashReason::MacBreakpointArm(ty);
-                    }
-                }
-                Cpu::Ppc => {
-                    if let Some(ty) =
-                        err::ExceptionCodeMacBreakpointPpcType::from_u32(exception_flags)
-                    {
-                        reason = CrashReason::MacBreakpointPpc(ty);
-                    }
-                }
-                Cpu::X86 | Cpu::X86_64 => {
-                    if let Some(ty) =
-                        err::ExceptionCodeMacBreakpointX86Type::from_u32(exception_flags)
-                    {
-                        reason = CrashReason::MacBreakpointX86(ty);
-                    }
-                }
-                _ => {
-                    // Do nothing
-                }
-            },
-            ExceptionCodeMac::EXC_RESOURCE => {
-                if let Some(ty) =
-                    err::ExceptionCodeMacResourceType::from_u32((exception_flags >> 29) & 0x7)
-                {
-                    reason = CrashReason::MacResource(ty, info[1], info[2]);
-                }
+            MinidumpLinuxMapKind::File(_) | MinidumpLinuxMapKind::DeletedFile(_)
+                if inaccessible =>
+            {
+                MemoryKind::Guard
             }
-            ExceptionCodeMac::EXC_GUARD => {
-                if let Some(ty) =
-                    err::ExceptionCodeMacGuardType::from_u32((exception_flags >> 29) & 0x7)
-                {
-                    reason = CrashReason::MacGuard(ty, info[1], info[2]);
+            MinidumpLinuxMapKind::File(path) | MinidumpLinuxMapKind::DeletedFile(path) => {
+                MemoryKind::MappedFile(Some(path.to_string_lossy().into_owned()))
+            }
+            MinidumpLinuxMapKind::AnonymousMap | MinidumpLinuxMapKind::NamedAnonymousMap(_) => {
+                if inaccessible {
+                    MemoryKind::Guard
+                } else if let Some(thread_id) = stack_owner(self.memory_range(), threads) {
+                    MemoryKind::Stack {
+                        thread_id: Some(thread_id),
+                    }
+                } else {
+                    MemoryKind::Anonymous
                 }
             }
-            _ => {
-                // Do nothing
+        }
+    }
+}
+
+impl<'a> Default for UnifiedMemoryInfoList<'a> {
+    fn default() -> Self {
+        Self::Info(MinidumpMemoryInfoList::default())
+    }
+}
+
+impl<'a> UnifiedMemoryInfoList<'a> {
+    /// Take two potential memory info sources and create an interface that unifies them.
+    ///
+    /// Under normal circumstances a minidump should only contain one of these.
+    /// If both are provided, one will be arbitrarily preferred to attempt to
+    /// make progress.
+    pub fn new(
+        info: Option<MinidumpMemoryInfoList<'a>>,
+        maps: Option<MinidumpLinuxMaps<'a>>,
+    ) -> Option<Self> {
+        match (info, maps) {
+            (Some(info), Some(_maps)) => {
+                warn!("UnifiedMemoryInfoList got both kinds of info! (using InfoList)");
+                // Just pick one I guess?
+                Some(Self::Info(info))
             }
+            (Some(info), None) => Some(Self::Info(info)),
+            (None, Some(maps)) => Some(Self::Maps(maps)),
+            (None, None) => None,
         }
-        Some(reason)
     }
 
-    pub fn from_linux_exception(
-        raw: &md::MINIDUMP_EXCEPTION_STREAM,
-        _cpu: Cpu,
-    ) -> Option<CrashReason> {
-        let record = &raw.exception_record;
-        let exception_code = record.exception_code;
-        let exception_flags = record.exception_flags;
+    /// Return a `MinidumpMemory` containing memory at `address`, if one exists.
+    pub fn memory_info_at_address(&self, address: u64) -> Option<UnifiedMemoryInfo> {
+        match self {
+            Self::Info(info) => info
+                .memory_info_at_address(address)
+                .map(UnifiedMemoryInfo::Info),
+            Self::Maps(maps) => maps
+                .memory_info_at_address(address)
+                .map(UnifiedMemoryInfo::Map),
+        }
+    }
+
+    /// What the memory at `address` was used for, see [`UnifiedMemoryInfo::kind`].
+    ///
+    /// Addresses that aren't in any region are [`MemoryKind::Unmapped`].
+    pub fn memory_kind_at_address(
+        &self,
+        address: u64,
+        threads: Option<&MinidumpThreadList>,
+    ) -> MemoryKind {
+        self.memory_info_at_address(address)
+            .map_or(MemoryKind::Unmapped, |info| info.kind(threads))
+    }
+
+    /// Iterate over the memory regions in the order contained in the minidump.
+    pub fn iter(&self) -> impl Iterator<Item = UnifiedMemoryInfo> {
+        // Use `flat_map` and `chain` to create a unified stream of the two types
+        // (only one of which will conatin any values). Note that we are using
+        // the fact that `Option` can be iterated (producing 1 to 0 values).
+        let info = self
+            .info()
+            .into_iter()
+            .flat_map(|info| info.iter().map(UnifiedMemoryInfo::Info));
+        let maps = self
+            .maps()
+            .into_iter()
+            .flat_map(|maps| maps.iter().map(UnifiedMemoryInfo::Map));
+
+        info.chain(maps)
+    }
+
+    /// Iterate over the memory regions in order by memory address.
+    pub fn by_addr(&self) -> impl Iterator<Item = UnifiedMemoryInfo> {
+        let info = self
+            .info()
+            .into_iter()
+            .flat_map(|info| info.by_addr().map(UnifiedMemoryInfo::Info));
+        let maps = self
+            .maps()
+            .into_iter()
+            .flat_map(|maps| maps.by_addr().map(UnifiedMemoryInfo::Map));
+
+        info.chain(maps)
+    }
+
+    /// Write a human-readable description of this `MinidumpMemoryList` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        match self {
+            Self::Info(info) => info.print(f),
+            Self::Maps(maps) => maps.print(f),
+        }
+    }
+
+    /// Get the [`MinidumpLinuxMaps`] contained inside, if it exists.
+    ///
+    /// Potentially useful for doing a more refined analysis in specific places.
+    pub fn maps(&self) -> Option<&MinidumpLinuxMaps<'a>> {
+        match &self {
+            Self::Maps(maps) => Some(maps),
+            Self::Info(_) => None,
+        }
+    }
+
+    /// Get the [`MinidumpMemoryInfoList`] contained inside, if it exists.
+    ///
+    /// Potentially useful for doing a more refined analysis in specific places.
+    pub fn info(&self) -> Option<&MinidumpMemoryInfoList<'a>> {
+        match &self {
+            Self::Maps(_) => None,
+            Self::Info(info) => Some(info),
+        }
+    }
+}
+
+impl<'a> UnifiedMemoryInfo<'a> {
+    /// Write a human-readable description.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        match self {
+            Self::Info(info) => info.print(f),
+            Self::Map(map) => map.print(f),
+        }
+    }
+
+    /// The range of memory this info applies to.
+    pub fn memory_range(&self) -> Option<Range<u64>> {
+        match self {
+            Self::Info(info) => info.memory_range(),
+            Self::Map(map) => map.memory_range(),
+        }
+    }
+
+    /// Whether this memory range was executable.
+    pub fn is_executable(&self) -> bool {
+        match self {
+            Self::Info(info) => info.is_executable(),
+            Self::Map(map) => map.is_executable(),
+        }
+    }
+
+    /// Whether this memory range was readable.
+    pub fn is_readable(&self) -> bool {
+        match self {
+            Self::Info(info) => info.is_readable(),
+            Self::Map(map) => map.is_readable(),
+        }
+    }
+
+    /// Whether this memory range was writable.
+    pub fn is_writable(&self) -> bool {
+        match self {
+            Self::Info(info) => info.is_writable(),
+            Self::Map(map) => map.is_writable(),
+        }
+    }
+
+    /// What this memory range was used for.
+    ///
+    /// `threads` is used to find which thread's stack a region is, when the
+    /// memory info doesn't say.
+    pub fn kind(&self, threads: Option<&MinidumpThreadList>) -> MemoryKind {
+        match self {
+            Self::Info(info) => info.kind(threads),
+            Self::Map(map) => map.kind(threads),
+        }
+    }
+}
+
+impl<'a> MinidumpThread<'a> {
+    /// Get this thread's extra information (like its start address) from `thread_infos`.
+    pub fn info<'b>(
+        &self,
+        thread_infos: &'b MinidumpThreadInfoList,
+    ) -> Option<&'b MinidumpThreadInfo> {
+        thread_infos.get_thread_info(self.raw.thread_id)
+    }
+
+    pub fn context(
+        &self,
+        system_info: &MinidumpSystemInfo,
+        misc: Option<&MinidumpMiscInfo>,
+    ) -> Option<Cow<MinidumpContext>> {
+        MinidumpContext::read(self.context?, self.endian, system_info, misc)
+            .ok()
+            .map(Cow::Owned)
+    }
+
+    /// Get the registers saved in the extended part of this thread's context,
+    /// like the AVX registers of an amd64 context.
+    ///
+    /// Unlike for [`context`][Self::context], `misc` is needed to find these.
+    pub fn extended_state(
+        &self,
+        system_info: &MinidumpSystemInfo,
+        misc: Option<&MinidumpMiscInfo>,
+    ) -> Option<MinidumpExtendedState> {
+        MinidumpExtendedState::read(self.context?, self.endian, system_info, misc)
+    }
+
+    pub fn stack_memory<'slf>(
+        &'slf self,
+        memory_list: &'slf UnifiedMemoryList<'a>,
+    ) -> Option<UnifiedMemory<'slf, 'a>> {
+        self.stack.as_ref().map(UnifiedMemory::Memory).or_else(|| {
+            // Sometimes the raw.stack RVA is null/busted, but the start_of_memory_range
+            // value is correct. So if the `read` fails, try resolving start_of_memory_range
+            // with the memory list. (This seems to specifically be a problem with
+            // Windows minidumps.)
+            let stack_addr = self.raw.stack.start_of_memory_range;
+            memory_list.memory_at_address(stack_addr)
+        })
+    }
+
+    /// The address range of the stack this thread's stack descriptor declares.
+    ///
+    /// Returns `None` if the descriptor is empty (or nonsensical).
+    pub fn declared_stack_range(&self) -> Option<std::ops::Range<u64>> {
+        let desc = self.raw.stack;
+        let start = desc.start_of_memory_range;
+        let end = start.checked_add(u64::from(desc.memory.data_size))?;
+        (end > start).then_some(start..end)
+    }
+
+    /// Get this thread's stack memory, limited to the range its stack
+    /// descriptor declares.
+    ///
+    /// When the stack can't be read directly, [`MinidumpThread::stack_memory`]
+    /// falls back to whichever region of `memory_list` contains the start of
+    /// the stack, which can extend past the end of this thread's stack (into
+    /// an adjacent thread's, for instance). This only returns the part of that
+    /// region the descriptor covers.
+    pub fn declared_stack_memory(
+        &self,
+        memory_list: &UnifiedMemoryList<'a>,
+    ) -> Option<MinidumpMemory<'a>> {
+        if let Some(stack) = &self.stack {
+            return Some(*stack);
+        }
+        let desc = self.raw.stack;
+        let memory = memory_list.memory_at_address(desc.start_of_memory_range)?;
+        let offset = desc.start_of_memory_range - memory.base_address();
+        let size = u64::from(desc.memory.data_size).min(memory.size() - offset);
+        let bytes = memory
+            .bytes()
+            .get(offset as usize..(offset + size) as usize)?;
+        Some(MinidumpMemory {
+            desc,
+            base_address: desc.start_of_memory_range,
+            size,
+            bytes,
+            endian: self.endian,
+        })
+    }
+
+    /// Get the memory regions associated with this thread.
+    ///
+    /// This is its [stack][MinidumpThread::stack_memory], along with any
+    /// other regions in `memory_list` that overlap the range the thread's
+    /// stack descriptor claims, or the thread's TEB (on Windows; on other
+    /// platforms `teb` holds the thread's TLS pointer, which is just as
+    /// interesting). Regions are returned in order by address, and a region
+    /// that appears both as the thread's own stack and in `memory_list` is
+    /// only returned once.
+    pub fn memory_regions<'slf>(
+        &'slf self,
+        memory_list: &'slf UnifiedMemoryList<'a>,
+    ) -> Vec<UnifiedMemory<'slf, 'a>> {
+        // Comfortably larger than the TEB of any version of Windows, for
+        // either pointer width.
+        const TEB_SIZE: u64 = 0x2000;
+
+        let stack = self.stack_memory(memory_list);
+        let stack_desc = &self.raw.stack;
+        let near_stack = memory_list.memory_overlapping(
+            stack_desc.start_of_memory_range,
+            stack_desc.memory.data_size as u64,
+        );
+        let near_teb = memory_list.memory_overlapping(self.raw.teb, TEB_SIZE);
+
+        let mut regions: Vec<UnifiedMemory<'slf, 'a>> = Vec::new();
+        for region in stack.into_iter().chain(near_stack).chain(near_teb) {
+            let duplicate = regions
+                .iter()
+                .any(|r| r.base_address() == region.base_address() && r.size() == region.size());
+            if !duplicate {
+                regions.push(region);
+            }
+        }
+        regions.sort_by_key(|region| region.base_address());
+        regions
+    }
+
+    /// Write a human-readable description of this `MinidumpThread` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(
+        &self,
+        f: &mut T,
+        memory: Option<&UnifiedMemoryList<'a>>,
+        system: Option<&MinidumpSystemInfo>,
+        misc: Option<&MinidumpMiscInfo>,
+        brief: bool,
+    ) -> io::Result<()> {
+        write!(
+            f,
+            r#"MINIDUMP_THREAD
+  thread_id                   = {:#x}
+  suspend_count               = {}
+  priority_class              = {:#x}
+  priority                    = {:#x}
+  teb                         = {:#x}
+  stack.start_of_memory_range = {:#x}
+  stack.memory.data_size      = {:#x}
+  stack.memory.rva            = {:#x}
+  thread_context.data_size    = {:#x}
+  thread_context.rva          = {:#x}
+
+"#,
+            self.raw.thread_id,
+            self.raw.suspend_count,
+            self.raw.priority_class,
+            self.raw.priority,
+            self.raw.teb,
+            self.raw.stack.start_of_memory_range,
+            self.raw.stack.memory.data_size,
+            self.raw.stack.memory.rva,
+            self.raw.thread_context.data_size,
+            self.raw.thread_context.rva,
+        )?;
+        if let Some(system_info) = system {
+            if let Some(ctx) = self.context(system_info, misc) {
+                ctx.print(f)?;
+            } else {
+                write!(f, "  (no context)\n\n")?;
+            }
+        } else {
+            write!(f, "  (no context)\n\n")?;
+        }
+
+        if brief {
+            writeln!(f)?;
+            return Ok(());
+        }
+
+        let pointer_width = system.map_or(PointerWidth::Unknown, |info| info.cpu.pointer_width());
+
+        // We might not need any memory, so try to limp forward with an empty
+        // MemoryList if we don't have one.
+        let dummy_memory = UnifiedMemoryList::default();
+        let memory = memory.unwrap_or(&dummy_memory);
+        if let Some(stack) = self.stack_memory(memory) {
+            writeln!(f, "Stack")?;
+
+            // For printing purposes, we'll treat any unknown CPU type as 64-bit
+            let chunk_size: usize = pointer_width.size_in_bytes().unwrap_or(8).into();
+            let mut offset = 0;
+            for chunk in stack.bytes().chunks_exact(chunk_size) {
+                write!(f, "    {:#010x}: ", offset)?;
+
+                match pointer_width {
+                    PointerWidth::Bits32 => {
+                        let value = match self.endian {
+                            scroll::Endian::Little => u32::from_le_bytes(chunk.try_into().unwrap()),
+                            scroll::Endian::Big => u32::from_be_bytes(chunk.try_into().unwrap()),
+                        };
+                        write!(f, "{:#010x}", value)?;
+                    }
+                    PointerWidth::Unknown | PointerWidth::Bits64 => {
+                        let value = match self.endian {
+                            scroll::Endian::Little => u64::from_le_bytes(chunk.try_into().unwrap()),
+                            scroll::Endian::Big => u64::from_be_bytes(chunk.try_into().unwrap()),
+                        };
+                        write!(f, "{:#018x}", value)?;
+                    }
+                }
+
+                writeln!(f)?;
+
+                offset += chunk_size;
+            }
+        } else {
+            writeln!(f, "No stack")?;
+        }
+        writeln!(f)?;
+        Ok(())
+    }
+
+    /// Gets the last error code the thread recorded, just like win32's GetLastError.
+    ///
+    /// The value is heuristically converted into a CrashReason because that's our
+    /// general error code handling machinery, even though this may not actually be
+    /// the reason for the crash!
+    ///
+    /// This is only meaningful for Windows minidumps, whose threads point at their
+    /// TEB. Returns `None` if the dump doesn't contain the TEB's memory.
+    pub fn last_error(&self, cpu: Cpu, memory: &UnifiedMemoryList) -> Option<CrashReason> {
+        // LastErrorValue follows the NT_TIB (7 pointers), EnvironmentPointer,
+        // ClientId (2 pointers), ActiveRpcHandle, ThreadLocalStoragePointer
+        // and ProcessEnvironmentBlock: 0x34 in the 32-bit TEB, and 0x68 in
+        // the 64-bit one.
+        let offset = match cpu.pointer_width() {
+            PointerWidth::Bits32 => 0x34,
+            PointerWidth::Bits64 => 0x68,
+            PointerWidth::Unknown => return None,
+        };
+        let addr = self.raw.teb.checked_add(offset)?;
+        let val: u32 = memory
+            .memory_at_address(addr)?
+            .get_memory_at_address(addr)?;
+
+        Some(CrashReason::from_windows_error(val))
+    }
+}
+
+impl CriticalSection {
+    /// Read the `CRITICAL_SECTION` at `address` out of `memory`.
+    ///
+    /// The layout of the structure depends on the pointer width of `cpu`.
+    /// Returns `None` if the dump doesn't contain the memory the structure
+    /// is in.
+    pub fn read(cpu: Cpu, memory: &UnifiedMemoryList, address: u64) -> Option<CriticalSection> {
+        // struct RTL_CRITICAL_SECTION {
+        //     PRTL_CRITICAL_SECTION_DEBUG DebugInfo;
+        //     LONG LockCount;
+        //     LONG RecursionCount;
+        //     HANDLE OwningThread;  // actually the thread id
+        //     HANDLE LockSemaphore;
+        //     ULONG_PTR SpinCount;
+        // }
+        let pointer_width = cpu.pointer_width().size_in_bytes()? as u64;
+        let region = memory.memory_at_address(address)?;
+        let lock_count_addr = address.checked_add(pointer_width)?;
+        let lock_count: i32 = region.get_memory_at_address(lock_count_addr)?;
+        let recursion_count: i32 = region.get_memory_at_address(lock_count_addr.checked_add(4)?)?;
+        let owning_thread: u32 = region.get_memory_at_address(lock_count_addr.checked_add(8)?)?;
+
+        Some(CriticalSection {
+            address,
+            lock_count,
+            recursion_count,
+            owning_thread: Some(owning_thread).filter(|&id| id != 0),
+        })
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpThreadList<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::ThreadListStream as u32;
+
+    fn read(
+        bytes: &'a [u8],
+        all: &'a [u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpThreadList<'a>, Error> {
+        let mut offset = 0;
+        let raw_threads: Vec<md::MINIDUMP_THREAD> = read_stream_list(&mut offset, bytes, endian)?;
+        let mut threads = Vec::with_capacity(raw_threads.len());
+        let mut thread_ids = HashMap::with_capacity(raw_threads.len());
+        for raw in raw_threads.into_iter() {
+            thread_ids.insert(raw.thread_id, threads.len());
+
+            // Defer parsing of this to the `context` method, where we will have access
+            // to other streams that are required to parse a context properly.
+            let context = location_slice(all, &raw.thread_context).ok();
+
+            // Try to get the stack memory here, but the `stack_memory` method will
+            // attempt a fallback method with access to other streams.
+            let stack = MinidumpMemory::read(&raw.stack, all, endian).ok();
+            threads.push(MinidumpThread {
+                raw,
+                context,
+                stack,
+                endian,
+            });
+        }
+        Ok(MinidumpThreadList {
+            threads,
+            thread_ids,
+        })
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpThreadInfoList {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::ThreadInfoListStream as u32;
+
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<Self, Error> {
+        let mut offset = 0;
+        let raw_infos: Vec<md::MINIDUMP_THREAD_INFO> =
+            read_ex_stream_list(&mut offset, bytes, endian)?;
+        let thread_infos = raw_infos
+            .into_iter()
+            .map(|raw| MinidumpThreadInfo {
+                dump_flags: md::ThreadInfoDumpFlags::from_bits_truncate(raw.dump_flags),
+                raw,
+            })
+            .collect();
+        Ok(MinidumpThreadInfoList::from_thread_infos(thread_infos))
+    }
+}
+
+impl MinidumpThreadInfoList {
+    /// Create a `MinidumpThreadInfoList` from a list of `MinidumpThreadInfo`s.
+    pub fn from_thread_infos(thread_infos: Vec<MinidumpThreadInfo>) -> MinidumpThreadInfoList {
+        let thread_ids = thread_infos
+            .iter()
+            .enumerate()
+            .map(|(i, info)| (info.raw.thread_id, i))
+            .collect();
+        MinidumpThreadInfoList {
+            thread_infos,
+            thread_ids,
+        }
+    }
+
+    /// Get the info for the thread with id `id`, if it exists.
+    pub fn get_thread_info(&self, id: u32) -> Option<&MinidumpThreadInfo> {
+        self.thread_ids
+            .get(&id)
+            .map(|&index| &self.thread_infos[index])
+    }
+
+    /// Iterate over the thread infos in the order contained in the minidump.
+    pub fn iter(&self) -> impl Iterator<Item = &MinidumpThreadInfo> {
+        self.thread_infos.iter()
+    }
+
+    /// Write a human-readable description of this `MinidumpThreadInfoList` to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MinidumpThreadInfoList
+  thread_info_count = {}
+
+",
+            self.thread_infos.len()
+        )?;
+        for (i, info) in self.thread_infos.iter().enumerate() {
+            writeln!(f, "thread_info[{}]", i)?;
+            info.print(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert a Windows `FILETIME` (100-nanosecond intervals since 1601) to a `SystemTime`.
+fn systemtime_from_filetime(filetime: u64) -> Option<SystemTime> {
+    const SECONDS_FROM_1601_TO_1970: u64 = 11_644_473_600;
+    let since_1601 = Duration::from_nanos(filetime.checked_mul(100)?);
+    SystemTime::UNIX_EPOCH
+        .checked_add(since_1601.checked_sub(Duration::from_secs(SECONDS_FROM_1601_TO_1970))?)
+}
+
+impl MinidumpThreadInfo {
+    /// The address of the thread's start routine.
+    ///
+    /// Symbolicating this is a good way to name a thread that has no name.
+    pub fn start_address(&self) -> u64 {
+        self.raw.start_address
+    }
+
+    /// When the thread was created, if known.
+    pub fn create_time(&self) -> Option<SystemTime> {
+        Some(self.raw.create_time)
+            .filter(|&t| t != 0)
+            .and_then(systemtime_from_filetime)
+    }
+
+    /// When the thread exited, if it had.
+    pub fn exit_time(&self) -> Option<SystemTime> {
+        Some(self.raw.exit_time)
+            .filter(|&t| t != 0)
+            .and_then(systemtime_from_filetime)
+    }
+
+    /// How long the thread has run in kernel mode.
+    pub fn kernel_time(&self) -> Duration {
+        Duration::from_nanos(self.raw.kernel_time.saturating_mul(100))
+    }
+
+    /// How long the thread has run in user mode.
+    pub fn user_time(&self) -> Duration {
+        Duration::from_nanos(self.raw.user_time.saturating_mul(100))
+    }
+
+    /// Write a human-readable description of this `MinidumpThreadInfo` to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MINIDUMP_THREAD_INFO
+  thread_id     = {:#x}
+  dump_flags    = {:#x}
+  dump_error    = {:#x}
+  exit_status   = {:#x}
+  create_time   = {:#x}
+  exit_time     = {:#x}
+  kernel_time   = {}
+  user_time     = {}
+  start_address = {:#x}
+  affinity      = {:#x}
+
+",
+            self.raw.thread_id,
+            self.dump_flags,
+            self.raw.dump_error,
+            self.raw.exit_status,
+            self.raw.create_time,
+            self.raw.exit_time,
+            self.raw.kernel_time,
+            self.raw.user_time,
+            self.raw.start_address,
+            self.raw.affinity,
+        )
+    }
+}
+
+impl<'a> MinidumpThreadList<'a> {
+    /// Get the thread with id `id` from this thread list if it exists.
+    pub fn get_thread(&self, id: u32) -> Option<&MinidumpThread<'a>> {
+        self.thread_ids.get(&id).map(|&index| &self.threads[index])
+    }
+
+    /// Get the name of the thread with id `id` from `names`, if the thread
+    /// exists and has a name.
+    pub fn thread_name<'n>(&self, id: u32, names: &'n MinidumpThreadNames) -> Option<Cow<'n, str>> {
+        self.get_thread(id)?;
+        names.get_name(id)
+    }
+
+    /// Iterate over the threads in this list, along with their names from
+    /// `names` (if they have one).
+    pub fn threads_with_names<'slf>(
+        &'slf self,
+        names: &'slf MinidumpThreadNames,
+    ) -> impl Iterator<Item = (&'slf MinidumpThread<'a>, Option<Cow<'slf, str>>)> {
+        self.threads
+            .iter()
+            .map(move |thread| (thread, names.get_name(thread.raw.thread_id)))
+    }
+
+    /// Get the memory regions associated with the thread with id `id`, if it
+    /// exists. See [`MinidumpThread::memory_regions`] for details.
+    pub fn thread_memory_regions<'slf>(
+        &'slf self,
+        id: u32,
+        memory_list: &'slf UnifiedMemoryList<'a>,
+    ) -> Option<Vec<UnifiedMemory<'slf, 'a>>> {
+        Some(self.get_thread(id)?.memory_regions(memory_list))
+    }
+
+    /// Get the thread holding the Windows `CRITICAL_SECTION` at `address`.
+    ///
+    /// Returns `None` if the critical section can't be read (see
+    /// [`CriticalSection::read`]), isn't held, or is held by a thread that
+    /// isn't in this list.
+    pub fn critical_section_owner(
+        &self,
+        cpu: Cpu,
+        memory: &UnifiedMemoryList,
+        address: u64,
+    ) -> Option<&MinidumpThread<'a>> {
+        let owner = CriticalSection::read(cpu, memory, address)?.owning_thread?;
+        self.get_thread(owner)
+    }
+
+    /// Write a human-readable description of this `MinidumpThreadList` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(
+        &self,
+        f: &mut T,
+        memory: Option<&UnifiedMemoryList<'a>>,
+        system: Option<&MinidumpSystemInfo>,
+        misc: Option<&MinidumpMiscInfo>,
+        brief: bool,
+    ) -> io::Result<()> {
+        write!(
+            f,
+            r#"MinidumpThreadList
+  thread_count = {}
+
+"#,
+            self.threads.len()
+        )?;
+
+        for (i, thread) in self.threads.iter().enumerate() {
+            writeln!(f, "thread[{}]", i)?;
+            thread.print(f, memory, system, misc, brief)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpSystemInfo {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::SystemInfoStream as u32;
+
+    fn read(
+        bytes: &[u8],
+        all: &[u8],
+        endian: scroll::Endian,
+        system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpSystemInfo, Error> {
+        if let Some(system_info) = system_info {
+            return Ok(system_info.clone());
+        }
+
+        use std::fmt::Write;
+
+        let raw: md::MINIDUMP_SYSTEM_INFO = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let os = Os::from_platform_id(raw.platform_id);
+        let cpu = Cpu::from_processor_architecture(raw.processor_architecture);
+
+        let mut csd_offset = raw.csd_version_rva as usize;
+        let csd_version = read_string_utf16(&mut csd_offset, all, endian);
+
+        // self.raw.cpu.data is actually a union which we resolve here.
+        let cpu_info = match cpu {
+            Cpu::X86 | Cpu::X86_64 => {
+                let mut cpu_info = String::new();
+
+                if let Cpu::X86 = cpu {
+                    // The vendor's ID is an ascii string but we need to flatten out the u32's into u8's
+                    let x86_info: md::X86CpuInfo = raw
+                        .cpu
+                        .data
+                        .pread_with(0, endian)
+                        .or(Err(Error::StreamReadFailure))?;
+
+                    cpu_info.extend(
+                        x86_info
+                            .vendor_id
+                            .iter()
+                            .flat_map(|i| IntoIterator::into_iter(i.to_le_bytes()))
+                            .map(char::from),
+                    );
+                    cpu_info.push(' ');
+                }
+
+                write!(
+                    &mut cpu_info,
+                    "family {} model {} stepping {}",
+                    raw.processor_level,
+                    (raw.processor_revision >> 8) & 0xff,
+                    raw.processor_revision & 0xff
+                )
+                .unwrap();
+
+                Some(cpu_info)
+            }
+            Cpu::Arm => {
+                let arm_info: md::ARMCpuInfo = raw
+                    .cpu
+                    .data
+                    .pread_with(0, endian)
+                    .or(Err(Error::StreamReadFailure))?;
+
+                let features = [
+                    (md::ArmElfHwCaps::HWCAP_SWP, "swp"),
+                    (md::ArmElfHwCaps::HWCAP_HALF, "half"),
+                    (md::ArmElfHwCaps::HWCAP_THUMB, "thumb"),
+                    (md::ArmElfHwCaps::HWCAP_26BIT, "26bit"),
+                    (md::ArmElfHwCaps::HWCAP_FAST_MULT, "fastmult"),
+                    (md::ArmElfHwCaps::HWCAP_FPA, "fpa"),
+                    (md::ArmElfHwCaps::HWCAP_VFP, "vfpv2"),
+                    (md::ArmElfHwCaps::HWCAP_EDSP, "edsp"),
+                    (md::ArmElfHwCaps::HWCAP_JAVA, "java"),
+                    (md::ArmElfHwCaps::HWCAP_IWMMXT, "iwmmxt"),
+                    (md::ArmElfHwCaps::HWCAP_CRUNCH, "crunch"),
+                    (md::ArmElfHwCaps::HWCAP_THUMBEE, "thumbee"),
+                    (md::ArmElfHwCaps::HWCAP_NEON, "neon"),
+                    (md::ArmElfHwCaps::HWCAP_VFPv3, "vfpv3"),
+                    (md::ArmElfHwCaps::HWCAP_VFPv3D16, "vfpv3d16"),
+                    (md::ArmElfHwCaps::HWCAP_TLS, "tls"),
+                    (md::ArmElfHwCaps::HWCAP_VFPv4, "vfpv4"),
+                    (md::ArmElfHwCaps::HWCAP_IDIVA, "idiva"),
+                    (md::ArmElfHwCaps::HWCAP_IDIVT, "idivt"),
+                ];
+
+                let mut cpu_info = format!("ARMv{}", raw.processor_level);
+
+                // Try to extract out known vendor/part names from the cpuid,
+                // falling back to just reporting the raw value.
+                let cpuid = arm_info.cpuid;
+                if cpuid != 0 {
+                    let vendor_id = (cpuid >> 24) as u8;
+                    let part_id = ((cpuid >> 4) & 0xfff) as u16;
+
+                    if let Some(vendor) = arm_vendor_name(vendor_id) {
+                        write!(&mut cpu_info, " {}", vendor).unwrap();
+                    } else {
+                        write!(&mut cpu_info, " vendor({:#x})", vendor_id).unwrap();
+                    }
+
+                    if let Some(part) = arm_part_name(vendor_id, part_id) {
+                        write!(&mut cpu_info, " {}", part).unwrap();
+                    } else {
+                        write!(&mut cpu_info, " part({:#x})", cpuid & 0xff00fff0).unwrap();
+                    }
+                }
+
+                // Report all the known hardware features.
+                let elf_hwcaps = md::ArmElfHwCaps::from_bits_truncate(arm_info.elf_hwcaps);
+                if !elf_hwcaps.is_empty() {
+                    cpu_info.push_str(" features: ");
+
+                    // Iterator::intersperse is still unstable, so do it manually
+                    let mut comma = "";
+                    for &(_, feature) in features
+                        .iter()
+                        .filter(|&&(feature, _)| elf_hwcaps.contains(feature))
+                    {
+                        cpu_info.push_str(comma);
+                        cpu_info.push_str(feature);
+                        comma = ",";
+                    }
+                }
+
+                Some(cpu_info)
+            }
+            _ => None,
+        };
+
+        let cpu_features = match cpu {
+            Cpu::Arm | Cpu::Arm64 => {
+                let mut features = ArmCpuFeatures::default();
+                if os == Os::Windows {
+                    let other_info: md::OtherCpuInfo = raw
+                        .cpu
+                        .data
+                        .pread_with(0, endian)
+                        .or(Err(Error::StreamReadFailure))?;
+                    features.add_windows_processor_features(other_info.processor_features);
+                } else {
+                    let arm_info: md::ARMCpuInfo = raw
+                        .cpu
+                        .data
+                        .pread_with(0, endian)
+                        .or(Err(Error::StreamReadFailure))?;
+                    if arm_info.cpuid != 0 {
+                        features.set_midr(arm_info.cpuid);
+                    }
+                    features.add_elf_hwcaps(arm_info.elf_hwcaps);
+                }
+                Some(features)
+            }
+            _ => None,
+        };
+
+        let (x86_vendor, x86_signature) = match cpu {
+            Cpu::X86 | Cpu::X86_64 => {
+                let x86_info: md::X86CpuInfo = raw
+                    .cpu
+                    .data
+                    .pread_with(0, endian)
+                    .or(Err(Error::StreamReadFailure))?;
+                // Windows only stores the vendor and cpuid for x86, amd64
+                // dumps have the `OtherCpuInfo` processor features here
+                // instead. Those won't look like a vendor string, though.
+                let vendor = x86_info
+                    .vendor_id
+                    .iter()
+                    .flat_map(|i| IntoIterator::into_iter(i.to_le_bytes()))
+                    .map(char::from)
+                    .collect::<String>();
+                let vendor = (vendor.chars().all(|c| c.is_ascii_graphic() || c == ' ')
+                    && vendor.chars().any(|c| c.is_ascii_alphanumeric()))
+                .then_some(vendor);
+
+                let signature = if vendor.is_some() && x86_info.version_information != 0 {
+                    X86CpuSignature::from_version_information(x86_info.version_information)
+                } else {
+                    // These are already the display family and model.
+                    X86CpuSignature {
+                        family: raw.processor_level as u32,
+                        model: ((raw.processor_revision >> 8) & 0xff) as u32,
+                        stepping: (raw.processor_revision & 0xff) as u32,
+                    }
+                };
+                (vendor, Some(signature))
+            }
+            _ => (None, None),
+        };
+
+        Ok(MinidumpSystemInfo {
+            raw,
+            os,
+            cpu,
+            csd_version,
+            cpu_info,
+            cpu_features,
+            x86_vendor,
+            x86_signature,
+        })
+    }
+}
+
+impl MinidumpSystemInfo {
+    /// Write a human-readable description of this `MinidumpSystemInfo` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MINIDUMP_SYSTEM_INFO
+  processor_architecture                     = {:#x}
+  processor_level                            = {}
+  processor_revision                         = {:#x}
+  number_of_processors                       = {}
+  product_type                               = {}
+  major_version                              = {}
+  minor_version                              = {}
+  build_number                               = {}
+  platform_id                                = {:#x}
+  csd_version_rva                            = {:#x}
+  suite_mask                                 = {:#x}
+  (version)                                  = {}.{}.{} {}
+  (cpu_info)                                 = {}
+
+",
+            self.raw.processor_architecture,
+            self.raw.processor_level,
+            self.raw.processor_revision,
+            self.raw.number_of_processors,
+            self.raw.product_type,
+            self.raw.major_version,
+            self.raw.minor_version,
+            self.raw.build_number,
+            self.raw.platform_id,
+            self.raw.csd_version_rva,
+            self.raw.suite_mask,
+            self.raw.major_version,
+            self.raw.minor_version,
+            self.raw.build_number,
+            self.csd_version().as_deref().unwrap_or(""),
+            self.cpu_info().as_deref().unwrap_or(""),
+        )?;
+        // TODO: cpu info etc
+        Ok(())
+    }
+
+    /// If the minidump was generated on:
+    /// - Windows: Returns the the name of the Service Pack.
+    /// - macOS: Returns the product build number.
+    /// - Linux: Returns the contents of `uname -srvmo`.
+    pub fn csd_version(&self) -> Option<Cow<str>> {
+        self.csd_version.as_deref().map(Cow::Borrowed)
+    }
+
+    /// Returns a string describing the cpu's vendor and model.
+    pub fn cpu_info(&self) -> Option<Cow<str>> {
+        self.cpu_info.as_deref().map(Cow::Borrowed)
+    }
+
+    /// The model and features of the cpu, if it's an ARM or ARM64 cpu.
+    ///
+    /// Pass the dump's [`MinidumpLinuxCpuInfo`][] stream, if it has one, to
+    /// fill in the model if the system info doesn't have it, and add the
+    /// features listed in `/proc/cpuinfo`, which are usually more complete.
+    pub fn cpu_features(
+        &self,
+        linux_cpu_info: Option<&MinidumpLinuxCpuInfo>,
+    ) -> Option<ArmCpuFeatures> {
+        let mut features = self.cpu_features.clone()?;
+        let linux_cpu_info = match linux_cpu_info {
+            Some(linux_cpu_info) => linux_cpu_info,
+            None => return Some(features),
+        };
+
+        let parse_hex = |val: &LinuxOsStr| {
+            val.to_str()
+                .ok()
+                .and_then(|val| val.strip_prefix("0x"))
+                .and_then(|val| u16::from_str_radix(val, 16).ok())
+        };
+        let mut implementer = None;
+        let mut part = None;
+        for (key, val) in linux_cpu_info.iter() {
+            match key.as_bytes() {
+                b"CPU implementer" if implementer.is_none() => {
+                    implementer = parse_hex(val).and_then(|val| val.try_into().ok());
+                }
+                b"CPU part" if part.is_none() => part = parse_hex(val),
+                b"Features" => {
+                    features.features.extend(
+                        val.split_ascii_whitespace()
+                            .filter_map(|name| name.to_str().ok())
+                            .filter_map(ArmFeature::from_cpuinfo_name),
+                    );
+                }
+                _ => {}
+            }
+        }
+        if features.implementer.is_none() {
+            features.implementer = implementer;
+            features.part = part;
+        }
+        Some(features)
+    }
+
+    /// The name of the cpu's microarchitecture, like "Tiger Lake", "Zen 3",
+    /// or "Cortex-A76", if it's a known one.
+    ///
+    /// For x86 and amd64 this is looked up from the vendor, family, and model
+    /// (see [`x86_microarchitecture`]); for ARM and ARM64 it's the name of
+    /// the core in [`cpu_features`][Self::cpu_features]. As with that,
+    /// `linux_cpu_info` fills in anything the system info doesn't have.
+    pub fn cpu_microarchitecture(
+        &self,
+        linux_cpu_info: Option<&MinidumpLinuxCpuInfo>,
+    ) -> Option<&'static str> {
+        match self.cpu {
+            Cpu::X86 | Cpu::X86_64 => x86_microarchitecture(
+                self.x86_vendor(linux_cpu_info).as_deref(),
+                self.x86_signature?,
+            ),
+            Cpu::Arm | Cpu::Arm64 => self.cpu_features(linux_cpu_info)?.part_name(),
+            _ => None,
+        }
+    }
+
+    /// A friendly description of the cpu, like "GenuineIntel family 6 model
+    /// 140 stepping 1 (Tiger Lake)" or "Qualcomm Kryo 4xx Gold".
+    ///
+    /// Unlike [`cpu_info`][Self::cpu_info], family and model numbers are the
+    /// display values, and names are looked up where they're known (see
+    /// [`cpu_microarchitecture`][Self::cpu_microarchitecture]). Returns `None`
+    /// if the cpu isn't identified by the dump at all.
+    pub fn cpu_description(&self, linux_cpu_info: Option<&MinidumpLinuxCpuInfo>) -> Option<String> {
+        use std::fmt::Write;
+
+        match self.cpu {
+            Cpu::X86 | Cpu::X86_64 => {
+                let signature = self.x86_signature?;
+                let mut description = String::new();
+                if let Some(vendor) = self.x86_vendor(linux_cpu_info) {
+                    write!(&mut description, "{} ", vendor).unwrap();
+                }
+                write!(
+                    &mut description,
+                    "family {} model {} stepping {}",
+                    signature.family, signature.model, signature.stepping
+                )
+                .unwrap();
+                if let Some(name) = self.cpu_microarchitecture(linux_cpu_info) {
+                    write!(&mut description, " ({})", name).unwrap();
+                }
+                Some(description)
+            }
+            Cpu::Arm | Cpu::Arm64 => {
+                let features = self.cpu_features(linux_cpu_info)?;
+                let implementer = features.implementer?;
+                let mut description = match features.vendor_name() {
+                    Some(vendor) => String::from(vendor),
+                    None => format!("implementer {:#x}", implementer),
+                };
+                match (features.part_name(), features.part) {
+                    (Some(part_name), _) => write!(&mut description, " {}", part_name).unwrap(),
+                    (None, Some(part)) => write!(&mut description, " part {:#x}", part).unwrap(),
+                    (None, None) => {}
+                }
+                Some(description)
+            }
+            _ => None,
+        }
+    }
+
+    /// The x86 vendor string, from the system info or else `/proc/cpuinfo`.
+    fn x86_vendor<'s>(
+        &'s self,
+        linux_cpu_info: Option<&'s MinidumpLinuxCpuInfo>,
+    ) -> Option<Cow<'s, str>> {
+        if let Some(vendor) = &self.x86_vendor {
+            return Some(Cow::Borrowed(vendor));
+        }
+        linux_cpu_info?
+            .iter()
+            .find(|(key, _)| key.as_bytes() == b"vendor_id")
+            .map(|(_, val)| val.to_string_lossy())
+    }
+
+    /// Strings identifying the version and build number of the operating
+    /// system. Returns a tuple in the format of (version, build number). This
+    /// may be useful to use if the minidump was created on a Linux machine and
+    /// is an producing empty-ish version number (0.0.0).
+    ///
+    /// Tries to parse the version number from the build if it cannot be found
+    /// in the version string. If the stream already contains a valid version
+    /// number or parsing from the build string fails, this will return what's
+    /// directly stored in the stream.
+    pub fn os_parts(&self) -> (String, Option<String>) {
+        let os_version = format!(
+            "{}.{}.{}",
+            self.raw.major_version, self.raw.minor_version, self.raw.build_number
+        );
+
+        let os_build = self
+            .csd_version()
+            .map(|v| v.trim().to_owned())
+            .filter(|v| !v.is_empty());
+
+        if md::PlatformId::from_u32(self.raw.platform_id) != Some(md::PlatformId::Linux)
+            || os_version != "0.0.0"
+        {
+            return (os_version, os_build);
+        }
+
+        // Try to parse the Linux build string. Breakpad and Crashpad run
+        // `uname -srvmo` to generate it. The string follows this structure:
+        // "Linux [version] [build...] [arch] Linux/GNU" where the Linux/GNU
+        // bit may not always be present.
+        let raw_build = self.csd_version().unwrap_or(Cow::Borrowed(""));
+        let mut parts = raw_build.split(' ');
+        let version = parts.nth(1).unwrap_or("0.0.0");
+        let _arch_or_os = parts.next_back().unwrap_or_default();
+        if _arch_or_os == "Linux/GNU" {
+            let _arch = parts.next_back();
+        }
+        let build = parts.collect::<Vec<&str>>().join(" ");
+
+        if version == "0.0.0" {
+            (os_version, os_build)
+        } else {
+            (version.into(), Some(build))
+        }
+    }
+}
+
+// Generates an accessor for a MISC_INFO field with two possible syntaxes:
+//
+// * VERSION_NUMBER: FIELD_NAME -> FIELD_TYPE
+// * VERSION_NUMBER: FIELD_NAME if FLAG -> FIELD_TYPE
+//
+// With the following definitions:
+//
+// * VERSION_NUMBER: The MISC_INFO version this field was introduced in
+// * FIELD_NAME: The name of the field to read
+// * FLAG: A MiscInfoFlag that defines if this field contains valid data
+// * FIELD_TYPE: The type of the field
+macro_rules! misc_accessors {
+    () => {};
+    (@defnoflag $name:ident $t:ty [$($variant:ident)+]) => {
+        #[allow(unreachable_patterns)]
+        pub fn $name(&self) -> Option<&$t> {
+            match self {
+                $(
+                    RawMiscInfo::$variant(ref raw) => Some(&raw.$name),
+                )+
+                _ => None,
+            }
+        }
+    };
+    (@def $name:ident $flag:ident $t:ty [$($variant:ident)+]) => {
+        #[allow(unreachable_patterns)]
+        pub fn $name(&self) -> Option<&$t> {
+            match self {
+                $(
+                    RawMiscInfo::$variant(ref raw) => if md::MiscInfoFlags::from_bits_truncate(raw.flags1).contains(md::MiscInfoFlags::$flag) { Some(&raw.$name) } else { None },
+                )+
+                _ => None,
+            }
+        }
+    };
+    (1: $name:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@defnoflag $name $t [MiscInfo MiscInfo2 MiscInfo3 MiscInfo4 MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+    (1: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@def $name $flag $t [MiscInfo MiscInfo2 MiscInfo3 MiscInfo4 MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+
+    (2: $name:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@defnoflag $name $t [MiscInfo2 MiscInfo3 MiscInfo4 MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+    (2: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@def $name $flag $t [MiscInfo2 MiscInfo3 MiscInfo4 MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+
+    (3: $name:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@defnoflag $name $t [MiscInfo3 MiscInfo4 MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+    (3: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@def $name $flag $t [MiscInfo3 MiscInfo4 MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+
+    (4: $name:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@defnoflag $name $t [MiscInfo4 MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+    (4: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@def $name $flag $t [MiscInfo4 MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+
+    (5: $name:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@defnoflag $name $t [MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+    (5: $name:ident if $flag:ident -> $t:ty, $($rest:tt)*) => {
+        misc_accessors!(@def $name $flag $t [MiscInfo5]);
+        misc_accessors!($($rest)*);
+    };
+}
+
+impl RawMiscInfo {
+    // Fields are grouped by the flag that guards them.
+    misc_accessors!(
+        1: size_of_info -> u32,
+        1: flags1 -> u32,
+
+        1: process_id if MINIDUMP_MISC1_PROCESS_ID -> u32,
+
+        1: process_create_time if MINIDUMP_MISC1_PROCESS_TIMES -> u32,
+        1: process_user_time if MINIDUMP_MISC1_PROCESS_TIMES -> u32,
+        1: process_kernel_time if MINIDUMP_MISC1_PROCESS_TIMES -> u32,
+
+        2: processor_max_mhz if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
+        2: processor_current_mhz if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
+        2: processor_mhz_limit if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
+        2: processor_max_idle_state if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
+        2: processor_current_idle_state if MINIDUMP_MISC1_PROCESSOR_POWER_INFO -> u32,
+
+        3: process_integrity_level if MINIDUMP_MISC3_PROCESS_INTEGRITY -> u32,
+
+        3: process_execute_flags if MINIDUMP_MISC3_PROCESS_EXECUTE_FLAGS -> u32,
+
+        3: protected_process if MINIDUMP_MISC3_PROTECTED_PROCESS -> u32,
+
+        3: time_zone_id if MINIDUMP_MISC3_TIMEZONE -> u32,
+        3: time_zone if MINIDUMP_MISC3_TIMEZONE -> md::TIME_ZONE_INFORMATION,
+
+        4: build_string if MINIDUMP_MISC4_BUILDSTRING -> [u16; 260],
+        4: dbg_bld_str if MINIDUMP_MISC4_BUILDSTRING -> [u16; 40],
+
+        5: xstate_data -> md::XSTATE_CONFIG_FEATURE_MSC_INFO,
+
+        5: process_cookie if MINIDUMP_MISC5_PROCESS_COOKIE -> u32,
+    );
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpMiscInfo {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MiscInfoStream as u32;
+
+    fn read(
+        bytes: &[u8],
+        _all: &[u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpMiscInfo, Error> {
+        // The misc info has gone through several revisions, so try to read the largest known
+        // struct possible.
+        macro_rules! do_read {
+            ($(($t:ty, $variant:ident),)+) => {
+                $(
+                    if bytes.len() >= <$t>::size_with(&endian) {
+                        return Ok(MinidumpMiscInfo {
+                            raw: RawMiscInfo::$variant(bytes.pread_with(0, endian).or(Err(Error::StreamReadFailure))?),
+                        });
+                    }
+                )+
+            }
+        }
+
+        do_read!(
+            (md::MINIDUMP_MISC_INFO_5, MiscInfo5),
+            (md::MINIDUMP_MISC_INFO_4, MiscInfo4),
+            (md::MINIDUMP_MISC_INFO_3, MiscInfo3),
+            (md::MINIDUMP_MISC_INFO_2, MiscInfo2),
+            (md::MINIDUMP_MISC_INFO, MiscInfo),
+        );
+        Err(Error::StreamReadFailure)
+    }
+}
+
+// Generates an accessor for a MAC_CRASH_INFO field with two possible syntaxes:
+//
+// * VERSION_NUMBER: FIELD_NAME -> FIELD_TYPE
+// * VERSION_NUMBER: string FIELD_NAME -> FIELD_TYPE
+//
+// With the following definitions:
+//
+// * VERSION_NUMBER: The MAC_CRASH_INFO version this field was introduced in
+// * FIELD_NAME: The name of the field to read
+// * FIELD_TYPE: The type of the field
+//
+// The "string" mode will retrieve the field from the variant's _RECORD_STRINGS
+// struct, while the other mode will retrieve it from the variant's _RECORD
+// struct.
+//
+// In both cases, None will be yielded if the value is null/empty.
+macro_rules! mac_crash_accessors {
+    () => {};
+    (@deffixed $name:ident $t:ty [$($variant:ident)+]) => {
+        #[allow(unreachable_patterns)]
+        pub fn $name(&self) -> Option<&$t> {
+            match self {
+                $(
+                    RawMacCrashInfo::$variant(ref fixed, _) => {
+                        if fixed.$name == 0 {
+                            None
+                        } else {
+                            Some(&fixed.$name)
+                        }
+                    },
+                )+
+                _ => None,
+            }
+        }
+    };
+    (@defstrings $name:ident $t:ty [$($variant:ident)+]) => {
+        #[allow(unreachable_patterns)]
+        pub fn $name(&self) -> Option<&$t> {
+            match self {
+                $(
+                    RawMacCrashInfo::$variant(_, ref strings) => {
+                        if strings.$name.is_empty() {
+                            None
+                        } else {
+                            Some(&*strings.$name)
+                        }
+                    }
+                )+
+                _ => None,
+            }
+        }
+    };
+    (1: $name:ident -> $t:ty, $($rest:tt)*) => {
+        mac_crash_accessors!(@deffixed $name $t [V1 V4 V5]);
+        mac_crash_accessors!($($rest)*);
+    };
+    (1: string $name:ident -> $t:ty, $($rest:tt)*) => {
+        mac_crash_accessors!(@defstrings $name $t [V1 V4 V5]);
+        mac_crash_accessors!($($rest)*);
+    };
+    (4: $name:ident -> $t:ty, $($rest:tt)*) => {
+        mac_crash_accessors!(@deffixed $name $t [V4 V5]);
+        mac_crash_accessors!($($rest)*);
+    };
+    (4: string $name:ident -> $t:ty, $($rest:tt)*) => {
+        mac_crash_accessors!(@defstrings $name $t [V4 V5]);
+        mac_crash_accessors!($($rest)*);
+    };
+    (5: $name:ident -> $t:ty, $($rest:tt)*) => {
+        mac_crash_accessors!(@deffixed $name $t [V5]);
+        mac_crash_accessors!($($rest)*);
+    };
+    (5: string $name:ident -> $t:ty, $($rest:tt)*) => {
+        mac_crash_accessors!(@defstrings $name $t [V5]);
+        mac_crash_accessors!($($rest)*);
+    };
+}
+
+impl RawMacCrashInfo {
+    // Fields are grouped by the flag that guards them.
+    mac_crash_accessors!(
+        1: version -> u64,
+
+        4: thread -> u64,
+        4: dialog_mode -> u64,
+
+        4: string module_path -> str,
+        4: string message -> str,
+        4: string signature_string -> str,
+        4: string backtrace -> str,
+        4: string message2 -> str,
+
+        5: abort_cause -> u64,
+    );
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpMacCrashInfo {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MozMacosCrashInfoStream as u32;
+
+    fn read(
+        bytes: &[u8],
+        all: &[u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpMacCrashInfo, Error> {
+        // Get the main header of the stream
+        let header: md::MINIDUMP_MAC_CRASH_INFO = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+
+        let strings_offset = header.record_start_size as usize;
+        let mut prev_version = None;
+        let mut infos = Vec::new();
+
+        // We use `take` here to better handle a corrupt record_count that is larger than the
+        // maximum supported size.
+        let records = header.records.iter().take(header.record_count as usize);
+
+        for record_location in records {
+            // Peek the V1 version to get the `version` field
+            let record_slice = location_slice(all, record_location)?;
+            let base: md::MINIDUMP_MAC_CRASH_INFO_RECORD = record_slice
+                .pread_with(0, endian)
+                .or(Err(Error::StreamReadFailure))?;
+
+            // The V1 version also includes the stream type again, but that's
+            // not really important, so just warn about it and keep going.
+            if base.stream_type != header.stream_type as u64 {
+                warn!(
+                    "MozMacosCrashInfoStream records don't have the right stream type? {}",
+                    base.stream_type
+                );
+            }
+
+            // Make sure every record has the same version, because they have to
+            // share their strings_offset which make heterogeneous records impossible.
+            if let Some(prev_version) = prev_version {
+                if prev_version != base.version {
+                    warn!(
+                        "MozMacosCrashInfoStream had two different versions ({} != {})",
+                        prev_version, base.version
+                    );
+                    return Err(Error::VersionMismatch);
+                }
+            }
+            prev_version = Some(base.version);
+
+            // Now actually read the full record and its strings for the version
+            macro_rules! do_read {
+                ($base_version:expr, $strings_offset:expr, $infos:ident,
+                    $(($version:expr, $fixed:ty, $strings:ty, $variant:ident),)+) => {$(
+                    if $base_version >= $version {
+                        let offset = &mut 0;
+                        let fixed: $fixed = record_slice
+                            .gread_with(offset, endian)
+                            .or(Err(Error::StreamReadFailure))?;
+
+                        // Sanity check that we haven't blown past where the strings start.
+                        if *offset > $strings_offset {
+                            warn!("MozMacosCrashInfoStream's record_start_size was too small! ({})",
+                                $strings_offset);
+                            return Err(Error::StreamReadFailure);
+                        }
+
+                        // We could be handling a newer version of the format than we know
+                        // how to support, so jump to where the strings start, potentially
+                        // skipping over some unknown fields.
+                        *offset = $strings_offset;
+                        let num_strings = <$strings>::num_strings();
+                        let mut strings = <$strings>::default();
+
+                        // Read out all the strings we know about
+                        for i in 0..num_strings {
+                            let string = read_cstring_utf8(offset, record_slice)
+                                .ok_or(Error::StreamReadFailure)?;
+                            strings.set_string(i, string);
+                        }
+                        // If this is a newer version, there may be some extra variable length
+                        // data in this record, but we don't know what it is, so don't try to parse it.
+
+                        infos.push(RawMacCrashInfo::$variant(fixed, strings));
+                        continue;
+                    }
+                )+}
+            }
+
+            do_read!(
+                base.version,
+                strings_offset,
+                infos,
+                (
+                    5,
+                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_5,
+                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_STRINGS_5,
+                    V5
+                ),
+                (
+                    4,
+                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_4,
+                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_STRINGS_4,
+                    V4
+                ),
+                (
+                    1,
+                    md::MINIDUMP_MAC_CRASH_INFO_RECORD,
+                    md::MINIDUMP_MAC_CRASH_INFO_RECORD_STRINGS,
+                    V1
+                ),
+            );
+        }
+        Ok(MinidumpMacCrashInfo { raw: infos })
+    }
+}
+
+impl MinidumpMacCrashInfo {
+    /// Write a human-readable description of this `MinidumpMacCrashInfo` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        macro_rules! write_simple_field {
+            ($stream:ident, $field:ident, $idx:ident, $format:literal) => {
+                write!(f, "    {:18}= ", stringify!($field))?;
+                match self.raw[$idx].$field() {
+                    Some($field) => {
+                        writeln!(f, $format, $field)?;
+                    }
+                    None => writeln!(f)?,
+                }
+            };
+            ($stream:ident, $field:ident, $idx:ident) => {
+                write_simple_field!($stream, $field, $idx, "{}");
+            };
+        }
+        writeln!(f, "MINIDUMP_MAC_CRASH_INFO")?;
+        writeln!(f, "  num_records = {}", self.raw.len())?;
+
+        for i in 0..self.raw.len() {
+            writeln!(f)?;
+            writeln!(f, "  RECORD[{}] ", i)?;
+            write_simple_field!(f, version, i);
+            write_simple_field!(f, thread, i);
+            write_simple_field!(f, dialog_mode, i, "{:x}");
+            write_simple_field!(f, module_path, i);
+            write_simple_field!(f, message, i);
+            write_simple_field!(f, signature_string, i);
+            write_simple_field!(f, backtrace, i);
+            write_simple_field!(f, message2, i);
+            write_simple_field!(f, abort_cause, i, "{:x}");
+        }
+
+        writeln!(f)?;
+        Ok(())
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpMacBootargs {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MozMacosBootargsStream as u32;
+
+    fn read(
+        bytes: &[u8],
+        all: &[u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpMacBootargs, Error> {
+        let raw: md::MINIDUMP_MAC_BOOTARGS = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        // Better to keep the stream than to fail it over an unreadable string.
+        let mut offset = raw.bootargs as usize;
+        let bootargs = if raw.bootargs != 0 {
+            read_string_utf16(&mut offset, all, endian)
+        } else {
+            None
+        };
+        if raw.bootargs != 0 && bootargs.is_none() {
+            warn!("Couldn't read MozMacosBootargsStream's bootargs");
+        }
+        Ok(MinidumpMacBootargs { raw, bootargs })
+    }
+}
+
+impl MinidumpMacBootargs {
+    /// Write a human-readable description of this `MinidumpMacBootargs` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MINIDUMP_MAC_BOOTARGS
+  stream_type = {:#x}
+  bootargs    = {:#x}
+  (bootargs)  = {}
+
+",
+            self.raw.stream_type,
+            self.raw.bootargs,
+            self.bootargs.as_deref().unwrap_or("(null)"),
+        )
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpLinuxLsbRelease<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxLsbRelease as u32;
+
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        _endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpLinuxLsbRelease<'a>, Error> {
+        Ok(Self { data: bytes })
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpLinuxEnviron<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxEnviron as u32;
+
+    #[allow(clippy::single_match)]
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        _endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpLinuxEnviron<'a>, Error> {
+        Ok(Self { data: bytes })
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpLinuxProcStatus<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxProcStatus as u32;
+
+    #[allow(clippy::single_match)]
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        _endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpLinuxProcStatus<'a>, Error> {
+        Ok(Self { data: bytes })
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpLinuxCpuInfo<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::LinuxCpuInfo as u32;
+
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        _endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpLinuxCpuInfo<'a>, Error> {
+        Ok(Self { data: bytes })
+    }
+}
+
+impl<'a> MinidumpLinuxCpuInfo<'a> {
+    /// Get an iterator over the key-value pairs stored in the `/proc/cpuinfo` dump.
+    ///
+    /// Keys and values are `trim`ed of leading/trailing spaces, and if a key
+    /// or value was surrounded by quotes ("like this"), the quotes will be
+    /// stripped.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a LinuxOsStr, &'a LinuxOsStr)> {
+        linux_list_iter(self.data, b':')
+    }
+
+    /// Get the raw bytes of the `/proc/cpuinfo` dump.
+    pub fn raw_bytes(&self) -> Cow<'a, [u8]> {
+        Cow::Borrowed(self.data)
+    }
+}
+
+impl<'a> MinidumpLinuxEnviron<'a> {
+    /// Get an iterator over the `KEY=VALUE` pairs stored in the `/proc/self/environ` dump.
+    ///
+    /// Variables are separated by NULs, and split at their first `=`. Unlike
+    /// the other Linux streams, keys and values aren't trimmed or unquoted,
+    /// since environment values can legitimately contain either.
+    ///
+    /// Entries without an `=` (like the last one in a truncated capture) are
+    /// yielded with an empty value.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a LinuxOsStr, &'a LinuxOsStr)> {
+        LinuxOsStr::from_bytes(self.data)
+            .split(b'\0')
+            .filter(|entry| !entry.as_bytes().is_empty())
+            .map(|entry| entry.split_once(b'=').unwrap_or((entry, LinuxOsStr::new())))
+    }
+
+    /// The value of the variable `key`, if it's set.
+    ///
+    /// If `key` is set more than once, this is the first value, like `getenv`.
+    pub fn get(&self, key: &str) -> Option<&'a LinuxOsStr> {
+        self.iter()
+            .find(|(k, _)| k.as_bytes() == key.as_bytes())
+            .map(|(_, value)| value)
+    }
+
+    /// Write a human-readable description of every variable to `f`.
+    ///
+    /// This includes the values of every variable, which are likely to
+    /// contain secrets. Use [`MinidumpLinuxEnviron::print_filtered`][] for
+    /// output that's going to be logged.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        self.print_filtered(f, |_, _| true)
+    }
+
+    /// Write a human-readable description of the variables `filter` accepts to `f`.
+    ///
+    /// `filter` is passed each variable's key and value. The variables it
+    /// rejects are only counted, so an allowlist of known-safe variables
+    /// keeps everything else out of the output:
+    ///
+    /// ```rust,no_run
+    /// # use minidump::{Minidump, MinidumpLinuxEnviron};
+    /// # fn foo() -> Result<(), minidump::Error> {
+    /// const SAFE_VARS: &[&[u8]] = &[b"LANG", b"XDG_SESSION_TYPE"];
+    ///
+    /// let dump = Minidump::read_path("../testdata/test.dmp")?;
+    /// let environ = dump.get_stream::<MinidumpLinuxEnviron>()?;
+    /// environ
+    ///     .print_filtered(&mut std::io::stdout(), |key, _| {
+    ///         SAFE_VARS.contains(&key.as_bytes())
+    ///     })
+    ///     .unwrap();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn print_filtered<T, F>(&self, f: &mut T, mut filter: F) -> io::Result<()>
+    where
+        T: Write,
+        F: FnMut(&LinuxOsStr, &LinuxOsStr) -> bool,
+    {
+        writeln!(f, "MinidumpLinuxEnviron")?;
+        let mut omitted = 0;
+        for (key, value) in self.iter() {
+            if filter(key, value) {
+                writeln!(f, "  {}={}", key.to_string_lossy(), value.to_string_lossy())?;
+            } else {
+                omitted += 1;
+            }
+        }
+        if omitted > 0 {
+            writeln!(f, "  ({} variables omitted)", omitted)?;
+        }
+        writeln!(f)
+    }
+
+    /// Get the raw bytes of the `/proc/self/environ` dump.
+    pub fn raw_bytes(&self) -> Cow<'a, [u8]> {
+        Cow::Borrowed(self.data)
+    }
+}
+
+impl<'a> MinidumpLinuxProcStatus<'a> {
+    /// Get an iterator over the key-value pairs stored in the `/proc/self/status` dump.
+    ///
+    /// Keys and values are `trim`ed of leading/trailing spaces, and if a key
+    /// or value was surrounded by quotes ("like this"), the quotes will be
+    /// stripped.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a LinuxOsStr, &'a LinuxOsStr)> {
+        linux_list_iter(self.data, b':')
+    }
+
+    /// Get the raw bytes of the `/proc/self/status` dump.
+    pub fn raw_bytes(&self) -> Cow<'a, [u8]> {
+        Cow::Borrowed(self.data)
+    }
+
+    /// The peak virtual memory size of the process (`VmPeak`), in bytes.
+    pub fn vm_peak(&self) -> Option<u64> {
+        self.size_field("VmPeak")
+    }
+
+    /// The virtual memory size of the process (`VmSize`), in bytes.
+    pub fn vm_size(&self) -> Option<u64> {
+        self.size_field("VmSize")
+    }
+
+    /// The resident set size of the process (`VmRSS`), in bytes.
+    pub fn vm_rss(&self) -> Option<u64> {
+        self.size_field("VmRSS")
+    }
+
+    /// How much of the process's memory was swapped out (`VmSwap`), in bytes.
+    pub fn vm_swap(&self) -> Option<u64> {
+        self.size_field("VmSwap")
+    }
+
+    /// The number of threads in the process (`Threads`).
+    pub fn threads(&self) -> Option<u32> {
+        self.number_field("Threads")
+    }
+
+    /// The number of file descriptor slots currently allocated (`FDSize`).
+    pub fn fd_size(&self) -> Option<u32> {
+        self.number_field("FDSize")
+    }
+
+    /// The set of ignored signals (`SigIgn`).
+    ///
+    /// Bit `n - 1` is set if signal `n` is ignored.
+    pub fn sig_ignored(&self) -> Option<u64> {
+        self.sigset_field("SigIgn")
+    }
+
+    /// The set of caught signals (`SigCgt`).
+    ///
+    /// Bit `n - 1` is set if signal `n` has a handler.
+    pub fn sig_caught(&self) -> Option<u64> {
+        self.sigset_field("SigCgt")
+    }
+
+    /// The thread group id of the process (`Tgid`), which is what userspace
+    /// calls its pid.
+    pub fn tgid(&self) -> Option<u32> {
+        self.number_field("Tgid")
+    }
+
+    /// The id of the thread that read `/proc/self/status` (`Pid`).
+    pub fn pid(&self) -> Option<u32> {
+        self.number_field("Pid")
+    }
+
+    /// The pid of the process's parent (`PPid`).
+    pub fn ppid(&self) -> Option<u32> {
+        self.number_field("PPid")
+    }
+
+    /// The value of the first field named `key`, as a string.
+    fn field(&self, key: &str) -> Option<&'a str> {
+        self.iter()
+            .find(|(k, _)| k.as_bytes() == key.as_bytes())
+            .and_then(|(_, v)| v.to_str().ok())
+    }
+
+    fn number_field<T: str::FromStr>(&self, key: &str) -> Option<T> {
+        self.field(key)?.parse().ok()
+    }
+
+    /// Parse a size like `1234 kB`. The kernel always uses kB for these, but
+    /// accept a bare number of bytes too.
+    fn size_field(&self, key: &str) -> Option<u64> {
+        let mut words = self.field(key)?.split_whitespace();
+        let value = words.next()?.parse::<u64>().ok()?;
+        match words.next() {
+            None => Some(value),
+            Some(unit) if unit.eq_ignore_ascii_case("kB") => value.checked_mul(1024),
+            Some(_) => None,
+        }
+    }
+
+    fn sigset_field(&self, key: &str) -> Option<u64> {
+        u64::from_str_radix(self.field(key)?, 16).ok()
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpLinuxLimits<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::MozLinuxLimits as u32;
+
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        _endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpLinuxLimits<'a>, Error> {
+        // Each line is a name (which contains spaces), a soft limit, a hard limit and
+        // an optional unit, like this:
+        //
+        // Max open files            1024                 1048576              files
+        //
+        // Names never contain numbers, so a limit starts at the first value that looks
+        // like one. This also skips the header line.
+        let is_value = |word: &str| word == "unlimited" || word.parse::<u64>().is_ok();
+        let parse_value = |word: &str| word.parse::<u64>().ok();
+        let limits = bytes
+            .split(|&b| b == b'\n')
+            .filter_map(|line| std::str::from_utf8(line).ok())
+            .filter_map(|line| {
+                let mut words = line.split_whitespace();
+                let soft = words.find(|word| is_value(word))?;
+                let name = line[..line.find(soft)?].trim();
+                let hard = words.next().filter(|word| is_value(word))?;
+                if name.is_empty() {
+                    return None;
+                }
+                Some(MinidumpLinuxLimit {
+                    name,
+                    soft: parse_value(soft),
+                    hard: parse_value(hard),
+                    unit: words.next(),
+                })
+            })
+            .collect();
+        Ok(Self {
+            data: bytes,
+            limits,
+        })
+    }
+}
+
+impl<'a> MinidumpLinuxLimits<'a> {
+    /// Iterate over the limits, in the order they were stored in the minidump.
+    pub fn iter(&self) -> impl Iterator<Item = &MinidumpLinuxLimit<'a>> {
+        self.limits.iter()
+    }
+
+    /// The limit with the given `name` (like "Max open files"), if present.
+    pub fn get(&self, name: &str) -> Option<&MinidumpLinuxLimit<'a>> {
+        self.limits.iter().find(|limit| limit.name == name)
+    }
+
+    /// The most file descriptors the process can have open (`RLIMIT_NOFILE`).
+    pub fn open_files(&self) -> Option<&MinidumpLinuxLimit<'a>> {
+        self.get("Max open files")
+    }
+
+    /// The most virtual memory the process can map, in bytes (`RLIMIT_AS`).
+    pub fn address_space(&self) -> Option<&MinidumpLinuxLimit<'a>> {
+        self.get("Max address space")
+    }
+
+    /// The largest core dump the process can write, in bytes (`RLIMIT_CORE`).
+    pub fn core_file_size(&self) -> Option<&MinidumpLinuxLimit<'a>> {
+        self.get("Max core file size")
+    }
+
+    /// Get the raw bytes of the `/proc/self/limits` dump.
+    pub fn raw_bytes(&self) -> Cow<'a, [u8]> {
+        Cow::Borrowed(self.data)
+    }
+
+    /// Write a human-readable description of this `MinidumpLinuxLimits` to `f`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        fn value(value: Option<u64>) -> Cow<'static, str> {
+            match value {
+                Some(value) => Cow::Owned(value.to_string()),
+                None => Cow::Borrowed("unlimited"),
+            }
+        }
+        writeln!(f, "MinidumpLinuxLimits")?;
+        for limit in &self.limits {
+            writeln!(
+                f,
+                "  {:25} soft = {:20} hard = {:20} {}",
+                limit.name,
+                value(limit.soft),
+                value(limit.hard),
+                limit.unit.unwrap_or(""),
+            )?;
+        }
+        writeln!(f)
+    }
+}
+
+impl<'a> MinidumpLinuxLsbRelease<'a> {
+    /// Get an iterator over the key-value pairs stored in the `/etc/lsb-release` dump.
+    ///
+    /// Keys and values are `trim`ed of leading/trailing spaces, and if a key
+    /// or value was surrounded by quotes ("like this"), the quotes will be
+    /// stripped.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a LinuxOsStr, &'a LinuxOsStr)> {
+        linux_list_iter(self.data, b'=')
+    }
+
+    /// Get the raw bytes of the `/etc/lsb-release` dump.
+    pub fn raw_bytes(&self) -> Cow<'a, [u8]> {
+        Cow::Borrowed(self.data)
+    }
+}
+
+fn systemtime_from_timestamp(timestamp: u64) -> Option<SystemTime> {
+    SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(timestamp))
+}
+
+impl XStateConfig {
+    /// Collect the enabled features of `raw`.
+    ///
+    /// Returns `None` if no features are enabled, which is what's written
+    /// when XSTATE isn't available.
+    pub fn from_raw(raw: &md::XSTATE_CONFIG_FEATURE_MSC_INFO) -> Option<XStateConfig> {
+        if raw.size_of_info == 0 || raw.enabled_features == 0 {
+            return None;
+        }
+        Some(XStateConfig {
+            context_size: raw.context_size,
+            features: raw.iter().collect(),
+        })
+    }
+
+    /// The offset and size of the feature `index`, if it's enabled.
+    pub fn feature(&self, index: md::XstateFeatureIndex) -> Option<md::XSTATE_FEATURE> {
+        self.features.get(&(index as usize)).copied()
+    }
+}
+
+impl MinidumpMiscInfo {
+    pub fn process_create_time(&self) -> Option<SystemTime> {
+        self.raw
+            .process_create_time()
+            .and_then(|t| systemtime_from_timestamp(*t as u64))
+    }
+
+    /// The XSTATE features saved in thread contexts, if this is a
+    /// `MINIDUMP_MISC_INFO_5` with any.
+    pub fn xstate_config(&self) -> Option<XStateConfig> {
+        self.raw.xstate_data().and_then(XStateConfig::from_raw)
+    }
+
+    /// The process's cookie, if this is a `MINIDUMP_MISC_INFO_5` that has one.
+    ///
+    /// Windows uses this to encode pointers (like with `EncodePointer`).
+    pub fn process_cookie(&self) -> Option<u32> {
+        self.raw.process_cookie().copied()
+    }
+
+    /// Write a human-readable description of this `MinidumpMiscInfo` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        macro_rules! write_simple_field {
+            ($stream:ident, $field:ident, $format:literal) => {
+                write!(f, "  {:29}= ", stringify!($field))?;
+                match self.raw.$field() {
+                    Some($field) => {
+                        writeln!(f, $format, $field)?;
+                    }
+                    None => writeln!(f, "(invalid)")?,
+                }
+            };
+            ($stream:ident, $field:ident) => {
+                write_simple_field!($stream, $field, "{}");
+            };
+        }
+        writeln!(f, "MINIDUMP_MISC_INFO")?;
+
+        write_simple_field!(f, size_of_info);
+        write_simple_field!(f, flags1, "{:x}");
+        write_simple_field!(f, process_id);
+        write!(f, "  process_create_time          = ")?;
+        match self.raw.process_create_time() {
+            Some(&process_create_time) => {
+                writeln!(
+                    f,
+                    "{:#x} {}",
+                    process_create_time,
+                    format_time_t(process_create_time),
+                )?;
+            }
+            None => writeln!(f, "(invalid)")?,
+        }
+        write_simple_field!(f, process_user_time);
+        write_simple_field!(f, process_kernel_time);
+
+        write_simple_field!(f, processor_max_mhz);
+        write_simple_field!(f, processor_current_mhz);
+        write_simple_field!(f, processor_mhz_limit);
+        write_simple_field!(f, processor_max_idle_state);
+        write_simple_field!(f, processor_current_idle_state);
+
+        write_simple_field!(f, process_integrity_level);
+        write_simple_field!(f, process_execute_flags, "{:x}");
+        write_simple_field!(f, protected_process);
+        write_simple_field!(f, time_zone_id);
+
+        write!(f, "  time_zone                    = ")?;
+        match self.raw.time_zone() {
+            Some(time_zone) => {
+                writeln!(f)?;
+                writeln!(f, "    bias          = {}", time_zone.bias)?;
+                writeln!(
+                    f,
+                    "    standard_name = {}",
+                    utf16_to_string(&time_zone.standard_name[..])
+                        .unwrap_or_else(|| String::from("(invalid)"))
+                )?;
+                writeln!(
+                    f,
+                    "    standard_date = {}",
+                    format_system_time(&time_zone.standard_date)
+                )?;
+                writeln!(f, "    standard_bias = {}", time_zone.standard_bias)?;
+                writeln!(
+                    f,
+                    "    daylight_name = {}",
+                    utf16_to_string(&time_zone.daylight_name[..])
+                        .unwrap_or_else(|| String::from("(invalid)"))
+                )?;
+                writeln!(
+                    f,
+                    "    daylight_date = {}",
+                    format_system_time(&time_zone.daylight_date)
+                )?;
+                writeln!(f, "    daylight_bias = {}", time_zone.daylight_bias)?;
+            }
+            None => writeln!(f, "(invalid)")?,
+        }
+
+        write!(f, "  build_string                 = ")?;
+        match self
+            .raw
+            .build_string()
+            .and_then(|string| utf16_to_string(&string[..]))
+        {
+            Some(build_string) => writeln!(f, "{}", build_string)?,
+            None => writeln!(f, "(invalid)")?,
+        }
+        write!(f, "  dbg_bld_str                  = ")?;
+        match self
+            .raw
+            .dbg_bld_str()
+            .and_then(|string| utf16_to_string(&string[..]))
+        {
+            Some(dbg_bld_str) => writeln!(f, "{}", dbg_bld_str)?,
+            None => writeln!(f, "(invalid)")?,
+        }
+
+        write!(f, "  xstate_data                  = ")?;
+        match (self.raw.xstate_data(), self.xstate_config()) {
+            (Some(_), None) => writeln!(f, "(no features)")?,
+            (Some(_), Some(config)) => {
+                writeln!(f, "context_size {}", config.context_size)?;
+                for (&i, feature) in &config.features {
+                    if let Some(feature) = md::XstateFeatureIndex::from_index(i) {
+                        let feature_name = format!("{:?}", feature);
+                        write!(f, "    feature {:2} - {:22}: ", i, feature_name)?;
+                    } else {
+                        write!(f, "    feature {:2} - (unknown)           : ", i)?;
+                    }
+                    writeln!(f, " offset {:4}, size {:4}", feature.offset, feature.size)?;
+                }
+            }
+            (None, _) => writeln!(f, "(invalid)")?,
+        }
+
+        write_simple_field!(f, process_cookie);
+        writeln!(f)?;
+        Ok(())
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpBreakpadInfo {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::BreakpadInfoStream as u32;
+
+    fn read(
+        bytes: &[u8],
+        _all: &[u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpBreakpadInfo, Error> {
+        let raw: md::MINIDUMP_BREAKPAD_INFO = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let flags = md::BreakpadInfoValid::from_bits_truncate(raw.validity);
+        let dump_thread_id = if flags.contains(md::BreakpadInfoValid::DumpThreadId) {
+            Some(raw.dump_thread_id)
+        } else {
+            None
+        };
+        let requesting_thread_id = if flags.contains(md::BreakpadInfoValid::RequestingThreadId) {
+            Some(raw.requesting_thread_id)
+        } else {
+            None
+        };
+        Ok(MinidumpBreakpadInfo {
+            raw,
+            dump_thread_id,
+            requesting_thread_id,
+        })
+    }
+}
+
+fn option_or_invalid<T: fmt::LowerHex>(what: &Option<T>) -> Cow<'_, str> {
+    match *what {
+        Some(ref val) => Cow::Owned(format!("{:#x}", val)),
+        None => Cow::Borrowed("(invalid)"),
+    }
+}
+
+impl MinidumpBreakpadInfo {
+    /// Write a human-readable description of this `MinidumpBreakpadInfo` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MINIDUMP_BREAKPAD_INFO
+  validity             = {:#x}
+  dump_thread_id       = {}
+  requesting_thread_id = {}
+
+",
+            self.raw.validity,
+            option_or_invalid(&self.dump_thread_id),
+            option_or_invalid(&self.requesting_thread_id),
+        )?;
+        Ok(())
+    }
+}
+
+impl CrashReason {
+    /// The decoded code and subcode of a Mac/iOS `EXC_RESOURCE` exception.
+    ///
+    /// Returns `None` for other crash reasons, or if the resource flavor is unknown.
+    pub fn mac_resource_info(&self) -> Option<err::ExceptionCodeMacResourceInfo> {
+        match *self {
+            CrashReason::MacResource(ty, code, subcode) => {
+                err::ExceptionCodeMacResourceInfo::from_codes(ty, code, subcode)
+            }
+            _ => None,
+        }
+    }
+
+    /// The decoded code and subcode of a Mac/iOS `EXC_GUARD` exception.
+    ///
+    /// Returns `None` for other crash reasons, or if the guard flavor is unknown.
+    pub fn mac_guard_info(&self) -> Option<err::ExceptionCodeMacGuardInfo> {
+        match *self {
+            CrashReason::MacGuard(ty, code, subcode) => {
+                err::ExceptionCodeMacGuardInfo::from_codes(ty, code, subcode)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get a `CrashReason` from a `MINIDUMP_EXCEPTION_STREAM` for a given `Os`.
+    fn from_exception(raw: &md::MINIDUMP_EXCEPTION_STREAM, os: Os, cpu: Cpu) -> CrashReason {
+        let record = &raw.exception_record;
+        let exception_code = record.exception_code;
+        let exception_flags = record.exception_flags;
+
+        let reason = match os {
+            Os::MacOs | Os::Ios => Self::from_mac_exception(raw, cpu),
+            Os::Linux | Os::Android => Self::from_linux_exception(raw, cpu),
+            Os::Windows => Self::from_windows_exception(raw, cpu),
+            _ => None,
+        };
+
+        // Default to a totally generic unknown error
+        reason.unwrap_or(CrashReason::Unknown(exception_code, exception_flags))
+    }
+
+    /// Heuristically identifies what kind of windows exception code this is.
+    ///
+    /// Augments [`CrashReason::from_windows_error`] by also including
+    /// `ExceptionCodeWindows`. Appropriate for an actual crash reason.
+    pub fn from_windows_code(exception_code: u32) -> CrashReason {
+        if let Some(err) = err::ExceptionCodeWindows::from_u32(exception_code) {
+            Self::WindowsGeneral(err)
+        } else {
+            Self::from_windows_error(exception_code)
+        }
+    }
+
+    /// Heuristically identifies what kind of windows error code this is.
+    ///
+    /// Appropriate for things like LastErrorValue() which may be non-fatal.
+    pub fn from_windows_error(error_code: u32) -> CrashReason {
+        if let Some(err) = err::WinErrorWindows::from_u32(error_code) {
+            Self::WindowsWinError(err)
+        } else if let Some(err) = err::NtStatusWindows::from_u32(error_code) {
+            Self::WindowsNtStatus(err)
+        } else if let Some(err) = Self::from_windows_error_with_facility(error_code) {
+            err
+        } else {
+            Self::WindowsUnknown(error_code)
+        }
+    }
+
+    pub fn from_windows_error_with_facility(error_code: u32) -> Option<CrashReason> {
+        static SEVERITY_MASK: u32 = 0xf0000000;
+        static FACILITY_MASK: u32 = 0x0fff0000;
+        static ERROR_MASK: u32 = 0x0000ffff;
+
+        if (error_code & SEVERITY_MASK) != 0 {
+            // This could be an NTSTATUS or HRESULT code of a specific facility
+            if let Some(facility) =
+                err::WinErrorFacilityWindows::from_u32((error_code & FACILITY_MASK) >> 16)
+            {
+                if let Some(error) = err::WinErrorWindows::from_u32(error_code & ERROR_MASK) {
+                    return Some(Self::WindowsWinErrorWithFacility(facility, error));
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn from_windows_exception(
+        raw: &md::MINIDUMP_EXCEPTION_STREAM,
+        _cpu: Cpu,
+    ) -> Option<CrashReason> {
+        let record = &raw.exception_record;
+        let mut reason = CrashReason::from_windows_code(record.exception_code);
+
+        // Refine the output for error codes that have more info
+        match WindowsExceptionParameters::from_record(record) {
+            Some(WindowsExceptionParameters::AccessViolation {
+                access: Some(ty), ..
+            }) => {
+                reason = CrashReason::WindowsAccessViolation(ty);
+            }
+            Some(WindowsExceptionParameters::InPageError {
+                access: Some(ty),
+                nt_status,
+                ..
+            }) => {
+                reason = CrashReason::WindowsInPageError(ty, nt_status);
+            }
+            Some(WindowsExceptionParameters::FastFail { code }) => {
+                reason = CrashReason::WindowsStackBufferOverrun(code);
+            }
+            _ => {
+                // Do nothing interesting
+            }
+        }
+
+        Some(reason)
+    }
+
+    pub fn from_mac_exception(
+        raw: &md::MINIDUMP_EXCEPTION_STREAM,
+        cpu: Cpu,
+    ) -> Option<CrashReason> {
+        use err::ExceptionCodeMac;
+
+        let record = &raw.exception_record;
+        let info = &record.exception_information;
+        let exception_code = record.exception_code;
+        let exception_flags = record.exception_flags;
+
+        // Default to just directly reporting this reason.
+        let mac_reason = err::ExceptionCodeMac::from_u32(exception_code)?;
+        let mut reason = CrashReason::MacGeneral(mac_reason, exception_flags);
+
+        // Refine the output for error codes that have more info
+        match mac_reason {
+            ExceptionCodeMac::EXC_BAD_ACCESS => {
+                if let Some(ty) = err::ExceptionCodeMacBadAccessKernType::from_u32(exception_flags)
+                {
+                    reason = CrashReason::MacBadAccessKern(ty);
+                } else {
+                    match cpu {
+                        Cpu::Arm64 => {
+                            if let Some(ty) =
+                                err::ExceptionCodeMacBadAccessArmType::from_u32(exception_flags)
+                            {
+                                reason = CrashReason::MacBadAccessArm(ty);
+                            }
+                        }
+                        Cpu::Ppc => {
+                            if let Some(ty) =
+                                err::ExceptionCodeMacBadAccessPpcType::from_u32(exception_flags)
+                            {
+                                reason = CrashReason::MacBadAccessPpc(ty);
+                            }
+                        }
+                        Cpu::X86 | Cpu::X86_64 => {
+                            if let Some(ty) =
+                                err::ExceptionCodeMacBadAccessX86Type::from_u32(exception_flags)
+                            {
+                                reason = CrashReason::MacBadAccessX86(ty);
+                            }
+                        }
+                        _ => {
+                            // Do nothing
+                        }
+                    }
+                }
+            }
+            ExceptionCodeMac::EXC_BAD_INSTRUCTION => match cpu {
+                Cpu::Arm64 => {
+                    if let Some(ty) =
+                        err::ExceptionCodeMacBadInstructionArmType::from_u32(exception_flags)
+                    {
+                        reason = CrashReason::MacBadInstructionArm(ty);
+                    }
+                }
+                Cpu::Ppc => {
+                    if let Some(ty) =
+                        err::ExceptionCodeMacBadInstructionPpcType::from_u32(exception_flags)
+                    {
+                        reason = CrashReason::MacBadInstructionPpc(ty);
+                    }
+                }
+                Cpu::X86 | Cpu::X86_64 => {
+                    if let Some(ty) =
+                        err::ExceptionCodeMacBadInstructionX86Type::from_u32(exception_flags)
+                    {
+                        reason = CrashReason::MacBadInstructionX86(ty);
+                    }
+                }
+                _ => {
+                    // Do nothing
+                }
+            },
+            ExceptionCodeMac::EXC_ARITHMETIC => match cpu {
+                Cpu::Arm64 => {
+                    if let Some(ty) =
+                        err::ExceptionCodeMacArithmeticArmType::from_u32(exception_flags)
+                    {
+                        reason = CrashReason::MacArithmeticArm(ty);
+                    }
+                }
+                Cpu::Ppc => {
+                    if let Some(ty) =
+                        err::ExceptionCodeMacArithmeticPpcType::from_u32(exception_flags)
+                    {
+                        reason = CrashReason::MacArithmeticPpc(ty);
+                    }
+                }
+                Cpu::X86 | Cpu::X86_64 => {
+                    if let Some(ty) =
+                        err::ExceptionCodeMacArithmeticX86Type::from_u32(exception_flags)
+                    {
+                        reason = CrashReason::MacArithmeticX86(ty);
+                    }
+                }
+                _ => {
+                    // Do nothing
+                }
+            },
+            ExceptionCodeMac::EXC_SOFTWARE => {
+                if let Some(ty) = err::ExceptionCodeMacSoftwareType::from_u32(exception_flags) {
+                    reason = CrashReason::MacSoftware(ty);
+                }
+            }
+            ExceptionCodeMac::EXC_BREAKPOINT => match cpu {
+                Cpu::Arm64 => {
+                    if let Some(ty) =
+                        err::ExceptionCodeMacBreakpointArmType::from_u32(exception_flags)
+                    {
+                        reason = CrashReason::MacBreakpointArm(ty);
+                    }
+                }
+                Cpu::Ppc => {
+                    if let Some(ty) =
+                        err::ExceptionCodeMacBreakpointPpcType::from_u32(exception_flags)
+                    {
+                        reason = CrashReason::MacBreakpointPpc(ty);
+                    }
+                }
+                Cpu::X86 | Cpu::X86_64 => {
+                    if let Some(ty) =
+                        err::ExceptionCodeMacBreakpointX86Type::from_u32(exception_flags)
+                    {
+                        reason = CrashReason::MacBreakpointX86(ty);
+                    }
+                }
+                _ => {
+                    // Do nothing
+                }
+            },
+            ExceptionCodeMac::EXC_RESOURCE => {
+                if let Some(ty) =
+                    err::ExceptionCodeMacResourceType::from_u32((exception_flags >> 29) & 0x7)
+                {
+                    reason = CrashReason::MacResource(ty, info[1], info[2]);
+                }
+            }
+            ExceptionCodeMac::EXC_GUARD => {
+                if let Some(ty) =
+                    err::ExceptionCodeMacGuardType::from_u32((exception_flags >> 29) & 0x7)
+                {
+                    reason = CrashReason::MacGuard(ty, info[1], info[2]);
+                }
+            }
+            _ => {
+                // Do nothing
+            }
+        }
+        Some(reason)
+    }
+
+    pub fn from_linux_exception(
+        raw: &md::MINIDUMP_EXCEPTION_STREAM,
+        _cpu: Cpu,
+    ) -> Option<CrashReason> {
+        let record = &raw.exception_record;
+        let exception_code = record.exception_code;
+        let exception_flags = record.exception_flags;
+
+        let linux_reason = err::ExceptionCodeLinux::from_u32(exception_code)?;
+        let mut reason = CrashReason::LinuxGeneral(linux_reason, exception_flags);
+        // Refine the output for error codes that have more info
+        match linux_reason {
+            err::ExceptionCodeLinux::SIGILL => {
+                if let Some(ty) = err::ExceptionCodeLinuxSigillKind::from_u32(exception_flags) {
+                    reason = CrashReason::LinuxSigill(ty);
+                }
+            }
+            err::ExceptionCodeLinux::SIGTRAP => {
+                if let Some(ty) = err::ExceptionCodeLinuxSigtrapKind::from_u32(exception_flags) {
+                    reason = CrashReason::LinuxSigtrap(ty);
+                }
+            }
+            err::ExceptionCodeLinux::SIGFPE => {
+                if let Some(ty) = err::ExceptionCodeLinuxSigfpeKind::from_u32(exception_flags) {
+                    reason = CrashReason::LinuxSigfpe(ty);
+                }
+            }
+            err::ExceptionCodeLinux::SIGSEGV => {
+                if let Some(ty) = err::ExceptionCodeLinuxSigsegvKind::from_u32(exception_flags) {
+                    reason = CrashReason::LinuxSigsegv(ty);
+                }
+            }
+            err::ExceptionCodeLinux::SIGBUS => {
+                if let Some(ty) = err::ExceptionCodeLinuxSigbusKind::from_u32(exception_flags) {
+                    reason = CrashReason::LinuxSigbus(ty);
+                }
+            }
+            err::ExceptionCodeLinux::SIGSYS => {
+                if let Some(ty) = err::ExceptionCodeLinuxSigsysKind::from_u32(exception_flags) {
+                    reason = CrashReason::LinuxSigsys(ty);
+                }
+            }
+            _ => {
+                // No refinements
+            }
+        }
+        Some(reason)
+    }
+}
+
+impl fmt::Display for CrashReason {
+    /// A string describing the crash reason.
+    ///
+    /// This is OS- and possibly CPU-specific.
+    /// For example, "EXCEPTION_ACCESS_VIOLATION" (Windows),
+    /// "EXC_BAD_ACCESS / KERN_INVALID_ADDRESS" (Mac OS X), "SIGSEGV"
+    /// (other Unix).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CrashReason::*;
+
+        fn write_nt_status(f: &mut fmt::Formatter<'_>, raw_nt_status: u64) -> fmt::Result {
+            let nt_status = err::NtStatusWindows::from_u64(raw_nt_status);
+            if let Some(nt_status) = nt_status {
+                write!(f, "{:?}", nt_status)
+            } else {
+                write!(f, "{:#010x}", raw_nt_status)
+            }
+        }
+
+        fn write_fast_fail(f: &mut fmt::Formatter<'_>, raw_fast_fail: u64) -> fmt::Result {
+            let fast_fail = err::FastFailCode::from_u64(raw_fast_fail);
+            if let Some(fast_fail) = fast_fail {
+                write!(f, "{:?}", fast_fail)
+            } else {
+                write!(f, "{:#010x}", raw_fast_fail)
+            }
+        }
+
+        fn write_exc_resource(
+            f: &mut fmt::Formatter<'_>,
+            ex: err::ExceptionCodeMacResourceType,
+            code: u64,
+            subcode: u64,
+        ) -> fmt::Result {
+            use err::ExceptionCodeMacResourceInfo::*;
+
+            write!(f, "EXC_RESOURCE / {:?} / ", ex)?;
+            match err::ExceptionCodeMacResourceInfo::from_codes(ex, code, subcode) {
+                Some(Cpu {
+                    flavor,
+                    interval_seconds,
+                    cpu_limit_percent,
+                    cpu_consumed_percent,
+                }) => write!(
+                    f,
+                    "{:?} interval: {}s CPU limit: {}% CPU consumed: {}%",
+                    flavor, interval_seconds, cpu_limit_percent, cpu_consumed_percent
+                ),
+                Some(Wakeups {
+                    flavor,
+                    interval_seconds,
+                    wakeups_permitted,
+                    wakeups_observed,
+                }) => write!(
+                    f,
+                    "{:?} interval: {}s wakeups permitted: {} wakeups observed: {}",
+                    flavor, interval_seconds, wakeups_permitted, wakeups_observed
+                ),
+                Some(Memory {
+                    flavor,
+                    high_watermark_limit_mib,
+                }) => write!(
+                    f,
+                    "{:?} high watermark limit: {}MiB",
+                    flavor, high_watermark_limit_mib
+                ),
+                Some(Io {
+                    flavor,
+                    interval_seconds,
+                    io_limit_percent,
+                    io_observed_percent,
+                }) => write!(
+                    f,
+                    "{:?} interval: {}s I/O limit: {}% I/O observed: {}%",
+                    flavor, interval_seconds, io_limit_percent, io_observed_percent
+                ),
+                Some(Threads {
+                    flavor,
+                    high_watermark_limit,
+                }) => write!(
+                    f,
+                    "{:?} high watermark limit: {}",
+                    flavor, high_watermark_limit
+                ),
+                None => write!(f, "{:#018x} / {:#018x}", code, subcode),
+            }
+        }
+
+        fn write_exc_guard(
+            f: &mut fmt::Formatter<'_>,
+            ex: err::ExceptionCodeMacGuardType,
+            code: u64,
+            subcode: u64,
+        ) -> fmt::Result {
+            use err::ExceptionCodeMacGuardInfo::*;
+
+            write!(f, "EXC_GUARD / {:?}", ex)?;
+            match err::ExceptionCodeMacGuardInfo::from_codes(ex, code, subcode) {
+                Some(Unknown) => Ok(()),
+                Some(MachPort {
+                    flavor,
+                    port_name,
+                    guard_identifier,
+                }) => write!(
+                    f,
+                    " / {:?} on port {} guard identifier: {:#x}",
+                    flavor, port_name, guard_identifier
+                ),
+                Some(Fd {
+                    flavor,
+                    fd,
+                    guard_identifier,
+                }) => write!(
+                    f,
+                    " / {:?} on fd {} guard identifier: {:#x}",
+                    flavor, fd, guard_identifier
+                ),
+                Some(User {
+                    namespace,
+                    reason_code,
+                }) => write!(
+                    f,
+                    " / namespace: {} reason code: {:#x}",
+                    namespace, reason_code
+                ),
+                Some(Vn {
+                    flavor,
+                    pid,
+                    guard_identifier,
+                }) => write!(
+                    f,
+                    " / {:?} by pid {} guard identifier: {:#x}",
+                    flavor, pid, guard_identifier
+                ),
+                Some(VirtMemory { flavor, offset }) => {
+                    write!(f, " / {:?} offset: {:#x}", flavor, offset)
+                }
+                None => write!(f, " / {:#018x} / {:#018x}", code, subcode),
+            }
+        }
+
+        fn write_signal(
+            f: &mut fmt::Formatter<'_>,
+            ex: err::ExceptionCodeLinux,
+            flags: u32,
+        ) -> fmt::Result {
+            if let Some(si_code) = err::ExceptionCodeLinuxSicode::from_u32(flags) {
+                if si_code == err::ExceptionCodeLinuxSicode::SI_USER {
+                    write!(f, "{:?}", ex)
+                } else {
+                    write!(f, "{:?} / {:?}", ex, si_code)
+                }
+            } else {
+                write!(f, "{:?} / {:#010x}", ex, flags)
+            }
+        }
+
+        // OK this is kinda a gross hack but I *really* don't want
+        // to write out all these strings again, so let's just lean on Debug
+        // repeating the name of the enum variant!
+        match *self {
+            // ======================== Mac/iOS ============================
+
+            // These codes get special messages
+            MacGeneral(err::ExceptionCodeMac::SIMULATED, _) => write!(f, "Simulated Exception"),
+
+            // Thse codes just repeat their names
+            MacGeneral(ex, flags) => write!(f, "{:?} / {:#010x}", ex, flags),
+            MacBadAccessKern(ex) => write!(f, "EXC_BAD_ACCESS / {:?}", ex),
+            MacBadAccessArm(ex) => write!(f, "EXC_BAD_ACCESS / {:?}", ex),
+            MacBadAccessPpc(ex) => write!(f, "EXC_BAD_ACCESS / {:?}", ex),
+            MacBadAccessX86(ex) => write!(f, "EXC_BAD_ACCESS / {:?}", ex),
+            MacBadInstructionArm(ex) => write!(f, "EXC_BAD_INSTRUCTION / {:?}", ex),
+            MacBadInstructionPpc(ex) => write!(f, "EXC_BAD_INSTRUCTION / {:?}", ex),
+            MacBadInstructionX86(ex) => write!(f, "EXC_BAD_INSTRUCTION / {:?}", ex),
+            MacArithmeticArm(ex) => write!(f, "EXC_ARITHMETIC / {:?}", ex),
+            MacArithmeticPpc(ex) => write!(f, "EXC_ARITHMETIC / {:?}", ex),
+            MacArithmeticX86(ex) => write!(f, "EXC_ARITHMETIC / {:?}", ex),
+            MacSoftware(ex) => write!(f, "EXC_SOFTWARE / {:?}", ex),
+            MacBreakpointArm(ex) => write!(f, "EXC_BREAKPOINT / {:?}", ex),
+            MacBreakpointPpc(ex) => write!(f, "EXC_BREAKPOINT / {:?}", ex),
+            MacBreakpointX86(ex) => write!(f, "EXC_BREAKPOINT / {:?}", ex),
+            MacResource(ex, code, subcode) => write_exc_resource(f, ex, code, subcode),
+            MacGuard(ex, code, subcode) => write_exc_guard(f, ex, code, subcode),
+
+            // ===================== Linux/Android =========================
+
+            // These codes just repeat their names
+            LinuxGeneral(ex, flags) => write_signal(f, ex, flags),
+            LinuxSigill(ex) => write!(f, "SIGILL / {:?}", ex),
+            LinuxSigtrap(ex) => write!(f, "SIGTRAP / {:?}", ex),
+            LinuxSigbus(ex) => write!(f, "SIGBUS / {:?}", ex),
+            LinuxSigfpe(ex) => write!(f, "SIGFPE / {:?}", ex),
+            LinuxSigsegv(ex) => write!(f, "SIGSEGV / {:?}", ex),
+            LinuxSigsys(ex) => write!(f, "SIGSYS / {:?}", ex),
+
+            // ======================== Windows =============================
+
+            // These codes get special messages
+            WindowsGeneral(err::ExceptionCodeWindows::OUT_OF_MEMORY) => write!(f, "Out of Memory"),
+            WindowsGeneral(err::ExceptionCodeWindows::UNHANDLED_CPP_EXCEPTION) => {
+                write!(f, "Unhandled C++ Exception")
+            }
+            WindowsGeneral(err::ExceptionCodeWindows::SIMULATED) => {
+                write!(f, "Simulated Exception")
+            }
+            // These codes just repeat their names
+            WindowsGeneral(ex) => write!(f, "{:?}", ex),
+            WindowsWinError(winerror) => write!(f, "{:?}", winerror),
+            WindowsWinErrorWithFacility(facility, winerror) => {
+                write!(f, "{:?} / {:?}", facility, winerror)
+            }
+            WindowsNtStatus(nt_status) => write_nt_status(f, nt_status as _),
+            WindowsAccessViolation(ex) => write!(f, "EXCEPTION_ACCESS_VIOLATION_{:?}", ex),
+            WindowsInPageError(ex, nt_status) => {
+                write!(f, "EXCEPTION_IN_PAGE_ERROR_{:?} / ", ex)?;
+                write_nt_status(f, nt_status)
+            }
+            WindowsStackBufferOverrun(fast_fail) => {
+                write!(f, "EXCEPTION_STACK_BUFFER_OVERRUN / ")?;
+                write_fast_fail(f, fast_fail)
+            }
+            WindowsUnknown(code) => write!(f, "unknown {:#010x}", code),
+
+            Unknown(code, flags) => write!(f, "unknown {:#010x} / {:#010x}", code, flags),
+        }
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpException<'a> {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::ExceptionStream as u32;
+
+    fn read(
+        bytes: &'a [u8],
+        all: &'a [u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<Self, Error> {
+        let raw: md::MINIDUMP_EXCEPTION_STREAM = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let context = location_slice(all, &raw.thread_context).ok();
+        let thread_id = raw.thread_id;
+        Ok(MinidumpException {
+            raw,
+            thread_id,
+            context,
+            endian,
+        })
+    }
+}
+
+impl<'a> MinidumpException<'a> {
+    /// Get the cpu context of the crashing (or otherwise minidump-requesting) thread.
+    ///
+    /// CPU contexts are a platform-specific format, so SystemInfo is required
+    /// to reliably parse them. We used to use heuristics to avoid this requirement,
+    /// but this made us too brittle to otherwise-backwards-compatible additions
+    /// to the format.
+    ///
+    /// MiscInfo can contain additional details on the cpu context's format, but
+    /// is optional because those details can be safely ignored (at the cost of
+    /// being unable to parse some very obscure cpu state).
+    pub fn context(
+        &self,
+        system_info: &MinidumpSystemInfo,
+        misc: Option<&MinidumpMiscInfo>,
+    ) -> Option<Cow<MinidumpContext>> {
+        MinidumpContext::read(self.context?, self.endian, system_info, misc)
+            .ok()
+            .map(Cow::Owned)
+    }
+
+    /// Get the registers saved in the extended part of the exception's context,
+    /// like the AVX registers of an amd64 context.
+    ///
+    /// Unlike for [`context`][Self::context], `misc` is needed to find these.
+    pub fn extended_state(
+        &self,
+        system_info: &MinidumpSystemInfo,
+        misc: Option<&MinidumpMiscInfo>,
+    ) -> Option<MinidumpExtendedState> {
+        MinidumpExtendedState::read(self.context?, self.endian, system_info, misc)
+    }
+
+    /// The exception's parameters (the used part of `exception_information`).
+    ///
+    /// What these mean depends on the exception code. A `number_parameters`
+    /// too big for `exception_information` is treated as all of it.
+    pub fn parameters(&self) -> &[u64] {
+        let record = &self.raw.exception_record;
+        let count = (record.number_parameters as usize).min(record.exception_information.len());
+        &record.exception_information[..count]
+    }
+
+    /// Get the address that "caused" the crash.
+    ///
+    /// The meaning of this value depends on the kind of crash this was.
+    ///
+    /// By default, it's the instruction pointer at the time of the crash.
+    /// However, if the crash was caused by an illegal memory access, the
+    /// the address would be the memory address.
+    ///
+    /// So for instance, if you crashed from dereferencing a null pointer,
+    /// the crash_address will be 0 (or close to it, due to offsets).
+    pub fn get_crash_address(&self, os: Os, cpu: Cpu) -> u64 {
+        self.get_nested_crash_address(&self.raw.exception_record, os, cpu)
+    }
+
+    /// Like [`get_crash_address`][Self::get_crash_address], but for `record`,
+    /// one of this exception's [nested exceptions][Self::nested_exceptions].
+    pub fn get_nested_crash_address(
+        &self,
+        record: &md::MINIDUMP_EXCEPTION,
+        os: Os,
+        cpu: Cpu,
+    ) -> u64 {
+        let addr = match (
+            os,
+            err::ExceptionCodeWindows::from_u32(record.exception_code),
+        ) {
+            (Os::Windows, Some(err::ExceptionCodeWindows::EXCEPTION_ACCESS_VIOLATION))
+            | (Os::Windows, Some(err::ExceptionCodeWindows::EXCEPTION_IN_PAGE_ERROR))
+                if record.number_parameters >= 2 =>
+            {
+                record.exception_information[1]
+            }
+            _ => record.exception_address,
+        };
+
+        // Sometimes on 32-bit these values can be incorrectly sign-extended,
+        // so mask and zero-extend them here.
+        match cpu.pointer_width() {
+            PointerWidth::Bits32 => addr as u32 as u64,
+            _ => addr,
+        }
+    }
+
+    /// Get the crash reason for an exception.
+    ///
+    /// The returned value reflects our best attempt to recover a
+    /// "native" error for the crashing system based on the OS and
+    /// things like raw error codes.
+    ///
+    /// This is an imperfect process, because OSes may have overlapping
+    /// error types (e.g. WinError and NTSTATUS overlap, so we have to
+    /// pick one arbirarily).
+    ///
+    /// The raw error codes can be extracted from [MinidumpException::raw][].
+    pub fn get_crash_reason(&self, os: Os, cpu: Cpu) -> CrashReason {
+        CrashReason::from_exception(&self.raw, os, cpu)
+    }
+
+    /// Like [`get_crash_reason`][Self::get_crash_reason], but for `record`,
+    /// one of this exception's [nested exceptions][Self::nested_exceptions].
+    pub fn get_nested_crash_reason(
+        &self,
+        record: &md::MINIDUMP_EXCEPTION,
+        os: Os,
+        cpu: Cpu,
+    ) -> CrashReason {
+        let raw = md::MINIDUMP_EXCEPTION_STREAM {
+            exception_record: record.clone(),
+            ..self.raw.clone()
+        };
+        CrashReason::from_exception(&raw, os, cpu)
+    }
+
+    /// Follow the chain of exception records nested in this one.
+    ///
+    /// A Windows exception record can point to another record (with its
+    /// `exception_record` field) when it was raised while that exception was
+    /// being dispatched: a C++ exception translated into an SEH exception, or
+    /// an `EXCEPTION_NONCONTINUABLE_EXCEPTION` raised by continuing a
+    /// noncontinuable one, for instance. Only the outermost record is in the
+    /// exception stream. The rest are in the crashed process's memory, and can
+    /// only be read if the minidump captured that memory.
+    ///
+    /// Returns the nested records from the outermost to the innermost, not
+    /// including the exception stream's own record. The chain ends at the first
+    /// null or unreadable pointer, or after [`MAX_NESTED_EXCEPTIONS`] records.
+    pub fn nested_exceptions(
+        &self,
+        memory_list: &UnifiedMemoryList,
+        cpu: Cpu,
+    ) -> Vec<md::MINIDUMP_EXCEPTION> {
+        let width = cpu.pointer_width();
+        let mut records = Vec::new();
+        let mut seen = Vec::new();
+        let mut address = self.raw.exception_record.exception_record;
+        while records.len() < MAX_NESTED_EXCEPTIONS {
+            if width == PointerWidth::Bits32 {
+                // Like the crash address, this may have been sign-extended.
+                address = address as u32 as u64;
+            }
+            if address == 0 || seen.contains(&address) {
+                break;
+            }
+            seen.push(address);
+            let record = memory_list
+                .memory_at_address(address)
+                .and_then(|memory| read_exception_record(&memory, address, width));
+            match record {
+                Some(record) => {
+                    address = record.exception_record;
+                    records.push(record);
+                }
+                None => break,
+            }
+        }
+        records
+    }
+
+    /// Decode the exception's parameters, for Windows exception codes that
+    /// document them.
+    ///
+    /// Returns `None` for other operating systems, whose exception streams
+    /// don't use the parameters this way.
+    pub fn windows_parameters(&self, os: Os) -> Option<WindowsExceptionParameters> {
+        match os {
+            Os::Windows => WindowsExceptionParameters::from_record(&self.raw.exception_record),
+            _ => None,
+        }
+    }
+
+    /// The id of the thread that caused the crash (or otherwise requested
+    /// the minidump, even if there wasn't actually a crash).
+    pub fn get_crashing_thread_id(&self) -> u32 {
+        self.thread_id
+    }
+
+    /// Write a human-readable description of this `MinidumpException` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(
+        &self,
+        f: &mut T,
+        system: Option<&MinidumpSystemInfo>,
+        misc: Option<&MinidumpMiscInfo>,
+    ) -> io::Result<()> {
+        write!(
+            f,
+            "MINIDUMP_EXCEPTION
+  thread_id                                  = {:#x}
+  exception_record.exception_code            = {:#x}
+  exception_record.exception_flags           = {:#x}
+  exception_record.exception_record          = {:#x}
+  exception_record.exception_address         = {:#x}
+  exception_record.number_parameters         = {}
+",
+            self.thread_id,
+            self.raw.exception_record.exception_code,
+            self.raw.exception_record.exception_flags,
+            self.raw.exception_record.exception_record,
+            self.raw.exception_record.exception_address,
+            self.raw.exception_record.number_parameters,
+        )?;
+        for (i, parameter) in self.parameters().iter().enumerate() {
+            writeln!(
+                f,
+                "  exception_record.exception_information[{:2}] = {:#x}",
+                i, parameter
+            )?;
+        }
+        write!(
+            f,
+            "  thread_context.data_size                   = {}
+  thread_context.rva                         = {:#x}
+",
+            self.raw.thread_context.data_size, self.raw.thread_context.rva
+        )?;
+        if let Some(system_info) = system {
+            if let Some(context) = self.context(system_info, misc) {
+                writeln!(f)?;
+                context.print(f)?;
+            } else {
+                write!(
+                    f,
+                    "  (no context)
+
+    "
+                )?;
+            }
+        } else {
+            write!(
+                f,
+                "  (no context)
+
+    "
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The most nested exception records [`MinidumpException::nested_exceptions`] follows.
+pub const MAX_NESTED_EXCEPTIONS: usize = 16;
+
+/// Read an in-process `EXCEPTION_RECORD` at `address`.
+///
+/// The 64-bit `EXCEPTION_RECORD` has the same layout as `MINIDUMP_EXCEPTION`;
+/// the fields of the 32-bit one are all 32 bits, so they're widened.
+fn read_exception_record(
+    memory: &UnifiedMemory,
+    address: u64,
+    width: PointerWidth,
+) -> Option<md::MINIDUMP_EXCEPTION> {
+    match width {
+        PointerWidth::Bits64 => memory.get_memory_at_address(address),
+        PointerWidth::Bits32 => {
+            let field = |index: u64| -> Option<u32> {
+                memory.get_memory_at_address(address.checked_add(index * 4)?)
+            };
+            let number_parameters = field(4)?;
+            let mut exception_information = [0; 15];
+            for (i, param) in exception_information.iter_mut().enumerate() {
+                *param = field(5 + i as u64)?.into();
+            }
+            Some(md::MINIDUMP_EXCEPTION {
+                exception_code: field(0)?,
+                exception_flags: field(1)?,
+                exception_record: field(2)?.into(),
+                exception_address: field(3)?.into(),
+                number_parameters,
+                __align: 0,
+                exception_information,
+            })
+        }
+        PointerWidth::Unknown => None,
+    }
+}
+
+impl<'a> MinidumpStream<'a> for MinidumpAssertion {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::AssertionInfoStream as u32;
+
+    fn read(
+        bytes: &'a [u8],
+        _all: &'a [u8],
+        endian: scroll::Endian,
+        _system_info: Option<&MinidumpSystemInfo>,
+    ) -> Result<MinidumpAssertion, Error> {
+        let raw: md::MINIDUMP_ASSERTION_INFO = bytes
+            .pread_with(0, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        Ok(MinidumpAssertion { raw })
+    }
+}
+
+/// Decode a 0-terminated (or 0-padded) UTF-16 string.
+///
+/// The code units were already read with the minidump's endianness.
+fn utf16_to_string(data: &[u16]) -> Option<String> {
+    let len = data.iter().take_while(|c| **c != 0).count();
+    String::from_utf16(&data[..len]).ok()
+}
+
+impl MinidumpAssertion {
+    /// Get the assertion expression as a `String` if one exists.
+    pub fn expression(&self) -> Option<String> {
+        utf16_to_string(&self.raw.expression)
+    }
+    /// Get the function name where the assertion happened as a `String` if it exists.
+    pub fn function(&self) -> Option<String> {
+        utf16_to_string(&self.raw.function)
+    }
+    /// Get the source file name where the assertion happened as a `String` if it exists.
+    pub fn file(&self) -> Option<String> {
+        utf16_to_string(&self.raw.file)
+    }
+    /// Get the line number in [`MinidumpAssertion::file`] where the assertion happened.
+    pub fn line(&self) -> u32 {
+        self.raw.line
+    }
+    /// Get the type of the assertion, if it's a known one.
+    pub fn assertion_type(&self) -> Option<md::AssertionType> {
+        md::AssertionType::from_u32(self.raw._type)
+    }
+
+    /// Write a human-readable description of this `MinidumpAssertion` to `f`.
+    ///
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MDAssertion
+  expression                                 = {}
+  function                                   = {}
+  file                                       = {}
+  line                                       = {}
+  type                                       = {} ({})
+
+",
+            self.expression().unwrap_or_default(),
+            self.function().unwrap_or_default(),
+            self.file().unwrap_or_default(),
+            self.raw.line,
+            self.raw._type,
+            self.assertion_type()
+                .map(|ty| format!("{:?}", ty))
+                .unwrap_or_else(|| String::from("unknown")),
+        )?;
+        Ok(())
+    }
+}
+
+fn read_string_list(
+    all: &[u8],
+    location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
+    endian: scroll::Endian,
+) -> Result<Vec<String>, Error> {
+    let data = location_slice(all, location).or(Err(Error::StreamReadFailure))?;
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut offset = 0;
+
+    let count: u32 = data
+        .gread_with(&mut offset, endian)
+        .or(Err(Error::StreamReadFailure))?;
+
+    let (count, _) = ensure_count_in_bound(all, count as usize, <md::RVA>::size_with(&endian), 0)?;
+
+    let mut strings = Vec::with_capacity(count);
+    for _ in 0..count {
+        let rva: md::RVA = data
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+
+        let string = read_string_utf8(&mut (rva as usize), all, endian)
+            .ok_or(Error::StreamReadFailure)?
+            .to_owned();
+
+        strings.push(string);
+    }
+
+    Ok(strings)
+}
 
-        let linux_reason = err::ExceptionCodeLinux::from_u32(exception_code)?;
-        let mut reason = CrashReason::LinuxGeneral(linux_reason, exception_flags);
-        // Refine the output for error codes that have more info
-        match linux_reason {
-            err::ExceptionCodeLinux::SIGILL => {
-                if let Some(ty) = err::ExceptionCodeLinuxSigillKind::from_u32(exception_flags) {
-                    reason = CrashReason::LinuxSigill(ty);
-                }
-            }
-            err::ExceptionCodeLinux::SIGTRAP => {
-                if let Some(ty) = err::ExceptionCodeLinuxSigtrapKind::from_u32(exception_flags) {
-                    reason = CrashReason::LinuxSigtrap(ty);
-                }
-            }
-            err::ExceptionCodeLinux::SIGFPE => {
-                if let Some(ty) = err::ExceptionCodeLinuxSigfpeKind::from_u32(exception_flags) {
-                    reason = CrashReason::LinuxSigfpe(ty);
-                }
-            }
-            err::ExceptionCodeLinux::SIGSEGV => {
-                if let Some(ty) = err::ExceptionCodeLinuxSigsegvKind::from_u32(exception_flags) {
-                    reason = CrashReason::LinuxSigsegv(ty);
-                }
-            }
-            err::ExceptionCodeLinux::SIGBUS => {
-                if let Some(ty) = err::ExceptionCodeLinuxSigbusKind::from_u32(exception_flags) {
-                    reason = CrashReason::LinuxSigbus(ty);
-                }
-            }
-            err::ExceptionCodeLinux::SIGSYS => {
-                if let Some(ty) = err::ExceptionCodeLinuxSigsysKind::from_u32(exception_flags) {
-                    reason = CrashReason::LinuxSigsys(ty);
-                }
-            }
-            _ => {
-                // No refinements
-            }
-        }
-        Some(reason)
+fn read_simple_string_dictionary(
+    all: &[u8],
+    location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
+    endian: scroll::Endian,
+) -> Result<BTreeMap<String, String>, Error> {
+    let mut dictionary = BTreeMap::new();
+
+    let data = location_slice(all, location).or(Err(Error::StreamReadFailure))?;
+    if data.is_empty() {
+        return Ok(dictionary);
+    }
+
+    let mut offset = 0;
+
+    let count: u32 = data
+        .gread_with(&mut offset, endian)
+        .or(Err(Error::StreamReadFailure))?;
+
+    for _ in 0..count {
+        let entry: md::MINIDUMP_SIMPLE_STRING_DICTIONARY_ENTRY = data
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+
+        let key = read_string_utf8(&mut (entry.key as usize), all, endian)
+            .ok_or(Error::StreamReadFailure)?;
+        let value = read_string_utf8(&mut (entry.value as usize), all, endian)
+            .ok_or(Error::StreamReadFailure)?;
+
+        dictionary.insert(key.to_owned(), value.to_owned());
     }
+
+    Ok(dictionary)
 }
 
-impl fmt::Display for CrashReason {
-    /// A string describing the crash reason.
-    ///
-    /// This is OS- and possibly CPU-specific.
-    /// For example, "EXCEPTION_ACCESS_VIOLATION" (Windows),
-    /// "EXC_BAD_ACCESS / KERN_INVALID_ADDRESS" (Mac OS X), "SIGSEGV"
-    /// (other Unix).
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use CrashReason::*;
+fn read_annotation_objects(
+    all: &[u8],
+    location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
+    endian: scroll::Endian,
+) -> Result<BTreeMap<String, MinidumpAnnotation>, Error> {
+    let mut dictionary = BTreeMap::new();
 
-        fn write_nt_status(f: &mut fmt::Formatter<'_>, raw_nt_status: u64) -> fmt::Result {
-            let nt_status = err::NtStatusWindows::from_u64(raw_nt_status);
-            if let Some(nt_status) = nt_status {
-                write!(f, "{:?}", nt_status)
-            } else {
-                write!(f, "{:#010x}", raw_nt_status)
-            }
-        }
+    let data = location_slice(all, location).or(Err(Error::StreamReadFailure))?;
+    if data.is_empty() {
+        return Ok(dictionary);
+    }
 
-        fn write_fast_fail(f: &mut fmt::Formatter<'_>, raw_fast_fail: u64) -> fmt::Result {
-            let fast_fail = err::FastFailCode::from_u64(raw_fast_fail);
-            if let Some(fast_fail) = fast_fail {
-                write!(f, "{:?}", fast_fail)
-            } else {
-                write!(f, "{:#010x}", raw_fast_fail)
-            }
-        }
+    let mut offset = 0;
 
-        fn write_exc_resource(
-            f: &mut fmt::Formatter<'_>,
-            ex: err::ExceptionCodeMacResourceType,
-            code: u64,
-            subcode: u64,
-        ) -> fmt::Result {
-            let flavor = (code >> 58) & 0x7;
-            write!(f, "EXC_RESOURCE / {:?} / ", ex)?;
-            match ex {
-                err::ExceptionCodeMacResourceType::RESOURCE_TYPE_CPU => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L71-L99
-                    if let Some(cpu_flavor) =
-                        err::ExceptionCodeMacResourceCpuFlavor::from_u64(flavor)
-                    {
-                        let interval = (code >> 7) & 0x1ffffff;
-                        let cpu_limit = code & 0x7;
-                        let cpu_consumed = subcode & 0x7;
-                        write!(
-                            f,
-                            "{:?} interval: {}s CPU limit: {}% CPU consumed: {}%",
-                            cpu_flavor, interval, cpu_limit, cpu_consumed
-                        )
-                    } else {
-                        write!(f, "{:#018x} / {:#018x}", code, subcode)
-                    }
-                }
-                err::ExceptionCodeMacResourceType::RESOURCE_TYPE_WAKEUPS => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L105-L134
-                    if let Some(wakeups_flavor) =
-                        err::ExceptionCodeMacResourceWakeupsFlavor::from_u64(flavor)
-                    {
-                        let interval = (code >> 20) & 0xfffff;
-                        let wakeups_permitted = code & 0xfff;
-                        let wakeups_observed = subcode & 0xfff;
-                        write!(
-                            f,
-                            "{:?} interval: {}s wakeups permitted: {} wakeups observed: {}",
-                            wakeups_flavor, interval, wakeups_permitted, wakeups_observed
-                        )
-                    } else {
-                        write!(f, "{:#018x} / {:#018x}", code, subcode)
-                    }
-                }
-                err::ExceptionCodeMacResourceType::RESOURCE_TYPE_MEMORY => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L139-L162
-                    if let Some(memory_flavor) =
-                        err::ExceptionCodeMacResourceMemoryFlavor::from_u64(flavor)
-                    {
-                        let hwm_limit = code & 0x1fff;
-                        write!(
-                            f,
-                            "{:?} high watermark limit: {}MiB",
-                            memory_flavor, hwm_limit
-                        )
-                    } else {
-                        write!(f, "{:#018x} / {:#018x}", code, subcode)
-                    }
-                }
-                err::ExceptionCodeMacResourceType::RESOURCE_TYPE_IO => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L168-L196
-                    if let Some(io_flavor) = err::ExceptionCodeMacResourceIOFlavor::from_u64(flavor)
-                    {
-                        let interval = (code >> 15) & 0x1ffff;
-                        let io_limit = code & 0x7fff;
-                        let io_observed = subcode & 0x7fff;
-                        write!(
-                            f,
-                            "{:?} interval: {}s I/O limit: {}% I/O observed: {}%",
-                            io_flavor, interval, io_limit, io_observed
-                        )
-                    } else {
-                        write!(f, "{:#018x} / {:#018x}", code, subcode)
-                    }
-                }
-                err::ExceptionCodeMacResourceType::RESOURCE_TYPE_THREADS => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_resource.h#L199-L207
-                    if let Some(threads_flavor) =
-                        err::ExceptionCodeMacResourceThreadsFlavor::from_u64(flavor)
-                    {
-                        let hwm_limit = code & 0x7fff;
-                        write!(
-                            f,
-                            "{:?} high watermark limit: {}",
-                            threads_flavor, hwm_limit
-                        )
-                    } else {
-                        write!(f, "{:#018x} / {:#018x}", code, subcode)
-                    }
-                }
+    let count: u32 = data
+        .gread_with(&mut offset, endian)
+        .or(Err(Error::StreamReadFailure))?;
+
+    for _ in 0..count {
+        let raw: md::MINIDUMP_ANNOTATION = data
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+
+        let key = read_string_utf8(&mut (raw.name as usize), all, endian)
+            .ok_or(Error::StreamReadFailure)?;
+
+        // A value that can't be read only loses that one annotation, as the
+        // entries themselves are fixed-size and the rest can still be read.
+        let ty = raw.ty;
+        let value = match ty {
+            md::MINIDUMP_ANNOTATION::TYPE_INVALID => Some(MinidumpAnnotation::Invalid),
+            md::MINIDUMP_ANNOTATION::TYPE_STRING => {
+                read_string_utf8_unterminated(&mut (raw.value as usize), all, endian)
+                    .map(|string| MinidumpAnnotation::String(string.to_owned()))
             }
-        }
+            _ => {
+                // Other annotations are a `MinidumpByteArray`, like strings.
+                let bytes = if raw.value == 0 {
+                    Some(Vec::new())
+                } else {
+                    let mut offset = raw.value as usize;
+                    all.gread_with::<u32>(&mut offset, endian)
+                        .and_then(|length| all.gread_with::<&[u8]>(&mut offset, length as usize))
+                        .ok()
+                        .map(<[u8]>::to_vec)
+                };
 
-        fn write_exc_guard(
-            f: &mut fmt::Formatter<'_>,
-            ex: err::ExceptionCodeMacGuardType,
-            code: u64,
-            subcode: u64,
-        ) -> fmt::Result {
-            let flavor = (code >> 32) & 0x1fffffff;
-            write!(f, "EXC_GUARD / {:?}", ex)?;
-            match ex {
-                err::ExceptionCodeMacGuardType::GUARD_TYPE_NONE => {
-                    write!(f, "")
-                }
-                err::ExceptionCodeMacGuardType::GUARD_TYPE_MACH_PORT => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_guard.h#L69-L81
-                    if let Some(mach_port_flavor) =
-                        err::ExceptionCodeMacGuardMachPortFlavor::from_u64(flavor)
-                    {
-                        let port_name = code & 0xfffffff;
-                        write!(
-                            f,
-                            " / {:?} port name: {} guard identifier: {}",
-                            mach_port_flavor, port_name, subcode,
-                        )
-                    } else {
-                        write!(f, " / {:#018x} / {:#018x}", code, subcode)
-                    }
-                }
-                err::ExceptionCodeMacGuardType::GUARD_TYPE_FD => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_guard.h#L85-L97
-                    if let Some(fd_flavor) = err::ExceptionCodeMacGuardFDFlavor::from_u64(flavor) {
-                        let fd = code & 0xfffffff;
-                        write!(
-                            f,
-                            " / {:?} file descriptor: {} guard identifier: {}",
-                            fd_flavor, fd, subcode,
-                        )
-                    } else {
-                        write!(f, " / {:#018x} / {:#018x}", code, subcode)
-                    }
-                }
-                err::ExceptionCodeMacGuardType::GUARD_TYPE_USER => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_guard.h#L101-L113
-                    let namespace = code & 0xffffffff;
-                    write!(
-                        f,
-                        "/ namespace: {} guard identifier: {}",
-                        namespace, subcode,
-                    )
-                }
-                err::ExceptionCodeMacGuardType::GUARD_TYPE_VN => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_guard.h#L117-L129
-                    if let Some(vn_flavor) = err::ExceptionCodeMacGuardVNFlavor::from_u64(flavor) {
-                        let pid = code & 0xfffffff;
-                        write!(
-                            f,
-                            " / {:?} pid: {} guard identifier: {}",
-                            vn_flavor, pid, subcode,
-                        )
-                    } else {
-                        write!(f, " / {:#018x} / {:#018x}", code, subcode)
-                    }
-                }
-                err::ExceptionCodeMacGuardType::GUARD_TYPE_VIRT_MEMORY => {
-                    // See https://github.com/apple/darwin-xnu/blob/2ff845c2e033bd0ff64b5b6aa6063a1f8f65aa32/osfmk/kern/exc_guard.h#L133-L145
-                    if let Some(virt_memory_flavor) =
-                        err::ExceptionCodeMacGuardVirtMemoryFlavor::from_u64(flavor)
-                    {
-                        write!(f, " / {:?} offset: {}", virt_memory_flavor, subcode)
+                bytes.map(|bytes| {
+                    if ty >= md::MINIDUMP_ANNOTATION::TYPE_USER_DEFINED {
+                        MinidumpAnnotation::UserDefined(raw, bytes)
                     } else {
-                        write!(f, " / {:#018x} / {:#018x}", code, subcode)
+                        MinidumpAnnotation::Unsupported(raw, bytes)
                     }
-                }
+                })
             }
-        }
+        };
 
-        fn write_signal(
-            f: &mut fmt::Formatter<'_>,
-            ex: err::ExceptionCodeLinux,
-            flags: u32,
-        ) -> fmt::Result {
-            if let Some(si_code) = err::ExceptionCodeLinuxSicode::from_u32(flags) {
-                if si_code == err::ExceptionCodeLinuxSicode::SI_USER {
-                    write!(f, "{:?}", ex)
-                } else {
-                    write!(f, "{:?} / {:?}", ex, si_code)
-                }
-            } else {
-                write!(f, "{:?} / {:#010x}", ex, flags)
+        match value {
+            Some(value) => {
+                dictionary.insert(key.to_owned(), value);
             }
+            None => warn!(
+                "Couldn't read the value of crashpad annotation {:?} (type {:#x}), skipping it",
+                key, ty
+            ),
         }
+    }
 
-        // OK this is kinda a gross hack but I *really* don't want
-        // to write out all these strings again, so let's just lean on Debug
-        // repeating the name of the enum variant!
-        match *self {
-            // ======================== Mac/iOS ============================
+    Ok(dictionary)
+}
 
-            // These codes get special messages
-            MacGeneral(err::ExceptionCodeMac::SIMULATED, _) => write!(f, "Simulated Exception"),
+impl MinidumpModuleCrashpadInfo {
+    pub fn read(
+        link: md::MINIDUMP_MODULE_CRASHPAD_INFO_LINK,
+        all: &[u8],
+        endian: scroll::Endian,
+    ) -> Result<Self, Error> {
+        let raw: md::MINIDUMP_MODULE_CRASHPAD_INFO = all
+            .pread_with(link.location.rva as usize, endian)
+            .or(Err(Error::StreamReadFailure))?;
 
-            // Thse codes just repeat their names
-            MacGeneral(ex, flags) => write!(f, "{:?} / {:#010x}", ex, flags),
-            MacBadAccessKern(ex) => write!(f, "EXC_BAD_ACCESS / {:?}", ex),
-            MacBadAccessArm(ex) => write!(f, "EXC_BAD_ACCESS / {:?}", ex),
-            MacBadAccessPpc(ex) => write!(f, "EXC_BAD_ACCESS / {:?}", ex),
-            MacBadAccessX86(ex) => write!(f, "EXC_BAD_ACCESS / {:?}", ex),
-            MacBadInstructionArm(ex) => write!(f, "EXC_BAD_INSTRUCTION / {:?}", ex),
-            MacBadInstructionPpc(ex) => write!(f, "EXC_BAD_INSTRUCTION / {:?}", ex),
-            MacBadInstructionX86(ex) => write!(f, "EXC_BAD_INSTRUCTION / {:?}", ex),
-            MacArithmeticArm(ex) => write!(f, "EXC_ARITHMETIC / {:?}", ex),
-            MacArithmeticPpc(ex) => write!(f, "EXC_ARITHMETIC / {:?}", ex),
-            MacArithmeticX86(ex) => write!(f, "EXC_ARITHMETIC / {:?}", ex),
-            MacSoftware(ex) => write!(f, "EXC_SOFTWARE / {:?}", ex),
-            MacBreakpointArm(ex) => write!(f, "EXC_BREAKPOINT / {:?}", ex),
-            MacBreakpointPpc(ex) => write!(f, "EXC_BREAKPOINT / {:?}", ex),
-            MacBreakpointX86(ex) => write!(f, "EXC_BREAKPOINT / {:?}", ex),
-            MacResource(ex, code, subcode) => write_exc_resource(f, ex, code, subcode),
-            MacGuard(ex, code, subcode) => write_exc_guard(f, ex, code, subcode),
+        let list_annotations = read_string_list(all, &raw.list_annotations, endian)?;
+        let simple_annotations =
+            read_simple_string_dictionary(all, &raw.simple_annotations, endian)?;
+        let annotation_objects = read_annotation_objects(all, &raw.annotation_objects, endian)?;
+
+        Ok(Self {
+            raw,
+            module_index: link.minidump_module_list_index as usize,
+            list_annotations,
+            simple_annotations,
+            annotation_objects,
+        })
+    }
+}
 
-            // ===================== Linux/Android =========================
+fn read_crashpad_module_links(
+    all: &[u8],
+    location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
+    endian: scroll::Endian,
+) -> Result<Vec<MinidumpModuleCrashpadInfo>, Error> {
+    let data = location_slice(all, location).or(Err(Error::StreamReadFailure))?;
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
 
-            // These codes just repeat their names
-            LinuxGeneral(ex, flags) => write_signal(f, ex, flags),
-            LinuxSigill(ex) => write!(f, "SIGILL / {:?}", ex),
-            LinuxSigtrap(ex) => write!(f, "SIGTRAP / {:?}", ex),
-            LinuxSigbus(ex) => write!(f, "SIGBUS / {:?}", ex),
-            LinuxSigfpe(ex) => write!(f, "SIGFPE / {:?}", ex),
-            LinuxSigsegv(ex) => write!(f, "SIGSEGV / {:?}", ex),
-            LinuxSigsys(ex) => write!(f, "SIGSYS / {:?}", ex),
+    let mut offset = 0;
 
-            // ======================== Windows =============================
+    let count: u32 = data
+        .gread_with(&mut offset, endian)
+        .or(Err(Error::StreamReadFailure))?;
 
-            // These codes get special messages
-            WindowsGeneral(err::ExceptionCodeWindows::OUT_OF_MEMORY) => write!(f, "Out of Memory"),
-            WindowsGeneral(err::ExceptionCodeWindows::UNHANDLED_CPP_EXCEPTION) => {
-                write!(f, "Unhandled C++ Exception")
-            }
-            WindowsGeneral(err::ExceptionCodeWindows::SIMULATED) => {
-                write!(f, "Simulated Exception")
-            }
-            // These codes just repeat their names
-            WindowsGeneral(ex) => write!(f, "{:?}", ex),
-            WindowsWinError(winerror) => write!(f, "{:?}", winerror),
-            WindowsWinErrorWithFacility(facility, winerror) => {
-                write!(f, "{:?} / {:?}", facility, winerror)
-            }
-            WindowsNtStatus(nt_status) => write_nt_status(f, nt_status as _),
-            WindowsAccessViolation(ex) => write!(f, "EXCEPTION_ACCESS_VIOLATION_{:?}", ex),
-            WindowsInPageError(ex, nt_status) => {
-                write!(f, "EXCEPTION_IN_PAGE_ERROR_{:?} / ", ex)?;
-                write_nt_status(f, nt_status)
-            }
-            WindowsStackBufferOverrun(fast_fail) => {
-                write!(f, "EXCEPTION_STACK_BUFFER_OVERRUN / ")?;
-                write_fast_fail(f, fast_fail)
-            }
-            WindowsUnknown(code) => write!(f, "unknown {:#010x}", code),
+    let (count, _) = ensure_count_in_bound(
+        all,
+        count as usize,
+        <md::MINIDUMP_MODULE_CRASHPAD_INFO_LINK>::size_with(&endian),
+        0,
+    )?;
 
-            Unknown(code, flags) => write!(f, "unknown {:#010x} / {:#010x}", code, flags),
-        }
+    let mut module_links = Vec::with_capacity(count);
+    for _ in 0..count {
+        let link: md::MINIDUMP_MODULE_CRASHPAD_INFO_LINK = data
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+
+        let info = MinidumpModuleCrashpadInfo::read(link, all, endian)?;
+        module_links.push(info);
     }
+
+    Ok(module_links)
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpException<'a> {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::ExceptionStream as u32;
+impl<'a> MinidumpStream<'a> for MinidumpCrashpadInfo {
+    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::CrashpadInfoStream as u32;
 
     fn read(
         bytes: &'a [u8],
@@ -4314,981 +8691,2472 @@ impl<'a> MinidumpStream<'a> for MinidumpException<'a> {
         endian: scroll::Endian,
         _system_info: Option<&MinidumpSystemInfo>,
     ) -> Result<Self, Error> {
-        let raw: md::MINIDUMP_EXCEPTION_STREAM = bytes
+        let raw: md::MINIDUMP_CRASHPAD_INFO = bytes
             .pread_with(0, endian)
             .or(Err(Error::StreamReadFailure))?;
-        let context = location_slice(all, &raw.thread_context).ok();
-        let thread_id = raw.thread_id;
-        Ok(MinidumpException {
+
+        if raw.version == 0 {
+            // 0 is an invalid version, but all future versions are compatible with v1.
+            return Err(Error::VersionMismatch);
+        }
+
+        let simple_annotations =
+            read_simple_string_dictionary(all, &raw.simple_annotations, endian)?;
+
+        let module_list = read_crashpad_module_links(all, &raw.module_list, endian)?;
+
+        Ok(Self {
             raw,
-            thread_id,
-            context,
-            endian,
+            simple_annotations,
+            module_list,
         })
     }
 }
 
-impl<'a> MinidumpException<'a> {
-    /// Get the cpu context of the crashing (or otherwise minidump-requesting) thread.
-    ///
-    /// CPU contexts are a platform-specific format, so SystemInfo is required
-    /// to reliably parse them. We used to use heuristics to avoid this requirement,
-    /// but this made us too brittle to otherwise-backwards-compatible additions
-    /// to the format.
+impl MinidumpCrashpadInfo {
+    /// Write a human-readable description of this `MinidumpCrashpadInfo` to `f`.
     ///
-    /// MiscInfo can contain additional details on the cpu context's format, but
-    /// is optional because those details can be safely ignored (at the cost of
-    /// being unable to parse some very obscure cpu state).
-    pub fn context(
-        &self,
-        system_info: &MinidumpSystemInfo,
-        misc: Option<&MinidumpMiscInfo>,
-    ) -> Option<Cow<MinidumpContext>> {
-        MinidumpContext::read(self.context?, self.endian, system_info, misc)
-            .ok()
-            .map(Cow::Owned)
+    /// This is very verbose, it is the format used by `minidump_dump`.
+    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
+        write!(
+            f,
+            "MDRawCrashpadInfo
+  version = {}
+  report_id = {}
+  client_id = {}
+",
+            self.raw.version, self.raw.report_id, self.raw.client_id,
+        )?;
+
+        for (name, value) in &self.simple_annotations {
+            writeln!(f, "  simple_annotations[\"{}\"] = {}", name, value)?;
+        }
+
+        for (index, module) in self.module_list.iter().enumerate() {
+            writeln!(
+                f,
+                "  module_list[{}].minidump_module_list_index = {}",
+                index, module.module_index,
+            )?;
+            writeln!(
+                f,
+                "  module_list[{}].version = {}",
+                index, module.raw.version,
+            )?;
+
+            for (annotation_index, annotation) in module.list_annotations.iter().enumerate() {
+                writeln!(
+                    f,
+                    "  module_list[{}].list_annotations[{}] = {}",
+                    index, annotation_index, annotation,
+                )?;
+            }
+
+            for (name, value) in &module.simple_annotations {
+                writeln!(
+                    f,
+                    "  module_list[{}].simple_annotations[\"{}\"] = {}",
+                    index, name, value,
+                )?;
+            }
+
+            for (name, value) in &module.annotation_objects {
+                write!(
+                    f,
+                    "  module_list[{}].annotation_objects[\"{}\"] = ",
+                    index, name,
+                )?;
+
+                match value {
+                    MinidumpAnnotation::Invalid => writeln!(f, "<invalid>"),
+                    MinidumpAnnotation::String(string) => writeln!(f, "{}", string),
+                    MinidumpAnnotation::UserDefined(raw, bytes) => {
+                        writeln!(f, "<user defined {:#06x}> {}", raw.ty, bytes_to_hex(bytes))
+                    }
+                    MinidumpAnnotation::Unsupported(raw, bytes) => {
+                        writeln!(f, "<unsupported {:#06x}> {}", raw.ty, bytes_to_hex(bytes))
+                    }
+                }?;
+            }
+        }
+
+        writeln!(f)?;
+
+        Ok(())
     }
+}
 
-    /// Get the address that "caused" the crash.
-    ///
-    /// The meaning of this value depends on the kind of crash this was.
+/// Read the header at the start of a minidump, and detect its endianness.
+fn read_header(data: &[u8]) -> Result<(md::MINIDUMP_HEADER, scroll::Endian), Error> {
+    let mut endian = LE;
+    let mut header: md::MINIDUMP_HEADER =
+        data.pread_with(0, endian).or(Err(Error::MissingHeader))?;
+    if header.signature != md::MINIDUMP_SIGNATURE {
+        if header.signature.swap_bytes() != md::MINIDUMP_SIGNATURE {
+            return Err(Error::HeaderMismatch);
+        }
+        // Try again with big-endian.
+        endian = BE;
+        header = data.pread_with(0, endian).or(Err(Error::MissingHeader))?;
+        if header.signature != md::MINIDUMP_SIGNATURE {
+            return Err(Error::HeaderMismatch);
+        }
+    }
+    if (header.version & 0x0000ffff) != md::MINIDUMP_VERSION {
+        return Err(Error::VersionMismatch);
+    }
+    Ok((header, endian))
+}
+
+/// Read the `count` entries of the stream directory at `offset` in `data`.
+///
+/// Also returns a map from each stream type to the index of the last
+/// entry of that type.
+fn read_directory(
+    data: &[u8],
+    mut offset: usize,
+    count: usize,
+    endian: scroll::Endian,
+) -> Result<(Vec<md::MINIDUMP_DIRECTORY>, HashMap<u32, usize>), Error> {
+    let (count, _) = ensure_count_in_bound(
+        data,
+        count,
+        <md::MINIDUMP_DIRECTORY>::size_with(&endian),
+        offset,
+    )?;
+    let mut directory = Vec::with_capacity(count);
+    let mut streams = HashMap::with_capacity(count);
+    for i in 0..count {
+        let dir: md::MINIDUMP_DIRECTORY = data
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::MissingDirectory))?;
+        directory.push(dir.clone());
+        if let Some(old_idx) = streams.insert(dir.stream_type, i) {
+            let old_dir: &md::MINIDUMP_DIRECTORY = &directory[old_idx];
+            if let Some(known_stream_type) = MINIDUMP_STREAM_TYPE::from_u32(dir.stream_type) {
+                warn!("Minidump contains multiple streams of type {} ({:?}) at indices {} ({} bytes) and {} ({} bytes) (using {})",
+                    dir.stream_type,
+                    known_stream_type,
+                    old_idx,
+                    old_dir.location.data_size,
+                    i,
+                    dir.location.data_size,
+                    i,
+                );
+            } else {
+                warn!("Minidump contains multiple streams of unknown type {} at indices {} ({} bytes) and {} ({} bytes) (using {})",
+                    dir.stream_type,
+                    old_idx,
+                    old_dir.location.data_size,
+                    i,
+                    dir.location.data_size,
+                    i,
+                );
+            }
+        }
+    }
+    Ok((directory, streams))
+}
+
+/// Reads a minidump incrementally from an [`io::Read`][] source, like a
+/// network socket or a download that hasn't finished yet.
+///
+/// Only the header and stream directory are read up front; the contents of
+/// each stream are read when they're requested with [`MinidumpReader::raw_stream`][]
+/// or [`MinidumpReader::read_location`][]. A source that is only `Read` is
+/// read sequentially, keeping everything read so far in a buffer, and never
+/// past the end of the last thing requested. A source that is also `Seek`
+/// (see [`MinidumpReader::new_seekable`][]) seeks to each request instead, and
+/// buffers nothing.
+///
+/// Most streams refer to other data in the minidump by RVA (the module list's
+/// module names, the memory list's memory, ...), so the typed stream
+/// interpretations need the whole dump: use [`MinidumpReader::into_minidump`][]
+/// to get a [`Minidump`][] once the rest of the source is available. Raw
+/// descriptors found in a stream can be fetched on demand with
+/// [`MinidumpReader::read_location`][] in the meantime, and the process's
+/// memory (like the stack memory a stack walk needs) with
+/// [`MinidumpReader::read_memory`][].
+#[derive(Debug)]
+pub struct MinidumpReader<R> {
+    /// The raw minidump header from the source.
+    pub header: md::MINIDUMP_HEADER,
+    /// The endianness of the minidump.
+    pub endian: scroll::Endian,
+    directory: Vec<md::MINIDUMP_DIRECTORY>,
+    /// Map from stream type to index in `directory`.
+    streams: HashMap<u32, usize>,
+    reader: R,
+    /// Everything read so far from a sequential source, from the start of the dump.
+    buffer: Vec<u8>,
+    /// How to seek `reader`, if it's seekable.
+    seek: Option<fn(&mut R, u64) -> io::Result<u64>>,
+    /// The address range and RVA of every memory region in the memory list
+    /// and memory64 list, once [`MinidumpReader::read_memory`][] has needed them.
+    memory_regions: Option<Vec<(std::ops::Range<u64>, u64)>>,
+}
+
+/// Read up to `len` bytes at `offset` in `reader`, seeking with `seek` if
+/// the reader is seekable, or else reading forward into `buffer`.
+///
+/// Fewer bytes are returned if the source ends first.
+fn read_source<'b, R: Read>(
+    reader: &mut R,
+    buffer: &'b mut Vec<u8>,
+    seek: Option<fn(&mut R, u64) -> io::Result<u64>>,
+    offset: u64,
+    len: usize,
+) -> Result<Cow<'b, [u8]>, Error> {
+    if let Some(seek) = seek {
+        seek(reader, offset).or(Err(Error::IoError))?;
+        // `len` usually comes from the minidump, so don't trust it enough to
+        // allocate it up front: the buffer only grows as the source has data.
+        let mut bytes = Vec::new();
+        reader
+            .take(len as u64)
+            .read_to_end(&mut bytes)
+            .or(Err(Error::IoError))?;
+        return Ok(Cow::Owned(bytes));
+    }
+
+    let start: usize = offset.try_into().or(Err(Error::StreamReadFailure))?;
+    let end = start.checked_add(len).ok_or(Error::StreamReadFailure)?;
+    if buffer.len() < end {
+        let needed = (end - buffer.len()) as u64;
+        reader
+            .take(needed)
+            .read_to_end(buffer)
+            .or(Err(Error::IoError))?;
+    }
+    let start = start.min(buffer.len());
+    let end = end.min(buffer.len());
+    Ok(Cow::Borrowed(&buffer[start..end]))
+}
+
+fn seek_to<R: Seek>(reader: &mut R, offset: u64) -> io::Result<u64> {
+    reader.seek(io::SeekFrom::Start(offset))
+}
+
+impl<R: Read> MinidumpReader<R> {
+    /// Read the header and stream directory of a minidump from a sequential source.
+    pub fn new(reader: R) -> Result<MinidumpReader<R>, Error> {
+        MinidumpReader::with_seek(reader, None)
+    }
+
+    fn with_seek(
+        reader: R,
+        seek: Option<fn(&mut R, u64) -> io::Result<u64>>,
+    ) -> Result<MinidumpReader<R>, Error> {
+        let mut reader = reader;
+        let mut buffer = Vec::new();
+        let header_size = md::MINIDUMP_HEADER::size_with(&LE);
+        let header_bytes = read_source(&mut reader, &mut buffer, seek, 0, header_size)?;
+        let (header, endian) = read_header(&header_bytes)?;
+
+        let dir_size = (header.stream_count as usize)
+            .checked_mul(md::MINIDUMP_DIRECTORY::size_with(&endian))
+            .ok_or(Error::MissingDirectory)?;
+        let rva = header.stream_directory_rva;
+        let dir_bytes = read_source(&mut reader, &mut buffer, seek, rva as u64, dir_size)?;
+        let (directory, streams) =
+            read_directory(&dir_bytes, 0, header.stream_count as usize, endian)?;
+
+        Ok(MinidumpReader {
+            header,
+            endian,
+            directory,
+            streams,
+            reader,
+            buffer,
+            seek,
+            memory_regions: None,
+        })
+    }
+
+    /// The raw stream directory of the minidump.
+    pub fn directory(&self) -> &[md::MINIDUMP_DIRECTORY] {
+        &self.directory
+    }
+
+    /// Whether the minidump has a stream of type `stream_type`.
+    pub fn has_stream(&self, stream_type: u32) -> bool {
+        self.streams.contains_key(&stream_type)
+    }
+
+    /// Read the data at `location` in the minidump.
     ///
-    /// By default, it's the instruction pointer at the time of the crash.
-    /// However, if the crash was caused by an illegal memory access, the
-    /// the address would be the memory address.
+    /// Fails with [`Error::StreamReadFailure`][] if the source ends before all of it.
+    pub fn read_location(
+        &mut self,
+        location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
+    ) -> Result<Cow<'_, [u8]>, Error> {
+        let len = location.data_size as usize;
+        let bytes = read_source(
+            &mut self.reader,
+            &mut self.buffer,
+            self.seek,
+            location.rva as u64,
+            len,
+        )?;
+        if bytes.len() < len {
+            return Err(Error::StreamReadFailure);
+        }
+        Ok(bytes)
+    }
+
+    /// Read the raw data of the stream of type `stream_type`.
     ///
-    /// So for instance, if you crashed from dereferencing a null pointer,
-    /// the crash_address will be 0 (or close to it, due to offsets).
-    pub fn get_crash_address(&self, os: Os, cpu: Cpu) -> u64 {
-        let addr = match (
-            os,
-            err::ExceptionCodeWindows::from_u32(self.raw.exception_record.exception_code),
-        ) {
-            (Os::Windows, Some(err::ExceptionCodeWindows::EXCEPTION_ACCESS_VIOLATION))
-            | (Os::Windows, Some(err::ExceptionCodeWindows::EXCEPTION_IN_PAGE_ERROR))
-                if self.raw.exception_record.number_parameters >= 2 =>
-            {
-                self.raw.exception_record.exception_information[1]
-            }
-            _ => self.raw.exception_record.exception_address,
+    /// If there are multiple streams of that type, this is the last one,
+    /// like [`Minidump::get_raw_stream`][].
+    pub fn raw_stream(&mut self, stream_type: u32) -> Result<Cow<'_, [u8]>, Error> {
+        let location = match self.streams.get(&stream_type) {
+            None => return Err(Error::StreamNotFound),
+            Some(&index) => self.directory[index].location,
         };
+        self.read_location(&location)
+    }
 
-        // Sometimes on 32-bit these values can be incorrectly sign-extended,
-        // so mask and zero-extend them here.
-        match cpu.pointer_width() {
-            PointerWidth::Bits32 => addr as u32 as u64,
-            _ => addr,
+    /// Read `len` bytes of the process's memory at `address`, from the memory
+    /// list or memory64 list.
+    ///
+    /// The first call reads the lists' descriptors, and every call reads only
+    /// the memory it asks for, so the memory a stack walk needs can be fetched
+    /// as it goes. Returns `Ok(None)` if no single region has all of it.
+    pub fn read_memory(
+        &mut self,
+        address: u64,
+        len: usize,
+    ) -> Result<Option<Cow<'_, [u8]>>, Error> {
+        if self.memory_regions.is_none() {
+            self.memory_regions = Some(self.read_memory_regions()?);
+        }
+        let end = match address.checked_add(len as u64) {
+            Some(end) => end,
+            None => return Ok(None),
+        };
+        let rva = self
+            .memory_regions
+            .iter()
+            .flatten()
+            .find(|(range, _)| range.start <= address && end <= range.end)
+            .map(|(range, rva)| rva + (address - range.start));
+        let rva = match rva {
+            Some(rva) => rva,
+            None => return Ok(None),
+        };
+        let bytes = read_source(&mut self.reader, &mut self.buffer, self.seek, rva, len)?;
+        if bytes.len() < len {
+            return Err(Error::StreamReadFailure);
+        }
+        Ok(Some(bytes))
+    }
+
+    /// Read the descriptors of the memory list and memory64 list.
+    fn read_memory_regions(&mut self) -> Result<Vec<(std::ops::Range<u64>, u64)>, Error> {
+        let endian = self.endian;
+        let mut regions = Vec::new();
+        if let Ok(bytes) = self.raw_stream(MINIDUMP_STREAM_TYPE::MemoryListStream as u32) {
+            let descriptors: Vec<md::MINIDUMP_MEMORY_DESCRIPTOR> =
+                read_stream_list(&mut 0, &bytes, endian)?;
+            for raw in descriptors {
+                let size = raw.memory.data_size as u64;
+                let start = raw.start_of_memory_range;
+                regions.push((start..start.saturating_add(size), raw.memory.rva as u64));
+            }
+        }
+        if let Ok(bytes) = self.raw_stream(MINIDUMP_STREAM_TYPE::Memory64ListStream as u32) {
+            let mut offset = 0;
+            let count: u64 = bytes
+                .gread_with(&mut offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+            let mut rva: u64 = bytes
+                .gread_with(&mut offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+            for _ in 0..count {
+                let raw: md::MINIDUMP_MEMORY_DESCRIPTOR64 = bytes
+                    .gread_with(&mut offset, endian)
+                    .or(Err(Error::StreamReadFailure))?;
+                let start = raw.start_of_memory_range;
+                regions.push((start..start.saturating_add(raw.data_size), rva));
+                rva = rva.saturating_add(raw.data_size);
+            }
         }
+        Ok(regions)
     }
 
-    /// Get the crash reason for an exception.
-    ///
-    /// The returned value reflects our best attempt to recover a
-    /// "native" error for the crashing system based on the OS and
-    /// things like raw error codes.
-    ///
-    /// This is an imperfect process, because OSes may have overlapping
-    /// error types (e.g. WinError and NTSTATUS overlap, so we have to
-    /// pick one arbirarily).
-    ///
-    /// The raw error codes can be extracted from [MinidumpException::raw][].
-    pub fn get_crash_reason(&self, os: Os, cpu: Cpu) -> CrashReason {
-        CrashReason::from_exception(&self.raw, os, cpu)
+    /// Read the rest of the source, and parse it as a [`Minidump`][].
+    pub fn into_minidump(mut self) -> Result<Minidump<'static, Vec<u8>>, Error> {
+        let data = if let Some(seek) = self.seek {
+            seek(&mut self.reader, 0).or(Err(Error::IoError))?;
+            let mut data = Vec::new();
+            self.reader.read_to_end(&mut data).or(Err(Error::IoError))?;
+            data
+        } else {
+            self.reader
+                .read_to_end(&mut self.buffer)
+                .or(Err(Error::IoError))?;
+            self.buffer
+        };
+        Minidump::read(data)
     }
+}
 
-    /// The id of the thread that caused the crash (or otherwise requested
-    /// the minidump, even if there wasn't actually a crash).
-    pub fn get_crashing_thread_id(&self) -> u32 {
-        self.thread_id
+impl<R: Read + Seek> MinidumpReader<R> {
+    /// Read the header and stream directory of a minidump from a seekable source.
+    ///
+    /// Streams are read by seeking to them, instead of buffering everything before them.
+    pub fn new_seekable(reader: R) -> Result<MinidumpReader<R>, Error> {
+        MinidumpReader::with_seek(reader, Some(seek_to::<R>))
     }
+}
 
-    /// Write a human-readable description of this `MinidumpException` to `f`.
+impl<'a> Minidump<'a, Mmap> {
+    /// Read a `Minidump` from a `Path` to a file on disk.
     ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(
-        &self,
-        f: &mut T,
-        system: Option<&MinidumpSystemInfo>,
-        misc: Option<&MinidumpMiscInfo>,
-    ) -> io::Result<()> {
-        write!(
-            f,
-            "MINIDUMP_EXCEPTION
-  thread_id                                  = {:#x}
-  exception_record.exception_code            = {:#x}
-  exception_record.exception_flags           = {:#x}
-  exception_record.exception_record          = {:#x}
-  exception_record.exception_address         = {:#x}
-  exception_record.number_parameters         = {}
-",
-            self.thread_id,
-            self.raw.exception_record.exception_code,
-            self.raw.exception_record.exception_flags,
-            self.raw.exception_record.exception_record,
-            self.raw.exception_record.exception_address,
-            self.raw.exception_record.number_parameters,
-        )?;
-        for i in 0..self.raw.exception_record.number_parameters as usize {
-            writeln!(
-                f,
-                "  exception_record.exception_information[{:2}] = {:#x}",
-                i, self.raw.exception_record.exception_information[i]
-            )?;
-        }
-        write!(
-            f,
-            "  thread_context.data_size                   = {}
-  thread_context.rva                         = {:#x}
-",
-            self.raw.thread_context.data_size, self.raw.thread_context.rva
-        )?;
-        if let Some(system_info) = system {
-            if let Some(context) = self.context(system_info, misc) {
-                writeln!(f)?;
-                context.print(f)?;
-            } else {
-                write!(
-                    f,
-                    "  (no context)
-
-    "
-                )?;
-            }
-        } else {
-            write!(
-                f,
-                "  (no context)
+    /// See [the type definition](Minidump.html) for an example.
+    pub fn read_path<P>(path: P) -> Result<Minidump<'a, Mmap>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Minidump::read_path_with_options(path, &MinidumpReadOptions::default())
+    }
 
-    "
-            )?;
-        }
-        Ok(())
+    /// Read a `Minidump` from a `Path` to a file on disk, with the given `options`.
+    ///
+    /// See [`Minidump::read_with_options`][].
+    pub fn read_path_with_options<P>(
+        path: P,
+        options: &MinidumpReadOptions,
+    ) -> Result<Minidump<'a, Mmap>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let f = File::open(path).or(Err(Error::FileNotFound))?;
+        let mmap = unsafe { Mmap::map(&f).or(Err(Error::IoError))? };
+        Minidump::read_with_options(mmap, options)
     }
 }
 
-impl<'a> MinidumpStream<'a> for MinidumpAssertion {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::AssertionInfoStream as u32;
+/// A stream in the minidump that this implementation can interpret,
+#[derive(Debug)]
+pub struct MinidumpImplementedStream {
+    pub stream_type: MINIDUMP_STREAM_TYPE,
+    pub location: md::MINIDUMP_LOCATION_DESCRIPTOR,
+    pub vendor: &'static str,
+}
 
-    fn read(
-        bytes: &'a [u8],
-        _all: &'a [u8],
-        endian: scroll::Endian,
-        _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<MinidumpAssertion, Error> {
-        let raw: md::MINIDUMP_ASSERTION_INFO = bytes
-            .pread_with(0, endian)
-            .or(Err(Error::StreamReadFailure))?;
-        Ok(MinidumpAssertion { raw })
-    }
+/// A stream in the minidump that this implementation has no knowledge of.
+#[derive(Debug, Clone)]
+pub struct MinidumpUnknownStream {
+    pub stream_type: u32,
+    pub location: md::MINIDUMP_LOCATION_DESCRIPTOR,
+    pub vendor: &'static str,
 }
 
-fn utf16_to_string(data: &[u16]) -> Option<String> {
-    use std::slice;
+/// A stream in the minidump that this implementation is aware of but doesn't
+/// yet support.
+#[derive(Debug, Clone)]
+pub struct MinidumpUnimplementedStream {
+    pub stream_type: MINIDUMP_STREAM_TYPE,
+    pub location: md::MINIDUMP_LOCATION_DESCRIPTOR,
+    pub vendor: &'static str,
+}
 
-    let len = data.iter().take_while(|c| **c != 0).count();
-    let s16 = &data[..len];
-    let bytes = unsafe { slice::from_raw_parts(s16.as_ptr() as *const u8, s16.len() * 2) };
-    UTF_16LE.decode(bytes, DecoderTrap::Strict).ok()
+/// An entry in the minidump's stream directory, from [`Minidump::all_streams`].
+#[derive(Debug, Clone)]
+pub struct MinidumpStreamInfo {
+    /// The index of this entry in the stream directory.
+    pub index: usize,
+    /// The numeric type of the stream.
+    pub stream_type: u32,
+    /// Where the stream's contents are in the minidump.
+    pub location: md::MINIDUMP_LOCATION_DESCRIPTOR,
+    /// Who defined the stream's type: "Official" for Microsoft's stream types, or the
+    /// extension it belongs to (like "Mozilla Extension") for the rest.
+    pub vendor: &'static str,
+    /// Whether this implementation can parse the stream with [`Minidump::get_stream`].
+    pub has_parser: bool,
+}
+
+/// The stream types this implementation knows of, but can't parse.
+static UNIMPLEMENTED_STREAMS: [MINIDUMP_STREAM_TYPE; 22] = [
+    // Presumably will never have an implementation:
+    MINIDUMP_STREAM_TYPE::UnusedStream,
+    MINIDUMP_STREAM_TYPE::ReservedStream0,
+    MINIDUMP_STREAM_TYPE::ReservedStream1,
+    MINIDUMP_STREAM_TYPE::LastReservedStream,
+    // Presumably should be implemented:
+    MINIDUMP_STREAM_TYPE::ThreadExListStream,
+    MINIDUMP_STREAM_TYPE::CommentStreamA,
+    MINIDUMP_STREAM_TYPE::CommentStreamW,
+    MINIDUMP_STREAM_TYPE::JavaScriptDataStream,
+    // Windows CE streams, very unlikely to be found in the wild.
+    // Their contents are documented here: https://docs.microsoft.com/en-us/previous-versions/windows/embedded/ms939618(v=msdn.10)
+    MINIDUMP_STREAM_TYPE::ceStreamNull,
+    MINIDUMP_STREAM_TYPE::ceStreamSystemInfo,
+    MINIDUMP_STREAM_TYPE::ceStreamException,
+    MINIDUMP_STREAM_TYPE::ceStreamModuleList,
+    MINIDUMP_STREAM_TYPE::ceStreamProcessList,
+    MINIDUMP_STREAM_TYPE::ceStreamThreadList,
+    MINIDUMP_STREAM_TYPE::ceStreamThreadContextList,
+    MINIDUMP_STREAM_TYPE::ceStreamThreadCallStackList,
+    MINIDUMP_STREAM_TYPE::ceStreamMemoryVirtualList,
+    MINIDUMP_STREAM_TYPE::ceStreamMemoryPhysicalList,
+    MINIDUMP_STREAM_TYPE::ceStreamBucketParameters,
+    MINIDUMP_STREAM_TYPE::ceStreamProcessModuleMap,
+    MINIDUMP_STREAM_TYPE::ceStreamDiagnosisList,
+    // non-standard streams (should also be implemented):
+    MINIDUMP_STREAM_TYPE::LinuxCmdLine,
+];
+
+impl MinidumpStreamInfo {
+    /// The type of the stream, if it's one this implementation knows of.
+    pub fn known_type(&self) -> Option<MINIDUMP_STREAM_TYPE> {
+        MINIDUMP_STREAM_TYPE::from_u32(self.stream_type)
+    }
+
+    /// The name of the stream's type, or "unknown".
+    pub fn name(&self) -> Cow<'static, str> {
+        match self.known_type() {
+            Some(stream_type) => Cow::Owned(format!("{:?}", stream_type)),
+            None => Cow::Borrowed("unknown"),
+        }
+    }
 }
 
-impl MinidumpAssertion {
-    /// Get the assertion expression as a `String` if one exists.
-    pub fn expression(&self) -> Option<String> {
-        utf16_to_string(&self.raw.expression)
+impl<'a, T> Minidump<'a, T>
+where
+    T: Deref<Target = [u8]> + 'a,
+{
+    /// Read a `Minidump` from the provided `data`.
+    ///
+    /// Typically this will be a `Vec<u8>` or `&[u8]` with the full contents of the minidump,
+    /// but you can also use something like `memmap::Mmap`.
+    pub fn read(data: T) -> Result<Minidump<'a, T>, Error> {
+        Minidump::read_with_options(data, &MinidumpReadOptions::default())
     }
-    /// Get the function name where the assertion happened as a `String` if it exists.
-    pub fn function(&self) -> Option<String> {
-        utf16_to_string(&self.raw.function)
+
+    /// Read a `Minidump` from the provided `data`, with the given `options`.
+    ///
+    /// This is like [`Minidump::read`][], but with
+    /// [`check_integrity`][MinidumpReadOptions::check_integrity] set it will
+    /// read what it can of a damaged stream directory instead of failing.
+    pub fn read_with_options(
+        data: T,
+        options: &MinidumpReadOptions,
+    ) -> Result<Minidump<'a, T>, Error> {
+        let (header, endian) = read_header(&data)?;
+        let mut read_issues = Vec::new();
+        let mut stream_count = header.stream_count as usize;
+        if options.check_integrity {
+            let entry_size = <md::MINIDUMP_DIRECTORY>::size_with(&endian);
+            let available = data
+                .len()
+                .saturating_sub(header.stream_directory_rva as usize)
+                / entry_size;
+            if stream_count > available {
+                read_issues.push(CorruptionIssue::DirectoryTruncated {
+                    count: header.stream_count,
+                    available: available as u32,
+                });
+                stream_count = available;
+            }
+        }
+        let (directory, streams) = read_directory(
+            &data,
+            header.stream_directory_rva as usize,
+            stream_count,
+            endian,
+        )?;
+        let system_info = streams
+            .get(&MinidumpSystemInfo::STREAM_TYPE)
+            .map(|&index| &directory[index])
+            .and_then(|dir| {
+                location_slice(data.deref(), &dir.location)
+                    .ok()
+                    .and_then(|bytes| {
+                        let all_bytes = data.deref();
+                        MinidumpSystemInfo::read(bytes, all_bytes, endian, None).ok()
+                    })
+            });
+
+        Ok(Minidump {
+            data,
+            header,
+            directory,
+            streams,
+            endian,
+            system_info,
+            read_issues,
+            _phantom: PhantomData,
+        })
     }
-    /// Get the source file name where the assertion happened as a `String` if it exists.
-    pub fn file(&self) -> Option<String> {
-        utf16_to_string(&self.raw.file)
+
+    /// Read and parse the specified [`MinidumpStream`][] `S` from the Minidump, if it exists.
+    ///
+    /// Because Minidump Streams can have totally different formats and meanings, the only
+    /// way to coherently access one is by specifying a static type that provides an
+    /// interpretation and interface of that format.
+    ///
+    /// As such, typical usage of this interface is to just statically request every
+    /// stream your care about. Depending on what analysis you're trying to perform, you may:
+    ///
+    /// * Consider it an error for a stream to be missing (using `?` or `unwrap`)
+    /// * Branch on the presence of stream to conditionally refine your analysis
+    /// * Use a stream's `Default` implementation to make progress (with `unwrap_or_default`)
+    ///
+    /// ```
+    /// use minidump::*;
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     // Read the minidump from a file
+    ///     let mut dump = minidump::Minidump::read_path("../testdata/test.dmp")?;
+    ///
+    ///     // Statically request (and require) several streams we care about:
+    ///     let system_info = dump.get_stream::<MinidumpSystemInfo>()?;
+    ///     let exception = dump.get_stream::<MinidumpException>()?;
+    ///
+    ///     // Combine the contents of the streams to perform more refined analysis
+    ///     let crash_reason = exception.get_crash_reason(system_info.os, system_info.cpu);
+    ///
+    ///     // Conditionally analyze a stream
+    ///     if let Ok(threads) = dump.get_stream::<MinidumpThreadList>() {
+    ///         // Use `Default` to try to make some progress when a stream is missing.
+    ///         // This is especially natural for memory lists because
+    ///         // everything needs to handle memory lookups failing anyway.
+    ///         let mem = dump.get_memory().unwrap_or_default();
+    ///
+    ///         for thread in &threads.threads {
+    ///            let stack = thread.stack_memory(&mem);
+    ///            // ...
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Some streams are impossible to fully parse/interpret without the contents
+    /// of other streams (for instance, many things require [`MinidumpSystemInfo`][] to interpret
+    /// hardware-specific details). As a result, some parsing of the stream may be
+    /// further deferred to methods on the Stream type where those dependencies can be provided
+    /// (e.g. [`MinidumpException::get_crash_reason`][]).
+    ///
+    /// Note that the lifetime of the returned stream is bound to the lifetime of the
+    /// `Minidump` struct itself and not to the lifetime of the data backing this minidump.
+    /// This is a consequence of how this struct relies on [`Deref`][] to access the data.
+    ///
+    /// ## Currently Supported Streams
+    ///
+    /// * [`MinidumpAssertion`][]
+    /// * [`MinidumpBreakpadInfo`][]
+    /// * [`MinidumpCrashpadInfo`][]
+    /// * [`MinidumpException`][]
+    /// * [`MinidumpFunctionTable`][]
+    /// * [`MinidumpHandleDataStream`][]
+    /// * [`MinidumpHandleOperationList`][]
+    /// * [`MinidumpIptTrace`][]
+    /// * [`MinidumpLinuxAuxv`][]
+    /// * [`MinidumpLinuxCpuInfo`][]
+    /// * [`MinidumpLinuxDsoDebug`][]
+    /// * [`MinidumpLinuxEnviron`][]
+    /// * [`MinidumpLinuxLimits`][]
+    /// * [`MinidumpLinuxLsbRelease`][]
+    /// * [`MinidumpLinuxMaps`][]
+    /// * [`MinidumpLinuxProcStatus`][]
+    /// * [`MinidumpMacBootargs`][]
+    /// * [`MinidumpMacCrashInfo`][]
+    /// * [`MinidumpMemoryList`][]
+    /// * [`MinidumpMemory64List`][]
+    /// * [`MinidumpMemoryInfoList`][]
+    /// * [`MinidumpMiscInfo`][]
+    /// * [`MinidumpModuleList`][]
+    /// * [`MinidumpProcessVmCounters`][]
+    /// * [`MinidumpSystemInfo`][]
+    /// * [`MinidumpSystemMemoryInfo`][]
+    /// * [`MinidumpThreadInfoList`][]
+    /// * [`MinidumpThreadList`][]
+    /// * [`MinidumpThreadNames`][]
+    /// * [`MinidumpTokenInfoList`][]
+    /// * [`MinidumpUnloadedModuleList`][]
+    ///
+    pub fn get_stream<S>(&'a self) -> Result<S, Error>
+    where
+        S: MinidumpStream<'a>,
+    {
+        match self.get_raw_stream(S::STREAM_TYPE) {
+            Err(e) => Err(e),
+            Ok(bytes) => {
+                let all_bytes = self.data.deref();
+                S::read(bytes, all_bytes, self.endian, self.system_info.as_ref())
+            }
+        }
     }
 
-    /// Write a human-readable description of this `MinidumpAssertion` to `f`.
+    /// Read and parse every instance of the [`MinidumpStream`][] `S` in the
+    /// Minidump, in the order they appear in the stream directory.
     ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        write!(
-            f,
-            "MDAssertion
-  expression                                 = {}
-  function                                   = {}
-  file                                       = {}
-  line                                       = {}
-  type                                       = {}
-
-",
-            self.expression().unwrap_or_default(),
-            self.function().unwrap_or_default(),
-            self.file().unwrap_or_default(),
-            self.raw.line,
-            self.raw._type,
-        )?;
-        Ok(())
+    /// Well-formed minidumps have at most one stream of each of the standard
+    /// types, and [`Minidump::get_stream`][] reads the last one if there are
+    /// more. But nothing stops a minidump writer from adding several streams
+    /// of the same (usually user-defined) type, and this is how to get all of
+    /// them.
+    pub fn get_all_streams<S>(&'a self) -> impl Iterator<Item = Result<S, Error>> + 'a
+    where
+        S: MinidumpStream<'a>,
+    {
+        let all_bytes = self.data.deref();
+        self.get_all_raw_streams(S::STREAM_TYPE)
+            .map(move |bytes| S::read(bytes?, all_bytes, self.endian, self.system_info.as_ref()))
     }
-}
 
-fn read_string_list(
-    all: &[u8],
-    location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
-    endian: scroll::Endian,
-) -> Result<Vec<String>, Error> {
-    let data = location_slice(all, location).or(Err(Error::StreamReadFailure))?;
-    if data.is_empty() {
-        return Ok(Vec::new());
+    /// Get the memory regions captured in this minidump, from whichever of
+    /// `MemoryListStream` or `Memory64ListStream` it contains.
+    ///
+    /// Returns `None` if neither stream is present (or readable).
+    pub fn get_memory(&'a self) -> Option<UnifiedMemoryList<'a>> {
+        UnifiedMemoryList::new(
+            self.get_stream::<MinidumpMemoryList>().ok(),
+            self.get_stream::<MinidumpMemory64List>().ok(),
+        )
     }
 
-    let mut offset = 0;
-
-    let count: u32 = data
-        .gread_with(&mut offset, endian)
-        .or(Err(Error::StreamReadFailure))?;
-
-    let (count, _) = ensure_count_in_bound(all, count as usize, <md::RVA>::size_with(&endian), 0)?;
-
-    let mut strings = Vec::with_capacity(count);
-    for _ in 0..count {
-        let rva: md::RVA = data
-            .gread_with(&mut offset, endian)
-            .or(Err(Error::StreamReadFailure))?;
-
-        let string = read_string_utf8(&mut (rva as usize), all, endian)
-            .ok_or(Error::StreamReadFailure)?
-            .to_owned();
-
-        strings.push(string);
+    /// Get a stream of raw data from the minidump.
+    ///
+    /// This can be used to get the contents of arbitrary minidump streams.
+    /// For streams of known types you almost certainly want to use
+    /// [`Minidump::get_stream`][] instead.
+    ///
+    /// This is also how to reach vendor-specific streams, to hand them to a tool
+    /// that understands them (see also [`Minidump::raw_stream`][]). The contents
+    /// are borrowed from the minidump's data, not copied, so this is cheap even
+    /// for very large streams.
+    ///
+    /// Note that the lifetime of the returned stream is bound to the lifetime of the this
+    /// `Minidump` struct itself and not to the lifetime of the data backing this minidump.
+    /// This is a consequence of how this struct relies on [Deref] to access the data.
+    pub fn get_raw_stream(&'a self, stream_type: u32) -> Result<&'a [u8], Error> {
+        match self.streams.get(&stream_type) {
+            None => Err(Error::StreamNotFound),
+            Some(&index) => {
+                let bytes = self.data.deref();
+                location_slice(bytes, &self.directory[index].location)
+            }
+        }
     }
 
-    Ok(strings)
-}
-
-fn read_simple_string_dictionary(
-    all: &[u8],
-    location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
-    endian: scroll::Endian,
-) -> Result<BTreeMap<String, String>, Error> {
-    let mut dictionary = BTreeMap::new();
-
-    let data = location_slice(all, location).or(Err(Error::StreamReadFailure))?;
-    if data.is_empty() {
-        return Ok(dictionary);
+    /// Get the raw data of the stream of type `stream_type`, if the minidump has one.
+    ///
+    /// This is [`Minidump::get_raw_stream`][] for when it doesn't matter why
+    /// the stream couldn't be read.
+    pub fn raw_stream(&'a self, stream_type: u32) -> Option<&'a [u8]> {
+        self.get_raw_stream(stream_type).ok()
     }
 
-    let mut offset = 0;
-
-    let count: u32 = data
-        .gread_with(&mut offset, endian)
-        .or(Err(Error::StreamReadFailure))?;
-
-    for _ in 0..count {
-        let entry: md::MINIDUMP_SIMPLE_STRING_DICTIONARY_ENTRY = data
-            .gread_with(&mut offset, endian)
-            .or(Err(Error::StreamReadFailure))?;
-
-        let key = read_string_utf8(&mut (entry.key as usize), all, endian)
-            .ok_or(Error::StreamReadFailure)?;
-        let value = read_string_utf8(&mut (entry.value as usize), all, endian)
-            .ok_or(Error::StreamReadFailure)?;
+    /// Get the raw data of every stream of type `stream_type` in the minidump,
+    /// in the order they appear in the stream directory.
+    ///
+    /// See [`Minidump::get_all_streams`][] and [`Minidump::get_raw_stream`][].
+    pub fn get_all_raw_streams(
+        &'a self,
+        stream_type: u32,
+    ) -> impl Iterator<Item = Result<&'a [u8], Error>> + 'a {
+        let bytes = self.data.deref();
+        self.directory
+            .iter()
+            .filter(move |dir| dir.stream_type == stream_type)
+            .map(move |dir| location_slice(bytes, &dir.location))
+    }
 
-        dictionary.insert(key.to_owned(), value.to_owned());
+    /// A listing of all the streams in the Minidump that this library is *aware* of,
+    /// but has no further analysis for.
+    ///
+    /// Streams are yielded in the order they appear in the stream directory, including
+    /// any duplicates.
+    pub fn unimplemented_streams(&self) -> impl Iterator<Item = MinidumpUnimplementedStream> + '_ {
+        self.directory.iter().filter_map(|stream| {
+            MINIDUMP_STREAM_TYPE::from_u32(stream.stream_type).and_then(|stream_type| {
+                if UNIMPLEMENTED_STREAMS.contains(&stream_type) {
+                    return Some(MinidumpUnimplementedStream {
+                        stream_type,
+                        location: stream.location,
+                        vendor: stream_vendor(stream.stream_type),
+                    });
+                }
+                None
+            })
+        })
     }
 
-    Ok(dictionary)
-}
+    /// A listing of all the streams in the Minidump that this library has no knowledge of.
+    ///
+    /// Streams are yielded in the order they appear in the stream directory, including
+    /// any duplicates.
+    pub fn unknown_streams(&self) -> impl Iterator<Item = MinidumpUnknownStream> + '_ {
+        self.directory.iter().filter_map(|stream| {
+            if MINIDUMP_STREAM_TYPE::from_u32(stream.stream_type).is_none() {
+                return Some(MinidumpUnknownStream {
+                    stream_type: stream.stream_type,
+                    location: stream.location,
+                    vendor: stream_vendor(stream.stream_type),
+                });
+            }
+            None
+        })
+    }
 
-fn read_annotation_objects(
-    all: &[u8],
-    location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
-    endian: scroll::Endian,
-) -> Result<BTreeMap<String, MinidumpAnnotation>, Error> {
-    let mut dictionary = BTreeMap::new();
+    /// A listing of all the streams in the Minidump.
+    ///
+    /// Streams are yielded in the order they appear in the stream directory, including
+    /// any duplicates.
+    pub fn all_streams(&self) -> impl Iterator<Item = MinidumpStreamInfo> + '_ {
+        self.directory
+            .iter()
+            .enumerate()
+            .map(|(index, stream)| MinidumpStreamInfo {
+                index,
+                stream_type: stream.stream_type,
+                location: stream.location,
+                vendor: stream_vendor(stream.stream_type),
+                has_parser: matches!(
+                    MINIDUMP_STREAM_TYPE::from_u32(stream.stream_type),
+                    Some(stream_type) if !UNIMPLEMENTED_STREAMS.contains(&stream_type)
+                ),
+            })
+    }
 
-    let data = location_slice(all, location).or(Err(Error::StreamReadFailure))?;
-    if data.is_empty() {
-        return Ok(dictionary);
+    /// Write a listing of all the streams in the Minidump to `f`.
+    ///
+    /// The contents of the streams this implementation can't parse are
+    /// hexdumped, up to `max_bytes` bytes of each.
+    pub fn print_all_streams<W: Write>(&self, f: &mut W, max_bytes: usize) -> io::Result<()> {
+        writeln!(f, "All streams")?;
+        for stream in self.all_streams() {
+            writeln!(
+                f,
+                "  stream[{}] type = {:#x} ({}, {}) offset = {:#x} size = {}{}",
+                stream.index,
+                stream.stream_type,
+                stream.name(),
+                stream.vendor,
+                stream.location.rva,
+                stream.location.data_size,
+                if stream.has_parser { "" } else { " (unparsed)" },
+            )?;
+            if stream.has_parser || max_bytes == 0 {
+                continue;
+            }
+            match location_slice(self.data.deref(), &stream.location) {
+                Ok(bytes) => {
+                    write_hexdump(f, &bytes[..bytes.len().min(max_bytes)])?;
+                    if bytes.len() > max_bytes {
+                        writeln!(f, "    ({} more bytes)", bytes.len() - max_bytes)?;
+                    }
+                }
+                Err(_) => writeln!(f, "    (out of bounds)")?,
+            }
+        }
+        writeln!(f)
     }
 
-    let mut offset = 0;
+    /// Check the structure of the minidump for damage.
+    ///
+    /// This looks for streams that extend past the end of the file or overlap
+    /// each other, lists whose counts don't fit in their stream, and stacks,
+    /// memory regions and module names that point outside the file. Along
+    /// with anything [`MinidumpReadOptions::check_integrity`][] tolerated
+    /// while reading, these are returned in the order they were found.
+    pub fn check_integrity(&self) -> Vec<CorruptionIssue> {
+        let data = self.data.deref();
+        let file_len = data.len() as u64;
+        let mut issues = self.read_issues.clone();
+
+        let mut in_bounds = Vec::new();
+        for (index, dir) in self.directory.iter().enumerate() {
+            let location = &dir.location;
+            let end = location.rva as u64 + location.data_size as u64;
+            if end > file_len {
+                issues.push(CorruptionIssue::StreamOutOfBounds {
+                    index,
+                    stream_type: dir.stream_type,
+                    rva: location.rva,
+                    size: location.data_size,
+                });
+            } else if location.data_size != 0 {
+                in_bounds.push((location.rva, end, index));
+            }
+        }
 
-    let count: u32 = data
-        .gread_with(&mut offset, endian)
-        .or(Err(Error::StreamReadFailure))?;
+        // Compare each stream with the one before it in the file that reaches
+        // the furthest, so a stream overlapping several is reported once.
+        let mut sorted = in_bounds.clone();
+        sorted.sort_unstable();
+        let mut furthest: Option<(u64, usize)> = None;
+        for &(rva, end, index) in &sorted {
+            match furthest {
+                Some((prev_end, prev_index)) if (rva as u64) < prev_end => {
+                    issues.push(CorruptionIssue::OverlappingStreams {
+                        first: prev_index,
+                        first_type: self.directory[prev_index].stream_type,
+                        second: index,
+                        second_type: self.directory[index].stream_type,
+                        offset: rva,
+                    });
+                    if end > prev_end {
+                        furthest = Some((end, index));
+                    }
+                }
+                _ => furthest = Some((end, index)),
+            }
+        }
 
-    for _ in 0..count {
-        let raw: md::MINIDUMP_ANNOTATION = data
-            .gread_with(&mut offset, endian)
-            .or(Err(Error::StreamReadFailure))?;
+        for &(rva, end, index) in &in_bounds {
+            let stream_type = self.directory[index].stream_type;
+            let bytes = &data[rva as usize..end as usize];
+            match MINIDUMP_STREAM_TYPE::from_u32(stream_type) {
+                Some(MINIDUMP_STREAM_TYPE::ThreadListStream) => {
+                    let threads: Vec<md::MINIDUMP_THREAD> =
+                        check_stream_list(&mut issues, stream_type, bytes, self.endian);
+                    for thread in &threads {
+                        check_memory_location(&mut issues, stream_type, &thread.stack, file_len);
+                    }
+                }
+                Some(MINIDUMP_STREAM_TYPE::MemoryListStream) => {
+                    let regions: Vec<md::MINIDUMP_MEMORY_DESCRIPTOR> =
+                        check_stream_list(&mut issues, stream_type, bytes, self.endian);
+                    for region in &regions {
+                        check_memory_location(&mut issues, stream_type, region, file_len);
+                    }
+                }
+                Some(MINIDUMP_STREAM_TYPE::Memory64ListStream) => {
+                    check_memory64_list(&mut issues, bytes, self.endian, file_len);
+                }
+                Some(MINIDUMP_STREAM_TYPE::ModuleListStream) => {
+                    let modules: Vec<md::MINIDUMP_MODULE> =
+                        check_stream_list(&mut issues, stream_type, bytes, self.endian);
+                    for module in &modules {
+                        let name_len: Option<u32> = data
+                            .pread_with(module.module_name_rva as usize, self.endian)
+                            .ok();
+                        let name_end = name_len.map(|len| {
+                            module.module_name_rva as u64
+                                + mem::size_of::<u32>() as u64
+                                + len as u64
+                        });
+                        if !matches!(name_end, Some(end) if end <= file_len) {
+                            issues.push(CorruptionIssue::ModuleNameOutOfBounds {
+                                base_address: module.base_of_image,
+                                rva: module.module_name_rva,
+                            });
+                        }
+                    }
+                    let (_, range_issues) = module_ranges(
+                        modules
+                            .iter()
+                            .map(|module| (module.base_of_image, module.size_of_image.into())),
+                    );
+                    issues.extend(range_issues);
+                }
+                _ => {}
+            }
+        }
+        issues
+    }
 
-        let key = read_string_utf8(&mut (raw.name as usize), all, endian)
-            .ok_or(Error::StreamReadFailure)?;
+    /// Write a verbose description of the `Minidump` to `f`.
+    pub fn print<W: Write>(&self, f: &mut W) -> io::Result<()> {
+        fn get_stream_name(stream_type: u32) -> Cow<'static, str> {
+            if let Some(stream) = MINIDUMP_STREAM_TYPE::from_u32(stream_type) {
+                Cow::Owned(format!("{:?}", stream))
+            } else {
+                Cow::Borrowed("unknown")
+            }
+        }
 
-        let value = match raw.ty {
-            md::MINIDUMP_ANNOTATION::TYPE_INVALID => MinidumpAnnotation::Invalid,
-            md::MINIDUMP_ANNOTATION::TYPE_STRING => {
-                let string = read_string_utf8_unterminated(&mut (raw.value as usize), all, endian)
-                    .ok_or(Error::StreamReadFailure)?
-                    .to_owned();
+        write!(
+            f,
+            r#"MDRawHeader
+  signature            = {:#x}
+  version              = {:#x}
+  stream_count         = {}
+  stream_directory_rva = {:#x}
+  checksum             = {:#x}
+  time_date_stamp      = {:#x} {}
+  flags                = {:#x}
 
-                MinidumpAnnotation::String(string)
-            }
-            _ if raw.ty >= md::MINIDUMP_ANNOTATION::TYPE_USER_DEFINED => {
-                MinidumpAnnotation::UserDefined(raw)
-            }
-            _ => MinidumpAnnotation::Unsupported(raw),
-        };
+"#,
+            self.header.signature,
+            self.header.version,
+            self.header.stream_count,
+            self.header.stream_directory_rva,
+            self.header.checksum,
+            self.header.time_date_stamp,
+            format_time_t(self.header.time_date_stamp),
+            self.header.flags,
+        )?;
+        for (i, stream) in self.directory.iter().enumerate() {
+            write!(
+                f,
+                r#"mDirectory[{}]
+MDRawDirectory
+  stream_type        = {:#x} ({})
+  location.data_size = {}
+  location.rva       = {:#x}
 
-        dictionary.insert(key.to_owned(), value);
+"#,
+                i,
+                stream.stream_type,
+                get_stream_name(stream.stream_type),
+                stream.location.data_size,
+                stream.location.rva
+            )?;
+        }
+        writeln!(f, "Streams:")?;
+        let mut streams = self.streams.iter().collect::<Vec<_>>();
+        streams.sort();
+        for (_, &i) in streams {
+            let stream = &self.directory[i];
+            writeln!(
+                f,
+                "  stream type {:#x} ({}) at index {}",
+                stream.stream_type,
+                get_stream_name(stream.stream_type),
+                i
+            )?;
+        }
+        writeln!(f)?;
+        Ok(())
     }
-
-    Ok(dictionary)
 }
 
-impl MinidumpModuleCrashpadInfo {
-    pub fn read(
-        link: md::MINIDUMP_MODULE_CRASHPAD_INFO_LINK,
-        all: &[u8],
-        endian: scroll::Endian,
-    ) -> Result<Self, Error> {
-        let raw: md::MINIDUMP_MODULE_CRASHPAD_INFO = all
-            .pread_with(link.location.rva as usize, endian)
-            .or(Err(Error::StreamReadFailure))?;
-
-        let list_annotations = read_string_list(all, &raw.list_annotations, endian)?;
-        let simple_annotations =
-            read_simple_string_dictionary(all, &raw.simple_annotations, endian)?;
-        let annotation_objects = read_annotation_objects(all, &raw.annotation_objects, endian)?;
-
-        Ok(Self {
-            raw,
-            module_index: link.minidump_module_list_index as usize,
-            list_annotations,
-            simple_annotations,
-            annotation_objects,
-        })
+fn stream_vendor(stream_type: u32) -> &'static str {
+    if stream_type <= MINIDUMP_STREAM_TYPE::LastReservedStream as u32 {
+        "Official"
+    } else {
+        match stream_type & 0xFFFF0000 {
+            0x4767_0000 => "Google Extension",
+            0x4d7a_0000 => "Mozilla Extension",
+            _ => "Unknown Extension",
+        }
     }
 }
 
-fn read_crashpad_module_links(
-    all: &[u8],
-    location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
-    endian: scroll::Endian,
-) -> Result<Vec<MinidumpModuleCrashpadInfo>, Error> {
-    let data = location_slice(all, location).or(Err(Error::StreamReadFailure))?;
-    if data.is_empty() {
-        return Ok(Vec::new());
-    }
+#[cfg(test)]
+mod test {
+    use super::*;
+    use md::GUID;
+    use minidump_common::format::{PlatformId, ProcessorArchitecture};
+    use minidump_synth::{
+        self, AnnotationValue, CpuInfo as SynthCpuInfo, CrashpadInfo, DumpSection, DumpString,
+        ExListStream, Exception, Memory, Memory64ListStream, MemoryInfo as SynthMemoryInfo,
+        MiscFieldsBuildString, MiscFieldsPowerInfo, MiscFieldsProcessTimes, MiscFieldsTimeZone,
+        MiscInfo5Fields, MiscStream, Module as SynthModule, ModuleCrashpadInfo, SimpleStream,
+        SynthMinidump, SystemInfo, Thread, ThreadName, UnloadedModule as SynthUnloadedModule,
+        STOCK_VERSION_INFO,
+    };
+    use std::mem;
+    use test_assembler::*;
 
-    let mut offset = 0;
+    fn read_synth_dump<'a>(dump: SynthMinidump) -> Result<Minidump<'a, Vec<u8>>, Error> {
+        Minidump::read(dump.finish().unwrap())
+    }
 
-    let count: u32 = data
-        .gread_with(&mut offset, endian)
-        .or(Err(Error::StreamReadFailure))?;
+    #[test]
+    fn test_simple_synth_dump() {
+        const STREAM_TYPE: u32 = 0x11223344;
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: STREAM_TYPE,
+            section: Section::with_endian(Endian::Little).D32(0x55667788),
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        assert_eq!(dump.endian, LE);
+        assert_eq!(
+            dump.get_raw_stream(STREAM_TYPE).unwrap(),
+            &[0x88, 0x77, 0x66, 0x55]
+        );
 
-    let (count, _) = ensure_count_in_bound(
-        all,
-        count as usize,
-        <md::MINIDUMP_MODULE_CRASHPAD_INFO_LINK>::size_with(&endian),
-        0,
-    )?;
+        assert_eq!(
+            dump.get_raw_stream(0xaabbccddu32),
+            Err(Error::StreamNotFound)
+        );
+    }
 
-    let mut module_links = Vec::with_capacity(count);
-    for _ in 0..count {
-        let link: md::MINIDUMP_MODULE_CRASHPAD_INFO_LINK = data
-            .gread_with(&mut offset, endian)
-            .or(Err(Error::StreamReadFailure))?;
+    #[test]
+    fn test_simple_synth_dump_bigendian() {
+        const STREAM_TYPE: u32 = 0x11223344;
+        let dump = SynthMinidump::with_endian(Endian::Big).add_stream(SimpleStream {
+            stream_type: STREAM_TYPE,
+            section: Section::with_endian(Endian::Big).D32(0x55667788),
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        assert_eq!(dump.endian, BE);
+        assert_eq!(
+            dump.get_raw_stream(STREAM_TYPE).unwrap(),
+            &[0x55, 0x66, 0x77, 0x88]
+        );
 
-        let info = MinidumpModuleCrashpadInfo::read(link, all, endian)?;
-        module_links.push(info);
+        assert_eq!(
+            dump.get_raw_stream(0xaabbccddu32),
+            Err(Error::StreamNotFound)
+        );
     }
 
-    Ok(module_links)
-}
+    #[test]
+    fn test_minidump_reader() {
+        const STREAM_TYPE: u32 = 0x11223344;
+        for endian in [Endian::Little, Endian::Big] {
+            let dump = SynthMinidump::with_endian(endian)
+                .add_stream(SimpleStream {
+                    stream_type: STREAM_TYPE,
+                    section: Section::with_endian(endian).D32(0x55667788),
+                })
+                .add_stream(SimpleStream {
+                    stream_type: MINIDUMP_STREAM_TYPE::LinuxCpuInfo as u32,
+                    section: Section::new().append_bytes(b"processor : 0\n"),
+                });
+            let bytes = dump.finish().unwrap();
+            let dump = Minidump::read(&bytes[..]).unwrap();
+            let expected = dump.get_raw_stream(STREAM_TYPE).unwrap();
+
+            // A sequential source is read no further than the directory.
+            let mut reader = MinidumpReader::new(&bytes[..]).unwrap();
+            let dir_end = reader.header.stream_directory_rva as usize
+                + reader.directory().len() * md::MINIDUMP_DIRECTORY::size_with(&reader.endian);
+            assert_eq!(reader.buffer.len(), dir_end);
+            assert_eq!(reader.endian, dump.endian);
+            assert_eq!(reader.directory().len(), 2);
+            assert!(reader.has_stream(STREAM_TYPE));
+            assert_eq!(&*reader.raw_stream(STREAM_TYPE).unwrap(), expected);
+            assert_eq!(
+                reader.raw_stream(0xaabbccdd).unwrap_err(),
+                Error::StreamNotFound
+            );
+            let read = reader.into_minidump().unwrap();
+            assert_eq!(read.get_raw_stream(STREAM_TYPE).unwrap(), expected);
+
+            // A seekable source buffers nothing.
+            let mut reader = MinidumpReader::new_seekable(io::Cursor::new(&bytes)).unwrap();
+            assert!(reader.buffer.is_empty());
+            assert_eq!(&*reader.raw_stream(STREAM_TYPE).unwrap(), expected);
+            let location = reader.directory()[1].location;
+            assert_eq!(
+                &*reader.read_location(&location).unwrap(),
+                b"processor : 0\n"
+            );
+            let read = reader.into_minidump().unwrap();
+            assert_eq!(read.get_raw_stream(STREAM_TYPE).unwrap(), expected);
+        }
 
-impl<'a> MinidumpStream<'a> for MinidumpCrashpadInfo {
-    const STREAM_TYPE: u32 = MINIDUMP_STREAM_TYPE::CrashpadInfoStream as u32;
+        // Truncated sources.
+        let bytes = SynthMinidump::with_endian(Endian::Little)
+            .add_stream(SimpleStream {
+                stream_type: STREAM_TYPE,
+                section: Section::with_endian(Endian::Little).D32(0x55667788),
+            })
+            .finish()
+            .unwrap();
+        assert_eq!(
+            MinidumpReader::new(&bytes[..16]).err(),
+            Some(Error::MissingHeader)
+        );
+        let mut reader = MinidumpReader::new_seekable(io::Cursor::new(&bytes)).unwrap();
+        let mut location = reader.directory()[0].location;
+        location.data_size = bytes.len() as u32;
+        assert_eq!(
+            reader.read_location(&location).unwrap_err(),
+            Error::StreamReadFailure
+        );
+        // Sizes from the minidump aren't allocated before they're read.
+        location.data_size = u32::MAX;
+        assert_eq!(
+            reader.read_location(&location).unwrap_err(),
+            Error::StreamReadFailure
+        );
+    }
 
-    fn read(
-        bytes: &'a [u8],
-        all: &'a [u8],
-        endian: scroll::Endian,
-        _system_info: Option<&MinidumpSystemInfo>,
-    ) -> Result<Self, Error> {
-        let raw: md::MINIDUMP_CRASHPAD_INFO = bytes
-            .pread_with(0, endian)
-            .or(Err(Error::StreamReadFailure))?;
+    #[test]
+    fn test_minidump_reader_memory() {
+        let memory = |contents: &[u8], address| {
+            Memory::with_section(Section::new().append_bytes(contents), address)
+        };
+        let with_memory = SynthMinidump::with_endian(Endian::Little)
+            .add_memory(memory(b"stack contents", 0x1000))
+            .finish()
+            .unwrap();
+        let with_memory64 = SynthMinidump::with_endian(Endian::Little)
+            .add_memory64(memory(b"first", 0x1000))
+            .add_memory64(memory(b"stack contents", 0x2000))
+            .finish()
+            .unwrap();
+
+        for (bytes, address) in [(&with_memory, 0x1000), (&with_memory64, 0x2000)] {
+            let mut reader = MinidumpReader::new(&bytes[..]).unwrap();
+            assert_eq!(
+                reader.read_memory(address + 6, 8).unwrap().as_deref(),
+                Some(&b"contents"[..])
+            );
+            assert_eq!(reader.read_memory(address + 6, 9).unwrap(), None);
+            assert_eq!(reader.read_memory(0x8000, 1).unwrap(), None);
+            assert_eq!(reader.read_memory(u64::MAX, 2).unwrap(), None);
 
-        if raw.version == 0 {
-            // 0 is an invalid version, but all future versions are compatible with v1.
-            return Err(Error::VersionMismatch);
+            let mut reader = MinidumpReader::new_seekable(io::Cursor::new(bytes)).unwrap();
+            assert_eq!(
+                reader.read_memory(address, 5).unwrap().as_deref(),
+                Some(&b"stack"[..])
+            );
+            assert!(reader.buffer.is_empty());
         }
+    }
 
-        let simple_annotations =
-            read_simple_string_dictionary(all, &raw.simple_annotations, endian)?;
-
-        let module_list = read_crashpad_module_links(all, &raw.module_list, endian)?;
+    #[test]
+    fn test_check_integrity() {
+        const STREAM_TYPE: u32 = 0x11223344;
+        let memory = Memory::with_section(Section::with_endian(Endian::Little).D32(0), 0x1000);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_stream(SimpleStream {
+                stream_type: STREAM_TYPE,
+                section: Section::with_endian(Endian::Little).D32(0x55667788),
+            })
+            .add_stream(SimpleStream {
+                stream_type: STREAM_TYPE + 1,
+                section: Section::with_endian(Endian::Little).D32(0x99aabbcc),
+            })
+            .add_memory(memory);
+        let bytes = dump.finish().unwrap();
+        let dump = Minidump::read(&bytes[..]).unwrap();
+        assert_eq!(dump.check_integrity(), vec![]);
+        let directory = dump.directory.clone();
+        let dir_rva = dump.header.stream_directory_rva as usize;
+        let memory_rva = directory[2].location.rva as usize;
+        let file_len = bytes.len() as u32;
+
+        // Point the second stream at the first, claim more memory regions
+        // than the stream holds, and move the region's contents past the end.
+        let mut damaged = bytes.clone();
+        damaged
+            .pwrite_with(directory[0].location.rva, dir_rva + 12 + 8, LE)
+            .unwrap();
+        damaged.pwrite_with(3u32, memory_rva, LE).unwrap();
+        damaged
+            .pwrite_with(file_len, memory_rva + 4 + 12, LE)
+            .unwrap();
+        let dump = Minidump::read(&damaged[..]).unwrap();
+        assert_eq!(
+            dump.check_integrity(),
+            vec![
+                CorruptionIssue::OverlappingStreams {
+                    first: 0,
+                    first_type: STREAM_TYPE,
+                    second: 1,
+                    second_type: STREAM_TYPE + 1,
+                    offset: directory[0].location.rva,
+                },
+                CorruptionIssue::ImpossibleCount {
+                    stream_type: MINIDUMP_STREAM_TYPE::MemoryListStream as u32,
+                    count: 3,
+                    available: 1,
+                },
+                CorruptionIssue::MemoryOutOfBounds {
+                    stream_type: MINIDUMP_STREAM_TYPE::MemoryListStream as u32,
+                    base_address: 0x1000,
+                    rva: file_len as u64,
+                    size: 4,
+                },
+            ]
+        );
 
-        Ok(Self {
-            raw,
-            simple_annotations,
-            module_list,
-        })
+        // A stream directory cut short fails to read, unless checking integrity.
+        let truncated = &bytes[..bytes.len() - 4];
+        assert!(matches!(
+            Minidump::read(truncated),
+            Err(Error::StreamSizeMismatch { .. })
+        ));
+        let options = MinidumpReadOptions {
+            check_integrity: true,
+        };
+        let dump = Minidump::read_with_options(truncated, &options).unwrap();
+        assert_eq!(dump.all_streams().count(), 2);
+        let issues = dump.check_integrity();
+        assert_eq!(
+            issues,
+            vec![CorruptionIssue::DirectoryTruncated {
+                count: 3,
+                available: 2
+            }]
+        );
+        assert_eq!(issues[0].name(), "DirectoryTruncated");
+        assert_eq!(issues[0].stream_type(), None);
     }
-}
 
-impl MinidumpCrashpadInfo {
-    /// Write a human-readable description of this `MinidumpCrashpadInfo` to `f`.
-    ///
-    /// This is very verbose, it is the format used by `minidump_dump`.
-    pub fn print<T: Write>(&self, f: &mut T) -> io::Result<()> {
-        write!(
-            f,
-            "MDRawCrashpadInfo
-  version = {}
-  report_id = {}
-  client_id = {}
-",
-            self.raw.version, self.raw.report_id, self.raw.client_id,
-        )?;
+    #[test]
+    fn test_linux_environ() {
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .set_linux_environ(b"HOME=/home/me\0TOKEN=a=b\0PS1= \"$\" \0HOME=/root\0TRUNCA");
+        let dump = read_synth_dump(dump).unwrap();
+        let environ = dump.get_stream::<MinidumpLinuxEnviron>().unwrap();
+        let vars = environ
+            .iter()
+            .map(|(k, v)| (k.as_bytes(), v.as_bytes()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vars,
+            vec![
+                (&b"HOME"[..], &b"/home/me"[..]),
+                (b"TOKEN", b"a=b"),
+                (b"PS1", b" \"$\" "),
+                (b"HOME", b"/root"),
+                (b"TRUNCA", b""),
+            ]
+        );
+        assert_eq!(environ.get("HOME").unwrap().as_bytes(), b"/home/me");
+        assert_eq!(environ.get("TRUNCA").unwrap().as_bytes(), b"");
+        assert!(environ.get("SHELL").is_none());
+
+        let mut out = Vec::new();
+        environ
+            .print_filtered(&mut out, |key, _| key.as_bytes() == b"HOME")
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "MinidumpLinuxEnviron\n  HOME=/home/me\n  HOME=/root\n  (3 variables omitted)\n\n"
+        );
+    }
 
-        for (name, value) in &self.simple_annotations {
-            writeln!(f, "  simple_annotations[\"{}\"] = {}", name, value)?;
-        }
+    #[test]
+    fn test_linux_proc_status() {
+        let input = b"Name:\tfirefox\nTgid:\t4242\nPid:\t4243\nPPid:\t1\n\
+            FDSize:\t256\nVmPeak:\t  2048 kB\nVmSize:\t  1024 kB\nVmRSS:\t     512 kB\n\
+            Threads:\t87\nSigIgn:\t0000000000001000\nSigCgt:\t00000001800044ea\n";
+        let dump = SynthMinidump::with_endian(Endian::Little).set_linux_proc_status(input);
+        let dump = read_synth_dump(dump).unwrap();
+        let status = dump.get_stream::<MinidumpLinuxProcStatus>().unwrap();
+        assert_eq!(status.tgid(), Some(4242));
+        assert_eq!(status.pid(), Some(4243));
+        assert_eq!(status.ppid(), Some(1));
+        assert_eq!(status.fd_size(), Some(256));
+        assert_eq!(status.vm_peak(), Some(2048 * 1024));
+        assert_eq!(status.vm_size(), Some(1024 * 1024));
+        assert_eq!(status.vm_rss(), Some(512 * 1024));
+        assert_eq!(status.vm_swap(), None);
+        assert_eq!(status.threads(), Some(87));
+        // SIGPIPE (13) is ignored, and SIGSEGV (11) is caught.
+        assert_eq!(status.sig_ignored(), Some(1 << 12));
+        assert_ne!(status.sig_caught().unwrap() & (1 << 10), 0);
+        assert_eq!(status.iter().count(), 11);
+    }
+    #[test]
+    fn test_cpu_description() {
+        let vendor_id = |vendor: &[u8; 12]| {
+            [0, 4, 8].map(|i| u32::from_le_bytes(vendor[i..i + 4].try_into().unwrap()))
+        };
 
-        for (index, module) in self.module_list.iter().enumerate() {
-            writeln!(
-                f,
-                "  module_list[{}].minidump_module_list_index = {}",
-                index, module.module_index,
-            )?;
-            writeln!(
-                f,
-                "  module_list[{}].version = {}",
-                index, module.raw.version,
-            )?;
+        // x86 dumps have the vendor and cpuid signature.
+        for (vendor, version_information, description) in [
+            (
+                b"GenuineIntel",
+                0x806c1,
+                "GenuineIntel family 6 model 140 stepping 1 (Tiger Lake)",
+            ),
+            (
+                b"AuthenticAMD",
+                0xa20f10,
+                "AuthenticAMD family 25 model 33 stepping 0 (Zen 3)",
+            ),
+            (
+                b"CentaurHauls",
+                0x6fe,
+                "CentaurHauls family 6 model 15 stepping 14",
+            ),
+        ] {
+            let system_info =
+                SystemInfo::new(Endian::Little).set_cpu_info(SynthCpuInfo::X86CpuInfo {
+                    vendor_id: vendor_id(vendor),
+                    version_information,
+                    feature_information: 0,
+                    amd_extended_cpu_features: 0,
+                });
+            let dump = SynthMinidump::with_endian(Endian::Little).add_system_info(system_info);
+            let dump = read_synth_dump(dump).unwrap();
+            let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+            assert_eq!(system_info.cpu_description(None).unwrap(), description);
+        }
+
+        // Windows amd64 dumps only have the display family and model.
+        let mut system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16)
+            .set_platform_id(PlatformId::VER_PLATFORM_WIN32_NT as u32)
+            .set_cpu_info(SynthCpuInfo::OtherCpuInfo {
+                processor_features: [0x3f1d_8cfe_0001_0000, 0],
+            });
+        system_info.processor_level = 25;
+        system_info.processor_revision = 0x2100;
+        let dump = SynthMinidump::with_endian(Endian::Little).add_system_info(system_info);
+        let dump = read_synth_dump(dump).unwrap();
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        assert_eq!(system_info.cpu_microarchitecture(None), Some("Zen 3"));
+        assert_eq!(
+            system_info.cpu_description(None).unwrap(),
+            "family 25 model 33 stepping 0 (Zen 3)"
+        );
 
-            for (annotation_index, annotation) in module.list_annotations.iter().enumerate() {
-                writeln!(
-                    f,
-                    "  module_list[{}].list_annotations[{}] = {}",
-                    index, annotation_index, annotation,
-                )?;
-            }
+        // /proc/cpuinfo can fill in the vendor.
+        let mut system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16)
+            .set_platform_id(PlatformId::Linux as u32);
+        system_info.processor_revision = 0x8c01;
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(system_info)
+            .set_linux_cpu_info(b"processor : 0\nvendor_id : GenuineIntel\n");
+        let dump = read_synth_dump(dump).unwrap();
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        let cpu_info = dump.get_stream::<MinidumpLinuxCpuInfo>().unwrap();
+        assert_eq!(
+            system_info.cpu_description(Some(&cpu_info)).unwrap(),
+            "GenuineIntel family 6 model 140 stepping 1 (Tiger Lake)"
+        );
 
-            for (name, value) in &module.simple_annotations {
-                writeln!(
-                    f,
-                    "  module_list[{}].simple_annotations[\"{}\"] = {}",
-                    index, name, value,
-                )?;
-            }
+        // ARM cpus are described by their MIDR.
+        for (cpuid, description, microarchitecture) in [
+            (0x410fd034, "ARM Cortex-A53", Some("Cortex-A53")),
+            (0x511f8040, "Qualcomm Kryo 4xx Gold", Some("Kryo 4xx Gold")),
+            (0x12003450, "implementer 0x12 part 0x345", None),
+        ] {
+            let system_info = SystemInfo::new(Endian::Little)
+                .set_processor_architecture(
+                    ProcessorArchitecture::PROCESSOR_ARCHITECTURE_ARM64_OLD as u16,
+                )
+                .set_platform_id(PlatformId::Linux as u32)
+                .set_cpu_info(SynthCpuInfo::ArmCpuInfo {
+                    cpuid,
+                    elf_hwcaps: 0,
+                });
+            let dump = SynthMinidump::with_endian(Endian::Little).add_system_info(system_info);
+            let dump = read_synth_dump(dump).unwrap();
+            let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+            assert_eq!(system_info.cpu_description(None).unwrap(), description);
+            assert_eq!(system_info.cpu_microarchitecture(None), microarchitecture);
+        }
+    }
 
-            for (name, value) in &module.annotation_objects {
-                write!(
-                    f,
-                    "  module_list[{}].annotation_objects[\"{}\"] = ",
-                    index, name,
-                )?;
+    #[test]
+    fn test_arm_cpu_features() {
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(
+                ProcessorArchitecture::PROCESSOR_ARCHITECTURE_ARM64_OLD as u16,
+            )
+            .set_platform_id(PlatformId::Linux as u32)
+            .set_cpu_info(SynthCpuInfo::ArmCpuInfo {
+                cpuid: 0x410fd034,
+                elf_hwcaps: (md::ArmElfHwCaps::HWCAP_NEON | md::ArmElfHwCaps::HWCAP_VFP).bits(),
+            });
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(system_info)
+            .set_linux_cpu_info(b"processor : 0\nFeatures : fp asimd aes crc32 atomics cpuid\n");
+        let dump = read_synth_dump(dump).unwrap();
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        let features = system_info.cpu_features(None).unwrap();
+        assert_eq!(features.implementer, Some(0x41));
+        assert_eq!(features.part, Some(0xd03));
+        assert_eq!(features.vendor_name(), Some("ARM"));
+        assert_eq!(features.part_name(), Some("Cortex-A53"));
+        assert_eq!(
+            features.features.iter().copied().collect::<Vec<_>>(),
+            vec![ArmFeature::Fp, ArmFeature::Neon]
+        );
 
-                match value {
-                    MinidumpAnnotation::Invalid => writeln!(f, "<invalid>"),
-                    MinidumpAnnotation::String(string) => writeln!(f, "{}", string),
-                    MinidumpAnnotation::UserDefined(_) => writeln!(f, "<user defined>"),
-                    MinidumpAnnotation::Unsupported(_) => writeln!(f, "<unsupported>"),
-                }?;
-            }
-        }
+        // /proc/cpuinfo adds the features the system info doesn't have room for.
+        let cpu_info = dump.get_stream::<MinidumpLinuxCpuInfo>().unwrap();
+        let features = system_info.cpu_features(Some(&cpu_info)).unwrap();
+        assert!(features.has(ArmFeature::Atomics));
+        assert_eq!(
+            features.features.iter().copied().collect::<Vec<_>>(),
+            vec![
+                ArmFeature::Fp,
+                ArmFeature::Neon,
+                ArmFeature::Aes,
+                ArmFeature::Crc32,
+                ArmFeature::Atomics
+            ]
+        );
 
-        writeln!(f)?;
+        // Without a cpuid, the model comes from /proc/cpuinfo too.
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(ProcessorArchitecture::PROCESSOR_ARCHITECTURE_ARM as u16)
+            .set_platform_id(PlatformId::Android as u32)
+            .set_cpu_info(SynthCpuInfo::ArmCpuInfo {
+                cpuid: 0,
+                elf_hwcaps: 0,
+            });
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(system_info)
+            .set_linux_cpu_info(b"CPU implementer : 0x51\nCPU part : 0x06f\n");
+        let dump = read_synth_dump(dump).unwrap();
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        let cpu_info = dump.get_stream::<MinidumpLinuxCpuInfo>().unwrap();
+        let features = system_info.cpu_features(Some(&cpu_info)).unwrap();
+        assert_eq!(features.vendor_name(), Some("Qualcomm"));
+        assert_eq!(features.part_name(), Some("Krait"));
 
-        Ok(())
-    }
-}
+        // Windows records features as `PF_*` processor feature bits.
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(ProcessorArchitecture::PROCESSOR_ARCHITECTURE_ARM64 as u16)
+            .set_platform_id(PlatformId::VER_PLATFORM_WIN32_NT as u32)
+            .set_cpu_info(SynthCpuInfo::OtherCpuInfo {
+                processor_features: [(1 << 19) | (1 << 34) | (1 << 46), 0],
+            });
+        let dump = SynthMinidump::with_endian(Endian::Little).add_system_info(system_info);
+        let dump = read_synth_dump(dump).unwrap();
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        let features = system_info.cpu_features(None).unwrap();
+        assert_eq!(features.implementer, None);
+        assert_eq!(
+            features.features.iter().copied().collect::<Vec<_>>(),
+            vec![ArmFeature::Neon, ArmFeature::Atomics, ArmFeature::Sve]
+        );
 
-impl<'a> Minidump<'a, Mmap> {
-    /// Read a `Minidump` from a `Path` to a file on disk.
-    ///
-    /// See [the type definition](Minidump.html) for an example.
-    pub fn read_path<P>(path: P) -> Result<Minidump<'a, Mmap>, Error>
-    where
-        P: AsRef<Path>,
-    {
-        let f = File::open(path).or(Err(Error::FileNotFound))?;
-        let mmap = unsafe { Mmap::map(&f).or(Err(Error::IoError))? };
-        Minidump::read(mmap)
+        // Other cpus have no ARM features.
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(SystemInfo::new(Endian::Little));
+        let dump = read_synth_dump(dump).unwrap();
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        assert_eq!(system_info.cpu_features(None), None);
     }
-}
 
-/// A stream in the minidump that this implementation can interpret,
-#[derive(Debug)]
-pub struct MinidumpImplementedStream {
-    pub stream_type: MINIDUMP_STREAM_TYPE,
-    pub location: md::MINIDUMP_LOCATION_DESCRIPTOR,
-    pub vendor: &'static str,
-}
+    #[test]
+    fn test_duplicate_streams() {
+        const STREAM_TYPE: u32 = 0x11223344;
+        let cpu_info = |s: &'static [u8]| SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::LinuxCpuInfo as u32,
+            section: Section::new().append_bytes(s),
+        };
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_stream(SimpleStream {
+                stream_type: STREAM_TYPE,
+                section: Section::with_endian(Endian::Little).D32(1),
+            })
+            .add_stream(cpu_info(b"processor : 0\n"))
+            .add_stream(SimpleStream {
+                stream_type: STREAM_TYPE,
+                section: Section::with_endian(Endian::Little).D32(2),
+            })
+            .add_stream(cpu_info(b"processor : 1\n"));
+        let dump = read_synth_dump(dump).unwrap();
 
-/// A stream in the minidump that this implementation has no knowledge of.
-#[derive(Debug, Clone)]
-pub struct MinidumpUnknownStream {
-    pub stream_type: u32,
-    pub location: md::MINIDUMP_LOCATION_DESCRIPTOR,
-    pub vendor: &'static str,
-}
+        // get_raw_stream keeps on using the last one.
+        assert_eq!(dump.get_raw_stream(STREAM_TYPE).unwrap(), &[2, 0, 0, 0]);
+        let raw = dump
+            .get_all_raw_streams(STREAM_TYPE)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(raw, vec![&[1, 0, 0, 0], &[2, 0, 0, 0]]);
+        assert_eq!(dump.get_all_raw_streams(0xaabbccdd).count(), 0);
+
+        let processors = dump
+            .get_all_streams::<MinidumpLinuxCpuInfo>()
+            .map(|info| {
+                let info = info.unwrap();
+                let (_, value) = info.iter().next().unwrap();
+                value.to_string_lossy().into_owned()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(processors, vec!["0", "1"]);
 
-/// A stream in the minidump that this implementation is aware of but doesn't
-/// yet support.
-#[derive(Debug, Clone)]
-pub struct MinidumpUnimplementedStream {
-    pub stream_type: MINIDUMP_STREAM_TYPE,
-    pub location: md::MINIDUMP_LOCATION_DESCRIPTOR,
-    pub vendor: &'static str,
-}
+        let types = dump
+            .all_streams()
+            .map(|stream| stream.stream_type)
+            .collect::<Vec<_>>();
+        let cpu_info_type = MINIDUMP_STREAM_TYPE::LinuxCpuInfo as u32;
+        assert_eq!(
+            types,
+            vec![STREAM_TYPE, cpu_info_type, STREAM_TYPE, cpu_info_type]
+        );
+        assert_eq!(dump.unknown_streams().count(), 2);
+    }
 
-impl<'a, T> Minidump<'a, T>
-where
-    T: Deref<Target = [u8]> + 'a,
-{
-    /// Read a `Minidump` from the provided `data`.
-    ///
-    /// Typically this will be a `Vec<u8>` or `&[u8]` with the full contents of the minidump,
-    /// but you can also use something like `memmap::Mmap`.
-    pub fn read(data: T) -> Result<Minidump<'a, T>, Error> {
-        let mut offset = 0;
-        let mut endian = LE;
-        let mut header: md::MINIDUMP_HEADER = data
-            .gread_with(&mut offset, endian)
-            .or(Err(Error::MissingHeader))?;
-        if header.signature != md::MINIDUMP_SIGNATURE {
-            if header.signature.swap_bytes() != md::MINIDUMP_SIGNATURE {
-                return Err(Error::HeaderMismatch);
-            }
-            // Try again with big-endian.
-            endian = BE;
-            offset = 0;
-            header = data
-                .gread_with(&mut offset, endian)
-                .or(Err(Error::MissingHeader))?;
-            if header.signature != md::MINIDUMP_SIGNATURE {
-                return Err(Error::HeaderMismatch);
-            }
-        }
-        if (header.version & 0x0000ffff) != md::MINIDUMP_VERSION {
-            return Err(Error::VersionMismatch);
-        }
+    #[test]
+    fn test_all_streams() {
+        const STREAM_TYPE: u32 = 0x4d7affff;
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_stream(SimpleStream {
+                stream_type: MINIDUMP_STREAM_TYPE::LinuxCpuInfo as u32,
+                section: Section::new().append_bytes(b"processor : 0\n"),
+            })
+            .add_stream(SimpleStream {
+                stream_type: STREAM_TYPE,
+                section: Section::new().append_bytes(b"0123456789abcdefXYZ"),
+            });
+        let dump = read_synth_dump(dump).unwrap();
 
-        offset = header.stream_directory_rva as usize;
+        let streams = dump.all_streams().collect::<Vec<_>>();
+        assert_eq!(streams.len(), 2);
+        assert_eq!(streams[0].index, 0);
+        assert_eq!(streams[0].name(), "LinuxCpuInfo");
+        assert_eq!(streams[0].vendor, "Google Extension");
+        assert!(streams[0].has_parser);
+        assert_eq!(streams[1].stream_type, STREAM_TYPE);
+        assert_eq!(streams[1].known_type(), None);
+        assert_eq!(streams[1].name(), "unknown");
+        assert_eq!(streams[1].vendor, "Mozilla Extension");
+        assert_eq!(streams[1].location.data_size, 19);
+        assert!(!streams[1].has_parser);
+
+        let mut output = Vec::new();
+        dump.print_all_streams(&mut output, 16).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "All streams");
+        assert!(
+            lines[1].starts_with("  stream[0] type = 0x47670003 (LinuxCpuInfo, Google Extension)")
+        );
+        assert!(lines[2].starts_with("  stream[1] type = 0x4d7affff (unknown, Mozilla Extension)"));
+        assert!(lines[2].ends_with("size = 19 (unparsed)"));
+        assert_eq!(
+            lines[3],
+            "    00000000: 30 31 32 33 34 35 36 37 38 39 61 62 63 64 65 66 0123456789abcdef"
+        );
+        assert_eq!(lines[4], "    (3 more bytes)");
+    }
 
-        let (count, _) = ensure_count_in_bound(
-            &data,
-            header.stream_count as usize,
-            <md::MINIDUMP_DIRECTORY>::size_with(&endian),
-            offset,
-        )?;
+    #[test]
+    fn test_thread_names() {
+        let good_thread_id = 17;
+        let corrupt_thread_id = 123;
 
-        let mut streams = HashMap::with_capacity(count);
-        for i in 0..header.stream_count {
-            let dir: md::MINIDUMP_DIRECTORY = data
-                .gread_with(&mut offset, endian)
-                .or(Err(Error::MissingDirectory))?;
-            if let Some((old_idx, old_dir)) = streams.insert(dir.stream_type, (i, dir.clone())) {
-                if let Some(known_stream_type) = MINIDUMP_STREAM_TYPE::from_u32(dir.stream_type) {
-                    warn!("Minidump contains multiple streams of type {} ({:?}) at indices {} ({} bytes) and {} ({} bytes) (using {})",
-                        dir.stream_type,
-                        known_stream_type,
-                        old_idx,
-                        old_dir.location.data_size,
-                        i,
-                        dir.location.data_size,
-                        i,
-                    );
-                } else {
-                    warn!("Minidump contains multiple streams of unknown type {} at indices {} ({} bytes) and {} ({} bytes) (using {})",
-                        dir.stream_type,
-                        old_idx,
-                        old_dir.location.data_size,
-                        i,
-                        dir.location.data_size,
-                        i,
-                    );
-                }
-            }
-        }
-        let system_info =
-            streams
-                .get(&MinidumpSystemInfo::STREAM_TYPE)
-                .and_then(|&(_, ref dir)| {
-                    location_slice(data.deref(), &dir.location)
-                        .ok()
-                        .and_then(|bytes| {
-                            let all_bytes = data.deref();
-                            MinidumpSystemInfo::read(bytes, all_bytes, endian, None).ok()
-                        })
-                });
+        let good_name = DumpString::new("MyCoolThread", Endian::Little);
+        // No corrupt name, will dangle
 
-        Ok(Minidump {
-            data,
-            header,
-            streams,
-            endian,
-            system_info,
-            _phantom: PhantomData,
-        })
-    }
+        let good_thread_name_entry =
+            ThreadName::new(Endian::Little, good_thread_id, Some(&good_name));
+        let corrupt_thread_name_entry = ThreadName::new(Endian::Little, corrupt_thread_id, None);
 
-    /// Read and parse the specified [`MinidumpStream`][] `S` from the Minidump, if it exists.
-    ///
-    /// Because Minidump Streams can have totally different formats and meanings, the only
-    /// way to coherently access one is by specifying a static type that provides an
-    /// interpretation and interface of that format.
-    ///
-    /// As such, typical usage of this interface is to just statically request every
-    /// stream your care about. Depending on what analysis you're trying to perform, you may:
-    ///
-    /// * Consider it an error for a stream to be missing (using `?` or `unwrap`)
-    /// * Branch on the presence of stream to conditionally refine your analysis
-    /// * Use a stream's `Default` implementation to make progress (with `unwrap_or_default`)
-    ///
-    /// ```
-    /// use minidump::*;
-    ///
-    /// fn main() -> Result<(), Error> {
-    ///     // Read the minidump from a file
-    ///     let mut dump = minidump::Minidump::read_path("../testdata/test.dmp")?;
-    ///
-    ///     // Statically request (and require) several streams we care about:
-    ///     let system_info = dump.get_stream::<MinidumpSystemInfo>()?;
-    ///     let exception = dump.get_stream::<MinidumpException>()?;
-    ///
-    ///     // Combine the contents of the streams to perform more refined analysis
-    ///     let crash_reason = exception.get_crash_reason(system_info.os, system_info.cpu);
-    ///
-    ///     // Conditionally analyze a stream
-    ///     if let Ok(threads) = dump.get_stream::<MinidumpThreadList>() {
-    ///         // Use `Default` to try to make some progress when a stream is missing.
-    ///         // This is especially natural for MinidumpMemoryList because
-    ///         // everything needs to handle memory lookups failing anyway.
-    ///         let mem = dump.get_stream::<MinidumpMemoryList>().unwrap_or_default();
-    ///
-    ///         for thread in &threads.threads {
-    ///            let stack = thread.stack_memory(&mem);
-    ///            // ...
-    ///         }
-    ///     }
-    ///
-    ///     Ok(())
-    /// }
-    /// ```
-    ///
-    /// Some streams are impossible to fully parse/interpret without the contents
-    /// of other streams (for instance, many things require [`MinidumpSystemInfo`][] to interpret
-    /// hardware-specific details). As a result, some parsing of the stream may be
-    /// further deferred to methods on the Stream type where those dependencies can be provided
-    /// (e.g. [`MinidumpException::get_crash_reason`][]).
-    ///
-    /// Note that the lifetime of the returned stream is bound to the lifetime of the
-    /// `Minidump` struct itself and not to the lifetime of the data backing this minidump.
-    /// This is a consequence of how this struct relies on [`Deref`][] to access the data.
-    ///
-    /// ## Currently Supported Streams
-    ///
-    /// * [`MinidumpAssertion`][]
-    /// * [`MinidumpBreakpadInfo`][]
-    /// * [`MinidumpCrashpadInfo`][]
-    /// * [`MinidumpException`][]
-    /// * [`MinidumpLinuxCpuInfo`][]
-    /// * [`MinidumpLinuxEnviron`][]
-    /// * [`MinidumpLinuxLsbRelease`][]
-    /// * [`MinidumpLinuxMaps`][]
-    /// * [`MinidumpLinuxProcStatus`][]
-    /// * [`MinidumpMacCrashInfo`][]
-    /// * [`MinidumpMemoryList`][]
-    /// * [`MinidumpMemory64List`][]
-    /// * [`MinidumpMemoryInfoList`][]
-    /// * [`MinidumpMiscInfo`][]
-    /// * [`MinidumpModuleList`][]
-    /// * [`MinidumpSystemInfo`][]
-    /// * [`MinidumpThreadList`][]
-    /// * [`MinidumpThreadNames`][]
-    /// * [`MinidumpUnloadedModuleList`][]
-    ///
-    pub fn get_stream<S>(&'a self) -> Result<S, Error>
-    where
-        S: MinidumpStream<'a>,
-    {
-        match self.get_raw_stream(S::STREAM_TYPE) {
-            Err(e) => Err(e),
-            Ok(bytes) => {
-                let all_bytes = self.data.deref();
-                S::read(bytes, all_bytes, self.endian, self.system_info.as_ref())
-            }
-        }
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread_name(good_thread_name_entry)
+            .add_thread_name(corrupt_thread_name_entry)
+            .add(good_name);
+
+        let dump = read_synth_dump(dump).unwrap();
+        let thread_names = dump.get_stream::<MinidumpThreadNames>().unwrap();
+        assert_eq!(thread_names.names.len(), 1);
+        assert_eq!(
+            &*thread_names.get_name(good_thread_id).unwrap(),
+            "MyCoolThread"
+        );
+        assert_eq!(thread_names.get_name(corrupt_thread_id), None);
     }
 
-    /// Get a stream of raw data from the minidump.
-    ///
-    /// This can be used to get the contents of arbitrary minidump streams.
-    /// For streams of known types you almost certainly want to use
-    /// [`Minidump::get_stream`][] instead.
-    ///
-    /// Note that the lifetime of the returned stream is bound to the lifetime of the this
-    /// `Minidump` struct itself and not to the lifetime of the data backing this minidump.
-    /// This is a consequence of how this struct relies on [Deref] to access the data.
-    pub fn get_raw_stream(&'a self, stream_type: u32) -> Result<&'a [u8], Error> {
-        match self.streams.get(&stream_type) {
-            None => Err(Error::StreamNotFound),
-            Some(&(_, ref dir)) => {
-                let bytes = self.data.deref();
-                location_slice(bytes, &dir.location)
-            }
-        }
+    #[test]
+    fn test_threads_with_names() {
+        let context = minidump_synth::x86_context(Endian::Little, 0xabcd1234, 0x1010);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x100),
+            0x1000,
+        );
+        let thread1 = Thread::new(Endian::Little, 17, &stack, &context);
+        let thread2 = Thread::new(Endian::Little, 18, &stack, &context);
+        let name = DumpString::new("MyCoolThread", Endian::Little);
+        let other_name = DumpString::new("NotInTheThreadList", Endian::Little);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread1)
+            .add_thread(thread2)
+            .add_thread_name(ThreadName::new(Endian::Little, 17, Some(&name)))
+            .add_thread_name(ThreadName::new(Endian::Little, 99, Some(&other_name)))
+            .add(name)
+            .add(other_name)
+            .add(context)
+            .add_memory(stack);
+
+        let dump = read_synth_dump(dump).unwrap();
+        let threads = dump.get_stream::<MinidumpThreadList>().unwrap();
+        let names = dump.get_stream::<MinidumpThreadNames>().unwrap();
+        assert_eq!(
+            threads.thread_name(17, &names).as_deref(),
+            Some("MyCoolThread")
+        );
+        assert_eq!(threads.thread_name(18, &names), None);
+        // Names of threads that aren't in the list are ignored.
+        assert_eq!(threads.thread_name(99, &names), None);
+
+        let joined = threads
+            .threads_with_names(&names)
+            .map(|(thread, name)| (thread.raw.thread_id, name.map(Cow::into_owned)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            joined,
+            vec![(17, Some(String::from("MyCoolThread"))), (18, None)]
+        );
     }
 
-    /// A listing of all the streams in the Minidump that this library is *aware* of,
-    /// but has no further analysis for.
-    ///
-    /// If there are multiple copies of the same stream type (which should not happen for
-    /// well-formed Minidumps), then only one of them will be yielded, arbitrarily.
-    pub fn unimplemented_streams(&self) -> impl Iterator<Item = MinidumpUnimplementedStream> + '_ {
-        static UNIMPLEMENTED_STREAMS: [MINIDUMP_STREAM_TYPE; 32] = [
-            // Presumably will never have an implementation:
-            MINIDUMP_STREAM_TYPE::UnusedStream,
-            MINIDUMP_STREAM_TYPE::ReservedStream0,
-            MINIDUMP_STREAM_TYPE::ReservedStream1,
-            MINIDUMP_STREAM_TYPE::LastReservedStream,
-            // Presumably should be implemented:
-            MINIDUMP_STREAM_TYPE::ThreadExListStream,
-            MINIDUMP_STREAM_TYPE::CommentStreamA,
-            MINIDUMP_STREAM_TYPE::CommentStreamW,
-            MINIDUMP_STREAM_TYPE::HandleDataStream,
-            MINIDUMP_STREAM_TYPE::FunctionTable,
-            MINIDUMP_STREAM_TYPE::ThreadInfoListStream,
-            MINIDUMP_STREAM_TYPE::HandleOperationListStream,
-            MINIDUMP_STREAM_TYPE::TokenStream,
-            MINIDUMP_STREAM_TYPE::JavaScriptDataStream,
-            MINIDUMP_STREAM_TYPE::SystemMemoryInfoStream,
-            MINIDUMP_STREAM_TYPE::ProcessVmCountersStream,
-            MINIDUMP_STREAM_TYPE::IptTraceStream,
-            // Windows CE streams, very unlikely to be found in the wild.
-            // Their contents are documented here: https://docs.microsoft.com/en-us/previous-versions/windows/embedded/ms939618(v=msdn.10)
-            MINIDUMP_STREAM_TYPE::ceStreamNull,
-            MINIDUMP_STREAM_TYPE::ceStreamSystemInfo,
-            MINIDUMP_STREAM_TYPE::ceStreamException,
-            MINIDUMP_STREAM_TYPE::ceStreamModuleList,
-            MINIDUMP_STREAM_TYPE::ceStreamProcessList,
-            MINIDUMP_STREAM_TYPE::ceStreamThreadList,
-            MINIDUMP_STREAM_TYPE::ceStreamThreadContextList,
-            MINIDUMP_STREAM_TYPE::ceStreamThreadCallStackList,
-            MINIDUMP_STREAM_TYPE::ceStreamMemoryVirtualList,
-            MINIDUMP_STREAM_TYPE::ceStreamMemoryPhysicalList,
-            MINIDUMP_STREAM_TYPE::ceStreamBucketParameters,
-            MINIDUMP_STREAM_TYPE::ceStreamProcessModuleMap,
-            MINIDUMP_STREAM_TYPE::ceStreamDiagnosisList,
-            // non-standard streams (should also be implemented):
-            MINIDUMP_STREAM_TYPE::LinuxCmdLine,
-            MINIDUMP_STREAM_TYPE::LinuxAuxv,
-            MINIDUMP_STREAM_TYPE::LinuxDsoDebug,
-        ];
-        self.streams.iter().filter_map(|(_, (_, stream))| {
-            MINIDUMP_STREAM_TYPE::from_u32(stream.stream_type).and_then(|stream_type| {
-                if UNIMPLEMENTED_STREAMS.contains(&stream_type) {
-                    return Some(MinidumpUnimplementedStream {
-                        stream_type,
-                        location: stream.location,
-                        vendor: stream_vendor(stream.stream_type),
-                    });
-                }
-                None
+    #[test]
+    fn test_handle_data() {
+        let type_name = DumpString::new("File", Endian::Little);
+        let object_name = DumpString::new("\\Device\\HarddiskVolume3\\log.txt", Endian::Little);
+        let descriptor = |section: Section,
+                          handle: u64,
+                          type_name: &DumpString,
+                          object_name: Option<&DumpString>| {
+            let section = section.D64(handle).D32(type_name.file_offset());
+            let section = match object_name {
+                Some(name) => section.D32(name.file_offset()),
+                None => section.D32(0),
+            };
+            section
+                .D32(0x2) // attributes
+                .D32(0x12019f) // granted_access
+                .D32(1) // handle_count
+                .D32(32770) // pointer_count
+        };
+
+        // Version 1 descriptors
+        let section = Section::with_endian(Endian::Little)
+            .D32(16) // size_of_header
+            .D32(32) // size_of_descriptor
+            .D32(2) // number_of_descriptors
+            .D32(0); // reserved
+        let section = descriptor(section, 0x4, &type_name, Some(&object_name));
+        let section = descriptor(section, 0x8, &type_name, None);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_stream(SimpleStream {
+                stream_type: MINIDUMP_STREAM_TYPE::HandleDataStream as u32,
+                section,
             })
-        })
+            .add(type_name)
+            .add(object_name);
+        let dump = read_synth_dump(dump).unwrap();
+        let handles = dump.get_stream::<MinidumpHandleDataStream>().unwrap();
+        let handles = handles.iter().collect::<Vec<_>>();
+        assert_eq!(handles.len(), 2);
+        assert!(matches!(
+            handles[0].raw,
+            RawHandleDescriptor::HandleDescriptor(_)
+        ));
+        assert_eq!(handles[0].handle(), 0x4);
+        assert_eq!(handles[0].type_name.as_deref(), Some("File"));
+        assert_eq!(
+            handles[0].object_name.as_deref(),
+            Some("\\Device\\HarddiskVolume3\\log.txt")
+        );
+        assert_eq!(handles[0].attributes(), 0x2);
+        assert_eq!(handles[0].granted_access(), 0x12019f);
+        assert_eq!(handles[0].handle_count(), 1);
+        assert_eq!(handles[0].pointer_count(), 32770);
+        assert_eq!(handles[0].object_info_rva(), None);
+        assert_eq!(handles[1].handle(), 0x8);
+        assert_eq!(handles[1].type_name.as_deref(), Some("File"));
+        assert_eq!(handles[1].object_name, None);
+
+        // Version 2 descriptors
+        let type_name = DumpString::new("Event", Endian::Little);
+        let section = Section::with_endian(Endian::Little)
+            .D32(16) // size_of_header
+            .D32(40) // size_of_descriptor
+            .D32(1) // number_of_descriptors
+            .D32(0); // reserved
+        let section = descriptor(section, 0x10, &type_name, None)
+            .D32(0x1234) // object_info_rva
+            .D32(0); // reserved0
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_stream(SimpleStream {
+                stream_type: MINIDUMP_STREAM_TYPE::HandleDataStream as u32,
+                section,
+            })
+            .add(type_name);
+        let dump = read_synth_dump(dump).unwrap();
+        let handles = dump.get_stream::<MinidumpHandleDataStream>().unwrap();
+        let handle = handles.iter().next().unwrap();
+        assert!(matches!(
+            handle.raw,
+            RawHandleDescriptor::HandleDescriptor2(_)
+        ));
+        assert_eq!(handle.handle(), 0x10);
+        assert_eq!(handle.type_name.as_deref(), Some("Event"));
+        assert_eq!(handle.object_info_rva(), Some(0x1234));
     }
 
-    /// A listing of all the streams in the Minidump that this library has no knowledge of.
-    ///
-    /// If there are multiple copies of the same stream (which should not happen for
-    /// well-formed Minidumps), then only one of them will be yielded, arbitrarily.
-    pub fn unknown_streams(&self) -> impl Iterator<Item = MinidumpUnknownStream> + '_ {
-        self.streams.iter().filter_map(|(_, (_, stream))| {
-            if MINIDUMP_STREAM_TYPE::from_u32(stream.stream_type).is_none() {
-                return Some(MinidumpUnknownStream {
-                    stream_type: stream.stream_type,
-                    location: stream.location,
-                    vendor: stream_vendor(stream.stream_type),
-                });
-            }
-            None
-        })
+    #[test]
+    fn test_thread_info_list() {
+        // Entries are 8 bytes larger than MINIDUMP_THREAD_INFO, which must be skipped.
+        let thread_info = |thread_id: u32, start_address: u64| {
+            Section::with_endian(Endian::Little)
+                .D32(thread_id)
+                .D32(md::ThreadInfoDumpFlags::MINIDUMP_THREAD_INFO_EXITED_THREAD.bits())
+                .D32(0) // dump_error
+                .D32(0) // exit_status
+                .D64(132_000_000_000_000_000) // create_time
+                .D64(0) // exit_time
+                .D64(2_500_000) // kernel_time
+                .D64(10_000_000) // user_time
+                .D64(start_address)
+                .D64(0xf) // affinity
+                .D64(0xdeadbeef) // unknown trailing field
+        };
+        let stream = ExListStream::new(
+            MINIDUMP_STREAM_TYPE::ThreadInfoListStream,
+            mem::size_of::<md::MINIDUMP_THREAD_INFO>() + 8,
+            Endian::Little,
+        )
+        .add(thread_info(17, 0x7ff6_1000))
+        .add(thread_info(42, 0x7ff6_2000));
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(stream);
+        let dump = read_synth_dump(dump).unwrap();
+        let thread_infos = dump.get_stream::<MinidumpThreadInfoList>().unwrap();
+        assert_eq!(thread_infos.iter().count(), 2);
+
+        let info = thread_infos.get_thread_info(42).unwrap();
+        assert_eq!(info.start_address(), 0x7ff6_2000);
+        assert_eq!(
+            info.dump_flags,
+            md::ThreadInfoDumpFlags::MINIDUMP_THREAD_INFO_EXITED_THREAD
+        );
+        assert_eq!(info.kernel_time(), Duration::from_millis(250));
+        assert_eq!(info.user_time(), Duration::from_secs(1));
+        assert_eq!(info.raw.affinity, 0xf);
+        assert_eq!(
+            info.create_time(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_555_526_400))
+        );
+        assert_eq!(info.exit_time(), None);
+        assert_eq!(
+            thread_infos.get_thread_info(17).unwrap().start_address(),
+            0x7ff6_1000
+        );
+        assert_eq!(thread_infos.get_thread_info(1), None);
     }
 
-    /// A listing of all the streams in the Minidump.
-    ///
-    /// If there are multiple copies of the same stream (which should not happen for
-    /// well-formed Minidumps), then only one of them will be yielded, arbitrarily.
-    pub fn all_streams(&self) -> impl Iterator<Item = &md::MINIDUMP_DIRECTORY> + '_ {
-        self.streams.iter().map(|(_, (_, stream))| stream)
+    #[test]
+    fn test_handle_operation_list() {
+        let operation = |handle: u64, operation_type: u32, backtrace: &[u64]| {
+            let mut section = Section::with_endian(Endian::Little)
+                .D64(handle)
+                .D32(0x1234) // process_id
+                .D32(0x5678) // thread_id
+                .D32(operation_type)
+                .D32(0) // spare0
+                .D32(backtrace.len() as u32) // depth
+                .D32(7); // index
+            for i in 0..md::AVRF_MAX_TRACES {
+                section = section.D64(backtrace.get(i).copied().unwrap_or(0));
+            }
+            section
+        };
+        let mut stream = Section::with_endian(Endian::Little)
+            .D32(16) // size_of_header
+            .D32(mem::size_of::<md::AVRF_HANDLE_OPERATION>() as u32) // size_of_entry
+            .D32(3) // number_of_entries
+            .D32(0); // reserved
+        stream = stream
+            .append_section(operation(0x44, 1, &[0x7ff6_1234, 0x7ff6_5678]))
+            .append_section(operation(0x44, 2, &[0x7ff6_9abc]))
+            .append_section(operation(0x48, 77, &[]));
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::HandleOperationListStream as u32,
+            section: stream,
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        let operations = dump.get_stream::<MinidumpHandleOperationList>().unwrap();
+        let operations = operations.iter().collect::<Vec<_>>();
+        assert_eq!(operations.len(), 3);
+
+        assert_eq!(operations[0].raw.handle, 0x44);
+        assert_eq!(operations[0].raw.process_id, 0x1234);
+        assert_eq!(operations[0].raw.thread_id, 0x5678);
+        assert_eq!(
+            operations[0].operation_type,
+            Some(md::HandleTraceOperation::OperationDbOPEN)
+        );
+        assert_eq!(operations[0].operation_name(), "OPEN");
+        assert_eq!(operations[0].backtrace(), &[0x7ff6_1234, 0x7ff6_5678]);
+
+        assert_eq!(operations[1].operation_name(), "CLOSE");
+        assert_eq!(operations[1].backtrace(), &[0x7ff6_9abc]);
+
+        assert_eq!(operations[2].operation_type, None);
+        assert_eq!(operations[2].operation_name(), "UNKNOWN");
+        assert!(operations[2].backtrace().is_empty());
     }
 
-    /// Write a verbose description of the `Minidump` to `f`.
-    pub fn print<W: Write>(&self, f: &mut W) -> io::Result<()> {
-        fn get_stream_name(stream_type: u32) -> Cow<'static, str> {
-            if let Some(stream) = MINIDUMP_STREAM_TYPE::from_u32(stream_type) {
-                Cow::Owned(format!("{:?}", stream))
-            } else {
-                Cow::Borrowed("unknown")
+    #[test]
+    fn test_function_table() {
+        let table = |section: Section, min: u64, max: u64, functions: &[(u32, u32, u32)]| {
+            let mut section = section
+                .D64(min) // minimum_address
+                .D64(max) // maximum_address
+                .D64(min) // base_address
+                .D32(functions.len() as u32) // entry_count
+                .D32(4) // size_of_align_pad
+                .append_repeated(0xab, 48); // native_descriptor
+            for &(begin, end, unwind_info) in functions {
+                section = section.D32(begin).D32(end).D32(unwind_info);
             }
-        }
+            section.append_repeated(0, 4)
+        };
+        let section = Section::with_endian(Endian::Little)
+            .D32(24) // size_of_header
+            .D32(32) // size_of_descriptor
+            .D32(48) // size_of_native_descriptor
+            .D32(12) // size_of_function_entry
+            .D32(2) // number_of_descriptors
+            .D32(8) // size_of_align_pad
+            .append_repeated(0, 8);
+        let section = table(
+            section,
+            0x2000_0000,
+            0x2000_1000,
+            &[(0x0, 0x40, 0x800), (0x40, 0x100, 0x810)],
+        );
+        let section = table(section, 0x1000_0000, 0x1000_0100, &[(0x10, 0x20, 0x80)]);
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::FunctionTable as u32,
+            section,
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        let function_table = dump.get_stream::<MinidumpFunctionTable>().unwrap();
+        assert_eq!(function_table.iter().count(), 2);
+
+        let first = function_table.iter().next().unwrap();
+        assert_eq!(first.raw.minimum_address, 0x2000_0000);
+        assert_eq!(first.entry_count(), 2);
+        assert_eq!(first.native_descriptor, &[0xab; 48][..]);
+        assert_eq!(first.raw_entries().count(), 2);
+        assert_eq!(
+            first.runtime_functions().collect::<Vec<_>>(),
+            vec![
+                md::IMAGE_RUNTIME_FUNCTION_ENTRY {
+                    begin_address: 0x0,
+                    end_address: 0x40,
+                    unwind_info_address: 0x800,
+                },
+                md::IMAGE_RUNTIME_FUNCTION_ENTRY {
+                    begin_address: 0x40,
+                    end_address: 0x100,
+                    unwind_info_address: 0x810,
+                },
+            ]
+        );
 
-        write!(
-            f,
-            r#"MDRawHeader
-  signature            = {:#x}
-  version              = {:#x}
-  stream_count         = {}
-  stream_directory_rva = {:#x}
-  checksum             = {:#x}
-  time_date_stamp      = {:#x} {}
-  flags                = {:#x}
+        let by_addr = function_table
+            .by_addr()
+            .map(|table| table.raw.minimum_address)
+            .collect::<Vec<_>>();
+        assert_eq!(by_addr, vec![0x1000_0000, 0x2000_0000]);
 
-"#,
-            self.header.signature,
-            self.header.version,
-            self.header.stream_count,
-            self.header.stream_directory_rva,
-            self.header.checksum,
-            self.header.time_date_stamp,
-            format_time_t(self.header.time_date_stamp),
-            self.header.flags,
-        )?;
-        let mut streams = self.streams.iter().collect::<Vec<_>>();
-        streams.sort_by(|&(&_, &(a, _)), &(&_, &(b, _))| a.cmp(&b));
-        for &(_, &(i, ref stream)) in streams.iter() {
-            write!(
-                f,
-                r#"mDirectory[{}]
-MDRawDirectory
-  stream_type        = {:#x} ({})
-  location.data_size = {}
-  location.rva       = {:#x}
+        let table = function_table.table_for_address(0x1000_00ff).unwrap();
+        assert_eq!(table.raw.minimum_address, 0x1000_0000);
+        assert_eq!(table.runtime_functions().next().unwrap().end_address, 0x20);
+        assert!(function_table.table_for_address(0x1000_0100).is_none());
+        assert!(function_table.table_for_address(0x2000_0fff).is_some());
+    }
 
-"#,
-                i,
-                stream.stream_type,
-                get_stream_name(stream.stream_type),
-                stream.location.data_size,
-                stream.location.rva
-            )?;
-        }
-        writeln!(f, "Streams:")?;
-        streams.sort_by(|&(&a, &(_, _)), &(&b, &(_, _))| a.cmp(&b));
-        for (_, &(i, ref stream)) in streams {
-            writeln!(
-                f,
-                "  stream type {:#x} ({}) at index {}",
-                stream.stream_type,
-                get_stream_name(stream.stream_type),
-                i
-            )?;
+    #[test]
+    fn test_process_vm_counters() {
+        let counters = |section: Section| {
+            section
+                .D32(1234) // page_fault_count
+                .D64(0x400_0000) // peak_working_set_size
+                .D64(0x300_0000) // working_set_size
+                .D64(0x10000) // quota_peak_paged_pool_usage
+                .D64(0x8000) // quota_paged_pool_usage
+                .D64(0x4000) // quota_peak_non_paged_pool_usage
+                .D64(0x2000) // quota_non_paged_pool_usage
+                .D64(0x500_0000) // pagefile_usage
+                .D64(0x600_0000) // peak_pagefile_usage
+        };
+
+        let section = counters(
+            Section::with_endian(Endian::Little)
+                .D16(1) // revision
+                .D16(0), // padding
+        )
+        .D64(0x480_0000); // private_usage
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::ProcessVmCountersStream as u32,
+            section,
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        let vm_counters = dump.get_stream::<MinidumpProcessVmCounters>().unwrap();
+        assert!(matches!(vm_counters.raw, RawProcessVmCounters::V1(_)));
+        assert_eq!(vm_counters.page_fault_count(), Some(1234));
+        assert_eq!(vm_counters.working_set_size(), Some(0x300_0000));
+        assert_eq!(vm_counters.peak_pagefile_usage(), Some(0x600_0000));
+        assert_eq!(vm_counters.private_usage(), Some(0x480_0000));
+        assert_eq!(vm_counters.virtual_size(), None);
+        assert_eq!(vm_counters.job_total_commit_limit(), None);
+
+        // Version 2, without the EX2 or JOB fields
+        let flags = md::ProcessVmCountersFlags::MINIDUMP_PROCESS_VM_COUNTERS
+            | md::ProcessVmCountersFlags::MINIDUMP_PROCESS_VM_COUNTERS_VIRTUALSIZE
+            | md::ProcessVmCountersFlags::MINIDUMP_PROCESS_VM_COUNTERS_EX;
+        let mut section = counters(
+            Section::with_endian(Endian::Little)
+                .D16(2) // revision
+                .D16(flags.bits()),
+        )
+        .D64(0x7000_0000) // peak_virtual_size
+        .D64(0x6000_0000) // virtual_size
+        .D64(0x480_0000); // private_usage
+        for _ in 0..8 {
+            section = section.D64(0xffff_ffff);
         }
-        writeln!(f)?;
-        Ok(())
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::ProcessVmCountersStream as u32,
+            section,
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        let vm_counters = dump.get_stream::<MinidumpProcessVmCounters>().unwrap();
+        assert!(matches!(vm_counters.raw, RawProcessVmCounters::V2(_)));
+        assert_eq!(vm_counters.page_fault_count(), Some(1234));
+        assert_eq!(vm_counters.working_set_size(), Some(0x300_0000));
+        assert_eq!(vm_counters.virtual_size(), Some(0x6000_0000));
+        assert_eq!(vm_counters.peak_virtual_size(), Some(0x7000_0000));
+        assert_eq!(vm_counters.private_usage(), Some(0x480_0000));
+        assert_eq!(vm_counters.private_working_set_size(), None);
+        assert_eq!(vm_counters.job_private_commit_limit(), None);
+
+        let mut output = vec![];
+        vm_counters.print(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("= 0x6000000 (96.0 MiB)"), "{}", output);
+        assert!(output.contains("private_working_set_size      = (invalid)"));
     }
-}
 
-fn stream_vendor(stream_type: u32) -> &'static str {
-    if stream_type <= MINIDUMP_STREAM_TYPE::LastReservedStream as u32 {
-        "Official"
-    } else {
-        match stream_type & 0xFFFF0000 {
-            0x4767_0000 => "Google Extension",
-            0x4d7a_0000 => "Mozilla Extension",
-            _ => "Unknown Extension",
-        }
+    #[test]
+    fn test_token_info_list() {
+        // A SID for S-1-5-21-1004336348-1177238915-682003330-512
+        let sid = Section::with_endian(Endian::Little)
+            .D8(1) // revision
+            .D8(5) // sub_authority_count
+            .append_bytes(&[0, 0, 0, 0, 0, 5]) // identifier_authority
+            .D32(21)
+            .D32(1004336348)
+            .D32(1177238915)
+            .D32(682003330)
+            .D32(512)
+            .get_contents()
+            .unwrap();
+        let section = Section::with_endian(Endian::Little)
+            .D32(16 + 16 + 4 + 16 + sid.len() as u32) // token_list_size
+            .D32(2) // token_list_entries
+            .D32(16) // list_header_size
+            .D32(16) // element_header_size
+            .D32(16 + 4) // token_size
+            .D32(1) // token_id
+            .D64(0x1c) // token_handle
+            .append_bytes(&[1, 2, 3, 4])
+            .D32(16 + sid.len() as u32) // token_size
+            .D32(2) // token_id
+            .D64(0x2c) // token_handle
+            .append_bytes(&sid);
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::TokenStream as u32,
+            section,
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        let tokens = dump.get_stream::<MinidumpTokenInfoList>().unwrap();
+        let tokens = tokens.iter().collect::<Vec<_>>();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].raw.token_id, 1);
+        assert_eq!(tokens[0].raw.token_handle, 0x1c);
+        assert_eq!(tokens[0].data, &[1, 2, 3, 4]);
+        assert_eq!(tokens[1].raw.token_handle, 0x2c);
+        assert_eq!(
+            MinidumpTokenInfo::format_sid(tokens[1].data).as_deref(),
+            Some("S-1-5-21-1004336348-1177238915-682003330-512")
+        );
+        assert_eq!(MinidumpTokenInfo::format_sid(tokens[0].data), None);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use md::GUID;
-    use minidump_common::format::{PlatformId, ProcessorArchitecture};
-    use minidump_synth::{
-        self, AnnotationValue, CrashpadInfo, DumpString, Exception, Memory,
-        MemoryInfo as SynthMemoryInfo, MiscFieldsBuildString, MiscFieldsPowerInfo,
-        MiscFieldsProcessTimes, MiscFieldsTimeZone, MiscInfo5Fields, MiscStream,
-        Module as SynthModule, ModuleCrashpadInfo, SimpleStream, SynthMinidump, SystemInfo, Thread,
-        ThreadName, UnloadedModule as SynthUnloadedModule, STOCK_VERSION_INFO,
-    };
-    use std::mem;
-    use test_assembler::*;
+    #[test]
+    fn test_linux_dso_debug() {
+        let exe_name = DumpString::new("", Endian::Little);
+        let lib_name = DumpString::new("/usr/lib/libfoo.so", Endian::Little);
+
+        // 64-bit entries have padding before `ld`.
+        let map = Section::with_endian(Endian::Little)
+            .D64(0)
+            .D32(exe_name.file_offset())
+            .D32(0)
+            .D64(0x5000_1000)
+            .D64(0x7f00_0000_0000)
+            .D32(lib_name.file_offset())
+            .D32(0)
+            .D64(0x7f00_0000_2000);
+        let section = Section::with_endian(Endian::Little)
+            .D32(1) // version
+            .D32(map.file_offset()) // map
+            .D32(2) // dso_count
+            .D32(0) // padding
+            .D64(0x7f00_1000_0000) // brk
+            .D64(0x7f00_2000_0000) // ldbase
+            .D64(0x5000_2000) // dynamic
+            .append_bytes(&[1, 2, 3, 4]);
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(system_info)
+            .add_stream(SimpleStream {
+                stream_type: MINIDUMP_STREAM_TYPE::LinuxDsoDebug as u32,
+                section,
+            })
+            .add(map)
+            .add(exe_name)
+            .add(lib_name);
+        let dump = read_synth_dump(dump).unwrap();
+        let dso_debug = dump.get_stream::<MinidumpLinuxDsoDebug>().unwrap();
+        assert!(matches!(dso_debug.raw, RawDsoDebug::DsoDebug64(_)));
+        assert_eq!(dso_debug.version(), 1);
+        assert_eq!(dso_debug.brk(), 0x7f00_1000_0000);
+        assert_eq!(dso_debug.ldbase(), 0x7f00_2000_0000);
+        assert_eq!(dso_debug.dynamic(), 0x5000_2000);
+        assert_eq!(dso_debug.dynamic_section, &[1, 2, 3, 4]);
+        assert_eq!(
+            dso_debug.iter().collect::<Vec<_>>(),
+            vec![(0, ""), (0x7f00_0000_0000, "/usr/lib/libfoo.so")]
+        );
+        assert_eq!(dso_debug.entries()[1].ld, 0x7f00_0000_2000);
+
+        // 32-bit dumps (the synth default is x86) have 32-bit addresses and no padding.
+        let lib_name = DumpString::new("/lib/libbar.so", Endian::Little);
+        let map = Section::with_endian(Endian::Little)
+            .D32(0x4000_0000)
+            .D32(lib_name.file_offset())
+            .D32(0x4000_2000);
+        let section = Section::with_endian(Endian::Little)
+            .D32(1) // version
+            .D32(map.file_offset()) // map
+            .D32(1) // dso_count
+            .D32(0x1000) // brk
+            .D32(0x2000) // ldbase
+            .D32(0x3000); // dynamic
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(SystemInfo::new(Endian::Little))
+            .add_stream(SimpleStream {
+                stream_type: MINIDUMP_STREAM_TYPE::LinuxDsoDebug as u32,
+                section,
+            })
+            .add(map)
+            .add(lib_name);
+        let dump = read_synth_dump(dump).unwrap();
+        let dso_debug = dump.get_stream::<MinidumpLinuxDsoDebug>().unwrap();
+        assert!(matches!(dso_debug.raw, RawDsoDebug::DsoDebug32(_)));
+        assert_eq!(dso_debug.ldbase(), 0x2000);
+        assert!(dso_debug.dynamic_section.is_empty());
+        assert_eq!(
+            dso_debug.entries(),
+            &[MinidumpLinuxLinkMapEntry {
+                addr: 0x4000_0000,
+                name: String::from("/lib/libbar.so"),
+                ld: 0x4000_2000,
+            }]
+        );
+    }
 
-    fn read_synth_dump<'a>(dump: SynthMinidump) -> Result<Minidump<'a, Vec<u8>>, Error> {
-        Minidump::read(dump.finish().unwrap())
+    #[test]
+    fn test_linux_auxv() {
+        use md::AuxvType::*;
+
+        let section = [
+            (AT_SYSINFO_EHDR as u64, 0x7ffc_0000_0000),
+            (AT_PAGESZ as u64, 0x1000),
+            (AT_PHDR as u64, 0x5555_0000_0040),
+            (AT_ENTRY as u64, 0x5555_0000_1000),
+            (0x1234, 5),
+            (AT_SECURE as u64, 1),
+            (AT_NULL as u64, 0),
+            (AT_PLATFORM as u64, 0xdead),
+        ]
+        .iter()
+        .fold(
+            Section::with_endian(Endian::Little),
+            |section, &(key, value)| section.D64(key).D64(value),
+        );
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(system_info)
+            .add_stream(SimpleStream {
+                stream_type: MINIDUMP_STREAM_TYPE::LinuxAuxv as u32,
+                section,
+            });
+        let dump = read_synth_dump(dump).unwrap();
+        let auxv = dump.get_stream::<MinidumpLinuxAuxv>().unwrap();
+        assert_eq!(auxv.vdso_base(), Some(0x7ffc_0000_0000));
+        assert_eq!(auxv.page_size(), Some(0x1000));
+        assert_eq!(auxv.program_headers(), Some(0x5555_0000_0040));
+        assert_eq!(auxv.entry_point(), Some(0x5555_0000_1000));
+        assert_eq!(auxv.secure(), Some(true));
+        // Entries after AT_NULL are ignored.
+        assert_eq!(auxv.platform(), None);
+        assert_eq!(auxv.iter().count(), 6);
+        assert!(auxv.iter().any(|entry| entry == (0x1234, 5)));
+
+        // 32-bit dumps (the synth default is x86) have 32-bit words, and a truncated
+        // vector is fine.
+        let section = Section::with_endian(Endian::Little)
+            .D32(AT_ENTRY as u32)
+            .D32(0x8048000)
+            .D32(AT_SECURE as u32);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(SystemInfo::new(Endian::Little))
+            .add_stream(SimpleStream {
+                stream_type: MINIDUMP_STREAM_TYPE::LinuxAuxv as u32,
+                section,
+            });
+        let dump = read_synth_dump(dump).unwrap();
+        let auxv = dump.get_stream::<MinidumpLinuxAuxv>().unwrap();
+        assert_eq!(
+            auxv.iter().collect::<Vec<_>>(),
+            vec![(AT_ENTRY as u64, 0x8048000)]
+        );
+        assert_eq!(auxv.secure(), None);
     }
 
     #[test]
-    fn test_simple_synth_dump() {
-        const STREAM_TYPE: u32 = 0x11223344;
+    fn test_linux_limits() {
+        let limits = b"Limit                     Soft Limit           Hard Limit           Units
+Max cpu time              unlimited            unlimited            seconds
+Max core file size        0                    unlimited            bytes
+Max open files            1024                 1048576              files
+Max address space         unlimited            unlimited            bytes
+Max nice priority         0                    0
+";
         let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
-            stream_type: STREAM_TYPE,
-            section: Section::with_endian(Endian::Little).D32(0x55667788),
+            stream_type: MINIDUMP_STREAM_TYPE::MozLinuxLimits as u32,
+            section: Section::new().append_bytes(limits),
         });
         let dump = read_synth_dump(dump).unwrap();
-        assert_eq!(dump.endian, LE);
+        let limits = dump.get_stream::<MinidumpLinuxLimits>().unwrap();
+        assert_eq!(limits.iter().count(), 5);
         assert_eq!(
-            dump.get_raw_stream(STREAM_TYPE).unwrap(),
-            &[0x88, 0x77, 0x66, 0x55]
+            limits.open_files(),
+            Some(&MinidumpLinuxLimit {
+                name: "Max open files",
+                soft: Some(1024),
+                hard: Some(1048576),
+                unit: Some("files"),
+            })
         );
+        let address_space = limits.address_space().unwrap();
+        assert_eq!((address_space.soft, address_space.hard), (None, None));
+        let core = limits.core_file_size().unwrap();
+        assert_eq!((core.soft, core.hard), (Some(0), None));
+        assert_eq!(limits.get("Max nice priority").unwrap().unit, None);
+        assert_eq!(limits.get("Limit"), None);
+    }
+
+    #[test]
+    fn test_mac_bootargs() {
+        let bootargs = DumpString::new("-v keepsyms=1", Endian::Little);
+        let stream = SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::MozMacosBootargsStream as u32,
+            section: Section::with_endian(Endian::Little)
+                .D32(MINIDUMP_STREAM_TYPE::MozMacosBootargsStream as u32)
+                .D64(bootargs.file_offset()),
+        };
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_stream(stream)
+            .add(bootargs);
+        let dump = read_synth_dump(dump).unwrap();
+        let bootargs = dump.get_stream::<MinidumpMacBootargs>().unwrap();
+        assert_eq!(bootargs.bootargs.as_deref(), Some("-v keepsyms=1"));
+
+        // A dangling string loses the bootargs, but not the stream.
+        let stream = SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::MozMacosBootargsStream as u32,
+            section: Section::with_endian(Endian::Little)
+                .D32(MINIDUMP_STREAM_TYPE::MozMacosBootargsStream as u32)
+                .D64(0xffff_ffff),
+        };
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(stream);
+        let dump = read_synth_dump(dump).unwrap();
+        let bootargs = dump.get_stream::<MinidumpMacBootargs>().unwrap();
+        assert_eq!(bootargs.bootargs, None);
+    }
 
+    #[test]
+    fn test_assertion() {
+        let field = |section: Section, s: &str| {
+            let mut units = s.encode_utf16().collect::<Vec<_>>();
+            units.resize(128, 0);
+            units
+                .into_iter()
+                .fold(section, |section, unit| section.D16(unit))
+        };
+        let section = Section::with_endian(Endian::Little);
+        let section = field(section, "x != 0");
+        let section = field(section, "foo");
+        let section = field(section, "bar.cc");
+        let section = section
+            .D32(42) // line
+            .D32(md::AssertionType::InvalidParameter as u32); // type
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::AssertionInfoStream as u32,
+            section,
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        let assertion = dump.get_stream::<MinidumpAssertion>().unwrap();
+        assert_eq!(assertion.expression().unwrap(), "x != 0");
+        assert_eq!(assertion.function().unwrap(), "foo");
+        assert_eq!(assertion.file().unwrap(), "bar.cc");
+        assert_eq!(assertion.line(), 42);
         assert_eq!(
-            dump.get_raw_stream(0xaabbccddu32),
-            Err(Error::StreamNotFound)
+            assertion.assertion_type(),
+            Some(md::AssertionType::InvalidParameter)
         );
     }
 
     #[test]
-    fn test_simple_synth_dump_bigendian() {
-        const STREAM_TYPE: u32 = 0x11223344;
-        let dump = SynthMinidump::with_endian(Endian::Big).add_stream(SimpleStream {
-            stream_type: STREAM_TYPE,
-            section: Section::with_endian(Endian::Big).D32(0x55667788),
+    fn test_ipt_trace() {
+        let thread = |section: Section, id: u64, trace: &[u8]| {
+            section
+                .D64(id) // thread_id
+                .D32(1) // timing_settings
+                .D32(3) // mtc_frequency
+                .D32(0x2a) // frequency_to_tsc_ratio
+                .D32(2) // ring_buffer_offset
+                .D32(trace.len() as u32) // trace_size
+                .append_bytes(trace)
+        };
+        let traces = thread(
+            Section::with_endian(Endian::Little),
+            0x1234,
+            b"\x02\x82\x02\x82",
+        );
+        let traces = thread(traces, 0x5678, b"\x99");
+        let section = Section::with_endian(Endian::Little)
+            .D16(1) // trace_version
+            .D16(1) // valid_trace
+            .D32(traces.size() as u32) // trace_size
+            .append_section(traces);
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::IptTraceStream as u32,
+            section,
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        let ipt = dump.get_stream::<MinidumpIptTrace>().unwrap();
+        assert_eq!(ipt.raw.trace_version, 1);
+        let threads = ipt.iter().collect::<Vec<_>>();
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].raw.thread_id, 0x1234);
+        assert_eq!(threads[0].raw.ring_buffer_offset, 2);
+        assert_eq!(threads[0].trace, b"\x02\x82\x02\x82");
+        assert_eq!(ipt.thread_trace(0x5678).unwrap().trace, b"\x99");
+        assert_eq!(ipt.thread_trace(0x9999), None);
+
+        // The traces are borrowed from the stream.
+        let raw = dump.raw_stream(MinidumpIptTrace::STREAM_TYPE).unwrap();
+        let raw_range = raw.as_ptr_range();
+        assert!(raw_range.contains(&threads[0].trace.as_ptr()));
+        assert_eq!(dump.raw_stream(0xaabbccdd), None);
+        assert!(dump
+            .unimplemented_streams()
+            .all(|stream| stream.stream_type != MINIDUMP_STREAM_TYPE::IptTraceStream));
+    }
+
+    #[test]
+    fn test_ipt_trace_truncated() {
+        // A thread trace that runs past the end of the stream's trace data.
+        let section = Section::with_endian(Endian::Little)
+            .D16(1) // trace_version
+            .D16(1) // valid_trace
+            .D32(28 + 2) // trace_size
+            .D64(0x1234) // thread_id
+            .append_repeated(0, 12)
+            .D32(4) // ring_buffer_offset
+            .D32(4) // trace_size
+            .append_bytes(b"\x02\x82");
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::IptTraceStream as u32,
+            section,
         });
         let dump = read_synth_dump(dump).unwrap();
-        assert_eq!(dump.endian, BE);
         assert_eq!(
-            dump.get_raw_stream(STREAM_TYPE).unwrap(),
-            &[0x55, 0x66, 0x77, 0x88]
+            dump.get_stream::<MinidumpIptTrace>().unwrap_err(),
+            Error::StreamReadFailure
         );
 
+        // Trace data that runs past the end of the stream.
+        let section = Section::with_endian(Endian::Little)
+            .D16(1) // trace_version
+            .D16(1) // valid_trace
+            .D32(0x100); // trace_size
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::IptTraceStream as u32,
+            section,
+        });
+        let dump = read_synth_dump(dump).unwrap();
         assert_eq!(
-            dump.get_raw_stream(0xaabbccddu32),
-            Err(Error::StreamNotFound)
+            dump.get_stream::<MinidumpIptTrace>().unwrap_err(),
+            Error::StreamSizeMismatch {
+                expected: 0x108,
+                actual: 8
+            }
         );
     }
 
     #[test]
-    fn test_thread_names() {
-        let good_thread_id = 17;
-        let corrupt_thread_id = 123;
-
-        let good_name = DumpString::new("MyCoolThread", Endian::Little);
-        // No corrupt name, will dangle
-
-        let good_thread_name_entry =
-            ThreadName::new(Endian::Little, good_thread_id, Some(&good_name));
-        let corrupt_thread_name_entry = ThreadName::new(Endian::Little, corrupt_thread_id, None);
-
-        let dump = SynthMinidump::with_endian(Endian::Little)
-            .add_thread_name(good_thread_name_entry)
-            .add_thread_name(corrupt_thread_name_entry)
-            .add(good_name);
-
+    fn test_system_memory_info() {
+        let section = Section::with_endian(Endian::Little)
+            .D16(1) // revision
+            .D16(0) // flags
+            // basic_info
+            .D32(156250) // timer_resolution
+            .D32(0x1000) // page_size
+            .D32(0x40_0000) // number_of_physical_pages
+            .D32(1) // lowest_physical_page_number
+            .D32(0x43_ffff) // highest_physical_page_number
+            .D32(0x10000) // allocation_granularity
+            .D64(0x10000) // minimum_user_mode_address
+            .D64(0x7fff_fffe_ffff) // maximum_user_mode_address
+            .D64(0xff) // active_processors_affinity_mask
+            .D32(8) // number_of_processors
+            // file_cache_info
+            .D64(0x1000_0000) // current_size
+            .D64(0x2000_0000) // peak_size
+            .D32(1234) // page_fault_count
+            .D64(0x10_0000) // minimum_working_set
+            .D64(0x20_0000) // maximum_working_set
+            .D64(0) // current_size_including_transition_in_pages
+            .D64(0) // peak_size_including_transition_in_pages
+            .D32(0) // transition_re_purpose_count
+            .D32(0) // flags
+            // basic_perf_info
+            .D64(0x8_0000) // available_pages
+            .D64(0x30_0000) // committed_pages
+            .D64(0x60_0000) // commit_limit
+            .D64(0x38_0000) // peak_commitment
+            // A truncated perf_info
+            .D64(0x1234); // idle_process_time
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::SystemMemoryInfoStream as u32,
+            section,
+        });
         let dump = read_synth_dump(dump).unwrap();
-        let thread_names = dump.get_stream::<MinidumpThreadNames>().unwrap();
-        assert_eq!(thread_names.names.len(), 1);
+        let info = dump.get_stream::<MinidumpSystemMemoryInfo>().unwrap();
+        assert_eq!(info.revision, 1);
+        assert_eq!(info.basic_info.as_ref().unwrap().number_of_processors, 8);
         assert_eq!(
-            &*thread_names.get_name(good_thread_id).unwrap(),
-            "MyCoolThread"
+            info.file_cache_info.as_ref().unwrap().page_fault_count,
+            1234
         );
-        assert_eq!(thread_names.get_name(corrupt_thread_id), None);
+        assert_eq!(info.perf_info, None);
+        assert_eq!(info.page_size(), Some(0x1000));
+        assert_eq!(info.available_physical_memory(), Some(0x8000_0000));
+        assert_eq!(info.committed_memory(), Some(0x3_0000_0000));
+        assert_eq!(info.commit_limit(), Some(0x6_0000_0000));
+
+        // Unknown revisions still give the fields we know about.
+        let section = Section::with_endian(Endian::Little)
+            .D16(7) // revision
+            .D16(0) // flags
+            .append_repeated(0, 16);
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(SimpleStream {
+            stream_type: MINIDUMP_STREAM_TYPE::SystemMemoryInfoStream as u32,
+            section,
+        });
+        let dump = read_synth_dump(dump).unwrap();
+        let info = dump.get_stream::<MinidumpSystemMemoryInfo>().unwrap();
+        assert_eq!(info.revision, 7);
+        assert_eq!(info.basic_info, None);
+        assert_eq!(info.commit_limit(), None);
     }
 
     #[test]
@@ -5336,6 +11204,41 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_module_version_info() {
+        let name1 = DumpString::new("windows module", Endian::Little);
+        let name2 = DumpString::new("linux module", Endian::Little);
+        let zeroed = md::VS_FIXEDFILEINFO::default();
+        let module1 = SynthModule::new(Endian::Little, 0x1000, 0x1000, &name1, 0, 0, None);
+        let module2 = SynthModule::new(Endian::Little, 0x2000, 0x1000, &name2, 0, 0, &zeroed);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_module(module1)
+            .add_module(module2)
+            .add(name1)
+            .add(name2);
+        let dump = read_synth_dump(dump).unwrap();
+        let module_list = dump.get_stream::<MinidumpModuleList>().unwrap();
+        let modules = module_list.iter().collect::<Vec<_>>();
+
+        let info = modules[0].version_info().unwrap();
+        assert_eq!(info.file_version, [0x1111, 0x1111, 0x2222, 0x2222]);
+        assert_eq!(info.product_version, [0x3333, 0x3333, 0x4444, 0x4444]);
+        assert_eq!(info.file_flags, md::VersionFileFlags::VS_FF_DEBUG);
+        assert_eq!(info.os(), Some(md::VersionFileOs::VOS_NT_WINDOWS32));
+        assert_eq!(info.file_type(), Some(md::VersionFileType::VFT_APP));
+        assert_eq!(modules[0].file_version().unwrap(), "4369.4369.8738.8738");
+        assert_eq!(
+            modules[0].product_version().unwrap(),
+            "13107.13107.17476.17476"
+        );
+        assert_eq!(modules[0].version().unwrap(), "4369.4369.8738.8738");
+
+        assert_eq!(modules[1].version_info(), None);
+        assert_eq!(modules[1].file_version(), None);
+        assert_eq!(modules[1].product_version(), None);
+        assert_eq!(modules[1].version(), None);
+    }
+
     #[test]
     fn test_module_list_pdb20() {
         let name = DumpString::new("single module", Endian::Little);
@@ -5406,6 +11309,58 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_unloaded_modules_at_address() {
+        let name1 = DumpString::new("first.dll", Endian::Little);
+        let name2 = DumpString::new("second.dll", Endian::Little);
+        let name3 = DumpString::new("other.dll", Endian::Little);
+        // second.dll was loaded twice at the same address, and first.dll
+        // overlaps both loads, so every entry should be reported in dump order.
+        let module1 =
+            SynthUnloadedModule::new(Endian::Little, 0x2000, 0x1000, &name2, 0x1111, 0xaaaa);
+        let module2 =
+            SynthUnloadedModule::new(Endian::Little, 0x1000, 0x3000, &name1, 0x2222, 0xbbbb);
+        let module3 =
+            SynthUnloadedModule::new(Endian::Little, 0x2000, 0x1000, &name2, 0x3333, 0xcccc);
+        let module4 =
+            SynthUnloadedModule::new(Endian::Little, 0x8000, 0x1000, &name3, 0x4444, 0xdddd);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_unloaded_module(module1)
+            .add_unloaded_module(module2)
+            .add_unloaded_module(module3)
+            .add_unloaded_module(module4)
+            .add(name1)
+            .add(name2)
+            .add(name3);
+        let dump = read_synth_dump(dump).unwrap();
+        let module_list = dump.get_stream::<MinidumpUnloadedModuleList>().unwrap();
+
+        let found = module_list
+            .unloaded_modules_at_address(0x2800)
+            .map(|module| {
+                (
+                    module.name.as_str(),
+                    module.time_date_stamp(),
+                    module.checksum(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("second.dll", 0x1111, 0xaaaa),
+                ("first.dll", 0x2222, 0xbbbb),
+                ("second.dll", 0x3333, 0xcccc),
+            ]
+        );
+
+        let found = module_list
+            .unloaded_modules_at_address(0x1800)
+            .map(|module| module.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec!["first.dll"]);
+        assert_eq!(module_list.unloaded_modules_at_address(0x5000).count(), 0);
+    }
     #[test]
     fn test_memory_info() {
         let info1_alloc_protection = md::MemoryProtection::PAGE_GUARD;
@@ -5482,6 +11437,8 @@ mod test {
         assert_eq!(infos[0].state, info1_state);
         assert_eq!(infos[0].ty, info1_ty);
         assert!(infos[0].is_executable());
+        assert!(infos[0].is_readable());
+        assert!(!infos[0].is_writable());
 
         assert_eq!(infos[1].raw.base_address, 0xd70206ca83eb2852);
         assert_eq!(infos[1].raw.allocation_base, 0xb802064383eb2752);
@@ -5499,6 +11456,8 @@ mod test {
         assert_eq!(infos[1].state, info2_state);
         assert_eq!(infos[1].ty, info2_ty);
         assert!(!infos[1].is_executable());
+        assert!(infos[1].is_readable());
+        assert!(!infos[1].is_writable());
     }
 
     #[test]
@@ -5551,6 +11510,8 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         assert!(maps[0].is_private);
         assert!(!maps[0].is_shared);
         assert!(maps[0].is_executable());
+        assert!(maps[0].is_readable());
+        assert!(!maps[0].is_writable());
 
         assert_eq!(maps[1].base_address, 0xc70206ca83eb2852);
         assert_eq!(maps[1].final_address, 0xde0206ca83eb2852);
@@ -5566,6 +11527,8 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         assert!(!maps[1].is_private);
         assert!(maps[1].is_shared);
         assert!(!maps[1].is_executable());
+        assert!(!maps[1].is_readable());
+        assert!(maps[1].is_writable());
 
         let mut unified_infos = unified_info.by_addr();
 
@@ -5573,6 +11536,151 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         assert!(matches!(unified_infos.next(), Some(UnifiedMemoryInfo::Map(m)) if m == maps[1]));
     }
 
+    #[test]
+    fn test_linux_maps_memory_kinds() {
+        // An smaps-style listing, with VmFlags (and other details) after the mappings.
+        let input = b"\
+10000-10fff r-xp 00000000 fd:05 1196511 /usr/bin/app
+Size:                  4 kB
+VmFlags: rd ex mr mw me
+20000-20fff rw-p 00000000 00:00 0 [heap]
+30000-30fff ---p 00000000 00:00 0
+31000-31fff rw-p 00000000 00:00 0
+VmFlags: rd wr mr mw me ac
+40000-40fff rw-p 00000000 00:00 0 [anon:scudo: primary]
+50000-50fff rw-p 00000000 00:00 0 [stack:4321]
+";
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x100),
+            0x31e00,
+        );
+        let context = minidump_synth::amd64_context(Endian::Little, 0, 0x31e00);
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .set_linux_maps(input)
+            .add_thread(thread)
+            .add(context)
+            .add_memory(stack);
+        let dump = read_synth_dump(dump).unwrap();
+        let maps = dump.get_stream::<MinidumpLinuxMaps>().unwrap();
+        let threads = dump.get_stream::<MinidumpThreadList>().unwrap();
+
+        let regions = maps.iter().collect::<Vec<_>>();
+        assert_eq!(regions.len(), 6);
+        assert!(regions[0].has_vm_flag("ex"));
+        assert!(!regions[0].has_vm_flag("wr"));
+        assert_eq!(regions[1].vm_flags, None);
+        assert!(regions[3].has_vm_flag("wr"));
+        assert_eq!(
+            regions[4].kind,
+            MinidumpLinuxMapKind::NamedAnonymousMap(Cow::Borrowed(LinuxOsStr::from_bytes(
+                b"scudo: primary"
+            )))
+        );
+
+        let unified = UnifiedMemoryInfoList::new(None, Some(maps)).unwrap();
+        let kind = |address| unified.memory_kind_at_address(address, Some(&threads));
+        assert_eq!(
+            kind(0x10100),
+            MemoryKind::MappedFile(Some(String::from("/usr/bin/app")))
+        );
+        assert_eq!(kind(0x20100), MemoryKind::Heap);
+        assert_eq!(kind(0x30100), MemoryKind::Guard);
+        assert_eq!(
+            kind(0x31100),
+            MemoryKind::Stack {
+                thread_id: Some(0x1234)
+            }
+        );
+        assert_eq!(kind(0x40100), MemoryKind::Anonymous);
+        assert_eq!(
+            kind(0x50100),
+            MemoryKind::Stack {
+                thread_id: Some(4321)
+            }
+        );
+        assert_eq!(kind(0x60000), MemoryKind::Unmapped);
+        // Without the thread list, the thread's stack is just anonymous memory.
+        assert_eq!(
+            unified.memory_kind_at_address(0x31100, None),
+            MemoryKind::Anonymous
+        );
+    }
+
+    #[test]
+    fn test_memory_info_memory_kinds() {
+        let info =
+            |base, state: md::MemoryState, protection: md::MemoryProtection, ty: md::MemoryType| {
+                SynthMemoryInfo::new(
+                    Endian::Little,
+                    base,
+                    base,
+                    protection.bits(),
+                    0x1000,
+                    state.bits(),
+                    protection.bits(),
+                    ty.bits(),
+                )
+            };
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x100),
+            0x3f00,
+        );
+        let context = minidump_synth::amd64_context(Endian::Little, 0, 0x3f00);
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_memory_info(info(
+                0x1000,
+                md::MemoryState::MEM_COMMIT,
+                md::MemoryProtection::PAGE_EXECUTE_READ,
+                md::MemoryType::MEM_IMAGE,
+            ))
+            .add_memory_info(info(
+                0x2000,
+                md::MemoryState::MEM_COMMIT,
+                md::MemoryProtection::PAGE_READWRITE | md::MemoryProtection::PAGE_GUARD,
+                md::MemoryType::MEM_PRIVATE,
+            ))
+            .add_memory_info(info(
+                0x3000,
+                md::MemoryState::MEM_COMMIT,
+                md::MemoryProtection::PAGE_READWRITE,
+                md::MemoryType::MEM_PRIVATE,
+            ))
+            .add_memory_info(info(
+                0x4000,
+                md::MemoryState::MEM_COMMIT,
+                md::MemoryProtection::PAGE_READWRITE,
+                md::MemoryType::MEM_PRIVATE,
+            ))
+            .add_memory_info(info(
+                0x5000,
+                md::MemoryState::MEM_RESERVE,
+                md::MemoryProtection::PAGE_NOACCESS,
+                md::MemoryType::MEM_PRIVATE,
+            ))
+            .add_thread(thread)
+            .add(context)
+            .add_memory(stack);
+        let dump = read_synth_dump(dump).unwrap();
+        let info_list = dump.get_stream::<MinidumpMemoryInfoList>().unwrap();
+        let threads = dump.get_stream::<MinidumpThreadList>().unwrap();
+        let unified = UnifiedMemoryInfoList::new(Some(info_list), None).unwrap();
+        let kind = |address| unified.memory_kind_at_address(address, Some(&threads));
+
+        assert_eq!(kind(0x1100), MemoryKind::MappedFile(None));
+        assert_eq!(kind(0x2100), MemoryKind::Guard);
+        assert_eq!(
+            kind(0x3100),
+            MemoryKind::Stack {
+                thread_id: Some(0x1234)
+            }
+        );
+        assert_eq!(kind(0x4100), MemoryKind::Anonymous);
+        assert_eq!(kind(0x5100), MemoryKind::Unmapped);
+        assert_eq!(kind(0x6100), MemoryKind::Unmapped);
+    }
+
     #[test]
     fn test_linux_map_parse() {
         use MinidumpLinuxMapKind::*;
@@ -5931,6 +12039,85 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
                 .code_file(),
             "module 5"
         );
+        assert_eq!(
+            module_list.range_issues(),
+            &[
+                CorruptionIssue::OverlappingModules {
+                    first: 0,
+                    second: 1,
+                    address: 0x100000000,
+                },
+                CorruptionIssue::OverlappingModules {
+                    first: 0,
+                    second: 2,
+                    address: 0x100000001,
+                },
+                CorruptionIssue::OverlappingModules {
+                    first: 0,
+                    second: 3,
+                    address: 0x100000001,
+                },
+            ]
+        );
+        assert!(module_list.range_is_suspect(0));
+        assert!(!module_list.range_is_suspect(4));
+        assert_eq!(dump.check_integrity(), module_list.range_issues());
+    }
+
+    #[test]
+    fn test_module_list_bad_ranges() {
+        let module_list = MinidumpModuleList::from_modules(vec![
+            MinidumpModule::new(0x10000, 0x1000, "small"),
+            MinidumpModule::new(0x8000, 0x10000, "large"),
+            MinidumpModule::new(0x40000, 0, "empty"),
+            MinidumpModule::new(0x50000, 0x9000_0000, "huge"),
+            MinidumpModule::new(u64::MAX - 0x10, 0x1000, "wrapping"),
+            MinidumpModule::new(0x20000, 0x1000, "fine"),
+        ]);
+
+        // The larger module wins, even though it's later in the list.
+        let large = module_list.module_at_address(0x10800).unwrap();
+        assert_eq!(large.code_file(), "large");
+        assert_eq!(
+            module_list.module_at_address(0x8000).unwrap().code_file(),
+            "large"
+        );
+        assert!(module_list.module_at_address(0x40000).is_none());
+        assert!(module_list.module_at_address(0x60000).is_none());
+        assert_eq!(
+            module_list.module_at_address(0x20000).unwrap().code_file(),
+            "fine"
+        );
+
+        assert_eq!(
+            module_list.range_issues(),
+            &[
+                CorruptionIssue::OverlappingModules {
+                    first: 1,
+                    second: 0,
+                    address: 0x10000,
+                },
+                CorruptionIssue::ImplausibleModuleSize {
+                    index: 2,
+                    base_address: 0x40000,
+                    size: 0,
+                },
+                CorruptionIssue::ImplausibleModuleSize {
+                    index: 3,
+                    base_address: 0x50000,
+                    size: 0x9000_0000,
+                },
+                CorruptionIssue::ImplausibleModuleSize {
+                    index: 4,
+                    base_address: u64::MAX - 0x10,
+                    size: 0x1000,
+                },
+            ]
+        );
+        let suspect = (0..6)
+            .map(|i| module_list.range_is_suspect(i))
+            .collect::<Vec<_>>();
+        assert_eq!(suspect, [true, true, true, true, true, false]);
     }
 
     #[test]
@@ -5950,6 +12137,166 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         assert_eq!(&regions[0].bytes, &CONTENTS);
     }
 
+    #[test]
+    fn test_memory_borrows_dump_data() {
+        // Memory regions are views of the minidump's data, not copies of it,
+        // so even full-memory dumps only cost their descriptors to read.
+        let memory = |base| {
+            Memory::with_section(
+                Section::with_endian(Endian::Little).append_repeated(0xaa, 0x100),
+                base,
+            )
+        };
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_memory(memory(0x1000))
+            .add_memory(memory(0x2000));
+        let dump = read_synth_dump(dump).unwrap();
+        let data = dump.data.as_ptr_range();
+        let in_dump = |bytes: &[u8]| {
+            let bytes = bytes.as_ptr_range();
+            data.start <= bytes.start && bytes.end <= data.end
+        };
+        let memory_list = dump.get_stream::<MinidumpMemoryList<'_>>().unwrap();
+        assert!(memory_list.iter().all(|region| in_dump(region.bytes)));
+
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_memory64(memory(0x1000))
+            .add_memory64(memory(0x3000));
+        let dump = read_synth_dump(dump).unwrap();
+        let data = dump.data.as_ptr_range();
+        let in_dump = |bytes: &[u8]| {
+            let bytes = bytes.as_ptr_range();
+            data.start <= bytes.start && bytes.end <= data.end
+        };
+        let memory_list = dump.get_memory().unwrap();
+        assert_eq!(memory_list.iter().count(), 2);
+        assert!(memory_list.iter().all(|region| in_dump(region.bytes())));
+        let merged = memory_list.merged_memory_at_address(0x1000).unwrap();
+        assert!(in_dump(merged.bytes()));
+    }
+
+    #[test]
+    fn test_memory_strings() {
+        let memory = Memory::with_section(
+            Section::with_endian(Endian::Little)
+                .append_bytes(b"abort message\0junk")
+                // "héllo" in UTF-16, then an unpaired surrogate
+                .D16(u16::from(b'h'))
+                .D16(0xe9)
+                .D16(u16::from(b'l'))
+                .D16(u16::from(b'l'))
+                .D16(u16::from(b'o'))
+                .D16(0)
+                .D16(0xd800)
+                .append_bytes(b"\xffbad"),
+            0x1000,
+        );
+        let dump = SynthMinidump::with_endian(Endian::Little).add_memory64(memory);
+        let dump = read_synth_dump(dump).unwrap();
+        let memory_list = dump.get_memory().unwrap();
+        let region = memory_list.memory_at_address(0x1000).unwrap();
+
+        assert_eq!(
+            region.read_cstring_at(0x1000, 100).as_deref(),
+            Some("abort message")
+        );
+        assert_eq!(region.read_cstring_at(0x1000, 5).as_deref(), Some("abort"));
+        assert_eq!(
+            region.read_utf16_string_at(0x1012, 100).as_deref(),
+            Some("héllo")
+        );
+        assert_eq!(
+            region.read_utf16_string_at(0x1012, 2).as_deref(),
+            Some("hé")
+        );
+        // Lossy decoding, up to the end of the region.
+        assert_eq!(
+            region.read_utf16_string_at(0x101e, 1).as_deref(),
+            Some("\u{fffd}")
+        );
+        assert_eq!(
+            region.read_cstring_at(0x1020, 100).as_deref(),
+            Some("\u{fffd}bad")
+        );
+        assert_eq!(region.read_cstring_at(0x1024, 100), None);
+        assert_eq!(region.read_cstring_at(0xfff, 100), None);
+    }
+
+    #[test]
+    fn test_read_pointer_at() {
+        for endian in [Endian::Little, Endian::Big] {
+            let memory = Memory::with_section(
+                Section::with_endian(endian)
+                    .D32(0x1234_5678)
+                    .D64(0x1122_3344_5566_7788),
+                0x1000,
+            );
+            let dump = SynthMinidump::with_endian(endian).add_memory(memory);
+            let dump = read_synth_dump(dump).unwrap();
+            let memory_list = dump.get_memory().unwrap();
+            let region = memory_list.memory_at_address(0x1000).unwrap();
+
+            assert_eq!(
+                region.read_pointer_at(0x1000, PointerWidth::Bits32),
+                Some(0x1234_5678)
+            );
+            assert_eq!(
+                region.read_pointer_at(0x1004, PointerWidth::Bits64),
+                Some(0x1122_3344_5566_7788)
+            );
+            assert_eq!(region.read_pointer_at(0x100a, PointerWidth::Bits32), None);
+            assert_eq!(region.read_pointer_at(0x1000, PointerWidth::Unknown), None);
+        }
+    }
+
+    #[test]
+    fn test_find_bytes() {
+        for use_memory64 in [false, true] {
+            let memory0 = Memory::with_section(
+                Section::with_endian(Endian::Little)
+                    .append_bytes(b"cookie..")
+                    .D64(0xdead_beef_0000_1234)
+                    .append_bytes(b"..cook"),
+                0x2000,
+            );
+            // Adjacent to the first region, but "cookie" doesn't match across them.
+            let memory1 = Memory::with_section(
+                Section::with_endian(Endian::Little)
+                    .append_bytes(b"iecookiecookie")
+                    .D32(0x0000_1234),
+                0x2016,
+            );
+            let dump = SynthMinidump::with_endian(Endian::Little);
+            let dump = if use_memory64 {
+                dump.add_memory64(memory1).add_memory64(memory0)
+            } else {
+                dump.add_memory(memory1).add_memory(memory0)
+            };
+            let dump = read_synth_dump(dump).unwrap();
+            let memory_list = dump.get_memory().unwrap();
+
+            let found = memory_list.find_bytes(b"cookie").collect::<Vec<_>>();
+            assert_eq!(found, vec![0x2000, 0x2018, 0x201e]);
+            assert_eq!(memory_list.find_bytes(b"").count(), 0);
+            assert_eq!(memory_list.find_bytes(b"missing").count(), 0);
+
+            let found = memory_list
+                .find_pointer(0xdead_beef_0000_1234, PointerWidth::Bits64)
+                .collect::<Vec<_>>();
+            assert_eq!(found, vec![0x2008]);
+            let found = memory_list
+                .find_pointer(0x1234, PointerWidth::Bits32)
+                .collect::<Vec<_>>();
+            assert_eq!(found, vec![0x2008, 0x2024]);
+            assert_eq!(
+                memory_list
+                    .find_pointer(0x1234, PointerWidth::Unknown)
+                    .count(),
+                0
+            );
+        }
+    }
+
     #[test]
     fn test_memory64_list() {
         const CONTENTS0: &[u8] = b"memory_contents";
@@ -5978,6 +12325,154 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         assert_eq!(&regions[1].bytes, &CONTENTS1);
     }
 
+    #[test]
+    fn test_memory64_list_lookup() {
+        const CONTENTS0: &[u8] = b"memory_contents";
+        const CONTENTS1: &[u8] = b"another_block";
+        let memory0 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_bytes(CONTENTS0),
+            0x309d68010bd21b2c,
+        );
+        let memory1 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_bytes(CONTENTS1),
+            0x1234,
+        );
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_memory64(memory0)
+            .add_memory64(memory1);
+        let dump = read_synth_dump(dump).unwrap();
+        let memory_list = dump.get_stream::<MinidumpMemory64List<'_>>().unwrap();
+        assert_eq!(memory_list.len(), 2);
+        assert_eq!(memory_list.descriptors()[1].start_of_memory_range, 0x1234);
+
+        let region = memory_list.memory_at_address(0x1234 + 8).unwrap();
+        assert_eq!(region.base_address, 0x1234);
+        assert_eq!(region.bytes, CONTENTS1);
+        assert_eq!(region.get_memory_at_address::<u8>(0x1234 + 8), Some(b'b'));
+        let region = memory_list
+            .memory_at_address(0x309d68010bd21b2c + 1)
+            .unwrap();
+        assert_eq!(region.bytes, CONTENTS0);
+        assert!(memory_list.memory_at_address(0x1234 - 1).is_none());
+        assert!(memory_list
+            .memory_at_address(0x1234 + CONTENTS1.len() as u64)
+            .is_none());
+
+        let bases = memory_list
+            .by_addr()
+            .map(|region| region.base_address)
+            .collect::<Vec<_>>();
+        assert_eq!(bases, vec![0x1234, 0x309d68010bd21b2c]);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_memory64_list_past_4gb() {
+        // Full-memory dumps only refer to their memory through the 64-bit
+        // RVA of the Memory64List, so fake one whose memory starts past 4GB.
+        // The file is sparse, so this doesn't actually write 4GB to disk.
+        const BASE_RVA: u64 = 0x1_0000_1000;
+        const CONTENTS0: &[u8] = b"memory_contents";
+        const CONTENTS1: &[u8] = b"another_block";
+        let memory0 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_bytes(CONTENTS0),
+            0x309d68010bd21b2c,
+        );
+        let memory1 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_bytes(CONTENTS1),
+            0x1234,
+        );
+        let memory64_list = Memory64ListStream::new(Endian::Little, &Label::from_const(BASE_RVA))
+            .add_memory(&memory0)
+            .add_memory(&memory1);
+        let header = SynthMinidump::with_endian(Endian::Little)
+            .add_stream(memory64_list)
+            .finish()
+            .unwrap();
+
+        // Removes the file once the dump (declared later, so dropped first)
+        // has unmapped it, which Windows insists on.
+        struct RemoveOnDrop(std::path::PathBuf);
+        impl Drop for RemoveOnDrop {
+            fn drop(&mut self) {
+                let _ = std::fs::remove_file(&self.0);
+            }
+        }
+
+        let path = RemoveOnDrop(std::env::temp_dir().join(format!(
+            "minidump-test-memory64-past-4gb-{}.dmp",
+            std::process::id()
+        )));
+        {
+            let mut file = File::create(&path.0).unwrap();
+            file.write_all(&header).unwrap();
+            file.seek(io::SeekFrom::Start(BASE_RVA)).unwrap();
+            file.write_all(CONTENTS0).unwrap();
+            file.write_all(CONTENTS1).unwrap();
+        }
+        let dump = Minidump::read_path(&path.0).unwrap();
+        let memory_list = dump.get_stream::<MinidumpMemory64List<'_>>().unwrap();
+        let regions = memory_list.iter().collect::<Vec<_>>();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].bytes, CONTENTS0);
+        assert_eq!(regions[1].bytes, CONTENTS1);
+        let region = memory_list.memory_at_address(0x1234).unwrap();
+        assert_eq!(region.bytes, CONTENTS1);
+
+        // Memory that is supposed to be past the end of the file is rejected.
+        let memory64_list = Memory64ListStream::new(Endian::Little, &Label::from_const(BASE_RVA))
+            .add_memory(&memory0);
+        let dump = SynthMinidump::with_endian(Endian::Little).add_stream(memory64_list);
+        let dump = read_synth_dump(dump).unwrap();
+        assert!(dump.get_stream::<MinidumpMemory64List<'_>>().is_err());
+    }
+
+    #[test]
+    fn test_unified_memory_list() {
+        const CONTENTS0: &[u8] = b"memory_contents";
+        const CONTENTS1: &[u8] = b"\x01\x02\x03\x04\x05\x06\x07\x08";
+        let memory0 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_bytes(CONTENTS0),
+            0x309d68010bd21b2c,
+        );
+        let memory1 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_bytes(CONTENTS1),
+            0x1234,
+        );
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_memory64(memory0)
+            .add_memory64(memory1);
+        let dump = read_synth_dump(dump).unwrap();
+
+        // The (empty) MemoryList should be passed over for the Memory64List.
+        let memory_list = dump.get_memory().unwrap();
+        assert!(memory_list.memory64().is_some());
+
+        let regions = memory_list.iter().collect::<Vec<_>>();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].base_address(), 0x309d68010bd21b2c);
+        assert_eq!(regions[0].bytes(), CONTENTS0);
+        let by_addr = memory_list
+            .by_addr()
+            .map(|region| region.base_address())
+            .collect::<Vec<_>>();
+        assert_eq!(by_addr, vec![0x1234, 0x309d68010bd21b2c]);
+
+        let region = memory_list.memory_at_address(0x1236).unwrap();
+        assert_eq!(region.base_address(), 0x1234);
+        assert_eq!(region.size(), CONTENTS1.len() as u64);
+        assert_eq!(
+            region.get_memory_at_address::<u32>(0x1234),
+            Some(0x04030201)
+        );
+        assert_eq!(
+            region.get_memory_at_address::<u32>(0x1238),
+            Some(0x08070605)
+        );
+        assert_eq!(region.get_memory_at_address::<u32>(0x123a), None);
+        assert!(memory_list.memory_at_address(0x123c).is_none());
+    }
+
     #[test]
     fn test_memory_list_lifetimes() {
         // A memory list should not own any of the minidump data.
@@ -6056,16 +12551,86 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         assert_eq!(regions[4].base_address, 0x2000);
         assert_eq!(regions[4].size, 0x1000);
 
-        // memory_at_address should discard overlapping regions.
-        assert_eq!(memory_list.by_addr().count(), 2);
-        let m1 = memory_list.memory_at_address(0x1a00).unwrap();
-        assert_eq!(m1.base_address, 0x1000);
-        assert_eq!(m1.size, 0x1000);
-        assert_eq!(m1.bytes, &[0u8; 0x1000][..]);
-        let m2 = memory_list.memory_at_address(0x2a00).unwrap();
-        assert_eq!(m2.base_address, 0x2000);
-        assert_eq!(m2.size, 0x1000);
-        assert_eq!(m2.bytes, &[4u8; 0x1000][..]);
+        // memory_at_address should discard duplicate and contained regions,
+        // and otherwise prefer whichever region extends furthest past the
+        // address, so reads near the end of memory1 don't fail needlessly.
+        assert_eq!(memory_list.by_addr().count(), 3);
+        let m1 = memory_list.memory_at_address(0x1000).unwrap();
+        assert_eq!(m1.base_address, 0x1000);
+        assert_eq!(m1.size, 0x1000);
+        assert_eq!(m1.bytes, &[0u8; 0x1000][..]);
+        let m3 = memory_list.memory_at_address(0x1a00).unwrap();
+        assert_eq!(m3.base_address, 0x1001);
+        assert_eq!(m3.size, 0x1000);
+        assert_eq!(m3.bytes, &[2u8; 0x1000][..]);
+        assert_eq!(
+            m3.get_memory_at_address::<u64>(0x1ff9),
+            Some(0x0202020202020202)
+        );
+        let m5 = memory_list.memory_at_address(0x2000).unwrap();
+        assert_eq!(m5.base_address, 0x2000);
+        assert_eq!(m5.size, 0x1000);
+        assert_eq!(m5.bytes, &[4u8; 0x1000][..]);
+    }
+
+    #[test]
+    fn test_merged_memory() {
+        let memory1 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(1, 0x100),
+            0x1000,
+        );
+        // Continues memory1 exactly.
+        let memory2 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(2, 0x100),
+            0x1100,
+        );
+        // Overlaps the end of memory2 and continues past it.
+        let memory3 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(3, 0x100),
+            0x1180,
+        );
+        // Separated from the rest by a gap.
+        let memory4 = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(4, 0x100),
+            0x2000,
+        );
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_memory(memory3)
+            .add_memory(memory4)
+            .add_memory(memory1)
+            .add_memory(memory2);
+        let dump = read_synth_dump(dump).unwrap();
+        let memory_list = dump.get_memory().unwrap();
+
+        for address in [0x1000, 0x1150, 0x127f] {
+            let merged = memory_list.merged_memory_at_address(address).unwrap();
+            assert_eq!(merged.base_address(), 0x1000);
+            assert_eq!(merged.size(), 0x280);
+            assert_eq!(merged.region_count(), 3);
+            let mut expected = vec![1u8; 0x100];
+            expected.extend_from_slice(&[2u8; 0x80]);
+            expected.extend_from_slice(&[3u8; 0x100]);
+            assert_eq!(merged.bytes(), &expected[..]);
+
+            // A read across the seam between regions works.
+            let memory = merged.as_memory();
+            assert_eq!(
+                memory.get_memory_at_address::<u32>(0x10fe),
+                Some(0x02020101)
+            );
+        }
+
+        let merged = memory_list.merged_memory_at_address(0x2010).unwrap();
+        assert_eq!(merged.base_address(), 0x2000);
+        assert_eq!(merged.region_count(), 1);
+        assert!(matches!(
+            merged,
+            MergedMemory {
+                bytes: Cow::Borrowed(_),
+                ..
+            }
+        ));
+        assert!(memory_list.merged_memory_at_address(0x1800).is_none());
     }
 
     #[test]
@@ -6331,6 +12896,23 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         );
         assert_eq!(xstate_iter.next(), None);
         assert_eq!(xstate_iter.next(), None);
+
+        assert_eq!(misc.process_cookie(), Some(PROCESS_COOKIE));
+        let config = misc.xstate_config().unwrap();
+        assert_eq!(config.context_size, CONTEXT_SIZE);
+        assert_eq!(
+            config.features.keys().copied().collect::<Vec<_>>(),
+            vec![KNOWN_FEATURE_IDX, UNKNOWN_FEATURE_IDX]
+        );
+        assert_eq!(
+            config.feature(md::XstateFeatureIndex::LEGACY_SSE),
+            Some(features[KNOWN_FEATURE_IDX])
+        );
+        assert_eq!(config.feature(md::XstateFeatureIndex::GSSE_AND_AVX), None);
+        assert_eq!(
+            XStateConfig::from_raw(&md::XSTATE_CONFIG_FEATURE_MSC_INFO::default()),
+            None
+        );
     }
 
     #[test]
@@ -6508,6 +13090,108 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         );
     }
 
+    #[test]
+    fn test_wine_elf_code_id() {
+        // Wine writes Windows minidumps, but the process also has the ELF
+        // libraries of the host loaded.
+        let name = DumpString::new("/usr/lib/wine/ntdll.so", Endian::Little);
+        const BUILD_ID: &[u8] = &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13,
+        ];
+        let cv_record = Section::with_endian(Endian::Little)
+            .D32(md::CvSignature::Elf as u32)
+            .append_bytes(BUILD_ID);
+        let module = SynthModule::new(Endian::Little, 0x7f0000000000, 0x4000, &name, 0, 0, None)
+            .cv_record(&cv_record);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(
+                SystemInfo::new(Endian::Little)
+                    .set_platform_id(PlatformId::VER_PLATFORM_WIN32_NT as u32),
+            )
+            .add_module(module)
+            .add(name)
+            .add(cv_record);
+        let dump = read_synth_dump(dump).unwrap();
+        let module_list = dump.get_stream::<MinidumpModuleList>().unwrap();
+        let module = module_list.iter().next().unwrap();
+        assert_eq!(
+            module.code_identifier_kind(),
+            Some(CodeIdentifierKind::ElfBuildId)
+        );
+        // The build id, not the (empty) timestamp and size.
+        assert_eq!(
+            module.code_identifier().unwrap(),
+            CodeId::new("000102030405060708090a0b0c0d0e0f10111213".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_pdb70_code_id_without_system_info() {
+        let pdb70 = |file_name: &[u8]| {
+            Section::with_endian(Endian::Little)
+                .D32(md::CvSignature::Pdb70 as u32)
+                .D32(0xaabbccdd)
+                .D16(0xeeff)
+                .D16(0x0011)
+                .append_bytes(b"\x22\x33\x44\x55\x66\x77\x88\x99")
+                .D32(1)
+                .append_bytes(file_name)
+        };
+        let name1 = DumpString::new("libfoo.dylib", Endian::Little);
+        let cv_record1 = pdb70(b"libfoo.dylib\0");
+        let module1 = SynthModule::new(
+            Endian::Little,
+            0x100000000,
+            0x4000,
+            &name1,
+            0xb1054d2a,
+            0,
+            None,
+        )
+        .cv_record(&cv_record1);
+        let name2 = DumpString::new("c:\\foo.dll", Endian::Little);
+        let cv_record2 = pdb70(b"c:\\foo.PDB\0");
+        let module2 = SynthModule::new(
+            Endian::Little,
+            0x200000000,
+            0x4000,
+            &name2,
+            0xb1054d2a,
+            0,
+            None,
+        )
+        .cv_record(&cv_record2);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_module(module1)
+            .add_module(module2)
+            .add(name1)
+            .add(cv_record1)
+            .add(name2)
+            .add(cv_record2);
+        let dump = read_synth_dump(dump).unwrap();
+        let module_list = dump.get_stream::<MinidumpModuleList>().unwrap();
+        let modules = module_list.iter().collect::<Vec<_>>();
+
+        // Without a .pdb file, the signature is a Mach-O UUID.
+        assert_eq!(
+            modules[0].code_identifier_kind(),
+            Some(CodeIdentifierKind::MachOUuid)
+        );
+        assert_eq!(
+            modules[0].code_identifier().unwrap(),
+            CodeId::new("AABBCCDDEEFF00112233445566778899".to_owned())
+        );
+        assert_eq!(
+            modules[1].code_identifier_kind(),
+            Some(CodeIdentifierKind::PeTimestampAndSize)
+        );
+        assert_eq!(
+            modules[1].code_identifier().unwrap(),
+            CodeId::new("B1054D2A4000".to_owned())
+        );
+    }
+
     #[test]
     fn test_null_id() {
         // Add a module with an ELF build id of nothing but zeros
@@ -6604,6 +13288,173 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         assert_eq!(stack.size, 0x1000);
     }
 
+    #[test]
+    fn test_big_endian_dump() {
+        let context = minidump_synth::x86_context(Endian::Big, 0xabcd1234, 0x1010);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Big)
+                .D32(0x11223344)
+                .append_repeated(0, 0xffc),
+            0x1010,
+        );
+        let arch = md::ProcessorArchitecture::PROCESSOR_ARCHITECTURE_INTEL as u16;
+        let system_info = SystemInfo::new(Endian::Big).set_processor_architecture(arch);
+        let thread = Thread::new(Endian::Big, 0x1234, &stack, &context);
+        let name = DumpString::new("single module", Endian::Big);
+        let module = SynthModule::new(
+            Endian::Big,
+            0xa90206ca83eb2852,
+            0xada542bd,
+            &name,
+            0xb1054d2a,
+            0x34571371,
+            Some(&STOCK_VERSION_INFO),
+        );
+        let dump = SynthMinidump::with_endian(Endian::Big)
+            .add_thread(thread)
+            .add(context)
+            .add_memory(stack)
+            .add_module(module)
+            .add(name)
+            .add_system_info(system_info);
+        let dump = read_synth_dump(dump).unwrap();
+        assert_eq!(dump.endian, BE);
+
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        assert_eq!(system_info.cpu, Cpu::X86);
+
+        let module_list = dump.get_stream::<MinidumpModuleList>().unwrap();
+        let module = module_list.iter().next().unwrap();
+        assert_eq!(module.base_address(), 0xa90206ca83eb2852);
+        assert_eq!(module.size(), 0xada542bd);
+        assert_eq!(module.code_file(), "single module");
+
+        let thread_list = dump.get_stream::<MinidumpThreadList<'_>>().unwrap();
+        let thread = &thread_list.threads[0];
+        assert_eq!(thread.raw.thread_id, 0x1234);
+        let context = thread.context(&system_info, None).unwrap();
+        match &context.raw {
+            MinidumpRawContext::X86(raw) => {
+                assert_eq!(raw.eip, 0xabcd1234);
+                assert_eq!(raw.esp, 0x1010);
+            }
+            _ => panic!("Got unexpected raw context type!"),
+        }
+
+        let memory_list = dump.get_memory().unwrap();
+        let stack = memory_list.memory_at_address(0x1010).unwrap();
+        assert_eq!(stack.get_memory_at_address::<u32>(0x1010), Some(0x11223344));
+    }
+
+    #[test]
+    fn test_thread_memory_regions() {
+        let context =
+            minidump_synth::amd64_context(Endian::Little, 0x1234abcd1234abcd, 0x7000_1010);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x7000_1000,
+        );
+        // Adjacent to, but not part of, the stack.
+        let below_stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x7000_0000,
+        );
+        let teb = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x2000),
+            0x9000_0000,
+        );
+        let unrelated = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x100),
+            0x5000_0000,
+        );
+        let thread = Thread::with_teb(Endian::Little, 0x1234, 0x9000_0000, &stack, &context);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add(context)
+            .add_memory(unrelated)
+            .add_memory(teb)
+            .add_memory(below_stack)
+            .add_memory(stack);
+        let dump = read_synth_dump(dump).unwrap();
+        let thread_list = dump.get_stream::<MinidumpThreadList<'_>>().unwrap();
+        let memory_list = dump.get_memory().unwrap();
+
+        let regions = thread_list
+            .thread_memory_regions(0x1234, &memory_list)
+            .unwrap()
+            .iter()
+            .map(|region| (region.base_address(), region.size()))
+            .collect::<Vec<_>>();
+        assert_eq!(regions, vec![(0x7000_1000, 0x1000), (0x9000_0000, 0x2000)]);
+
+        assert!(thread_list
+            .thread_memory_regions(0x4321, &memory_list)
+            .is_none());
+    }
+
+    #[test]
+    fn test_critical_section_owner() {
+        let context =
+            minidump_synth::amd64_context(Endian::Little, 0x1234abcd1234abcd, 0x7000_1010);
+        let stack = Memory::with_section(
+            Section::with_endian(Endian::Little).append_repeated(0, 0x1000),
+            0x7000_1000,
+        );
+        let locks = Memory::with_section(
+            Section::with_endian(Endian::Little)
+                // A held critical section
+                .D64(0x5000_1000) // DebugInfo
+                .D32(0xfffffffa) // LockCount
+                .D32(2) // RecursionCount
+                .D64(0x1234) // OwningThread
+                .D64(0) // LockSemaphore
+                .D64(0) // SpinCount
+                // A free critical section
+                .D64(0x5000_1000)
+                .D32(0xffffffff)
+                .D32(0)
+                .D64(0)
+                .D64(0)
+                .D64(0),
+            0x5000_0000,
+        );
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add(context)
+            .add_memory(locks)
+            .add_memory(stack);
+        let dump = read_synth_dump(dump).unwrap();
+        let thread_list = dump.get_stream::<MinidumpThreadList<'_>>().unwrap();
+        let memory_list = dump.get_memory().unwrap();
+
+        assert_eq!(
+            CriticalSection::read(Cpu::X86_64, &memory_list, 0x5000_0000),
+            Some(CriticalSection {
+                address: 0x5000_0000,
+                lock_count: -6,
+                recursion_count: 2,
+                owning_thread: Some(0x1234),
+            })
+        );
+        let owner = thread_list
+            .critical_section_owner(Cpu::X86_64, &memory_list, 0x5000_0000)
+            .unwrap();
+        assert_eq!(owner.raw.thread_id, 0x1234);
+
+        let free = CriticalSection::read(Cpu::X86_64, &memory_list, 0x5000_0028).unwrap();
+        assert_eq!(free.owning_thread, None);
+        assert!(thread_list
+            .critical_section_owner(Cpu::X86_64, &memory_list, 0x5000_0028)
+            .is_none());
+
+        // Not in the dump at all
+        assert_eq!(
+            CriticalSection::read(Cpu::X86_64, &memory_list, 0x6000_0000),
+            None
+        );
+    }
+
     #[test]
     fn test_thread_list_amd64() {
         let context =
@@ -6713,6 +13564,209 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
             module.annotation_objects["invalid"],
             MinidumpAnnotation::Invalid
         );
+
+        let custom = &module.annotation_objects["custom"];
+        assert!(matches!(custom, MinidumpAnnotation::UserDefined(..)));
+        assert_eq!(custom.ty(), 0x8001);
+        assert_eq!(custom.value(), &[42]);
+        assert_eq!(
+            module.annotation_objects["string"].ty(),
+            md::MINIDUMP_ANNOTATION::TYPE_STRING
+        );
+    }
+
+    #[test]
+    fn test_crashpad_info_bad_annotation() {
+        let value = [0xde, 0xad, 0xbe, 0xef];
+        let module = ModuleCrashpadInfo::new(0, Endian::Little)
+            .add_annotation_object("bad", AnnotationValue::Custom(0x1234, value.to_vec()))
+            .add_annotation_object("string", AnnotationValue::String("value".to_owned()));
+        let crashpad_info = CrashpadInfo::new(Endian::Little).add_module(module);
+        let mut bytes = SynthMinidump::with_endian(Endian::Little)
+            .add_crashpad_info(crashpad_info)
+            .finish()
+            .unwrap();
+
+        // Make the first annotation's value claim to run past the end of the dump.
+        let byte_array = [&4u32.to_le_bytes()[..], &value].concat();
+        let offset = bytes
+            .windows(byte_array.len())
+            .position(|window| window == byte_array)
+            .unwrap();
+        bytes[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let dump = Minidump::read(bytes).unwrap();
+        let crashpad_info = dump.get_stream::<MinidumpCrashpadInfo>().unwrap();
+        let module = &crashpad_info.module_list[0];
+        assert!(!module.annotation_objects.contains_key("bad"));
+        assert_eq!(
+            module.annotation_objects["string"],
+            MinidumpAnnotation::String("value".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_mac_guard_and_resource_reasons() {
+        let mac_exception = |code: u32, flags: u32, info: [u64; 2]| {
+            let mut exception_information = [0; 15];
+            exception_information[1] = info[0];
+            exception_information[2] = info[1];
+            let raw = md::MINIDUMP_EXCEPTION_STREAM {
+                thread_id: 0,
+                __align: 0,
+                exception_record: md::MINIDUMP_EXCEPTION {
+                    exception_code: code,
+                    exception_flags: flags,
+                    exception_information,
+                    ..md::MINIDUMP_EXCEPTION::default()
+                },
+                thread_context: md::MINIDUMP_LOCATION_DESCRIPTOR::default(),
+            };
+            CrashReason::from_mac_exception(&raw, Cpu::Arm64).unwrap()
+        };
+
+        // A guarded fd 17 was closed.
+        let reason = mac_exception(
+            err::ExceptionCodeMac::EXC_GUARD as u32,
+            2 << 29,
+            [(2 << 61) | (1 << 32) | 17, 0xfeed],
+        );
+        assert_eq!(
+            reason.mac_guard_info(),
+            Some(err::ExceptionCodeMacGuardInfo::Fd {
+                flavor: err::ExceptionCodeMacGuardFDFlavor::GUARD_EXC_CLOSE,
+                fd: 17,
+                guard_identifier: 0xfeed,
+            })
+        );
+        assert_eq!(reason.mac_resource_info(), None);
+        assert_eq!(
+            reason.to_string(),
+            "EXC_GUARD / GUARD_TYPE_FD / GUARD_EXC_CLOSE on fd 17 guard identifier: 0xfeed"
+        );
+
+        // An unknown flavor falls back to the raw codes.
+        let reason = mac_exception(
+            err::ExceptionCodeMac::EXC_GUARD as u32,
+            2 << 29,
+            [(2 << 61) | (3 << 32) | 17, 0],
+        );
+        assert_eq!(reason.mac_guard_info(), None);
+        assert_eq!(
+            reason.to_string(),
+            "EXC_GUARD / GUARD_TYPE_FD / 0x4000000300000011 / 0x0000000000000000"
+        );
+
+        // Using 90% of the CPU over 3 minutes, when 50% is allowed.
+        let reason = mac_exception(
+            err::ExceptionCodeMac::EXC_RESOURCE as u32,
+            1 << 29,
+            [(1 << 61) | (1 << 58) | (180 << 7) | 50, 90],
+        );
+        assert_eq!(
+            reason.mac_resource_info(),
+            Some(err::ExceptionCodeMacResourceInfo::Cpu {
+                flavor: err::ExceptionCodeMacResourceCpuFlavor::FLAVOR_CPU_MONITOR,
+                interval_seconds: 180,
+                cpu_limit_percent: 50,
+                cpu_consumed_percent: 90,
+            })
+        );
+        assert_eq!(reason.mac_guard_info(), None);
+        assert_eq!(
+            reason.to_string(),
+            "EXC_RESOURCE / RESOURCE_TYPE_CPU / FLAVOR_CPU_MONITOR interval: 180s CPU limit: 50% CPU consumed: 90%"
+        );
+    }
+
+    #[test]
+    fn test_windows_exception_parameters() {
+        let windows_exception = |code: u32, info: &[u64]| {
+            let mut exception_information = [0; 15];
+            exception_information[..info.len()].copy_from_slice(info);
+            md::MINIDUMP_EXCEPTION_STREAM {
+                thread_id: 0,
+                __align: 0,
+                exception_record: md::MINIDUMP_EXCEPTION {
+                    exception_code: code,
+                    number_parameters: info.len() as u32,
+                    exception_information,
+                    ..md::MINIDUMP_EXCEPTION::default()
+                },
+                thread_context: md::MINIDUMP_LOCATION_DESCRIPTOR::default(),
+            }
+        };
+        let decode = |raw: &md::MINIDUMP_EXCEPTION_STREAM| {
+            let params = WindowsExceptionParameters::from_record(&raw.exception_record);
+            let reason = CrashReason::from_windows_exception(raw, Cpu::X86_64).unwrap();
+            (params, reason.to_string())
+        };
+
+        let av = err::ExceptionCodeWindows::EXCEPTION_ACCESS_VIOLATION as u32;
+        assert_eq!(
+            decode(&windows_exception(av, &[1, 0x1234])),
+            (
+                Some(WindowsExceptionParameters::AccessViolation {
+                    access: Some(err::ExceptionCodeWindowsAccessType::WRITE),
+                    address: Some(0x1234),
+                }),
+                String::from("EXCEPTION_ACCESS_VIOLATION_WRITE"),
+            )
+        );
+        assert_eq!(
+            decode(&windows_exception(av, &[5])),
+            (
+                Some(WindowsExceptionParameters::AccessViolation {
+                    access: None,
+                    address: None,
+                }),
+                String::from("EXCEPTION_ACCESS_VIOLATION"),
+            )
+        );
+        assert_eq!(
+            decode(&windows_exception(av, &[])),
+            (None, String::from("EXCEPTION_ACCESS_VIOLATION"))
+        );
+
+        let in_page = windows_exception(
+            err::ExceptionCodeWindows::EXCEPTION_IN_PAGE_ERROR as u32,
+            &[8, 0x5678, 0xc000009c],
+        );
+        let (params, reason) = decode(&in_page);
+        assert_eq!(
+            params,
+            Some(WindowsExceptionParameters::InPageError {
+                access: Some(err::ExceptionCodeWindowsInPageErrorType::EXEC),
+                address: 0x5678,
+                nt_status: 0xc000009c,
+            })
+        );
+        assert_eq!(
+            params.unwrap().nt_status(),
+            Some(err::NtStatusWindows::STATUS_DEVICE_DATA_ERROR)
+        );
+        assert_eq!(
+            reason,
+            "EXCEPTION_IN_PAGE_ERROR_EXEC / STATUS_DEVICE_DATA_ERROR"
+        );
+
+        let fast_fail = windows_exception(
+            err::NtStatusWindows::STATUS_STACK_BUFFER_OVERRUN as u32,
+            &[2],
+        );
+        let (params, reason) = decode(&fast_fail);
+        assert_eq!(
+            params,
+            Some(WindowsExceptionParameters::FastFail { code: 2 })
+        );
+        assert_eq!(
+            params.unwrap().fast_fail_code(),
+            Some(err::FastFailCode::FAST_FAIL_STACK_COOKIE_CHECK_FAILURE)
+        );
+        assert_eq!(
+            reason,
+            "EXCEPTION_STACK_BUFFER_OVERRUN / FAST_FAIL_STACK_COOKIE_CHECK_FAILURE"
+        );
     }
 
     #[test]
@@ -6766,6 +13820,134 @@ c70206ca83eb2852-de0206ca83eb2852  -w-s  10bac9000 fd:05 1196511 /usr/lib64/libt
         );
     }
 
+    #[test]
+    fn test_exception_parameters() {
+        let parameters = |number_parameters: u32| {
+            let mut exception = Exception::new(Endian::Little);
+            exception.exception_record.number_parameters = number_parameters;
+            exception.exception_record.exception_information[0] = 0x1;
+            exception.exception_record.exception_information[1] = 0x8013_1500;
+            let dump = SynthMinidump::with_endian(Endian::Little).add_exception(exception);
+            let dump = read_synth_dump(dump).unwrap();
+            let exception = dump.get_stream::<MinidumpException>().unwrap();
+            exception.parameters().to_vec()
+        };
+        assert_eq!(parameters(0), Vec::<u64>::new());
+        assert_eq!(parameters(2), vec![0x1, 0x8013_1500]);
+        // A corrupt count doesn't read past the end of the parameters.
+        assert_eq!(parameters(0xffff).len(), 15);
+    }
+
+    #[test]
+    fn test_nested_exceptions() {
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(ProcessorArchitecture::PROCESSOR_ARCHITECTURE_AMD64 as u16)
+            .set_platform_id(PlatformId::VER_PLATFORM_WIN32_NT as u32);
+
+        let mut exception = Exception::new(Endian::Little);
+        exception.exception_record.exception_code = 0xc0000025; // EXCEPTION_NONCONTINUABLE_EXCEPTION
+        exception.exception_record.exception_record = 0x1000;
+
+        // An access violation, followed by a record pointing back at it.
+        let records = Section::with_endian(Endian::Little)
+            .D32(0xc0000005) // exception_code
+            .D32(0) // exception_flags
+            .D64(0x1100) // exception_record
+            .D64(0xabcd) // exception_address
+            .D32(2) // number_parameters
+            .D32(0) // __align
+            .D64(1)
+            .D64(0x44)
+            .append_repeated(0, 13 * 8)
+            .append_repeated(0, 0x100 - 152)
+            .D32(0xe06d7363)
+            .D32(1)
+            .D64(0x1000)
+            .D64(0xef01)
+            .D32(0)
+            .D32(0)
+            .append_repeated(0, 15 * 8);
+        let memory = Memory::with_section(records, 0x1000);
+
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(system_info)
+            .add_exception(exception)
+            .add_memory(memory);
+        let dump = read_synth_dump(dump).unwrap();
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        let exception = dump.get_stream::<MinidumpException>().unwrap();
+        let memory_list = dump.get_memory().unwrap_or_default();
+
+        let nested = exception.nested_exceptions(&memory_list, system_info.cpu);
+        assert_eq!(nested.len(), 2);
+        assert_eq!(nested[0].exception_code, 0xc0000005);
+        assert_eq!(nested[1].exception_code, 0xe06d7363);
+        assert_eq!(nested[1].exception_flags, 1);
+        assert_eq!(
+            exception.get_nested_crash_address(&nested[0], system_info.os, system_info.cpu),
+            0x44
+        );
+        assert_eq!(
+            exception.get_nested_crash_address(&nested[1], system_info.os, system_info.cpu),
+            0xef01
+        );
+        assert_eq!(
+            exception
+                .get_nested_crash_reason(&nested[0], system_info.os, system_info.cpu)
+                .to_string(),
+            "EXCEPTION_ACCESS_VIOLATION_WRITE"
+        );
+        assert_eq!(
+            exception
+                .get_crash_reason(system_info.os, system_info.cpu)
+                .to_string(),
+            "EXCEPTION_NONCONTINUABLE_EXCEPTION"
+        );
+    }
+
+    #[test]
+    fn test_nested_exceptions_x86() {
+        let system_info = SystemInfo::new(Endian::Little);
+
+        let mut exception = Exception::new(Endian::Little);
+        // Sign-extended, like the crash address can be
+        exception.exception_record.exception_record = 0xffff_ffff_8000_2000;
+
+        let mut record = Section::with_endian(Endian::Little)
+            .D32(0xc0000005) // exception_code
+            .D32(0) // exception_flags
+            .D32(0x3000) // exception_record (not in the dump)
+            .D32(0xabcd) // exception_address
+            .D32(2) // number_parameters
+            .D32(0)
+            .D32(0x44);
+        for _ in 2..15 {
+            record = record.D32(0);
+        }
+        let memory = Memory::with_section(record, 0x8000_2000);
+
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_system_info(system_info)
+            .add_exception(exception)
+            .add_memory(memory);
+        let dump = read_synth_dump(dump).unwrap();
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        let exception = dump.get_stream::<MinidumpException>().unwrap();
+        let memory_list = dump.get_memory().unwrap_or_default();
+
+        let nested = exception.nested_exceptions(&memory_list, system_info.cpu);
+        assert_eq!(nested.len(), 1);
+        assert_eq!(nested[0].exception_code, 0xc0000005);
+        assert_eq!(nested[0].exception_record, 0x3000);
+        assert_eq!(nested[0].exception_address, 0xabcd);
+        assert_eq!(nested[0].number_parameters, 2);
+        assert_eq!(nested[0].exception_information[1], 0x44);
+
+        // Without the memory, there's nothing to follow.
+        let nested = exception.nested_exceptions(&UnifiedMemoryList::default(), system_info.cpu);
+        assert!(nested.is_empty());
+    }
+
     #[test]
     fn test_fuzzed_oom() {
         // https://github.com/rust-minidump/rust-minidump/issues/381
@@ -0,0 +1,799 @@
+//! Rewriting minidumps with sensitive data removed.
+//!
+//! A [`MinidumpRedactor`] takes a parsed [`Minidump`] and produces the bytes
+//! of a new minidump with some streams dropped and some memory zeroed or
+//! removed, so the dump can be shared without the user data in it.
+//!
+//! The output keeps the layout of the original file: removed data is
+//! overwritten with zeroes where it was, and lists that lose entries are
+//! compacted within the space they already had. That way every RVA the
+//! redaction doesn't touch stays valid, and the output is as well-formed as
+//! the input was for any other minidump reader.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::mem;
+use std::ops::{Deref, Range};
+
+use num_traits::FromPrimitive;
+use scroll::ctx::SizeWith;
+use scroll::{Pread, Pwrite};
+
+use crate::minidump::read_stream_list;
+use crate::{Error, Minidump};
+use minidump_common::format::{self as md, MINIDUMP_STREAM_TYPE};
+
+/// What a [`MinidumpRedactor`] should do with a region of memory.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MemoryAction {
+    /// Keep the region's contents (minus any ranges that are zeroed).
+    Keep,
+    /// Keep the region, but overwrite all of its contents with zeroes.
+    Zero,
+    /// Remove the region from the memory lists, and zero its contents.
+    Remove,
+}
+
+type MemoryFilter<'r> = Box<dyn Fn(Range<u64>) -> MemoryAction + 'r>;
+
+/// Rewrites a minidump with some of its contents scrubbed.
+///
+/// Configure what to remove with the builder methods, then call
+/// [`MinidumpRedactor::redact`][]. A redactor with nothing configured
+/// reproduces its input exactly.
+///
+/// Memory regions are found in the memory list, the memory64 list, and
+/// the stacks of the thread list. Thread stacks whose memory is removed are
+/// left with an empty memory descriptor.
+///
+/// ```rust,no_run
+/// use minidump::{format::MINIDUMP_STREAM_TYPE, Minidump, MinidumpRedactor, MemoryAction};
+///
+/// # fn foo() -> Result<(), minidump::Error> {
+/// let dump = Minidump::read_path("../testdata/test.dmp")?;
+/// let redacted = MinidumpRedactor::new()
+///     .drop_stream(MINIDUMP_STREAM_TYPE::CommentStreamA as u32)
+///     .memory_filter(|range| {
+///         if range.end - range.start > 0x10000 {
+///             MemoryAction::Remove
+///         } else {
+///             MemoryAction::Keep
+///         }
+///     })
+///     .scrub_linux_environ()
+///     .redact(&dump)?;
+/// std::fs::write("redacted.dmp", redacted).unwrap();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct MinidumpRedactor<'r> {
+    dropped_streams: HashSet<u32>,
+    zeroed_ranges: Vec<Range<u64>>,
+    removed_ranges: Vec<Range<u64>>,
+    memory_filter: Option<MemoryFilter<'r>>,
+    max_memory64_regions: Option<usize>,
+    scrub_environ: bool,
+    scrub_cmd_line: bool,
+}
+
+impl<'r> fmt::Debug for MinidumpRedactor<'r> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MinidumpRedactor")
+            .field("dropped_streams", &self.dropped_streams)
+            .field("zeroed_ranges", &self.zeroed_ranges)
+            .field("removed_ranges", &self.removed_ranges)
+            .field("memory_filter", &self.memory_filter.is_some())
+            .field("max_memory64_regions", &self.max_memory64_regions)
+            .field("scrub_environ", &self.scrub_environ)
+            .field("scrub_cmd_line", &self.scrub_cmd_line)
+            .finish()
+    }
+}
+
+/// Where the contents of memory regions moved to, by their old RVA.
+///
+/// Each entry is the old RVA range of a region and its new RVA, or `None`
+/// if the region was removed.
+#[derive(Default)]
+struct RvaMap(Vec<(Range<u64>, Option<u64>)>);
+
+impl RvaMap {
+    /// The new location of the data at `location`, or `None` if it was removed.
+    fn remap(
+        &self,
+        location: md::MINIDUMP_LOCATION_DESCRIPTOR,
+    ) -> Option<md::MINIDUMP_LOCATION_DESCRIPTOR> {
+        let rva = location.rva as u64;
+        match self.0.iter().find(|(old, _)| old.contains(&rva)) {
+            None => Some(location),
+            Some((_, None)) => None,
+            Some((old, Some(new))) => Some(md::MINIDUMP_LOCATION_DESCRIPTOR {
+                rva: (new + (rva - old.start)) as u32,
+                ..location
+            }),
+        }
+    }
+}
+
+fn overlaps(a: &Range<u64>, b: &Range<u64>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// Overwrite `len` bytes at `offset` in `out` with zeroes, as far as `out` goes.
+fn zero(out: &mut [u8], offset: u64, len: u64) {
+    let file_len = out.len() as u64;
+    let start = offset.min(file_len) as usize;
+    let end = offset.saturating_add(len).min(file_len) as usize;
+    out[start..end].fill(0);
+}
+
+fn location_range(location: &md::MINIDUMP_LOCATION_DESCRIPTOR) -> Range<usize> {
+    let start = location.rva as usize;
+    start..start.saturating_add(location.data_size as usize)
+}
+
+/// Replace the contents of the stream at `location` with `contents`,
+/// zeroing whatever is left of its old contents.
+///
+/// Streams can only shrink: `contents` must be no longer than the old ones.
+fn rewrite_stream(
+    out: &mut [u8],
+    location: &mut md::MINIDUMP_LOCATION_DESCRIPTOR,
+    contents: &[u8],
+) {
+    let old = &mut out[location_range(location)];
+    assert!(
+        contents.len() <= old.len(),
+        "rewritten stream grew from {} to {} bytes",
+        old.len(),
+        contents.len()
+    );
+    let (new, rest) = old.split_at_mut(contents.len());
+    new.copy_from_slice(contents);
+    rest.fill(0);
+    location.data_size = contents.len() as u32;
+}
+
+impl<'r> MinidumpRedactor<'r> {
+    /// A redactor that changes nothing yet.
+    pub fn new() -> MinidumpRedactor<'r> {
+        MinidumpRedactor::default()
+    }
+
+    /// Drop every stream of type `stream_type`.
+    ///
+    /// The stream's own bytes are zeroed, but data it refers to elsewhere
+    /// in the file (like the names of a module list) is left alone, except
+    /// for the memory of a dropped memory list or memory64 list, which is
+    /// removed as well.
+    pub fn drop_stream(mut self, stream_type: u32) -> Self {
+        self.dropped_streams.insert(stream_type);
+        self
+    }
+
+    /// Zero the contents of any memory in the address range `range`.
+    pub fn zero_memory(mut self, range: Range<u64>) -> Self {
+        self.zeroed_ranges.push(range);
+        self
+    }
+
+    /// Remove every memory region that overlaps the address range `range`.
+    pub fn remove_memory(mut self, range: Range<u64>) -> Self {
+        self.removed_ranges.push(range);
+        self
+    }
+
+    /// Decide what to do with each memory region by its address range.
+    ///
+    /// Regions removed by [`MinidumpRedactor::remove_memory`][] are removed
+    /// without consulting `filter`.
+    pub fn memory_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(Range<u64>) -> MemoryAction + 'r,
+    {
+        self.memory_filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Keep only the first `max_regions` regions of the memory64 list.
+    ///
+    /// If the memory64 list's data is at the end of the file (as it usually
+    /// is), the output is shortened as well.
+    pub fn truncate_memory64(mut self, max_regions: usize) -> Self {
+        self.max_memory64_regions = Some(max_regions);
+        self
+    }
+
+    /// Remove the values of every variable in the Linux environ stream,
+    /// keeping only their names.
+    ///
+    /// The values are overwritten with NULs, so the stream keeps its size.
+    pub fn scrub_linux_environ(mut self) -> Self {
+        self.scrub_environ = true;
+        self
+    }
+
+    /// Remove the arguments from the Linux command line stream, keeping only
+    /// the program name.
+    ///
+    /// The arguments are overwritten with NULs, so the stream keeps its size.
+    pub fn scrub_linux_cmd_line(mut self) -> Self {
+        self.scrub_cmd_line = true;
+        self
+    }
+
+    /// Produce the bytes of a copy of `dump` with the configured redactions applied.
+    ///
+    /// Fails if a stream that needs to be rewritten can't be parsed.
+    pub fn redact<'a, T>(&self, dump: &Minidump<'a, T>) -> Result<Vec<u8>, Error>
+    where
+        T: Deref<Target = [u8]> + 'a,
+    {
+        let endian = dump.endian;
+        let mut out = dump.data.to_vec();
+        let mut directory = dump.directory.clone();
+        let mut rva_map = RvaMap::default();
+        let mut new_len = None;
+
+        // The memory64 list's contents may move, so it goes first, as the
+        // other lists of memory may point into it. Thread stacks go after
+        // both lists, so they know which of their memory was removed.
+        for dir in &mut directory {
+            let dropped = self.dropped_streams.contains(&dir.stream_type);
+            if dir.stream_type == MINIDUMP_STREAM_TYPE::Memory64ListStream as u32 {
+                let truncated = self.redact_memory64(
+                    &mut out,
+                    &mut dir.location,
+                    endian,
+                    dropped,
+                    &mut rva_map,
+                )?;
+                new_len = new_len.or(truncated);
+            }
+        }
+        for dir in &mut directory {
+            let dropped = self.dropped_streams.contains(&dir.stream_type);
+            if dir.stream_type == MINIDUMP_STREAM_TYPE::MemoryListStream as u32 {
+                self.redact_memory_list(
+                    &mut out,
+                    &mut dir.location,
+                    endian,
+                    dropped,
+                    &mut rva_map,
+                )?;
+            }
+        }
+        for dir in &mut directory {
+            let dropped = self.dropped_streams.contains(&dir.stream_type);
+            match MINIDUMP_STREAM_TYPE::from_u32(dir.stream_type) {
+                Some(MINIDUMP_STREAM_TYPE::ThreadListStream) if !dropped => {
+                    self.redact_thread_list(&mut out, &dir.location, endian, &rva_map)?
+                }
+                Some(MINIDUMP_STREAM_TYPE::LinuxEnviron) if self.scrub_environ && !dropped => {
+                    let bytes = out
+                        .get(location_range(&dir.location))
+                        .ok_or(Error::StreamReadFailure)?;
+                    let scrubbed = scrub_environ(bytes);
+                    rewrite_stream(&mut out, &mut dir.location, &scrubbed);
+                }
+                Some(MINIDUMP_STREAM_TYPE::LinuxCmdLine) if self.scrub_cmd_line && !dropped => {
+                    let bytes = out
+                        .get(location_range(&dir.location))
+                        .ok_or(Error::StreamReadFailure)?;
+                    let scrubbed = scrub_cmd_line(bytes);
+                    rewrite_stream(&mut out, &mut dir.location, &scrubbed);
+                }
+                _ => {}
+            }
+            if dropped {
+                zero(
+                    &mut out,
+                    dir.location.rva as u64,
+                    dir.location.data_size as u64,
+                );
+            }
+        }
+
+        // Rewrite the directory in place, without the dropped streams.
+        let old_count = directory.len();
+        directory.retain(|dir| !self.dropped_streams.contains(&dir.stream_type));
+        let entry_size = md::MINIDUMP_DIRECTORY::size_with(&endian);
+        let mut offset = dump.header.stream_directory_rva as usize;
+        for dir in directory.iter().cloned() {
+            out.gwrite_with(dir, &mut offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+        }
+        zero(
+            &mut out,
+            offset as u64,
+            ((old_count - directory.len()) * entry_size) as u64,
+        );
+        let header = md::MINIDUMP_HEADER {
+            stream_count: directory.len() as u32,
+            ..dump.header.clone()
+        };
+        out.pwrite_with(header, 0, endian)
+            .or(Err(Error::MissingHeader))?;
+
+        if let Some(len) = new_len {
+            out.truncate(len);
+        }
+        Ok(out)
+    }
+
+    /// What to do with the memory region at `range`.
+    fn memory_action(&self, range: &Range<u64>) -> MemoryAction {
+        if self.removed_ranges.iter().any(|r| overlaps(r, range)) {
+            return MemoryAction::Remove;
+        }
+        match &self.memory_filter {
+            Some(filter) => filter(range.clone()),
+            None => MemoryAction::Keep,
+        }
+    }
+
+    /// Zero whatever `action` and the zeroed ranges say to of the memory
+    /// region at `range`, whose contents are at `rva`.
+    fn zero_region(&self, out: &mut [u8], rva: u64, range: &Range<u64>, action: MemoryAction) {
+        if action != MemoryAction::Keep {
+            zero(out, rva, range.end - range.start);
+            return;
+        }
+        for zeroed in self.zeroed_ranges.iter().filter(|r| overlaps(r, range)) {
+            let start = zeroed.start.max(range.start);
+            let end = zeroed.end.min(range.end);
+            zero(out, rva + (start - range.start), end - start);
+        }
+    }
+
+    /// Redact the memory64 list at `location`, compacting the contents of
+    /// the regions that are kept.
+    ///
+    /// Returns the new length of the file, if the list's contents were at
+    /// the end of it.
+    fn redact_memory64(
+        &self,
+        out: &mut [u8],
+        location: &mut md::MINIDUMP_LOCATION_DESCRIPTOR,
+        endian: scroll::Endian,
+        remove_all: bool,
+        rva_map: &mut RvaMap,
+    ) -> Result<Option<usize>, Error> {
+        let bytes = out
+            .get(location_range(location))
+            .ok_or(Error::StreamReadFailure)?;
+        let mut offset = 0;
+        let count: u64 = bytes
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let base_rva: u64 = bytes
+            .gread_with(&mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let mut descriptors = Vec::new();
+        for _ in 0..count {
+            let raw: md::MINIDUMP_MEMORY_DESCRIPTOR64 = bytes
+                .gread_with(&mut offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+            descriptors.push(raw);
+        }
+
+        let mut old_rva = base_rva;
+        let mut new_rva = base_rva;
+        let mut kept = Vec::with_capacity(descriptors.len());
+        for (index, raw) in descriptors.into_iter().enumerate() {
+            let old = old_rva
+                ..old_rva
+                    .checked_add(raw.data_size)
+                    .ok_or(Error::StreamReadFailure)?;
+            if old.end > out.len() as u64 {
+                return Err(Error::StreamReadFailure);
+            }
+            old_rva = old.end;
+
+            let range =
+                raw.start_of_memory_range..raw.start_of_memory_range.saturating_add(raw.data_size);
+            let truncated = matches!(self.max_memory64_regions, Some(max) if index >= max);
+            let action = if remove_all || truncated {
+                MemoryAction::Remove
+            } else {
+                self.memory_action(&range)
+            };
+            if action == MemoryAction::Remove {
+                rva_map.0.push((old, None));
+                continue;
+            }
+
+            if new_rva != old.start {
+                out.copy_within(old.start as usize..old.end as usize, new_rva as usize);
+            }
+            rva_map.0.push((old, Some(new_rva)));
+            self.zero_region(out, new_rva, &range, action);
+            new_rva += raw.data_size;
+            kept.push(raw);
+        }
+        // Everything between the end of the kept contents and the old end was removed.
+        zero(out, new_rva, old_rva - new_rva);
+
+        let descriptor_size = md::MINIDUMP_MEMORY_DESCRIPTOR64::size_with(&endian);
+        let mut contents = vec![0; 2 * mem::size_of::<u64>() + kept.len() * descriptor_size];
+        let mut offset = 0;
+        contents
+            .gwrite_with(kept.len() as u64, &mut offset, endian)
+            .and_then(|_| contents.gwrite_with(base_rva, &mut offset, endian))
+            .or(Err(Error::StreamReadFailure))?;
+        for raw in kept {
+            contents
+                .gwrite_with(raw, &mut offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+        }
+        rewrite_stream(out, location, &contents);
+
+        if old_rva == out.len() as u64 {
+            Ok(Some(new_rva as usize))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Redact the memory list at `location`, recording removed regions in `rva_map`.
+    fn redact_memory_list(
+        &self,
+        out: &mut [u8],
+        location: &mut md::MINIDUMP_LOCATION_DESCRIPTOR,
+        endian: scroll::Endian,
+        remove_all: bool,
+        rva_map: &mut RvaMap,
+    ) -> Result<(), Error> {
+        let bytes = out
+            .get(location_range(location))
+            .ok_or(Error::StreamReadFailure)?;
+        let mut offset = 0;
+        let descriptors: Vec<md::MINIDUMP_MEMORY_DESCRIPTOR> =
+            read_stream_list(&mut offset, bytes, endian)?;
+        // Keep any padding between the count and the entries.
+        let header_size =
+            offset - descriptors.len() * md::MINIDUMP_MEMORY_DESCRIPTOR::size_with(&endian);
+        let mut contents = bytes[..header_size].to_vec();
+
+        let mut removed = Vec::new();
+        let mut kept = Vec::with_capacity(descriptors.len());
+        for mut raw in descriptors {
+            let range = raw.start_of_memory_range
+                ..raw
+                    .start_of_memory_range
+                    .saturating_add(raw.memory.data_size as u64);
+            // Memory that was removed from the memory64 list is already zeroed.
+            let memory = match rva_map.remap(raw.memory) {
+                Some(memory) => memory,
+                None => continue,
+            };
+            let action = if remove_all {
+                MemoryAction::Remove
+            } else {
+                self.memory_action(&range)
+            };
+            if action == MemoryAction::Remove {
+                zero(out, memory.rva as u64, memory.data_size as u64);
+                let rva = memory.rva as u64;
+                removed.push((rva..rva + memory.data_size as u64, None));
+                continue;
+            }
+            self.zero_region(out, memory.rva as u64, &range, action);
+            raw.memory = memory;
+            kept.push(raw);
+        }
+        rva_map.0.extend(removed);
+
+        let descriptor_size = md::MINIDUMP_MEMORY_DESCRIPTOR::size_with(&endian);
+        contents.resize(header_size + kept.len() * descriptor_size, 0);
+        let mut offset = 0;
+        contents
+            .gwrite_with(kept.len() as u32, &mut offset, endian)
+            .or(Err(Error::StreamReadFailure))?;
+        let mut offset = header_size;
+        for raw in kept {
+            contents
+                .gwrite_with(raw, &mut offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+        }
+        rewrite_stream(out, location, &contents);
+        Ok(())
+    }
+
+    /// Redact the stacks of the thread list at `location`.
+    fn redact_thread_list(
+        &self,
+        out: &mut [u8],
+        location: &md::MINIDUMP_LOCATION_DESCRIPTOR,
+        endian: scroll::Endian,
+        rva_map: &RvaMap,
+    ) -> Result<(), Error> {
+        let bytes = out
+            .get(location_range(location))
+            .ok_or(Error::StreamReadFailure)?;
+        let mut offset = 0;
+        let threads: Vec<md::MINIDUMP_THREAD> = read_stream_list(&mut offset, bytes, endian)?;
+        let mut offset = location.rva as usize + offset
+            - threads.len() * md::MINIDUMP_THREAD::size_with(&endian);
+
+        for mut thread in threads {
+            let stack = &mut thread.stack;
+            let range = stack.start_of_memory_range
+                ..stack
+                    .start_of_memory_range
+                    .saturating_add(stack.memory.data_size as u64);
+            // Memory that was removed from a memory list is already zeroed.
+            match rva_map.remap(stack.memory) {
+                None => stack.memory = md::MINIDUMP_LOCATION_DESCRIPTOR::default(),
+                Some(memory) => {
+                    stack.memory = memory;
+                    let rva = memory.rva as u64;
+                    let action = self.memory_action(&range);
+                    if action == MemoryAction::Remove {
+                        zero(out, rva, memory.data_size as u64);
+                        stack.memory = md::MINIDUMP_LOCATION_DESCRIPTOR::default();
+                    } else {
+                        self.zero_region(out, rva, &range, action);
+                    }
+                }
+            }
+            out.gwrite_with(thread, &mut offset, endian)
+                .or(Err(Error::StreamReadFailure))?;
+        }
+        Ok(())
+    }
+}
+
+/// A Linux environ stream with the value of each `NAME=value` entry zeroed.
+///
+/// Entries without an `=` are only a name, and are kept as they are.
+fn scrub_environ(bytes: &[u8]) -> Vec<u8> {
+    let mut scrubbed = bytes.to_vec();
+    for entry in scrubbed.split_mut(|&b| b == 0) {
+        if let Some(equals) = entry.iter().position(|&b| b == b'=') {
+            entry[equals + 1..].fill(0);
+        }
+    }
+    scrubbed
+}
+
+/// A Linux command line stream with everything after the program name zeroed.
+fn scrub_cmd_line(bytes: &[u8]) -> Vec<u8> {
+    let mut scrubbed = bytes.to_vec();
+    if let Some(end) = scrubbed.iter().position(|&b| b == 0) {
+        scrubbed[end..].fill(0);
+    }
+    scrubbed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        MinidumpLinuxEnviron, MinidumpMemory64List, MinidumpMemoryList, MinidumpStream,
+        MinidumpThreadList, UnifiedMemoryList,
+    };
+    use minidump_synth::{x86_context, Memory, SimpleStream, SynthMinidump, Thread};
+    use test_assembler::*;
+
+    const CUSTOM_STREAM: u32 = 0x11223344;
+    const SECRET: &[u8] = b"hunter2hunter2";
+
+    fn memory(address: u64, contents: &[u8]) -> Memory {
+        Memory::with_section(
+            Section::with_endian(Endian::Little).append_bytes(contents),
+            address,
+        )
+    }
+
+    fn synth_dump() -> Vec<u8> {
+        let context = x86_context(Endian::Little, 0xabcd1234, 0x1010);
+        let stack = memory(0x1000, &[0x55; 0x100]);
+        let thread = Thread::new(Endian::Little, 0x1234, &stack, &context);
+        SynthMinidump::with_endian(Endian::Little)
+            .add_thread(thread)
+            .add(context)
+            .add_memory(stack)
+            .add_memory(memory(0x8000, SECRET))
+            .add_stream(SimpleStream {
+                stream_type: CUSTOM_STREAM,
+                section: Section::new().append_bytes(SECRET),
+            })
+            .add_stream(SimpleStream {
+                stream_type: MINIDUMP_STREAM_TYPE::LinuxCmdLine as u32,
+                section: Section::new().append_bytes(b"/bin/prog\0--password\0hunter2\0"),
+            })
+            .set_linux_environ(b"HOME=/home/me\0TOKEN=hunter2\0SHELL\0")
+            .finish()
+            .unwrap()
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn test_redact_nothing() {
+        let bytes = synth_dump();
+        let dump = Minidump::read(&bytes[..]).unwrap();
+        let redacted = MinidumpRedactor::new().redact(&dump).unwrap();
+        assert_eq!(redacted, bytes);
+
+        let redacted = Minidump::read(&redacted[..]).unwrap();
+        assert_eq!(redacted.directory.len(), dump.directory.len());
+        for (old, new) in dump.directory.iter().zip(&redacted.directory) {
+            assert_eq!(old.stream_type, new.stream_type);
+            assert_eq!(
+                dump.get_raw_stream(old.stream_type).unwrap(),
+                redacted.get_raw_stream(new.stream_type).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_redact_streams() {
+        let no_memory = UnifiedMemoryList::default();
+        let bytes = synth_dump();
+        let dump = Minidump::read(&bytes[..]).unwrap();
+        let redacted = MinidumpRedactor::new()
+            .drop_stream(CUSTOM_STREAM)
+            .remove_memory(0x8000..0x8001)
+            .scrub_linux_environ()
+            .scrub_linux_cmd_line()
+            .redact(&dump)
+            .unwrap();
+        assert!(!contains(&redacted, SECRET));
+        assert!(!contains(&redacted, b"hunter2"));
+        assert_eq!(redacted.len(), bytes.len());
+
+        let redacted = Minidump::read(&redacted[..]).unwrap();
+        assert_eq!(redacted.directory.len(), dump.directory.len() - 1);
+        assert_eq!(
+            redacted.get_raw_stream(CUSTOM_STREAM),
+            Err(Error::StreamNotFound)
+        );
+        assert_eq!(
+            redacted
+                .get_raw_stream(MINIDUMP_STREAM_TYPE::LinuxCmdLine as u32)
+                .unwrap(),
+            [&b"/bin/prog"[..], &[0; 20]].concat()
+        );
+        let environ = redacted.get_stream::<MinidumpLinuxEnviron>().unwrap();
+        assert_eq!(
+            &environ.raw_bytes()[..],
+            b"HOME=\0\0\0\0\0\0\0\0\0TOKEN=\0\0\0\0\0\0\0\0SHELL\0"
+        );
+        let names = environ
+            .iter()
+            .map(|(name, _)| name.as_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(names, [&b"HOME"[..], b"TOKEN", b"SHELL"]);
+
+        let memory = redacted.get_stream::<MinidumpMemoryList>().unwrap();
+        assert_eq!(memory.iter().count(), 1);
+        assert!(memory.memory_at_address(0x8000).is_none());
+        let threads = redacted.get_stream::<MinidumpThreadList>().unwrap();
+        assert_eq!(
+            threads.threads[0].stack_memory(&no_memory).unwrap().bytes(),
+            &[0x55; 0x100]
+        );
+    }
+
+    #[test]
+    fn test_scrub_environ_without_values() {
+        // Entries without an `=` would grow if one were added to them.
+        for environ in [
+            &b"SHELL"[..],
+            b"SHELL\0",
+            b"SHELL\0\0TERM\0",
+            b"TOKEN=hunter2",
+        ] {
+            let bytes = SynthMinidump::with_endian(Endian::Little)
+                .set_linux_environ(environ)
+                .finish()
+                .unwrap();
+            let dump = Minidump::read(&bytes[..]).unwrap();
+            let redacted = MinidumpRedactor::new()
+                .scrub_linux_environ()
+                .redact(&dump)
+                .unwrap();
+            assert_eq!(redacted.len(), bytes.len());
+            assert!(!contains(&redacted, b"hunter2"));
+
+            let redacted = Minidump::read(&redacted[..]).unwrap();
+            let raw = redacted
+                .get_raw_stream(MINIDUMP_STREAM_TYPE::LinuxEnviron as u32)
+                .unwrap();
+            assert_eq!(raw.len(), environ.len());
+        }
+    }
+
+    #[test]
+    fn test_redact_stacks() {
+        let no_memory = UnifiedMemoryList::default();
+        let bytes = synth_dump();
+        let dump = Minidump::read(&bytes[..]).unwrap();
+
+        // Zeroing part of the stack zeroes it for the thread and the memory list.
+        let redacted = MinidumpRedactor::new()
+            .zero_memory(0x1080..0x2000)
+            .redact(&dump)
+            .unwrap();
+        let redacted = Minidump::read(&redacted[..]).unwrap();
+        let threads = redacted.get_stream::<MinidumpThreadList>().unwrap();
+        let stack = threads.threads[0].stack_memory(&no_memory).unwrap();
+        assert_eq!(&stack.bytes()[..0x80], &[0x55; 0x80]);
+        assert_eq!(&stack.bytes()[0x80..], &[0; 0x80]);
+        let memory = redacted.get_stream::<MinidumpMemoryList>().unwrap();
+        assert_eq!(
+            memory.memory_at_address(0x1000).unwrap().bytes,
+            stack.bytes()
+        );
+
+        // Removing it leaves the thread without a stack.
+        let redacted = MinidumpRedactor::new()
+            .memory_filter(|range| match range.start {
+                0x1000 => MemoryAction::Remove,
+                _ => MemoryAction::Zero,
+            })
+            .redact(&dump)
+            .unwrap();
+        let redacted = Minidump::read(&redacted[..]).unwrap();
+        let threads = redacted.get_stream::<MinidumpThreadList>().unwrap();
+        assert_eq!(threads.threads.len(), 1);
+        assert!(threads.threads[0].stack_memory(&no_memory).is_none());
+        let memory = redacted.get_stream::<MinidumpMemoryList>().unwrap();
+        let regions = memory.iter().collect::<Vec<_>>();
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].base_address, 0x8000);
+        assert_eq!(regions[0].bytes, &[0; SECRET.len()]);
+    }
+
+    #[test]
+    fn test_redact_memory64() {
+        let bytes = SynthMinidump::with_endian(Endian::Little)
+            .add_memory64(memory(0x1000, b"first"))
+            .add_memory64(memory(0x2000, SECRET))
+            .add_memory64(memory(0x3000, b"third"))
+            .finish()
+            .unwrap();
+        let dump = Minidump::read(&bytes[..]).unwrap();
+
+        let redacted = MinidumpRedactor::new()
+            .remove_memory(0x2000..0x3000)
+            .redact(&dump)
+            .unwrap();
+        assert!(!contains(&redacted, SECRET));
+        let redacted = Minidump::read(&redacted[..]).unwrap();
+        let memory = redacted.get_stream::<MinidumpMemory64List>().unwrap();
+        let regions = memory
+            .iter()
+            .map(|region| (region.base_address, region.bytes))
+            .collect::<Vec<_>>();
+        let expected: Vec<(u64, &[u8])> = vec![(0x1000, b"first"), (0x3000, b"third")];
+        assert_eq!(regions, expected);
+
+        let redacted = MinidumpRedactor::new()
+            .truncate_memory64(1)
+            .redact(&dump)
+            .unwrap();
+        let redacted = Minidump::read(&redacted[..]).unwrap();
+        let memory = redacted.get_stream::<MinidumpMemory64List>().unwrap();
+        let regions = memory
+            .iter()
+            .map(|region| (region.base_address, region.bytes))
+            .collect::<Vec<_>>();
+        let expected: Vec<(u64, &[u8])> = vec![(0x1000, b"first")];
+        assert_eq!(regions, expected);
+
+        // Dropping the stream removes its memory too.
+        let redacted = MinidumpRedactor::new()
+            .drop_stream(MinidumpMemory64List::STREAM_TYPE)
+            .redact(&dump)
+            .unwrap();
+        assert!(!contains(&redacted, b"first"));
+        let redacted = Minidump::read(&redacted[..]).unwrap();
+        assert!(redacted.get_stream::<MinidumpMemory64List>().is_err());
+    }
+}
@@ -0,0 +1,410 @@
+//! `Serialize` implementations for the parsed stream types, behind the `serde` feature.
+//!
+//! These describe what the streams contain rather than mirroring their raw
+//! layout: fields are the values the types' accessors return, CPU contexts
+//! are maps from register names to values, and byte buffers (like annotation
+//! objects) are described by their length instead of their contents.
+//! Contexts can't be serialized as part of threads or exceptions, since
+//! parsing them needs the system info and misc info streams, so serialize
+//! the [`MinidumpContext`] they return on its own.
+
+use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
+
+use crate::strings::LinuxOsStr;
+use crate::system_info::{ArmCpuFeatures, ArmFeature, Cpu, Os};
+use crate::{
+    MinidumpAnnotation, MinidumpContext, MinidumpCrashpadInfo, MinidumpException,
+    MinidumpLinuxCpuInfo, MinidumpLinuxEnviron, MinidumpLinuxLimit, MinidumpLinuxLimits,
+    MinidumpLinuxLsbRelease, MinidumpLinuxMapInfo, MinidumpLinuxMapKind, MinidumpLinuxMaps,
+    MinidumpLinuxProcStatus, MinidumpMiscInfo, MinidumpModule, MinidumpModuleCrashpadInfo,
+    MinidumpModuleList, MinidumpSystemInfo, MinidumpThread, MinidumpThreadList, Module,
+};
+
+/// Serialize `(key, value)` pairs of Linux strings as a map.
+fn serialize_linux_map<'a, S, I>(serializer: S, entries: I) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    I: Iterator<Item = (&'a LinuxOsStr, &'a LinuxOsStr)>,
+{
+    serializer.collect_map(entries.map(|(k, v)| (k.to_string_lossy(), v.to_string_lossy())))
+}
+
+/// Decode a NUL-terminated UTF-16 string from a `MINIDUMP_MISC_INFO_4`.
+fn utf16_string(chars: &[u16]) -> String {
+    let len = chars.iter().position(|&c| c == 0).unwrap_or(chars.len());
+    String::from_utf16_lossy(&chars[..len])
+}
+
+impl Serialize for Os {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for Cpu {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for ArmFeature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl Serialize for ArmCpuFeatures {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ArmCpuFeatures", 5)?;
+        state.serialize_field("implementer", &self.implementer)?;
+        state.serialize_field("part", &self.part)?;
+        state.serialize_field("vendor_name", &self.vendor_name())?;
+        state.serialize_field("part_name", &self.part_name())?;
+        state.serialize_field("features", &self.features)?;
+        state.end()
+    }
+}
+
+impl Serialize for MinidumpContext {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.valid_registers())
+    }
+}
+
+impl Serialize for MinidumpSystemInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let raw = &self.raw;
+        let mut state = serializer.serialize_struct("MinidumpSystemInfo", 13)?;
+        state.serialize_field("os", &self.os)?;
+        state.serialize_field("cpu", &self.cpu)?;
+        state.serialize_field("processor_level", &raw.processor_level)?;
+        state.serialize_field("processor_revision", &raw.processor_revision)?;
+        state.serialize_field("number_of_processors", &raw.number_of_processors)?;
+        state.serialize_field("major_version", &raw.major_version)?;
+        state.serialize_field("minor_version", &raw.minor_version)?;
+        state.serialize_field("build_number", &raw.build_number)?;
+        state.serialize_field("csd_version", &self.csd_version())?;
+        state.serialize_field("cpu_info", &self.cpu_info())?;
+        state.serialize_field("cpu_features", &self.cpu_features(None))?;
+        state.serialize_field("cpu_microarchitecture", &self.cpu_microarchitecture(None))?;
+        state.serialize_field("cpu_description", &self.cpu_description(None))?;
+        state.end()
+    }
+}
+
+impl Serialize for MinidumpModule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("MinidumpModule", 9)?;
+        state.serialize_field("base_address", &self.base_address())?;
+        state.serialize_field("size", &self.size())?;
+        state.serialize_field("code_file", &self.code_file())?;
+        state.serialize_field(
+            "code_identifier",
+            &self.code_identifier().map(|id| id.to_string()),
+        )?;
+        state.serialize_field("debug_file", &self.debug_file())?;
+        state.serialize_field(
+            "debug_identifier",
+            &self.debug_identifier().map(|id| id.breakpad().to_string()),
+        )?;
+        state.serialize_field("version", &self.version())?;
+        state.serialize_field("time_date_stamp", &self.raw.time_date_stamp)?;
+        state.serialize_field("checksum", &self.raw.checksum)?;
+        state.end()
+    }
+}
+
+impl Serialize for MinidumpModuleList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'a> Serialize for MinidumpThread<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let raw = &self.raw;
+        let mut state = serializer.serialize_struct("MinidumpThread", 7)?;
+        state.serialize_field("thread_id", &raw.thread_id)?;
+        state.serialize_field("suspend_count", &raw.suspend_count)?;
+        state.serialize_field("priority_class", &raw.priority_class)?;
+        state.serialize_field("priority", &raw.priority)?;
+        state.serialize_field("teb", &raw.teb)?;
+        state.serialize_field("stack_start", &raw.stack.start_of_memory_range)?;
+        state.serialize_field("stack_size", &raw.stack.memory.data_size)?;
+        state.end()
+    }
+}
+
+impl<'a> Serialize for MinidumpThreadList<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.threads)
+    }
+}
+
+impl<'a> Serialize for MinidumpException<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let record = &self.raw.exception_record;
+        let parameters = &record.exception_information
+            [..(record.number_parameters as usize).min(record.exception_information.len())];
+        let mut state = serializer.serialize_struct("MinidumpException", 6)?;
+        state.serialize_field("thread_id", &self.thread_id)?;
+        state.serialize_field("exception_code", &record.exception_code)?;
+        state.serialize_field("exception_flags", &record.exception_flags)?;
+        state.serialize_field("exception_record", &record.exception_record)?;
+        state.serialize_field("exception_address", &record.exception_address)?;
+        state.serialize_field("exception_parameters", parameters)?;
+        state.end()
+    }
+}
+
+impl Serialize for MinidumpMiscInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let raw = &self.raw;
+        let mut state = serializer.serialize_struct("MinidumpMiscInfo", 15)?;
+        state.serialize_field("process_id", &raw.process_id())?;
+        state.serialize_field("process_create_time", &raw.process_create_time())?;
+        state.serialize_field("process_user_time", &raw.process_user_time())?;
+        state.serialize_field("process_kernel_time", &raw.process_kernel_time())?;
+        state.serialize_field("processor_max_mhz", &raw.processor_max_mhz())?;
+        state.serialize_field("processor_current_mhz", &raw.processor_current_mhz())?;
+        state.serialize_field("processor_mhz_limit", &raw.processor_mhz_limit())?;
+        state.serialize_field("process_integrity_level", &raw.process_integrity_level())?;
+        state.serialize_field("process_execute_flags", &raw.process_execute_flags())?;
+        state.serialize_field("protected_process", &raw.protected_process())?;
+        state.serialize_field("time_zone_id", &raw.time_zone_id())?;
+        state.serialize_field(
+            "time_zone_name",
+            &raw.time_zone()
+                .map(|time_zone| utf16_string(&time_zone.standard_name)),
+        )?;
+        state.serialize_field("build_string", &raw.build_string().map(|s| utf16_string(s)))?;
+        state.serialize_field("dbg_bld_str", &raw.dbg_bld_str().map(|s| utf16_string(s)))?;
+        state.serialize_field("process_cookie", &self.process_cookie())?;
+        state.end()
+    }
+}
+
+impl Serialize for MinidumpAnnotation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MinidumpAnnotation::String(string) => serializer.serialize_str(string),
+            _ => {
+                let mut state = serializer.serialize_struct("MinidumpAnnotation", 2)?;
+                state.serialize_field("type", &self.ty())?;
+                state.serialize_field("size", &self.value().len())?;
+                state.end()
+            }
+        }
+    }
+}
+
+impl Serialize for MinidumpModuleCrashpadInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("MinidumpModuleCrashpadInfo", 4)?;
+        state.serialize_field("module_index", &self.module_index)?;
+        state.serialize_field("list_annotations", &self.list_annotations)?;
+        state.serialize_field("simple_annotations", &self.simple_annotations)?;
+        state.serialize_field("annotation_objects", &self.annotation_objects)?;
+        state.end()
+    }
+}
+
+impl Serialize for MinidumpCrashpadInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("MinidumpCrashpadInfo", 4)?;
+        state.serialize_field("report_id", &self.raw.report_id.to_string())?;
+        state.serialize_field("client_id", &self.raw.client_id.to_string())?;
+        state.serialize_field("simple_annotations", &self.simple_annotations)?;
+        state.serialize_field("module_list", &self.module_list)?;
+        state.end()
+    }
+}
+
+impl<'a> Serialize for MinidumpLinuxLsbRelease<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_linux_map(serializer, self.iter())
+    }
+}
+
+impl<'a> Serialize for MinidumpLinuxEnviron<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_linux_map(serializer, self.iter())
+    }
+}
+
+impl<'a> Serialize for MinidumpLinuxProcStatus<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_linux_map(serializer, self.iter())
+    }
+}
+
+/// `/proc/cpuinfo` repeats its keys for every processor, so this is a list of
+/// `[key, value]` pairs rather than a map.
+impl<'a> Serialize for MinidumpLinuxCpuInfo<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for (key, value) in self.iter() {
+            seq.serialize_element(&(key.to_string_lossy(), value.to_string_lossy()))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'a> Serialize for MinidumpLinuxLimit<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("MinidumpLinuxLimit", 3)?;
+        state.serialize_field("name", self.name)?;
+        state.serialize_field("soft", &self.soft)?;
+        state.serialize_field("hard", &self.hard)?;
+        state.end()
+    }
+}
+
+impl<'a> Serialize for MinidumpLinuxLimits<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'a> Serialize for MinidumpLinuxMapKind<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const NAME: &str = "MinidumpLinuxMapKind";
+        match self {
+            MinidumpLinuxMapKind::MainThreadStack => {
+                serializer.serialize_unit_variant(NAME, 0, "MainThreadStack")
+            }
+            MinidumpLinuxMapKind::Stack(tid) => {
+                serializer.serialize_newtype_variant(NAME, 1, "Stack", tid)
+            }
+            MinidumpLinuxMapKind::Heap => serializer.serialize_unit_variant(NAME, 2, "Heap"),
+            MinidumpLinuxMapKind::Vdso => serializer.serialize_unit_variant(NAME, 3, "Vdso"),
+            MinidumpLinuxMapKind::AnonymousMap => {
+                serializer.serialize_unit_variant(NAME, 4, "AnonymousMap")
+            }
+            MinidumpLinuxMapKind::NamedAnonymousMap(name) => serializer.serialize_newtype_variant(
+                NAME,
+                5,
+                "NamedAnonymousMap",
+                &name.to_string_lossy(),
+            ),
+            MinidumpLinuxMapKind::UnknownSpecial(kind) => serializer.serialize_newtype_variant(
+                NAME,
+                6,
+                "UnknownSpecial",
+                &kind.to_string_lossy(),
+            ),
+            MinidumpLinuxMapKind::File(path) => {
+                serializer.serialize_newtype_variant(NAME, 7, "File", &path.to_string_lossy())
+            }
+            MinidumpLinuxMapKind::DeletedFile(path) => serializer.serialize_newtype_variant(
+                NAME,
+                8,
+                "DeletedFile",
+                &path.to_string_lossy(),
+            ),
+        }
+    }
+}
+
+impl<'a> Serialize for MinidumpLinuxMapInfo<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("MinidumpLinuxMapInfo", 9)?;
+        state.serialize_field("base_address", &self.base_address)?;
+        state.serialize_field("final_address", &self.final_address)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("is_read", &self.is_read)?;
+        state.serialize_field("is_write", &self.is_write)?;
+        state.serialize_field("is_exec", &self.is_exec)?;
+        state.serialize_field("is_shared", &self.is_shared)?;
+        state.serialize_field("is_private", &self.is_private)?;
+        state.serialize_field(
+            "vm_flags",
+            &self.vm_flags.as_ref().map(|flags| flags.to_string_lossy()),
+        )?;
+        state.end()
+    }
+}
+
+impl<'a> Serialize for MinidumpLinuxMaps<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::format::CONTEXT_X86;
+    use crate::format::{PlatformId, ProcessorArchitecture};
+    use crate::{
+        Minidump, MinidumpContext, MinidumpException, MinidumpLinuxCpuInfo, MinidumpLinuxEnviron,
+        MinidumpModuleList, MinidumpRawContext, MinidumpSystemInfo,
+    };
+    use minidump_synth::{DumpString, Exception, Module as SynthModule, SynthMinidump, SystemInfo};
+    use serde_json::json;
+    use test_assembler::Endian;
+
+    #[test]
+    fn test_serialize_streams() {
+        let name = DumpString::new("c:\\test.exe", Endian::Little);
+        let module = SynthModule::new(Endian::Little, 0x1000, 0x2000, &name, 0xb1054d2a, 0xa, None);
+        let system_info = SystemInfo::new(Endian::Little)
+            .set_processor_architecture(ProcessorArchitecture::PROCESSOR_ARCHITECTURE_INTEL as u16)
+            .set_platform_id(PlatformId::VER_PLATFORM_WIN32_NT as u32);
+        let mut exception = Exception::new(Endian::Little);
+        exception.thread_id = 7;
+        exception.exception_record.exception_code = 0xc0000005;
+        exception.exception_record.number_parameters = 2;
+        exception.exception_record.exception_information[1] = 0x1234;
+        let dump = SynthMinidump::with_endian(Endian::Little)
+            .add_module(module)
+            .add(name)
+            .add_system_info(system_info)
+            .add_exception(exception)
+            .set_linux_environ(b"HOME=/home/me\0LANG=C\0")
+            .set_linux_cpu_info(b"processor : 0\nprocessor : 1\n");
+        let dump = Minidump::read(dump.finish().unwrap()).unwrap();
+
+        let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
+        let json = serde_json::to_value(&system_info).unwrap();
+        assert_eq!(json["os"], "windows");
+        assert_eq!(json["cpu"], "x86");
+
+        let modules = dump.get_stream::<MinidumpModuleList>().unwrap();
+        let json = serde_json::to_value(&modules).unwrap();
+        assert_eq!(json[0]["base_address"], 0x1000);
+        assert_eq!(json[0]["size"], 0x2000);
+        assert_eq!(json[0]["code_file"], "c:\\test.exe");
+        assert_eq!(json[0]["time_date_stamp"], 0xb1054d2a_u32);
+
+        let exception = dump.get_stream::<MinidumpException>().unwrap();
+        let json = serde_json::to_value(&exception).unwrap();
+        assert_eq!(json["thread_id"], 7);
+        assert_eq!(json["exception_code"], 0xc0000005_u32);
+        assert_eq!(json["exception_parameters"], json!([0, 0x1234]));
+
+        let environ = dump.get_stream::<MinidumpLinuxEnviron>().unwrap();
+        assert_eq!(
+            serde_json::to_value(&environ).unwrap(),
+            json!({ "HOME": "/home/me", "LANG": "C" })
+        );
+        let cpu_info = dump.get_stream::<MinidumpLinuxCpuInfo>().unwrap();
+        assert_eq!(
+            serde_json::to_value(&cpu_info).unwrap(),
+            json!([["processor", "0"], ["processor", "1"]])
+        );
+    }
+
+    #[test]
+    fn test_serialize_context() {
+        let raw = CONTEXT_X86 {
+            eip: 0x1000,
+            esp: 0x8000,
+            ..CONTEXT_X86::default()
+        };
+        let context = MinidumpContext::from_raw(MinidumpRawContext::X86(raw));
+        let json = serde_json::to_value(&context).unwrap();
+        assert_eq!(json["eip"], 0x1000);
+        assert_eq!(json["esp"], 0x8000);
+        assert_eq!(json["eax"], 0);
+    }
+}
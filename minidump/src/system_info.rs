@@ -5,6 +5,7 @@
 
 use num_traits::FromPrimitive;
 use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::fmt;
 
 use minidump_common::format as md;
@@ -168,3 +169,265 @@ impl PointerWidth {
         }
     }
 }
+
+/// A feature of an ARM or ARM64 cpu, from [`ArmCpuFeatures`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ArmFeature {
+    /// Hardware floating point (VFP on 32-bit ARM).
+    Fp,
+    /// Advanced SIMD (NEON, called ASIMD on ARM64).
+    Neon,
+    /// Hardware integer division.
+    Idiv,
+    /// AES instructions.
+    Aes,
+    /// Polynomial multiply instructions.
+    Pmull,
+    /// SHA1 instructions.
+    Sha1,
+    /// SHA256 instructions.
+    Sha2,
+    /// SHA3 instructions.
+    Sha3,
+    /// SHA512 instructions.
+    Sha512,
+    /// CRC32 instructions.
+    Crc32,
+    /// ARMv8.1 Large System Extension (LSE) atomics.
+    Atomics,
+    /// Half-precision floating point.
+    Fp16,
+    /// SIMD dot product instructions.
+    DotProd,
+    /// The Scalable Vector Extension.
+    Sve,
+    /// The second version of the Scalable Vector Extension.
+    Sve2,
+}
+
+impl ArmFeature {
+    /// The name of the feature, as Linux spells it in `/proc/cpuinfo` on ARM64.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArmFeature::Fp => "fp",
+            ArmFeature::Neon => "asimd",
+            ArmFeature::Idiv => "idiv",
+            ArmFeature::Aes => "aes",
+            ArmFeature::Pmull => "pmull",
+            ArmFeature::Sha1 => "sha1",
+            ArmFeature::Sha2 => "sha2",
+            ArmFeature::Sha3 => "sha3",
+            ArmFeature::Sha512 => "sha512",
+            ArmFeature::Crc32 => "crc32",
+            ArmFeature::Atomics => "atomics",
+            ArmFeature::Fp16 => "fphp",
+            ArmFeature::DotProd => "asimddp",
+            ArmFeature::Sve => "sve",
+            ArmFeature::Sve2 => "sve2",
+        }
+    }
+
+    /// The feature a name in the `Features` line of `/proc/cpuinfo` refers to.
+    ///
+    /// This understands both the ARM and ARM64 spellings, like `neon` and `asimd`.
+    pub fn from_cpuinfo_name(name: &str) -> Option<ArmFeature> {
+        Some(match name {
+            "fp" | "vfp" | "vfpv3" | "vfpv3d16" | "vfpv4" | "vfpd32" => ArmFeature::Fp,
+            "neon" | "asimd" => ArmFeature::Neon,
+            "idiva" | "idivt" => ArmFeature::Idiv,
+            "aes" => ArmFeature::Aes,
+            "pmull" => ArmFeature::Pmull,
+            "sha1" => ArmFeature::Sha1,
+            "sha2" => ArmFeature::Sha2,
+            "sha3" => ArmFeature::Sha3,
+            "sha512" => ArmFeature::Sha512,
+            "crc32" => ArmFeature::Crc32,
+            "atomics" => ArmFeature::Atomics,
+            "fphp" | "asimdhp" => ArmFeature::Fp16,
+            "asimddp" => ArmFeature::DotProd,
+            "sve" => ArmFeature::Sve,
+            "sve2" => ArmFeature::Sve2,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for ArmFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The model and features of an ARM or ARM64 cpu.
+///
+/// See [`MinidumpSystemInfo::cpu_features`][crate::MinidumpSystemInfo::cpu_features].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArmCpuFeatures {
+    /// The implementer code from the cpu's `MIDR` register, like `0x41` for ARM.
+    pub implementer: Option<u8>,
+    /// The part number from the cpu's `MIDR` register.
+    pub part: Option<u16>,
+    /// The features the cpu is known to have.
+    ///
+    /// A feature that's missing may just not have been recorded: minidumps
+    /// from different platforms record different subsets of them.
+    pub features: BTreeSet<ArmFeature>,
+}
+
+impl ArmCpuFeatures {
+    /// The name of the cpu's implementer, like "ARM" or "Apple", if it's a known one.
+    pub fn vendor_name(&self) -> Option<&'static str> {
+        arm_vendor_name(self.implementer?)
+    }
+
+    /// The name of the cpu's core, like "Cortex-A53" or "M1 Firestorm", if it's a known one.
+    pub fn part_name(&self) -> Option<&'static str> {
+        arm_part_name(self.implementer?, self.part?)
+    }
+
+    /// Whether the cpu is known to have `feature`.
+    pub fn has(&self, feature: ArmFeature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Record the implementer and part number of a `MIDR` value (Breakpad's `cpuid`).
+    pub(crate) fn set_midr(&mut self, midr: u32) {
+        self.implementer = Some((midr >> 24) as u8);
+        self.part = Some(((midr >> 4) & 0xfff) as u16);
+    }
+
+    /// Add the features in Breakpad's ARM `elf_hwcaps`.
+    pub(crate) fn add_elf_hwcaps(&mut self, elf_hwcaps: u32) {
+        let hwcaps = md::ArmElfHwCaps::from_bits_truncate(elf_hwcaps);
+        let features = [
+            (md::ArmElfHwCaps::HWCAP_VFP, ArmFeature::Fp),
+            (md::ArmElfHwCaps::HWCAP_NEON, ArmFeature::Neon),
+            (md::ArmElfHwCaps::HWCAP_IDIVA, ArmFeature::Idiv),
+            (md::ArmElfHwCaps::HWCAP_IDIVT, ArmFeature::Idiv),
+        ];
+        for (hwcap, feature) in features {
+            if hwcaps.contains(hwcap) {
+                self.features.insert(feature);
+            }
+        }
+    }
+
+    /// Add the features in Windows' `processor_features`, which are indexed
+    /// by `PF_*` constants like those `IsProcessorFeaturePresent` takes.
+    pub(crate) fn add_windows_processor_features(&mut self, processor_features: [u64; 2]) {
+        let features: &[(u32, &[ArmFeature])] = &[
+            // PF_ARM_VFP_32_REGISTERS_AVAILABLE
+            (18, &[ArmFeature::Fp]),
+            // PF_ARM_NEON_INSTRUCTIONS_AVAILABLE
+            (19, &[ArmFeature::Neon]),
+            // PF_ARM_DIVIDE_INSTRUCTION_AVAILABLE
+            (24, &[ArmFeature::Idiv]),
+            // PF_ARM_V8_CRYPTO_INSTRUCTIONS_AVAILABLE
+            (
+                30,
+                &[
+                    ArmFeature::Aes,
+                    ArmFeature::Pmull,
+                    ArmFeature::Sha1,
+                    ArmFeature::Sha2,
+                ],
+            ),
+            // PF_ARM_V8_CRC32_INSTRUCTIONS_AVAILABLE
+            (31, &[ArmFeature::Crc32]),
+            // PF_ARM_V81_ATOMIC_INSTRUCTIONS_AVAILABLE
+            (34, &[ArmFeature::Atomics]),
+            // PF_ARM_V82_DP_INSTRUCTIONS_AVAILABLE
+            (43, &[ArmFeature::DotProd]),
+            // PF_ARM_SVE_INSTRUCTIONS_AVAILABLE
+            (46, &[ArmFeature::Sve]),
+            // PF_ARM_SVE2_INSTRUCTIONS_AVAILABLE
+            (47, &[ArmFeature::Sve2]),
+        ];
+        for &(bit, bit_features) in features {
+            if processor_features[(bit / 64) as usize] & (1 << (bit % 64)) != 0 {
+                self.features.extend(bit_features);
+            }
+        }
+    }
+}
+
+/// The name of an ARM `MIDR` implementer code.
+///
+/// There is no good list of implementer id values, but the following
+/// pages provide some help:
+///   http://comments.gmane.org/gmane.linux.linaro.devel/6903
+///   http://forum.xda-developers.com/archive/index.php/t-480226.html
+pub(crate) fn arm_vendor_name(implementer: u8) -> Option<&'static str> {
+    Some(match implementer {
+        0x41 => "ARM",
+        0x48 => "HiSilicon",
+        0x4e => "NVIDIA",
+        0x51 => "Qualcomm",
+        0x53 => "Samsung",
+        0x56 => "Marvell",
+        0x61 => "Apple",
+        0x69 => "Intel/Marvell",
+        _ => return None,
+    })
+}
+
+/// The name of an ARM core, from the implementer code and part number of its `MIDR`.
+pub(crate) fn arm_part_name(implementer: u8, part: u16) -> Option<&'static str> {
+    Some(match (implementer, part) {
+        (0x41, 0x926) => "ARM926",
+        (0x41, 0x946) => "ARM946",
+        (0x41, 0x966) => "ARM966",
+        (0x41, 0xb02) => "ARM11-MPCore",
+        (0x41, 0xb36) => "ARM1136",
+        (0x41, 0xb56) => "ARM1156",
+        (0x41, 0xb76) => "ARM1176",
+        (0x41, 0xc05) => "Cortex-A5",
+        (0x41, 0xc07) => "Cortex-A7",
+        (0x41, 0xc08) => "Cortex-A8",
+        (0x41, 0xc09) => "Cortex-A9",
+        (0x41, 0xc0d) => "Cortex-A12",
+        (0x41, 0xc0e) => "Cortex-A17",
+        (0x41, 0xc0f) => "Cortex-A15",
+        (0x41, 0xc14) => "Cortex-R4",
+        (0x41, 0xc15) => "Cortex-R5",
+        (0x41, 0xd03) => "Cortex-A53",
+        (0x41, 0xd04) => "Cortex-A35",
+        (0x41, 0xd05) => "Cortex-A55",
+        (0x41, 0xd07) => "Cortex-A57",
+        (0x41, 0xd08) => "Cortex-A72",
+        (0x41, 0xd09) => "Cortex-A73",
+        (0x41, 0xd0a) => "Cortex-A75",
+        (0x41, 0xd0b) => "Cortex-A76",
+        (0x41, 0xd0c) => "Neoverse-N1",
+        (0x41, 0xd0d) => "Cortex-A77",
+        (0x41, 0xd40) => "Neoverse-V1",
+        (0x41, 0xd41) => "Cortex-A78",
+        (0x41, 0xd44) => "Cortex-X1",
+        (0x41, 0xd46) => "Cortex-A510",
+        (0x41, 0xd47) => "Cortex-A710",
+        (0x41, 0xd48) => "Cortex-X2",
+        (0x41, 0xd49) => "Neoverse-N2",
+        (0x51, 0x00f) => "Scorpion",
+        (0x51, 0x06f) => "Krait",
+        (0x51, 0x800) => "Kryo 2xx Gold",
+        (0x51, 0x801) => "Kryo 2xx Silver",
+        (0x51, 0x802) => "Kryo 3xx Gold",
+        (0x51, 0x803) => "Kryo 3xx Silver",
+        (0x51, 0x804) => "Kryo 4xx Gold",
+        (0x51, 0x805) => "Kryo 4xx Silver",
+        (0x53, 0x001) => "Exynos M1",
+        (0x53, 0x002) => "Exynos M3",
+        (0x53, 0x003) => "Exynos M4",
+        (0x53, 0x004) => "Exynos M5",
+        (0x61, 0x022) => "M1 Icestorm",
+        (0x61, 0x023) => "M1 Firestorm",
+        (0x61, 0x024) => "M1 Pro Icestorm",
+        (0x61, 0x025) => "M1 Pro Firestorm",
+        (0x61, 0x028) => "M1 Max Icestorm",
+        (0x61, 0x029) => "M1 Max Firestorm",
+        (0x61, 0x032) => "M2 Blizzard",
+        (0x61, 0x033) => "M2 Avalanche",
+        _ => return None,
+    })
+}
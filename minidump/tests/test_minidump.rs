@@ -253,9 +253,7 @@ fn test_thread_list() {
     let thread_list = dump.get_stream::<MinidumpThreadList<'_>>().unwrap();
     let system_info = dump.get_stream::<MinidumpSystemInfo>().unwrap();
     let misc_info = dump.get_stream::<MinidumpMiscInfo>().ok();
-    let memory_list = dump
-        .get_stream::<MinidumpMemoryList<'_>>()
-        .unwrap_or_default();
+    let memory_list = dump.get_memory().unwrap_or_default();
 
     let threads = &thread_list.threads;
     assert_eq!(threads.len(), 2);
@@ -282,7 +280,7 @@ fn test_thread_list() {
     } else {
         panic!("Missing context");
     }
-    if let Some(ref stack) = threads[0].stack_memory(&memory_list) {
+    if let Some(stack) = threads[0].stack_memory(&memory_list) {
         // Try the beginning
         assert_eq!(stack.get_memory_at_address::<u8>(0x12f31c).unwrap(), 0);
         assert_eq!(stack.get_memory_at_address::<u16>(0x12f31c).unwrap(), 0);